@@ -0,0 +1,57 @@
+//! Column rules on a wide dataset, on one thread and on every CPU.
+//!
+//! Run with `cargo bench -p pipe-audit-core --bench column_rules`.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use pipa_core::run::{ExecutionPlan, MemoryHistory, validate_plan};
+use polars::prelude::*;
+
+const COLUMNS: usize = 120;
+const ROWS: usize = 20_000;
+
+fn contract(limits: &str) -> String {
+    let mut toml = String::from("[contract]\nname = \"wide\"\nversion = \"1.0.0\"\ntags = []\n");
+    for i in 0..COLUMNS {
+        toml.push_str(&format!(
+            "\n[[columns]]\nname = \"c{}\"\nvalidation = [\
+             {{ rule = \"date_format\", format = \"%Y-%m-%d\" }}, \
+             {{ rule = \"not_null\" }}, \
+             {{ rule = \"type\", dtype = \"string\" }}]\n",
+            i
+        ));
+    }
+    toml.push_str(limits);
+    toml
+}
+
+fn wide_frame() -> DataFrame {
+    let columns = (0..COLUMNS)
+        .map(|i| {
+            let values: Vec<String> = (0..ROWS)
+                .map(|row| format!("2024-{:02}-{:02}", (row + i) % 12 + 1, row % 28 + 1))
+                .collect();
+            Column::new(format!("c{}", i).into(), values)
+        })
+        .collect();
+    DataFrame::new(columns).unwrap()
+}
+
+fn column_rules(c: &mut Criterion) {
+    let df = wide_frame();
+    let history = MemoryHistory::default();
+    let mut group = c.benchmark_group("column_rules");
+    group.sample_size(10);
+    for (name, limits) in [
+        ("single_thread", "\n[limits]\nmax_threads = 1\n"),
+        ("all_cpus", ""),
+    ] {
+        let plan = ExecutionPlan::from_toml(&contract(limits)).unwrap();
+        group.bench_function(name, |b| {
+            b.iter(|| validate_plan(&df, &plan, &history).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, column_rules);
+criterion_main!(benches);
@@ -0,0 +1,443 @@
+//! Project-wide settings from `pipa.toml` in the project directory.
+//!
+//! ```toml
+//! [[notifications.channels]]
+//! type = "slack"
+//! webhook_url = "${SLACK_WEBHOOK_URL}"
+//! on = ["failure", "quarantine"]
+//!
+//! [[log_sinks]]
+//! type = "s3"
+//! location = "s3://compliance-archive/pipa/"
+//! profile = "archive"
+//!
+//! [notarization]
+//! type = "rfc3161"
+//! url = "https://freetsa.org/tsr"
+//!
+//! [ledger_key]
+//! provider = "aws_kms"
+//! key_id = "alias/pipa-ledger"
+//!
+//! [sealing]
+//! cron = "5 0 * * *"
+//!
+//! [paths]
+//! logs = "/var/log/pipa"
+//! contracts = "etl/contracts"
+//! ledger = "/var/lib/pipa/hash_ledger.enc"
+//!
+//! [retention]
+//! keep_days = 365
+//! archive_dir = "/mnt/archive/pipa"
+//!
+//! [defaults]
+//! jobs = 4
+//! output = "json"
+//! ```
+//!
+//! The file is optional. String values may reference `${NAME}` as in
+//! contracts (see `contracts::vars`), so secrets such as webhook URLs can
+//! stay in the environment.
+//!
+//! The `[paths]` directories default to `logs/` and `contracts/` in the
+//! project directory, and the ledger to `hash_ledger.enc` in the logs
+//! directory. `PIPA_LOGS_DIR`, `PIPA_CONTRACTS_DIR`, and `PIPA_LEDGER_PATH`
+//! override them; relative paths are resolved against the project
+//! directory. A `Project` carries the resolved paths of one directory;
+//! `logs_dir()`, `contracts_dir()`, and `ledger_path()` resolve them for
+//! the working directory, which `Project::enter` switches to a project.
+//!
+//! `[defaults]` holds what commands fall back on when no flag is given:
+//! `jobs` (contracts validated concurrently) and `output` (`text` or
+//! `json` reports). `PIPA_JOBS` and `PIPA_OUTPUT` override them, and
+//! front ends apply their own flags over `load_defaults`.
+
+use crate::contracts::Notifications;
+use crate::contracts::vars;
+use crate::history::STATE_DIR;
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::logging::ledger::LedgerKeyProvider;
+use crate::logging::notary::Notary;
+use crate::logging::retention::RetentionPolicy;
+use crate::logging::sinks::LogSink;
+use crate::profiles::PROFILES_FILE;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Default project config file name.
+pub const PROJECT_FILE: &str = "pipa.toml";
+
+/// A project directory and the paths resolved inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    /// Project directory; empty for the working directory.
+    pub root: PathBuf,
+    /// `pipa.toml`.
+    pub project_file: PathBuf,
+    /// `profiles.toml`.
+    pub profiles_file: PathBuf,
+    pub contracts_dir: PathBuf,
+    pub logs_dir: PathBuf,
+    pub ledger_path: PathBuf,
+    /// Run history and drift baselines (see `history`).
+    pub state_dir: PathBuf,
+}
+
+impl Project {
+    /// The project in `root`, with `[paths]` from its `pipa.toml` and the
+    /// `PIPA_*` overrides resolved against it. Paths are absolute, so they
+    /// stay valid whatever the working directory.
+    ///
+    /// # Errors
+    /// Returns `ValidationError::Config` if `root` isn't a directory or its
+    /// `pipa.toml` can't be loaded.
+    pub fn open(root: &Path) -> ValidationResult<Self> {
+        let root = root
+            .canonicalize()
+            .ok()
+            .filter(|root| root.is_dir())
+            .ok_or_else(|| {
+                ValidationError::Config(format!(
+                    "Project directory {} does not exist",
+                    root.display()
+                ))
+            })?;
+        let project = load_project_config(&root.join(PROJECT_FILE))?;
+        Ok(Self::resolve(root, project.paths))
+    }
+
+    /// The project in the working directory. An unreadable `pipa.toml`
+    /// leaves the default paths, as its error is reported by whatever else
+    /// loads it.
+    pub fn current() -> Self {
+        let paths = load_project_config(Path::new(PROJECT_FILE))
+            .map(|project| project.paths)
+            .unwrap_or_default();
+        Self::resolve(PathBuf::new(), paths)
+    }
+
+    fn resolve(root: PathBuf, paths: ProjectPaths) -> Self {
+        let configured = |env: &str, value: Option<PathBuf>| {
+            std::env::var_os(env)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+                .or(value)
+                .map(|path| root.join(path))
+        };
+        let logs_dir = configured("PIPA_LOGS_DIR", paths.logs).unwrap_or_else(|| root.join("logs"));
+        let contracts_dir = configured("PIPA_CONTRACTS_DIR", paths.contracts)
+            .unwrap_or_else(|| root.join("contracts"));
+        let ledger_path = configured("PIPA_LEDGER_PATH", paths.ledger)
+            .unwrap_or_else(|| logs_dir.join("hash_ledger.enc"));
+        Self {
+            project_file: root.join(PROJECT_FILE),
+            profiles_file: root.join(PROFILES_FILE),
+            state_dir: root.join(STATE_DIR),
+            contracts_dir,
+            logs_dir,
+            ledger_path,
+            root,
+        }
+    }
+
+    /// Make the project directory the working directory, so everything
+    /// else in it (profiles, run history, relative source locations)
+    /// resolves there too.
+    ///
+    /// # Errors
+    /// Returns `ValidationError::Io` if the directory can't be entered.
+    pub fn enter(&self) -> ValidationResult<()> {
+        if !self.root.as_os_str().is_empty() {
+            std::env::set_current_dir(&self.root)?;
+        }
+        Ok(())
+    }
+}
+
+/// Settings shared by every contract in a project.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectConfig {
+    /// Channels told about run outcomes, unless a contract declares its own.
+    pub notifications: Option<Notifications>,
+    /// Where audit log entries and sealed daily files are shipped (see
+    /// `logging::sinks`).
+    #[serde(default)]
+    pub log_sinks: Vec<LogSink>,
+    /// Where sealed log hashes are timestamped (see `logging::notary`).
+    pub notarization: Option<Notary>,
+    /// Where the ledger key is kept (see `logging::ledger::keys`).
+    pub ledger_key: Option<LedgerKeyProvider>,
+    /// When `pipa scheduler start` seals the previous day's log.
+    pub sealing: Option<SealingSchedule>,
+    /// Where logs, contracts, and the ledger live.
+    #[serde(default)]
+    pub paths: ProjectPaths,
+    /// How long sealed logs are kept (see `logging::retention`).
+    pub retention: Option<RetentionPolicy>,
+    /// What commands use when no flag is given.
+    #[serde(default)]
+    pub defaults: ProjectDefaults,
+}
+
+/// `[defaults]`: settings commands fall back on when no flag is given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectDefaults {
+    /// Contracts validated concurrently by a multi-contract run
+    /// (`PIPA_JOBS`).
+    pub jobs: Option<usize>,
+    /// Format of reports that can also be printed as JSON (`PIPA_OUTPUT`).
+    pub output: Option<OutputFormat>,
+}
+
+/// Report format of `[defaults] output`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown output format '{}' (supported: text, json)",
+                other
+            )),
+        }
+    }
+}
+
+impl ProjectDefaults {
+    /// These defaults with the `PIPA_JOBS` / `PIPA_OUTPUT` values `var`
+    /// returns applied over them; unset or empty variables are skipped.
+    ///
+    /// # Errors
+    /// Returns `ValidationError::Config` if a variable, or `jobs`, isn't
+    /// valid.
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> ValidationResult<Self> {
+        let var = |name: &str| var(name).filter(|v| !v.is_empty());
+        if let Some(jobs) = var("PIPA_JOBS") {
+            self.jobs = Some(jobs.parse().map_err(|_| {
+                ValidationError::Config(format!(
+                    "PIPA_JOBS must be a positive integer, got '{}'",
+                    jobs
+                ))
+            })?);
+        }
+        if let Some(output) = var("PIPA_OUTPUT") {
+            self.output = Some(
+                output
+                    .parse()
+                    .map_err(|e| ValidationError::Config(format!("PIPA_OUTPUT: {}", e)))?,
+            );
+        }
+        if self.jobs == Some(0) {
+            return Err(ValidationError::Config(
+                "defaults.jobs must be at least 1".to_string(),
+            ));
+        }
+        Ok(self)
+    }
+}
+
+/// `[paths]`: project directories, relative to the project directory.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectPaths {
+    pub logs: Option<PathBuf>,
+    pub contracts: Option<PathBuf>,
+    pub ledger: Option<PathBuf>,
+}
+
+/// `[sealing]`: when the scheduler seals daily logs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SealingSchedule {
+    /// Cron expression (UTC), as in a contract's `[schedule]`; each time it
+    /// fires, every unsealed log older than today is sealed.
+    pub cron: String,
+}
+
+/// Load the project config at `path`; a missing file is an empty config.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if the file can't be read or parsed,
+/// or a `${NAME}` reference can't be resolved.
+pub fn load_project_config(path: &Path) -> ValidationResult<ProjectConfig> {
+    if !path.exists() {
+        return Ok(ProjectConfig::default());
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        ValidationError::Config(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    let mut table: toml::Table = toml::from_str(&content).map_err(|e| {
+        ValidationError::Config(format!("Failed to parse {}: {}", path.display(), e))
+    })?;
+    vars::interpolate(&mut table).map_err(|e| {
+        ValidationError::Config(format!("{}: {}: {}", path.display(), e.field, e.message))
+    })?;
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| {
+            ValidationError::Config(format!(
+                "Failed to parse {}: {}",
+                path.display(),
+                e.message()
+            ))
+        })
+}
+
+/// The `[defaults]` of the project config at `path`, with the `PIPA_JOBS`
+/// and `PIPA_OUTPUT` environment overrides applied. Front ends load these
+/// once at startup and apply their own flags over them.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if the file can't be loaded or a
+/// default or override isn't valid.
+pub fn load_defaults(path: &Path) -> ValidationResult<ProjectDefaults> {
+    load_project_config(path)?
+        .defaults
+        .with_env(|name| std::env::var(name).ok())
+}
+
+/// Directory of the daily audit logs (default `logs`).
+pub fn logs_dir() -> PathBuf {
+    Project::current().logs_dir
+}
+
+/// Directory of the contract files (default `contracts`).
+pub fn contracts_dir() -> PathBuf {
+    Project::current().contracts_dir
+}
+
+/// The file of contract `name`.
+pub fn contract_path(name: &str) -> PathBuf {
+    contracts_dir().join(format!("{}.toml", name))
+}
+
+/// Glob pattern matching every contract file.
+pub fn contracts_pattern() -> String {
+    contracts_dir()
+        .join("*.toml")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The encrypted hash ledger (default `hash_ledger.enc` in the logs
+/// directory).
+pub fn ledger_path() -> PathBuf {
+    Project::current().ledger_path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::{ChannelTarget, NotifyOn};
+    use tempfile::TempDir;
+
+    #[test]
+    fn loads_notifications_and_defaults_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(PROJECT_FILE);
+        assert!(load_project_config(&path).unwrap().notifications.is_none());
+
+        std::fs::write(
+            &path,
+            r#"
+[[notifications.channels]]
+type = "webhook"
+url = "https://hooks.example.com/${PIPA_TEST_HOOK:-runs}"
+
+[[notifications.channels]]
+type = "email"
+smtp_host = "smtp.internal"
+from = "pipa@example.com"
+to = ["data@example.com"]
+on = ["failure", "quarantine"]
+"#,
+        )
+        .unwrap();
+        let channels = load_project_config(&path)
+            .unwrap()
+            .notifications
+            .unwrap()
+            .channels;
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0].on, vec![NotifyOn::Failure]);
+        assert!(matches!(
+            &channels[0].target,
+            ChannelTarget::Webhook { url, .. } if url == "https://hooks.example.com/runs"
+        ));
+        assert!(matches!(
+            channels[1].target,
+            ChannelTarget::Email { smtp_port: 25, .. }
+        ));
+    }
+
+    #[test]
+    fn opens_a_project_with_paths_resolved_against_it() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join(PROJECT_FILE),
+            "[paths]\nlogs = \"audit\"\nledger = \"/var/lib/pipa/ledger.enc\"\n",
+        )
+        .unwrap();
+
+        let project = Project::open(dir.path()).unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        assert_eq!(project.logs_dir, root.join("audit"));
+        assert_eq!(project.contracts_dir, root.join("contracts"));
+        assert_eq!(
+            project.ledger_path,
+            PathBuf::from("/var/lib/pipa/ledger.enc")
+        );
+        assert_eq!(project.profiles_file, root.join("profiles.toml"));
+
+        assert!(
+            Project::open(&dir.path().join("missing"))
+                .unwrap_err()
+                .is_config()
+        );
+    }
+
+    #[test]
+    fn defaults_load_from_the_file_with_env_overrides() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(PROJECT_FILE);
+        assert_eq!(
+            load_project_config(&path).unwrap().defaults,
+            ProjectDefaults::default()
+        );
+
+        std::fs::write(&path, "[defaults]\njobs = 4\noutput = \"json\"\n").unwrap();
+        let defaults = load_project_config(&path).unwrap().defaults;
+        assert_eq!(defaults.jobs, Some(4));
+        assert_eq!(defaults.output, Some(OutputFormat::Json));
+
+        let env = |jobs: &'static str, output: &'static str| {
+            move |name: &str| match name {
+                "PIPA_JOBS" => Some(jobs.to_string()),
+                "PIPA_OUTPUT" => Some(output.to_string()),
+                _ => None,
+            }
+        };
+        let overridden = defaults.with_env(env("8", "text")).unwrap();
+        assert_eq!(overridden.jobs, Some(8));
+        assert_eq!(overridden.output, Some(OutputFormat::Text));
+        assert_eq!(defaults.with_env(env("", "")).unwrap(), defaults);
+
+        assert!(defaults.with_env(env("many", "")).unwrap_err().is_config());
+        assert!(defaults.with_env(env("0", "")).unwrap_err().is_config());
+        assert!(defaults.with_env(env("", "yaml")).unwrap_err().is_config());
+
+        std::fs::write(&path, "[defaults]\nthreads = 4\n").unwrap();
+        assert!(load_project_config(&path).unwrap_err().is_config());
+    }
+}
@@ -1,21 +1,49 @@
 use anyhow::Result;
-use std::io::Read;
+use tokio::io::AsyncRead;
+
+/// Object body streamed by `Connector::fetch`.
+pub type ObjectReader = Box<dyn AsyncRead + Send + Unpin>;
 
 /// Common interface for all connectors
 #[async_trait::async_trait]
 pub trait Connector: Send + Sync {
-    async fn fetch(&self, source: &str) -> Result<Box<dyn Read>>;
+    /// Open an object as a stream; the body is read as the caller consumes
+    /// it rather than buffered up front.
+    async fn fetch(&self, source: &str) -> Result<ObjectReader>;
+
+    /// Objects whose location starts with `prefix`, sorted by location.
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>>;
+}
+
+/// An object found by `Connector::list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectInfo {
+    /// Full location, in the form `fetch` accepts.
+    pub location: String,
+    pub size: u64,
 }
 
 // bring in each connector implementation
+pub mod archive;
 pub mod azure;
+pub mod delta;
+pub mod download;
 pub mod fetch;
 pub mod gcs;
+pub mod kafka;
 pub mod local;
+#[cfg(any(test, feature = "test-util"))]
+pub mod memory;
+pub mod retry;
 pub mod s3;
 pub mod sftp;
+pub mod spool;
+pub mod upload;
 
-pub use azure::AzureConnector;
-pub use gcs::GCSConnector;
+pub use azure::{AzureConnector, AzureCredential};
+pub use gcs::{GCSConnector, GcsCredential};
+pub use kafka::KafkaConnector;
 pub use local::LocalConnector;
+#[cfg(feature = "test-util")]
+pub use memory::{FailingConnector, MemoryConnector};
 pub use s3::S3Connector;
@@ -0,0 +1,226 @@
+//! Zip and tar archives as sources.
+//!
+//! A source whose location ends in `.zip`, `.tar`, `.tar.gz`, or `.tgz` is
+//! fetched like any other object, then the members matching its
+//! `archive_member` glob (every file when unset) are extracted in memory,
+//! in archive order. Each member is read with the driver for its own
+//! extension, so a daily bundle of CSV files validates like the files
+//! themselves.
+//!
+//! Member paths are matched as written in the archive (e.g.
+//! `daily/orders.csv`); as in source globs, wildcards don't cross `/`.
+//! Directories and other non-file entries are skipped.
+
+use super::spool::Payload;
+use anyhow::{Context, Result, anyhow};
+use flate2::read::GzDecoder;
+use glob::{MatchOptions, Pattern};
+use std::io::{Cursor, Read, Seek};
+
+/// Supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// The archive format a location's extension names, if any.
+    pub fn of(location: &str) -> Option<Self> {
+        let location = location.to_ascii_lowercase();
+        if location.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if location.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else if location.ends_with(".tar.gz") || location.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else {
+            None
+        }
+    }
+
+    /// Name of the format, as reported in errors.
+    pub fn name(self) -> &'static str {
+        match self {
+            ArchiveKind::Zip => "zip",
+            ArchiveKind::Tar => "tar",
+            ArchiveKind::TarGz => "tar.gz",
+        }
+    }
+
+    /// `location` without its archive extension, e.g. `s3://b/batch` for
+    /// `s3://b/batch.tar.gz`.
+    pub fn strip(self, location: &str) -> &str {
+        let suffix = match self {
+            ArchiveKind::Zip => ".zip",
+            ArchiveKind::Tar => ".tar",
+            ArchiveKind::TarGz if location.to_ascii_lowercase().ends_with(".tgz") => ".tgz",
+            ArchiveKind::TarGz => ".tar.gz",
+        };
+        &location[..location.len() - suffix.len()]
+    }
+}
+
+/// A file extracted from an archive.
+pub struct ArchiveMember {
+    /// Path of the member inside the archive.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Extract the members of `payload` matching `pattern` (every file when
+/// `None`), in archive order.
+///
+/// # Errors
+/// Returns an error if `pattern` isn't a valid glob or the payload isn't
+/// a readable archive of `kind`.
+pub fn extract_members(
+    kind: ArchiveKind,
+    payload: &Payload,
+    pattern: Option<&str>,
+) -> Result<Vec<ArchiveMember>> {
+    let pattern = pattern
+        .map(Pattern::new)
+        .transpose()
+        .map_err(|e| anyhow!("invalid archive_member glob: {}", e))?;
+    let wanted = |name: &str| {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::default()
+        };
+        pattern
+            .as_ref()
+            .is_none_or(|pattern| pattern.matches_with(name, options))
+    };
+
+    match payload {
+        Payload::Memory(data) => extract(kind, Cursor::new(data), wanted),
+        Payload::Spooled { file, .. } => {
+            let handle = file.reopen().context("Failed to reopen spool file")?;
+            extract(kind, handle, wanted)
+        }
+    }
+}
+
+fn extract<R: Read + Seek>(
+    kind: ArchiveKind,
+    reader: R,
+    wanted: impl Fn(&str) -> bool,
+) -> Result<Vec<ArchiveMember>> {
+    match kind {
+        ArchiveKind::Zip => extract_zip(reader, wanted),
+        ArchiveKind::Tar => extract_tar(reader, wanted),
+        ArchiveKind::TarGz => extract_tar(GzDecoder::new(reader), wanted),
+    }
+}
+
+fn extract_zip<R: Read + Seek>(
+    reader: R,
+    wanted: impl Fn(&str) -> bool,
+) -> Result<Vec<ArchiveMember>> {
+    let mut archive = zip::ZipArchive::new(reader).context("Failed to read zip archive")?;
+    let mut members = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if !entry.is_file() || !wanted(entry.name()) {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry
+            .read_to_end(&mut data)
+            .with_context(|| format!("Failed to extract {}", name))?;
+        members.push(ArchiveMember { name, data });
+    }
+    Ok(members)
+}
+
+fn extract_tar<R: Read>(reader: R, wanted: impl Fn(&str) -> bool) -> Result<Vec<ArchiveMember>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut members = Vec::new();
+    for entry in archive.entries().context("Failed to read tar archive")? {
+        let mut entry = entry.context("Failed to read tar archive")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        if !wanted(&name) {
+            continue;
+        }
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry
+            .read_to_end(&mut data)
+            .with_context(|| format!("Failed to extract {}", name))?;
+        members.push(ArchiveMember { name, data });
+    }
+    Ok(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const FILES: [(&str, &str); 3] = [
+        ("orders_1.csv", "id\n1\n"),
+        ("orders_2.csv", "id\n2\n"),
+        ("README.txt", "daily bundle"),
+    ];
+
+    fn names(members: &[ArchiveMember]) -> Vec<&str> {
+        members.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    #[test]
+    fn it_extracts_matching_zip_members() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.add_directory("nested/", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        for (name, content) in FILES {
+            zip.start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        let payload = Payload::Memory(zip.finish().unwrap().into_inner());
+
+        let members = extract_members(ArchiveKind::Zip, &payload, Some("orders_*.csv")).unwrap();
+        assert_eq!(names(&members), ["orders_1.csv", "orders_2.csv"]);
+        assert_eq!(members[1].data, b"id\n2\n");
+        let all = extract_members(ArchiveKind::Zip, &payload, None).unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(extract_members(ArchiveKind::Tar, &payload, None).is_err());
+    }
+
+    #[test]
+    fn it_extracts_gzipped_tar_members() {
+        let mut tar = tar::Builder::new(Vec::new());
+        for (name, content) in FILES {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, format!("daily/{}", name), content.as_bytes())
+                .unwrap();
+        }
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        gz.write_all(&tar.into_inner().unwrap()).unwrap();
+        let payload = Payload::Memory(gz.finish().unwrap());
+
+        let members = extract_members(ArchiveKind::TarGz, &payload, Some("daily/*.csv")).unwrap();
+        assert_eq!(
+            names(&members),
+            ["daily/orders_1.csv", "daily/orders_2.csv"]
+        );
+        assert!(
+            extract_members(ArchiveKind::TarGz, &payload, Some("*.csv"))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            ArchiveKind::of("s3://b/batch.TGZ"),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(ArchiveKind::of("s3://b/batch.csv"), None);
+    }
+}
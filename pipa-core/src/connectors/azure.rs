@@ -1,161 +1,847 @@
 //! Azure Blob Storage connector.
 //!
-//! Provides read/write access to Azure Blob Storage using the REST API
-//! and SharedKey authentication. Implements the generic `Connector`
-//! trait so it can be used interchangeably with other backends (S3, GCS, local).
+//! Provides read/write access to Azure Blob Storage using the REST API.
+//! Implements the generic `Connector` trait so it can be used
+//! interchangeably with other backends (S3, GCS, local).
 //!
 //! ## Responsibilities
-//! - Parse `connection_string` from a profile.
-//! - Generate SharedKey authorization headers.
-//! - Upload (`put_object_from_url`) and fetch (`fetch`) blobs.
+//! - Pick a credential from the profile (see `AzureCredential`).
+//! - Authorize requests: SharedKey signatures, SAS tokens, or Azure AD
+//!   bearer tokens (cached until shortly before they expire).
+//! - Upload (`put_object_from_url`), fetch (`fetch`), and list (`list`) blobs.
 //!
 //! ## Profile fields used
-//! - `connection_string` (must contain `AccountName` and `AccountKey`).
+//! One of, in this order of precedence:
+//! - `connection_string` with `AccountKey` or `SharedAccessSignature`
+//! - `sas_token`
+//! - `tenant_id` + `client_id` + `client_secret` (Azure AD service principal)
+//! - `managed_identity = true` (optional `client_id` picks a user-assigned
+//!   identity)
+//!
+//! Also:
+//! - `account_name` (optional; otherwise taken from the connection string
+//!   or the blob URL host)
+//! - `max_download_bytes_per_sec` / `resume_attempts` (see `connectors::download`)
+//! - `retry_attempts` / `retry_backoff_ms` / `retry_max_backoff_ms` (see `connectors::retry`)
+//! - `multipart_threshold_bytes` / `multipart_part_bytes` (see `connectors::upload`)
 //!
 //! ## Expected URL format
 //! - `https://<account>.blob.core.windows.net/<container>/<blob>`
 
-use crate::connectors::Connector;
+use crate::connectors::download::{
+    ChunkReader, Download, DownloadOptions, RangedBody, download_with_resume,
+};
+use crate::connectors::retry::{RetryPolicy, classify, retry};
+use crate::connectors::upload::{UploadOptions, content_type};
+use crate::connectors::{Connector, ObjectInfo, ObjectReader};
 use crate::profiles::Profile;
 use anyhow::{Result, anyhow, bail};
 use async_trait::async_trait;
 use base64::{Engine as _, engine::general_purpose};
 use chrono::Utc;
 use hmac::{Hmac, Mac};
+use regex::Regex;
+use reqwest::{Method, RequestBuilder};
 use sha2::Sha256;
-use std::io::Read;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use url::Url;
 
+/// Blob service REST API version sent with every request.
+const API_VERSION: &str = "2020-04-08";
+
+/// Azure AD resource (and scope prefix) for Blob Storage tokens.
+const STORAGE_RESOURCE: &str = "https://storage.azure.com/";
+
+/// Instance Metadata Service token endpoint for Managed Identity.
+const IMDS_TOKEN_URL: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+
+/// Bearer tokens are refreshed this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// How requests to Azure are authorized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AzureCredential {
+    /// Account key, used to sign SharedKey `Authorization` headers.
+    SharedKey { account_key: String },
+    /// Shared access signature appended to every request URL.
+    Sas { token: String },
+    /// Azure AD service principal (OAuth2 client credentials flow).
+    ServicePrincipal {
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+    },
+    /// Managed Identity of the VM, App Service, or container running pipa;
+    /// `client_id` selects a user-assigned identity.
+    ManagedIdentity { client_id: Option<String> },
+}
+
+impl AzureCredential {
+    /// Pick the credential a profile configures, and the account name from
+    /// its connection string if it has one.
+    pub fn from_profile(profile: &Profile) -> Result<(Self, Option<String>)> {
+        if let Some(connection_string) = &profile.connection_string {
+            return Self::parse_connection_string(connection_string);
+        }
+        if let Some(token) = &profile.sas_token {
+            let token = token.trim_start_matches('?').to_string();
+            return Ok((AzureCredential::Sas { token }, None));
+        }
+        match (
+            &profile.tenant_id,
+            &profile.client_id,
+            &profile.client_secret,
+        ) {
+            (Some(tenant_id), Some(client_id), Some(client_secret)) => {
+                return Ok((
+                    AzureCredential::ServicePrincipal {
+                        tenant_id: tenant_id.clone(),
+                        client_id: client_id.clone(),
+                        client_secret: client_secret.clone(),
+                    },
+                    None,
+                ));
+            }
+            (_, _, Some(_)) => {
+                bail!("Azure profile with client_secret also needs tenant_id and client_id")
+            }
+            _ => {}
+        }
+        if profile.managed_identity.unwrap_or(false) {
+            return Ok((
+                AzureCredential::ManagedIdentity {
+                    client_id: profile.client_id.clone(),
+                },
+                None,
+            ));
+        }
+        bail!(
+            "Azure profile needs connection_string, sas_token, a service principal \
+             (tenant_id, client_id, client_secret), or managed_identity = true"
+        )
+    }
+
+    /// Parse `AccountName` and `AccountKey` or `SharedAccessSignature` from
+    /// a connection string.
+    fn parse_connection_string(connection_string: &str) -> Result<(Self, Option<String>)> {
+        let mut account_name = None;
+        let mut credential = None;
+
+        for part in connection_string.split(';') {
+            if let Some(name) = part.strip_prefix("AccountName=") {
+                account_name = Some(name.to_string());
+            } else if let Some(key) = part.strip_prefix("AccountKey=") {
+                credential = Some(AzureCredential::SharedKey {
+                    account_key: key.to_string(),
+                });
+            } else if let Some(token) = part.strip_prefix("SharedAccessSignature=") {
+                credential = Some(AzureCredential::Sas {
+                    token: token.trim_start_matches('?').to_string(),
+                });
+            }
+        }
+
+        match (credential, account_name) {
+            (Some(AzureCredential::SharedKey { .. }), None) | (None, _) => {
+                bail!("Invalid connection string format")
+            }
+            (Some(credential), account_name) => Ok((credential, account_name)),
+        }
+    }
+
+    /// Fetch an Azure AD access token for `resource` (e.g.
+    /// `https://storage.azure.com/`); returns it with its lifetime in seconds.
+    ///
+    /// Only service principals and managed identities use bearer tokens.
+    pub async fn access_token(
+        &self,
+        client: &reqwest::Client,
+        resource: &str,
+    ) -> Result<(String, u64)> {
+        let request = match self {
+            AzureCredential::ServicePrincipal {
+                tenant_id,
+                client_id,
+                client_secret,
+            } => client
+                .post(format!(
+                    "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+                    tenant_id
+                ))
+                .form(&[
+                    ("grant_type", "client_credentials"),
+                    ("client_id", client_id.as_str()),
+                    ("client_secret", client_secret.as_str()),
+                    (
+                        "scope",
+                        &format!("{}/.default", resource.trim_end_matches('/')),
+                    ),
+                ]),
+            AzureCredential::ManagedIdentity { client_id } => {
+                let mut query = vec![("resource", resource)];
+                if let Some(client_id) = client_id {
+                    query.push(("client_id", client_id));
+                }
+                // App Service, Functions, and Container Apps expose their own
+                // endpoint; VMs use the Instance Metadata Service.
+                match (
+                    std::env::var("IDENTITY_ENDPOINT"),
+                    std::env::var("IDENTITY_HEADER"),
+                ) {
+                    (Ok(endpoint), Ok(header)) => {
+                        query.push(("api-version", "2019-08-01"));
+                        client
+                            .get(endpoint)
+                            .header("X-IDENTITY-HEADER", header)
+                            .query(&query)
+                    }
+                    _ => {
+                        query.push(("api-version", "2018-02-01"));
+                        client
+                            .get(IMDS_TOKEN_URL)
+                            .header("Metadata", "true")
+                            .query(&query)
+                    }
+                }
+            }
+            _ => bail!("Azure credential does not use bearer tokens"),
+        };
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            bail!("Azure AD token request failed: {} - {}", status, error_text);
+        }
+        let body: serde_json::Value = response.json().await?;
+        let token = body["access_token"]
+            .as_str()
+            .ok_or_else(|| anyhow!("access_token not found in Azure AD response"))?
+            .to_string();
+        // `expires_in` is a number from Azure AD and a string from IMDS.
+        let expires_in = body["expires_in"]
+            .as_u64()
+            .or_else(|| body["expires_in"].as_str().and_then(|s| s.parse().ok()))
+            .unwrap_or(3600);
+        Ok((token, expires_in))
+    }
+}
+
 /// Concrete connector for Azure Blob Storage.
 pub struct AzureConnector {
     account_name: String,
-    account_key: String,
+    credential: AzureCredential,
+    /// Cached Azure AD token and when it should be refreshed.
+    token: Mutex<Option<(String, Instant)>>,
     client: reqwest::Client,
+    retry: RetryPolicy,
+    upload: UploadOptions,
 }
 
 impl AzureConnector {
     /// Build an `AzureConnector` from a profile and URL.
     ///
-    /// Expects `connection_string` in the profile.
-    pub async fn from_profile_and_url(profile: &Profile, _url: &Url) -> Result<Self> {
-        let connection_string = profile
-            .connection_string
-            .as_ref()
-            .ok_or_else(|| anyhow!("Azure profile missing connection_string"))?;
-
-        let (account_name, account_key) = Self::parse_connection_string(connection_string)?;
+    /// The account name comes from the connection string, the profile's
+    /// `account_name`, or the URL host (`<account>.blob.core.windows.net`).
+    pub async fn from_profile_and_url(profile: &Profile, url: &Url) -> Result<Self> {
+        let (credential, connection_account) = AzureCredential::from_profile(profile)?;
+        let account_name = connection_account
+            .or_else(|| profile.account_name.clone())
+            .or_else(|| {
+                url.host_str()
+                    .and_then(|host| host.strip_suffix(".blob.core.windows.net"))
+                    .map(str::to_string)
+            })
+            .ok_or_else(|| anyhow!("Azure profile missing account_name"))?;
 
         Ok(AzureConnector {
             account_name,
-            account_key,
+            credential,
+            token: Mutex::new(None),
             client: reqwest::Client::new(),
+            retry: RetryPolicy::from_profile(profile),
+            upload: UploadOptions::from_profile(profile),
         })
     }
 
-    /// Parse `AccountName` and `AccountKey` from a connection string.
-    fn parse_connection_string(connection_string: &str) -> Result<(String, String)> {
-        let mut account_name = None;
-        let mut account_key = None;
+    /// Start an authorized request to `url`.
+    ///
+    /// `content_length` and `range` (the `Range` header for ranged GETs, or
+    /// empty) are part of a SharedKey signature; the caller still sets the
+    /// `Range` header itself.
+    async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        content_length: usize,
+        range: &str,
+    ) -> Result<RequestBuilder> {
+        self.request_with_headers(method, url, content_length, range, &[])
+            .await
+    }
 
-        for part in connection_string.split(';') {
-            if let Some(name) = part.strip_prefix("AccountName=") {
-                account_name = Some(name.to_string());
-            } else if let Some(key) = part.strip_prefix("AccountKey=") {
-                account_key = Some(key.to_string());
+    /// `request` with extra `x-ms-*` headers, which are set on the request
+    /// and, for SharedKey, signed with it.
+    async fn request_with_headers(
+        &self,
+        method: Method,
+        url: &str,
+        content_length: usize,
+        range: &str,
+        ms_headers: &[(&str, &str)],
+    ) -> Result<RequestBuilder> {
+        let request = match &self.credential {
+            AzureCredential::SharedKey { .. } => {
+                let (auth_header, date) = self.create_auth_header(
+                    method.as_str(),
+                    url,
+                    content_length,
+                    range,
+                    ms_headers,
+                )?;
+                self.client
+                    .request(method, url)
+                    .header("Authorization", auth_header)
+                    .header("x-ms-date", date)
             }
-        }
+            AzureCredential::Sas { token } => self.client.request(method, with_sas(url, token)),
+            AzureCredential::ServicePrincipal { .. } | AzureCredential::ManagedIdentity { .. } => {
+                let token = self.bearer_token().await?;
+                self.client
+                    .request(method, url)
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("x-ms-date", http_date())
+            }
+        };
+        let request = ms_headers.iter().fold(request, |request, (name, value)| {
+            request.header(*name, *value)
+        });
+        Ok(request.header("x-ms-version", API_VERSION))
+    }
 
-        match (account_name, account_key) {
-            (Some(name), Some(key)) => Ok((name, key)),
-            _ => bail!("Invalid connection string format"),
+    /// Azure AD access token for Blob Storage, fetched once and reused until
+    /// shortly before it expires.
+    async fn bearer_token(&self) -> Result<String> {
+        let mut cached = self.token.lock().await;
+        if let Some((token, refresh_at)) = cached.as_ref()
+            && Instant::now() < *refresh_at
+        {
+            return Ok(token.clone());
         }
+
+        let (token, expires_in) = self
+            .credential
+            .access_token(&self.client, STORAGE_RESOURCE)
+            .await?;
+        let refresh_at =
+            Instant::now() + Duration::from_secs(expires_in).saturating_sub(TOKEN_REFRESH_MARGIN);
+        *cached = Some((token.clone(), refresh_at));
+        Ok(token)
     }
 
     /// Create a SharedKey authorization header for Azure Blob Storage.
     ///
     /// Builds the canonical string to sign and computes the HMAC-SHA256 signature.
+    ///
+    /// `range` is the `Range` header value for ranged GETs (empty otherwise);
+    /// `ms_headers` are `x-ms-*` headers sent besides date and version.
     fn create_auth_header(
         &self,
         method: &str,
         url: &str,
         content_length: usize,
+        range: &str,
+        ms_headers: &[(&str, &str)],
     ) -> Result<(String, String)> {
         let parsed_url = Url::parse(url)?;
-        let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let date = http_date();
 
-        let resource = format!("/{}{}", self.account_name, parsed_url.path());
+        let mut resource = format!("/{}{}", self.account_name, parsed_url.path());
+        let mut params: Vec<(String, String)> = parsed_url
+            .query_pairs()
+            .map(|(name, value)| (name.to_lowercase(), value.into_owned()))
+            .collect();
+        params.sort();
+        for (name, value) in params {
+            resource.push_str(&format!("\n{}:{}", name, value));
+        }
+
+        let mut headers = vec![("x-ms-date", date.as_str()), ("x-ms-version", API_VERSION)];
+        headers.extend_from_slice(ms_headers);
+        headers.sort();
+        let headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
 
-        // Canonical string differs for GET vs PUT
+        // Canonical string differs for GET vs PUT; a zero Content-Length is
+        // signed as empty.
         let string_to_sign = if method == "GET" {
             format!(
-                "{}\n\n\n\n\n\n\n\n\n\n\n\nx-ms-date:{}\nx-ms-version:2020-04-08\n{}",
-                method, date, resource
+                "{}\n\n\n\n\n\n\n\n\n\n\n{}\n{}{}",
+                method, range, headers, resource
             )
         } else {
+            let content_length = match content_length {
+                0 => String::new(),
+                n => n.to_string(),
+            };
             format!(
-                "{}\n\n\n{}\n\n\n\n\n\n\n\n\nx-ms-date:{}\nx-ms-version:2020-04-08\n{}",
-                method, content_length, date, resource
+                "{}\n\n\n{}\n\n\n\n\n\n\n\n\n{}{}",
+                method, content_length, headers, resource
             )
         };
 
+        Ok((self.sign(&string_to_sign)?, date))
+    }
+
+    /// Sign a canonical string and build the SharedKey `Authorization` value.
+    fn sign(&self, string_to_sign: &str) -> Result<String> {
+        let AzureCredential::SharedKey { account_key } = &self.credential else {
+            bail!("SharedKey signing needs an account key");
+        };
         // Decode account key and compute HMAC-SHA256 signature
-        let key_bytes = general_purpose::STANDARD.decode(&self.account_key)?;
+        let key_bytes = general_purpose::STANDARD.decode(account_key)?;
         let mut mac = Hmac::<Sha256>::new_from_slice(&key_bytes)?;
         mac.update(string_to_sign.as_bytes());
         let signature = general_purpose::STANDARD.encode(mac.finalize().into_bytes());
 
-        let auth_header = format!("SharedKey {}:{}", self.account_name, signature);
-        Ok((auth_header, date))
+        Ok(format!("SharedKey {}:{}", self.account_name, signature))
     }
 
-    /// Upload a blob to Azure given a full HTTPS URL.
-    pub async fn put_object_from_url(&self, azure_url: &str, data: &[u8]) -> Result<()> {
-        let (auth_header, date) = self.create_auth_header("PUT", azure_url, data.len())?;
+    /// Create a container given its URL (`.../<container>`).
+    ///
+    /// Used to seed emulators such as Azurite in integration tests.
+    #[cfg(feature = "test-util")]
+    pub async fn create_container(&self, container_url: &str) -> Result<()> {
+        let parsed_url = Url::parse(container_url)?;
+        let date = http_date();
+        let string_to_sign = format!(
+            "PUT\n\n\n\n\n\n\n\n\n\n\n\nx-ms-date:{}\nx-ms-version:{}\n/{}{}\nrestype:container",
+            date,
+            API_VERSION,
+            self.account_name,
+            parsed_url.path()
+        );
 
         let response = self
             .client
-            .put(azure_url)
-            .header("Authorization", auth_header)
+            .put(format!("{}?restype=container", container_url))
+            .header("Authorization", self.sign(&string_to_sign)?)
             .header("x-ms-date", date)
-            .header("x-ms-version", "2020-04-08")
-            .header("x-ms-blob-type", "BlockBlob")
-            .header("Content-Type", "application/octet-stream")
-            .body(data.to_vec())
+            .header("x-ms-version", API_VERSION)
+            .header("Content-Length", "0")
             .send()
             .await?;
 
-        if !response.status().is_success() {
+        // 409 Conflict: the container already exists
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::CONFLICT {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            bail!("Failed to upload blob: {} - {}", status, error_text);
+            bail!("Failed to create container: {} - {}", status, error_text);
         }
 
         Ok(())
     }
-}
 
-#[async_trait]
-impl Connector for AzureConnector {
-    /// Fetch a blob from Azure and return it as a `Read` stream.
-    async fn fetch(&self, source: &str) -> Result<Box<dyn Read>> {
-        let (auth_header, date) = self.create_auth_header("GET", source, 0)?;
+    /// Split a blob URL into its container URL and blob-name prefix.
+    ///
+    /// Emulators such as Azurite use path-style URLs
+    /// (`http://host/<account>/<container>/<blob>`), so a leading account
+    /// segment is kept in the container URL.
+    fn container_and_prefix(&self, blob_url: &str) -> Result<(Url, String)> {
+        let mut url = Url::parse(blob_url)?;
+        let segments: Vec<String> = url
+            .path()
+            .trim_start_matches('/')
+            .split('/')
+            .map(str::to_string)
+            .collect();
+        let container_index = usize::from(segments[0] == self.account_name && segments.len() > 1);
+        if segments[container_index].is_empty() {
+            bail!("Invalid Azure blob URL: missing container");
+        }
+
+        let prefix = segments[container_index + 1..].join("/");
+        url.set_path(&segments[..=container_index].join("/"));
+        url.set_query(None);
+        Ok((url, prefix))
+    }
+
+    /// Delete a blob given a full HTTPS URL.
+    pub async fn delete_object_from_url(&self, azure_url: &str) -> Result<()> {
+        retry(&self.retry, || async move {
+            let response = self
+                .request(Method::DELETE, azure_url, 0, "")
+                .await?
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(classify(
+                    status.as_u16(),
+                    anyhow!("Failed to delete blob: {} - {}", status, error_text),
+                ));
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Copy a blob server-side (Copy Blob) between two HTTPS URLs in this
+    /// account.
+    ///
+    /// Copies within an account complete synchronously; one the service
+    /// leaves pending is reported as an error.
+    pub async fn copy_object_from_url(&self, from_url: &str, to_url: &str) -> Result<()> {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_with_headers(
+                    Method::PUT,
+                    to_url,
+                    0,
+                    "",
+                    &[("x-ms-copy-source", from_url)],
+                )
+                .await?
+                .header("Content-Length", "0")
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(classify(
+                    status.as_u16(),
+                    anyhow!("Failed to copy blob: {} - {}", status, error_text),
+                ));
+            }
+            let copy_status = response
+                .headers()
+                .get("x-ms-copy-status")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("success");
+            if copy_status != "success" {
+                bail!("Blob copy to {} is {}", to_url, copy_status);
+            }
 
+            Ok(())
+        })
+        .await
+    }
+
+    /// Move an object between two HTTPS URLs: Blob Storage has no rename, so it is
+    /// copied server-side and the original deleted.
+    pub async fn rename_object_from_url(&self, from_url: &str, to_url: &str) -> Result<()> {
+        self.copy_object_from_url(from_url, to_url).await?;
+        self.delete_object_from_url(from_url).await
+    }
+
+    /// Upload a blob to Azure given a full HTTPS URL.
+    ///
+    /// Blobs over `multipart_threshold_bytes` are uploaded as blocks. The
+    /// blob's content type follows the URL's extension (see `content_type`).
+    pub async fn put_object_from_url(&self, azure_url: &str, data: &[u8]) -> Result<()> {
+        if self.upload.is_multipart(data.len()) {
+            return self.put_blocks(azure_url, data).await;
+        }
+
+        let headers = [
+            ("x-ms-blob-content-type", content_type(azure_url)),
+            ("x-ms-blob-type", "BlockBlob"),
+        ];
+        retry(&self.retry, || async move {
+            let response = self
+                .request_with_headers(Method::PUT, azure_url, data.len(), "", &headers)
+                .await?
+                .body(data.to_vec())
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(classify(
+                    status.as_u16(),
+                    anyhow!("Failed to upload blob: {} - {}", status, error_text),
+                ));
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Stage `data` as blocks with Put Block, then commit them with Put Block List.
+    async fn put_blocks(&self, azure_url: &str, data: &[u8]) -> Result<()> {
+        let mut block_list =
+            String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<BlockList>");
+        for (index, chunk) in self.upload.parts(data, 1, 1).enumerate() {
+            // Block IDs must all be the same length within a blob.
+            let block_id = general_purpose::STANDARD.encode(format!("block-{:08}", index));
+            let url = format!(
+                "{}?comp=block&blockid={}",
+                azure_url,
+                urlencoding::encode(&block_id)
+            );
+            retry(&self.retry, || {
+                self.put_signed(&url, chunk.to_vec(), &[], "Failed to upload block")
+            })
+            .await?;
+            block_list.push_str(&format!("<Latest>{}</Latest>", block_id));
+        }
+        block_list.push_str("</BlockList>");
+
+        let url = format!("{}?comp=blocklist", azure_url);
+        let headers = [("x-ms-blob-content-type", content_type(azure_url))];
+        retry(&self.retry, || {
+            self.put_signed(
+                &url,
+                block_list.clone().into_bytes(),
+                &headers,
+                "Failed to commit block list",
+            )
+        })
+        .await
+    }
+
+    /// Signed `PUT` of `body` to `url` with extra `x-ms-*` headers; `action`
+    /// prefixes the error message.
+    async fn put_signed(
+        &self,
+        url: &str,
+        body: Vec<u8>,
+        ms_headers: &[(&str, &str)],
+        action: &str,
+    ) -> Result<()> {
         let response = self
-            .client
-            .get(source)
-            .header("Authorization", auth_header)
-            .header("x-ms-date", date)
-            .header("x-ms-version", "2020-04-08")
+            .request_with_headers(Method::PUT, url, body.len(), "", ms_headers)
+            .await?
+            .body(body)
             .send()
             .await?;
 
-        if response.status().is_success() {
-            let data = response.bytes().await?;
-            Ok(Box::new(std::io::Cursor::new(data.to_vec())))
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(classify(
+                status.as_u16(),
+                anyhow!("{}: {} - {}", action, status, error_text),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Fetch one page of a `List Blobs` response, starting at `marker`.
+    async fn list_page(
+        &self,
+        container_url: &Url,
+        blob_prefix: &str,
+        marker: Option<&str>,
+    ) -> Result<String> {
+        let mut url = format!(
+            "{}?restype=container&comp=list&prefix={}",
+            container_url,
+            urlencoding::encode(blob_prefix)
+        );
+        if let Some(marker) = marker {
+            url.push_str(&format!("&marker={}", urlencoding::encode(marker)));
+        }
+
+        let response = self.request(Method::GET, &url, 0, "").await?.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(classify(
+                status.as_u16(),
+                anyhow!("Failed to list blobs: {} - {}", status, error_text),
+            ));
+        }
+
+        Ok(response.text().await?)
+    }
+
+    /// Fetch a blob with ranged resumption and optional throttling.
+    pub async fn fetch_ranged(&self, source: &str, options: &DownloadOptions) -> Result<Download> {
+        download_with_resume(options, |offset| self.open(source, offset)).await
+    }
+
+    /// Request a blob from byte `offset` onwards.
+    async fn open(&self, source: &str, offset: u64) -> Result<RangedBody> {
+        let range = if offset > 0 {
+            format!("bytes={}-", offset)
         } else {
+            String::new()
+        };
+        let mut request = self.request(Method::GET, source, 0, &range).await?;
+        if offset > 0 {
+            request = request.header("Range", range);
+        }
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            bail!("Failed to fetch blob: {} - {}", status, error_text);
+            return Err(classify(
+                status.as_u16(),
+                anyhow!("Failed to fetch blob: {} - {}", status, error_text),
+            ));
+        }
+
+        let honors_range = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        Ok(RangedBody {
+            stream: Box::new(response),
+            honors_range,
+        })
+    }
+}
+
+#[async_trait]
+impl Connector for AzureConnector {
+    /// Stream a blob from Azure; only the initial request is retried.
+    async fn fetch(&self, source: &str) -> Result<ObjectReader> {
+        let body = retry(&self.retry, || self.open(source, 0)).await?;
+        Ok(Box::new(ChunkReader::new(body.stream)))
+    }
+
+    /// List blobs under `https://<account>.blob.core.windows.net/<container>/<prefix>`.
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let (container_url, blob_prefix) = self.container_and_prefix(prefix)?;
+        let blob_re = Regex::new(
+            r"(?s)<Blob>.*?<Name>(.*?)</Name>.*?<Content-Length>(\d*)</Content-Length>",
+        )?;
+        let marker_re = Regex::new(r"<NextMarker>(.+?)</NextMarker>")?;
+
+        let mut objects = Vec::new();
+        let mut marker: Option<String> = None;
+        loop {
+            let body = retry(&self.retry, || {
+                self.list_page(&container_url, &blob_prefix, marker.as_deref())
+            })
+            .await?;
+            for blob in blob_re.captures_iter(&body) {
+                objects.push(ObjectInfo {
+                    location: format!("{}/{}", container_url, unescape_xml(&blob[1])),
+                    size: blob[2].parse().unwrap_or(0),
+                });
+            }
+            marker = marker_re.captures(&body).map(|m| unescape_xml(&m[1]));
+            if marker.is_none() {
+                break;
+            }
         }
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        Ok(objects)
+    }
+}
+
+/// Current time in the RFC 1123 format `x-ms-date` expects.
+fn http_date() -> String {
+    Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Append a SAS token to a request URL.
+fn with_sas(url: &str, token: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", url, separator, token)
+}
+
+/// Undo XML entity escaping in a `List Blobs` response value.
+fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credential(toml: &str) -> Result<(AzureCredential, Option<String>)> {
+        AzureCredential::from_profile(&toml::from_str::<Profile>(toml).unwrap())
+    }
+
+    #[test]
+    fn picks_credential_from_profile() {
+        let (shared_key, account) = credential(
+            r#"provider = "azure"
+            connection_string = "DefaultEndpointsProtocol=https;AccountName=acct;AccountKey=a2V5"
+            sas_token = "sv=1""#,
+        )
+        .unwrap();
+        assert_eq!(
+            shared_key,
+            AzureCredential::SharedKey {
+                account_key: "a2V5".to_string()
+            }
+        );
+        assert_eq!(account.as_deref(), Some("acct"));
+
+        let (sas, account) = credential(
+            r#"provider = "azure"
+            connection_string = "BlobEndpoint=https://acct.blob.core.windows.net/;SharedAccessSignature=?sv=1&sig=x""#,
+        )
+        .unwrap();
+        assert_eq!(
+            sas,
+            AzureCredential::Sas {
+                token: "sv=1&sig=x".to_string()
+            }
+        );
+        assert_eq!(account, None);
+
+        let (principal, _) = credential(
+            r#"provider = "azure"
+            tenant_id = "t"
+            client_id = "c"
+            client_secret = "s"
+            managed_identity = true"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            principal,
+            AzureCredential::ServicePrincipal { .. }
+        ));
+
+        let (identity, _) = credential(
+            r#"provider = "azure"
+            client_id = "c"
+            managed_identity = true"#,
+        )
+        .unwrap();
+        assert_eq!(
+            identity,
+            AzureCredential::ManagedIdentity {
+                client_id: Some("c".to_string())
+            }
+        );
+
+        assert!(credential(r#"provider = "azure""#).is_err());
+        assert!(credential("provider = \"azure\"\nclient_secret = \"s\"").is_err());
+        assert!(
+            credential("provider = \"azure\"\nconnection_string = \"AccountKey=a2V5\"").is_err()
+        );
+    }
+
+    #[test]
+    fn appends_sas_token_to_urls() {
+        let blob = "https://acct.blob.core.windows.net/c/a.csv";
+        assert_eq!(with_sas(blob, "sv=1"), format!("{}?sv=1", blob));
+        assert_eq!(
+            with_sas(
+                "https://acct.blob.core.windows.net/c?restype=container",
+                "sv=1"
+            ),
+            "https://acct.blob.core.windows.net/c?restype=container&sv=1"
+        );
     }
 }
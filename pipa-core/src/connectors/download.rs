@@ -0,0 +1,357 @@
+//! Shared download loop for cloud connectors.
+//!
+//! Cloud connectors (S3, Azure, GCS) fetch objects through
+//! `download_with_resume`, which:
+//! - streams the body chunk by chunk,
+//! - resumes from the last received byte with a ranged request
+//!   (`Range: bytes=<offset>-`) when the transfer breaks mid-way,
+//! - optionally throttles throughput to a configured bytes/sec budget,
+//! - spools to a temp file past a size threshold (see `spool`),
+//! - reports each chunk to the run's `RunProgress`.
+//!
+//! Each resume is recorded as a `ResumeAttempt` so the runner can write it
+//! to the audit trail; a download that fails before its first byte is not a
+//! resume and is left to the connector's retry. Resumes wait out the profile's retry backoff (see
+//! `connectors::retry`); permanent failures such as a 404 are not resumed.
+//!
+//! ## Profile fields used
+//! - `max_download_bytes_per_sec` (optional, unlimited when unset)
+//! - `resume_attempts` (optional, default: 3)
+//! - `spool_threshold_bytes` (optional, in-memory only when unset)
+//! - `retry_backoff_ms` / `retry_max_backoff_ms` (delay between resumes)
+
+use crate::connectors::retry::{RetryPolicy, is_permanent};
+use crate::connectors::spool::{Payload, Spool};
+use crate::environment::Progress;
+use crate::profiles::Profile;
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Default number of ranged resumes before a download is abandoned.
+const DEFAULT_RESUME_ATTEMPTS: u32 = 3;
+
+/// Download tuning derived from a profile.
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    pub max_bytes_per_sec: Option<u64>,
+    pub max_resume_attempts: u32,
+    pub spool_threshold_bytes: Option<usize>,
+    /// Backoff between resumes.
+    pub retry: RetryPolicy,
+    /// Told about every chunk received.
+    pub progress: Progress,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes_per_sec: None,
+            max_resume_attempts: DEFAULT_RESUME_ATTEMPTS,
+            spool_threshold_bytes: None,
+            retry: RetryPolicy::default(),
+            progress: Progress::default(),
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// Read throttling/resume/spool/retry settings from a profile, falling back to defaults.
+    pub fn from_profile(profile: &Profile) -> Self {
+        Self {
+            max_bytes_per_sec: profile.max_download_bytes_per_sec.filter(|rate| *rate > 0),
+            max_resume_attempts: profile.resume_attempts.unwrap_or(DEFAULT_RESUME_ATTEMPTS),
+            spool_threshold_bytes: profile.spool_threshold_bytes.map(|bytes| bytes as usize),
+            retry: RetryPolicy::from_profile(profile),
+            progress: Progress::default(),
+        }
+    }
+}
+
+/// A single ranged resume performed during a download.
+#[derive(Debug, Clone)]
+pub struct ResumeAttempt {
+    pub attempt: u32,
+    pub offset: u64,
+    pub cause: String,
+}
+
+/// A completed download and the resumes it needed.
+pub struct Download {
+    pub payload: Payload,
+    pub resumes: Vec<ResumeAttempt>,
+}
+
+/// A response body that can be drained chunk by chunk.
+#[async_trait]
+pub trait ChunkStream: Send {
+    /// Append the next chunk to `buf`, returning its size (0 at end of body).
+    async fn read_chunk(&mut self, buf: &mut Vec<u8>) -> Result<usize>;
+}
+
+/// Response for a (possibly ranged) request.
+pub struct RangedBody {
+    pub stream: Box<dyn ChunkStream>,
+    /// `false` when the server ignored the `Range` header and is sending
+    /// the object from byte 0 again.
+    pub honors_range: bool,
+}
+
+#[async_trait]
+impl ChunkStream for reqwest::Response {
+    async fn read_chunk(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        match self.chunk().await? {
+            Some(chunk) => {
+                buf.extend_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+#[async_trait]
+impl ChunkStream for aws_sdk_s3::primitives::ByteStream {
+    async fn read_chunk(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        match self.next().await {
+            Some(chunk) => {
+                let chunk = chunk?;
+                buf.extend_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+/// `AsyncRead` over a `ChunkStream`, so response bodies can be handed out
+/// by `Connector::fetch` without buffering them first.
+pub struct ChunkReader {
+    chunks: BoxStream<'static, io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    pos: usize,
+}
+
+impl ChunkReader {
+    pub fn new(stream: Box<dyn ChunkStream>) -> Self {
+        let chunks = futures::stream::unfold(Some(stream), |stream| async move {
+            let mut stream = stream?;
+            let mut chunk = Vec::new();
+            match stream.read_chunk(&mut chunk).await {
+                Ok(0) => None,
+                Ok(_) => Some((Ok(chunk), Some(stream))),
+                Err(e) => Some((Err(io::Error::other(e)), None)),
+            }
+        });
+        Self {
+            chunks: chunks.boxed(),
+            chunk: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl AsyncRead for ChunkReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        while self.pos == self.chunk.len() {
+            match ready!(self.chunks.poll_next_unpin(cx)) {
+                Some(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.pos = 0;
+                }
+                Some(Err(e)) => return Poll::Ready(Err(e)),
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+        let n = buf.remaining().min(self.chunk.len() - self.pos);
+        let start = self.pos;
+        buf.put_slice(&self.chunk[start..start + n]);
+        self.pos += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Download an object, resuming from the last received byte on failure.
+///
+/// `open` is called with the byte offset to start from (0 for the initial
+/// request) and must issue a request for `bytes=<offset>-`. A failure
+/// before the first byte arrives is returned as is rather than resumed.
+pub async fn download_with_resume<F, Fut>(
+    options: &DownloadOptions,
+    mut open: F,
+) -> Result<Download>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<RangedBody>>,
+{
+    let mut spool = Spool::new(options.spool_threshold_bytes);
+    let mut resumes: Vec<ResumeAttempt> = Vec::new();
+    let started = Instant::now();
+    let mut received: u64 = 0;
+
+    loop {
+        let offset = spool.len() as u64;
+        let outcome = match open(offset).await {
+            Ok(mut body) => {
+                if offset > 0 && !body.honors_range {
+                    // Server restarted from byte 0; drop what we had.
+                    spool.clear()?;
+                }
+                drain(&mut body, &mut spool, options, started, &mut received).await
+            }
+            Err(e) => Err(e),
+        };
+
+        match outcome {
+            Ok(()) => {
+                return Ok(Download {
+                    payload: spool.finish()?,
+                    resumes,
+                });
+            }
+            Err(e) => {
+                // With nothing received there is nothing to resume; the
+                // connector's own retry owns the initial request.
+                if is_permanent(&e) || spool.len() == 0 {
+                    return Err(e);
+                }
+                if resumes.len() as u32 >= options.max_resume_attempts {
+                    return Err(e.context(format!(
+                        "download failed after {} resume attempts at offset {}",
+                        resumes.len(),
+                        spool.len()
+                    )));
+                }
+                resumes.push(ResumeAttempt {
+                    attempt: resumes.len() as u32 + 1,
+                    offset: spool.len() as u64,
+                    cause: e.to_string(),
+                });
+                tokio::time::sleep(options.retry.backoff(resumes.len() as u32)).await;
+            }
+        }
+    }
+}
+
+/// Drain a body into the spool, pausing as needed to honor the bandwidth cap.
+async fn drain(
+    body: &mut RangedBody,
+    spool: &mut Spool,
+    options: &DownloadOptions,
+    started: Instant,
+    received: &mut u64,
+) -> Result<()> {
+    let mut chunk = Vec::new();
+    loop {
+        chunk.clear();
+        let n = body.stream.read_chunk(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        spool.write(&chunk)?;
+        *received += n as u64;
+        options.progress.downloaded(n as u64);
+
+        if let Some(rate) = options.max_bytes_per_sec {
+            let expected = Duration::from_secs_f64(*received as f64 / rate as f64);
+            let elapsed = started.elapsed();
+            if expected > elapsed {
+                tokio::time::sleep(expected - elapsed).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use tokio::io::AsyncReadExt;
+
+    /// Serves fixed chunks, then an optional error.
+    struct Chunks(Vec<&'static [u8]>, Option<&'static str>);
+
+    #[async_trait]
+    impl ChunkStream for Chunks {
+        async fn read_chunk(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+            if self.0.is_empty() {
+                return match self.1.take() {
+                    Some(message) => Err(anyhow!(message)),
+                    None => Ok(0),
+                };
+            }
+            let chunk = self.0.remove(0);
+            buf.extend_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[tokio::test]
+    async fn chunk_reader_streams_body() {
+        let mut reader = ChunkReader::new(Box::new(Chunks(vec![b"id,", b"amount\n1,10\n"], None)));
+        let mut first = [0u8; 2];
+        reader.read_exact(&mut first).await.unwrap();
+        assert_eq!(&first, b"id");
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).await.unwrap();
+        assert_eq!(rest, ",amount\n1,10\n");
+
+        let mut broken = ChunkReader::new(Box::new(Chunks(vec![b"id\n"], Some("reset"))));
+        let err = broken.read_to_end(&mut Vec::new()).await.unwrap_err();
+        assert_eq!(err.to_string(), "reset");
+    }
+
+    #[tokio::test]
+    async fn only_downloads_with_bytes_received_are_resumed() {
+        let options = DownloadOptions {
+            retry: RetryPolicy {
+                initial_backoff: Duration::ZERO,
+                ..RetryPolicy::default()
+            },
+            ..DownloadOptions::default()
+        };
+
+        let mut offsets = Vec::new();
+        let download = download_with_resume(&options, |offset| {
+            offsets.push(offset);
+            let chunks = match offset {
+                0 => Chunks(vec![b"id,", b"amount\n"], Some("reset")),
+                _ => Chunks(vec![b"1,10\n"], None),
+            };
+            async move {
+                Ok(RangedBody {
+                    stream: Box::new(chunks),
+                    honors_range: true,
+                })
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(offsets, [0, 10]);
+        assert_eq!(download.resumes.len(), 1);
+        assert_eq!(download.resumes[0].offset, 10);
+        assert_eq!(download.resumes[0].cause, "reset");
+
+        let mut opened = 0;
+        let err = download_with_resume(&options, |_| {
+            opened += 1;
+            async { Err::<RangedBody, _>(anyhow!("connection refused")) }
+        })
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(opened, 1);
+        assert_eq!(err.to_string(), "connection refused");
+    }
+}
@@ -1,18 +1,77 @@
-use crate::connectors::{AzureConnector, Connector, GCSConnector, LocalConnector, S3Connector};
+use crate::connectors::delta;
+use crate::connectors::download::{DownloadOptions, ResumeAttempt};
+use crate::connectors::kafka::ConsumeLimits;
+use crate::connectors::spool::Payload;
+use crate::connectors::{
+    AzureConnector, Connector, GCSConnector, KafkaConnector, LocalConnector, ObjectInfo,
+    S3Connector,
+};
 use crate::contracts::schema::Source;
+use crate::environment::Progress;
 use crate::logging::error::{ValidationError, ValidationResult};
 use crate::profiles::Profiles;
-use std::io::Read;
+use polars::prelude::ParquetWriter;
+use std::path::Path;
+use tokio::io::AsyncReadExt;
 use url::Url;
 
+/// Object fetched from a source (in memory or spooled to disk), plus any
+/// ranged resumes needed to get it.
+pub struct FetchedData {
+    pub payload: Payload,
+    pub resumes: Vec<ResumeAttempt>,
+}
+
+/// Guess the connector type for a location from its scheme.
+///
+/// `s3://` → `s3`, `gs://` → `gcs`, Azure blob URLs → `azure`,
+/// anything else is treated as a local path.
+pub fn source_type_for_location(location: &str) -> &'static str {
+    if location.starts_with("s3://") {
+        "s3"
+    } else if location.starts_with("gs://") {
+        "gcs"
+    } else if location.starts_with("https://") && location.contains(".blob.core.windows.net") {
+        "azure"
+    } else {
+        "local"
+    }
+}
+
 pub async fn fetch_data_from_source(
     source: &Source,
     profiles: &Profiles,
-) -> ValidationResult<Vec<u8>> {
+) -> ValidationResult<FetchedData> {
+    fetch_data_with_progress(source, profiles, &Progress::default()).await
+}
+
+/// Fetch as `fetch_data_from_source`, telling `progress` about bytes
+/// received: chunk by chunk from S3, Azure, and GCS, and in one go once
+/// other sources have been read.
+pub async fn fetch_data_with_progress(
+    source: &Source,
+    profiles: &Profiles,
+    progress: &Progress,
+) -> ValidationResult<FetchedData> {
     let location = source
         .location
         .as_ref()
-        .ok_or_else(|| ValidationError::Other("Source missing location".to_string()))?;
+        .ok_or_else(|| ValidationError::Config("Source missing location".to_string()))?;
+
+    match source.format.as_deref() {
+        None | Some("fixed_width") => {}
+        Some("delta") => {
+            let fetched = fetch_delta_table(source, profiles).await?;
+            progress.downloaded(fetched.payload.len() as u64);
+            return Ok(fetched);
+        }
+        Some(format) => {
+            return Err(ValidationError::Config(format!(
+                "Unsupported source format: {} (expected \"delta\" or \"fixed_width\")",
+                format
+            )));
+        }
+    }
 
     match source.r#type.as_str() {
         "local" => {
@@ -20,84 +79,240 @@ pub async fn fetch_data_from_source(
             let mut reader = connector
                 .fetch(location)
                 .await
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
             let mut buf = Vec::new();
             reader
                 .read_to_end(&mut buf)
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
-            Ok(buf)
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            progress.downloaded(buf.len() as u64);
+            Ok(FetchedData {
+                payload: Payload::Memory(buf),
+                resumes: Vec::new(),
+            })
         }
         "s3" => {
             let profile_name = source
                 .profile
                 .as_ref()
-                .ok_or_else(|| ValidationError::Other("S3 source requires profile".to_string()))?;
+                .ok_or_else(|| ValidationError::Config("S3 source requires profile".to_string()))?;
             let profile = profiles
                 .get(profile_name)
                 .ok_or_else(|| ValidationError::ProfileNotFound(profile_name.clone()))?;
             let url = Url::parse(location)
-                .map_err(|_| ValidationError::Other("Invalid URL".to_string()))?;
+                .map_err(|e| ValidationError::Config(format!("Invalid URL {}: {}", location, e)))?;
             let connector = S3Connector::from_profile_and_url(profile, &url)
                 .await
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
-            let mut reader = connector
-                .fetch(location)
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            let download = connector
+                .fetch_ranged(
+                    location,
+                    &DownloadOptions {
+                        progress: progress.clone(),
+                        ..DownloadOptions::from_profile(profile)
+                    },
+                )
                 .await
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
-            let mut buffer = Vec::new();
-            reader
-                .read_to_end(&mut buffer)
-                .map_err(ValidationError::Io)?;
-            Ok(buffer)
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            Ok(FetchedData {
+                payload: download.payload,
+                resumes: download.resumes,
+            })
         }
         "azure" => {
             let profile_name = source.profile.as_ref().ok_or_else(|| {
-                ValidationError::Other("Azure source requires profile".to_string())
+                ValidationError::Config("Azure source requires profile".to_string())
             })?;
             let profile = profiles
                 .get(profile_name)
                 .ok_or_else(|| ValidationError::ProfileNotFound(profile_name.clone()))?;
             let url = Url::parse(location)
-                .map_err(|_| ValidationError::Other("Invalid URL".to_string()))?;
+                .map_err(|e| ValidationError::Config(format!("Invalid URL {}: {}", location, e)))?;
             let connector = AzureConnector::from_profile_and_url(profile, &url)
                 .await
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
-            let mut reader = connector
-                .fetch(location)
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            let download = connector
+                .fetch_ranged(
+                    location,
+                    &DownloadOptions {
+                        progress: progress.clone(),
+                        ..DownloadOptions::from_profile(profile)
+                    },
+                )
                 .await
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
-            let mut buffer = Vec::new();
-            reader
-                .read_to_end(&mut buffer)
-                .map_err(ValidationError::Io)?;
-            Ok(buffer)
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            Ok(FetchedData {
+                payload: download.payload,
+                resumes: download.resumes,
+            })
         }
         "gcs" => {
-            let profile_name = source
-                .profile
-                .as_ref()
-                .ok_or_else(|| ValidationError::Other("GCS source requires profile".to_string()))?;
+            let profile_name = source.profile.as_ref().ok_or_else(|| {
+                ValidationError::Config("GCS source requires profile".to_string())
+            })?;
             let profile = profiles
                 .get(profile_name)
                 .ok_or_else(|| ValidationError::ProfileNotFound(profile_name.clone()))?;
             let url = Url::parse(location)
-                .map_err(|_| ValidationError::Other("Invalid URL".to_string()))?;
+                .map_err(|e| ValidationError::Config(format!("Invalid URL {}: {}", location, e)))?;
             let connector = GCSConnector::from_profile_and_url(profile, &url)
                 .await
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            let download = connector
+                .fetch_ranged(
+                    location,
+                    &DownloadOptions {
+                        progress: progress.clone(),
+                        ..DownloadOptions::from_profile(profile)
+                    },
+                )
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            Ok(FetchedData {
+                payload: download.payload,
+                resumes: download.resumes,
+            })
+        }
+        "kafka" => {
+            let profile_name = source.profile.as_ref().ok_or_else(|| {
+                ValidationError::Config("Kafka source requires profile".to_string())
+            })?;
+            let profile = profiles
+                .get(profile_name)
+                .ok_or_else(|| ValidationError::ProfileNotFound(profile_name.clone()))?;
+            let connector =
+                KafkaConnector::from_profile(profile, ConsumeLimits::from_options(&source.options))
+                    .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
             let mut reader = connector
                 .fetch(location)
                 .await
-                .map_err(|e| ValidationError::Connector(e.to_string()))?;
-            let mut buffer = Vec::new();
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            let mut buf = Vec::new();
+            reader
+                .read_to_end(&mut buf)
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            progress.downloaded(buf.len() as u64);
+            Ok(FetchedData {
+                payload: Payload::Memory(buf),
+                resumes: Vec::new(),
+            })
+        }
+        #[cfg(any(test, feature = "test-util"))]
+        "memory" => {
+            let mut reader = crate::connectors::memory::MemoryConnector::shared()
+                .fetch(location)
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            let mut buf = Vec::new();
             reader
-                .read_to_end(&mut buffer)
-                .map_err(ValidationError::Io)?;
-            Ok(buffer)
+                .read_to_end(&mut buf)
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            progress.downloaded(buf.len() as u64);
+            Ok(FetchedData {
+                payload: Payload::Memory(buf),
+                resumes: Vec::new(),
+            })
         }
-        _ => Err(ValidationError::Other(format!(
+        _ => Err(ValidationError::Config(format!(
             "Unsupported source type: {}",
             source.r#type
         ))),
     }
 }
+
+/// Driver extension for the data fetched from `source`.
+///
+/// Kafka messages arrive as newline-delimited JSON and Delta tables as
+/// Parquet; `format = "fixed_width"` sources use the fixed-width driver;
+/// other files use their location's extension, defaulting to CSV.
+pub fn source_extension(source: &Source) -> &str {
+    if source.r#type == "kafka" {
+        return "ndjson";
+    }
+    match source.format.as_deref() {
+        Some("delta") => return "parquet",
+        Some("fixed_width") => return "fixed_width",
+        _ => {}
+    }
+    source
+        .location
+        .as_deref()
+        .and_then(|loc| Path::new(loc).extension().and_then(|s| s.to_str()))
+        .unwrap_or("csv")
+}
+
+/// Read the Delta table at `source`'s location as Parquet.
+async fn fetch_delta_table(source: &Source, profiles: &Profiles) -> ValidationResult<FetchedData> {
+    let location = source
+        .location
+        .as_ref()
+        .ok_or_else(|| ValidationError::Config("Source missing location".to_string()))?;
+    let connector = source_connector(source, location, profiles).await?;
+    let mut df = delta::read_table(connector.as_ref(), location)
+        .await
+        .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+    let mut buf = Vec::new();
+    ParquetWriter::new(&mut buf).finish(&mut df)?;
+    Ok(FetchedData {
+        payload: Payload::Memory(buf),
+        resumes: Vec::new(),
+    })
+}
+
+/// List the objects under `prefix` using the connector for `source`'s type
+/// and profile.
+pub async fn list_source_objects(
+    source: &Source,
+    prefix: &str,
+    profiles: &Profiles,
+) -> ValidationResult<Vec<ObjectInfo>> {
+    source_connector(source, prefix, profiles)
+        .await?
+        .list(prefix)
+        .await
+        .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))
+}
+
+/// Connector for `source`'s type and profile, addressed by `location`.
+async fn source_connector(
+    source: &Source,
+    location: &str,
+    profiles: &Profiles,
+) -> ValidationResult<Box<dyn Connector>> {
+    let connector: Box<dyn Connector> = match source.r#type.as_str() {
+        "local" => Box::new(LocalConnector::new()),
+        #[cfg(any(test, feature = "test-util"))]
+        "memory" => Box::new(crate::connectors::memory::MemoryConnector::shared()),
+        kind @ ("s3" | "azure" | "gcs") => {
+            let profile_name = source.profile.as_ref().ok_or_else(|| {
+                ValidationError::Config(format!("{} source requires profile", kind))
+            })?;
+            let profile = profiles
+                .get(profile_name)
+                .ok_or_else(|| ValidationError::ProfileNotFound(profile_name.clone()))?;
+            let url = Url::parse(location)
+                .map_err(|e| ValidationError::Config(format!("Invalid URL {}: {}", location, e)))?;
+            let connector: anyhow::Result<Box<dyn Connector>> = match kind {
+                "s3" => S3Connector::from_profile_and_url(profile, &url)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn Connector>),
+                "azure" => AzureConnector::from_profile_and_url(profile, &url)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn Connector>),
+                _ => GCSConnector::from_profile_and_url(profile, &url)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn Connector>),
+            };
+            connector.map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?
+        }
+        _ => {
+            return Err(ValidationError::Config(format!(
+                "Unsupported source type: {}",
+                source.r#type
+            )));
+        }
+    };
+    Ok(connector)
+}
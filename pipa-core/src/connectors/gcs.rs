@@ -5,175 +5,682 @@
 //! interchangeably with other backends (S3, Azure, local).
 //!
 //! ## Responsibilities
-//! - Parse service account JSON from a profile.
-//! - Generate OAuth2 access tokens via JWT bearer flow.
-//! - Upload (`put_object_from_url`) and fetch (`fetch`) objects.
+//! - Parse service account JSON from a profile, or resolve Application
+//!   Default Credentials (see `GcsCredential`).
+//! - Generate OAuth2 access tokens (JWT bearer flow, refresh tokens, or the
+//!   metadata server) and cache them until shortly before they expire.
+//! - Upload (`put_object_from_url`), fetch (`fetch`), and list (`list`) objects.
 //! - Convert `gs://bucket/object` style URLs into REST API endpoints.
 //!
 //! ## Profile fields used
 //! - `service_account_json` (must contain `client_email` and `private_key`)
+//! - `application_default_credentials` (optional; `true` uses ADC instead of
+//!   `service_account_json`: the file named by `GOOGLE_APPLICATION_CREDENTIALS`,
+//!   then gcloud's `application_default_credentials.json`, then the metadata
+//!   server, which serves Workload Identity tokens on GKE and Cloud Run)
+//! - `endpoint` (optional, e.g. a fake-gcs-server emulator; without
+//!   credentials, requests to a custom endpoint are anonymous)
+//! - `max_download_bytes_per_sec` / `resume_attempts` (see `connectors::download`)
+//! - `retry_attempts` / `retry_backoff_ms` / `retry_max_backoff_ms` (see `connectors::retry`)
+//! - `multipart_threshold_bytes` / `multipart_part_bytes` (see `connectors::upload`)
 
-use crate::connectors::Connector;
+use crate::connectors::download::{
+    ChunkReader, Download, DownloadOptions, RangedBody, download_with_resume,
+};
+use crate::connectors::retry::{RetryPolicy, classify, retry};
+use crate::connectors::upload::{UploadOptions, content_type};
+use crate::connectors::{Connector, ObjectInfo, ObjectReader};
 use crate::profiles::Profile;
 use anyhow::{Result, anyhow, bail};
 use async_trait::async_trait;
 use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
-use serde_json::json;
-use std::io::Read;
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use url::Url;
 
+/// Default GCS API endpoint.
+const GCS_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// Google OAuth2 token endpoint.
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// OAuth2 scope requested for GCS access.
+const SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Default metadata server host (overridden by `GCE_METADATA_HOST`).
+const METADATA_HOST: &str = "metadata.google.internal";
+
+/// Access tokens are refreshed this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// Resumable upload chunks must be a multiple of 256 KiB (except the last).
+const CHUNK_ALIGN: usize = 256 * 1024;
+
+/// Where GCS access tokens come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GcsCredential {
+    /// Service account key, exchanged with a signed JWT.
+    ServiceAccount {
+        client_email: String,
+        private_key: String,
+    },
+    /// User credentials from `gcloud auth application-default login`.
+    AuthorizedUser {
+        client_id: String,
+        client_secret: String,
+        refresh_token: String,
+    },
+    /// The metadata server of the GCE VM, GKE pod (Workload Identity), or
+    /// Cloud Run service running pipa.
+    MetadataServer,
+}
+
+impl GcsCredential {
+    /// Pick the credential a profile configures; `None` when it has none.
+    pub fn from_profile(profile: &Profile) -> Result<Option<Self>> {
+        if let Some(json) = &profile.service_account_json {
+            return Self::from_json(json).map(Some);
+        }
+        if profile.application_default_credentials.unwrap_or(false) {
+            return Self::application_default().map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Resolve Application Default Credentials: the key file named by
+    /// `GOOGLE_APPLICATION_CREDENTIALS`, gcloud's well-known file, and
+    /// finally the metadata server.
+    pub fn application_default() -> Result<Self> {
+        if let Ok(path) = std::env::var("GOOGLE_APPLICATION_CREDENTIALS") {
+            let json = std::fs::read_to_string(&path).map_err(|e| {
+                anyhow!(
+                    "Failed to read GOOGLE_APPLICATION_CREDENTIALS {}: {}",
+                    path,
+                    e
+                )
+            })?;
+            return Self::from_json(&json);
+        }
+        if let Some(path) = gcloud_credentials_path().filter(|path| path.is_file()) {
+            return Self::from_json(&std::fs::read_to_string(path)?);
+        }
+        Ok(GcsCredential::MetadataServer)
+    }
+
+    /// Parse a service account key or gcloud user credentials file.
+    fn from_json(credentials_json: &str) -> Result<Self> {
+        let json: Value = serde_json::from_str(credentials_json)?;
+        let field = |name: &str| {
+            json[name]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("Missing {} in credentials JSON", name))
+        };
+
+        match json["type"].as_str() {
+            Some("authorized_user") => Ok(GcsCredential::AuthorizedUser {
+                client_id: field("client_id")?,
+                client_secret: field("client_secret")?,
+                refresh_token: field("refresh_token")?,
+            }),
+            Some("service_account") | None => Ok(GcsCredential::ServiceAccount {
+                client_email: field("client_email")?,
+                private_key: field("private_key")?,
+            }),
+            Some(other) => bail!("Unsupported GCS credentials type '{}'", other),
+        }
+    }
+
+    /// Fetch an access token; returns it with its lifetime in seconds.
+    pub async fn access_token(&self, client: &reqwest::Client) -> Result<(String, u64)> {
+        let request = match self {
+            GcsCredential::ServiceAccount {
+                client_email,
+                private_key,
+            } => {
+                // Sign a JWT with the service account key and exchange it.
+                let now = chrono::Utc::now().timestamp();
+                let claims = json!({
+                    "iss": client_email,
+                    "scope": SCOPE,
+                    "aud": TOKEN_URL,
+                    "exp": now + 3600,
+                    "iat": now
+                });
+                let header = Header::new(Algorithm::RS256);
+                let encoding_key = EncodingKey::from_rsa_pem(private_key.as_bytes())?;
+                let jwt_token = encode(&header, &claims, &encoding_key)?;
+                client.post(TOKEN_URL).form(&[
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                    ("assertion", &jwt_token),
+                ])
+            }
+            GcsCredential::AuthorizedUser {
+                client_id,
+                client_secret,
+                refresh_token,
+            } => client.post(TOKEN_URL).form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", client_id),
+                ("client_secret", client_secret),
+                ("refresh_token", refresh_token),
+            ]),
+            GcsCredential::MetadataServer => {
+                let host = std::env::var("GCE_METADATA_HOST")
+                    .unwrap_or_else(|_| METADATA_HOST.to_string());
+                client
+                    .get(format!(
+                        "http://{}/computeMetadata/v1/instance/service-accounts/default/token",
+                        host
+                    ))
+                    .header("Metadata-Flavor", "Google")
+            }
+        };
+
+        let token_response = request.send().await?;
+        if !token_response.status().is_success() {
+            bail!("Token exchange failed: {}", token_response.status());
+        }
+
+        let token_json: Value = token_response.json().await?;
+        let access_token = token_json["access_token"]
+            .as_str()
+            .ok_or_else(|| anyhow!("access_token not found in response"))?
+            .to_string();
+        let expires_in = token_json["expires_in"].as_u64().unwrap_or(3600);
+
+        Ok((access_token, expires_in))
+    }
+}
+
+/// gcloud's Application Default Credentials file for the current user.
+fn gcloud_credentials_path() -> Option<PathBuf> {
+    let config_dir = match std::env::var_os("CLOUDSDK_CONFIG") {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => PathBuf::from(std::env::var_os("APPDATA")?).join("gcloud"),
+        None => home::home_dir()?.join(".config").join("gcloud"),
+    };
+    Some(config_dir.join("application_default_credentials.json"))
+}
+
 /// Concrete connector for GCS.
 pub struct GCSConnector {
-    client_email: String,
-    private_key: String,
+    /// `None` for anonymous emulator access.
+    credentials: Option<GcsCredential>,
+    /// Cached access token and when it should be refreshed.
+    token: Mutex<Option<(String, Instant)>>,
+    endpoint: String,
     client: reqwest::Client,
+    retry: RetryPolicy,
+    upload: UploadOptions,
 }
 
 impl GCSConnector {
     /// Build a GCS connector from a profile and URL.
     ///
-    /// Expects `service_account_json` in the profile.
+    /// Expects `service_account_json` or `application_default_credentials`
+    /// in the profile, unless a custom `endpoint` is set (emulators accept
+    /// anonymous requests).
     pub async fn from_profile_and_url(profile: &Profile, _url: &Url) -> Result<Self> {
-        let service_account_json = profile
-            .service_account_json
-            .as_ref()
-            .ok_or_else(|| anyhow!("GCS profile missing service_account_json"))?;
-
-        let (client_email, private_key) = Self::parse_service_account(service_account_json)?;
+        let credentials = GcsCredential::from_profile(profile)?;
+        if credentials.is_none() && profile.endpoint.is_none() {
+            bail!(
+                "GCS profile missing service_account_json (or application_default_credentials = true)"
+            );
+        }
 
         Ok(GCSConnector {
-            client_email,
-            private_key,
+            credentials,
+            token: Mutex::new(None),
+            endpoint: profile
+                .endpoint
+                .as_deref()
+                .unwrap_or(GCS_ENDPOINT)
+                .trim_end_matches('/')
+                .to_string(),
             client: reqwest::Client::new(),
+            retry: RetryPolicy::from_profile(profile),
+            upload: UploadOptions::from_profile(profile),
         })
     }
 
-    /// Parse service account JSON string into `(client_email, private_key)`.
-    fn parse_service_account(service_account_json: &str) -> Result<(String, String)> {
-        use serde_json::Value;
+    /// `Authorization` header value, or `None` for anonymous access.
+    ///
+    /// The access token is fetched once and reused until shortly before it
+    /// expires.
+    async fn authorization(&self) -> Result<Option<String>> {
+        let Some(credentials) = &self.credentials else {
+            return Ok(None);
+        };
 
-        let json: Value = serde_json::from_str(service_account_json)?;
+        let mut cached = self.token.lock().await;
+        if let Some((token, refresh_at)) = cached.as_ref()
+            && Instant::now() < *refresh_at
+        {
+            return Ok(Some(format!("Bearer {}", token)));
+        }
 
-        let client_email = json["client_email"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Missing client_email in service account JSON"))?
-            .to_string();
+        let (token, expires_in) = credentials.access_token(&self.client).await?;
+        let refresh_at =
+            Instant::now() + Duration::from_secs(expires_in).saturating_sub(TOKEN_REFRESH_MARGIN);
+        *cached = Some((token.clone(), refresh_at));
+        Ok(Some(format!("Bearer {}", token)))
+    }
 
-        let private_key = json["private_key"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Missing private_key in service account JSON"))?
-            .to_string();
+    /// Split a `gs://bucket/object` URL into bucket and object name.
+    fn bucket_and_object(source_url: &str) -> Result<(String, String)> {
+        let (bucket, object) = Self::bucket_and_prefix(source_url)?;
+        if object.is_empty() {
+            bail!("Invalid GCS URL format");
+        }
+        Ok((bucket, object))
+    }
+
+    /// Split a `gs://bucket/prefix` URL into bucket and (possibly empty)
+    /// object prefix.
+    fn bucket_and_prefix(source_url: &str) -> Result<(String, String)> {
+        let url = Url::parse(source_url)?;
+        let bucket = url.host_str().unwrap_or_default();
+        if bucket.is_empty() {
+            bail!("Invalid GCS URL format");
+        }
+        Ok((
+            bucket.to_string(),
+            url.path().trim_start_matches('/').to_string(),
+        ))
+    }
+
+    /// Convert a `gs://bucket/object` style URL into a REST API endpoint.
+    fn convert_to_rest_api_url(&self, source_url: &str) -> Result<String> {
+        let (bucket, object) = Self::bucket_and_object(source_url)?;
 
-        Ok((client_email, private_key))
+        Ok(format!(
+            "{}/storage/v1/b/{}/o/{}?alt=media",
+            self.endpoint,
+            bucket,
+            urlencoding::encode(&object)
+        ))
     }
 
-    /// Generate an OAuth2 access token using JWT bearer flow.
+    /// Create a bucket (used to seed emulators in integration tests).
     ///
-    /// - Signs a JWT with the service account private key.
-    /// - Exchanges it for an access token at Google’s token endpoint.
-    async fn generate_access_token(&self) -> Result<String> {
-        let now = chrono::Utc::now().timestamp();
-        let claims = json!({
-            "iss": self.client_email,
-            "scope": "https://www.googleapis.com/auth/cloud-platform",
-            "aud": "https://oauth2.googleapis.com/token",
-            "exp": now + 3600,
-            "iat": now
-        });
-
-        let header = Header::new(Algorithm::RS256);
-        let encoding_key = EncodingKey::from_rsa_pem(self.private_key.as_bytes())?;
-        let jwt_token = encode(&header, &claims, &encoding_key)?;
-
-        let token_response = self
-            .client
-            .post("https://oauth2.googleapis.com/token")
-            .form(&[
-                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
-                ("assertion", &jwt_token),
-            ])
-            .send()
-            .await?;
+    /// A bucket that already exists is not an error.
+    #[cfg(feature = "test-util")]
+    pub async fn create_bucket(&self, bucket: &str, project: &str) -> Result<()> {
+        let authorization = self.authorization().await?;
+        let mut request = self.client.post(format!(
+            "{}/storage/v1/b?project={}",
+            self.endpoint, project
+        ));
+        if let Some(authorization) = authorization {
+            request = request.header("Authorization", authorization);
+        }
+        let response = request.json(&json!({ "name": bucket })).send().await?;
 
-        if !token_response.status().is_success() {
-            bail!("Token exchange failed: {}", token_response.status());
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::CONFLICT {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            bail!("Failed to create bucket: {} - {}", status, error_text);
         }
 
-        let token_json: serde_json::Value = token_response.json().await?;
-        let access_token = token_json["access_token"]
-            .as_str()
-            .ok_or_else(|| anyhow!("access_token not found in response"))?
-            .to_string();
+        Ok(())
+    }
+
+    /// Delete an object given a `gs://bucket/object` URL.
+    pub async fn delete_object_from_url(&self, gcs_url: &str) -> Result<()> {
+        let authorization = self.authorization().await?;
+        let (bucket, object) = Self::bucket_and_object(gcs_url)?;
+        let api_url = format!(
+            "{}/storage/v1/b/{}/o/{}",
+            self.endpoint,
+            bucket,
+            urlencoding::encode(&object)
+        );
+
+        retry(&self.retry, || {
+            let mut request = self.client.delete(&api_url);
+            if let Some(authorization) = &authorization {
+                request = request.header("Authorization", authorization);
+            }
+            async move {
+                let response = request.send().await?;
 
-        Ok(access_token)
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(classify(
+                        status.as_u16(),
+                        anyhow!("Failed to delete object: {} - {}", status, error_text),
+                    ));
+                }
+
+                Ok(())
+            }
+        })
+        .await
     }
 
-    /// Convert a `gs://bucket/object` style URL into a REST API endpoint.
-    fn convert_to_rest_api_url(&self, source_url: &str) -> Result<String> {
-        let url = Url::parse(source_url)?;
-        let path = url.path().trim_start_matches('/');
-        let parts: Vec<&str> = path.splitn(2, '/').collect();
+    /// Copy an object server-side between two `gs://bucket/object` URLs.
+    pub async fn copy_object_from_url(&self, from_url: &str, to_url: &str) -> Result<()> {
+        let authorization = self.authorization().await?;
+        let (from_bucket, from_object) = Self::bucket_and_object(from_url)?;
+        let (to_bucket, to_object) = Self::bucket_and_object(to_url)?;
+        let api_url = format!(
+            "{}/storage/v1/b/{}/o/{}/copyTo/b/{}/o/{}",
+            self.endpoint,
+            from_bucket,
+            urlencoding::encode(&from_object),
+            to_bucket,
+            urlencoding::encode(&to_object)
+        );
 
-        if parts.len() != 2 {
-            bail!("Invalid GCS URL format");
-        }
+        retry(&self.retry, || {
+            let mut request = self.client.post(&api_url).json(&json!({}));
+            if let Some(authorization) = &authorization {
+                request = request.header("Authorization", authorization);
+            }
+            async move {
+                let response = request.send().await?;
 
-        let bucket = parts[0];
-        let object_path = urlencoding::encode(parts[1]);
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(classify(
+                        status.as_u16(),
+                        anyhow!("Failed to copy object: {} - {}", status, error_text),
+                    ));
+                }
 
-        Ok(format!(
-            "https://storage.googleapis.com/storage/v1/b/{}/o/{}?alt=media",
-            bucket, object_path
-        ))
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    /// Move an object between two `gs://bucket/object` URLs: GCS has no rename, so it is
+    /// copied server-side and the original deleted.
+    pub async fn rename_object_from_url(&self, from_url: &str, to_url: &str) -> Result<()> {
+        self.copy_object_from_url(from_url, to_url).await?;
+        self.delete_object_from_url(from_url).await
     }
 
     /// Upload an object to GCS given a `gs://bucket/object` URL.
+    ///
+    /// Objects over `multipart_threshold_bytes` use a resumable upload.
     pub async fn put_object_from_url(&self, gcs_url: &str, data: &[u8]) -> Result<()> {
-        let access_token = self.generate_access_token().await?;
-        let api_url = self
-            .convert_to_rest_api_url(gcs_url)?
-            .replace("?alt=media", ""); // remove download flag for upload
-
-        let response = self
-            .client
-            .post(&api_url)
-            .header("Authorization", format!("Bearer {}", access_token))
-            .header("Content-Type", "application/octet-stream")
-            .body(data.to_vec())
-            .send()
+        let authorization = self.authorization().await?;
+        let (bucket, object) = Self::bucket_and_object(gcs_url)?;
+        if self.upload.is_multipart(data.len()) {
+            return self
+                .put_resumable(&bucket, &object, authorization.as_deref(), data)
+                .await;
+        }
+        let api_url = format!(
+            "{}/upload/storage/v1/b/{}/o?uploadType=media&name={}",
+            self.endpoint,
+            bucket,
+            urlencoding::encode(&object)
+        );
+
+        retry(&self.retry, || {
+            let mut request = self.client.post(&api_url);
+            if let Some(authorization) = &authorization {
+                request = request.header("Authorization", authorization);
+            }
+            let request = request
+                .header("Content-Type", content_type(&object))
+                .body(data.to_vec());
+            async move {
+                let response = request.send().await?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(classify(
+                        status.as_u16(),
+                        anyhow!("Failed to upload object: {} - {}", status, error_text),
+                    ));
+                }
+
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    /// Upload `data` in chunks through a resumable upload session.
+    async fn put_resumable(
+        &self,
+        bucket: &str,
+        object: &str,
+        authorization: Option<&str>,
+        data: &[u8],
+    ) -> Result<()> {
+        let api_url = format!(
+            "{}/upload/storage/v1/b/{}/o?uploadType=resumable&name={}",
+            self.endpoint,
+            bucket,
+            urlencoding::encode(object)
+        );
+        let session_url = retry(&self.retry, || {
+            let mut request = self.client.post(&api_url);
+            if let Some(authorization) = authorization {
+                request = request.header("Authorization", authorization);
+            }
+            let request = request
+                .header("X-Upload-Content-Length", data.len())
+                .header("X-Upload-Content-Type", content_type(object))
+                .body(Vec::new());
+            async move {
+                let response = request.send().await?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(classify(
+                        status.as_u16(),
+                        anyhow!("Failed to start upload: {} - {}", status, error_text),
+                    ));
+                }
+
+                response
+                    .headers()
+                    .get("Location")
+                    .and_then(|location| location.to_str().ok())
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow!("GCS returned no resumable upload session"))
+            }
+        })
+        .await?;
+
+        let mut start = 0;
+        for chunk in self.upload.parts(data, CHUNK_ALIGN, CHUNK_ALIGN) {
+            let end = start + chunk.len();
+            let content_range = format!("bytes {}-{}/{}", start, end - 1, data.len());
+            retry(&self.retry, || {
+                let request = self
+                    .client
+                    .put(&session_url)
+                    .header("Content-Range", content_range.as_str())
+                    .body(chunk.to_vec());
+                async move {
+                    let response = request.send().await?;
+
+                    // 308 Resume Incomplete acknowledges an intermediate chunk.
+                    let status = response.status();
+                    if !status.is_success() && status.as_u16() != 308 {
+                        let error_text = response.text().await.unwrap_or_default();
+                        return Err(classify(
+                            status.as_u16(),
+                            anyhow!("Failed to upload chunk: {} - {}", status, error_text),
+                        ));
+                    }
+
+                    Ok(())
+                }
+            })
             .await?;
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    /// Fetch an object with ranged resumption and optional throttling.
+    pub async fn fetch_ranged(&self, source: &str, options: &DownloadOptions) -> Result<Download> {
+        let authorization = self.authorization().await?;
+        let api_url = self.convert_to_rest_api_url(source)?;
+
+        download_with_resume(options, |offset| {
+            self.open(&api_url, authorization.as_deref(), offset)
+        })
+        .await
+    }
+
+    /// Request an object's media from byte `offset` onwards.
+    async fn open(
+        &self,
+        api_url: &str,
+        authorization: Option<&str>,
+        offset: u64,
+    ) -> Result<RangedBody> {
+        let mut request = self.client.get(api_url);
+        if let Some(authorization) = authorization {
+            request = request.header("Authorization", authorization);
+        }
+        if offset > 0 {
+            request = request.header("Range", format!("bytes={}-", offset));
+        }
+        let response = request.send().await?;
 
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            bail!("Failed to upload object: {} - {}", status, error_text);
+            return Err(classify(
+                status.as_u16(),
+                anyhow!("Failed to fetch object: {} - {}", status, error_text),
+            ));
         }
 
-        Ok(())
+        let honors_range = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        Ok(RangedBody {
+            stream: Box::new(response),
+            honors_range,
+        })
     }
 }
 
 #[async_trait]
 impl Connector for GCSConnector {
-    /// Fetch an object from GCS and return it as a `Read` stream.
-    async fn fetch(&self, source: &str) -> Result<Box<dyn Read>> {
-        let access_token = self.generate_access_token().await?;
+    /// Stream an object from GCS; only the initial request is retried.
+    async fn fetch(&self, source: &str) -> Result<ObjectReader> {
+        let authorization = self.authorization().await?;
         let api_url = self.convert_to_rest_api_url(source)?;
+        let body = retry(&self.retry, || {
+            self.open(&api_url, authorization.as_deref(), 0)
+        })
+        .await?;
+        Ok(Box::new(ChunkReader::new(body.stream)))
+    }
 
-        let response = self
-            .client
-            .get(&api_url)
-            .header("Authorization", format!("Bearer {}", access_token))
-            .send()
-            .await?;
+    /// List objects under a `gs://bucket/prefix` URL.
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let authorization = self.authorization().await?;
+        let (bucket, object_prefix) = Self::bucket_and_prefix(prefix)?;
 
-        if response.status().is_success() {
-            let data = response.bytes().await?;
-            Ok(Box::new(std::io::Cursor::new(data.to_vec())))
-        } else {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            bail!("Failed to fetch object: {} - {}", status, error_text);
+        let mut objects = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut api_url = format!(
+                "{}/storage/v1/b/{}/o?prefix={}",
+                self.endpoint,
+                bucket,
+                urlencoding::encode(&object_prefix)
+            );
+            if let Some(token) = &page_token {
+                api_url.push_str(&format!("&pageToken={}", urlencoding::encode(token)));
+            }
+            let page: serde_json::Value = retry(&self.retry, || {
+                let mut request = self.client.get(&api_url);
+                if let Some(authorization) = &authorization {
+                    request = request.header("Authorization", authorization);
+                }
+                async move {
+                    let response = request.send().await?;
+
+                    if !response.status().is_success() {
+                        let status = response.status();
+                        let error_text = response.text().await.unwrap_or_default();
+                        return Err(classify(
+                            status.as_u16(),
+                            anyhow!("Failed to list objects: {} - {}", status, error_text),
+                        ));
+                    }
+
+                    Ok(response.json().await?)
+                }
+            })
+            .await?;
+            for item in page["items"].as_array().into_iter().flatten() {
+                if let Some(name) = item["name"].as_str() {
+                    objects.push(ObjectInfo {
+                        location: format!("gs://{}/{}", bucket, name),
+                        // The JSON API reports sizes as strings.
+                        size: item["size"]
+                            .as_str()
+                            .and_then(|s| s.parse().ok())
+                            .unwrap_or(0),
+                    });
+                }
+            }
+            page_token = page["nextPageToken"].as_str().map(str::to_string);
+            if page_token.is_none() {
+                break;
+            }
         }
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        Ok(objects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_credentials_files() {
+        let user = GcsCredential::from_json(
+            r#"{"type": "authorized_user", "client_id": "c", "client_secret": "s", "refresh_token": "r"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            user,
+            GcsCredential::AuthorizedUser {
+                client_id: "c".to_string(),
+                client_secret: "s".to_string(),
+                refresh_token: "r".to_string(),
+            }
+        );
+
+        let key = GcsCredential::from_json(
+            r#"{"type": "service_account", "client_email": "a@p.iam.gserviceaccount.com", "private_key": "k"}"#,
+        )
+        .unwrap();
+        assert!(matches!(key, GcsCredential::ServiceAccount { .. }));
+
+        let err = GcsCredential::from_json(r#"{"type": "service_account", "private_key": "k"}"#);
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "Missing client_email in credentials JSON"
+        );
+        assert!(GcsCredential::from_json(r#"{"type": "external_account"}"#).is_err());
+    }
+
+    #[test]
+    fn profile_without_credentials_has_none() {
+        let profile = toml::from_str::<Profile>(r#"provider = "gcs""#).unwrap();
+        assert_eq!(GcsCredential::from_profile(&profile).unwrap(), None);
     }
 }
@@ -0,0 +1,301 @@
+//! Kafka topic connector.
+//!
+//! Reads a batch of messages from a topic so a streaming pipeline can be
+//! spot-checked with the same contracts used for files. Messages are read
+//! through a Kafka REST Proxy (Confluent REST Proxy API v2): each fetch
+//! creates a temporary consumer instance, subscribes it to the topic, polls
+//! until `max_messages` messages have arrived or `window_secs` have passed,
+//! and deletes the instance. Offsets are never committed, so the pipeline's
+//! own consumers are unaffected.
+//!
+//! Message values must be JSON objects. They are returned as
+//! newline-delimited JSON, one message per line, and loaded with the JSON
+//! driver.
+//!
+//! Locations have the form `kafka://<topic>`.
+//!
+//! ## Profile fields used
+//! - `endpoint` (REST Proxy URL, e.g. `http://localhost:8082`)
+//! - `consumer_group` (optional, default: `pipa`)
+//! - `retry_attempts` / `retry_backoff_ms` / `retry_max_backoff_ms` (see `connectors::retry`)
+//!
+//! ## Source options used
+//! - `max_messages` (optional, default: 1000)
+//! - `window_secs` (optional, default: 30)
+//! - `from_beginning` (optional, default: false; read the oldest retained
+//!   messages instead of waiting for new ones)
+
+use crate::connectors::retry::{RetryPolicy, classify, retry};
+use crate::connectors::{Connector, ObjectInfo, ObjectReader};
+use crate::contracts::SourceOptions;
+use crate::profiles::Profile;
+use anyhow::{Result, anyhow, bail};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+/// Default number of messages to read.
+const DEFAULT_MAX_MESSAGES: usize = 1000;
+
+/// Default time to wait for messages.
+const DEFAULT_WINDOW: Duration = Duration::from_secs(30);
+
+/// Default consumer group for the temporary consumer instances.
+const DEFAULT_CONSUMER_GROUP: &str = "pipa";
+
+/// How long one poll waits for records before returning.
+const POLL_TIMEOUT_MS: u64 = 1000;
+
+/// Content type of REST Proxy v2 request bodies.
+const CONTENT_TYPE: &str = "application/vnd.kafka.v2+json";
+
+/// Accept header for JSON-embedded records.
+const ACCEPT_JSON_RECORDS: &str = "application/vnd.kafka.json.v2+json";
+
+/// How much of a topic to read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumeLimits {
+    pub max_messages: usize,
+    pub window: Duration,
+    pub from_beginning: bool,
+}
+
+impl Default for ConsumeLimits {
+    fn default() -> Self {
+        Self {
+            max_messages: DEFAULT_MAX_MESSAGES,
+            window: DEFAULT_WINDOW,
+            from_beginning: false,
+        }
+    }
+}
+
+impl ConsumeLimits {
+    /// Read limits from `[source.options]`, falling back to defaults.
+    pub fn from_options(options: &SourceOptions) -> Self {
+        Self {
+            max_messages: options
+                .max_messages
+                .filter(|max| *max > 0)
+                .unwrap_or(DEFAULT_MAX_MESSAGES),
+            window: options
+                .window_secs
+                .map_or(DEFAULT_WINDOW, Duration::from_secs),
+            from_beginning: options.from_beginning.unwrap_or(false),
+        }
+    }
+}
+
+/// Concrete connector for Kafka topics behind a REST Proxy.
+pub struct KafkaConnector {
+    endpoint: String,
+    consumer_group: String,
+    limits: ConsumeLimits,
+    client: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+impl KafkaConnector {
+    /// Build a Kafka connector from a profile and the source's read limits.
+    ///
+    /// Expects `endpoint` (the REST Proxy URL) in the profile.
+    pub fn from_profile(profile: &Profile, limits: ConsumeLimits) -> Result<Self> {
+        let endpoint = profile
+            .endpoint
+            .as_deref()
+            .ok_or_else(|| anyhow!("Kafka profile missing endpoint (REST Proxy URL)"))?;
+
+        Ok(KafkaConnector {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            consumer_group: profile
+                .consumer_group
+                .clone()
+                .unwrap_or_else(|| DEFAULT_CONSUMER_GROUP.to_string()),
+            limits,
+            client: reqwest::Client::new(),
+            retry: RetryPolicy::from_profile(profile),
+        })
+    }
+
+    /// Extract the topic from a `kafka://<topic>` location.
+    pub fn topic(location: &str) -> Result<&str> {
+        let topic = location
+            .strip_prefix("kafka://")
+            .ok_or_else(|| anyhow!("Kafka location must start with kafka://"))?
+            .trim_end_matches('/');
+        if topic.is_empty() || topic.contains('/') {
+            bail!("Kafka location must be kafka://<topic>, got '{}'", location);
+        }
+        Ok(topic)
+    }
+
+    /// Read up to `max_messages` message values from `topic`.
+    pub async fn consume(&self, topic: &str) -> Result<Vec<Value>> {
+        let base_uri = self.create_consumer().await?;
+        let consumed = self.poll(&base_uri, topic).await;
+
+        // Always remove the consumer instance, even if polling failed; one
+        // left behind expires on the proxy after its idle timeout.
+        let _ = self
+            .client
+            .delete(&base_uri)
+            .header("Content-Type", CONTENT_TYPE)
+            .send()
+            .await;
+        consumed
+    }
+
+    /// Create a consumer instance; returns its base URI.
+    async fn create_consumer(&self) -> Result<String> {
+        let url = format!("{}/consumers/{}", self.endpoint, self.consumer_group);
+        let name = format!(
+            "pipa-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        );
+        let body = json!({
+            "name": name,
+            "format": "json",
+            "auto.offset.reset": if self.limits.from_beginning { "earliest" } else { "latest" },
+            "auto.commit.enable": "false",
+        });
+        let created = self.send_json(self.client.post(&url).json(&body)).await?;
+        created["base_uri"]
+            .as_str()
+            .map(|uri| uri.trim_end_matches('/').to_string())
+            .ok_or_else(|| anyhow!("base_uri not found in consumer response"))
+    }
+
+    /// Subscribe the consumer to `topic` and poll until a limit is reached.
+    async fn poll(&self, base_uri: &str, topic: &str) -> Result<Vec<Value>> {
+        let subscription = json!({ "topics": [topic] });
+        self.send_json(
+            self.client
+                .post(format!("{}/subscription", base_uri))
+                .json(&subscription),
+        )
+        .await?;
+
+        let records_url = format!("{}/records?timeout={}", base_uri, POLL_TIMEOUT_MS);
+        let started = Instant::now();
+        let mut messages = Vec::new();
+        while messages.len() < self.limits.max_messages && started.elapsed() < self.limits.window {
+            let records = self
+                .send_json(
+                    self.client
+                        .get(&records_url)
+                        .header("Accept", ACCEPT_JSON_RECORDS),
+                )
+                .await?;
+            for record in records.as_array().into_iter().flatten() {
+                messages.push(message_value(record)?);
+            }
+        }
+        messages.truncate(self.limits.max_messages);
+        Ok(messages)
+    }
+
+    /// Send a REST Proxy request with retries and decode its JSON response.
+    async fn send_json(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        retry(&self.retry, || {
+            let request = request
+                .try_clone()
+                .map(|r| r.header("Content-Type", CONTENT_TYPE));
+            async move {
+                let response = request
+                    .ok_or_else(|| anyhow!("Kafka request can't be retried"))?
+                    .send()
+                    .await?;
+                let status = response.status();
+                if !status.is_success() {
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(classify(
+                        status.as_u16(),
+                        anyhow!(
+                            "Kafka REST Proxy request failed: {} - {}",
+                            status,
+                            error_text
+                        ),
+                    ));
+                }
+                if status == reqwest::StatusCode::NO_CONTENT {
+                    return Ok(Value::Null);
+                }
+                Ok(response.json().await?)
+            }
+        })
+        .await
+    }
+}
+
+/// The JSON value of a consumed record, which must be an object.
+fn message_value(record: &Value) -> Result<Value> {
+    match &record["value"] {
+        value @ Value::Object(_) => Ok(value.clone()),
+        _ => bail!(
+            "Kafka message at partition {} offset {} is not a JSON object",
+            record["partition"],
+            record["offset"]
+        ),
+    }
+}
+
+/// Render message values as newline-delimited JSON.
+fn to_ndjson(messages: &[Value]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for message in messages {
+        out.extend_from_slice(message.to_string().as_bytes());
+        out.push(b'\n');
+    }
+    out
+}
+
+#[async_trait]
+impl Connector for KafkaConnector {
+    /// Read a batch of messages from a `kafka://<topic>` location as
+    /// newline-delimited JSON.
+    async fn fetch(&self, location: &str) -> Result<ObjectReader> {
+        let messages = self.consume(Self::topic(location)?).await?;
+        Ok(Box::new(Cursor::new(to_ndjson(&messages))))
+    }
+
+    /// Topics aren't objects and can't be listed.
+    async fn list(&self, _prefix: &str) -> Result<Vec<ObjectInfo>> {
+        bail!("Kafka sources can't be listed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_topics_and_records() {
+        assert_eq!(KafkaConnector::topic("kafka://orders").unwrap(), "orders");
+        assert_eq!(KafkaConnector::topic("kafka://orders/").unwrap(), "orders");
+        assert!(KafkaConnector::topic("kafka://").is_err());
+        assert!(KafkaConnector::topic("s3://bucket/orders").is_err());
+
+        let records = json!([
+            { "topic": "orders", "partition": 0, "offset": 7, "value": { "id": 1 } },
+            { "topic": "orders", "partition": 1, "offset": 3, "value": { "id": 2, "note": "a\nb" } },
+        ]);
+        let messages: Vec<Value> = records
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|record| message_value(record).unwrap())
+            .collect();
+        assert_eq!(
+            String::from_utf8(to_ndjson(&messages)).unwrap(),
+            "{\"id\":1}\n{\"id\":2,\"note\":\"a\\nb\"}\n"
+        );
+
+        let err = message_value(&json!({ "partition": 2, "offset": 9, "value": "text" }));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "Kafka message at partition 2 offset 9 is not a JSON object"
+        );
+    }
+}
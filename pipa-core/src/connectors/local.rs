@@ -1,7 +1,7 @@
-use super::Connector;
+use super::{Connector, ObjectInfo, ObjectReader};
 use anyhow::Result;
-use std::fs::File;
-use std::io::Read;
+use std::fs;
+use std::path::Path;
 
 pub struct LocalConnector;
 
@@ -13,7 +13,42 @@ impl LocalConnector {
 
 #[async_trait::async_trait]
 impl Connector for LocalConnector {
-    async fn fetch(&self, path: &str) -> Result<Box<dyn Read>> {
-        Ok(Box::new(File::open(path)?))
+    async fn fetch(&self, path: &str) -> Result<ObjectReader> {
+        Ok(Box::new(tokio::fs::File::open(path).await?))
+    }
+
+    /// Files directly inside the directory `prefix` (when it ends with `/`
+    /// or is a directory), or beside it whose path starts with `prefix`.
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let (dir, stem) = if prefix.ends_with('/') || Path::new(prefix).is_dir() {
+            (prefix.trim_end_matches('/'), "")
+        } else {
+            match prefix.rsplit_once('/') {
+                Some((dir, stem)) => (dir, stem),
+                None => (".", prefix),
+            }
+        };
+        let dir = if dir.is_empty() { "/" } else { dir };
+
+        let mut objects = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !metadata.is_file() || !name.starts_with(stem) {
+                continue;
+            }
+            let location = if dir == "." && !prefix.starts_with("./") {
+                name
+            } else {
+                format!("{}/{}", dir.trim_end_matches('/'), name)
+            };
+            objects.push(ObjectInfo {
+                location,
+                size: metadata.len(),
+            });
+        }
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        Ok(objects)
     }
 }
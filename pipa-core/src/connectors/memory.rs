@@ -0,0 +1,194 @@
+//! In-memory and failing connectors for tests (`test-util` feature).
+//!
+//! - `MemoryConnector` serves objects from a map keyed by location. The
+//!   process-wide store behind `MemoryConnector::shared()` also backs the
+//!   `memory` source type, so contracts can run end-to-end without a real
+//!   backend:
+//!   ```toml
+//!   [source]
+//!   type = "memory"
+//!   location = "orders.csv"
+//!   ```
+//! - `FailingConnector` always fails with a fixed message, for error paths.
+
+use crate::connectors::{Connector, ObjectInfo, ObjectReader};
+use anyhow::{Result, anyhow, bail};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::{Arc, RwLock};
+
+type Objects = Arc<RwLock<HashMap<String, Vec<u8>>>>;
+
+/// Store behind `MemoryConnector::shared()` and the `memory` source type.
+static SHARED: Lazy<Objects> = Lazy::new(Default::default);
+
+/// Connector serving objects from memory.
+#[derive(Clone, Default)]
+pub struct MemoryConnector {
+    objects: Objects,
+}
+
+impl MemoryConnector {
+    /// A connector with its own, empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A handle on the process-wide store used by `type = "memory"` sources.
+    pub fn shared() -> Self {
+        Self {
+            objects: Arc::clone(&SHARED),
+        }
+    }
+
+    /// Store `data` under `location`, replacing any previous object.
+    pub fn insert(&self, location: &str, data: impl Into<Vec<u8>>) {
+        self.objects
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(location.to_string(), data.into());
+    }
+
+    /// The object stored under `location`.
+    pub fn get(&self, location: &str) -> Option<Vec<u8>> {
+        self.objects
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(location)
+            .cloned()
+    }
+
+    /// Remove the object stored under `location`, returning it.
+    pub fn remove(&self, location: &str) -> Option<Vec<u8>> {
+        self.objects
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(location)
+    }
+
+    /// All stored locations, sorted.
+    pub fn locations(&self) -> Vec<String> {
+        let mut locations: Vec<String> = self
+            .objects
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        locations.sort();
+        locations
+    }
+}
+
+#[async_trait]
+impl Connector for MemoryConnector {
+    async fn fetch(&self, source: &str) -> Result<ObjectReader> {
+        let data = self
+            .get(source)
+            .ok_or_else(|| anyhow!("No in-memory object at '{}'", source))?;
+        Ok(Box::new(Cursor::new(data)))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let mut objects: Vec<ObjectInfo> = self
+            .objects
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(location, _)| location.starts_with(prefix))
+            .map(|(location, data)| ObjectInfo {
+                location: location.clone(),
+                size: data.len() as u64,
+            })
+            .collect();
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        Ok(objects)
+    }
+}
+
+/// Connector whose every fetch fails with `message`.
+pub struct FailingConnector {
+    pub message: String,
+}
+
+impl FailingConnector {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Connector for FailingConnector {
+    async fn fetch(&self, _source: &str) -> Result<ObjectReader> {
+        bail!("{}", self.message)
+    }
+
+    async fn list(&self, _prefix: &str) -> Result<Vec<ObjectInfo>> {
+        bail!("{}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::fetch::fetch_data_from_source;
+    use crate::contracts::schema::Source;
+    use crate::drivers::get_driver;
+    use crate::profiles::Profiles;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn serves_inserted_objects() {
+        let connector = MemoryConnector::new();
+        connector.insert("a.csv", "id\n1\n");
+        let mut out = String::new();
+        connector
+            .fetch("a.csv")
+            .await
+            .unwrap()
+            .read_to_string(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(out, "id\n1\n");
+        assert!(connector.fetch("missing.csv").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failing_connector_reports_message() {
+        let err = FailingConnector::new("boom")
+            .fetch("anything")
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "boom");
+    }
+
+    #[tokio::test]
+    async fn memory_source_type_uses_shared_store() {
+        MemoryConnector::shared().insert("memory-test/orders.csv", "id,amount\n1,10\n2,12\n");
+        let source = Source {
+            r#type: "memory".to_string(),
+            location: Some("memory-test/orders.csv".to_string()),
+            profile: None,
+            format: None,
+            options: Default::default(),
+            csv: Default::default(),
+            on_success: Default::default(),
+            archive_location: None,
+            on_duplicate: Default::default(),
+            archive_member: None,
+            archive_mode: Default::default(),
+        };
+
+        let fetched = fetch_data_from_source(&source, &Profiles::new())
+            .await
+            .unwrap();
+        let driver = get_driver("csv", &source.options, &source.csv, &[]).unwrap();
+        let df = fetched.payload.load(driver.as_ref()).unwrap();
+        assert_eq!(df.shape(), (2, 2));
+    }
+}
@@ -0,0 +1,175 @@
+//! Retries with exponential backoff for cloud connectors.
+//!
+//! S3, Azure, and GCS requests (listing, uploads, and the ranged requests
+//! behind `download_with_resume`) go through a `RetryPolicy`: a failed
+//! request is retried after a backoff that doubles on every attempt, up to
+//! a cap. Failures retrying cannot fix — 4xx responses other than
+//! `408 Request Timeout` and `429 Too Many Requests` — are marked
+//! `Permanent` and returned at once.
+//!
+//! ## Profile fields used
+//! - `retry_attempts` (optional, default: 3; total tries per request)
+//! - `retry_backoff_ms` (optional, default: 200; delay before the first retry)
+//! - `retry_max_backoff_ms` (optional, default: 10000)
+
+use crate::profiles::Profile;
+use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// Default number of tries per request.
+const DEFAULT_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry.
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Default upper bound on the delay between retries.
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// How often and how patiently to retry a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total tries per request, including the first (at least 1).
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_ATTEMPTS,
+            initial_backoff: DEFAULT_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Read retry settings from a profile, falling back to defaults.
+    pub fn from_profile(profile: &Profile) -> Self {
+        Self {
+            max_attempts: profile.retry_attempts.unwrap_or(DEFAULT_ATTEMPTS).max(1),
+            initial_backoff: profile
+                .retry_backoff_ms
+                .map_or(DEFAULT_BACKOFF, Duration::from_millis),
+            max_backoff: profile
+                .retry_max_backoff_ms
+                .map_or(DEFAULT_MAX_BACKOFF, Duration::from_millis),
+        }
+    }
+
+    /// Delay before retry number `retry` (1 for the first retry).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// A failure that retrying cannot fix, such as a `404 Not Found`.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct Permanent(pub anyhow::Error);
+
+/// Whether `error` was marked `Permanent`.
+pub fn is_permanent(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<Permanent>().is_some()
+}
+
+/// Mark `error` `Permanent` if an HTTP `status` says retrying is pointless.
+pub fn classify(status: u16, error: anyhow::Error) -> anyhow::Error {
+    let client_error = (400..500).contains(&status);
+    if client_error && status != 408 && status != 429 {
+        Permanent(error).into()
+    } else {
+        error
+    }
+}
+
+/// Run `op` until it succeeds, fails permanently, or runs out of attempts,
+/// sleeping `policy.backoff(n)` before retry `n`.
+pub async fn retry<T, F, Fut>(policy: &RetryPolicy, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= policy.max_attempts || is_permanent(&e) => return Err(e),
+            Err(_) => {
+                tokio::time::sleep(policy.backoff(attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::cell::Cell;
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = policy(10);
+        let delays: Vec<u64> = (1..=5)
+            .map(|retry| policy.backoff(retry).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, [1, 2, 4, 4, 4]);
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures() {
+        let calls = Cell::new(0);
+        let result = retry(&policy(3), || {
+            calls.set(calls.get() + 1);
+            let call = calls.get();
+            async move {
+                if call < 3 {
+                    Err(classify(503, anyhow!("unavailable")))
+                } else {
+                    Ok(call)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+
+        calls.set(0);
+        let result: Result<()> = retry(&policy(2), || {
+            calls.set(calls.get() + 1);
+            async { Err(anyhow!("connection reset")) }
+        })
+        .await;
+        assert_eq!(result.unwrap_err().to_string(), "connection reset");
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn gives_up_at_once_on_permanent_failures() {
+        let calls = Cell::new(0);
+        let result: Result<()> = retry(&policy(5), || {
+            calls.set(calls.get() + 1);
+            async { Err(classify(404, anyhow!("not found"))) }
+        })
+        .await;
+        let err = result.unwrap_err();
+        assert!(is_permanent(&err));
+        assert_eq!(err.to_string(), "not found");
+        assert_eq!(calls.get(), 1);
+        assert!(!is_permanent(&classify(429, anyhow!("slow down"))));
+    }
+}
@@ -7,8 +7,9 @@
 //! ## Responsibilities
 //! - Construct an `S3Client` from a `Profile` and S3 URL.
 //! - Support both virtual-hosted and path-style addressing.
-//! - Upload (`put_object_from_url`) and fetch (`fetch`) objects.
-//! - List objects under a given prefix.
+//! - Upload (`put_object_from_url`) objects, and stream (`fetch`) or
+//!   download (`fetch_ranged`) them.
+//! - List objects under a given prefix (`list`).
 //!
 //! ## Expected URL format
 //! - `s3://bucket/key`
@@ -17,28 +18,137 @@
 //! - `region` (default: `us-east-1`)
 //! - `endpoint` (optional, for custom endpoints / MinIO)
 //! - `access_key` / `secret_key` (optional, overrides default credentials)
+//! - `session_token` (optional, for temporary `access_key` / `secret_key`)
+//! - `role_arn` (optional, assume this role with STS; authorized by the
+//!   credentials above or the default chain, and refreshed before expiry)
+//! - `external_id` / `role_session_name` (optional, for `role_arn`; session
+//!   name default: `pipa`)
 //! - `path_style` (optional, forces path-style addressing)
+//! - `max_download_bytes_per_sec` / `resume_attempts` (see `connectors::download`)
+//! - `retry_attempts` / `retry_backoff_ms` / `retry_max_backoff_ms` (see
+//!   `connectors::retry`; the SDK's own retries are disabled in favor of these)
+//! - `multipart_threshold_bytes` / `multipart_part_bytes` (see `connectors::upload`)
 
-use super::Connector;
+use super::download::{ChunkReader, Download, DownloadOptions, RangedBody, download_with_resume};
+use super::retry::{RetryPolicy, classify, retry};
+use super::upload::{UploadOptions, content_type};
+use super::{Connector, ObjectInfo, ObjectReader};
 use crate::profiles::Profile;
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Result, anyhow};
 use aws_config::BehaviorVersion;
+use aws_config::SdkConfig;
+use aws_config::sts::AssumeRoleProvider;
 use aws_sdk_s3::Client as S3Client;
-use aws_sdk_s3::config::Credentials;
-use std::io::{Cursor, Read};
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::config::retry::RetryConfig;
+use aws_sdk_s3::config::{Credentials, SharedCredentialsProvider};
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+
+/// Smallest part S3 accepts in a multipart upload (except the last).
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Default STS session name when assuming `role_arn`.
+const DEFAULT_ROLE_SESSION_NAME: &str = "pipa";
+
+/// Build the S3 client configuration for a profile: region, endpoint,
+/// credentials (static, session, or assumed role), and path-style.
+///
+/// The endpoint applies to S3 only, so STS calls for `role_arn` still go
+/// to AWS when `endpoint` points at MinIO or another S3-compatible store.
+pub(crate) async fn client_config(profile: &Profile) -> aws_sdk_s3::config::Builder {
+    let region = profile
+        .region
+        .clone()
+        .unwrap_or_else(|| "us-east-1".to_string());
+
+    // Build base AWS config
+    let base_config = aws_config::defaults(BehaviorVersion::latest())
+        .region(aws_config::Region::new(region))
+        .load()
+        .await;
+    let mut s3_config = aws_sdk_s3::config::Builder::from(&base_config);
+
+    if let Some(endpoint) = &profile.endpoint {
+        s3_config = s3_config.endpoint_url(endpoint);
+    }
+
+    // Override credentials if explicitly provided in profile
+    if let Some(provider) = profile_credentials(profile, &base_config).await {
+        s3_config = s3_config.credentials_provider(provider);
+    }
+
+    // Force path-style if requested
+    if profile.path_style.unwrap_or(false) {
+        s3_config = s3_config.force_path_style(true);
+    }
+
+    s3_config
+}
+
+/// Credentials a profile sets explicitly (static or session keys, and
+/// `role_arn` to assume with them), or `None` to use `base_config`'s
+/// default chain.
+pub(crate) async fn profile_credentials(
+    profile: &Profile,
+    base_config: &SdkConfig,
+) -> Option<SharedCredentialsProvider> {
+    let static_credentials = match (&profile.access_key, &profile.secret_key) {
+        (Some(access_key), Some(secret_key))
+            if !access_key.is_empty() && !secret_key.is_empty() =>
+        {
+            Some(Credentials::new(
+                access_key.clone(),
+                secret_key.clone(),
+                profile
+                    .session_token
+                    .clone()
+                    .filter(|token| !token.is_empty()),
+                None,
+                "profile",
+            ))
+        }
+        _ => None,
+    };
+
+    match profile.role_arn.as_deref().filter(|arn| !arn.is_empty()) {
+        Some(role_arn) => {
+            let mut role = AssumeRoleProvider::builder(role_arn)
+                .session_name(
+                    profile
+                        .role_session_name
+                        .as_deref()
+                        .unwrap_or(DEFAULT_ROLE_SESSION_NAME),
+                )
+                .configure(base_config);
+            if let Some(external_id) = &profile.external_id {
+                role = role.external_id(external_id);
+            }
+            let provider = match static_credentials {
+                Some(credentials) => role.build_from_provider(credentials).await,
+                None => role.build().await,
+            };
+            Some(SharedCredentialsProvider::new(provider))
+        }
+        None => static_credentials.map(SharedCredentialsProvider::new),
+    }
+}
 
 /// Concrete connector for S3.
 pub struct S3Connector {
     client: S3Client,
     bucket: String,
+    retry: RetryPolicy,
+    upload: UploadOptions,
 }
 
 impl S3Connector {
     /// Build an `S3Connector` from a profile and URL.
     ///
     /// - Extracts bucket name from the URL.
-    /// - Loads AWS config (region, endpoint, credentials).
-    /// - Applies path-style if requested.
+    /// - Loads AWS config (region, endpoint, credentials, path-style; see
+    ///   `client_config`).
     pub async fn from_profile_and_url(profile: &Profile, url: &url::Url) -> Result<Self> {
         // Expect s3://bucket/key style URLs
         let bucket = url
@@ -46,47 +156,108 @@ impl S3Connector {
             .ok_or_else(|| anyhow!("Invalid S3 URL: missing bucket name"))?
             .to_string();
 
-        let region = profile
-            .region
-            .clone()
-            .unwrap_or_else(|| "us-east-1".to_string());
+        let s3_config = client_config(profile)
+            .await
+            .retry_config(RetryConfig::disabled());
+        let client = S3Client::from_conf(s3_config.build());
 
-        // Build base AWS config
-        let mut config_loader =
-            aws_config::defaults(BehaviorVersion::latest()).region(aws_config::Region::new(region));
+        Ok(S3Connector {
+            client,
+            bucket,
+            retry: RetryPolicy::from_profile(profile),
+            upload: UploadOptions::from_profile(profile),
+        })
+    }
 
-        if let Some(endpoint) = &profile.endpoint {
-            config_loader = config_loader.endpoint_url(endpoint);
+    /// Create the connector's bucket (used to seed emulators in integration
+    /// tests). A bucket that already exists is not an error.
+    #[cfg(feature = "test-util")]
+    pub async fn create_bucket(&self) -> Result<()> {
+        match self
+            .client
+            .create_bucket()
+            .bucket(&self.bucket)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e)
+                if e.as_service_error().is_some_and(|e| {
+                    e.is_bucket_already_owned_by_you() || e.is_bucket_already_exists()
+                }) =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(anyhow::Error::new(e).context("Failed to create S3 bucket")),
         }
+    }
 
-        let base_config = config_loader.load().await;
-        let mut s3_config = aws_sdk_s3::config::Builder::from(&base_config);
-
-        // Override credentials if explicitly provided in profile
-        if let (Some(access_key), Some(secret_key)) = (&profile.access_key, &profile.secret_key) {
-            if !access_key.is_empty() && !secret_key.is_empty() {
-                let creds = Credentials::new(
-                    access_key.clone(),
-                    secret_key.clone(),
-                    None,
-                    None,
-                    "profile",
-                );
-                s3_config = s3_config.credentials_provider(creds);
+    /// Delete an object given a full `s3://bucket/key` URL.
+    pub async fn delete_object_from_url(&self, s3_url: &str) -> Result<()> {
+        let url = url::Url::parse(s3_url)?;
+        let bucket = url
+            .host_str()
+            .ok_or_else(|| anyhow!("Invalid S3 URL: missing bucket"))?;
+        let key = url.path().trim_start_matches('/');
+
+        retry(&self.retry, || {
+            let request = self.client.delete_object().bucket(bucket).key(key);
+            async move {
+                request
+                    .send()
+                    .await
+                    .map_err(|e| sdk_error(e, "Failed to delete S3 object"))?;
+                Ok(())
             }
-        }
+        })
+        .await
+    }
 
-        // Force path-style if requested
-        if profile.path_style.unwrap_or(false) {
-            s3_config = s3_config.force_path_style(true);
-        }
+    /// Copy an object server-side between two `s3://bucket/key` URLs
+    /// reachable with this connector's credentials.
+    pub async fn copy_object_from_url(&self, from_url: &str, to_url: &str) -> Result<()> {
+        let (from, to) = (url::Url::parse(from_url)?, url::Url::parse(to_url)?);
+        let from_bucket = from
+            .host_str()
+            .ok_or_else(|| anyhow!("Invalid S3 URL: missing bucket"))?;
+        let to_bucket = to
+            .host_str()
+            .ok_or_else(|| anyhow!("Invalid S3 URL: missing bucket"))?;
+        let copy_source = format!(
+            "{}/{}",
+            from_bucket,
+            urlencoding::encode(from.path().trim_start_matches('/'))
+        );
+        let key = to.path().trim_start_matches('/');
 
-        let client = S3Client::from_conf(s3_config.build());
+        retry(&self.retry, || {
+            let request = self
+                .client
+                .copy_object()
+                .copy_source(&copy_source)
+                .bucket(to_bucket)
+                .key(key);
+            async move {
+                request
+                    .send()
+                    .await
+                    .map_err(|e| sdk_error(e, "Failed to copy S3 object"))?;
+                Ok(())
+            }
+        })
+        .await
+    }
 
-        Ok(S3Connector { client, bucket })
+    /// Move an object between two `s3://bucket/key` URLs: S3 has no rename, so it is
+    /// copied server-side and the original deleted.
+    pub async fn rename_object_from_url(&self, from_url: &str, to_url: &str) -> Result<()> {
+        self.copy_object_from_url(from_url, to_url).await?;
+        self.delete_object_from_url(from_url).await
     }
 
     /// Upload an object to S3 given a full `s3://bucket/key` URL.
+    ///
+    /// Objects over `multipart_threshold_bytes` use multipart upload.
     pub async fn put_object_from_url(&self, s3_url: &str, data: &[u8]) -> Result<()> {
         let url = url::Url::parse(s3_url)?;
         let bucket = url
@@ -94,18 +265,150 @@ impl S3Connector {
             .ok_or_else(|| anyhow!("Invalid S3 URL: missing bucket"))?;
         let key = url.path().trim_start_matches('/');
 
-        use aws_sdk_s3::primitives::ByteStream;
+        if self.upload.is_multipart(data.len()) {
+            return self.put_multipart(bucket, key, data).await;
+        }
+
+        retry(&self.retry, || {
+            let request = self
+                .client
+                .put_object()
+                .bucket(bucket)
+                .key(key)
+                .content_type(content_type(key))
+                .body(ByteStream::from(data.to_vec()));
+            async move {
+                request
+                    .send()
+                    .await
+                    .map_err(|e| sdk_error(e, "Failed to upload S3 object"))?;
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    /// Upload `data` with S3 multipart upload, aborting the upload on failure
+    /// so no orphaned parts are left behind.
+    async fn put_multipart(&self, bucket: &str, key: &str, data: &[u8]) -> Result<()> {
+        let created = retry(&self.retry, || {
+            let request = self
+                .client
+                .create_multipart_upload()
+                .bucket(bucket)
+                .key(key)
+                .content_type(content_type(key));
+            async move {
+                request
+                    .send()
+                    .await
+                    .map_err(|e| sdk_error(e, "Failed to start S3 multipart upload"))
+            }
+        })
+        .await?;
+        let upload_id = created
+            .upload_id()
+            .ok_or_else(|| anyhow!("S3 returned no multipart upload id"))?;
 
-        self.client
-            .put_object()
-            .bucket(bucket)
-            .key(key)
-            .body(ByteStream::from(data.to_vec()))
+        let result = self.upload_parts(bucket, key, upload_id, data).await;
+        if result.is_err() {
+            // Best effort; the upload error is the one worth reporting.
+            let _ = self
+                .client
+                .abort_multipart_upload()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .send()
+                .await;
+        }
+        result
+    }
+
+    /// Upload the parts of a multipart upload and complete it.
+    async fn upload_parts(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        data: &[u8],
+    ) -> Result<()> {
+        let mut parts = Vec::new();
+        for (index, chunk) in self.upload.parts(data, MIN_PART_SIZE, 1).enumerate() {
+            let part_number = index as i32 + 1;
+            let uploaded = retry(&self.retry, || {
+                let request = self
+                    .client
+                    .upload_part()
+                    .bucket(bucket)
+                    .key(key)
+                    .upload_id(upload_id)
+                    .part_number(part_number)
+                    .body(ByteStream::from(chunk.to_vec()));
+                async move {
+                    request
+                        .send()
+                        .await
+                        .map_err(|e| sdk_error(e, "Failed to upload S3 part"))
+                }
+            })
+            .await?;
+            parts.push(
+                CompletedPart::builder()
+                    .set_e_tag(uploaded.e_tag().map(str::to_string))
+                    .part_number(part_number)
+                    .build(),
+            );
+        }
+
+        retry(&self.retry, || {
+            let request = self
+                .client
+                .complete_multipart_upload()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(parts.clone()))
+                        .build(),
+                );
+            async move {
+                request
+                    .send()
+                    .await
+                    .map_err(|e| sdk_error(e, "Failed to complete S3 multipart upload"))?;
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    /// Fetch an object with ranged resumption and optional throttling.
+    pub async fn fetch_ranged(
+        &self,
+        location: &str,
+        options: &DownloadOptions,
+    ) -> Result<Download> {
+        let key = self.parse_s3_path(location)?;
+        download_with_resume(options, |offset| self.open(&key, offset)).await
+    }
+
+    /// Request `key` from byte `offset` onwards.
+    async fn open(&self, key: &str, offset: u64) -> Result<RangedBody> {
+        let mut request = self.client.get_object().bucket(&self.bucket).key(key);
+        if offset > 0 {
+            request = request.range(format!("bytes={}-", offset));
+        }
+        let resp = request
             .send()
             .await
-            .context("Failed to upload S3 object")?;
-
-        Ok(())
+            .map_err(|e| sdk_error(e, "Failed to fetch S3 object"))?;
+        let honors_range = resp.content_range().is_some();
+        Ok(RangedBody {
+            stream: Box::new(resp.body),
+            honors_range,
+        })
     }
 
     /// Normalize an S3 path into a key (strip `s3://bucket/` if present).
@@ -121,25 +424,167 @@ impl S3Connector {
 
 #[async_trait::async_trait]
 impl Connector for S3Connector {
-    /// Fetch an object from S3 and return it as a `Read` stream.
-    async fn fetch(&self, location: &str) -> Result<Box<dyn Read>> {
+    /// Stream an object from S3; only the initial request is retried.
+    async fn fetch(&self, location: &str) -> Result<ObjectReader> {
         let key = self.parse_s3_path(location)?;
-        let resp = self
-            .client
-            .get_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .send()
-            .await
-            .context("Failed to fetch S3 object")?;
+        let body = retry(&self.retry, || self.open(&key, 0)).await?;
+        Ok(Box::new(ChunkReader::new(body.stream)))
+    }
 
-        let data = resp
-            .body
-            .collect()
+    /// List objects under an `s3://bucket/prefix` (or bare key prefix).
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let key_prefix = self.parse_s3_path(prefix)?;
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = retry(&self.retry, || {
+                let request = self
+                    .client
+                    .list_objects_v2()
+                    .bucket(&self.bucket)
+                    .prefix(&key_prefix)
+                    .set_continuation_token(continuation_token.clone());
+                async move {
+                    request
+                        .send()
+                        .await
+                        .map_err(|e| sdk_error(e, "Failed to list S3 objects"))
+                }
+            })
+            .await?;
+            for object in page.contents() {
+                if let Some(key) = object.key() {
+                    objects.push(ObjectInfo {
+                        location: format!("s3://{}/{}", self.bucket, key),
+                        size: object.size().unwrap_or(0).max(0) as u64,
+                    });
+                }
+            }
+            continuation_token = page.next_continuation_token().map(str::to_string);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        Ok(objects)
+    }
+}
+
+/// Attach `context` to an SDK error, marking 4xx responses `Permanent`.
+fn sdk_error<E>(e: SdkError<E, HttpResponse>, context: &'static str) -> anyhow::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let status = e.raw_response().map(|response| response.status().as_u16());
+    let error = anyhow::Error::new(e).context(context);
+    match status {
+        Some(status) => classify(status, error),
+        None => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_s3::config::interceptors::BeforeTransmitInterceptorContextRef;
+    use aws_sdk_s3::config::{ConfigBag, Intercept, RuntimeComponents};
+    use aws_sdk_s3::error::BoxError;
+    use std::sync::{Arc, Mutex};
+
+    /// Records the credential headers of a signed request, then stops it
+    /// before it is sent.
+    #[derive(Debug, Clone, Default)]
+    struct Signed(Arc<Mutex<Option<SignedHeaders>>>);
+
+    #[derive(Debug)]
+    struct SignedHeaders {
+        authorization: String,
+        security_token: Option<String>,
+    }
+
+    impl Intercept for Signed {
+        fn name(&self) -> &'static str {
+            "Signed"
+        }
+
+        fn read_before_transmit(
+            &self,
+            context: &BeforeTransmitInterceptorContextRef<'_>,
+            _components: &RuntimeComponents,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), BoxError> {
+            let headers = context.request().headers();
+            *self.0.lock().unwrap() = Some(SignedHeaders {
+                authorization: headers.get("authorization").unwrap_or_default().to_string(),
+                security_token: headers.get("x-amz-security-token").map(str::to_string),
+            });
+            Err("stopped before sending".into())
+        }
+    }
+
+    fn profile(toml: &str) -> Profile {
+        toml::from_str::<Profile>(toml).unwrap()
+    }
+
+    /// Sign (but don't send) a request with the config built for `profile`.
+    async fn signed_headers(profile: &Profile) -> SignedHeaders {
+        let signed = Signed::default();
+        let config = client_config(profile)
             .await
-            .context("Failed to read S3 object body")?
-            .into_bytes();
+            .interceptor(signed.clone())
+            .build();
+        let result = S3Client::from_conf(config)
+            .head_object()
+            .bucket("landing")
+            .key("orders.csv")
+            .send()
+            .await;
+        assert!(result.is_err());
+        signed.0.lock().unwrap().take().unwrap()
+    }
+
+    #[tokio::test]
+    async fn session_token_is_carried_into_credentials() {
+        let headers = signed_headers(&profile(
+            r#"provider = "s3"
+            access_key = "AKIDEXAMPLE"
+            secret_key = "secret"
+            session_token = "token""#,
+        ))
+        .await;
+        assert!(headers.authorization.contains("Credential=AKIDEXAMPLE/"));
+        assert_eq!(headers.security_token.as_deref(), Some("token"));
+    }
+
+    #[tokio::test]
+    async fn role_arn_installs_an_assume_role_provider() {
+        let config = client_config(&profile(
+            r#"provider = "s3"
+            access_key = "AKIDEXAMPLE"
+            secret_key = "secret"
+            role_arn = "arn:aws:iam::123456789012:role/loader""#,
+        ))
+        .await
+        .build();
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("AssumeRoleProvider"));
+        assert!(debug.contains("arn:aws:iam::123456789012:role/loader"));
+    }
+
+    #[tokio::test]
+    async fn empty_role_arn_and_session_token_are_ignored() {
+        let profile = profile(
+            r#"provider = "s3"
+            access_key = "AKIDEXAMPLE"
+            secret_key = "secret"
+            session_token = ""
+            role_arn = """#,
+        );
+        let config = client_config(&profile).await.build();
+        assert!(!format!("{:?}", config).contains("AssumeRoleProvider"));
 
-        Ok(Box::new(Cursor::new(data)))
+        let headers = signed_headers(&profile).await;
+        assert!(headers.authorization.contains("Credential=AKIDEXAMPLE/"));
+        assert_eq!(headers.security_token, None);
     }
 }
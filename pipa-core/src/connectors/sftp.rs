@@ -1,6 +1,5 @@
-use super::Connector;
+use super::{Connector, ObjectInfo, ObjectReader};
 use anyhow::Result;
-use std::io::Read;
 
 #[allow(dead_code)]
 pub struct SftpConnector;
@@ -14,7 +13,11 @@ impl SftpConnector {
 
 #[async_trait::async_trait]
 impl Connector for SftpConnector {
-    async fn fetch(&self, _location: &str) -> Result<Box<dyn Read>> {
+    async fn fetch(&self, _location: &str) -> Result<ObjectReader> {
+        Err(anyhow::anyhow!("SFTP connector not implemented"))
+    }
+
+    async fn list(&self, _prefix: &str) -> Result<Vec<ObjectInfo>> {
         Err(anyhow::anyhow!("SFTP connector not implemented"))
     }
 }
@@ -0,0 +1,183 @@
+//! Disk spooling for large downloads.
+//!
+//! Fetched objects are buffered in memory until they cross the profile's
+//! `spool_threshold_bytes`; past that point the buffer is flushed to a
+//! temporary file and the rest of the transfer streams to disk. Drivers then
+//! read the spooled file directly, keeping memory flat for multi-GB inputs.
+//!
+//! The temp file is removed when the `Payload` is dropped.
+
+use crate::drivers::Driver;
+use crate::drivers::csv::{detect_encoding, detect_file_encoding};
+use anyhow::{Context, Result};
+use encoding_rs::Encoding;
+use polars::prelude::DataFrame;
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::ops::ControlFlow;
+use tempfile::NamedTempFile;
+
+/// Fetched object contents, either in memory or spooled to a temp file.
+pub enum Payload {
+    Memory(Vec<u8>),
+    Spooled { file: NamedTempFile, len: usize },
+}
+
+impl Payload {
+    /// Total size of the payload in bytes.
+    pub fn len(&self) -> usize {
+        match self {
+            Payload::Memory(data) => data.len(),
+            Payload::Spooled { len, .. } => *len,
+        }
+    }
+
+    /// Whether the payload holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the payload was spooled to disk.
+    pub fn is_spooled(&self) -> bool {
+        matches!(self, Payload::Spooled { .. })
+    }
+
+    /// Parse the payload into a DataFrame with the given driver.
+    pub fn load(&self, driver: &dyn Driver) -> Result<DataFrame> {
+        match self {
+            Payload::Memory(data) => driver.load(data),
+            Payload::Spooled { file, .. } => driver.load_path(file.path()),
+        }
+    }
+
+    /// Parse the payload in batches of about `batch_rows` rows, calling `f`
+    /// with each until it returns `ControlFlow::Break`.
+    ///
+    /// A spooled payload is streamed from disk; one held in memory is
+    /// already bounded by the spool threshold, so it is parsed whole and
+    /// sliced.
+    pub fn load_batches(
+        &self,
+        driver: &dyn Driver,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()> {
+        match self {
+            Payload::Memory(data) => {
+                let df = driver.load(data)?;
+                let batch_rows = batch_rows.max(1);
+                for offset in (0..df.height()).step_by(batch_rows) {
+                    if f(df.slice(offset as i64, batch_rows))?.is_break() {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+            Payload::Spooled { file, .. } => driver.load_batches(file.path(), batch_rows, f),
+        }
+    }
+
+    /// Hex SHA-256 of the payload's content; a spooled payload is hashed
+    /// from disk.
+    pub fn sha256(&self) -> Result<String> {
+        let mut hasher = Sha256::new();
+        match self {
+            Payload::Memory(data) => hasher.update(data),
+            Payload::Spooled { file, .. } => {
+                let mut handle = file.reopen().context("Failed to reopen spool file")?;
+                std::io::copy(&mut handle, &mut hasher).context("Failed to hash spool file")?;
+            }
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// The text encoding the CSV driver reads the payload in: `declared`,
+    /// or the one detected from its content (see `drivers::csv`).
+    pub fn csv_encoding(&self, declared: Option<&'static Encoding>) -> Result<&'static Encoding> {
+        match self {
+            Payload::Memory(data) => Ok(detect_encoding(data, declared)),
+            Payload::Spooled { file, .. } => detect_file_encoding(file.path(), declared),
+        }
+    }
+
+    /// Turn the payload into a reader positioned at the start.
+    pub fn into_reader(self) -> Result<Box<dyn Read>> {
+        match self {
+            Payload::Memory(data) => Ok(Box::new(Cursor::new(data))),
+            Payload::Spooled { file, .. } => {
+                let mut handle = file.reopen().context("Failed to reopen spool file")?;
+                handle.seek(SeekFrom::Start(0))?;
+                Ok(Box::new(handle))
+            }
+        }
+    }
+}
+
+/// Write side of a payload: collects chunks and spills to disk past a threshold.
+pub struct Spool {
+    threshold: Option<usize>,
+    payload: Payload,
+}
+
+impl Spool {
+    /// Create a spool. With `threshold = None` everything stays in memory.
+    pub fn new(threshold: Option<usize>) -> Self {
+        Self {
+            threshold,
+            payload: Payload::Memory(Vec::new()),
+        }
+    }
+
+    /// Bytes written so far.
+    pub fn len(&self) -> usize {
+        self.payload.len()
+    }
+
+    /// Append a chunk, spilling to a temp file once the threshold is crossed.
+    pub fn write(&mut self, chunk: &[u8]) -> Result<()> {
+        if let Payload::Memory(data) = &mut self.payload {
+            match self.threshold {
+                Some(limit) if data.len() + chunk.len() > limit => {
+                    let mut file = NamedTempFile::new().context("Failed to create spool file")?;
+                    file.write_all(data)?;
+                    self.payload = Payload::Spooled {
+                        file,
+                        len: data.len(),
+                    };
+                }
+                _ => {
+                    data.extend_from_slice(chunk);
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Payload::Spooled { file, len } = &mut self.payload {
+            file.write_all(chunk)
+                .context("Failed to write spool file")?;
+            *len += chunk.len();
+        }
+        Ok(())
+    }
+
+    /// Discard everything written so far (used when a resume restarts at byte 0).
+    pub fn clear(&mut self) -> Result<()> {
+        match &mut self.payload {
+            Payload::Memory(data) => data.clear(),
+            Payload::Spooled { file, len } => {
+                file.as_file_mut().set_len(0)?;
+                file.seek(SeekFrom::Start(0))?;
+                *len = 0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush and hand back the finished payload.
+    pub fn finish(mut self) -> Result<Payload> {
+        if let Payload::Spooled { file, .. } = &mut self.payload {
+            file.flush().context("Failed to flush spool file")?;
+        }
+        Ok(self.payload)
+    }
+}
@@ -0,0 +1,130 @@
+//! Chunked uploads for cloud connectors.
+//!
+//! Destination and quarantine writes above `multipart_threshold_bytes` are
+//! split into parts instead of sent as one request, so multi-GB outputs
+//! don't hit single-request size limits and a failed part is retried on
+//! its own (see `connectors::retry`):
+//! - S3 uses multipart upload (aborted if any part fails),
+//! - Azure stages blocks with Put Block and commits them with Put Block List,
+//! - GCS streams chunks through a resumable upload session.
+//!
+//! ## Profile fields used
+//! - `multipart_threshold_bytes` (optional, default: 64 MiB)
+//! - `multipart_part_bytes` (optional, default: 16 MiB; raised to each
+//!   service's minimum where it has one)
+//!
+//! Uploaded objects get a content type from their extension (see
+//! `content_type`), so compressed outputs such as `orders.csv.gz` are
+//! served as `application/gzip`.
+
+use crate::profiles::Profile;
+
+/// Default size above which uploads are split into parts.
+const DEFAULT_MULTIPART_THRESHOLD: usize = 64 * 1024 * 1024;
+
+/// Default part size.
+const DEFAULT_PART_SIZE: usize = 16 * 1024 * 1024;
+
+/// Upload tuning derived from a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadOptions {
+    pub multipart_threshold_bytes: usize,
+    pub part_size_bytes: usize,
+}
+
+impl Default for UploadOptions {
+    fn default() -> Self {
+        Self {
+            multipart_threshold_bytes: DEFAULT_MULTIPART_THRESHOLD,
+            part_size_bytes: DEFAULT_PART_SIZE,
+        }
+    }
+}
+
+impl UploadOptions {
+    /// Read multipart settings from a profile, falling back to defaults.
+    pub fn from_profile(profile: &Profile) -> Self {
+        Self {
+            multipart_threshold_bytes: profile
+                .multipart_threshold_bytes
+                .map_or(DEFAULT_MULTIPART_THRESHOLD, |bytes| bytes as usize),
+            part_size_bytes: profile
+                .multipart_part_bytes
+                .filter(|bytes| *bytes > 0)
+                .map_or(DEFAULT_PART_SIZE, |bytes| bytes as usize),
+        }
+    }
+
+    /// Whether an upload of `len` bytes is split into parts.
+    pub fn is_multipart(&self, len: usize) -> bool {
+        len > self.multipart_threshold_bytes
+    }
+
+    /// Split `data` into parts of the configured size, raised to at least
+    /// `min` bytes and rounded up to a multiple of `align` bytes.
+    pub fn parts<'a>(
+        &self,
+        data: &'a [u8],
+        min: usize,
+        align: usize,
+    ) -> std::slice::Chunks<'a, u8> {
+        let size = self.part_size_bytes.max(min).max(1).div_ceil(align) * align;
+        data.chunks(size)
+    }
+}
+
+/// Content type of an object, from the extension of its name or URL.
+pub fn content_type(location: &str) -> &'static str {
+    let path = location.split(['?', '#']).next().unwrap_or_default();
+    let name = path.rsplit('/').next().unwrap_or_default();
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "csv" => "text/csv",
+        "parquet" => "application/vnd.apache.parquet",
+        "json" => "application/json",
+        "ndjson" | "jsonl" => "application/x-ndjson",
+        "gz" => "application/gzip",
+        "zst" => "application/zstd",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_into_aligned_parts() {
+        let options = UploadOptions {
+            multipart_threshold_bytes: 8,
+            part_size_bytes: 5,
+        };
+        let data = [0u8; 20];
+        assert!(options.is_multipart(data.len()));
+        assert!(!options.is_multipart(8));
+
+        let sizes = |min, align| -> Vec<usize> {
+            options.parts(&data, min, align).map(<[u8]>::len).collect()
+        };
+        assert_eq!(sizes(1, 1), [5, 5, 5, 5]);
+        assert_eq!(sizes(7, 1), [7, 7, 6]);
+        assert_eq!(sizes(1, 4), [8, 8, 4]);
+    }
+
+    #[test]
+    fn content_type_follows_the_extension() {
+        assert_eq!(content_type("s3://bucket/out/orders.csv"), "text/csv");
+        assert_eq!(content_type("orders.ndjson.gz"), "application/gzip");
+        assert_eq!(
+            content_type("https://acct.blob.core.windows.net/c/o.parquet?sv=1"),
+            "application/vnd.apache.parquet"
+        );
+        assert_eq!(
+            content_type("data/no_extension"),
+            "application/octet-stream"
+        );
+    }
+}
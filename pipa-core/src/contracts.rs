@@ -1,10 +1,37 @@
 // Submodules that define different contract domains
+pub mod builder; // `SchemaContracts::builder` for contracts built in code
 pub mod column; // Column-level constraints (type, nullability, length, etc.)
 pub mod compound; // Multi-column constraints (e.g., uniqueness across fields)
+pub mod dates; // `{{ today }}`-style run-date tokens in source locations
+pub mod drift; // Run-to-run drift metrics and reference selection
 pub mod file; // File-level constraints (row counts, completeness)
+pub mod filename; // `{stem}`-style filename templates for moved data
+pub mod headers; // Normalizing incoming column names before validation
+pub mod limits; // Per-run resource limits (rows, bytes, wall time, regex size)
+pub mod notifications; // Slack/Teams/webhook/email targets for run outcomes
+pub mod overlay; // Per-environment `<name>.<env>.toml` contract overlays
+pub mod retry; // Retrying runs that fail with transient errors
+pub mod sampling; // Adaptive sampling for very large datasets
+pub mod schedule; // Cron schedule for the resident scheduler
 pub mod schema; // Schema definitions and contract orchestration
+pub mod sources; // Several `[[sources]]` combined by concat or join
+pub mod streaming; // Chunked validation for inputs larger than memory
+pub mod transform; // Clean-up steps applied before validation
 pub mod types; // Shared enums and type definitions for contracts
+pub mod vars; // `${NAME}` interpolation from `[vars]` and the environment
 
 // Curated re-exports: the stable API surface for contracts
-pub use schema::{SchemaContracts, load_contract_for_file};
+pub use builder::ContractBuilder;
+pub use drift::{DriftMetric, DriftReference};
+pub use headers::Headers;
+pub use limits::Limits;
+pub use notifications::{ChannelTarget, NotificationChannel, Notifications, NotifyOn};
+pub use overlay::{apply_overlay, is_overlay};
+pub use retry::Retry;
+pub use sampling::Sampling;
+pub use schema::{
+    ContractParseError, CsvDialect, OnSuccess, SchemaContracts, SourceOptions,
+    load_contract_for_file, parse_contract,
+};
+pub use sources::{Combine, CombineMode, JoinHow, NamedSource};
 pub use types::ContractType;
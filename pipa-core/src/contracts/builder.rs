@@ -0,0 +1,367 @@
+use super::column::ColumnContracts;
+use super::compound::CompoundUnique;
+use super::file::FileContracts;
+use super::limits::Limits;
+use super::sampling::Sampling;
+use super::schema::{
+    ArchiveMode, Contract, Destination, OnDuplicate, OnSuccess, Quarantine, SchemaContracts, Source,
+};
+use super::types::ContractType;
+use crate::engine::contracts::format_contract_toml;
+
+/// Builds a `SchemaContracts` in code, for teams embedding pipa as a
+/// library: validate a `DataFrame` in-process with it
+/// (`run::validate_dataframe`), or write it out as a contract file with
+/// `to_toml`.
+///
+/// Rules, column dtypes, the source, destination, and quarantine,
+/// `[limits]`, and `[sampling]` can be set; the other sections are left
+/// out.
+///
+/// Example:
+/// ```ignore
+/// let contracts = ContractBuilder::new("orders")
+///     .file_rule(ContractType::RowCount { min: 1, max: None })
+///     .column("id", [ContractType::NotNull, ContractType::Unique])
+///     .typed_column("amount", "Float64", [ContractType::Completeness { min_ratio: 0.99 }])
+///     .compound_unique(["customer_id", "order_date"])
+///     .source_s3("s3://landing/orders.csv", "landing")
+///     .destination("local", "clean/", None)
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct ContractBuilder {
+    contracts: SchemaContracts,
+}
+
+impl SchemaContracts {
+    /// Start building contract `name` at `version` with no rules.
+    pub fn builder(name: &str, version: &str) -> ContractBuilder {
+        ContractBuilder::new(name).version(version)
+    }
+}
+
+impl ContractBuilder {
+    /// Start building contract `name`, at version `0.1.0` and with no
+    /// rules.
+    pub fn new(name: &str) -> Self {
+        ContractBuilder {
+            contracts: SchemaContracts {
+                contract: Contract {
+                    name: name.to_string(),
+                    version: "0.1.0".to_string(),
+                    tags: Vec::new(),
+                    strict_types: false,
+                    strict_rules: false,
+                },
+                file: None,
+                columns: Vec::new(),
+                headers: None,
+                mapping: None,
+                transform: None,
+                compound_unique: None,
+                source: None,
+                sources: None,
+                combine: None,
+                destination: None,
+                quarantine: None,
+                limits: None,
+                retry: None,
+                sampling: None,
+                schedule: None,
+                streaming: None,
+                notifications: None,
+            },
+        }
+    }
+
+    /// Set the contract version.
+    pub fn version(mut self, version: &str) -> Self {
+        self.contracts.contract.version = version.to_string();
+        self
+    }
+
+    /// Add tags, as in `[contract] tags`.
+    pub fn tags<S: Into<String>>(mut self, tags: impl IntoIterator<Item = S>) -> Self {
+        self.contracts
+            .contract
+            .tags
+            .extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Require every column to declare a dtype (`strict_types`).
+    pub fn strict_types(mut self, strict: bool) -> Self {
+        self.contracts.contract.strict_types = strict;
+        self
+    }
+
+    /// Fail on rules declared where they have no implementation
+    /// (`strict_rules`).
+    pub fn strict_rules(mut self, strict: bool) -> Self {
+        self.contracts.contract.strict_rules = strict;
+        self
+    }
+
+    /// Add a file-level rule, as in `[file] validation`.
+    pub fn file_rule(mut self, rule: ContractType) -> Self {
+        self.contracts
+            .file
+            .get_or_insert_with(|| FileContracts {
+                validation: Vec::new(),
+            })
+            .validation
+            .push(rule);
+        self
+    }
+
+    /// Add a `[[columns]]` entry without a declared dtype.
+    pub fn column(self, name: &str, rules: impl IntoIterator<Item = ContractType>) -> Self {
+        self.push_column(name, None, rules)
+    }
+
+    /// Add a `[[columns]]` entry declaring its Polars dtype (e.g. `"Int64"`).
+    pub fn typed_column(
+        self,
+        name: &str,
+        dtype: &str,
+        rules: impl IntoIterator<Item = ContractType>,
+    ) -> Self {
+        self.push_column(name, Some(dtype.to_string()), rules)
+    }
+
+    /// Require the combination of `columns` to be unique.
+    pub fn compound_unique<S: Into<String>>(
+        mut self,
+        columns: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.contracts
+            .compound_unique
+            .get_or_insert_with(Vec::new)
+            .push(CompoundUnique {
+                columns: columns.into_iter().map(Into::into).collect(),
+            });
+        self
+    }
+
+    /// Read from `location` with connector `kind` (`local`, `s3`, `gcs`,
+    /// `azure`, `kafka`), using `profile` when given.
+    pub fn source(mut self, kind: &str, location: &str, profile: Option<&str>) -> Self {
+        self.contracts.source = Some(Source {
+            r#type: kind.to_string(),
+            location: Some(location.to_string()),
+            profile: profile.map(str::to_string),
+            format: None,
+            options: Default::default(),
+            csv: Default::default(),
+            on_success: OnSuccess::Keep,
+            archive_location: None,
+            on_duplicate: OnDuplicate::Allow,
+            archive_member: None,
+            archive_mode: ArchiveMode::Each,
+        });
+        self
+    }
+
+    /// Read a local file.
+    pub fn source_local(self, location: &str) -> Self {
+        self.source("local", location, None)
+    }
+
+    /// Read `s3://` `location` with `profile`.
+    pub fn source_s3(self, location: &str, profile: &str) -> Self {
+        self.source("s3", location, Some(profile))
+    }
+
+    /// Read `gs://` `location` with `profile`.
+    pub fn source_gcs(self, location: &str, profile: &str) -> Self {
+        self.source("gcs", location, Some(profile))
+    }
+
+    /// Read `azure://` `location` with `profile`.
+    pub fn source_azure(self, location: &str, profile: &str) -> Self {
+        self.source("azure", location, Some(profile))
+    }
+
+    /// Write data that passes to `location` with connector `kind`.
+    pub fn destination(mut self, kind: &str, location: &str, profile: Option<&str>) -> Self {
+        self.contracts.destination = Some(Destination {
+            r#type: kind.to_string(),
+            location: Some(location.to_string()),
+            profile: profile.map(str::to_string),
+            format: None,
+            filename_template: None,
+            partition_by: Vec::new(),
+            annotate: false,
+        });
+        self
+    }
+
+    /// Write data that fails to `location` with connector `kind`.
+    pub fn quarantine(mut self, kind: &str, location: &str, profile: Option<&str>) -> Self {
+        self.contracts.quarantine = Some(Quarantine {
+            r#type: kind.to_string(),
+            location: Some(location.to_string()),
+            profile: profile.map(str::to_string),
+            format: None,
+            filename_template: None,
+            annotate: false,
+        });
+        self
+    }
+
+    /// Set `[limits]`.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.contracts.limits = Some(limits);
+        self
+    }
+
+    /// Set `[sampling]`.
+    pub fn sampling(mut self, sampling: Sampling) -> Self {
+        self.contracts.sampling = Some(sampling);
+        self
+    }
+
+    /// The built contract.
+    pub fn build(self) -> SchemaContracts {
+        self.contracts
+    }
+
+    /// The contract as TOML, laid out as `pipa contract fmt` would and
+    /// without settings left at their defaults.
+    ///
+    /// # Errors
+    /// Returns the serializer's message if the contract can't be written
+    /// as TOML.
+    pub fn to_toml(&self) -> Result<String, String> {
+        let mut value = toml::Value::try_from(&self.contracts).map_err(|e| e.to_string())?;
+        prune_defaults(&mut value);
+        let toml = toml::to_string(&value).map_err(|e| e.to_string())?;
+        Ok(format_contract_toml(&toml, false)?.toml)
+    }
+
+    fn push_column(
+        mut self,
+        name: &str,
+        dtype: Option<String>,
+        rules: impl IntoIterator<Item = ContractType>,
+    ) -> Self {
+        self.contracts.columns.push(ColumnContracts {
+            name: name.to_string(),
+            dtype,
+            start: None,
+            width: None,
+            aliases: Vec::new(),
+            validation: rules.into_iter().collect(),
+        });
+        self
+    }
+}
+
+/// Drop empty tables and lists, `false` flags, `on_success = "keep"`,
+/// `on_duplicate = "allow"`, and `archive_mode = "each"`, all of which
+/// parse back as their defaults. `tags` is required, so it stays even when
+/// empty.
+fn prune_defaults(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            table
+                .iter_mut()
+                .for_each(|(_, value)| prune_defaults(value));
+            table.retain(|key, value| match value {
+                toml::Value::Table(fields) => !fields.is_empty(),
+                toml::Value::Array(items) => !items.is_empty() || key == "tags",
+                toml::Value::Boolean(flag) => *flag,
+                toml::Value::String(text) => {
+                    !(key == "on_success" && text == "keep"
+                        || key == "on_duplicate" && text == "allow"
+                        || key == "archive_mode" && text == "each")
+                }
+                _ => true,
+            });
+        }
+        toml::Value::Array(items) => items.iter_mut().for_each(prune_defaults),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::parse_contract;
+
+    #[test]
+    fn builds_the_same_contract_as_toml() {
+        let built = SchemaContracts::builder("orders", "1.0")
+            .tags(["finance"])
+            .file_rule(ContractType::RowCount { min: 1, max: None })
+            .column("id", [ContractType::NotNull, ContractType::Unique])
+            .typed_column("age", "Int64", [ContractType::Range { min: 0, max: 120 }])
+            .compound_unique(["id", "age"])
+            .build();
+        let parsed = parse_contract(
+            r#"
+[contract]
+name = "orders"
+version = "1.0"
+tags = ["finance"]
+
+[file]
+validation = [{ rule = "row_count", min = 1 }]
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }, { rule = "unique" }]
+
+[[columns]]
+name = "age"
+dtype = "Int64"
+validation = [{ rule = "range", min = 0, max = 120 }]
+
+[[compound_unique]]
+columns = ["id", "age"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            toml::to_string(&built).unwrap(),
+            toml::to_string(&parsed).unwrap()
+        );
+    }
+    #[test]
+    fn writes_a_contract_file() {
+        let toml = ContractBuilder::new("customers")
+            .column("id", [ContractType::NotNull, ContractType::Unique])
+            .source_s3("s3://landing/customers.csv", "landing")
+            .destination("local", "clean/", None)
+            .to_toml()
+            .unwrap();
+
+        assert_eq!(
+            toml,
+            r#"[contract]
+name = "customers"
+version = "0.1.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [
+  { rule = "not_null" },
+  { rule = "unique" },
+]
+
+[source]
+type = "s3"
+location = "s3://landing/customers.csv"
+profile = "landing"
+
+[destination]
+type = "local"
+location = "clean/"
+"#
+        );
+        assert!(parse_contract(&toml).is_ok());
+    }
+}
@@ -1,5 +1,5 @@
 use super::types::ContractType;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Column-level contract definition.
 ///
@@ -11,17 +11,39 @@ use serde::Deserialize;
 /// ```toml
 /// [[columns]]
 /// name = "email"
+/// dtype = "String"
 /// validation = [
 ///   { rule = "not_null" },
 ///   { rule = "pattern", pattern = "^[^@]+@[^@]+$" }
 /// ]
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ColumnContracts {
     /// The column name in the dataset to which these rules apply.
     pub name: String,
 
+    /// Declared Polars dtype (e.g. `"Int64"`, `"String"`).
+    /// Required for every column when the contract sets `strict_types = true`.
+    #[serde(default)]
+    pub dtype: Option<String>,
+
+    /// Byte offset of the column in each line of a fixed-width source
+    /// (`format = "fixed_width"`), counted from 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<usize>,
+
+    /// Width of the column in bytes, for fixed-width sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<usize>,
+
+    /// Other names the column may arrive under (e.g. `"Cust ID"`); a
+    /// matching incoming column is renamed to `name` before validation.
+    /// Compared after `[headers]` normalization.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
     /// A list of validation rules to enforce on this column.
     /// Each rule is a `ContractType` variant (e.g., NotNull, Pattern, MaxLength).
+    #[serde(default)]
     pub validation: Vec<ContractType>,
 }
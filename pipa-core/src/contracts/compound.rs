@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Compound uniqueness contract.
 ///
@@ -14,7 +14,7 @@ use serde::Deserialize;
 ///
 /// This would enforce that no two rows share the same
 /// (first_name, last_name, dob) triple.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CompoundUnique {
     /// The set of columns that must be unique in combination.
     pub columns: Vec<String>,
@@ -0,0 +1,120 @@
+//! Run-date tokens for date-partitioned source locations.
+//!
+//! `[source] location` may contain `{{ token }}` placeholders, rendered
+//! against the run date (`pipa run --date`, default: today in UTC) just
+//! before the source is listed or fetched:
+//!
+//! | Token              | Example (run date 2025-01-15) |
+//! |--------------------|-------------------------------|
+//! | `today`, `ds`      | `2025-01-15`                  |
+//! | `yesterday`        | `2025-01-14`                  |
+//! | `tomorrow`         | `2025-01-16`                  |
+//! | `ds_nodash`        | `20250115`                    |
+//! | `yesterday_nodash` | `20250114`                    |
+//! | `year`             | `2025`                        |
+//! | `month`            | `01`                          |
+//! | `day`              | `15`                          |
+//!
+//! Example TOML:
+//! ```toml
+//! [source]
+//! type = "local"
+//! location = "data/{{ today }}/orders.csv"
+//! ```
+//!
+//! Only `source.location` is rendered. An unknown token is an error rather
+//! than a literal `{{...}}` reaching a connector.
+
+use chrono::{Days, NaiveDate};
+
+/// Whether `text` contains a `{{ ... }}` token.
+pub fn has_date_tokens(text: &str) -> bool {
+    text.find("{{")
+        .is_some_and(|start| text[start..].contains("}}"))
+}
+
+/// Replace every `{{ token }}` in `text` with its value for `date`.
+pub fn render_date_tokens(text: &str, date: NaiveDate) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let body = &rest[start + 2..];
+        let end = body
+            .find("}}")
+            .ok_or_else(|| format!("unterminated date token in '{}'", text))?;
+        out.push_str(&token_value(body[..end].trim(), date)?);
+        rest = &body[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Parse a `--date` argument (`YYYY-MM-DD`).
+pub fn parse_run_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{}' (expected YYYY-MM-DD)", text))
+}
+
+fn token_value(token: &str, date: NaiveDate) -> Result<String, String> {
+    let value = match token {
+        "today" | "ds" => date.format("%Y-%m-%d"),
+        "ds_nodash" => date.format("%Y%m%d"),
+        "yesterday" => offset(date, -1)?.format("%Y-%m-%d"),
+        "yesterday_nodash" => offset(date, -1)?.format("%Y%m%d"),
+        "tomorrow" => offset(date, 1)?.format("%Y-%m-%d"),
+        "year" => date.format("%Y"),
+        "month" => date.format("%m"),
+        "day" => date.format("%d"),
+        other => {
+            return Err(format!(
+                "unknown date token '{{{{ {} }}}}' (expected today, ds, yesterday, tomorrow, \
+                 ds_nodash, yesterday_nodash, year, month, or day)",
+                other
+            ));
+        }
+    };
+    Ok(value.to_string())
+}
+
+fn offset(date: NaiveDate, days: i64) -> Result<NaiveDate, String> {
+    let shifted = if days < 0 {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    } else {
+        date.checked_add_days(Days::new(days as u64))
+    };
+    shifted.ok_or_else(|| format!("date out of range: {} {:+} days", date, days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        parse_run_date(text).unwrap()
+    }
+
+    #[test]
+    fn renders_every_token() {
+        let rendered = render_date_tokens(
+            "data/{{ today }}/{{ds}}/{{ yesterday }}/{{ tomorrow }}/{{ ds_nodash }}/\
+             {{ yesterday_nodash }}/{{ year }}/{{ month }}/{{ day }}.csv",
+            date("2025-03-01"),
+        )
+        .unwrap();
+        assert_eq!(
+            rendered,
+            "data/2025-03-01/2025-03-01/2025-02-28/2025-03-02/20250301/20250228/2025/03/01.csv"
+        );
+        assert!(has_date_tokens("data/{{ today }}/x.csv"));
+        assert!(!has_date_tokens("data/{2,3}/x.csv"));
+    }
+
+    #[test]
+    fn unknown_tokens_and_bad_dates_are_errors() {
+        let err = render_date_tokens("data/{{ todya }}.csv", date("2025-01-15")).unwrap_err();
+        assert!(err.contains("unknown date token '{{ todya }}'"), "{}", err);
+        assert!(render_date_tokens("data/{{ today.csv", date("2025-01-15")).is_err());
+        assert!(parse_run_date("15/01/2025").is_err());
+    }
+}
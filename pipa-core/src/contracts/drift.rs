@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// Column statistic compared by a `drift` rule.
+///
+/// Ratios (`null_ratio`, `distinct_ratio`) are compared by absolute
+/// difference; `row_count`, `mean`, and `stdev` by relative change
+/// against the reference value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftMetric {
+    RowCount,
+    NullRatio,
+    DistinctRatio,
+    Mean,
+    Stdev,
+}
+
+impl DriftMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftMetric::RowCount => "row_count",
+            DriftMetric::NullRatio => "null_ratio",
+            DriftMetric::DistinctRatio => "distinct_ratio",
+            DriftMetric::Mean => "mean",
+            DriftMetric::Stdev => "stdev",
+        }
+    }
+
+    /// Whether the metric is a ratio compared by absolute difference.
+    pub fn is_ratio(&self) -> bool {
+        matches!(self, DriftMetric::NullRatio | DriftMetric::DistinctRatio)
+    }
+}
+
+/// Which stored run a `drift` rule compares against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftReference {
+    /// The most recent recorded run of the same contract.
+    #[default]
+    Previous,
+    /// The run pinned with `pipa contract baseline`.
+    Baseline,
+}
+
+impl DriftReference {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftReference::Previous => "previous",
+            DriftReference::Baseline => "baseline",
+        }
+    }
+}
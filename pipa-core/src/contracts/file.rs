@@ -1,5 +1,5 @@
 use super::types::ContractType;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// File-level contract definition.
 ///
@@ -11,12 +11,13 @@ use serde::Deserialize;
 /// [file]
 /// validation = [
 ///   { rule = "row_count", min = 100, max = 200 },
-///   { rule = "completeness", min_ratio = 0.95 }
+///   { rule = "completeness", min_ratio = 0.95 },
+///   { rule = "expected_columns", columns = ["id", "email"], ordered = true }
 /// ]
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FileContracts {
     /// A list of validation rules to enforce at the file level.
-    /// Examples: RowCount, Completeness, Exists.
+    /// Examples: RowCount, Completeness, ExpectedColumns.
     pub validation: Vec<ContractType>,
 }
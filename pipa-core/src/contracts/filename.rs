@@ -0,0 +1,159 @@
+//! Output filename templates for moved data.
+//!
+//! `[destination]` and `[quarantine]` may set `filename_template` to name
+//! the files written there. `{token}` placeholders are replaced when the
+//! file is written:
+//!
+//! | Token        | Value                                                  |
+//! |--------------|--------------------------------------------------------|
+//! | `{stem}`     | source file name without its extension (`orders`)      |
+//! | `{ext}`      | output extension: the target's `format`, else the source's (`csv`) |
+//! | `{date}`     | UTC date of the write (`20250115`)                     |
+//! | `{time}`     | UTC time of the write (`143005`)                       |
+//! | `{contract}` | contract name                                          |
+//! | `{uuid}`     | random version 4 UUID                                  |
+//! | `{status}`   | `passed` in the destination, `failed` in quarantine    |
+//!
+//! Example TOML:
+//! ```toml
+//! [destination]
+//! type = "s3"
+//! location = "s3://clean/orders/"
+//! filename_template = "{date}/{contract}-{uuid}.{ext}"
+//! ```
+//!
+//! Without a template, files are named `{stem}_{date}_{time}.{ext}` and,
+//! in quarantine, `{stem}_{date}_{time}_quarantine.{ext}`. A `/` in the
+//! template writes into subdirectories; an unknown token is an error.
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+
+/// Name of files written to a destination without a template.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{stem}_{date}_{time}.{ext}";
+
+/// Name of files written to quarantine without a template.
+pub const DEFAULT_QUARANTINE_FILENAME_TEMPLATE: &str = "{stem}_{date}_{time}_quarantine.{ext}";
+
+/// Every token a template may use.
+pub const FILENAME_TOKENS: &[&str] = &["stem", "ext", "date", "time", "contract", "uuid", "status"];
+
+/// Values the tokens of one written file take.
+pub struct FilenameValues<'a> {
+    pub stem: &'a str,
+    pub ext: &'a str,
+    pub contract: &'a str,
+    /// Whether the data passed validation (`{status}`).
+    pub passed: bool,
+    pub written_at: DateTime<Utc>,
+}
+
+/// Replace every `{token}` in `template` with its value.
+pub fn render_filename(template: &str, values: &FilenameValues) -> Result<String, String> {
+    render(template, |token| {
+        Ok(match token {
+            "stem" => values.stem.to_string(),
+            "ext" => values.ext.to_string(),
+            "date" => values.written_at.format("%Y%m%d").to_string(),
+            "time" => values.written_at.format("%H%M%S").to_string(),
+            "contract" => values.contract.to_string(),
+            "uuid" => uuid_v4(),
+            "status" => if values.passed { "passed" } else { "failed" }.to_string(),
+            other => return Err(unknown_token(other)),
+        })
+    })
+}
+
+/// Check that `template` only uses known tokens.
+pub fn check_filename_template(template: &str) -> Result<(), String> {
+    render(template, |token| {
+        if FILENAME_TOKENS.contains(&token) {
+            Ok(String::new())
+        } else {
+            Err(unknown_token(token))
+        }
+    })
+    .map(|_| ())
+}
+
+fn render(
+    template: &str,
+    mut value: impl FnMut(&str) -> Result<String, String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let body = &rest[start + 1..];
+        let end = body
+            .find('}')
+            .ok_or_else(|| format!("unterminated token in filename_template '{}'", template))?;
+        out.push_str(&value(body[..end].trim())?);
+        rest = &body[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn unknown_token(token: &str) -> String {
+    format!(
+        "unknown filename_template token '{{{}}}' (expected {})",
+        token,
+        FILENAME_TOKENS.join(", ")
+    )
+}
+
+/// A random (version 4) UUID in its hyphenated form.
+pub(crate) fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn values(passed: bool) -> FilenameValues<'static> {
+        FilenameValues {
+            stem: "orders",
+            ext: "parquet",
+            contract: "daily_orders",
+            passed,
+            written_at: Utc.with_ymd_and_hms(2025, 1, 15, 14, 30, 5).unwrap(),
+        }
+    }
+
+    #[test]
+    fn renders_tokens() {
+        assert_eq!(
+            render_filename(DEFAULT_QUARANTINE_FILENAME_TEMPLATE, &values(false)).unwrap(),
+            "orders_20250115_143005_quarantine.parquet"
+        );
+        assert_eq!(
+            render_filename("{date}/{contract}-{status}.{ext}", &values(true)).unwrap(),
+            "20250115/daily_orders-passed.parquet"
+        );
+        let name = render_filename("{uuid}", &values(true)).unwrap();
+        assert_eq!(name.len(), 36);
+        assert_eq!(&name[14..15], "4");
+    }
+
+    #[test]
+    fn rejects_unknown_and_unterminated_tokens() {
+        assert!(check_filename_template("{stem}_{batch}.csv").is_err());
+        assert!(check_filename_template("{stem.csv").is_err());
+        assert!(check_filename_template("{stem}.{ext}").is_ok());
+    }
+}
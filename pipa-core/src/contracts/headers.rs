@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// Normalization of incoming column names (`[headers]`).
+///
+/// Applied to every frame a contract's source driver loads, before any
+/// rule runs, so cosmetic header drift (stray spaces, capitalization, a
+/// byte order mark glued to the first name) doesn't fail every rule. Steps
+/// run in field order. A header that still doesn't match a `[[columns]]`
+/// name can be mapped to it with that column's `aliases`, which are
+/// normalized the same way before comparing.
+///
+/// Example TOML:
+/// ```toml
+/// [headers]
+/// strip_bom = true
+/// trim = true
+/// lowercase = true
+/// spaces_to_underscores = true
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Headers {
+    /// Remove a leading byte order mark (`U+FEFF`).
+    #[serde(default)]
+    pub strip_bom: bool,
+    /// Remove leading and trailing whitespace.
+    #[serde(default)]
+    pub trim: bool,
+    /// Lowercase the name.
+    #[serde(default)]
+    pub lowercase: bool,
+    /// Replace each space with `_`.
+    #[serde(default)]
+    pub spaces_to_underscores: bool,
+}
+
+impl Headers {
+    /// `name` with the enabled steps applied.
+    pub fn normalize(&self, name: &str) -> String {
+        let mut name = name;
+        if self.strip_bom {
+            name = name.trim_start_matches('\u{feff}');
+        }
+        if self.trim {
+            name = name.trim();
+        }
+        let mut name = name.to_string();
+        if self.lowercase {
+            name = name.to_lowercase();
+        }
+        if self.spaces_to_underscores {
+            name = name.replace(' ', "_");
+        }
+        name
+    }
+}
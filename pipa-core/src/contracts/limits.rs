@@ -0,0 +1,57 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Per-run resource limits for a contract.
+///
+/// Guards shared validation hosts against pathological contracts or inputs.
+/// When a limit is hit, the affected rules are reported with result
+/// `"aborted"` and a `limit exceeded: ...` detail instead of being evaluated.
+/// The timeouts instead fail the run: a fetch that runs out is an error
+/// like any other fetch failure (and retried per `[retry]`), and a run that
+/// runs out is cancelled and logged as `run_timeout`.
+///
+/// Example TOML:
+/// ```toml
+/// [limits]
+/// max_rows = 5_000_000
+/// max_bytes = 2_147_483_648
+/// max_wall_time_secs = 300
+/// max_regex_size = 1_048_576
+/// max_threads = 4
+/// max_memory_bytes = 4_294_967_296
+/// fetch_timeout_secs = 120
+/// run_timeout_secs = 1800
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Limits {
+    /// Abort all rules when the parsed dataset has more rows than this.
+    pub max_rows: Option<usize>,
+    /// Abort all rules (without parsing) when the input is larger than this.
+    pub max_bytes: Option<usize>,
+    /// Abort the remaining rules once validation has run this long.
+    pub max_wall_time_secs: Option<u64>,
+    /// Abort a `pattern` rule whose compiled regex exceeds this many bytes.
+    pub max_regex_size: Option<usize>,
+    /// Run column rules on at most this many threads (`1` runs them in
+    /// turn; `0` is rejected). Defaults to one thread per CPU.
+    #[serde(default, deserialize_with = "deserialize_max_threads")]
+    pub max_threads: Option<usize>,
+    /// Keep a run's data under this many bytes: a larger input is streamed
+    /// when the contract has `[streaming]`, and otherwise every rule is
+    /// aborted without parsing it, as is a parsed dataset this large.
+    pub max_memory_bytes: Option<usize>,
+    /// Fail a source fetch (or glob listing) that takes longer than this.
+    pub fetch_timeout_secs: Option<u64>,
+    /// Cancel the run, retries included, once it has taken this long.
+    pub run_timeout_secs: Option<u64>,
+}
+
+fn deserialize_max_threads<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<usize>, D::Error> {
+    match Option::<usize>::deserialize(deserializer)? {
+        Some(0) => Err(serde::de::Error::custom(
+            "max_threads must be at least 1 (leave it unset for one thread per CPU)",
+        )),
+        threads => Ok(threads),
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Where to send run notifications, and for which outcomes.
+///
+/// May be declared in a contract or project-wide in `pipa.toml`; a
+/// contract's `[notifications]` replaces the project's for its runs.
+///
+/// Example TOML:
+/// ```toml
+/// [[notifications.channels]]
+/// type = "slack"
+/// webhook_url = "${SLACK_WEBHOOK_URL}"
+/// on = ["failure", "quarantine"]
+///
+/// [[notifications.channels]]
+/// type = "email"
+/// smtp_host = "smtp.internal"
+/// from = "pipa@example.com"
+/// to = ["data-team@example.com"]
+/// on = ["failure"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Notifications {
+    #[serde(default)]
+    pub channels: Vec<NotificationChannel>,
+}
+
+/// One notification target and the outcomes it is sent for.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NotificationChannel {
+    /// Outcomes to notify on; defaults to `["failure"]`.
+    #[serde(default = "default_on")]
+    pub on: Vec<NotifyOn>,
+    #[serde(flatten)]
+    pub target: ChannelTarget,
+}
+
+/// A run outcome a channel can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyOn {
+    /// Every rule passed.
+    Success,
+    /// A rule failed or the run errored.
+    Failure,
+    /// Failing data was written to the quarantine.
+    Quarantine,
+}
+
+/// Notification backend, selected by `type`.
+///
+/// Webhook URLs and headers usually carry credentials, so they serialize
+/// as `<redacted>` (e.g. in `pipa contract show --json`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChannelTarget {
+    /// Slack incoming webhook.
+    Slack {
+        #[serde(serialize_with = "redacted")]
+        webhook_url: String,
+    },
+    /// Microsoft Teams incoming webhook.
+    Teams {
+        #[serde(serialize_with = "redacted")]
+        webhook_url: String,
+    },
+    /// JSON `POST` of the run summary to any URL.
+    Webhook {
+        #[serde(serialize_with = "redacted")]
+        url: String,
+        #[serde(default, serialize_with = "redacted_values")]
+        headers: BTreeMap<String, String>,
+    },
+    /// Plain-text email through an SMTP relay (no TLS or authentication).
+    Email {
+        smtp_host: String,
+        #[serde(default = "default_smtp_port")]
+        smtp_port: u16,
+        from: String,
+        to: Vec<String>,
+    },
+}
+
+fn redacted<S: Serializer>(_: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str("<redacted>")
+}
+
+fn redacted_values<S: Serializer>(
+    headers: &BTreeMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(headers.keys().map(|name| (name, "<redacted>")))
+}
+
+impl NotifyOn {
+    /// Outcome name as written in `on = [...]`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotifyOn::Success => "success",
+            NotifyOn::Failure => "failure",
+            NotifyOn::Quarantine => "quarantine",
+        }
+    }
+}
+
+impl ChannelTarget {
+    /// Backend name as written in `type = "..."`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ChannelTarget::Slack { .. } => "slack",
+            ChannelTarget::Teams { .. } => "teams",
+            ChannelTarget::Webhook { .. } => "webhook",
+            ChannelTarget::Email { .. } => "email",
+        }
+    }
+}
+
+fn default_on() -> Vec<NotifyOn> {
+    vec![NotifyOn::Failure]
+}
+
+fn default_smtp_port() -> u16 {
+    25
+}
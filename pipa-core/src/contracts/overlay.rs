@@ -0,0 +1,229 @@
+//! Environment overlays for contract TOML.
+//!
+//! With an environment selected (`pipa --env prod`, or `PIPA_ENV=prod`),
+//! contract `customers` is loaded from `contracts/customers.toml` merged
+//! with `contracts/customers.prod.toml`, when that file exists. An overlay
+//! is a partial contract:
+//! - tables merge key by key, so `[source] location = ...` replaces only
+//!   the location,
+//! - `[[columns]]` and `[[sources]]` entries update the base entry with
+//!   the same `name`; entries with a new name are appended,
+//! - `validation` rules update the base rule with the same `rule` name
+//!   (repeated rules in order) and override only the parameters given;
+//!   new rules are appended,
+//! - any other value, arrays included, replaces the base value.
+//!
+//! Example TOML (`contracts/customers.prod.toml`):
+//! ```toml
+//! [source]
+//! location = "s3://prod-landing/customers.csv"
+//! profile = "prod_s3"
+//!
+//! [file]
+//! validation = [{ rule = "row_count", min = 100000 }]
+//! ```
+//!
+//! Overlays are merged before `${NAME}` interpolation, so they may also
+//! override `[vars]`. The merged TOML is what gets hashed as the run's
+//! `contract_hash`. Overlay files are not contracts of their own: they are
+//! left out of contract listings (`pipa contract list`, `pipa run --all`).
+
+use super::schema::ContractParseError;
+use std::path::{Path, PathBuf};
+
+/// Environment variable selecting the overlay environment.
+pub const ENV_VAR: &str = "PIPA_ENV";
+
+/// The selected environment, if any.
+pub fn selected_env() -> Option<String> {
+    std::env::var(ENV_VAR).ok().filter(|env| !env.is_empty())
+}
+
+/// The overlay of contract `name` for `env`: `contracts/<name>.<env>.toml`.
+pub fn overlay_path(name: &str, env: &str) -> PathBuf {
+    crate::config::contracts_dir().join(format!("{}.{}.toml", name, env))
+}
+
+/// Whether `path` is an overlay, i.e. `<name>.<env>.toml` next to a
+/// `<name>.toml`.
+pub fn is_overlay(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit_once('.'))
+        .is_some_and(|(name, _)| path.with_file_name(format!("{}.toml", name)).exists())
+}
+
+/// Apply the selected environment's overlay to the TOML of contract `name`.
+///
+/// Returns the TOML unchanged (and no path) when no environment is
+/// selected or the contract has no overlay for it.
+///
+/// # Errors
+/// Returns a `ContractParseError` for the overlay if it can't be read or
+/// either side isn't valid TOML.
+pub fn apply_overlay(
+    name: &str,
+    toml_str: String,
+) -> Result<(String, Option<PathBuf>), ContractParseError> {
+    let Some(env) = selected_env() else {
+        return Ok((toml_str, None));
+    };
+    let path = overlay_path(name, &env);
+    if !path.exists() {
+        return Ok((toml_str, None));
+    }
+    let error = |message: String| ContractParseError {
+        path: Some(path.display().to_string()),
+        message,
+        line: None,
+        column: None,
+        field: None,
+    };
+    let overlay = std::fs::read_to_string(&path)
+        .map_err(|e| error(format!("Failed to read overlay: {}", e)))?;
+    let merged = merge_overlay(&toml_str, &overlay).map_err(|e| ContractParseError {
+        path: Some(path.display().to_string()),
+        ..e
+    })?;
+    Ok((merged, Some(path)))
+}
+
+/// Merge `overlay` TOML over `base` TOML, as described in the module docs.
+///
+/// # Errors
+/// Returns a `ContractParseError` (without a path) if either side isn't
+/// valid TOML.
+pub fn merge_overlay(base: &str, overlay: &str) -> Result<String, ContractParseError> {
+    let mut merged: toml::Table =
+        toml::from_str(base).map_err(|e| ContractParseError::new(base, &e))?;
+    let overlay: toml::Table =
+        toml::from_str(overlay).map_err(|e| ContractParseError::new(overlay, &e))?;
+    merge_tables(&mut merged, overlay);
+    toml::to_string(&merged).map_err(|e| ContractParseError {
+        path: None,
+        message: e.to_string(),
+        line: None,
+        column: None,
+        field: None,
+    })
+}
+
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        let id = match key.as_str() {
+            "columns" | "sources" => Some("name"),
+            "validation" => Some("rule"),
+            _ => None,
+        };
+        match (base.get_mut(&key), value, id) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay), _) => {
+                merge_tables(base, overlay)
+            }
+            (Some(toml::Value::Array(base)), toml::Value::Array(overlay), Some(id)) => {
+                merge_entries(base, overlay, id)
+            }
+            (_, value, _) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Merge each overlay table into the first unmatched base table with the
+/// same `id` value, appending the ones without a match.
+fn merge_entries(base: &mut Vec<toml::Value>, overlay: Vec<toml::Value>, id: &str) {
+    let mut matched = vec![false; base.len()];
+    for entry in overlay {
+        let target = entry.get(id).and_then(|key| {
+            (0..matched.len()).find(|&i| !matched[i] && base[i].get(id) == Some(key))
+        });
+        match (target, entry) {
+            (Some(i), toml::Value::Table(entry)) => {
+                matched[i] = true;
+                if let toml::Value::Table(target) = &mut base[i] {
+                    merge_tables(target, entry);
+                }
+            }
+            (_, entry) => base.push(entry),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::{ContractType, parse_contract};
+
+    const BASE: &str = r#"[contract]
+name = "customers"
+version = "1.0"
+tags = ["pii"]
+
+[file]
+validation = [{ rule = "row_count", min = 1, max = 1000 }]
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }, { rule = "unique" }]
+
+[[columns]]
+name = "age"
+validation = [{ rule = "range", min = 0, max = 120 }]
+
+[source]
+type = "local"
+location = "data/customers.csv"
+"#;
+
+    const PROD: &str = r#"[source]
+type = "s3"
+location = "s3://prod-landing/customers.csv"
+profile = "prod_s3"
+
+[file]
+validation = [{ rule = "row_count", min = 100000 }]
+
+[[columns]]
+name = "age"
+validation = [{ rule = "range", max = 110 }]
+
+[[columns]]
+name = "email"
+validation = [{ rule = "not_null" }]
+"#;
+
+    #[test]
+    fn overlay_overrides_source_and_thresholds() {
+        let merged = parse_contract(&merge_overlay(BASE, PROD).unwrap()).unwrap();
+
+        let source = merged.source.unwrap();
+        assert_eq!(source.r#type, "s3");
+        assert_eq!(
+            source.location.as_deref(),
+            Some("s3://prod-landing/customers.csv")
+        );
+        assert_eq!(source.profile.as_deref(), Some("prod_s3"));
+        assert_eq!(merged.contract.tags, vec!["pii"]);
+
+        assert!(matches!(
+            merged.file.unwrap().validation[..],
+            [ContractType::RowCount {
+                min: 100000,
+                max: Some(1000)
+            }]
+        ));
+        let columns: Vec<_> = merged.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(columns, vec!["id", "age", "email"]);
+        assert_eq!(merged.columns[0].validation.len(), 2);
+        assert!(matches!(
+            merged.columns[1].validation[..],
+            [ContractType::Range { min: 0, max: 110 }]
+        ));
+    }
+
+    #[test]
+    fn invalid_overlay_is_reported() {
+        let error = merge_overlay(BASE, "[source\n").err().unwrap();
+        assert_eq!(error.line, Some(1));
+    }
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::Duration;
+
+/// How often to retry a run that fails with a transient error.
+///
+/// Errors reading the source (connector failures, timeouts, I/O) are
+/// retried up to `retries` more times, waiting `delay` between attempts;
+/// rule failures and configuration errors are not. These are whole-run
+/// retries, on top of the per-request retries connectors already make
+/// (`connectors::retry`). Each retry is logged as a `run_retried` audit
+/// event. `pipa run --retries N --retry-delay D` overrides the contract's
+/// settings.
+///
+/// Example TOML:
+/// ```toml
+/// [retry]
+/// retries = 3
+/// delay = "30s"   # also "500ms", "5m", "1h", or plain seconds
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Retry {
+    /// Attempts after the first one.
+    pub retries: u32,
+    /// Wait between attempts; defaults to `DEFAULT_RETRY_DELAY`.
+    #[serde(
+        default = "default_delay",
+        deserialize_with = "deserialize_delay",
+        serialize_with = "serialize_delay"
+    )]
+    pub delay: Duration,
+}
+
+/// Wait between attempts when `[retry]` doesn't set `delay`.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+fn default_delay() -> Duration {
+    DEFAULT_RETRY_DELAY
+}
+
+/// Parse a delay such as `"30s"`, `"500ms"`, `"5m"`, `"1h"`, or `"30"`
+/// (seconds).
+pub fn parse_delay(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid delay '{}': expected e.g. 30s, 500ms, 5m", text))?;
+    match unit {
+        "ms" => Ok(Duration::from_millis(amount)),
+        "" | "s" => Ok(Duration::from_secs(amount)),
+        "m" => Ok(Duration::from_secs(amount * 60)),
+        "h" => Ok(Duration::from_secs(amount * 3600)),
+        _ => Err(format!(
+            "invalid delay unit '{}' in '{}': use ms, s, m, or h",
+            unit, text
+        )),
+    }
+}
+
+/// A delay as written in contracts: whole seconds as `"30s"`, else `"ms"`.
+pub fn format_delay(delay: Duration) -> String {
+    if delay.subsec_millis() == 0 {
+        format!("{}s", delay.as_secs())
+    } else {
+        format!("{}ms", delay.as_millis())
+    }
+}
+
+fn deserialize_delay<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse_delay(&text).map_err(serde::de::Error::custom)
+}
+
+fn serialize_delay<S: Serializer>(delay: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format_delay(*delay))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_delays_with_units() {
+        assert_eq!(parse_delay("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_delay("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_delay("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_delay("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_delay("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_delay("soon").is_err());
+        assert!(parse_delay("10d").is_err());
+        assert_eq!(format_delay(Duration::from_millis(1500)), "1500ms");
+    }
+
+    #[test]
+    fn delay_defaults_and_round_trips() {
+        let retry: Retry = toml::from_str("retries = 2").unwrap();
+        assert_eq!(retry.delay, DEFAULT_RETRY_DELAY);
+
+        let retry: Retry = toml::from_str("retries = 2\ndelay = \"5m\"").unwrap();
+        assert_eq!(
+            toml::to_string(&retry).unwrap(),
+            "retries = 2\ndelay = \"300s\"\n"
+        );
+        assert!(toml::from_str::<Retry>("retries = 2\ndelay = \"later\"").is_err());
+    }
+}
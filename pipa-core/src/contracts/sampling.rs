@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// Adaptive sampling for very large datasets.
+///
+/// When the parsed dataset has more than `min_rows` rows, row-level and
+/// threshold rules are evaluated on a random sample sized for the requested
+/// `confidence` and `margin`, and their results carry a confidence interval.
+/// A threshold rule whose interval straddles its threshold is re-run on the
+/// full data. Rules that can't be estimated from a sample (uniqueness, row
+/// counts, drift, custom rules, ...) always run on the full data.
+///
+/// Example TOML:
+/// ```toml
+/// [sampling]
+/// min_rows = 1_000_000
+/// confidence = 0.95
+/// margin = 0.01
+/// seed = 42
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Sampling {
+    /// Only sample datasets with more rows than this.
+    #[serde(default = "default_min_rows")]
+    pub min_rows: usize,
+    /// Confidence level of the reported intervals, in `(0, 1)`.
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+    /// Target half-width of the interval for a proportion; sets the sample size.
+    #[serde(default = "default_margin")]
+    pub margin: f64,
+    /// Seed for reproducible samples.
+    pub seed: Option<u64>,
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Self {
+            min_rows: default_min_rows(),
+            confidence: default_confidence(),
+            margin: default_margin(),
+            seed: None,
+        }
+    }
+}
+
+fn default_min_rows() -> usize {
+    1_000_000
+}
+
+fn default_confidence() -> f64 {
+    0.95
+}
+
+fn default_margin() -> f64 {
+    0.01
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// When `pipa scheduler start` runs a contract.
+///
+/// `cron` is a five-field cron expression evaluated in UTC (see
+/// `engine::cron`).
+///
+/// Example TOML:
+/// ```toml
+/// [schedule]
+/// cron = "0 6 * * *"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Schedule {
+    pub cron: String,
+}
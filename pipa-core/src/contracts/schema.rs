@@ -1,18 +1,33 @@
-use serde::Deserialize;
+use crate::logging::error::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
-use super::{column::ColumnContracts, compound::CompoundUnique, file::FileContracts};
+use super::{
+    column::ColumnContracts, compound::CompoundUnique, file::FileContracts, headers::Headers,
+    limits::Limits, notifications::Notifications, retry::Retry, sampling::Sampling,
+    schedule::Schedule, sources::Combine, sources::NamedSource, streaming::Streaming,
+    transform::Transform, vars,
+};
 
 /// High-level metadata about a contract.
 ///
 /// - `name` and `version` identify the contract.
 /// - `tags` can be used for grouping or filtering, but are currently unused.
-#[derive(Debug, Deserialize)]
+/// - `strict_types` requires every column to declare a `dtype` and emits a
+///   schema conformance result per column (declared vs actual dtype).
+/// - `strict_rules` fails the run when a rule is declared where the engine
+///   has no implementation for it (e.g. `exists`, or a column rule under
+///   `[file]`). Otherwise such rules are reported as `rule_unrecognized`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Contract {
     pub name: String,
     pub version: String,
-    #[allow(dead_code)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub strict_types: bool,
+    #[serde(default)]
+    pub strict_rules: bool,
 }
 
 /// Input source definition for a contract.
@@ -20,38 +35,250 @@ pub struct Contract {
 /// - `type`: connector type (e.g., "s3", "local").
 /// - `location`: path/URI to the data.
 /// - `profile`: optional profile name for credentials/config.
-#[derive(Debug, Deserialize)]
+/// - `format`: optional table format; `"delta"` reads the Delta Lake table
+///   rooted at `location` (see `connectors::delta`), and `"fixed_width"`
+///   reads fixed-width text laid out by the `start` and `width` of each
+///   `[[columns]]` entry.
+/// - `options`: parsing options applied when the driver loads the data.
+/// - `csv`: the CSV dialect (delimiter, quoting, header, ...) of CSV data.
+/// - `on_success`: what happens to the source file once its data has been
+///   written to the destination; `archive` moves it under
+///   `archive_location` (same connector and profile).
+/// - `on_duplicate`: what happens when the file's content was already
+///   delivered in an earlier run.
+/// - `archive_member`: for a zip or tar archive, a glob selecting the
+///   members to validate (default: every file in it).
+/// - `archive_mode`: whether an archive's members are validated one by
+///   one or as a concatenation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Source {
     #[serde(rename = "type")]
     pub r#type: String,
     pub location: Option<String>,
     pub profile: Option<String>,
+    pub format: Option<String>,
+    #[serde(default)]
+    pub options: SourceOptions,
+    #[serde(default)]
+    pub csv: CsvDialect,
+    #[serde(default)]
+    pub on_success: OnSuccess,
+    pub archive_location: Option<String>,
+    #[serde(default)]
+    pub on_duplicate: OnDuplicate,
+    pub archive_member: Option<String>,
+    #[serde(default)]
+    pub archive_mode: ArchiveMode,
+}
+
+/// What happens to a source file after a successful movement.
+///
+/// Example TOML:
+/// ```toml
+/// [source]
+/// type = "s3"
+/// location = "s3://landing/orders.csv"
+/// profile = "landing"
+/// on_success = "archive"
+/// archive_location = "s3://landing/processed/"
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnSuccess {
+    /// Leave the file in place (the default).
+    #[default]
+    Keep,
+    /// Delete the file.
+    Delete,
+    /// Copy the file to `archive_location`, then delete it.
+    Archive,
+}
+
+/// What happens to a source file whose exact content (by SHA-256) passed
+/// validation in an earlier run, e.g. when upstream re-sends yesterday's
+/// batch.
+///
+/// Earlier deliveries are looked up in the run-history store (see
+/// `history`), which records every source file whose data passed.
+///
+/// Example TOML:
+/// ```toml
+/// [source]
+/// type = "s3"
+/// location = "s3://landing/orders.csv"
+/// profile = "landing"
+/// on_duplicate = "skip"
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnDuplicate {
+    /// Validate and move it again (the default).
+    #[default]
+    Allow,
+    /// Don't validate or move it; the run passes with no results.
+    Skip,
+    /// Fail the run with `ValidationError::DuplicateSource`.
+    Fail,
+}
+
+/// How the members of an archive source (`.zip`, `.tar`, `.tar.gz`,
+/// `.tgz`) are validated.
+///
+/// Members matching `archive_member` are extracted in memory and read
+/// with the driver for their own extension. The archive as a whole is
+/// what `on_success` and `on_duplicate` apply to.
+///
+/// Example TOML:
+/// ```toml
+/// [source]
+/// type = "s3"
+/// location = "s3://vendor/daily-{{ ds_nodash }}.zip"
+/// profile = "vendor"
+/// archive_member = "orders_*.csv"
+/// archive_mode = "concat"
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveMode {
+    /// Validate and move each member on its own, like the objects of a
+    /// glob source (the default).
+    #[default]
+    Each,
+    /// Stack the members' rows (they must have the same columns), then
+    /// validate and move them as one input.
+    Concat,
+}
+
+/// Parsing options for a source (`[source.options]`).
+///
+/// Locale-aware numbers: with `decimal_separator` or `thousands_separator`
+/// set, CSV columns whose every value is a number in that format (e.g.
+/// `1.234,56`) are parsed as Int64/Float64; other columns stay String.
+///
+/// CSV data in another encoding than UTF-8 is transcoded before parsing:
+/// `encoding` names it (a WHATWG label such as `latin1`, `windows-1252`,
+/// or `utf-16le`), or, when unset or `auto`, it is detected (see
+/// `drivers::csv::detect_encoding`). Bytes the encoding can't decode fail
+/// the load rather than being replaced.
+///
+/// `kafka` sources read up to `max_messages` messages, waiting at most
+/// `window_secs` (see `connectors::kafka`).
+///
+/// Example TOML:
+/// ```toml
+/// [source.options]
+/// delimiter = ";"
+/// decimal_separator = ","
+/// thousands_separator = "."
+/// encoding = "latin1"
+/// ```
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct SourceOptions {
+    /// CSV field delimiter (default `,`).
+    pub delimiter: Option<char>,
+    /// CSV text encoding (default `auto`: detected).
+    pub encoding: Option<String>,
+    /// Decimal separator for numbers (default `.`).
+    pub decimal_separator: Option<char>,
+    /// Thousands (grouping) separator for numbers, if any.
+    pub thousands_separator: Option<char>,
+    /// Kafka: most messages to read (default 1000).
+    pub max_messages: Option<usize>,
+    /// Kafka: longest time to wait for messages, in seconds (default 30).
+    pub window_secs: Option<u64>,
+    /// Kafka: read the oldest retained messages instead of new ones.
+    pub from_beginning: Option<bool>,
+}
+
+impl SourceOptions {
+    /// Whether numbers need locale-aware parsing.
+    pub fn has_locale(&self) -> bool {
+        self.decimal_separator.is_some_and(|c| c != '.') || self.thousands_separator.is_some()
+    }
+}
+
+/// CSV dialect of a source (`[source.csv]`), for feeds that aren't
+/// comma-separated with a header row.
+///
+/// Without a header row (`has_header = false`) columns are named
+/// `column_1`, `column_2`, ... in file order; declare `[[columns]]` under
+/// those names.
+///
+/// Example TOML:
+/// ```toml
+/// [source.csv]
+/// delimiter = "|"
+/// quote_char = "'"
+/// has_header = false
+/// skip_rows = 2
+/// null_values = ["", "NULL", "N/A"]
+/// comment_char = "#"
+/// ```
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct CsvDialect {
+    /// Field delimiter (default `,`; overrides `[source.options] delimiter`).
+    pub delimiter: Option<char>,
+    /// Quote character (default `"`).
+    pub quote_char: Option<char>,
+    /// Whether the first row after `skip_rows` names the columns (default
+    /// `true`).
+    pub has_header: Option<bool>,
+    /// Rows to skip before the header (default 0).
+    pub skip_rows: Option<usize>,
+    /// Values read as null in every column.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub null_values: Vec<String>,
+    /// Lines starting with this character are skipped.
+    pub comment_char: Option<char>,
 }
 
 /// Output destination definition.
 ///
 /// Similar to `Source`, but may include a `format` override
-/// (e.g., force output as CSV even if input was Parquet).
-#[derive(Debug, Deserialize, Clone)]
+/// (e.g., force output as CSV even if input was Parquet) and a
+/// `filename_template` naming the written files (see `contracts::filename`).
+/// `partition_by` splits the data into hive-style `<column>=<value>/`
+/// directories, one file per partition:
+///
+/// ```toml
+/// [destination]
+/// type = "s3"
+/// location = "s3://lake/orders/"
+/// format = "parquet"
+/// partition_by = ["country", "load_date"]
+/// ```
+///
+/// With `annotate = true`, the written data gets `_pipa_contract`,
+/// `_pipa_run_id`, and `_pipa_validated_at` columns recording where it came
+/// from.
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Destination {
     #[serde(rename = "type")]
     pub r#type: String,
     pub location: Option<String>,
     pub profile: Option<String>,
     pub format: Option<String>,
+    pub filename_template: Option<String>,
+    #[serde(default)]
+    pub partition_by: Vec<String>,
+    #[serde(default)]
+    pub annotate: bool,
 }
 
 /// Quarantine sink definition.
 ///
 /// Used to redirect invalid rows/files. Mirrors `Destination`
 /// but semantically distinct.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Quarantine {
     #[serde(rename = "type")]
     pub r#type: String,
     pub location: Option<String>,
     pub profile: Option<String>,
     pub format: Option<String>,
+    pub filename_template: Option<String>,
+    #[serde(default)]
+    pub annotate: bool,
 }
 
 /// The full schema contract definition.
@@ -61,36 +288,313 @@ pub struct Quarantine {
 /// - `contract`: metadata
 /// - `file`: file-level rules
 /// - `columns`: column-level rules
+/// - `headers`: optional normalization of incoming column names (see
+///   `contracts::headers`)
+/// - `mapping`: optional renames from incoming to canonical column names,
+///   applied as the data is loaded, before any rule runs
+/// - `transform`: optional clean-up steps applied before any rule runs (see
+///   `contracts::transform`)
 /// - `compound_unique`: multi-column uniqueness rules
 /// - `source`, `destination`, `quarantine`: I/O configuration
-#[derive(Debug, Deserialize)]
+/// - `sources`, `combine`: several inputs combined into one dataset, in
+///   place of `source` (see `contracts::sources`)
+/// - `limits`: optional per-run resource limits
+/// - `sampling`: optional adaptive sampling for very large datasets
+/// - `schedule`: optional cron schedule for `pipa scheduler start`
+/// - `streaming`: optional chunked validation for inputs larger than memory
+/// - `notifications`: optional channels told about run outcomes, in place of
+///   the project's (see `contracts::notifications`)
+///
+/// A `[vars]` table may also be present; its values (and environment
+/// variables) fill `${NAME}` references when the contract is parsed (see
+/// `contracts::vars`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SchemaContracts {
     pub contract: Contract,
     pub file: Option<FileContracts>,
     pub columns: Vec<ColumnContracts>,
+    pub headers: Option<Headers>,
+    pub mapping: Option<BTreeMap<String, String>>,
+    pub transform: Option<Transform>,
     pub compound_unique: Option<Vec<CompoundUnique>>,
     pub source: Option<Source>,
+    pub sources: Option<Vec<NamedSource>>,
+    pub combine: Option<Combine>,
     pub destination: Option<Destination>,
     pub quarantine: Option<Quarantine>,
+    pub limits: Option<Limits>,
+    pub retry: Option<Retry>,
+    pub sampling: Option<Sampling>,
+    pub schedule: Option<Schedule>,
+    pub streaming: Option<Streaming>,
+    pub notifications: Option<Notifications>,
+}
+
+/// Why a contract couldn't be loaded, and where in the TOML.
+///
+/// `line` and `column` are 1-based. `field` is the dotted path of the key
+/// the error points at, with `[[array]]` tables indexed from 0 (e.g.
+/// `columns[1].validation`), or the missing field for `missing field` errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractParseError {
+    pub path: Option<String>,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub field: Option<String>,
+}
+
+impl ContractParseError {
+    pub(crate) fn new(toml_str: &str, error: &toml::de::Error) -> Self {
+        let message = error.message().trim().to_string();
+        let Some(span) = error.span() else {
+            return Self {
+                path: None,
+                message,
+                line: None,
+                column: None,
+                field: None,
+            };
+        };
+
+        let before = &toml_str[..span.start.min(toml_str.len())];
+        let line = before.matches('\n').count();
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        let mut field = field_at(toml_str, line);
+        if let Some(missing) = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.split('`').next())
+        {
+            field = Some(match field {
+                Some(table) => format!("{}.{}", table, missing),
+                None => missing.to_string(),
+            });
+        }
+
+        Self {
+            path: None,
+            message,
+            line: Some(line + 1),
+            column: Some(column),
+            field,
+        }
+    }
+}
+
+impl std::fmt::Display for ContractParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.path, self.line.zip(self.column)) {
+            (Some(path), Some((line, column))) => write!(f, "{}:{}:{}: ", path, line, column)?,
+            (Some(path), None) => write!(f, "{}: ", path)?,
+            (None, Some((line, column))) => write!(f, "line {}, column {}: ", line, column)?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(field) = &self.field {
+            write!(f, " (in `{}`)", field)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ContractParseError {}
+
+impl From<ContractParseError> for ValidationError {
+    fn from(error: ContractParseError) -> Self {
+        ValidationError::ContractParse(error.to_string())
+    }
+}
+
+/// Dotted path of the key on `line` (0-based), or of the table it is in.
+fn field_at(toml_str: &str, line: usize) -> Option<String> {
+    let mut table = String::new();
+    let mut array_counts: HashMap<String, usize> = HashMap::new();
+
+    for (i, text) in toml_str.lines().enumerate().take(line + 1) {
+        let text = text.trim();
+        if let Some(name) = text.strip_prefix("[[") {
+            let name = name.split("]]").next().unwrap_or("").trim().to_string();
+            let count = array_counts.entry(name.clone()).or_insert(0);
+            table = format!("{}[{}]", name, count);
+            *count += 1;
+        } else if let Some(name) = text.strip_prefix('[') {
+            table = name.split(']').next().unwrap_or("").trim().to_string();
+        } else if i == line
+            && let Some((key, _)) = text.split_once('=')
+            && !key.trim().is_empty()
+            && !key.contains(['{', '"', '#'])
+        {
+            let key = key.trim();
+            return Some(if table.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", table, key)
+            });
+        }
+    }
+
+    (!table.is_empty()).then_some(table)
+}
+
+/// Parse contract TOML, reporting where it fails to match the schema.
+///
+/// `${NAME}` references are resolved from `[vars]` and the environment
+/// (see `contracts::vars`) after the TOML has been checked against the
+/// schema, so schema errors still point at a line and column.
+pub fn parse_contract(toml_str: &str) -> Result<SchemaContracts, ContractParseError> {
+    let contracts = toml::from_str(toml_str).map_err(|e| ContractParseError::new(toml_str, &e))?;
+    if !vars::has_placeholders(toml_str) {
+        return Ok(contracts);
+    }
+
+    let mut table: toml::Table =
+        toml::from_str(toml_str).map_err(|e| ContractParseError::new(toml_str, &e))?;
+    vars::interpolate(&mut table).map_err(|e| ContractParseError {
+        path: None,
+        message: e.message,
+        line: None,
+        column: None,
+        field: Some(e.field),
+    })?;
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| ContractParseError {
+            path: None,
+            message: e.message().trim().to_string(),
+            line: None,
+            column: None,
+            field: None,
+        })
 }
 
 /// Load the TOML contract file that matches the data filename.
 ///
 /// - Derives the contract filename from the data file stem.
-/// - Reads `contracts/{stem}.toml`.
-/// - Panics if the file is missing or invalid.
+/// - Reads `contracts/{stem}.toml`, merged with the selected environment's
+///   overlay (see `contracts::overlay`).
+/// - Returns a `ContractParseError` (with the contract path) if the file is
+///   missing or invalid.
 ///
 /// Example:
 /// ```ignore
-/// let schema = load_contract_for_file(Path::new("people.csv"));
+/// let schema = load_contract_for_file(Path::new("people.csv"))?;
 /// // loads "contracts/people.toml"
 /// ```
-pub fn load_contract_for_file(path: &Path) -> SchemaContracts {
-    let stem = path.file_stem().unwrap().to_str().unwrap();
-    let contract_path = format!("contracts/{}.toml", stem);
+pub fn load_contract_for_file(path: &Path) -> Result<SchemaContracts, ContractParseError> {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| ContractParseError {
+            path: None,
+            message: format!("Invalid path: {:?}", path),
+            line: None,
+            column: None,
+            field: None,
+        })?;
+    let contract_path = crate::config::contract_path(stem).display().to_string();
+
+    let toml_str = std::fs::read_to_string(&contract_path).map_err(|_| ContractParseError {
+        path: Some(contract_path.clone()),
+        message: "Missing contract file".to_string(),
+        line: None,
+        column: None,
+        field: None,
+    })?;
+
+    let (toml_str, overlay) = super::overlay::apply_overlay(stem, toml_str)?;
+    parse_contract(&toml_str).map_err(|e| match overlay {
+        // Positions would point into the merged TOML, not either file
+        Some(overlay) => ContractParseError {
+            path: Some(format!("{} with {}", contract_path, overlay.display())),
+            line: None,
+            column: None,
+            ..e
+        },
+        None => ContractParseError {
+            path: Some(contract_path),
+            ..e
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = r#"[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[[columns]]
+name = "amount"
+validation = [{ rule = "range", min = "zero", max = 10 }]
+"#;
+
+    #[test]
+    fn parse_error_points_at_field() {
+        let error = parse_contract(CONTRACT).err().unwrap();
+        assert_eq!(error.line, Some(12));
+        assert_eq!(error.field.as_deref(), Some("columns[1].validation"));
+        assert!(error.to_string().starts_with("line 12, column "));
+    }
+
+    #[test]
+    fn missing_field_is_named() {
+        let error = parse_contract("columns = []\n\n[contract]\nname = \"orders\"\ntags = []\n")
+            .err()
+            .unwrap();
+        assert!(error.message.contains("missing field `version`"));
+        assert_eq!(error.field.as_deref(), Some("contract.version"));
+    }
+
+    #[test]
+    fn syntax_error_has_location() {
+        let error = parse_contract("[contract]\nname = \"orders\n")
+            .err()
+            .unwrap();
+        assert_eq!(error.line, Some(2));
+        assert!(error.column.is_some());
+        assert!(matches!(
+            ValidationError::from(error),
+            ValidationError::ContractParse(_)
+        ));
+    }
+
+    #[test]
+    fn serializes_rules_with_parameters_and_redacts_webhooks() {
+        let contract = parse_contract(
+            r#"[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "amount"
+validation = [{ rule = "not_null" }, { rule = "range", min = 0, max = 10 }]
 
-    let toml_str = std::fs::read_to_string(&contract_path)
-        .unwrap_or_else(|_| panic!("Missing contract file: {}", contract_path));
+[[notifications.channels]]
+type = "slack"
+webhook_url = "https://hooks.example/T0/secret"
+"#,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&contract).unwrap();
 
-    toml::from_str(&toml_str).expect("Failed to parse contract TOML")
+        assert_eq!(
+            json["columns"][0]["validation"],
+            serde_json::json!([
+                { "rule": "not_null" },
+                { "rule": "range", "min": 0, "max": 10 },
+            ])
+        );
+        assert_eq!(
+            json["notifications"]["channels"][0]["webhook_url"],
+            "<redacted>"
+        );
+    }
 }
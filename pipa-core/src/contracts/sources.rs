@@ -0,0 +1,72 @@
+use super::schema::Source;
+use serde::{Deserialize, Serialize};
+
+/// One of several inputs validated together (`[[sources]]`).
+///
+/// Takes every `[source]` key plus a `name`, which identifies the input in
+/// audit entries and suffixes its clashing columns in a join.
+///
+/// Example TOML:
+/// ```toml
+/// [[sources]]
+/// name = "header"
+/// type = "local"
+/// location = "data/orders_header.csv"
+///
+/// [[sources]]
+/// name = "detail"
+/// type = "local"
+/// location = "data/orders_detail.csv"
+///
+/// [combine]
+/// mode = "join"
+/// on = ["order_id"]
+/// how = "left"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NamedSource {
+    pub name: String,
+    #[serde(flatten)]
+    pub source: Source,
+}
+
+/// How `[[sources]]` are combined into the one dataset the rules see
+/// (`[combine]`; default: concat).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Combine {
+    #[serde(default)]
+    pub mode: CombineMode,
+    /// Join key columns, present in every source (join only).
+    #[serde(default)]
+    pub on: Vec<String>,
+    /// Join type, applied left to right in `[[sources]]` order (join only).
+    #[serde(default)]
+    pub how: JoinHow,
+}
+
+/// `[combine] mode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CombineMode {
+    /// Stack the rows of every source; all sources must have the same
+    /// column names (columns follow the first source's order).
+    #[default]
+    Concat,
+    /// Join the sources on `on`. A non-key column already present from an
+    /// earlier source gets a `_<name>` suffix, e.g. `amount_detail`.
+    Join,
+}
+
+/// `[combine] how`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinHow {
+    /// Keep rows whose key is in both sides.
+    #[default]
+    Inner,
+    /// Keep every row of the left side; unmatched right columns are null
+    /// (so `not_null` on a right column catches orphaned keys).
+    Left,
+    /// Keep every row of both sides.
+    Full,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Chunked validation for inputs larger than memory.
+///
+/// Inputs of at least `min_bytes` are read in batches of about `chunk_rows`
+/// rows rather than as one DataFrame. Row-level rules run on every batch;
+/// aggregate rules (means, deviations, uniqueness, completeness, drift) are
+/// computed from statistics accumulated across batches. `[sampling]` does
+/// not apply to streamed inputs: every row is validated.
+///
+/// Example TOML:
+/// ```toml
+/// [streaming]
+/// chunk_rows = 250_000
+/// min_bytes = 1_073_741_824
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Streaming {
+    /// Rows per batch.
+    #[serde(default = "default_chunk_rows")]
+    pub chunk_rows: usize,
+    /// Only stream inputs at least this large; smaller inputs are loaded whole.
+    #[serde(default)]
+    pub min_bytes: usize,
+}
+
+impl Streaming {
+    /// Whether an input of `bytes` bytes is streamed.
+    pub fn applies(&self, bytes: usize) -> bool {
+        bytes >= self.min_bytes
+    }
+}
+
+fn default_chunk_rows() -> usize {
+    100_000
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Clean-up steps applied to the data before any rule runs (`[transform]`).
+///
+/// Steps run in order on every frame read for the contract, after column
+/// names are normalized and mapped, so rules see the cleaned data and it's
+/// the cleaned data that is moved. A step naming a column the data doesn't
+/// have fails the run.
+///
+/// Example TOML:
+/// ```toml
+/// [transform]
+/// steps = [
+///   { op = "trim" },
+///   { op = "cast", column = "amount", dtype = "Float64" },
+///   { op = "fill_null", column = "country", value = "US" },
+///   { op = "drop", columns = ["internal_note"] },
+///   { op = "filter", expr = "status <> 'test'" },
+/// ]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Transform {
+    #[serde(default)]
+    pub steps: Vec<TransformStep>,
+}
+
+/// One `[transform]` step.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TransformStep {
+    /// Cast `column` to `dtype` (Polars spelling, e.g. `"Int64"`); values
+    /// that don't convert become null.
+    Cast { column: String, dtype: String },
+    /// Strip surrounding whitespace from `columns` (default: every String
+    /// column).
+    Trim {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        columns: Vec<String>,
+    },
+    /// Replace nulls in `column` with `value`.
+    FillNull { column: String, value: FillValue },
+    /// Remove `columns`.
+    Drop { columns: Vec<String> },
+    /// Keep only the rows where SQL expression `expr` is true, e.g.
+    /// `"amount > 0 AND status <> 'test'"`.
+    Filter { expr: String },
+}
+
+/// The value a `fill_null` step writes.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FillValue {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
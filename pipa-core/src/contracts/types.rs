@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use super::drift::{DriftMetric, DriftReference};
+use serde::{Deserialize, Serialize};
 
 /// Enumeration of all supported contract rules.
 ///
@@ -9,7 +10,7 @@ use serde::Deserialize;
 /// The `#[serde(tag = "rule", rename_all = "snake_case")]` attribute means:
 /// - Contracts must specify a field `"rule"` with the variant name.
 /// - Variant names are expected in snake_case (e.g., `not_null`, `max_length`).
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "rule", rename_all = "snake_case")]
 pub enum ContractType {
     // Column-level rules
@@ -38,6 +39,24 @@ pub enum ContractType {
     DateFormat {
         format: String,
     },
+    // Bounds are dates or relative to the run (`now`, `now-7d`); `format`
+    // defaults to the column's `date_format`
+    DateBetween {
+        min: Option<String>,
+        max: Option<String>,
+        format: Option<String>,
+    },
+    NoFutureDates {
+        format: Option<String>,
+    },
+
+    // Well-known string formats, checked natively rather than by regex
+    Uuid,
+    Email,
+    Url,
+    Ipv4,
+    Ipv6,
+    PhoneE164,
 
     // Statistical rules
     OutlierSigma {
@@ -64,6 +83,36 @@ pub enum ContractType {
         max: Option<usize>,
     },
     Exists,
+    ExpectedColumns {
+        columns: Vec<String>,
+        #[serde(default)]
+        ordered: bool,
+        #[serde(default)]
+        allow_extra_columns: bool,
+        #[serde(default)]
+        allow_missing_columns: bool,
+    },
+
+    // Change in a column statistic against the run history
+    Drift {
+        metric: DriftMetric,
+        max_change: f64,
+        #[serde(default)]
+        against: DriftReference,
+    },
+
+    // Sandboxed WebAssembly column rule
+    Wasm {
+        module: String,
+        fuel: Option<u64>,
+    },
+
+    // User-defined rules, resolved through `validators::custom`
+    Custom {
+        name: String,
+        #[serde(default)]
+        params: toml::Table,
+    },
 
     // Experimental / unused rules
     #[allow(dead_code)]
@@ -77,3 +126,42 @@ pub enum ContractType {
         max: i64,
     },
 }
+
+impl ContractType {
+    /// The rule name as written in contracts (`rule = "..."`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContractType::NotNull => "not_null",
+            ContractType::Unique => "unique",
+            ContractType::Pattern { .. } => "pattern",
+            ContractType::MaxLength { .. } => "max_length",
+            ContractType::Range { .. } => "range",
+            ContractType::InSet { .. } => "in_set",
+            ContractType::NotInSet { .. } => "not_in_set",
+            ContractType::Boolean => "boolean",
+            ContractType::Type { .. } => "type",
+            ContractType::DateFormat { .. } => "date_format",
+            ContractType::DateBetween { .. } => "date_between",
+            ContractType::NoFutureDates { .. } => "no_future_dates",
+            ContractType::Uuid => "uuid",
+            ContractType::Email => "email",
+            ContractType::Url => "url",
+            ContractType::Ipv4 => "ipv4",
+            ContractType::Ipv6 => "ipv6",
+            ContractType::PhoneE164 => "phone_e164",
+            ContractType::OutlierSigma { .. } => "outlier_sigma",
+            ContractType::Distinctness { .. } => "distinctness",
+            ContractType::Completeness { .. } => "completeness",
+            ContractType::MeanBetween { .. } => "mean_between",
+            ContractType::StdevBetween { .. } => "stdev_between",
+            ContractType::RowCount { .. } => "row_count",
+            ContractType::Exists => "exists",
+            ContractType::ExpectedColumns { .. } => "expected_columns",
+            ContractType::Drift { .. } => "drift",
+            ContractType::Wasm { .. } => "wasm",
+            ContractType::Custom { .. } => "custom",
+            ContractType::MinBetween { .. } => "min_between",
+            ContractType::MaxBetween { .. } => "max_between",
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Variable interpolation for contract TOML.
+//!
+//! Any string value may reference `${NAME}`, resolved when the contract is
+//! loaded: first from the contract's `[vars]` table, then from the
+//! environment. `${NAME:-default}` falls back to `default` when neither
+//! defines `NAME`, and `$${` writes a literal `${`; `${` not followed by a
+//! variable name and `}` is kept as written. `[vars]` values may
+//! themselves reference environment variables (but not other vars).
+//!
+//! Example TOML:
+//! ```toml
+//! [vars]
+//! bucket = "landing-${DEPLOY_ENV:-dev}"
+//!
+//! [source]
+//! type = "s3"
+//! location = "s3://${bucket}/${REGION}/customers.csv"
+//! ```
+//!
+//! An unresolved reference fails the load instead of reaching a connector
+//! as a literal `${...}`.
+
+use std::collections::HashMap;
+
+/// A `${...}` reference that couldn't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarError {
+    /// Dotted path of the value holding the reference, e.g. `source.location`.
+    pub field: String,
+    pub message: String,
+}
+
+/// Whether `text` contains a `${` reference (escaped `$${` excluded).
+pub fn has_placeholders(text: &str) -> bool {
+    !placeholders(text).is_empty()
+}
+
+/// Names referenced as `${NAME}` or `${NAME:-default}` in `text`, in order
+/// of first appearance.
+pub fn placeholders(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let escaped = start > 0 && rest.as_bytes()[start - 1] == b'$';
+        rest = &rest[start + 2..];
+        if escaped {
+            continue;
+        }
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = rest[..end].split(":-").next().unwrap_or_default();
+        if is_name(name) && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Resolve every `${...}` reference in the string values of `table`,
+/// removing its `[vars]` section.
+pub fn interpolate(table: &mut toml::Table) -> Result<(), VarError> {
+    let mut vars = HashMap::new();
+    if let Some(declared) = table.remove("vars") {
+        let toml::Value::Table(declared) = declared else {
+            return Err(VarError {
+                field: "vars".to_string(),
+                message: "`vars` must be a table".to_string(),
+            });
+        };
+        for (name, value) in declared {
+            let field = format!("vars.{}", name);
+            let toml::Value::String(value) = value else {
+                return Err(VarError {
+                    field,
+                    message: format!("variable `{}` must be a string", name),
+                });
+            };
+            let value = substitute(&value, &HashMap::new())
+                .map_err(|message| VarError { field, message })?;
+            vars.insert(name, value);
+        }
+    }
+
+    for (key, value) in table.iter_mut() {
+        interpolate_value(value, key, &vars)?;
+    }
+    Ok(())
+}
+
+fn interpolate_value(
+    value: &mut toml::Value,
+    field: &str,
+    vars: &HashMap<String, String>,
+) -> Result<(), VarError> {
+    match value {
+        toml::Value::String(text) => {
+            *text = substitute(text, vars).map_err(|message| VarError {
+                field: field.to_string(),
+                message,
+            })?;
+        }
+        toml::Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                interpolate_value(item, &format!("{}[{}]", field, i), vars)?;
+            }
+        }
+        toml::Value::Table(entries) => {
+            for (key, entry) in entries.iter_mut() {
+                interpolate_value(entry, &format!("{}.{}", field, key), vars)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace the references in `text`; errors name the unresolved variable.
+fn substitute(text: &str, vars: &HashMap<String, String>) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix("${") {
+            out.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let Some(body) = after.strip_prefix('{') else {
+            out.push('$');
+            rest = after;
+            continue;
+        };
+        let reference = body.find('}').and_then(|end| {
+            let (name, default) = match body[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&body[..end], None),
+            };
+            is_name(name).then_some((end, name, default))
+        });
+        // Not a reference (e.g. a regex quantifier such as `${2}`): keep it.
+        let Some((end, name, default)) = reference else {
+            out.push_str("${");
+            rest = body;
+            continue;
+        };
+        let value = vars
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+            .or_else(|| default.map(str::to_string))
+            .ok_or_else(|| {
+                format!(
+                    "undefined variable `{}` (not in [vars] or the environment)",
+                    name
+                )
+            })?;
+        out.push_str(&value);
+        rest = &body[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::parse_contract;
+
+    const CONTRACT: &str = r#"
+[vars]
+bucket = "landing-${PIPA_TEST_UNSET_ENV:-dev}"
+region = "eu-west-1"
+
+[contract]
+name = "customers"
+version = "1.0"
+tags = ["${region}"]
+
+[[columns]]
+name = "id"
+validation = [{ rule = "pattern", pattern = "^[0-9]{3}$$${literal}" }]
+
+[source]
+type = "s3"
+location = "s3://${bucket}/${region}/customers.csv"
+"#;
+
+    #[test]
+    fn resolves_vars_defaults_and_escapes() {
+        let contracts = parse_contract(CONTRACT).unwrap();
+        let source = contracts.source.unwrap();
+        assert_eq!(
+            source.location.as_deref(),
+            Some("s3://landing-dev/eu-west-1/customers.csv")
+        );
+        assert_eq!(contracts.contract.tags, vec!["eu-west-1"]);
+        match &contracts.columns[0].validation[0] {
+            crate::contracts::ContractType::Pattern { pattern } => {
+                assert_eq!(pattern, "^[0-9]{3}$${literal}")
+            }
+            other => panic!("unexpected rule {:?}", other),
+        }
+    }
+
+    #[test]
+    fn environment_fills_references_not_in_vars() {
+        let path = std::env::var("PATH").unwrap();
+        let mut table: toml::Table = toml::from_str(r#"location = "${PATH}/x""#).unwrap();
+        interpolate(&mut table).unwrap();
+        assert_eq!(
+            table["location"].as_str(),
+            Some(format!("{}/x", path).as_str())
+        );
+    }
+
+    #[test]
+    fn undefined_reference_names_the_field() {
+        let error = parse_contract(
+            "columns = []\n\n[contract]\nname = \"c\"\nversion = \"1\"\ntags = []\n\n\
+             [source]\ntype = \"local\"\nlocation = \"${PIPA_TEST_UNSET_ENV}/x.csv\"\n",
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.field.as_deref(), Some("source.location"));
+        assert!(
+            error
+                .message
+                .contains("undefined variable `PIPA_TEST_UNSET_ENV`")
+        );
+    }
+
+    #[test]
+    fn finds_placeholders_but_not_escapes_or_quantifiers() {
+        assert_eq!(
+            placeholders("${A} $${B} x${2} ${C:-d} ${A}"),
+            vec!["A".to_string(), "C".to_string()]
+        );
+        assert!(!has_placeholders("^[0-9]{3}$"));
+    }
+}
@@ -1,32 +1,101 @@
 pub mod csv;
+pub mod fixed_width;
+pub mod headers;
+pub mod json;
 pub mod parquet;
+pub mod transform;
 
+use crate::contracts::column::ColumnContracts;
+use crate::contracts::{CsvDialect, SchemaContracts, SourceOptions};
 use anyhow::{Result, anyhow};
 use polars::prelude::*;
+use std::ops::ControlFlow;
+use std::path::Path;
 
 /// Trait that all drivers implement to load data from an in-memory byte slice.
 ///
 /// This abstraction allows the engine to support multiple file formats
 /// (CSV, Parquet, etc.) behind a uniform interface. Each driver is responsible
 /// for parsing raw bytes into a Polars `DataFrame`.
-pub trait Driver {
+///
+/// `load_path` reads from a file on disk instead (used for spooled
+/// downloads), so large inputs don't need to be held in memory first.
+/// `load_batches` streams such a file in batches of about `batch_rows`
+/// rows, for `[streaming]` contracts; `f` can stop it early with
+/// `ControlFlow::Break`.
+///
+/// Drivers are `Send + Sync` so a validation run can move across threads
+/// (e.g. `pipa run --all --jobs N`).
+pub trait Driver: Send + Sync {
     fn load(&self, data: &[u8]) -> Result<DataFrame>;
+    fn load_path(&self, path: &Path) -> Result<DataFrame>;
+    fn load_batches(
+        &self,
+        path: &Path,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()>;
 }
 
 /// Factory function to get the correct driver based on a file extension.
 ///
 /// # Arguments
-/// * `extension` - File extension string (e.g., `"csv"`, `"parquet"`).
+/// * `extension` - File extension string (e.g., `"csv"`, `"parquet"`,
+///   `"ndjson"`), or `"fixed_width"` for fixed-width sources.
+/// * `options` - Source parsing options (delimiter, encoding, number
+///   locale); only text formats use them.
+/// * `csv` - The source's CSV dialect (`[source.csv]`); only CSV uses it.
+/// * `columns` - The contract's `[[columns]]`; fixed-width text is read
+///   at their `start` and `width`.
 ///
 /// # Returns
 /// * `Box<dyn Driver>` - A boxed driver implementing the `Driver` trait.
 ///
 /// # Errors
-/// Returns an error if the extension is unsupported.
-pub fn get_driver(extension: &str) -> Result<Box<dyn Driver>> {
+/// Returns an error if the extension is unsupported, or the options or
+/// layout are invalid for it.
+pub fn get_driver(
+    extension: &str,
+    options: &SourceOptions,
+    csv: &CsvDialect,
+    columns: &[ColumnContracts],
+) -> Result<Box<dyn Driver>> {
     match extension {
-        "csv" => Ok(Box::new(csv::CsvDriver)),
+        "csv" => Ok(Box::new(csv::CsvDriver::new(options.clone(), csv.clone())?)),
+        "fixed_width" => Ok(Box::new(fixed_width::FixedWidthDriver::new(
+            columns, options,
+        )?)),
         "parquet" => Ok(Box::new(parquet::ParquetDriver)),
+        "json" | "ndjson" | "jsonl" => Ok(Box::new(json::JsonDriver)),
         _ => Err(anyhow!("Unsupported file extension: {}", extension)),
     }
 }
+
+/// `get_driver` for reading a contract's data: the frames it loads have
+/// their columns renamed per the contract's `[headers]`, `[mapping]`, and
+/// column `aliases` (see `headers::HeaderDriver`), then its `[transform]`
+/// steps applied (see `transform::TransformDriver`).
+///
+/// # Errors
+/// As `get_driver`.
+pub fn contract_driver(
+    extension: &str,
+    options: &SourceOptions,
+    csv: &CsvDialect,
+    contracts: &SchemaContracts,
+) -> Result<Box<dyn Driver>> {
+    let mut driver = get_driver(extension, options, csv, &contracts.columns)?;
+    let has_aliases = contracts.columns.iter().any(|c| !c.aliases.is_empty());
+    if contracts.headers.is_some() || contracts.mapping.is_some() || has_aliases {
+        driver = Box::new(headers::HeaderDriver::new(
+            driver,
+            contracts.headers.clone().unwrap_or_default(),
+            &contracts.mapping.clone().unwrap_or_default(),
+            &contracts.columns,
+        ));
+    }
+    if let Some(transform) = &contracts.transform {
+        driver = Box::new(transform::TransformDriver::new(driver, transform.clone()));
+    }
+    Ok(driver)
+}
@@ -1,36 +1,394 @@
 use super::Driver; // Trait that all drivers must implement
-use anyhow::Result; // Standardized error handling
+use crate::contracts::{CsvDialect, SourceOptions}; // Dialect, encoding, and number locale
+use anyhow::{Result, anyhow}; // Standardized error handling
+use encoding_rs::{DecoderResult, Encoding, UTF_8, UTF_16BE, UTF_16LE, WINDOWS_1252}; // Transcoding to UTF-8
 use polars::prelude::*; // Core Polars DataFrame types
 use polars_io::SerReader; // Trait for finishing a reader into a DataFrame
-use polars_io::prelude::CsvReadOptions; // Explicit import of CSV options
-use std::io::Cursor; // Wraps &[u8] into a reader
+use polars_io::prelude::{CsvParseOptions, CsvReadOptions}; // Explicit import of CSV options
+use std::borrow::Cow;
+use std::fs::File; // Spooled input on disk
+use std::io::{Cursor, Read, Write}; // In-memory reader, transcoding I/O
+use std::ops::ControlFlow; // Lets a batch callback stop reading early
+use std::path::Path;
+use tempfile::NamedTempFile; // UTF-8 copy of a spooled file in another encoding
+
+/// Bytes read from the start of a file on disk to detect its encoding.
+pub const DETECT_BYTES: usize = 64 * 1024;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
 
 /// CSV file driver
 ///
 /// Implements the `Driver` trait for CSV data sources.
 /// Loads CSV data from an in‑memory byte slice into a Polars `DataFrame`.
-pub struct CsvDriver;
+///
+/// `[source.options]` control the delimiter, the text encoding, and
+/// locale-aware number parsing (see `SourceOptions`), and `[source.csv]`
+/// the rest of the dialect: quoting, header, skipped rows, null values,
+/// and comments (see `CsvDialect`). Data in another encoding than UTF-8 is
+/// transcoded to UTF-8 before parsing.
+#[derive(Default)]
+pub struct CsvDriver {
+    options: SourceOptions,
+    dialect: CsvDialect,
+    /// The declared encoding; `None` detects it.
+    encoding: Option<&'static Encoding>,
+}
+
+impl CsvDriver {
+    /// Create a driver, rejecting options the CSV reader can't honour.
+    pub fn new(options: SourceOptions, dialect: CsvDialect) -> Result<Self> {
+        for (name, value) in [
+            ("delimiter", options.delimiter),
+            ("decimal_separator", options.decimal_separator),
+            ("thousands_separator", options.thousands_separator),
+            ("delimiter", dialect.delimiter),
+            ("quote_char", dialect.quote_char),
+            ("comment_char", dialect.comment_char),
+        ] {
+            if let Some(c) = value.filter(|c| !c.is_ascii()) {
+                return Err(anyhow!(
+                    "{} must be a single ASCII character, got '{}'",
+                    name,
+                    c
+                ));
+            }
+        }
+        if options.decimal_separator.is_some()
+            && options.decimal_separator == options.thousands_separator
+        {
+            return Err(anyhow!(
+                "decimal_separator and thousands_separator must differ"
+            ));
+        }
+        let encoding = declared_encoding(&options)?;
+        Ok(Self {
+            options,
+            dialect,
+            encoding,
+        })
+    }
+
+    /// Encoding of data starting with (or made of) `sample`.
+    pub fn encoding(&self, sample: &[u8]) -> &'static Encoding {
+        detect_encoding(sample, self.encoding)
+    }
+
+    /// A UTF-8 copy of the file at `path`, unless it already is UTF-8
+    /// without a byte order mark.
+    ///
+    /// # Errors
+    /// Returns an error if the file has bytes its encoding can't decode.
+    fn transcode_file(&self, path: &Path) -> Result<Option<NamedTempFile>> {
+        let head = read_head(path)?;
+        let encoding = file_encoding(path, &head, self.encoding)?;
+        if encoding == UTF_8 && !head.starts_with(UTF8_BOM) {
+            return Ok(None);
+        }
+
+        let mut input = File::open(path)?;
+        let mut output = NamedTempFile::new()?;
+        let mut decoder = encoding.new_decoder_with_bom_removal();
+        let mut buffer = vec![0; DETECT_BYTES];
+        let mut text = String::new();
+        let mut offset = 0;
+        loop {
+            let read = input.read(&mut buffer)?;
+            let last = read == 0;
+            text.clear();
+            text.reserve(
+                decoder
+                    .max_utf8_buffer_length_without_replacement(read)
+                    .ok_or_else(|| anyhow!("CSV chunk too large to transcode"))?,
+            );
+            let (result, consumed) =
+                decoder.decode_to_string_without_replacement(&buffer[..read], &mut text, last);
+            if let DecoderResult::Malformed(..) = result {
+                return Err(malformed(encoding, offset + consumed));
+            }
+            output.write_all(text.as_bytes())?;
+            offset += read;
+            if last {
+                break;
+            }
+        }
+        output.flush()?;
+        Ok(Some(output))
+    }
+
+    fn read_options(&self) -> CsvReadOptions {
+        let dialect = &self.dialect;
+        let mut parse_options = CsvParseOptions::default();
+        if let Some(delimiter) = dialect.delimiter.or(self.options.delimiter) {
+            parse_options = parse_options.with_separator(delimiter as u8);
+        }
+        if let Some(quote) = dialect.quote_char {
+            parse_options = parse_options.with_quote_char(Some(quote as u8));
+        }
+        if !dialect.null_values.is_empty() {
+            let null_values = dialect.null_values.iter().map(|v| v.as_str().into());
+            parse_options =
+                parse_options.with_null_values(Some(NullValues::AllColumns(null_values.collect())));
+        }
+        if let Some(comment) = dialect.comment_char {
+            parse_options =
+                parse_options.with_comment_prefix(Some(CommentPrefix::Single(comment as u8)));
+        }
+        let options = CsvReadOptions::default()
+            .with_has_header(dialect.has_header.unwrap_or(true))
+            .with_skip_rows(dialect.skip_rows.unwrap_or(0))
+            .with_parse_options(parse_options);
+
+        // Locale-formatted numbers are read as text and converted afterwards,
+        // so e.g. `1.234` with a `.` thousands separator isn't inferred as 1.234.
+        if self.options.has_locale() {
+            options.with_infer_schema_length(Some(0))
+        } else {
+            options
+        }
+    }
+
+    fn finish(&self, df: DataFrame) -> Result<DataFrame> {
+        if !self.options.has_locale() {
+            return Ok(df);
+        }
+        let decimal = self.options.decimal_separator.unwrap_or('.');
+        let thousands = self.options.thousands_separator;
+
+        let columns = df
+            .get_columns()
+            .iter()
+            .map(|column| {
+                let series = column.as_materialized_series();
+                Ok(parse_locale_numbers(series, decimal, thousands)?
+                    .unwrap_or_else(|| series.clone())
+                    .into())
+            })
+            .collect::<Result<Vec<Column>>>()?;
+        Ok(DataFrame::new(columns)?)
+    }
+}
 
 impl Driver for CsvDriver {
     /// Load CSV data from memory into a DataFrame.
     ///
     /// # Arguments
-    /// * `data` - Raw CSV bytes (UTF‑8 encoded).
+    /// * `data` - Raw CSV bytes, transcoded to UTF-8 if needed.
     ///
     /// # Returns
     /// * `Result<DataFrame>` - A Polars DataFrame if parsing succeeds.
     fn load(&self, data: &[u8]) -> Result<DataFrame> {
-        let cursor = Cursor::new(data);
-
-        // Configure CSV reader options
-        let mut options = CsvReadOptions::default();
-        options.has_header = true;
+        let data = to_utf8(data, self.encoding(data))?;
+        let cursor = Cursor::new(data.as_ref());
 
         // Build a reader with options and finish into a DataFrame
-        let df = CsvReader::new(cursor).with_options(options).finish()?;
+        let df = CsvReader::new(cursor)
+            .with_options(self.read_options())
+            .finish()?;
+
+        self.finish(df)
+    }
+
+    /// Load CSV data from a file on disk into a DataFrame.
+    fn load_path(&self, path: &Path) -> Result<DataFrame> {
+        let transcoded = self.transcode_file(path)?;
+        let file = File::open(transcoded.as_ref().map_or(path, |file| file.path()))?;
+
+        let df = CsvReader::new(file)
+            .with_options(self.read_options())
+            .finish()?;
+
+        self.finish(df)
+    }
+
+    /// Read a CSV file on disk in batches of about `batch_rows` rows.
+    ///
+    /// The schema is inferred once, from the start of the file, and every
+    /// batch is parsed with it.
+    fn load_batches(
+        &self,
+        path: &Path,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()> {
+        let transcoded = self.transcode_file(path)?;
+        let file = File::open(transcoded.as_ref().map_or(path, |file| file.path()))?;
+        let mut reader =
+            CsvReader::new(file).with_options(self.read_options().with_chunk_size(batch_rows));
+        let mut batches = reader.batched_borrowed()?;
+
+        while let Some(dfs) = batches.next_batches(1)? {
+            for df in dfs {
+                if f(self.finish(df)?)?.is_break() {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The encoding `options` declare; `None` when unset or `auto`.
+///
+/// # Errors
+/// Returns an error if `encoding` isn't a known encoding label.
+pub fn declared_encoding(options: &SourceOptions) -> Result<Option<&'static Encoding>> {
+    match options.encoding.as_deref() {
+        None | Some("auto") => Ok(None),
+        Some(label) => Encoding::for_label(label.as_bytes())
+            .map(Some)
+            .ok_or_else(|| anyhow!("unknown encoding '{}'", label)),
+    }
+}
+
+/// Encoding of CSV data starting with (or made of) `sample`: `declared`
+/// when given; else the one its byte order mark names; else UTF-16 when
+/// most of every other byte at the start is NUL; else UTF-8 when `sample`
+/// is valid UTF-8 (a character cut off at its end is fine); else
+/// windows-1252, a superset of Latin-1.
+pub fn detect_encoding(sample: &[u8], declared: Option<&'static Encoding>) -> &'static Encoding {
+    if let Some(encoding) = declared {
+        return encoding;
+    }
+    if let Some((encoding, _)) = Encoding::for_bom(sample) {
+        return encoding;
+    }
 
-        Ok(df)
+    let head = &sample[..sample.len().min(1024) & !1];
+    let nuls = |offset: usize| {
+        head.iter()
+            .skip(offset)
+            .step_by(2)
+            .filter(|&&byte| byte == 0)
+            .count()
+    };
+    let pairs = head.len() / 2;
+    if pairs > 0 && nuls(1) * 2 > pairs {
+        return UTF_16LE;
     }
+    if pairs > 0 && nuls(0) * 2 > pairs {
+        return UTF_16BE;
+    }
+
+    match std::str::from_utf8(sample) {
+        Ok(_) => UTF_8,
+        Err(e) if e.error_len().is_none() => UTF_8,
+        Err(_) => WINDOWS_1252,
+    }
+}
+
+/// Encoding of the CSV file at `path`: `detect_encoding` over its first
+/// `DETECT_BYTES`, except that a file that only looks like UTF-8 there is
+/// windows-1252.
+pub fn detect_file_encoding(
+    path: &Path,
+    declared: Option<&'static Encoding>,
+) -> Result<&'static Encoding> {
+    file_encoding(path, &read_head(path)?, declared)
+}
+
+/// `detect_file_encoding` for the file at `path` starting with `head`.
+fn file_encoding(
+    path: &Path,
+    head: &[u8],
+    declared: Option<&'static Encoding>,
+) -> Result<&'static Encoding> {
+    let encoding = detect_encoding(head, declared);
+    let whole = head.len() < DETECT_BYTES;
+    if declared.is_some() || encoding != UTF_8 || head.starts_with(UTF8_BOM) || whole {
+        return Ok(encoding);
+    }
+
+    // Check the rest of the file is UTF-8 too.
+    let mut input = File::open(path)?;
+    let mut decoder = UTF_8.new_decoder_without_bom_handling();
+    let mut buffer = vec![0; DETECT_BYTES];
+    let mut text = String::with_capacity(DETECT_BYTES + 4);
+    loop {
+        let read = input.read(&mut buffer)?;
+        let last = read == 0;
+        text.clear();
+        let (result, _) =
+            decoder.decode_to_string_without_replacement(&buffer[..read], &mut text, last);
+        if let DecoderResult::Malformed(..) = result {
+            return Ok(WINDOWS_1252);
+        }
+        if last {
+            return Ok(UTF_8);
+        }
+    }
+}
+
+/// The first `DETECT_BYTES` of the file at `path`.
+fn read_head(path: &Path) -> Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(DETECT_BYTES);
+    File::open(path)?
+        .take(DETECT_BYTES as u64)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// `data` as UTF-8 without a byte order mark; borrowed when it already is.
+///
+/// # Errors
+/// Returns an error if `data` has bytes `encoding` can't decode.
+fn to_utf8<'a>(data: &'a [u8], encoding: &'static Encoding) -> Result<Cow<'a, [u8]>> {
+    if encoding == UTF_8 && !data.starts_with(UTF8_BOM) {
+        return Ok(Cow::Borrowed(data));
+    }
+    let mut decoder = encoding.new_decoder_with_bom_removal();
+    let mut text = String::with_capacity(
+        decoder
+            .max_utf8_buffer_length_without_replacement(data.len())
+            .ok_or_else(|| anyhow!("CSV data too large to transcode"))?,
+    );
+    match decoder.decode_to_string_without_replacement(data, &mut text, true) {
+        (DecoderResult::Malformed(..), read) => Err(malformed(encoding, read)),
+        _ => Ok(Cow::Owned(text.into_bytes())),
+    }
+}
+
+/// Error for data `encoding` can't decode, about `offset` bytes in.
+fn malformed(encoding: &'static Encoding, offset: usize) -> anyhow::Error {
+    anyhow!(
+        "CSV data is not valid {}: malformed bytes near byte {}",
+        encoding.name(),
+        offset
+    )
+}
+
+/// Convert a String column of locale-formatted numbers to Int64/Float64.
+///
+/// Returns `None` (keep the column as is) unless every non-null value
+/// parses as a number.
+fn parse_locale_numbers(
+    series: &Series,
+    decimal: char,
+    thousands: Option<char>,
+) -> Result<Option<Series>> {
+    let Ok(values) = series.str() else {
+        return Ok(None);
+    };
+    if values.null_count() == values.len() {
+        return Ok(None);
+    }
+
+    let normalized: StringChunked = values.apply_values(|v| {
+        let mut out = String::with_capacity(v.len());
+        for c in v.trim().chars() {
+            if Some(c) == thousands {
+                continue;
+            }
+            out.push(if c == decimal { '.' } else { c });
+        }
+        out.into()
+    });
+    let normalized = normalized.into_series();
+
+    // strict casts fail on any value that doesn't parse
+    Ok([DataType::Int64, DataType::Float64]
+        .iter()
+        .find_map(|dtype| normalized.strict_cast(dtype).ok()))
 }
 
 #[cfg(test)]
@@ -40,10 +398,184 @@ mod tests {
     #[test]
     fn it_loads_csv_data_from_memory() {
         let csv_data = "col_a,col_b\n1,one\n2,two\n3,three";
-        let driver = CsvDriver;
+        let driver = CsvDriver::default();
         let result = driver.load(csv_data.as_bytes());
         assert!(result.is_ok());
         let df = result.unwrap();
         assert_eq!(df.shape(), (3, 2));
     }
+
+    #[test]
+    fn it_loads_csv_data_from_path() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"col_a,col_b\n1,one\n2,two").unwrap();
+        let df = CsvDriver::default().load_path(file.path()).unwrap();
+        assert_eq!(df.shape(), (2, 2));
+    }
+
+    #[test]
+    fn it_loads_csv_data_in_batches() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let mut csv_data = String::from("id,label\n");
+        for i in 0..1000 {
+            csv_data.push_str(&format!("{},row{}\n", i, i));
+        }
+        std::io::Write::write_all(&mut file, csv_data.as_bytes()).unwrap();
+
+        let mut heights = Vec::new();
+        CsvDriver::default()
+            .load_batches(file.path(), 100, &mut |df| {
+                assert_eq!(df.width(), 2);
+                heights.push(df.height());
+                Ok(ControlFlow::Continue(()))
+            })
+            .unwrap();
+        assert!(heights.len() > 1);
+        assert_eq!(heights.iter().sum::<usize>(), 1000);
+    }
+
+    #[test]
+    fn it_parses_locale_formatted_numbers() {
+        let csv_data = "id;amount;label\n1;1.234,50;a\n2;2.000;b\n3;;c";
+        let driver = CsvDriver::new(
+            SourceOptions {
+                delimiter: Some(';'),
+                decimal_separator: Some(','),
+                thousands_separator: Some('.'),
+                ..SourceOptions::default()
+            },
+            CsvDialect::default(),
+        )
+        .unwrap();
+        let df = driver.load(csv_data.as_bytes()).unwrap();
+
+        assert_eq!(df.column("id").unwrap().dtype(), &DataType::Int64);
+        let amount = df.column("amount").unwrap();
+        assert_eq!(amount.dtype(), &DataType::Float64);
+        let amount = amount.f64().unwrap();
+        assert_eq!(amount.get(0), Some(1234.5));
+        assert_eq!(amount.get(1), Some(2000.0));
+        assert_eq!(amount.get(2), None);
+        assert_eq!(df.column("label").unwrap().dtype(), &DataType::String);
+    }
+
+    #[test]
+    fn it_detects_and_transcodes_encodings() {
+        let latin1 = b"id,name\n1,Jos\xe9\n2,Fran\xe7oise\n";
+        let utf16: Vec<u8> = "\u{feff}id,name\n1,José\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let utf16_without_bom = &utf16[2..];
+        let driver = CsvDriver::default();
+        assert_eq!(driver.encoding(latin1), WINDOWS_1252);
+        assert_eq!(driver.encoding(&utf16), UTF_16LE);
+        assert_eq!(driver.encoding(utf16_without_bom), UTF_16LE);
+        assert_eq!(driver.encoding("id,name\n1,José\n".as_bytes()), UTF_8);
+
+        for data in [&latin1[..], &utf16, utf16_without_bom] {
+            let df = driver.load(data).unwrap();
+            assert_eq!(
+                df.column("name").unwrap().str().unwrap().get(0),
+                Some("José")
+            );
+        }
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, latin1).unwrap();
+        let df = driver.load_path(file.path()).unwrap();
+        assert_eq!(
+            df.column("name").unwrap().str().unwrap().get(1),
+            Some("Françoise")
+        );
+    }
+
+    #[test]
+    fn it_detects_encodings_past_the_head_of_a_file() {
+        let mut latin1 = String::from("id,name\n").into_bytes();
+        while latin1.len() < DETECT_BYTES {
+            latin1.extend_from_slice(b"1,Jose\n");
+        }
+        latin1.extend_from_slice(b"2,Fran\xe7oise\n");
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &latin1).unwrap();
+
+        assert_eq!(
+            detect_file_encoding(file.path(), None).unwrap(),
+            WINDOWS_1252
+        );
+        let df = CsvDriver::default().load_path(file.path()).unwrap();
+        let names = df.column("name").unwrap();
+        assert_eq!(names.str().unwrap().get(df.height() - 1), Some("Françoise"));
+    }
+
+    #[test]
+    fn it_rejects_malformed_data_instead_of_replacing_it() {
+        let data = b"\xef\xbb\xbfid,name\n1,Jos\xff\n";
+        let error = CsvDriver::default().load(data).unwrap_err();
+        assert!(error.to_string().starts_with("CSV data is not valid UTF-8"));
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, data).unwrap();
+        assert!(CsvDriver::default().load_path(file.path()).is_err());
+    }
+
+    #[test]
+    fn it_uses_the_declared_encoding() {
+        let options = |encoding: &str| SourceOptions {
+            encoding: Some(encoding.to_string()),
+            ..SourceOptions::default()
+        };
+        // Valid UTF-8, but declared as Latin-1
+        let driver = CsvDriver::new(options("latin1"), CsvDialect::default()).unwrap();
+        let df = driver.load("id,name\n1,José\n".as_bytes()).unwrap();
+        assert_eq!(
+            df.column("name").unwrap().str().unwrap().get(0),
+            Some("JosÃ©")
+        );
+
+        assert!(CsvDriver::new(options("auto"), CsvDialect::default()).is_ok());
+        assert!(CsvDriver::new(options("klingon"), CsvDialect::default()).is_err());
+    }
+
+    #[test]
+    fn it_rejects_conflicting_separators() {
+        let options = SourceOptions {
+            delimiter: None,
+            decimal_separator: Some(','),
+            thousands_separator: Some(','),
+            ..SourceOptions::default()
+        };
+        assert!(CsvDriver::new(options, CsvDialect::default()).is_err());
+
+        let dialect = CsvDialect {
+            quote_char: Some('«'),
+            ..CsvDialect::default()
+        };
+        assert!(CsvDriver::new(SourceOptions::default(), dialect).is_err());
+    }
+
+    #[test]
+    fn it_reads_pipe_delimited_headerless_feeds() {
+        let csv_data = "generated by feedd\n# exported 2025-01-01\n\
+                        1|'Smith| Jo'|NULL\n2|Lee|42\n# trailer\n";
+        let dialect = CsvDialect {
+            delimiter: Some('|'),
+            quote_char: Some('\''),
+            has_header: Some(false),
+            skip_rows: Some(1),
+            null_values: vec!["NULL".to_string()],
+            comment_char: Some('#'),
+        };
+        let driver = CsvDriver::new(SourceOptions::default(), dialect).unwrap();
+        let df = driver.load(csv_data.as_bytes()).unwrap();
+
+        assert_eq!(df.get_column_names(), ["column_1", "column_2", "column_3"]);
+        assert_eq!(df.height(), 2);
+        let names = df.column("column_2").unwrap();
+        assert_eq!(names.str().unwrap().get(0), Some("Smith| Jo"));
+        let scores = df.column("column_3").unwrap();
+        assert_eq!(scores.null_count(), 1);
+        assert_eq!(scores.dtype(), &DataType::Int64);
+    }
 }
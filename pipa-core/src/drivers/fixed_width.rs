@@ -0,0 +1,243 @@
+use super::Driver; // Trait that all drivers must implement
+use crate::contracts::SourceOptions;
+use crate::contracts::column::ColumnContracts;
+use anyhow::{Result, anyhow}; // Standardized error handling
+use encoding_rs::{Encoding, UTF_8}; // Field decoding
+use polars::prelude::*; // Core Polars DataFrame types
+use std::fs::File; // Spooled input on disk
+use std::io::{BufRead, BufReader}; // Line-by-line reads for batches
+use std::ops::ControlFlow; // Lets a batch callback stop reading early
+use std::path::Path;
+
+/// A column of a fixed-width layout: `width` bytes from byte `start`.
+#[derive(Debug, Clone)]
+struct FixedWidthColumn {
+    name: String,
+    start: usize,
+    width: usize,
+}
+
+/// Fixed-width text driver
+///
+/// Implements the `Driver` trait for fixed-width text, as exported from
+/// mainframes: one record per line, each column at the byte offset and
+/// width declared on its `[[columns]]` entry (`start`, `width`).
+///
+/// Fields are trimmed of surrounding spaces and decoded in the source's
+/// declared encoding (`[source.options] encoding`, UTF-8 by default);
+/// blank fields, and fields past the end of a short line, are null.
+/// Empty lines are skipped. A column whose non-null values are all
+/// integers is Int64, all numbers Float64, and String otherwise.
+pub struct FixedWidthDriver {
+    columns: Vec<FixedWidthColumn>,
+    encoding: &'static Encoding,
+}
+
+impl FixedWidthDriver {
+    /// Build the layout from the contract's `[[columns]]`, in declared
+    /// order. Columns without `start` and `width` are not read.
+    ///
+    /// # Errors
+    /// Returns an error if a column declares only one of `start` and
+    /// `width`, a width is 0, no column declares a layout, or the declared
+    /// encoding is unknown.
+    pub fn new(columns: &[ColumnContracts], options: &SourceOptions) -> Result<Self> {
+        let mut layout = Vec::new();
+        for column in columns {
+            match (column.start, column.width) {
+                (Some(start), Some(width)) if width > 0 => layout.push(FixedWidthColumn {
+                    name: column.name.clone(),
+                    start,
+                    width,
+                }),
+                (Some(_), Some(_)) => {
+                    return Err(anyhow!(
+                        "column '{}': width must be at least 1",
+                        column.name
+                    ));
+                }
+                (None, None) => {}
+                _ => {
+                    return Err(anyhow!(
+                        "column '{}': fixed-width columns need both start and width",
+                        column.name
+                    ));
+                }
+            }
+        }
+        if layout.is_empty() {
+            return Err(anyhow!(
+                "fixed-width sources need start and width on their [[columns]]"
+            ));
+        }
+        let encoding = super::csv::declared_encoding(options)?.unwrap_or(UTF_8);
+        Ok(FixedWidthDriver {
+            columns: layout,
+            encoding,
+        })
+    }
+
+    /// The field of `column` in `line`, or `None` when it is blank or
+    /// past the end of the line.
+    fn field(&self, line: &[u8], column: &FixedWidthColumn) -> Option<String> {
+        let end = (column.start + column.width).min(line.len());
+        let bytes = line.get(column.start..end)?;
+        let (text, _) = self.encoding.decode_without_bom_handling(bytes);
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// Build a DataFrame from `lines`, one row per non-empty line.
+    fn parse<'a>(&self, lines: impl IntoIterator<Item = &'a [u8]>) -> Result<DataFrame> {
+        let mut fields: Vec<Vec<Option<String>>> = vec![Vec::new(); self.columns.len()];
+        for line in lines {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+            for (values, column) in fields.iter_mut().zip(&self.columns) {
+                values.push(self.field(line, column));
+            }
+        }
+        let columns = self
+            .columns
+            .iter()
+            .zip(fields)
+            .map(|(column, values)| typed_column(&column.name, values))
+            .collect();
+        Ok(DataFrame::new(columns)?)
+    }
+}
+
+impl Driver for FixedWidthDriver {
+    /// Load fixed-width text from memory into a DataFrame.
+    fn load(&self, data: &[u8]) -> Result<DataFrame> {
+        self.parse(data.split(|&b| b == b'\n'))
+    }
+
+    /// Load fixed-width text from a file on disk into a DataFrame.
+    fn load_path(&self, path: &Path) -> Result<DataFrame> {
+        self.load(&std::fs::read(path)?)
+    }
+
+    /// Read fixed-width text on disk in batches of `batch_rows` lines.
+    fn load_batches(
+        &self,
+        path: &Path,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()> {
+        let batch_rows = batch_rows.max(1);
+        let mut lines = Vec::with_capacity(batch_rows);
+        for line in BufReader::new(File::open(path)?).split(b'\n') {
+            let line = line?;
+            if line.is_empty() || line == b"\r" {
+                continue;
+            }
+            lines.push(line);
+            if lines.len() == batch_rows {
+                let df = self.parse(lines.iter().map(Vec::as_slice))?;
+                lines.clear();
+                if f(df)?.is_break() {
+                    return Ok(());
+                }
+            }
+        }
+        if !lines.is_empty() {
+            // Last batch: whether the callback breaks or continues, reading ends.
+            let _ = f(self.parse(lines.iter().map(Vec::as_slice))?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Build one column, choosing the narrowest type that fits every value.
+fn typed_column(name: &str, values: Vec<Option<String>>) -> Column {
+    let present = || values.iter().flatten();
+    let name = PlSmallStr::from(name);
+
+    if present().all(|v| v.parse::<i64>().is_ok()) {
+        let values: Vec<Option<i64>> = values.iter().map(|v| v.as_ref()?.parse().ok()).collect();
+        Column::new(name, values)
+    } else if present().all(|v| v.parse::<f64>().is_ok()) {
+        let values: Vec<Option<f64>> = values.iter().map(|v| v.as_ref()?.parse().ok()).collect();
+        Column::new(name, values)
+    } else {
+        Column::new(name, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXTRACT: &str = "0001SMITH     00012.50\r\n\
+                           0002LEE       00100.00\r\n\
+                           \r\n\
+                           0003O'BRIEN\r\n";
+
+    fn columns(layout: &[(&str, Option<usize>, Option<usize>)]) -> Vec<ColumnContracts> {
+        layout
+            .iter()
+            .map(|&(name, start, width)| ColumnContracts {
+                name: name.to_string(),
+                dtype: None,
+                start,
+                width,
+                aliases: Vec::new(),
+                validation: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn driver() -> FixedWidthDriver {
+        let layout = columns(&[
+            ("id", Some(0), Some(4)),
+            ("name", Some(4), Some(10)),
+            ("amount", Some(14), Some(8)),
+            ("checked", None, None),
+        ]);
+        FixedWidthDriver::new(&layout, &SourceOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn it_loads_fixed_width_text() {
+        let df = driver().load(EXTRACT.as_bytes()).unwrap();
+        assert_eq!(df.get_column_names_str(), ["id", "name", "amount"]);
+        assert_eq!(df.height(), 3);
+        assert_eq!(df.column("id").unwrap().dtype(), &DataType::Int64);
+        let names = df.column("name").unwrap();
+        assert_eq!(names.str().unwrap().get(2), Some("O'BRIEN"));
+        let amounts = df.column("amount").unwrap();
+        assert_eq!(amounts.dtype(), &DataType::Float64);
+        assert_eq!(amounts.f64().unwrap().get(0), Some(12.5));
+        assert_eq!(amounts.null_count(), 1);
+    }
+
+    #[test]
+    fn it_loads_fixed_width_batches() {
+        let path = std::env::temp_dir().join(format!("pipa_fixed_{}.txt", std::process::id()));
+        std::fs::write(&path, EXTRACT).unwrap();
+        let mut heights = Vec::new();
+        driver()
+            .load_batches(&path, 2, &mut |df| {
+                heights.push(df.height());
+                Ok(ControlFlow::Continue(()))
+            })
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(heights, [2, 1]);
+    }
+
+    #[test]
+    fn it_rejects_incomplete_layouts() {
+        let options = SourceOptions::default();
+        for layout in [
+            columns(&[("id", Some(0), None)]),
+            columns(&[("id", Some(0), Some(0))]),
+            columns(&[("id", None, None)]),
+        ] {
+            assert!(FixedWidthDriver::new(&layout, &options).is_err());
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use super::Driver; // Shared trait for all drivers
+use crate::contracts::Headers;
+use crate::contracts::column::ColumnContracts;
+use anyhow::{Result, bail};
+use polars::prelude::*;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::ControlFlow;
+use std::path::Path;
+
+/// Wraps another driver, renaming the columns of every frame it loads per
+/// a contract's `[headers]`, `[mapping]`, and column `aliases`.
+///
+/// Each incoming name is normalized; if the result equals a normalized
+/// `[mapping]` key, the column takes the mapped name, and otherwise if it
+/// equals a normalized alias, that `[[columns]]` entry's `name`.
+pub struct HeaderDriver {
+    inner: Box<dyn Driver>,
+    headers: Headers,
+    /// Normalized incoming name → canonical column name, `[mapping]` first.
+    renames: Vec<(String, String)>,
+}
+
+impl HeaderDriver {
+    pub fn new(
+        inner: Box<dyn Driver>,
+        headers: Headers,
+        mapping: &BTreeMap<String, String>,
+        columns: &[ColumnContracts],
+    ) -> Self {
+        let mapped = mapping
+            .iter()
+            .map(|(from, to)| (headers.normalize(from), to.clone()));
+        let aliases = columns.iter().flat_map(|column| {
+            column
+                .aliases
+                .iter()
+                .map(|alias| (headers.normalize(alias), column.name.clone()))
+        });
+        let renames = mapped.chain(aliases).collect();
+        Self {
+            inner,
+            headers,
+            renames,
+        }
+    }
+
+    /// The name an incoming column is validated under.
+    fn canonical(&self, name: &str) -> String {
+        let normalized = self.headers.normalize(name);
+        self.renames
+            .iter()
+            .find(|(from, _)| *from == normalized)
+            .map(|(_, column)| column.clone())
+            .unwrap_or(normalized)
+    }
+
+    fn rename(&self, mut df: DataFrame) -> Result<DataFrame> {
+        let names: Vec<String> = df
+            .get_column_names_str()
+            .into_iter()
+            .map(|name| self.canonical(name))
+            .collect();
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = names.iter().find(|name| !seen.insert(name.as_str())) {
+            bail!(
+                "Column '{}' appears more than once after header normalization",
+                duplicate
+            );
+        }
+        df.set_column_names(names)?;
+        Ok(df)
+    }
+}
+
+impl Driver for HeaderDriver {
+    fn load(&self, data: &[u8]) -> Result<DataFrame> {
+        self.rename(self.inner.load(data)?)
+    }
+
+    fn load_path(&self, path: &Path) -> Result<DataFrame> {
+        self.rename(self.inner.load_path(path)?)
+    }
+
+    fn load_batches(
+        &self,
+        path: &Path,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()> {
+        self.inner
+            .load_batches(path, batch_rows, &mut |batch| f(self.rename(batch)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::csv::CsvDriver;
+
+    fn column(name: &str, aliases: &[&str]) -> ColumnContracts {
+        ColumnContracts {
+            name: name.to_string(),
+            dtype: None,
+            start: None,
+            width: None,
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            validation: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn it_normalizes_headers_and_applies_aliases() {
+        let headers = Headers {
+            strip_bom: true,
+            trim: true,
+            lowercase: true,
+            spaces_to_underscores: true,
+        };
+        let columns = [column("customer_id", &["Cust ID", "CUSTNO"])];
+        let driver = HeaderDriver::new(
+            Box::new(CsvDriver::default()),
+            headers,
+            &BTreeMap::new(),
+            &columns,
+        );
+
+        let df = driver
+            .load("\u{feff} Cust ID ,Order Total,custno2\n1,10,x\n".as_bytes())
+            .unwrap();
+        assert_eq!(
+            df.get_column_names_str(),
+            ["customer_id", "order_total", "custno2"]
+        );
+
+        let err = driver.load(b"Cust ID,custno\n1,2\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'customer_id' appears more than once")
+        );
+    }
+
+    #[test]
+    fn it_maps_columns_before_aliases() {
+        let mapping = BTreeMap::from([
+            ("CUST_NO".to_string(), "customer_id".to_string()),
+            ("Amt".to_string(), "amount".to_string()),
+        ]);
+        let driver = HeaderDriver::new(
+            Box::new(CsvDriver::default()),
+            Headers::default(),
+            &mapping,
+            &[column("legacy_id", &["CUST_NO"])],
+        );
+        let df = driver.load(b"CUST_NO,Amt,note\n1,2,x\n").unwrap();
+        assert_eq!(df.get_column_names_str(), ["customer_id", "amount", "note"]);
+    }
+
+    #[test]
+    fn it_leaves_names_alone_by_default() {
+        let driver = HeaderDriver::new(
+            Box::new(CsvDriver::default()),
+            Headers::default(),
+            &BTreeMap::new(),
+            &[column("id", &["ID"])],
+        );
+        let df = driver.load(b"ID,Amount \n1,2\n").unwrap();
+        assert_eq!(df.get_column_names_str(), ["id", "Amount "]);
+    }
+}
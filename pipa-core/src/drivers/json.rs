@@ -0,0 +1,194 @@
+use super::Driver; // Trait that all drivers must implement
+use anyhow::{Result, anyhow}; // Standardized error handling
+use polars::prelude::*; // Core Polars DataFrame types
+use serde_json::{Map, Value}; // Decoded JSON records
+use std::collections::BTreeSet; // Column names across records
+use std::fs::File; // Spooled input on disk
+use std::io::{BufRead, BufReader}; // Line-by-line reads for batches
+use std::ops::ControlFlow; // Lets a batch callback stop reading early
+use std::path::Path;
+
+/// JSON records driver
+///
+/// Implements the `Driver` trait for newline-delimited JSON (one object per
+/// line, as produced by the Kafka connector) and for files holding a single
+/// JSON array of objects.
+///
+/// Columns are ordered by key name. A column whose non-null values are all
+/// booleans is Boolean, all integers Int64, all numbers Float64; anything
+/// else is String, with nested arrays and objects kept as JSON text.
+/// Missing keys are null.
+pub struct JsonDriver;
+
+impl Driver for JsonDriver {
+    /// Load JSON records from memory into a DataFrame.
+    fn load(&self, data: &[u8]) -> Result<DataFrame> {
+        let text = std::str::from_utf8(data)?;
+        if text.trim_start().starts_with('[') {
+            let records: Vec<Map<String, Value>> = serde_json::from_str(text)?;
+            return records_to_dataframe(&records);
+        }
+        let records = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| parse_record(line, index + 1))
+            .collect::<Result<Vec<_>>>()?;
+        records_to_dataframe(&records)
+    }
+
+    /// Load JSON records from a file on disk into a DataFrame.
+    fn load_path(&self, path: &Path) -> Result<DataFrame> {
+        self.load(&std::fs::read(path)?)
+    }
+
+    /// Read newline-delimited JSON on disk in batches of `batch_rows` lines.
+    ///
+    /// A JSON array can't be read incrementally, so it is loaded whole and
+    /// sliced.
+    fn load_batches(
+        &self,
+        path: &Path,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()> {
+        let batch_rows = batch_rows.max(1);
+        let mut reader = BufReader::new(File::open(path)?);
+        if reader.fill_buf()?.trim_ascii_start().starts_with(b"[") {
+            let df = self.load_path(path)?;
+            let mut offset = 0;
+            while offset < df.height() {
+                if f(df.slice(offset as i64, batch_rows))?.is_break() {
+                    break;
+                }
+                offset += batch_rows;
+            }
+            return Ok(());
+        }
+
+        let mut records = Vec::with_capacity(batch_rows);
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            records.push(parse_record(&line, index + 1)?);
+            if records.len() == batch_rows {
+                let df = records_to_dataframe(&records)?;
+                records.clear();
+                if f(df)?.is_break() {
+                    return Ok(());
+                }
+            }
+        }
+        if !records.is_empty() {
+            // Last batch: whether the callback breaks or continues, reading ends.
+            let _ = f(records_to_dataframe(&records)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse line `line_number` of newline-delimited JSON as an object.
+fn parse_record(line: &str, line_number: usize) -> Result<Map<String, Value>> {
+    match serde_json::from_str(line) {
+        Ok(Value::Object(record)) => Ok(record),
+        Ok(_) => Err(anyhow!("line {}: expected a JSON object", line_number)),
+        Err(e) => Err(anyhow!("line {}: {}", line_number, e)),
+    }
+}
+
+/// Build a DataFrame with one row per record and one column per key.
+fn records_to_dataframe(records: &[Map<String, Value>]) -> Result<DataFrame> {
+    let names: BTreeSet<&str> = records
+        .iter()
+        .flat_map(|record| record.keys().map(String::as_str))
+        .collect();
+
+    let columns = names
+        .into_iter()
+        .map(|name| {
+            let values: Vec<&Value> = records
+                .iter()
+                .map(|record| record.get(name).unwrap_or(&Value::Null))
+                .collect();
+            json_column(name, &values)
+        })
+        .collect();
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Build one column, choosing the narrowest type that fits every value.
+fn json_column(name: &str, values: &[&Value]) -> Column {
+    let present = || values.iter().filter(|v| !v.is_null());
+    let name = PlSmallStr::from(name);
+
+    if present().all(|v| v.is_boolean()) {
+        let values: Vec<Option<bool>> = values.iter().map(|v| v.as_bool()).collect();
+        Column::new(name, values)
+    } else if present().all(|v| v.is_i64()) {
+        let values: Vec<Option<i64>> = values.iter().map(|v| v.as_i64()).collect();
+        Column::new(name, values)
+    } else if present().all(|v| v.is_number()) {
+        let values: Vec<Option<f64>> = values.iter().map(|v| v.as_f64()).collect();
+        Column::new(name, values)
+    } else {
+        let values: Vec<Option<String>> = values
+            .iter()
+            .map(|v| match v {
+                Value::Null => None,
+                Value::String(s) => Some(s.clone()),
+                other => Some(other.to_string()),
+            })
+            .collect();
+        Column::new(name, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NDJSON: &str = "{\"id\": 1, \"amount\": 2.5, \"ok\": true, \"tags\": [\"a\"]}\n\
+        \n\
+        {\"id\": 2, \"amount\": 3, \"code\": \"x\"}\n\
+        {\"id\": null, \"amount\": 4.0, \"ok\": false}\n";
+
+    #[test]
+    fn it_loads_newline_delimited_json() {
+        let df = JsonDriver.load(NDJSON.as_bytes()).unwrap();
+        assert_eq!(df.shape(), (3, 5));
+        assert_eq!(
+            df.get_column_names_str(),
+            ["amount", "code", "id", "ok", "tags"]
+        );
+        assert_eq!(df.column("id").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(df.column("id").unwrap().null_count(), 1);
+        assert_eq!(df.column("amount").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("ok").unwrap().dtype(), &DataType::Boolean);
+        let tags = df.column("tags").unwrap();
+        assert_eq!(tags.str().unwrap().get(0), Some("[\"a\"]"));
+        assert_eq!(df.column("code").unwrap().null_count(), 2);
+    }
+
+    #[test]
+    fn it_loads_json_arrays_and_batches() {
+        let df = JsonDriver.load(br#"[{"id": 1}, {"id": 2}]"#).unwrap();
+        assert_eq!(df.shape(), (2, 1));
+
+        let path = std::env::temp_dir().join(format!("pipa_json_{}.ndjson", std::process::id()));
+        std::fs::write(&path, NDJSON).unwrap();
+        let mut heights = Vec::new();
+        JsonDriver
+            .load_batches(&path, 2, &mut |df| {
+                heights.push(df.height());
+                Ok(ControlFlow::Continue(()))
+            })
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(heights, [2, 1]);
+
+        let err = JsonDriver.load(b"{\"id\": 1}\n[1]\n").unwrap_err();
+        assert!(err.to_string().starts_with("line 2"), "{}", err);
+    }
+}
@@ -1,7 +1,10 @@
 use super::Driver; // Shared trait for all drivers
 use anyhow::Result; // Application-level error handling
 use polars::prelude::*; // Polars DataFrame + ParquetReader/Writer
+use std::fs::File; // Spooled input on disk
 use std::io::Cursor; // Wrap &[u8] into a reader
+use std::ops::ControlFlow; // Lets a batch callback stop reading early
+use std::path::Path;
 
 /// Parquet file driver
 ///
@@ -25,6 +28,39 @@ impl Driver for ParquetDriver {
 
         Ok(df)
     }
+
+    /// Load Parquet data from a file on disk into a DataFrame.
+    fn load_path(&self, path: &Path) -> Result<DataFrame> {
+        let file = File::open(path)?;
+        let df = ParquetReader::new(file).finish()?;
+
+        Ok(df)
+    }
+
+    /// Read a Parquet file on disk in slices of `batch_rows` rows.
+    ///
+    /// Each slice only decodes the row groups it overlaps.
+    fn load_batches(
+        &self,
+        path: &Path,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()> {
+        let rows = ParquetReader::new(File::open(path)?).num_rows()?;
+        let batch_rows = batch_rows.max(1);
+
+        let mut offset = 0;
+        while offset < rows {
+            let df = ParquetReader::new(File::open(path)?)
+                .with_slice(Some((offset, batch_rows)))
+                .finish()?;
+            offset += batch_rows;
+            if f(df)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,178 @@
+use super::Driver; // Shared trait for all drivers
+use crate::contracts::transform::{FillValue, Transform, TransformStep};
+use anyhow::{Context, Result, anyhow};
+use polars::prelude::*;
+use polars::sql::sql_expr;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+/// Wraps another driver, applying a contract's `[transform]` steps to
+/// every frame it loads.
+pub struct TransformDriver {
+    inner: Box<dyn Driver>,
+    transform: Transform,
+}
+
+impl TransformDriver {
+    pub fn new(inner: Box<dyn Driver>, transform: Transform) -> Self {
+        Self { inner, transform }
+    }
+}
+
+impl Driver for TransformDriver {
+    fn load(&self, data: &[u8]) -> Result<DataFrame> {
+        apply_transform(self.inner.load(data)?, &self.transform)
+    }
+
+    fn load_path(&self, path: &Path) -> Result<DataFrame> {
+        apply_transform(self.inner.load_path(path)?, &self.transform)
+    }
+
+    fn load_batches(
+        &self,
+        path: &Path,
+        batch_rows: usize,
+        f: &mut dyn FnMut(DataFrame) -> Result<ControlFlow<()>>,
+    ) -> Result<()> {
+        self.inner.load_batches(path, batch_rows, &mut |batch| {
+            f(apply_transform(batch, &self.transform)?)
+        })
+    }
+}
+
+/// Run `transform`'s steps over `df`, in order.
+///
+/// # Errors
+/// Returns an error naming the failing step (1-based) if a step refers to
+/// a missing column, names an unknown dtype, or has an invalid `expr`.
+pub fn apply_transform(mut df: DataFrame, transform: &Transform) -> Result<DataFrame> {
+    for (index, step) in transform.steps.iter().enumerate() {
+        df = apply_step(df, step).with_context(|| format!("[transform] step {}", index + 1))?;
+    }
+    Ok(df)
+}
+
+fn apply_step(df: DataFrame, step: &TransformStep) -> Result<DataFrame> {
+    let df = match step {
+        TransformStep::Cast { column, dtype } => df
+            .lazy()
+            .with_column(col(column.as_str()).cast(parse_dtype(dtype)?))
+            .collect()?,
+        TransformStep::Trim { columns } => {
+            let columns: Vec<Expr> = if columns.is_empty() {
+                df.get_columns()
+                    .iter()
+                    .filter(|c| c.dtype() == &DataType::String)
+                    .map(|c| col(c.name().clone()))
+                    .collect()
+            } else {
+                columns.iter().map(|c| col(c.as_str())).collect()
+            };
+            let trimmed: Vec<Expr> = columns
+                .into_iter()
+                .map(|c| c.str().strip_chars(lit(NULL)))
+                .collect();
+            df.lazy().with_columns(trimmed).collect()?
+        }
+        TransformStep::FillNull { column, value } => {
+            let value = match value {
+                FillValue::Boolean(v) => lit(*v),
+                FillValue::Integer(v) => lit(*v),
+                FillValue::Float(v) => lit(*v),
+                FillValue::String(v) => lit(v.as_str()),
+            };
+            df.lazy()
+                .with_column(col(column.as_str()).fill_null(value))
+                .collect()?
+        }
+        TransformStep::Drop { columns } => {
+            let mut df = df;
+            for column in columns {
+                df = df.drop(column)?;
+            }
+            df
+        }
+        TransformStep::Filter { expr } => {
+            let predicate =
+                sql_expr(expr).map_err(|e| anyhow!("invalid filter expr '{}': {}", expr, e))?;
+            df.lazy().filter(predicate).collect()?
+        }
+    };
+    Ok(df)
+}
+
+/// The Polars dtype a `cast` step names.
+fn parse_dtype(dtype: &str) -> Result<DataType> {
+    Ok(match dtype {
+        "Boolean" => DataType::Boolean,
+        "Int32" => DataType::Int32,
+        "Int64" => DataType::Int64,
+        "UInt32" => DataType::UInt32,
+        "UInt64" => DataType::UInt64,
+        "Float32" => DataType::Float32,
+        "Float64" => DataType::Float64,
+        "String" => DataType::String,
+        "Date" => DataType::Date,
+        _ => {
+            return Err(anyhow!(
+                "unsupported cast dtype '{}' (expected Boolean, Int32, Int64, UInt32, UInt64, \
+                 Float32, Float64, String, or Date)",
+                dtype
+            ));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::csv::CsvDriver;
+
+    fn steps(toml: &str) -> Transform {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn it_applies_steps_in_order() {
+        let transform = steps(
+            r#"steps = [
+                { op = "trim" },
+                { op = "cast", column = "amount", dtype = "Int64" },
+                { op = "fill_null", column = "country", value = "US" },
+                { op = "drop", columns = ["note"] },
+                { op = "filter", expr = "status <> 'test' AND amount > 0" },
+            ]"#,
+        );
+        let driver = TransformDriver::new(Box::new(CsvDriver::default()), transform);
+
+        let df = driver
+            .load(
+                b"status,amount,country,note\n\
+                  \" live \",10,,a\n\
+                  test,5,DE,b\n\
+                  live,x,FR,c\n\
+                  live,7,NL,d\n",
+            )
+            .unwrap();
+        assert_eq!(df.get_column_names_str(), ["status", "amount", "country"]);
+        assert_eq!(df.height(), 2);
+        let country = df.column("country").unwrap().str().unwrap();
+        assert_eq!(country.get(0), Some("US"));
+        assert_eq!(
+            df.column("status").unwrap().str().unwrap().get(0),
+            Some("live")
+        );
+        assert_eq!(df.column("amount").unwrap().dtype(), &DataType::Int64);
+    }
+
+    #[test]
+    fn it_names_the_failing_step() {
+        let df = df!("id" => [1i64, 2]).unwrap();
+        let missing = steps(r#"steps = [{ op = "trim" }, { op = "drop", columns = ["x"] }]"#);
+        let err = apply_transform(df.clone(), &missing).unwrap_err();
+        assert!(err.to_string().contains("step 2"));
+
+        let unknown = steps(r#"steps = [{ op = "cast", column = "id", dtype = "Decimal" }]"#);
+        assert!(apply_transform(df, &unknown).is_err());
+    }
+}
@@ -1,10 +1,20 @@
 pub mod contracts;
+pub mod cron;
 pub mod init;
+pub mod lint;
 pub mod logging;
 pub mod logs;
+pub mod metrics;
+pub mod plan;
+pub mod profiler;
 pub mod profiles;
+pub mod rules;
+pub mod sampling;
+pub mod sources;
+pub mod streaming;
 pub mod system;
 pub mod validation;
+pub mod workspace;
 
 // Re-export core functions for public use
 
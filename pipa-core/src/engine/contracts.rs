@@ -1,18 +1,107 @@
 // Submodules that implement contract execution logic
+pub mod baseline; // Pinning drift baselines from the run history
+pub mod check; // Schema pre-flight against a sample of the live source
+pub mod contract_diff; // Rule and column changes between two contract versions
+pub mod diff; // Comparing statistics of two recorded runs
+pub mod export; // Rendering contracts as JSON Schema
+pub mod format; // Canonical contract formatting
+pub mod generate; // Draft contract generation from sample data
 pub mod meta; // Metadata + contract lookup/listing/validation
+pub mod quarantine; // Re-validating quarantined files and releasing them
 pub mod runner; // Execution engine for running validations
+pub mod scheduler; // Running contracts on their cron schedules
+pub mod tags; // Selecting and running contracts by tag
+pub mod timings; // Per-run stage durations
+pub mod transfer; // Per-run byte accounting for connector traffic
+pub mod watch; // Polling sources and running contracts on new files
 
 // Curated re-exports: the stable API surface for engine contracts
 pub use meta::{
     ContractInfo,       // Metadata about a contract (name, version, etc.)
+    ContractLint,       // Result of linting a contract
     ContractList,       // Collection of available contracts
     ContractValidation, // Result of validating a contract
     get_contract,       // Lookup a single contract by name/path
+    lint_contract,      // Semantic checks with codes and suggestions
     list_contracts,     // Enumerate all available contracts
     validate_contract,  // Validate a contract definition (schema-level check)
 };
 
+pub use baseline::set_baseline; // Pin a recorded run as the drift baseline
+
+pub use check::{
+    DtypeMismatch,       // Declared vs inferred dtype of one column
+    SchemaCheck,         // Missing/extra columns and dtype mismatches in a sample
+    check_contract,      // Compare a contract's columns with its live source
+    check_contract_with, // Same, with injected backends (`Environment`)
+};
+
+pub use contract_diff::{
+    ChangeKind,         // Whether an item was added, removed, or changed
+    ContractChange,     // One difference between two contract versions
+    ContractDiff,       // Differences between two contract versions
+    diff_contract_toml, // Diff two versions of contract TOML
+    diff_contracts,     // Diff an older version against `contracts/<name>.toml`
+};
+
+pub use diff::{
+    ColumnChange, // One changed statistic of one column
+    RunDiff,      // Differences between two recorded runs
+    diff_runs,    // Diff two runs from the run history
+};
+
+pub use export::{
+    ContractExport,  // A contract rendered in another schema language
+    ExportFormat,    // Target format (`json-schema`)
+    export_contract, // Export `contracts/<name>.toml` in a format
+    json_schema,     // Render a JSON Schema for a parsed contract
+};
+
+pub use format::{
+    ContractFormat,       // A contract in canonical form
+    RuleFix,              // A rule name respelled to a known rule
+    format_contract,      // Format `contracts/<name>.toml`
+    format_contract_toml, // Format contract TOML
+};
+
+pub use generate::{
+    ContractDraft,     // Contract name, source, and columns to render as TOML
+    DraftColumn,       // One column of a `ContractDraft`
+    GeneratedContract, // Draft contract rendered from a data sample
+    generate_contract, // Profile a sample and render a draft contract
+};
+
+pub use quarantine::{
+    Revalidation,           // Outcome of re-validating one quarantined file
+    revalidate_quarantined, // Re-run a contract on a quarantined file, releasing it on pass
+};
+
 pub use runner::{
-    ValidationOutcome,       // Result of executing a contract against data
-    run_contract_validation, // Entry point to run validations
+    ObjectOutcome,                // Outcome for one object matched by a glob source
+    ValidationOutcome,            // Result of executing a contract against data
+    run_contract_validation,      // Entry point to run validations
+    run_contract_validation_with, // Same, with injected backends (`Environment`)
+};
+pub use scheduler::{
+    ScheduledContract,   // A contract and its cron schedule
+    Scheduler,           // Runs scheduled contracts at their cron times
+    SchedulerSummary,    // Tallies over a scheduler session
+    scheduled_contracts, // Contracts with a `[schedule]` section
+    sealing_schedule,    // The `[sealing] cron` in `pipa.toml`
+};
+pub use tags::{
+    ContractRun,          // Outcome of one contract in a tag-selected run
+    TagFilter,            // Tags to include / exclude
+    run_contracts_by_tag, // Run every contract matching a tag filter
+    select_contracts,     // Contract names matching a tag filter
+};
+pub use timings::RunTimings; // Time spent fetching, parsing, and validating
+pub use transfer::TransferStats; // Bytes downloaded/uploaded per connector
+pub use watch::{
+    DEFAULT_DEBOUNCE,      // Default settle time before a new object is run
+    DEFAULT_POLL_INTERVAL, // Default time between polls
+    WatchOptions,          // Contracts, interval, and debounce to watch with
+    WatchRun,              // A run triggered by a new or changed object
+    WatchSummary,          // Tallies over a watch session
+    Watcher,               // Polls sources and runs contracts on new objects
 };
@@ -0,0 +1,45 @@
+//! Pinning drift baselines.
+//!
+//! `drift` rules with `against = "baseline"` compare each run with a pinned
+//! run from the history store (`.pipa/state/<contract>/baseline.json`).
+
+use crate::engine::log_action;
+use crate::history::{HistoryStore, RunHistory, RunStats};
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+
+/// Pin a recorded run as the drift baseline for `contract_name`.
+///
+/// # Arguments
+/// * `logger` - The audit logger implementation to use
+/// * `contract_name` - Contract whose history is used
+/// * `run_id` - Run to pin; defaults to the most recent run
+pub fn set_baseline<L: AuditLogger>(
+    logger: &L,
+    contract_name: &str,
+    run_id: Option<&str>,
+) -> ValidationResult<(RunStats, String)> {
+    let store = HistoryStore::default();
+    let run = match run_id {
+        Some(id) => store.run(contract_name, id)?.ok_or_else(|| {
+            ValidationError::RunNotFound(format!("run '{}' for contract '{}'", id, contract_name))
+        })?,
+        None => store.latest(contract_name)?.ok_or_else(|| {
+            ValidationError::RunNotFound(format!(
+                "no recorded runs for contract '{}'",
+                contract_name
+            ))
+        })?,
+    };
+    store.set_baseline(contract_name, &run)?;
+
+    let message = log_action(
+        logger,
+        "baseline_set",
+        Some(&format!("run_id={}", run.run_id)),
+        Some(contract_name),
+        Some(&run.contract_version),
+        None,
+    );
+    Ok((run, message))
+}
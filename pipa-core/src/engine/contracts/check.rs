@@ -0,0 +1,235 @@
+//! Schema pre-flight against a contract's live source (`pipa contract check`).
+//!
+//! Reads the first rows of the source and compares the columns Polars
+//! infers with the contract's `[[columns]]`: declared columns missing from
+//! the data, columns in the data the contract doesn't declare, and declared
+//! `dtype`s that differ from the inferred ones. No rules are run, nothing is
+//! moved, and no run history is recorded.
+//!
+//! For a glob source the first matching object is sampled.
+
+use crate::connectors::fetch::source_extension;
+use crate::drivers::contract_driver;
+use crate::engine::contracts::runner::SourceGlob;
+use crate::engine::log_action;
+use crate::engine::plan::ExecutionPlan;
+use crate::environment::Environment;
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use polars::prelude::DataFrame;
+use std::ops::ControlFlow;
+
+/// A declared column whose `dtype` differs from the sampled data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DtypeMismatch {
+    pub column: String,
+    pub declared: String,
+    pub actual: String,
+}
+
+/// Result of checking a contract's schema against a sample of its source.
+#[derive(Debug, Clone)]
+pub struct SchemaCheck {
+    pub location: String,               // object that was sampled
+    pub rows_sampled: usize,            // rows read (at most the requested sample)
+    pub missing: Vec<String>,           // declared columns absent from the data
+    pub extra: Vec<String>,             // data columns the contract doesn't declare
+    pub mismatches: Vec<DtypeMismatch>, // declared vs inferred dtypes
+}
+
+impl SchemaCheck {
+    /// Whether every declared column is present with its declared dtype.
+    ///
+    /// Extra columns are reported but don't fail the check.
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+/// Check a contract's declared columns against the first `rows` rows of its
+/// source, using the default `Environment`.
+pub async fn check_contract<L: AuditLogger>(
+    logger: &L,
+    contract_name: &str,
+    rows: usize,
+) -> ValidationResult<(SchemaCheck, String)> {
+    check_contract_with(&Environment::default(), logger, contract_name, rows).await
+}
+
+/// Like `check_contract`, with contracts, profiles, and data served by `env`.
+pub async fn check_contract_with<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    rows: usize,
+) -> ValidationResult<(SchemaCheck, String)> {
+    let (plan, _) = ExecutionPlan::cached(&env.config.contract_toml(contract_name)?)?;
+    let contracts = &plan.contracts;
+    let profiles = env.config.profiles()?;
+
+    if contracts.sources.is_some() {
+        return Err(ValidationError::Config(
+            "contract check samples a single [source]; this contract declares [[sources]]"
+                .to_string(),
+        ));
+    }
+    let source = &env.dated_source(
+        contracts
+            .source
+            .as_ref()
+            .ok_or_else(|| ValidationError::Config("Contract missing source".to_string()))?,
+    )?;
+    let pattern = source
+        .location
+        .as_ref()
+        .ok_or_else(|| ValidationError::Config("Source missing location".to_string()))?;
+
+    let location = match SourceGlob::parse(pattern)? {
+        Some(glob) => env
+            .storage
+            .list(source, glob.prefix(), &profiles)
+            .await?
+            .into_iter()
+            .map(|object| object.location)
+            .find(|location| glob.matches(location))
+            .ok_or_else(|| {
+                ValidationError::Config(format!("No objects match source location '{}'", pattern))
+            })?,
+        None => pattern.clone(),
+    };
+    let mut sampled = source.clone();
+    sampled.location = Some(location.clone());
+
+    let fetched = env.storage.fetch(&sampled, &profiles).await?;
+    let extension = source_extension(&sampled);
+    let driver = contract_driver(extension, &sampled.options, &sampled.csv, &plan.contracts)
+        .map_err(|e| ValidationError::driver_load(extension, e))?;
+    let mut sample = None;
+    fetched
+        .payload
+        .load_batches(driver.as_ref(), rows.max(1), &mut |batch| {
+            sample = Some(batch);
+            Ok(ControlFlow::Break(()))
+        })
+        .map_err(|e| ValidationError::driver_load(extension, e))?;
+    let sample = sample.unwrap_or_default();
+
+    let check = compare_schema(&location, &sample, &plan);
+    let details = format!(
+        "rows={}, missing={}, extra={}, mismatches={}",
+        check.rows_sampled,
+        check.missing.len(),
+        check.extra.len(),
+        check.mismatches.len()
+    );
+    let message = log_action(
+        logger,
+        "contract_checked",
+        Some(&details),
+        Some(&contracts.contract.name),
+        Some(&contracts.contract.version),
+        Some(&location),
+    );
+    Ok((check, message))
+}
+
+/// Compare a sampled DataFrame's columns with the contract's declarations.
+fn compare_schema(location: &str, sample: &DataFrame, plan: &ExecutionPlan) -> SchemaCheck {
+    let declared = &plan.contracts.columns;
+    let mut missing = Vec::new();
+    let mut mismatches = Vec::new();
+    for col in declared {
+        let Ok(series) = sample.column(&col.name) else {
+            missing.push(col.name.clone());
+            continue;
+        };
+        let actual = format!("{:?}", series.dtype());
+        if let Some(dtype) = &col.dtype
+            && *dtype != actual
+        {
+            mismatches.push(DtypeMismatch {
+                column: col.name.clone(),
+                declared: dtype.clone(),
+                actual,
+            });
+        }
+    }
+    let extra = sample
+        .get_column_names()
+        .into_iter()
+        .filter(|name| !declared.iter().any(|col| col.name == name.as_str()))
+        .map(|name| name.to_string())
+        .collect();
+
+    SchemaCheck {
+        location: location.to_string(),
+        rows_sampled: sample.height(),
+        missing,
+        extra,
+        mismatches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::InMemory;
+    use crate::history::RunHistory;
+    use crate::logging::MemoryLogger;
+
+    const CONTRACT: &str = r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+dtype = "Int64"
+validation = [{ rule = "not_null" }]
+
+[[columns]]
+name = "amount"
+dtype = "Int64"
+validation = []
+
+[[columns]]
+name = "region"
+validation = []
+
+[source]
+type = "local"
+location = "landing/*.csv"
+"#;
+
+    #[tokio::test]
+    async fn reports_missing_extra_and_mismatched_columns() {
+        let memory = InMemory::new();
+        memory.add_contract("orders", CONTRACT);
+        memory.put_object(
+            "landing/orders.csv",
+            "id,amount,note\n1,1.5,a\n2,2.5,b\n3,3.5,c\n",
+        );
+        let logger = MemoryLogger::new();
+
+        let (check, _) = check_contract_with(&memory.environment(), &logger, "orders", 2)
+            .await
+            .unwrap();
+
+        assert_eq!(check.location, "landing/orders.csv");
+        assert_eq!(check.rows_sampled, 2);
+        assert_eq!(check.missing, vec!["region"]);
+        assert_eq!(check.extra, vec!["note"]);
+        assert_eq!(
+            check.mismatches,
+            vec![DtypeMismatch {
+                column: "amount".to_string(),
+                declared: "Int64".to_string(),
+                actual: "Float64".to_string(),
+            }]
+        );
+        assert!(!check.passed());
+        assert_eq!(logger.events(), vec!["contract_checked"]);
+        assert!(memory.history().runs("orders").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,459 @@
+//! Diffing two versions of a contract (`pipa contract diff`).
+//!
+//! Compares the TOML of an older version with the current contract and
+//! reports what a reviewer needs to know:
+//! - columns added or removed, and changed column settings (`dtype`, ...);
+//! - rules added, removed, or with changed parameters, per column and under
+//!   `[file]`; rules are matched by name (repeated rules by position);
+//! - `[[compound_unique]]` column sets added or removed;
+//! - changed keys in every other section (`[contract]`, `[source]`,
+//!   `[limits]`, ...), as dotted paths.
+//!
+//! Values are compared as written, before `${NAME}` interpolation. Each
+//! side's content hash is the one recorded as `contract_hash` in the audit
+//! entries of runs made with it.
+
+use crate::config::contract_path;
+use crate::contracts::parse_contract;
+use crate::engine::log_action;
+use crate::engine::plan::contract_hash;
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Whether an item was added, removed, or changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference between two contract versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContractChange {
+    pub kind: ChangeKind,
+    /// Where: `column 'amount'`, `[file]`, `[compound_unique]`, `[source]`, ...
+    pub section: String,
+    /// What: `rule range`, `dtype`, `location`, ...; empty when the whole
+    /// column or section was added or removed.
+    pub item: String,
+    /// Old value (removed or changed items), as TOML.
+    pub from: Option<String>,
+    /// New value (added or changed items), as TOML.
+    pub to: Option<String>,
+}
+
+impl fmt::Display for ContractChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (sign, from, to) = match self.kind {
+            ChangeKind::Added => ("+", None, self.to.as_deref()),
+            ChangeKind::Removed => ("-", self.from.as_deref(), None),
+            ChangeKind::Changed => ("~", self.from.as_deref(), self.to.as_deref()),
+        };
+        write!(f, "{} {}", sign, self.section)?;
+        if !self.item.is_empty() {
+            write!(f, " {}", self.item)?;
+        }
+        match (from, to) {
+            (Some(from), Some(to)) => write!(f, ": {} → {}", from, to),
+            (Some(value), None) | (None, Some(value)) => write!(f, " {}", value),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Differences between two versions of a contract.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractDiff {
+    pub contract: String,
+    pub from_version: String,
+    pub to_version: String,
+    /// Content hash of the older version.
+    pub from_hash: String,
+    /// Content hash of the current version.
+    pub to_hash: String,
+    pub changes: Vec<ContractChange>,
+}
+
+impl ContractDiff {
+    /// Whether the two versions are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Diff an older version of a contract, read from `old_path`, against the
+/// current `contracts/<name>.toml`.
+pub fn diff_contracts<L: AuditLogger>(
+    logger: &L,
+    name: &str,
+    old_path: &str,
+) -> ValidationResult<(ContractDiff, String)> {
+    let current = std::fs::read_to_string(contract_path(name))
+        .map_err(|_| ValidationError::ContractNotFound(name.to_string()))?;
+    let old = std::fs::read_to_string(old_path)
+        .map_err(|e| ValidationError::Config(format!("Failed to read '{}': {}", old_path, e)))?;
+
+    let diff = diff_contract_toml(&old, &current)?;
+    let message = log_action(
+        logger,
+        "contract_diffed",
+        Some(&format!(
+            "from_hash={}, to_hash={}, changes={}",
+            diff.from_hash,
+            diff.to_hash,
+            diff.changes.len()
+        )),
+        Some(&diff.contract),
+        Some(&diff.to_version),
+        Some(old_path),
+    );
+    Ok((diff, message))
+}
+
+/// Diff two versions of contract TOML. Both must parse.
+pub fn diff_contract_toml(old: &str, new: &str) -> ValidationResult<ContractDiff> {
+    let old_contract = parse_contract(old)?;
+    let new_contract = parse_contract(new)?;
+    let old_table = raw_table(old)?;
+    let new_table = raw_table(new)?;
+
+    let mut changes = Vec::new();
+    diff_columns(&old_table, &new_table, &mut changes);
+    diff_rules(
+        "[file]",
+        rules(old_table.get("file").and_then(|f| f.as_table())),
+        rules(new_table.get("file").and_then(|f| f.as_table())),
+        &mut changes,
+    );
+    diff_compound_unique(&old_table, &new_table, &mut changes);
+
+    let sections: BTreeSet<&String> = old_table.keys().chain(new_table.keys()).collect();
+    for section in sections {
+        if matches!(section.as_str(), "columns" | "compound_unique") {
+            continue;
+        }
+        let mut old_value = old_table.get(section).cloned();
+        let mut new_value = new_table.get(section).cloned();
+        if section == "file" {
+            // `[file] validation` is diffed rule by rule above.
+            for value in [&mut old_value, &mut new_value].into_iter().flatten() {
+                if let toml::Value::Table(table) = value {
+                    table.remove("validation");
+                }
+            }
+        }
+        diff_values(
+            &format!("[{}]", section),
+            "",
+            old_value.as_ref(),
+            new_value.as_ref(),
+            &mut changes,
+        );
+    }
+
+    Ok(ContractDiff {
+        contract: new_contract.contract.name,
+        from_version: old_contract.contract.version,
+        to_version: new_contract.contract.version,
+        from_hash: contract_hash(old),
+        to_hash: contract_hash(new),
+        changes,
+    })
+}
+
+fn raw_table(toml_str: &str) -> ValidationResult<toml::Table> {
+    toml::from_str(toml_str)
+        .map_err(|e| ValidationError::ContractParse(format!("Failed to parse contract: {}", e)))
+}
+
+fn change(
+    kind: ChangeKind,
+    section: &str,
+    item: &str,
+    from: Option<&toml::Value>,
+    to: Option<&toml::Value>,
+) -> ContractChange {
+    ContractChange {
+        kind,
+        section: section.to_string(),
+        item: item.to_string(),
+        from: from.map(toml::Value::to_string),
+        to: to.map(toml::Value::to_string),
+    }
+}
+
+/// The `[[columns]]` entries by name, in declaration order.
+fn columns(table: &toml::Table) -> Vec<(&str, &toml::Table)> {
+    table
+        .get("columns")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|col| {
+            let col = col.as_table()?;
+            Some((col.get("name")?.as_str()?, col))
+        })
+        .collect()
+}
+
+fn column<'a>(columns: &[(&str, &'a toml::Table)], name: &str) -> Option<&'a toml::Table> {
+    columns
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, col)| *col)
+}
+
+fn diff_columns(old: &toml::Table, new: &toml::Table, changes: &mut Vec<ContractChange>) {
+    let old_columns = columns(old);
+    let new_columns = columns(new);
+    for (name, _) in &old_columns {
+        if column(&new_columns, name).is_none() {
+            let section = format!("column '{}'", name);
+            changes.push(change(ChangeKind::Removed, &section, "", None, None));
+        }
+    }
+    for (name, new_col) in &new_columns {
+        let section = format!("column '{}'", name);
+        let Some(old_col) = column(&old_columns, name) else {
+            changes.push(change(ChangeKind::Added, &section, "", None, None));
+            continue;
+        };
+        let keys: BTreeSet<&String> = old_col.keys().chain(new_col.keys()).collect();
+        for key in keys {
+            if matches!(key.as_str(), "name" | "validation") {
+                continue;
+            }
+            diff_values(&section, key, old_col.get(key), new_col.get(key), changes);
+        }
+        diff_rules(
+            &section,
+            rules(Some(old_col)),
+            rules(Some(new_col)),
+            changes,
+        );
+    }
+}
+
+/// The `validation` rules of a column or `[file]` table.
+fn rules(table: Option<&toml::Table>) -> Vec<toml::Value> {
+    table
+        .and_then(|t| t.get("validation"))
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Match rules by name (repeated rules by position among that name) and
+/// report additions, removals, and parameter changes.
+fn diff_rules(
+    section: &str,
+    old: Vec<toml::Value>,
+    new: Vec<toml::Value>,
+    changes: &mut Vec<ContractChange>,
+) {
+    let name = |rule: &toml::Value| {
+        rule.get("rule")
+            .and_then(|r| r.as_str())
+            .unwrap_or("?")
+            .to_string()
+    };
+    let mut remaining: Vec<Option<toml::Value>> = old.into_iter().map(Some).collect();
+    for rule in &new {
+        let rule_name = name(rule);
+        let matched = remaining
+            .iter_mut()
+            .find(|old| old.as_ref().is_some_and(|old| name(old) == rule_name))
+            .and_then(Option::take);
+        let item = format!("rule {}", rule_name);
+        match matched {
+            None => changes.push(change(ChangeKind::Added, section, &item, None, Some(rule))),
+            Some(old) if old != *rule => changes.push(change(
+                ChangeKind::Changed,
+                section,
+                &item,
+                Some(&old),
+                Some(rule),
+            )),
+            Some(_) => {}
+        }
+    }
+    for old in remaining.into_iter().flatten() {
+        let item = format!("rule {}", name(&old));
+        changes.push(change(
+            ChangeKind::Removed,
+            section,
+            &item,
+            Some(&old),
+            None,
+        ));
+    }
+}
+
+fn diff_compound_unique(old: &toml::Table, new: &toml::Table, changes: &mut Vec<ContractChange>) {
+    let sets = |table: &toml::Table| -> Vec<toml::Value> {
+        table
+            .get("compound_unique")
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.get("columns").cloned())
+            .collect()
+    };
+    let old_sets = sets(old);
+    let new_sets = sets(new);
+    for set in old_sets.iter().filter(|set| !new_sets.contains(set)) {
+        changes.push(change(
+            ChangeKind::Removed,
+            "[compound_unique]",
+            "columns",
+            Some(set),
+            None,
+        ));
+    }
+    for set in new_sets.iter().filter(|set| !old_sets.contains(set)) {
+        changes.push(change(
+            ChangeKind::Added,
+            "[compound_unique]",
+            "columns",
+            None,
+            Some(set),
+        ));
+    }
+}
+
+/// Compare two values under `section`, descending into tables so each
+/// changed key is reported by its dotted path.
+fn diff_values(
+    section: &str,
+    path: &str,
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+    changes: &mut Vec<ContractChange>,
+) {
+    match (old, new) {
+        (Some(toml::Value::Table(old)), Some(toml::Value::Table(new))) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_values(section, &path, old.get(key), new.get(key), changes);
+            }
+        }
+        (Some(old), Some(new)) if old != new => changes.push(change(
+            ChangeKind::Changed,
+            section,
+            path,
+            Some(old),
+            Some(new),
+        )),
+        (Some(_), Some(_)) | (None, None) => {}
+        (old, new) => {
+            let kind = if old.is_some() {
+                ChangeKind::Removed
+            } else {
+                ChangeKind::Added
+            };
+            changes.push(change(kind, section, path, old, new));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+dtype = "Int64"
+validation = [{ rule = "not_null" }, { rule = "unique" }]
+
+[[columns]]
+name = "amount"
+dtype = "Int64"
+validation = [{ rule = "range", min = 0, max = 10 }]
+
+[[columns]]
+name = "legacy"
+validation = []
+
+[source]
+type = "local"
+location = "data/orders.csv"
+"#;
+
+    const NEW: &str = r#"
+[contract]
+name = "orders"
+version = "1.1.0"
+tags = []
+
+[[columns]]
+name = "id"
+dtype = "Int64"
+validation = [{ rule = "not_null" }]
+
+[[columns]]
+name = "amount"
+dtype = "Float64"
+validation = [{ rule = "range", min = 0, max = 100 }, { rule = "not_null" }]
+
+[[columns]]
+name = "region"
+validation = []
+
+[[compound_unique]]
+columns = ["id", "region"]
+
+[source]
+type = "local"
+location = "data/orders/*.csv"
+
+[limits]
+max_rows = 1000
+"#;
+
+    #[test]
+    fn reports_column_rule_and_section_changes() {
+        let diff = diff_contract_toml(OLD, NEW).unwrap();
+        let lines: Vec<String> = diff.changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "- column 'legacy'",
+                "- column 'id' rule unique { rule = \"unique\" }",
+                "~ column 'amount' dtype: \"Int64\" → \"Float64\"",
+                "~ column 'amount' rule range: { max = 10, min = 0, rule = \"range\" } → \
+                 { max = 100, min = 0, rule = \"range\" }",
+                "+ column 'amount' rule not_null { rule = \"not_null\" }",
+                "+ column 'region'",
+                "+ [compound_unique] columns [\"id\", \"region\"]",
+                "~ [contract] version: \"1.0.0\" → \"1.1.0\"",
+                "+ [limits] { max_rows = 1000 }",
+                "~ [source] location: \"data/orders.csv\" → \"data/orders/*.csv\"",
+            ]
+        );
+        assert_eq!(diff.from_version, "1.0.0");
+        assert_ne!(diff.from_hash, diff.to_hash);
+    }
+
+    #[test]
+    fn identical_contracts_have_no_changes() {
+        let diff = diff_contract_toml(OLD, OLD).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.from_hash, diff.to_hash);
+    }
+}
@@ -0,0 +1,215 @@
+//! Diffing two recorded runs of a contract.
+//!
+//! Compares the column statistics stored in the run history
+//! (`.pipa/state/<contract>/history.jsonl`) and reports:
+//! - row count delta,
+//! - added and removed columns,
+//! - per-column changes in null/distinct ratios, mean, and stdev.
+
+use crate::contracts::DriftMetric;
+use crate::engine::log_action;
+use crate::history::{HistoryStore, RunHistory, RunStats};
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use serde::Serialize;
+
+/// Metrics compared per column, in report order.
+const COLUMN_METRICS: [DriftMetric; 4] = [
+    DriftMetric::NullRatio,
+    DriftMetric::DistinctRatio,
+    DriftMetric::Mean,
+    DriftMetric::Stdev,
+];
+
+/// A change in one statistic of one column.
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnChange {
+    pub column: String,
+    pub metric: &'static str,
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+/// Differences between two recorded runs.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunDiff {
+    pub contract: String,
+    pub from_run: String,
+    pub to_run: String,
+    pub rows_from: usize,
+    pub rows_to: usize,
+    pub row_delta: i64,
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    pub changes: Vec<ColumnChange>,
+}
+
+impl RunDiff {
+    /// Whether the two runs have identical statistics.
+    pub fn is_empty(&self) -> bool {
+        self.row_delta == 0
+            && self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.changes.is_empty()
+    }
+}
+
+/// Diff two recorded runs of `contract_name`.
+///
+/// # Arguments
+/// * `logger` - The audit logger implementation to use
+/// * `contract_name` - Contract whose history is used
+/// * `runs` - Run ids: none compares the last two runs, one compares that
+///   run with the latest, two compare the first with the second
+pub fn diff_runs<L: AuditLogger>(
+    logger: &L,
+    contract_name: &str,
+    runs: &[String],
+) -> ValidationResult<(RunDiff, String)> {
+    let history = HistoryStore::default().runs(contract_name)?;
+    let find = |id: &str| {
+        history.iter().find(|run| run.run_id == id).ok_or_else(|| {
+            let recent: Vec<&str> = history
+                .iter()
+                .rev()
+                .take(5)
+                .map(|run| run.run_id.as_str())
+                .collect();
+            ValidationError::RunNotFound(format!(
+                "run '{}' for contract '{}' (recent runs: {})",
+                id,
+                contract_name,
+                if recent.is_empty() {
+                    "none".to_string()
+                } else {
+                    recent.join(", ")
+                }
+            ))
+        })
+    };
+    let latest = || {
+        history.last().ok_or_else(|| {
+            ValidationError::RunNotFound(format!(
+                "no recorded runs for contract '{}'",
+                contract_name
+            ))
+        })
+    };
+
+    let (from, to) = match runs {
+        [] if history.len() >= 2 => (&history[history.len() - 2], &history[history.len() - 1]),
+        [] => {
+            return Err(ValidationError::RunNotFound(format!(
+                "contract '{}' needs at least two recorded runs to diff",
+                contract_name
+            )));
+        }
+        [a] => (find(a)?, latest()?),
+        [a, b] => (find(a)?, find(b)?),
+        _ => {
+            return Err(ValidationError::Config(
+                "at most two runs can be compared".to_string(),
+            ));
+        }
+    };
+
+    let diff = diff_stats(contract_name, from, to);
+    let message = log_action(
+        logger,
+        "runs_diffed",
+        Some(&format!(
+            "from={}, to={}, changes={}",
+            diff.from_run,
+            diff.to_run,
+            diff.changes.len()
+        )),
+        Some(contract_name),
+        Some(&to.contract_version),
+        None,
+    );
+    Ok((diff, message))
+}
+
+/// Compare the statistics of two runs.
+pub fn diff_stats(contract_name: &str, from: &RunStats, to: &RunStats) -> RunDiff {
+    let added_columns = to
+        .columns
+        .keys()
+        .filter(|name| !from.columns.contains_key(*name))
+        .cloned()
+        .collect();
+    let removed_columns = from
+        .columns
+        .keys()
+        .filter(|name| !to.columns.contains_key(*name))
+        .cloned()
+        .collect();
+
+    let mut changes = Vec::new();
+    for (name, before) in &from.columns {
+        let Some(after) = to.columns.get(name) else {
+            continue;
+        };
+        for metric in COLUMN_METRICS {
+            let (a, b) = (before.metric(metric), after.metric(metric));
+            if a != b {
+                changes.push(ColumnChange {
+                    column: name.clone(),
+                    metric: metric.as_str(),
+                    from: a,
+                    to: b,
+                });
+            }
+        }
+    }
+
+    RunDiff {
+        contract: contract_name.to_string(),
+        from_run: from.run_id.clone(),
+        to_run: to.run_id.clone(),
+        rows_from: from.rows,
+        rows_to: to.rows,
+        row_delta: to.rows as i64 - from.rows as i64,
+        added_columns,
+        removed_columns,
+        changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn run(id: &str, df: DataFrame) -> RunStats {
+        RunStats::from_dataframe(&df, "1.0.0", id).unwrap()
+    }
+
+    #[test]
+    fn reports_columns_rows_and_shifts() {
+        let from = run(
+            "a",
+            df!("id" => &[1i64, 2, 3], "legacy" => &["x", "y", "z"]).unwrap(),
+        );
+        let to = run(
+            "b",
+            df!("id" => &[1i64, 2, 3, 10], "email" => &["a", "b", "c", "d"]).unwrap(),
+        );
+
+        let diff = diff_stats("orders", &from, &to);
+        assert_eq!(diff.row_delta, 1);
+        assert_eq!(diff.added_columns, vec!["email".to_string()]);
+        assert_eq!(diff.removed_columns, vec!["legacy".to_string()]);
+        let metrics: Vec<_> = diff.changes.iter().map(|c| c.metric).collect();
+        assert_eq!(metrics, vec!["mean", "stdev"]);
+        assert_eq!(diff.changes[0].from, Some(2.0));
+        assert_eq!(diff.changes[0].to, Some(4.0));
+    }
+
+    #[test]
+    fn identical_runs_have_no_diff() {
+        let df = df!("id" => &[1i64, 2]).unwrap();
+        let diff = diff_stats("orders", &run("a", df.clone()), &run("b", df));
+        assert!(diff.is_empty());
+    }
+}
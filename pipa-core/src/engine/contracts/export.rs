@@ -0,0 +1,318 @@
+//! Exporting contracts to other schema languages (`pipa contract export`).
+//!
+//! `json-schema` renders a JSON Schema (draft 2020-12) for one row of the
+//! contract's data: an object with a property per `[[columns]]` entry.
+//!
+//! - `dtype` (or a `type` / `boolean` rule) → `type`: `integer`, `number`,
+//!   `string`, or `boolean`; `Date` / `Datetime` add `format` `date` /
+//!   `date-time`.
+//! - `not_null` → listed in `required`; other columns also allow `null`.
+//! - `pattern` → `pattern`; `in_set` → `enum`; `not_in_set` → `not: { enum }`.
+//! - `range` → `minimum` / `maximum`; `max_length` → `maxLength`.
+//! - `uuid`, `email`, `url`, `ipv4`, `ipv6` → `format` `uuid`, `email`,
+//!   `uri`, `ipv4`, `ipv6`; `phone_e164` → the equivalent `pattern`.
+//! - `[file]` `expected_columns` without `allow_extra_columns` →
+//!   `additionalProperties: false`.
+//!
+//! Rules with no JSON Schema equivalent (statistical, uniqueness, drift,
+//! custom, ...) are left out and listed in `ContractExport::skipped`.
+//! Patterns are copied as written: Rust `regex` syntax is close to, but not
+//! the same as, the ECMA-262 dialect JSON Schema validators use.
+
+use crate::config::contract_path;
+use crate::contracts::column::ColumnContracts;
+use crate::contracts::{ContractType, SchemaContracts, parse_contract};
+use crate::engine::log_action;
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use serde_json::{Map, Value, json};
+use std::str::FromStr;
+
+/// Target format for `export_contract`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// JSON Schema, draft 2020-12 (`json-schema`).
+    JsonSchema,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json-schema" => Ok(Self::JsonSchema),
+            other => Err(format!(
+                "unknown export format '{}' (supported: json-schema)",
+                other
+            )),
+        }
+    }
+}
+
+/// A contract rendered in another schema language.
+#[derive(Debug, Clone)]
+pub struct ContractExport {
+    pub document: Value,
+    /// Rules left out for lack of an equivalent, as `column 'x': rule`.
+    pub skipped: Vec<String>,
+}
+
+/// Export `contracts/<name>.toml` in `format`.
+pub fn export_contract<L: AuditLogger>(
+    logger: &L,
+    name: &str,
+    format: ExportFormat,
+) -> ValidationResult<(ContractExport, String)> {
+    let toml_str = std::fs::read_to_string(contract_path(name))
+        .map_err(|_| ValidationError::ContractNotFound(name.to_string()))?;
+    let contracts = parse_contract(&toml_str)?;
+
+    let export = match format {
+        ExportFormat::JsonSchema => json_schema(&contracts),
+    };
+    let message = log_action(
+        logger,
+        "contract_exported",
+        Some(&format!(
+            "format=json-schema, skipped={}",
+            export.skipped.len()
+        )),
+        Some(&contracts.contract.name),
+        Some(&contracts.contract.version),
+        None,
+    );
+    Ok((export, message))
+}
+
+/// Render a JSON Schema for one row of `contracts`' data.
+pub fn json_schema(contracts: &SchemaContracts) -> ContractExport {
+    let mut skipped = Vec::new();
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for col in &contracts.columns {
+        let (property, not_null) = column_schema(col, &mut skipped);
+        if not_null {
+            required.push(Value::String(col.name.clone()));
+        }
+        properties.insert(col.name.clone(), Value::Object(property));
+    }
+
+    let mut document = Map::new();
+    document.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    document.insert("title".to_string(), json!(contracts.contract.name));
+    document.insert(
+        "description".to_string(),
+        json!(format!(
+            "Row of contract '{}' version {}",
+            contracts.contract.name, contracts.contract.version
+        )),
+    );
+    document.insert("type".to_string(), json!("object"));
+    document.insert("properties".to_string(), Value::Object(properties));
+    document.insert("required".to_string(), Value::Array(required));
+
+    for rule in contracts.file.iter().flat_map(|file| &file.validation) {
+        match rule {
+            ContractType::ExpectedColumns {
+                allow_extra_columns,
+                ..
+            } => {
+                if !allow_extra_columns {
+                    document.insert("additionalProperties".to_string(), json!(false));
+                }
+            }
+            other => skipped.push(format!("[file]: {}", other.name())),
+        }
+    }
+
+    ContractExport {
+        document: Value::Object(document),
+        skipped,
+    }
+}
+
+/// The property schema for one column, and whether it is `not_null`.
+fn column_schema(col: &ColumnContracts, skipped: &mut Vec<String>) -> (Map<String, Value>, bool) {
+    let mut property = Map::new();
+    let not_null = col
+        .validation
+        .iter()
+        .any(|rule| matches!(rule, ContractType::NotNull));
+
+    let declared = col.dtype.as_deref().or_else(|| {
+        col.validation.iter().find_map(|rule| match rule {
+            ContractType::Type { dtype } => Some(dtype.as_str()),
+            ContractType::Boolean => Some("Boolean"),
+            _ => None,
+        })
+    });
+    if let Some((json_type, format)) = declared.and_then(json_type) {
+        property.insert(
+            "type".to_string(),
+            if not_null {
+                json!(json_type)
+            } else {
+                json!([json_type, "null"])
+            },
+        );
+        if let Some(format) = format {
+            property.insert("format".to_string(), json!(format));
+        }
+    }
+
+    for rule in &col.validation {
+        match rule {
+            ContractType::NotNull | ContractType::Type { .. } | ContractType::Boolean => {}
+            ContractType::Pattern { pattern } => {
+                property.insert("pattern".to_string(), json!(pattern));
+            }
+            ContractType::InSet { values } => {
+                let mut values: Vec<Value> = values.iter().map(|v| json!(v)).collect();
+                if !not_null {
+                    values.push(Value::Null);
+                }
+                property.insert("enum".to_string(), Value::Array(values));
+            }
+            ContractType::NotInSet { values } => {
+                property.insert("not".to_string(), json!({ "enum": values }));
+            }
+            ContractType::Range { min, max } => {
+                property.insert("minimum".to_string(), json!(min));
+                property.insert("maximum".to_string(), json!(max));
+            }
+            ContractType::MaxLength { value } => {
+                property.insert("maxLength".to_string(), json!(value));
+            }
+            ContractType::Uuid => {
+                property.insert("format".to_string(), json!("uuid"));
+            }
+            ContractType::Email => {
+                property.insert("format".to_string(), json!("email"));
+            }
+            ContractType::Url => {
+                property.insert("format".to_string(), json!("uri"));
+            }
+            ContractType::Ipv4 => {
+                property.insert("format".to_string(), json!("ipv4"));
+            }
+            ContractType::Ipv6 => {
+                property.insert("format".to_string(), json!("ipv6"));
+            }
+            ContractType::PhoneE164 => {
+                property.insert("pattern".to_string(), json!("^\\+[1-9][0-9]{1,14}$"));
+            }
+            other => skipped.push(format!("column '{}': {}", col.name, other.name())),
+        }
+    }
+    (property, not_null)
+}
+
+/// JSON Schema `type` (and `format`) for a Polars dtype, if it maps.
+fn json_type(dtype: &str) -> Option<(&'static str, Option<&'static str>)> {
+    match dtype {
+        "String" => Some(("string", None)),
+        "Boolean" => Some(("boolean", None)),
+        "Date" => Some(("string", Some("date"))),
+        d if d.starts_with("Datetime") => Some(("string", Some("date-time"))),
+        d if d.starts_with("Int") || d.starts_with("UInt") => Some(("integer", None)),
+        d if d.starts_with("Float") => Some(("number", None)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = r#"
+[contract]
+name = "orders"
+version = "1.2.0"
+tags = []
+
+[file]
+validation = [{ rule = "expected_columns", columns = ["id", "status", "amount"] }]
+
+[[columns]]
+name = "id"
+dtype = "Int64"
+validation = [{ rule = "not_null" }, { rule = "unique" }, { rule = "range", min = 1, max = 999 }]
+
+[[columns]]
+name = "status"
+dtype = "String"
+validation = [{ rule = "in_set", values = ["open", "closed"] }, { rule = "pattern", pattern = "^[a-z]+$" }]
+
+[[columns]]
+name = "amount"
+validation = [{ rule = "type", dtype = "Float64" }, { rule = "mean_between", min = 0.0, max = 10.0 }]
+"#;
+
+    #[test]
+    fn maps_columns_and_rules_to_json_schema() {
+        let export = json_schema(&parse_contract(CONTRACT).unwrap());
+        assert_eq!(
+            export.document,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "orders",
+                "description": "Row of contract 'orders' version 1.2.0",
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer", "minimum": 1, "maximum": 999 },
+                    "status": {
+                        "type": ["string", "null"],
+                        "enum": ["open", "closed", null],
+                        "pattern": "^[a-z]+$"
+                    },
+                    "amount": { "type": ["number", "null"] }
+                },
+                "required": ["id"],
+                "additionalProperties": false
+            })
+        );
+        assert_eq!(
+            export.skipped,
+            vec!["column 'id': unique", "column 'amount': mean_between"]
+        );
+    }
+
+    #[test]
+    fn maps_semantic_rules_to_formats() {
+        let export = json_schema(
+            &parse_contract(
+                r#"
+[contract]
+name = "users"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "email"
+validation = [{ rule = "not_null" }, { rule = "email" }]
+
+[[columns]]
+name = "phone"
+validation = [{ rule = "phone_e164" }]
+"#,
+            )
+            .unwrap(),
+        );
+        let properties = &export.document["properties"];
+        assert_eq!(properties["email"], json!({ "format": "email" }));
+        assert_eq!(
+            properties["phone"],
+            json!({ "pattern": "^\\+[1-9][0-9]{1,14}$" })
+        );
+        assert!(export.skipped.is_empty());
+    }
+
+    #[test]
+    fn parses_export_formats() {
+        assert_eq!("json-schema".parse(), Ok(ExportFormat::JsonSchema));
+        assert!("avro".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,481 @@
+//! Canonical formatting of contract TOML (`pipa contract fmt`).
+//!
+//! Formatting keeps every value and comment, and normalizes the layout so
+//! that two people editing the same contract produce the same text:
+//! - top-level sections in a fixed order: `[contract]`, `[vars]`, `[file]`,
+//!   `[[columns]]`, `[[compound_unique]]`, `[source]` / `[[sources]]` /
+//!   `[combine]`, `[destination]`, `[quarantine]`, then `[limits]`,
+//!   `[sampling]`, `[streaming]`, `[schedule]`, and `[notifications]`;
+//!   unknown sections follow in their original order,
+//! - identifying keys first (`name`, `version`, `tags`, `dtype`, `type`,
+//!   `location`, `profile`) and `validation` last,
+//! - each `validation` list as one inline `{ rule = ... }` table per line,
+//!   including lists written as `[[columns.validation]]` tables,
+//! - `key = value` spacing, one blank line between sections, and strings
+//!   quoted the default way (single quotes for regexes with backslashes).
+//!
+//! Rule names that differ from a known rule only in case or separators
+//! (`NotNull`, `not-null`) are reported as `RuleFix`es, and rewritten when
+//! formatting with `fix`.
+
+use crate::config::contract_path;
+use crate::engine::log_action;
+use crate::engine::rules::catalog;
+use crate::logging::AuditLogger;
+use toml_edit::{
+    Array, ArrayOfTables, Decor, DocumentMut, Formatted, InlineTable, Item, Table, Value,
+};
+
+/// Top-level sections in canonical order.
+const SECTION_ORDER: &[&str] = &[
+    "contract",
+    "vars",
+    "file",
+    "columns",
+    "headers",
+    "mapping",
+    "transform",
+    "compound_unique",
+    "source",
+    "sources",
+    "combine",
+    "destination",
+    "quarantine",
+    "limits",
+    "retry",
+    "sampling",
+    "streaming",
+    "schedule",
+    "notifications",
+];
+
+/// Keys written first in any table, in this order.
+const LEADING_KEYS: &[&str] = &[
+    "rule", "name", "version", "tags", "dtype", "start", "width", "aliases", "type", "location",
+    "profile",
+];
+
+/// A contract in canonical form.
+#[derive(Debug, Clone)]
+pub struct ContractFormat {
+    pub toml: String,
+    /// Whether `toml` differs from the input.
+    pub changed: bool,
+    /// Misspelled rule names, rewritten when formatting with `fix`.
+    pub fixes: Vec<RuleFix>,
+}
+
+/// A rule name that only differs from a known rule in spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleFix {
+    /// Where in the contract: `column 'amount'` or `[file]`.
+    pub location: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Format `contracts/<name>.toml`. The file is left as it is; callers
+/// write `ContractFormat::toml` back when it `changed`.
+///
+/// # Errors
+/// Returns a message if the contract can't be read or isn't valid TOML.
+pub fn format_contract<L: AuditLogger>(
+    logger: &L,
+    name: &str,
+    fix: bool,
+) -> Result<(ContractFormat, String), String> {
+    let path = contract_path(name);
+    let toml_str = std::fs::read_to_string(&path).map_err(|_| "Contract not found".to_string())?;
+    let formatted =
+        format_contract_toml(&toml_str, fix).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let message = log_action(
+        logger,
+        "contract_formatted",
+        Some(&format!(
+            "changed={}, rule_fixes={}",
+            formatted.changed,
+            formatted.fixes.len()
+        )),
+        Some(name),
+        None,
+        None,
+    );
+    Ok((formatted, message))
+}
+
+/// Format contract TOML; see the module docs for the canonical form.
+///
+/// # Errors
+/// Returns the parser's message if `toml_str` isn't valid TOML.
+pub fn format_contract_toml(toml_str: &str, fix: bool) -> Result<ContractFormat, String> {
+    let mut doc: DocumentMut = toml_str
+        .parse()
+        .map_err(|e: toml_edit::TomlError| e.message().trim().to_string())?;
+
+    let root = doc.as_table_mut();
+    let header = take_header_comment(root);
+    let mut fixes = Vec::new();
+    if let Some(file) = root.get_mut("file").and_then(Item::as_table_mut) {
+        format_rules(file, "[file]", fix, &mut fixes);
+    }
+    if let Some(columns) = root
+        .get_mut("columns")
+        .and_then(Item::as_array_of_tables_mut)
+    {
+        for (i, column) in columns.iter_mut().enumerate() {
+            let location = match column.get("name").and_then(Item::as_str) {
+                Some(name) => format!("column '{}'", name),
+                None => format!("columns[{}]", i),
+            };
+            format_rules(column, &location, fix, &mut fixes);
+        }
+    }
+
+    root.sort_values_by(|a, _, b, _| section_rank(a).cmp(&section_rank(b)));
+    format_table(root);
+    let mut position = 0;
+    let mut first = true;
+    for (_, item) in root.iter_mut() {
+        renumber(item, &mut position, &mut first);
+    }
+    doc.set_trailing("");
+
+    let mut toml = header + doc.to_string().trim_start_matches('\n');
+    if !toml.ends_with('\n') {
+        toml.push('\n');
+    }
+    Ok(ContractFormat {
+        changed: toml != toml_str,
+        toml,
+        fixes,
+    })
+}
+
+fn section_rank(key: &str) -> usize {
+    SECTION_ORDER
+        .iter()
+        .position(|section| *section == key)
+        .unwrap_or(SECTION_ORDER.len())
+}
+
+fn key_rank(key: &str) -> usize {
+    match LEADING_KEYS.iter().position(|leading| *leading == key) {
+        Some(rank) => rank,
+        None if key == "validation" => LEADING_KEYS.len() + 1,
+        None => LEADING_KEYS.len(),
+    }
+}
+
+/// Detach the comment at the top of the file from the section it sits
+/// above, so it stays at the top when sections are reordered.
+fn take_header_comment(root: &mut Table) -> String {
+    if root.iter().any(|(_, item)| item.is_value()) {
+        return String::new();
+    }
+    let mut first: Option<&mut Table> = None;
+    for (_, item) in root.iter_mut() {
+        let tables: Vec<&mut Table> = match item {
+            Item::Table(table) if !table.is_implicit() => vec![table],
+            Item::ArrayOfTables(tables) => tables.iter_mut().collect(),
+            _ => continue,
+        };
+        for table in tables {
+            let earlier = match (&first, table.position()) {
+                (Some(current), Some(position)) => current.position().is_none_or(|p| position < p),
+                (None, _) => true,
+                (Some(_), None) => false,
+            };
+            if earlier {
+                first = Some(table);
+            }
+        }
+    }
+    let Some(first) = first else {
+        return String::new();
+    };
+    let header = comment_lines(first.decor().prefix().and_then(|p| p.as_str()), "");
+    first.decor_mut().set_prefix("");
+    header
+}
+
+/// Rewrite a table's `validation` list as one inline rule table per line.
+fn format_rules(table: &mut Table, location: &str, fix: bool, fixes: &mut Vec<RuleFix>) {
+    let Some(item) = table.get_mut("validation") else {
+        return;
+    };
+    if let Some(tables) = item.as_array_of_tables() {
+        *item = Item::Value(Value::Array(rules_from_tables(tables)));
+    }
+    let Some(rules) = item.as_array_mut() else {
+        return;
+    };
+
+    for rule in rules.iter_mut() {
+        let comments = comment_lines(rule.decor().prefix().and_then(|p| p.as_str()), "  ");
+        if let Value::InlineTable(rule) = rule {
+            fix_rule_name(rule, location, fix, fixes);
+            rule.sort_values_by(|a, _, b, _| key_rank(a).cmp(&key_rank(b)));
+            rule.fmt();
+        }
+        rule.decor_mut().set_prefix(format!("\n{}  ", comments));
+        rule.decor_mut().set_suffix("");
+    }
+    if rules.is_empty() {
+        rules.set_trailing("");
+        rules.set_trailing_comma(false);
+    } else {
+        let trailing = comment_lines(rules.trailing().as_str(), "  ");
+        rules.set_trailing(format!("\n{}", trailing));
+        rules.set_trailing_comma(true);
+    }
+}
+
+/// Inline tables for rules written as `[[columns.validation]]`.
+fn rules_from_tables(tables: &ArrayOfTables) -> Array {
+    let mut rules = Array::new();
+    for table in tables.iter() {
+        let comments = comment_lines(table.decor().prefix().and_then(|p| p.as_str()), "  ");
+        let mut rule = InlineTable::new();
+        for (key, item) in table.iter() {
+            if let Some(value) = item.as_value() {
+                rule.insert(key, value.clone());
+            }
+        }
+        rule.decor_mut().set_prefix(comments);
+        rules.push_formatted(Value::InlineTable(rule));
+    }
+    rules
+}
+
+/// Report, and with `fix` rewrite, a rule name that is a known rule
+/// spelled with different case or separators.
+fn fix_rule_name(rule: &mut InlineTable, location: &str, fix: bool, fixes: &mut Vec<RuleFix>) {
+    let Some(name) = rule.get("rule").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(known) = known_spelling(name) else {
+        return;
+    };
+    fixes.push(RuleFix {
+        location: location.to_string(),
+        from: name.to_string(),
+        to: known.to_string(),
+    });
+    if fix {
+        rule.insert("rule", Value::from(known));
+    }
+}
+
+/// The catalog rule `name` is a respelling of, if it isn't one already.
+fn known_spelling(name: &str) -> Option<&'static str> {
+    let squash = |name: &str| {
+        name.chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let rules = catalog();
+    if rules.iter().any(|info| info.name == name) {
+        return None;
+    }
+    let squashed = squash(name);
+    let mut matches = rules.iter().filter(|info| squash(info.name) == squashed);
+    match (matches.next(), matches.next()) {
+        (Some(info), None) => Some(info.name),
+        _ => None,
+    }
+}
+
+/// Order a table's keys and normalize its `key = value` spacing and the
+/// quoting of its strings, recursing into subtables.
+fn format_table(table: &mut Table) {
+    table.sort_values_by(|a, _, b, _| key_rank(a).cmp(&key_rank(b)));
+    for (mut key, item) in table.iter_mut() {
+        match item {
+            Item::Value(value) => {
+                let comments =
+                    comment_lines(key.leaf_decor().prefix().and_then(|p| p.as_str()), "");
+                key.leaf_decor_mut().set_prefix(comments);
+                key.leaf_decor_mut().set_suffix(" ");
+                let trailing = trailing_comment(value.decor());
+                normalize_strings(value);
+                value.decor_mut().set_prefix(" ");
+                value.decor_mut().set_suffix(trailing);
+            }
+            Item::Table(table) => format_table(table),
+            Item::ArrayOfTables(tables) => tables.iter_mut().for_each(format_table),
+            Item::None => {}
+        }
+    }
+}
+
+/// Give every table a document position in key order, with a blank line
+/// before each header but the first.
+fn renumber(item: &mut Item, position: &mut isize, first: &mut bool) {
+    let place = |table: &mut Table, position: &mut isize, first: &mut bool| {
+        *position += 1;
+        table.set_position(*position);
+        if !table.is_implicit() {
+            let decor = table.decor_mut();
+            let comments = comment_lines(decor.prefix().and_then(|p| p.as_str()), "");
+            let blank = if *first { "" } else { "\n" };
+            decor.set_prefix(format!("{}{}", blank, comments));
+            let trailing = trailing_comment(decor);
+            decor.set_suffix(trailing);
+            *first = false;
+        }
+        for (_, child) in table.iter_mut() {
+            renumber(child, position, first);
+        }
+    };
+    match item {
+        Item::Table(table) => place(table, position, first),
+        Item::ArrayOfTables(tables) => {
+            for table in tables.iter_mut() {
+                place(table, position, first);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Requote strings the default way, keeping their surrounding decor.
+fn normalize_strings(value: &mut Value) {
+    match value {
+        Value::String(string) => {
+            let decor = string.decor().clone();
+            *string = Formatted::new(string.value().clone());
+            *string.decor_mut() = decor;
+        }
+        Value::Array(array) => array.iter_mut().for_each(normalize_strings),
+        Value::InlineTable(table) => table.iter_mut().for_each(|(_, v)| normalize_strings(v)),
+        _ => {}
+    }
+}
+
+/// The comment lines of a decor string, each indented by `indent`.
+fn comment_lines(raw: Option<&str>, indent: &str) -> String {
+    raw.unwrap_or("")
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+        .map(|line| format!("{}{}\n", indent, line))
+        .collect()
+}
+
+/// A same-line comment after a value or header, as `  # ...`.
+fn trailing_comment(decor: &Decor) -> String {
+    decor
+        .suffix()
+        .and_then(|s| s.as_str())
+        .map(str::trim)
+        .filter(|s| s.starts_with('#'))
+        .map(|comment| format!("  {}", comment))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::SchemaContracts;
+
+    const MESSY: &str = r#"# Orders landed by the nightly export
+[source]
+location="data/orders.csv"
+type   =   "local"
+
+[[columns]]
+validation = [{ rule = "NotNull" },{rule="pattern",pattern="^\\d+$"}, # ids
+  { min = 0, rule = "range", max = 10 }]
+name = 'order_id'
+
+[contract]
+version = "0.1.0"
+name = "orders"   # display name
+tags = ["a"]
+[file]
+validation = [ {rule = "row_count", min = 1} ]
+
+[[columns]]
+name = "note"
+[[columns.validation]]
+rule = "max_length"
+value = 200
+"#;
+
+    #[test]
+    fn formats_into_canonical_order_and_layout() {
+        let formatted = format_contract_toml(MESSY, false).unwrap();
+        assert!(formatted.changed);
+        assert_eq!(
+            formatted.toml,
+            r#"# Orders landed by the nightly export
+[contract]
+name = "orders"  # display name
+version = "0.1.0"
+tags = ["a"]
+
+[file]
+validation = [
+  { rule = "row_count", min = 1 },
+]
+
+[[columns]]
+name = "order_id"
+validation = [
+  { rule = "NotNull" },
+  { rule = "pattern", pattern = '^\d+$' },
+  # ids
+  { rule = "range", min = 0, max = 10 },
+]
+
+[[columns]]
+name = "note"
+validation = [
+  { rule = "max_length", value = 200 },
+]
+
+[source]
+type = "local"
+location = "data/orders.csv"
+"#
+        );
+        assert_eq!(
+            formatted.fixes,
+            vec![RuleFix {
+                location: "column 'order_id'".to_string(),
+                from: "NotNull".to_string(),
+                to: "not_null".to_string(),
+            }]
+        );
+
+        let again = format_contract_toml(&formatted.toml, false).unwrap();
+        assert!(!again.changed, "{}", again.toml);
+    }
+
+    #[test]
+    fn fix_rewrites_respelled_rule_names() {
+        let formatted = format_contract_toml(MESSY, true).unwrap();
+        assert!(formatted.toml.contains("{ rule = \"not_null\" }"));
+
+        let contract: SchemaContracts = toml::from_str(&formatted.toml).unwrap();
+        assert_eq!(contract.columns[0].validation.len(), 3);
+        assert_eq!(contract.columns[1].validation.len(), 1);
+        assert!(
+            format_contract_toml(&formatted.toml, true)
+                .unwrap()
+                .fixes
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn unknown_names_are_not_guessed_and_syntax_errors_are_reported() {
+        assert_eq!(known_spelling("Unique"), Some("unique"));
+        assert_eq!(known_spelling("unique"), None);
+        assert_eq!(known_spelling("not_nul"), None);
+
+        assert!(format_contract_toml("[contract\nname = 1", false).is_err());
+    }
+}
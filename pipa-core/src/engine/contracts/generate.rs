@@ -0,0 +1,280 @@
+//! Draft contract generation from sample data.
+//!
+//! Profiles a sample file (see `engine::profiler`) and renders a TOML
+//! contract with:
+//! - one `[[columns]]` entry per column, with the inferred `dtype`,
+//! - suggested `not_null` (no nulls seen), `unique` (all values distinct),
+//!   and `range` (integer columns, observed min/max) rules,
+//! - a `[source]` block pre-filled with the sample's location.
+//!
+//! The draft is a starting point: suggestions only reflect the sample.
+//!
+//! `ContractDraft` is the rendered form; `pipa contract new` fills one in
+//! from the operator's answers instead of a profile.
+
+use crate::engine::log_action;
+use crate::engine::profiler::{ColumnProfile, DataProfile, profile_data};
+use crate::logging::AuditLogger;
+use crate::logging::error::ValidationResult;
+use std::path::Path;
+
+/// A generated draft contract.
+pub struct GeneratedContract {
+    pub name: String,   // contract name (defaults to the sample's file stem)
+    pub toml: String,   // rendered contract
+    pub columns: usize, // number of columns described
+}
+
+/// A contract to render: name, source, and columns with their rules.
+#[derive(Debug, Clone, Default)]
+pub struct ContractDraft {
+    pub name: String,
+    /// Connector type (`local`, `s3`, `gcs`, `azure`, `kafka`).
+    pub source_type: String,
+    pub location: String,
+    pub profile: Option<String>,
+    pub columns: Vec<DraftColumn>,
+}
+
+/// One `[[columns]]` entry of a `ContractDraft`.
+#[derive(Debug, Clone, Default)]
+pub struct DraftColumn {
+    pub name: String,
+    pub dtype: Option<String>,
+    /// Rules as inline TOML tables, e.g. `{ rule = "not_null" }`.
+    pub rules: Vec<String>,
+}
+
+impl DraftColumn {
+    /// Add a rule that takes no parameters (`not_null`, `unique`, ...).
+    pub fn with_rule(mut self, rule: &str) -> Self {
+        self.rules.push(format!("{{ rule = {} }}", quote(rule)));
+        self
+    }
+}
+
+impl ContractDraft {
+    /// A draft for a profiled dataset: every column with its inferred
+    /// dtype and the rules the sample suggests.
+    pub fn from_profile(name: &str, report: &DataProfile, profile: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            source_type: report.source_type.clone(),
+            location: report.location.clone(),
+            profile: profile.map(|p| p.to_string()),
+            columns: report
+                .columns
+                .iter()
+                .map(|col| DraftColumn {
+                    name: col.name.clone(),
+                    dtype: Some(col.inferred_dtype.clone()),
+                    rules: suggest_rules(col, report.rows),
+                })
+                .collect(),
+        }
+    }
+
+    /// Render the draft as contract TOML.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str("[contract]\n");
+        out.push_str(&format!("name = {}\n", quote(&self.name)));
+        out.push_str("version = \"0.1.0\"\n");
+        out.push_str("tags = []\n");
+
+        for col in &self.columns {
+            out.push_str("\n[[columns]]\n");
+            out.push_str(&format!("name = {}\n", quote(&col.name)));
+            if let Some(dtype) = &col.dtype {
+                out.push_str(&format!("dtype = {}\n", quote(dtype)));
+            }
+            if col.rules.is_empty() {
+                out.push_str("validation = []\n");
+            } else {
+                out.push_str("validation = [\n");
+                for rule in &col.rules {
+                    out.push_str(&format!("  {},\n", rule));
+                }
+                out.push_str("]\n");
+            }
+        }
+
+        out.push_str("\n[source]\n");
+        out.push_str(&format!("type = {}\n", quote(&self.source_type)));
+        out.push_str(&format!("location = {}\n", quote(&self.location)));
+        if let Some(profile) = &self.profile {
+            out.push_str(&format!("profile = {}\n", quote(profile)));
+        }
+        out
+    }
+}
+
+/// Profile `location` and render a draft contract for it.
+///
+/// # Arguments
+/// * `logger` - The audit logger implementation to use
+/// * `location` - Sample data location (local path or remote URL)
+/// * `name` - Contract name; defaults to the file stem of `location`
+/// * `profile` - Connection profile for remote sources
+pub async fn generate_contract<L: AuditLogger>(
+    logger: &L,
+    location: &str,
+    name: Option<&str>,
+    profile: Option<&str>,
+) -> ValidationResult<(GeneratedContract, String)> {
+    let (report, _) = profile_data(logger, location, profile, 0).await?;
+
+    let name = name
+        .map(|n| n.to_string())
+        .or_else(|| {
+            Path::new(location)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|| "generated".to_string());
+
+    let toml = render_contract(&name, &report, profile);
+    let message = log_action(
+        logger,
+        "contract_generated",
+        Some(&format!("columns={}", report.columns.len())),
+        Some(&name),
+        Some("0.1.0"),
+        Some(location),
+    );
+
+    Ok((
+        GeneratedContract {
+            name,
+            toml,
+            columns: report.columns.len(),
+        },
+        message,
+    ))
+}
+
+/// Render a draft contract for a profiled dataset.
+pub fn render_contract(name: &str, report: &DataProfile, profile: Option<&str>) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "# Draft contract generated from {} ({} rows sampled).\n",
+        report.location, report.rows
+    ));
+    out.push_str("# Review suggested rules before use: they only reflect the sample.\n\n");
+    out.push_str(&ContractDraft::from_profile(name, report, profile).render());
+    out
+}
+
+/// Suggest rules for a column from its profile.
+fn suggest_rules(col: &ColumnProfile, rows: usize) -> Vec<String> {
+    let mut rules = Vec::new();
+    if rows == 0 {
+        return rules;
+    }
+    if col.null_ratio == 0.0 {
+        rules.push("{ rule = \"not_null\" }".to_string());
+    }
+    if col.distinct_ratio == 1.0 && col.null_ratio == 0.0 {
+        rules.push("{ rule = \"unique\" }".to_string());
+    }
+    if is_integer_dtype(&col.inferred_dtype) {
+        let min = col.min.as_deref().and_then(|v| v.parse::<i64>().ok());
+        let max = col.max.as_deref().and_then(|v| v.parse::<i64>().ok());
+        if let (Some(min), Some(max)) = (min, max) {
+            rules.push(format!(
+                "{{ rule = \"range\", min = {}, max = {} }}",
+                min, max
+            ));
+        }
+    }
+    rules
+}
+
+fn is_integer_dtype(dtype: &str) -> bool {
+    matches!(
+        dtype,
+        "Int8" | "Int16" | "Int32" | "Int64" | "UInt8" | "UInt16" | "UInt32" | "UInt64"
+    )
+}
+
+/// Quote a string as a TOML basic string.
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::SchemaContracts;
+    use crate::engine::profiler::profile_dataframe;
+    use polars::prelude::*;
+
+    fn sample_report() -> DataProfile {
+        let df = df!(
+            "id" => &[1i64, 2, 3],
+            "email" => &[Some("a@x.io"), None, Some("c@x.io")],
+        )
+        .unwrap();
+        DataProfile {
+            location: "data/people.csv".to_string(),
+            source_type: "local".to_string(),
+            rows: df.height(),
+            columns: profile_dataframe(&df, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn suggests_rules_from_profile() {
+        let toml = render_contract("people", &sample_report(), None);
+        assert!(toml.contains("{ rule = \"not_null\" }"));
+        assert!(toml.contains("{ rule = \"unique\" }"));
+        assert!(toml.contains("{ rule = \"range\", min = 1, max = 3 }"));
+        // `email` has a null, so it gets no not_null/unique suggestion
+        let email = toml.split("name = \"email\"").nth(1).unwrap();
+        assert!(email.contains("validation = []"));
+    }
+
+    #[test]
+    fn renders_a_parseable_contract() {
+        let toml = render_contract("people", &sample_report(), Some("s3_raw"));
+        let contract: SchemaContracts = toml::from_str(&toml).unwrap();
+        assert_eq!(contract.contract.name, "people");
+        assert_eq!(contract.columns.len(), 2);
+        assert_eq!(contract.columns[0].dtype.as_deref(), Some("Int64"));
+        let source = contract.source.unwrap();
+        assert_eq!(source.location.as_deref(), Some("data/people.csv"));
+        assert_eq!(source.profile.as_deref(), Some("s3_raw"));
+    }
+
+    #[test]
+    fn renders_a_hand_written_draft() {
+        let draft = ContractDraft {
+            name: "orders".to_string(),
+            source_type: "s3".to_string(),
+            location: "s3://landing/orders.csv".to_string(),
+            profile: Some("landing".to_string()),
+            columns: vec![
+                DraftColumn {
+                    name: "order_id".to_string(),
+                    dtype: Some("Int64".to_string()),
+                    ..Default::default()
+                }
+                .with_rule("not_null")
+                .with_rule("unique"),
+                DraftColumn {
+                    name: "note \"free text\"".to_string(),
+                    ..Default::default()
+                },
+            ],
+        };
+        let toml = draft.render();
+        assert!(crate::engine::lint::lint_toml(&toml).is_empty(), "{}", toml);
+
+        let contract: SchemaContracts = toml::from_str(&toml).unwrap();
+        assert_eq!(contract.columns[0].validation.len(), 2);
+        assert_eq!(contract.columns[1].name, "note \"free text\"");
+        assert_eq!(contract.columns[1].dtype, None);
+        assert_eq!(contract.source.unwrap().r#type, "s3");
+    }
+}
@@ -1,9 +1,14 @@
 //! Contract metadata and syntax validation functions
 
-use crate::contracts::load_contract_for_file; // loads and parses TOML into SchemaContracts
+use crate::config::{contract_path, contracts_pattern}; // `[paths] contracts` in pipa.toml
+use crate::contracts::{ContractParseError, SchemaContracts, is_overlay, load_contract_for_file}; // parse TOML into SchemaContracts
+use crate::engine::cron::CronSchedule; // `[schedule] cron` parsing
+use crate::engine::lint::{LintDiagnostic, LintSeverity, lint_toml}; // semantic contract checks
 use crate::engine::log_action; // audit logging hook
+use crate::engine::rules::{type_mismatches, unrecognized_rules}; // load-time rule checks
 use crate::logging::AuditLogger;
 use glob; // filesystem globbing
+use serde::Serialize;
 use std::path::Path;
 
 /// Result of listing contracts
@@ -12,24 +17,45 @@ pub struct ContractList {
 }
 
 /// Result of getting a contract
+#[derive(Debug, Serialize)]
 pub struct ContractInfo {
-    pub name: String,    // contract name
-    pub version: String, // version string from contract metadata
-    pub exists: bool,    // whether the contract file exists
+    pub name: String,                      // contract name
+    pub version: String,                   // version string from contract metadata
+    pub exists: bool,                      // whether the contract file exists
+    pub contract: Option<SchemaContracts>, // parsed contract, `${...}` resolved
+    pub error: Option<String>,             // why the contract failed to load, if it did
 }
 
 /// Result of validating a contract
 pub struct ContractValidation {
-    pub valid: bool,           // true if contract parsed successfully
-    pub error: Option<String>, // error message if invalid
+    pub valid: bool,                             // true if contract parsed successfully
+    pub error: Option<String>,                   // error message if invalid
+    pub warnings: Vec<String>, // rule/dtype mismatches that would be skipped at run time
+    pub parse_error: Option<ContractParseError>, // where the TOML failed to parse, if it did
 }
 
-/// List all available contracts by scanning `contracts/*.toml`.
+/// Result of linting a contract
+pub struct ContractLint {
+    pub diagnostics: Vec<LintDiagnostic>, // findings in contract order
+}
+
+impl ContractLint {
+    /// Whether any finding is an error (the contract won't run as written).
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == LintSeverity::Error)
+    }
+}
+
+/// List all available contracts by scanning `contracts/*.toml`, leaving
+/// out environment overlays (`<name>.<env>.toml`).
 /// Returns both the list and a log message.
 pub fn list_contracts<L: AuditLogger>(logger: &L) -> Result<(ContractList, String), String> {
-    let contracts: Vec<String> = match glob::glob("contracts/*.toml") {
+    let contracts: Vec<String> = match glob::glob(&contracts_pattern()) {
         Ok(paths) => paths
             .filter_map(Result::ok)
+            .filter(|p| !is_overlay(p))
             .filter_map(|p| {
                 p.file_stem()
                     .and_then(|s| s.to_str())
@@ -44,9 +70,11 @@ pub fn list_contracts<L: AuditLogger>(logger: &L) -> Result<(ContractList, Strin
 }
 
 /// Get information about a specific contract.
-/// Returns metadata (name, version, exists) and a log message.
+/// Returns metadata (name, version, exists), the parsed contract with its
+/// columns, rules, and source/destination/quarantine blocks, and a log
+/// message.
 pub fn get_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractInfo, String) {
-    let contract_path = format!("contracts/{}.toml", name);
+    let contract_path = contract_path(name).display().to_string();
 
     if !Path::new(&contract_path).exists() {
         let message = log_action(
@@ -62,12 +90,36 @@ pub fn get_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractInfo, St
                 name: name.to_string(),
                 version: "".to_string(),
                 exists: false,
+                contract: None,
+                error: None,
             },
             message,
         );
     }
 
-    let contract = load_contract_for_file(Path::new(&contract_path));
+    let contract = match load_contract_for_file(Path::new(&contract_path)) {
+        Ok(contract) => contract,
+        Err(e) => {
+            let message = log_action(
+                logger,
+                "contract_retrieved",
+                Some(&format!("exists=true, error={}", e)),
+                Some(name),
+                None,
+                None,
+            );
+            return (
+                ContractInfo {
+                    name: name.to_string(),
+                    version: "".to_string(),
+                    exists: true,
+                    contract: None,
+                    error: Some(e.to_string()),
+                },
+                message,
+            );
+        }
+    };
     let message = log_action(
         logger,
         "contract_retrieved",
@@ -78,9 +130,11 @@ pub fn get_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractInfo, St
     );
     (
         ContractInfo {
-            name: contract.contract.name,
-            version: contract.contract.version,
+            name: contract.contract.name.clone(),
+            version: contract.contract.version.clone(),
             exists: true,
+            contract: Some(contract),
+            error: None,
         },
         message,
     )
@@ -90,7 +144,7 @@ pub fn get_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractInfo, St
 /// Attempts to parse the TOML file into a SchemaContracts.
 /// Returns validation result and a log message.
 pub fn validate_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractValidation, String) {
-    let contract_path = format!("contracts/{}.toml", name);
+    let contract_path = contract_path(name).display().to_string();
 
     if !Path::new(&contract_path).exists() {
         let message = log_action(
@@ -105,17 +159,69 @@ pub fn validate_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractVal
             ContractValidation {
                 valid: false,
                 error: Some("Contract not found".to_string()),
+                warnings: Vec::new(),
+                parse_error: None,
             },
             message,
         );
     }
 
-    match std::panic::catch_unwind(|| load_contract_for_file(Path::new(&contract_path))) {
+    match load_contract_for_file(Path::new(&contract_path)) {
+        // Rules the engine wouldn't evaluate make the contract invalid here,
+        // whether or not it opts into `strict_rules` for runs.
+        Ok(contract) if !unrecognized_rules(&contract).is_empty() => {
+            let reasons: Vec<String> = unrecognized_rules(&contract)
+                .iter()
+                .map(|r| r.to_string())
+                .collect();
+            let error = format!("rule_unrecognized: {}", reasons.join("; "));
+            let message = log_action(
+                logger,
+                "contract_validated",
+                Some(&format!("error={}", error)),
+                Some(&contract.contract.name),
+                Some(&contract.contract.version),
+                None,
+            );
+            (
+                ContractValidation {
+                    valid: false,
+                    error: Some(error),
+                    warnings: Vec::new(),
+                    parse_error: None,
+                },
+                message,
+            )
+        }
+        Ok(contract) if schedule_error(&contract).is_some() => {
+            let error = schedule_error(&contract).unwrap_or_default();
+            let message = log_action(
+                logger,
+                "contract_validated",
+                Some(&format!("error={}", error)),
+                Some(&contract.contract.name),
+                Some(&contract.contract.version),
+                None,
+            );
+            (
+                ContractValidation {
+                    valid: false,
+                    error: Some(error),
+                    warnings: Vec::new(),
+                    parse_error: None,
+                },
+                message,
+            )
+        }
         Ok(contract) => {
+            let warnings: Vec<String> = type_mismatches(&contract)
+                .iter()
+                .map(|m| m.to_string())
+                .collect();
             let message = log_action(
                 logger,
                 "contract_validated",
-                Some("valid=true"),
+                Some(&format!("valid=true, warnings={}", warnings.len())),
                 Some(&contract.contract.name),
                 Some(&contract.contract.version),
                 None,
@@ -124,15 +230,18 @@ pub fn validate_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractVal
                 ContractValidation {
                     valid: true,
                     error: None,
+                    warnings,
+                    parse_error: None,
                 },
                 message,
             )
         }
-        Err(_) => {
+        Err(e) => {
+            let error = e.to_string();
             let message = log_action(
                 logger,
                 "contract_validated",
-                Some("error=Contract failed to parse"),
+                Some(&format!("error={}", error)),
                 Some(name),
                 None,
                 None,
@@ -140,10 +249,51 @@ pub fn validate_contract<L: AuditLogger>(logger: &L, name: &str) -> (ContractVal
             (
                 ContractValidation {
                     valid: false,
-                    error: Some("Contract failed to parse".to_string()),
+                    error: Some(error),
+                    warnings: Vec::new(),
+                    parse_error: Some(e),
                 },
                 message,
             )
         }
     }
 }
+
+/// Lint a contract for semantic problems beyond syntax.
+/// Returns every finding (with code and suggestion) and a log message,
+/// or an error if the contract file can't be read.
+pub fn lint_contract<L: AuditLogger>(
+    logger: &L,
+    name: &str,
+) -> Result<(ContractLint, String), String> {
+    let contract_path = contract_path(name).display().to_string();
+    let toml_str =
+        std::fs::read_to_string(&contract_path).map_err(|_| "Contract not found".to_string())?;
+
+    let diagnostics = lint_toml(&toml_str);
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == LintSeverity::Error)
+        .count();
+    let message = log_action(
+        logger,
+        "contract_linted",
+        Some(&format!(
+            "errors={}, warnings={}",
+            errors,
+            diagnostics.len() - errors
+        )),
+        Some(name),
+        None,
+        None,
+    );
+    Ok((ContractLint { diagnostics }, message))
+}
+
+/// Why the contract's `[schedule] cron` is invalid, if it is.
+fn schedule_error(contract: &SchemaContracts) -> Option<String> {
+    let schedule = contract.schedule.as_ref()?;
+    CronSchedule::parse(&schedule.cron)
+        .err()
+        .map(|e| format!("schedule: {}", e))
+}
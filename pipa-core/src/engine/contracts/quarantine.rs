@@ -0,0 +1,286 @@
+//! Re-validating quarantined data.
+//!
+//! `revalidate_quarantined` runs a contract against a file in its
+//! `[quarantine]` location instead of its `[source]`, typically after the
+//! contract or an upstream fix makes the data acceptable. If every rule
+//! passes, the data is written to the `[destination]` as in a normal run
+//! (named without its `_<timestamp>_quarantine` suffix) and the quarantined
+//! copy is deleted. A file that fails again is left where it is: the re-run
+//! has no quarantine of its own, so it is never quarantined twice.
+
+use crate::config::ProjectConfig;
+use crate::contracts::schema::{Quarantine, Source};
+use crate::engine::contracts::runner::{ValidationOutcome, run_contract_validation_with};
+use crate::engine::log_action;
+use crate::engine::plan::ExecutionPlan;
+use crate::environment::{ConfigStore, Environment};
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::logging::schema::Executor;
+use crate::movement::FileMovement;
+use crate::profiles::Profiles;
+use std::sync::Arc;
+
+/// Outcome of re-validating one quarantined file.
+pub struct Revalidation {
+    /// The quarantined file that was re-run.
+    pub location: String,
+    pub outcome: ValidationOutcome,
+    /// Whether the file was written to the destination and removed from
+    /// quarantine.
+    pub released: bool,
+}
+
+/// Re-run `contract_name` against `file` in its quarantine and, if it
+/// passes, release it to the destination.
+///
+/// `file` is either a bare file name in the quarantine location or a full
+/// location. In a dry run (`Environment::dry_run`) nothing is moved or
+/// deleted. The contract needs both a `[quarantine]` and a `[destination]`
+/// that aren't `not_moved`.
+pub async fn revalidate_quarantined<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    file: &str,
+    executor: &Executor,
+    log_to_console: bool,
+) -> ValidationResult<(Revalidation, String)> {
+    let (plan, _) = ExecutionPlan::cached(&env.config.contract_toml(contract_name)?)?;
+    let contracts = &plan.contracts;
+    let quarantine = contracts
+        .quarantine
+        .as_ref()
+        .filter(|q| q.r#type != "not_moved")
+        .ok_or_else(|| {
+            ValidationError::Config(format!(
+                "Contract '{}' has no quarantine to revalidate from",
+                contract_name
+            ))
+        })?;
+    if contracts
+        .destination
+        .as_ref()
+        .is_none_or(|d| d.r#type == "not_moved")
+    {
+        return Err(ValidationError::Config(format!(
+            "Contract '{}' has no destination to release quarantined data to",
+            contract_name
+        )));
+    }
+    let base = quarantine
+        .location
+        .as_deref()
+        .ok_or_else(|| ValidationError::Config("Quarantine missing location".to_string()))?;
+    let location = if file.contains('/') {
+        file.to_string()
+    } else {
+        FileMovement::build_destination_path(base, file)
+    };
+
+    let run_env = Environment {
+        config: Arc::new(QuarantineSource {
+            inner: Arc::clone(&env.config),
+            contract: contract_name.to_string(),
+            source: quarantine_source(quarantine, &location),
+        }),
+        ..env.clone()
+    };
+    let (outcome, _) =
+        run_contract_validation_with(&run_env, logger, contract_name, executor, log_to_console)
+            .await?;
+
+    // A failed destination write records no upload; keep the file then.
+    let released = outcome.passed && !env.dry_run && outcome.transfer.total_uploaded() > 0;
+    if released {
+        let source = Source {
+            r#type: quarantine.r#type.clone(),
+            location: Some(location.clone()),
+            profile: quarantine.profile.clone(),
+            format: quarantine.format.clone(),
+            options: Default::default(),
+            csv: Default::default(),
+            on_success: Default::default(),
+            archive_location: None,
+            on_duplicate: Default::default(),
+            archive_member: None,
+            archive_mode: Default::default(),
+        };
+        env.storage
+            .delete(&source, &env.config.profiles()?)
+            .await
+            .map_err(|e| ValidationError::MovementFailed {
+                target: location.clone(),
+                cause: e.to_string(),
+            })?;
+    }
+
+    let message = log_action(
+        logger,
+        "quarantine_revalidated",
+        Some(&format!("passed={}, released={}", outcome.passed, released)),
+        Some(&contracts.contract.name),
+        Some(&contracts.contract.version),
+        Some(&location),
+    );
+    Ok((
+        Revalidation {
+            location,
+            outcome,
+            released,
+        },
+        message,
+    ))
+}
+
+/// `[source]` table reading `location` with the quarantine's backend.
+fn quarantine_source(quarantine: &Quarantine, location: &str) -> toml::Table {
+    let mut source = toml::Table::new();
+    source.insert("type".to_string(), quarantine.r#type.as_str().into());
+    source.insert("location".to_string(), location.into());
+    if let Some(profile) = &quarantine.profile {
+        source.insert("profile".to_string(), profile.as_str().into());
+    }
+    if let Some(format) = &quarantine.format {
+        source.insert("format".to_string(), format.as_str().into());
+    }
+    source
+}
+
+/// Serves a contract reading from its quarantine: `[source]` is replaced
+/// and `[quarantine]`, `[[sources]]`, and `[combine]` are dropped.
+struct QuarantineSource {
+    inner: Arc<dyn ConfigStore>,
+    contract: String,
+    source: toml::Table,
+}
+
+impl ConfigStore for QuarantineSource {
+    fn contract_toml(&self, name: &str) -> ValidationResult<String> {
+        let toml_str = self.inner.contract_toml(name)?;
+        if name != self.contract {
+            return Ok(toml_str);
+        }
+
+        let mut contract: toml::Table = toml::from_str(&toml_str).map_err(|e| {
+            ValidationError::ContractParse(format!("Failed to parse contract TOML: {}", e))
+        })?;
+        for key in ["quarantine", "sources", "combine"] {
+            contract.remove(key);
+        }
+        contract.insert(
+            "source".to_string(),
+            toml::Value::Table(self.source.clone()),
+        );
+        toml::to_string(&contract).map_err(|e| {
+            ValidationError::ContractParse(format!("Failed to serialize contract TOML: {}", e))
+        })
+    }
+
+    fn profiles(&self) -> ValidationResult<Profiles> {
+        self.inner.profiles()
+    }
+
+    fn project(&self) -> ValidationResult<ProjectConfig> {
+        self.inner.project()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::InMemory;
+    use crate::logging::MemoryLogger;
+
+    const CONTRACT: &str = r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "local"
+location = "landing/orders.csv"
+
+[destination]
+type = "local"
+location = "clean/"
+
+[quarantine]
+type = "local"
+location = "quarantine/"
+"#;
+
+    const QUARANTINED: &str = "quarantine/orders_20250101_120000_quarantine.csv";
+
+    fn executor() -> Executor {
+        Executor {
+            user: "test".to_string(),
+            host: "test".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn releases_passing_data_to_the_destination() {
+        let memory = InMemory::new();
+        memory.add_contract("orders", CONTRACT);
+        memory.put_object(QUARANTINED, "id\n1\n");
+        let logger = MemoryLogger::new();
+
+        let (revalidation, _) = revalidate_quarantined(
+            &memory.environment(),
+            &logger,
+            "orders",
+            "orders_20250101_120000_quarantine.csv",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(revalidation.outcome.passed);
+        assert!(revalidation.released);
+        assert_eq!(revalidation.location, QUARANTINED);
+        assert!(memory.object(QUARANTINED).is_none());
+        let released: Vec<String> = memory
+            .locations()
+            .into_iter()
+            .filter(|location| location.starts_with("clean/"))
+            .collect();
+        assert_eq!(released.len(), 1);
+        assert!(!released[0].contains("quarantine"));
+        assert!(
+            logger
+                .events()
+                .iter()
+                .any(|e| e == "quarantine_revalidated")
+        );
+    }
+
+    #[tokio::test]
+    async fn leaves_failing_data_in_quarantine() {
+        let memory = InMemory::new();
+        memory.add_contract("orders", CONTRACT);
+        memory.put_object(QUARANTINED, "id\n\n");
+        let logger = MemoryLogger::new();
+
+        let (revalidation, _) = revalidate_quarantined(
+            &memory.environment(),
+            &logger,
+            "orders",
+            QUARANTINED,
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(!revalidation.outcome.passed);
+        assert!(!revalidation.released);
+        assert_eq!(memory.locations(), vec![QUARANTINED.to_string()]);
+    }
+}
@@ -2,24 +2,128 @@
 //! - If source profile is invalid → abort validation.
 //! - If destination/quarantine profile is invalid → skip movement and log error.
 //! This prevents wasted work and clearer operator feedback.
-use crate::connectors::fetch::fetch_data_from_source; // fetch raw bytes from source connector
-use crate::contracts::load_contract_for_file; // load TOML contract into SchemaContracts
+//!
+//! A run fetches the contract's source (a single object, a glob, an
+//! archive, or `[[sources]]`), validates it against the cached
+//! `ExecutionPlan`, moves the data to the destination or quarantine, and
+//! applies each input's `on_success`. Every audit entry carries the run id,
+//! and the start and completion entries carry `contract_hash=<sha256>`.
+//! Transient failures are retried per `[retry]`, `[limits]` bounds fetches
+//! and the whole run, and a finished run notifies `[notifications]`.
+use crate::connectors::archive::{ArchiveKind, extract_members}; // zip/tar sources
+use crate::connectors::fetch::source_extension; // driver extension for a source
+use crate::connectors::spool::Payload;
+use crate::contracts::filename::uuid_v4; // run ids
+use crate::contracts::retry::{DEFAULT_RETRY_DELAY, format_delay};
+use crate::contracts::schema::{ArchiveMode, OnDuplicate, OnSuccess, Source};
+use crate::contracts::{Combine, NamedSource, Retry, SchemaContracts};
+use crate::drivers::csv::declared_encoding; // CSV text encoding
+use crate::engine::contracts::timings::RunTimings; // per-run stage durations
+use crate::engine::contracts::transfer::TransferStats; // per-connector byte accounting
 use crate::engine::log_action; // audit logging
-use crate::engine::validation::execute_validation; // run validators against data
+use crate::engine::plan::ExecutionPlan; // parsed contract + compiled rules, cached per hash
+use crate::engine::rules::{type_mismatches, unrecognized_rules}; // load-time rule checks
+use crate::engine::sources::{combine_frames, declared_sources}; // `[[sources]]` inputs
+use crate::engine::validation::{
+    execute_validation, execute_validation_frame, memory_limit_exceeded,
+}; // run validators
+use crate::environment::Environment; // contract/profile, storage, and history backends
+use crate::history::{Delivery, ReadOnlyHistory, RunHistory}; // dry runs leave history untouched
 use crate::logging::error::{ValidationError, ValidationResult};
 use crate::logging::schema::{Executor, RuleResult};
-use crate::logging::{AuditLogEntry, AuditLogger};
-use crate::movement::FileMovement; // handles writing success/quarantine data
-use crate::profiles::{Profiles, load_profiles}; // profile management
+use crate::logging::{AuditLogEntry, AuditLogger, DryRunLogger, RunLogger};
+use crate::movement::{FileMovement, Provenance}; // handles writing success/quarantine data
+use crate::notify::notify_run; // `[notifications]` channels told about each run
+use crate::profiles::Profiles;
 use chrono::Utc;
-use std::path::Path as StdPath;
+use glob::{MatchOptions, Pattern};
+use polars::prelude::DataFrame;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::runtime::RuntimeFlavor;
+use tokio::task::block_in_place;
 
-/// Outcome of running a contract validation
+/// Outcome of running a contract validation.
+///
+/// Serializes with these field names, which `pipa run --output json` and
+/// `--emit-results-file` document as stable.
+#[derive(Serialize)]
 pub struct ValidationOutcome {
-    pub passed: bool,             // true if no rules failed
-    pub pass_count: usize,        // number of passing rules
-    pub fail_count: usize,        // number of failing rules
-    pub results: Vec<RuleResult>, // detailed results per rule
+    pub passed: bool,                // true if no rules failed
+    pub pass_count: usize,           // number of passing rules
+    pub fail_count: usize,           // number of failing rules
+    pub results: Vec<RuleResult>,    // detailed results per rule
+    pub transfer: TransferStats,     // bytes downloaded/uploaded per connector
+    pub objects: Vec<ObjectOutcome>, // per-object outcomes for glob sources
+    pub quarantined: bool,           // true if failing data was written to quarantine
+    pub run_id: String,              // tags every audit entry of the run
+    pub timings: RunTimings,         // time spent fetching, parsing, validating
+}
+
+/// Outcome for one object matched by a glob source location.
+#[derive(Serialize)]
+pub struct ObjectOutcome {
+    pub location: String,
+    pub outcome: ValidationOutcome,
+}
+
+/// A glob source location: the pattern and the literal prefix to list.
+pub(crate) struct SourceGlob {
+    pattern: Pattern,
+    prefix: String,
+}
+
+impl SourceGlob {
+    /// Parse `location` as a glob, or `None` if it has no wildcards.
+    pub(crate) fn parse(location: &str) -> ValidationResult<Option<Self>> {
+        let Some(wildcard) = location.find(['*', '?', '[']) else {
+            return Ok(None);
+        };
+        let pattern = Pattern::new(location).map_err(|e| {
+            ValidationError::Config(format!("Invalid source glob '{}': {}", location, e))
+        })?;
+        Ok(Some(Self {
+            pattern,
+            prefix: location[..wildcard].to_string(),
+        }))
+    }
+
+    /// Literal prefix to list objects under.
+    pub(crate) fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Whether an object location matches the pattern. Wildcards don't
+    /// match a leading `.`, so hidden files (and files still being written
+    /// under their temporary name, see `movement`) are left out.
+    pub(crate) fn matches(&self, location: &str) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            require_literal_leading_dot: true,
+            ..MatchOptions::default()
+        };
+        self.pattern.matches_with(location, options)
+    }
+}
+
+/// Everything a run needs besides the source being read.
+#[derive(Clone, Copy)]
+struct Run<'a, L> {
+    env: &'a Environment,
+    logger: &'a L,
+    contract_name: &'a str,
+    plan: &'a ExecutionPlan,
+    plan_cached: bool,
+    profiles: &'a Profiles,
+    executor: &'a Executor,
+    log_to_console: bool,
+    /// Set on every audit entry and in `annotate`d output (`_pipa_run_id`).
+    run_id: &'a str,
+    /// Set once any of the run's data has been written.
+    moved: &'a AtomicBool,
 }
 
 /// Run a contract validation end-to-end:
@@ -29,6 +133,9 @@ pub struct ValidationOutcome {
 /// - Move data to destination or quarantine (only if profiles are valid)
 /// - Log all actions
 ///
+/// Uses the default `Environment`: contracts and profiles from the working
+/// directory, the storage connectors, and the `.pipa/state` run history.
+///
 /// # Arguments
 /// * `logger` - The audit logger implementation to use
 /// * `contract_name` - Name of the contract to validate
@@ -40,34 +147,755 @@ pub async fn run_contract_validation<L: AuditLogger>(
     executor: &Executor,
     log_to_console: bool,
 ) -> ValidationResult<(ValidationOutcome, String)> {
-    // --- Contract existence check ---
-    let contract_path = format!("contracts/{}.toml", contract_name);
-    if !StdPath::new(&contract_path).exists() {
-        return Err(ValidationError::Other(format!(
-            "Contract '{}' not found",
-            contract_name
-        )));
+    run_contract_validation_with(
+        &Environment::default(),
+        logger,
+        contract_name,
+        executor,
+        log_to_console,
+    )
+    .await
+}
+
+/// Like `run_contract_validation`, with contracts, profiles, data, and run
+/// history served by `env` (e.g. `InMemory` for hermetic tests).
+///
+/// Each call is a run with a fresh UUID `run_id`, set on every audit entry
+/// it logs and returned in the `ValidationOutcome`. Every run that isn't
+/// cancelled, including one that errors, ends by notifying the contract's
+/// (or the project's) `[notifications]` channels (see `notify`).
+///
+/// In a dry run (`Environment::dry_run`) nothing is written: movement is
+/// logged as skipped, run history is read but not recorded, no
+/// notifications are sent, and every audit entry is tagged `dry_run=true`.
+pub async fn run_contract_validation_with<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    executor: &Executor,
+    log_to_console: bool,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let run_id = uuid_v4();
+    let logger = RunLogger::new(logger, &run_id);
+    if env.dry_run {
+        return run_bounded(
+            env,
+            &DryRunLogger::new(&logger),
+            contract_name,
+            executor,
+            log_to_console,
+            &run_id,
+        )
+        .await;
     }
+    let result = run_bounded(
+        env,
+        &logger,
+        contract_name,
+        executor,
+        log_to_console,
+        &run_id,
+    )
+    .await;
+    if !matches!(result, Err(ValidationError::Cancelled(_))) {
+        notify_run(env, &logger, contract_name, &result, log_to_console).await;
+    }
+    result
+}
 
-    // --- Load contract + profiles ---
-    let contracts = load_contract_for_file(StdPath::new(&contract_path));
-    let profiles: Profiles = load_profiles()?;
+/// Run the contract's attempts, cancelling them once its run timeout is
+/// up, and log how a cancelled run stopped.
+///
+/// Cancelling `Environment::cancel` stops the run at its next safe point:
+/// before it starts, during a fetch or retry wait, or once validation is
+/// done but before any data is moved. Movement already under way (e.g. an
+/// earlier object of a glob source) completes, so nothing is left half
+/// written. A cancelled run logs a terminal `run_cancelled` entry, sends no
+/// notifications, and returns `ValidationError::Cancelled`.
+async fn run_bounded<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    executor: &Executor,
+    log_to_console: bool,
+    run_id: &str,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let result = run_timed(env, logger, contract_name, executor, log_to_console, run_id).await;
+    if let Err(error @ ValidationError::Cancelled(_)) = &result {
+        let message = log_action(
+            logger,
+            "run_cancelled",
+            Some(&error.to_string()),
+            Some(contract_name),
+            None,
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+    }
+    result
+}
 
-    // --- Validate source config ---
-    let source = contracts
-        .source
-        .as_ref()
-        .ok_or_else(|| ValidationError::Other("Contract missing source".to_string()))?;
+/// Run the contract's attempts within its run timeout.
+///
+/// The whole run, retries included, is bounded by `[limits]
+/// run_timeout_secs` (or `Environment::run_timeout`). The timeout cancels a
+/// child of `env.cancel`, so a run that runs out stops at the same safe
+/// points as a cancelled one and never mid-movement; then `run_timeout` is
+/// logged and a `ValidationError::RunTimeout` returned. Parsing and
+/// validation run on a blocking thread, so the deadline passes while they
+/// do.
+async fn run_timed<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    executor: &Executor,
+    log_to_console: bool,
+    run_id: &str,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let declared = cached_plan(env, contract_name)
+        .and_then(|plan| plan.contracts.limits.as_ref()?.run_timeout_secs)
+        .map(Duration::from_secs);
+    let Some(timeout) = env.run_timeout.or(declared) else {
+        return run_attempts(env, logger, contract_name, executor, log_to_console, run_id).await;
+    };
+
+    let cancel = env.cancel.child_token();
+    let timed = Environment {
+        cancel: cancel.clone(),
+        ..env.clone()
+    };
+    // Spawned so the deadline passes even while parsing blocks this task.
+    let deadline = tokio::spawn({
+        let cancel = cancel.clone();
+        async move {
+            tokio::time::sleep(timeout).await;
+            cancel.cancel();
+        }
+    });
+    let result = run_attempts(
+        &timed,
+        logger,
+        contract_name,
+        executor,
+        log_to_console,
+        run_id,
+    )
+    .await;
+    deadline.abort();
+
+    match result {
+        Err(ValidationError::Cancelled(_)) if !env.cancel.is_cancelled() => {
+            let message = log_action(
+                logger,
+                "run_timeout",
+                Some(&format!("timeout={}", format_delay(timeout))),
+                Some(contract_name),
+                None,
+                None,
+            );
+            if log_to_console {
+                println!("{}", message);
+            }
+            Err(ValidationError::RunTimeout(timeout))
+        }
+        result => result,
+    }
+}
+
+/// Run CPU-bound parsing and validation on a blocking thread, so the
+/// runtime's timers (the run timeout among them) and other runs keep going
+/// meanwhile. On a current-thread runtime, which has no blocking threads to
+/// hand over to, `f` runs in place.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => block_in_place(f),
+        _ => f(),
+    }
+}
+
+/// Run the contract, attempting it again after a transient error for as
+/// long as its retry policy allows and none of its data has been written.
+///
+/// A transient error (`ValidationError::is_transient`, e.g. a connector
+/// timeout) is attempted again per the contract's `[retry]` (or
+/// `Environment::retries` / `retry_delay`), logging `run_retried` before
+/// each new attempt; all attempts share the run id. Once any data has been
+/// written (e.g. an earlier object of a glob source) a failed run is not
+/// attempted again, so nothing is written twice.
+async fn run_attempts<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    executor: &Executor,
+    log_to_console: bool,
+    run_id: &str,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let mut attempt = 1;
+    let moved = AtomicBool::new(false);
+    loop {
+        let result = run_contract(
+            env,
+            logger,
+            contract_name,
+            executor,
+            log_to_console,
+            run_id,
+            &moved,
+        )
+        .await;
+        let error = match &result {
+            Err(error) if error.is_transient() && !moved.load(Ordering::Relaxed) => error,
+            _ => return result,
+        };
+        let Some(policy) = retry_policy(env, contract_name).filter(|p| attempt <= p.retries) else {
+            return result;
+        };
+
+        attempt += 1;
+        let message = log_action(
+            logger,
+            "run_retried",
+            Some(&format!(
+                "attempt={}/{}, delay={}, error={}",
+                attempt,
+                policy.retries + 1,
+                format_delay(policy.delay),
+                error
+            )),
+            Some(contract_name),
+            None,
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+        tokio::select! {
+            () = env.cancel.cancelled() => {
+                return Err(ValidationError::Cancelled("during retry delay"));
+            }
+            () = tokio::time::sleep(policy.delay) => {}
+        }
+    }
+}
+
+/// The contract's `[retry]` with the environment's overrides applied;
+/// `None` when neither asks for retries.
+fn retry_policy(env: &Environment, contract_name: &str) -> Option<Retry> {
+    let declared = cached_plan(env, contract_name).and_then(|plan| plan.contracts.retry);
+    Some(Retry {
+        retries: env.retries.or(declared.map(|retry| retry.retries))?,
+        delay: env
+            .retry_delay
+            .or(declared.map(|retry| retry.delay))
+            .unwrap_or(DEFAULT_RETRY_DELAY),
+    })
+}
+
+/// The contract's plan, for reading run settings ahead of the run; `None`
+/// when it can't be loaded (the run itself reports why).
+fn cached_plan(env: &Environment, contract_name: &str) -> Option<Arc<ExecutionPlan>> {
+    env.config
+        .contract_toml(contract_name)
+        .and_then(|toml| ExecutionPlan::cached(&toml))
+        .ok()
+        .map(|(plan, _)| plan)
+}
+
+/// How long a single fetch may take: `Environment::fetch_timeout` or the
+/// contract's `[limits] fetch_timeout_secs`.
+fn fetch_timeout(env: &Environment, contracts: &SchemaContracts) -> Option<Duration> {
+    env.fetch_timeout.or_else(|| {
+        contracts
+            .limits
+            .as_ref()?
+            .fetch_timeout_secs
+            .map(Duration::from_secs)
+    })
+}
+
+/// Await `fetch` of `source`, failing with a `SourceFetch` error once the
+/// fetch timeout is up and abandoning it if the run is cancelled.
+///
+/// Running out of time is a transient error, so the run is retried like
+/// after any other failed fetch.
+async fn bounded_fetch<T>(
+    env: &Environment,
+    contracts: &SchemaContracts,
+    source: &Source,
+    fetch: impl Future<Output = ValidationResult<T>>,
+) -> ValidationResult<T> {
+    let timed = async {
+        let Some(timeout) = fetch_timeout(env, contracts) else {
+            return fetch.await;
+        };
+        tokio::time::timeout(timeout, fetch)
+            .await
+            .unwrap_or_else(|_| {
+                Err(ValidationError::source_fetch(
+                    &source.r#type,
+                    format!("timed out after {}", format_delay(timeout)),
+                ))
+            })
+    };
+    tokio::select! {
+        () = env.cancel.cancelled() => Err(ValidationError::Cancelled("during fetch")),
+        result = timed => result,
+    }
+}
+
+/// Fail with `ValidationError::Cancelled` if the run has been cancelled.
+fn ensure_not_cancelled(env: &Environment, stage: &'static str) -> ValidationResult<()> {
+    if env.cancel.is_cancelled() {
+        return Err(ValidationError::Cancelled(stage));
+    }
+    Ok(())
+}
+
+async fn run_contract<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    executor: &Executor,
+    log_to_console: bool,
+    run_id: &str,
+    moved: &AtomicBool,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    ensure_not_cancelled(env, "before start")?;
+
+    // --- Load contract (cached plan per contract hash) + profiles ---
+    let (plan, plan_cached) = ExecutionPlan::cached(&env.config.contract_toml(contract_name)?)?;
+    let contracts = &plan.contracts;
+    let profiles = env.config.profiles()?;
+
+    // --- Strict rules: refuse before fetching any data ---
+    if contracts.contract.strict_rules {
+        let unrecognized = unrecognized_rules(contracts);
+        if !unrecognized.is_empty() {
+            let reasons: Vec<String> = unrecognized.iter().map(|r| r.to_string()).collect();
+            return Err(ValidationError::Config(format!(
+                "strict_rules: {}",
+                reasons.join("; ")
+            )));
+        }
+    }
+
+    // --- Rule/dtype mismatches: warn before fetching any data ---
+    for mismatch in type_mismatches(contracts) {
+        let message = log_action(
+            logger,
+            "rule_type_mismatch",
+            Some(&mismatch.to_string()),
+            Some(&contracts.contract.name),
+            Some(&contracts.contract.version),
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+    }
+
+    let run = Run {
+        env,
+        logger,
+        contract_name,
+        plan: &plan,
+        plan_cached,
+        profiles: &profiles,
+        executor,
+        log_to_console,
+        run_id,
+        moved,
+    };
+
+    // --- Several sources: fetch each, combine, and validate the result ---
+    if let Some(sources) = declared_sources(contracts)? {
+        let sources = sources
+            .iter()
+            .map(|named| {
+                Ok(NamedSource {
+                    name: named.name.clone(),
+                    source: env.dated_source(&named.source)?,
+                })
+            })
+            .collect::<ValidationResult<Vec<_>>>()?;
+        return run_sources(&run, &sources).await;
+    }
+
+    // --- Validate source config; render run-date tokens in its location ---
+    let source = &env.dated_source(
+        contracts
+            .source
+            .as_ref()
+            .ok_or_else(|| ValidationError::Config("Contract missing source".to_string()))?,
+    )?;
     let location = source
         .location
         .as_ref()
-        .ok_or_else(|| ValidationError::Other("Source missing location".to_string()))?;
+        .ok_or_else(|| ValidationError::Config("Source missing location".to_string()))?;
+
+    match SourceGlob::parse(location)? {
+        Some(glob) => run_glob(&run, source, &glob).await,
+        None => run_object(&run, source).await,
+    }
+}
+
+/// Run every object matching a glob source location and combine the outcomes.
+///
+/// A location containing `*`, `?`, or `[...]` (e.g.
+/// `s3://bucket/incoming/*.csv`) is a glob: the objects under its literal
+/// prefix are listed, and the contract runs against each match in turn.
+/// Wildcards don't cross `/`, and local globs only match files in the
+/// prefix's directory. Per-object outcomes are kept in
+/// `ValidationOutcome::objects`.
+async fn run_glob<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+    glob: &SourceGlob,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let started = Instant::now();
+    let contracts = &run.plan.contracts;
+    let pattern = source.location.as_deref().unwrap_or_default();
+    let objects: Vec<String> = bounded_fetch(
+        run.env,
+        contracts,
+        source,
+        run.env.storage.list(source, glob.prefix(), run.profiles),
+    )
+    .await?
+    .into_iter()
+    .map(|object| object.location)
+    .filter(|location| glob.matches(location))
+    .collect();
+    if objects.is_empty() {
+        return Err(ValidationError::Config(format!(
+            "No objects match source location '{}'",
+            pattern
+        )));
+    }
+
+    let message = log_action(
+        run.logger,
+        "source_expanded",
+        Some(&format!("objects={}", objects.len())),
+        Some(&contracts.contract.name),
+        Some(&contracts.contract.version),
+        Some(pattern),
+    );
+    if run.log_to_console {
+        println!("{}", message);
+    }
+
+    let mut outcomes = Vec::with_capacity(objects.len());
+    for location in objects {
+        let object_source = Source {
+            location: Some(location.clone()),
+            ..source.clone()
+        };
+        let (outcome, _) = run_object(run, &object_source).await?;
+        outcomes.push(ObjectOutcome { location, outcome });
+    }
+    Ok(complete_objects(
+        run,
+        outcomes,
+        TransferStats::default(),
+        RunTimings::default(),
+        started,
+        pattern,
+    ))
+}
+
+/// Combine the outcomes of a glob source's objects (or an archive's
+/// members) on top of the run's own `transfer` and `timings`, and log
+/// completion against `location`.
+fn complete_objects<L: AuditLogger>(
+    run: &Run<'_, L>,
+    objects: Vec<ObjectOutcome>,
+    transfer: TransferStats,
+    timings: RunTimings,
+    started: Instant,
+    location: &str,
+) -> (ValidationOutcome, String) {
+    let contracts = &run.plan.contracts;
+    let mut combined = ValidationOutcome {
+        passed: true,
+        pass_count: 0,
+        fail_count: 0,
+        results: Vec::new(),
+        transfer,
+        objects: Vec::new(),
+        quarantined: false,
+        run_id: run.run_id.to_string(),
+        timings,
+    };
+    for object in objects {
+        let outcome = &object.outcome;
+        combined.passed &= outcome.passed;
+        combined.pass_count += outcome.pass_count;
+        combined.fail_count += outcome.fail_count;
+        combined.results.extend(outcome.results.iter().cloned());
+        combined.transfer.merge(&outcome.transfer);
+        combined.quarantined |= outcome.quarantined;
+        combined.timings.merge(&outcome.timings);
+        combined.objects.push(object);
+    }
+    combined.timings.total = started.elapsed();
+
+    let failed = combined
+        .objects
+        .iter()
+        .filter(|o| !o.outcome.passed)
+        .count();
+    let details = format!(
+        "objects={}, objects_failed={}, pass={}, fail={}, {}, {}, contract_hash={}",
+        combined.objects.len(),
+        failed,
+        combined.pass_count,
+        combined.fail_count,
+        combined.transfer.to_details(),
+        combined.timings.to_details(),
+        run.plan.hash
+    );
+    let message = log_action(
+        run.logger,
+        "contract_validation_completed",
+        Some(&details),
+        Some(&contracts.contract.name),
+        Some(&contracts.contract.version),
+        Some(location),
+    );
+    if run.log_to_console {
+        println!("{}", message);
+    }
+    (combined, message)
+}
+
+/// Fetch every `[[sources]]` input, combine them (see `engine::sources`),
+/// then validate and move the combined data as one input.
+///
+/// The first source's profile is checked before movement and its location
+/// names the written object.
+async fn run_sources<L: AuditLogger>(
+    run: &Run<'_, L>,
+    sources: &[NamedSource],
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let Run {
+        env,
+        logger,
+        contract_name,
+        plan,
+        plan_cached,
+        profiles,
+        executor,
+        log_to_console,
+        ..
+    } = *run;
+    let contracts = &plan.contracts;
+    let started = Instant::now();
 
-    // --- Start log ---
     let start_message = log_action(
         logger,
         "contract_validation_started",
+        Some(&format!(
+            "plan_cached={}, contract_hash={}, sources={}",
+            plan_cached,
+            plan.hash,
+            sources.len()
+        )),
+        Some(contract_name),
+        None,
         None,
+    );
+    if log_to_console {
+        println!("{}", start_message);
+    }
+
+    // --- Fetch each source; parse it unless the inputs are over max_bytes or max_memory_bytes ---
+    let mut transfer = TransferStats::default();
+    let mut timings = RunTimings::default();
+    let mut payloads = Vec::with_capacity(sources.len());
+    let mut hashes = Vec::with_capacity(sources.len());
+    for named in sources {
+        let source = &named.source;
+        let location = source.location.as_deref().ok_or_else(|| {
+            ValidationError::Config(format!("Source '{}' missing location", named.name))
+        })?;
+        if SourceGlob::parse(location)?.is_some() {
+            return Err(ValidationError::Config(format!(
+                "Source '{}': glob locations aren't supported in [[sources]]",
+                named.name
+            )));
+        }
+        let fetch_started = Instant::now();
+        let data = bounded_fetch(
+            env,
+            contracts,
+            source,
+            env.storage
+                .fetch_with_progress(source, profiles, &env.progress),
+        )
+        .await?
+        .payload;
+        let fetch = fetch_started.elapsed();
+        timings.fetch += fetch;
+        transfer.record_download(&source.r#type, data.len());
+        let sha256 = data.sha256()?;
+        let _ = log_action(
+            logger,
+            "file_read",
+            Some(&format!(
+                "source={}, bytes={}, duration_ms={}, sha256={}{}",
+                named.name,
+                data.len(),
+                fetch.as_millis(),
+                sha256,
+                encoding_details(source, &data)?
+            )),
+            None,
+            None,
+            Some(location),
+        );
+        payloads.push(data);
+        hashes.push(sha256);
+    }
+
+    // --- Inputs delivered before: skip or fail per `on_duplicate` ---
+    for (named, sha256) in sources.iter().zip(&hashes) {
+        if let Some(message) = check_duplicate(run, &named.source, sha256)? {
+            return Ok(skipped_outcome(run, message, transfer, timings, started));
+        }
+    }
+    let bytes = payloads.iter().map(|data| data.len()).sum();
+    let over_byte_limit = exceeds_max_bytes(contracts, bytes);
+    let mut over_memory_limit =
+        !over_byte_limit && memory_limit_exceeded("bytes", bytes, plan).is_some();
+
+    let parse_started = Instant::now();
+    let df = if over_byte_limit || over_memory_limit {
+        DataFrame::empty()
+    } else {
+        blocking(|| {
+            let mut frames = Vec::with_capacity(sources.len());
+            for (named, data) in sources.iter().zip(&payloads) {
+                let extension = source_extension(&named.source);
+                let driver = crate::drivers::contract_driver(
+                    extension,
+                    &named.source.options,
+                    &named.source.csv,
+                    contracts,
+                )
+                .map_err(|e| ValidationError::driver_load(extension, e))?;
+                let df = data
+                    .load(driver.as_ref())
+                    .map_err(|e| ValidationError::driver_load(extension, e))?;
+                frames.push((named.name.as_str(), df));
+            }
+            let combine = contracts.combine.clone().unwrap_or_default();
+            let df = combine_frames(frames, &combine)?;
+            let _ = log_action(
+                logger,
+                "sources_combined",
+                Some(&format!(
+                    "sources={}, mode={:?}, rows={}, cols={}",
+                    sources.len(),
+                    combine.mode,
+                    df.height(),
+                    df.width()
+                )),
+                Some(&contracts.contract.name),
+                Some(&contracts.contract.version),
+                None,
+            );
+            Ok::<_, ValidationError>(df)
+        })?
+    };
+
+    // --- Execute validations ---
+    let read_only = ReadOnlyHistory(env.history.as_ref());
+    let history: &dyn RunHistory = if env.dry_run {
+        &read_only
+    } else {
+        env.history.as_ref()
+    };
+    timings.parse += parse_started.elapsed();
+    let results = blocking(|| {
+        execute_validation_frame(
+            logger,
+            history,
+            &df,
+            bytes,
+            plan,
+            executor,
+            run.run_id,
+            &mut timings,
+            &*env.progress,
+        )
+    })?;
+    // A combined frame over max_memory_bytes had every rule aborted; don't keep it for movement.
+    over_memory_limit |= memory_limit_exceeded("frame_bytes", df.estimated_size(), plan).is_some();
+    let df = if over_memory_limit {
+        DataFrame::empty()
+    } else {
+        df
+    };
+
+    let inputs: Vec<&Source> = sources.iter().map(|named| &named.source).collect();
+    let completed = move_and_complete(
+        run,
+        &inputs,
+        Checked {
+            results,
+            df,
+            over_byte_limit,
+            over_memory_limit,
+            hashes,
+            transfer,
+            timings,
+            started,
+            output_location: None,
+        },
+    )
+    .await?;
+    Ok((completed.outcome, completed.message))
+}
+
+/// Run `source` as a zip or tar archive when its location names one, and
+/// as a single object otherwise.
+async fn run_object<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let location = source.location.as_deref().unwrap_or_default();
+    match ArchiveKind::of(location) {
+        Some(kind) if source.format.is_none() => run_archive(run, source, kind).await,
+        _ => run_source(run, source).await,
+    }
+}
+
+/// Fetch, validate, and move the data at `source`'s location.
+async fn run_source<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let Run {
+        logger,
+        contract_name,
+        plan,
+        plan_cached,
+        log_to_console,
+        ..
+    } = *run;
+    let started = Instant::now();
+
+    // --- Start log ---
+    let start_message = log_action(
+        logger,
+        "contract_validation_started",
+        Some(&format!(
+            "plan_cached={}, contract_hash={}",
+            plan_cached, plan.hash
+        )),
         Some(contract_name),
         None,
         None,
@@ -77,51 +905,730 @@ pub async fn run_contract_validation<L: AuditLogger>(
     }
 
     // --- Fetch data ---
-    let data = fetch_data_from_source(source, &profiles).await?;
+    let mut transfer = TransferStats::default();
+    let mut timings = RunTimings::default();
+    let (data, sha256) = fetch_input(run, source, &mut transfer, &mut timings).await?;
+
+    // --- Delivered before: skip or fail per `on_duplicate` ---
+    if let Some(message) = check_duplicate(run, source, &sha256)? {
+        return Ok(skipped_outcome(run, message, transfer, timings, started));
+    }
+
+    let completed = validate_and_move(
+        run,
+        source,
+        &data,
+        Checked::input(sha256, transfer, timings, started),
+    )
+    .await?;
+    Ok((completed.outcome, completed.message))
+}
+
+/// Fetch `source`, logging `file_read` (and any `download_resumed`), and
+/// return its data with the SHA-256 of its content.
+///
+/// `file_read` carries the `sha256=` and, for CSV, the `encoding=` the data
+/// is read in.
+async fn fetch_input<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+    transfer: &mut TransferStats,
+    timings: &mut RunTimings,
+) -> ValidationResult<(Payload, String)> {
+    let Run {
+        env,
+        logger,
+        plan,
+        profiles,
+        ..
+    } = *run;
+    let contracts = &plan.contracts;
+    let location = source
+        .location
+        .as_ref()
+        .ok_or_else(|| ValidationError::Config("Source missing location".to_string()))?;
+
+    let fetch_started = Instant::now();
+    let fetched = bounded_fetch(
+        env,
+        contracts,
+        source,
+        env.storage
+            .fetch_with_progress(source, profiles, &env.progress),
+    )
+    .await?;
+    let fetch = fetch_started.elapsed();
+    timings.fetch += fetch;
+    for resume in &fetched.resumes {
+        let _ = log_action(
+            logger,
+            "download_resumed",
+            Some(&format!(
+                "attempt={}, offset={}, cause={}",
+                resume.attempt, resume.offset, resume.cause
+            )),
+            Some(&contracts.contract.name),
+            Some(&contracts.contract.version),
+            Some(location),
+        );
+    }
+    let data = fetched.payload;
+    transfer.record_download(&source.r#type, data.len());
+    let sha256 = data.sha256()?;
     let _ = log_action(
         logger,
         "file_read",
-        Some(&format!("bytes={}", data.len())),
+        Some(&format!(
+            "bytes={}, duration_ms={}, sha256={}{}",
+            data.len(),
+            fetch.as_millis(),
+            sha256,
+            encoding_details(source, &data)?
+        )),
         None,
         None,
         Some(location),
     );
+    Ok((data, sha256))
+}
+
+/// Validate `data` read from `source` with the driver for its extension,
+/// then move it. `checked` carries the input's hash, transfer, and timings
+/// so far; its results and data are filled in here.
+async fn validate_and_move<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+    data: &Payload,
+    mut checked: Checked,
+) -> ValidationResult<Completed> {
+    let Run {
+        env,
+        logger,
+        plan,
+        executor,
+        ..
+    } = *run;
+    let contracts = &plan.contracts;
 
     // --- Determine file extension ---
-    let extension = source
-        .location
-        .as_ref()
-        .and_then(|loc| StdPath::new(loc).extension().and_then(|s| s.to_str()))
-        .unwrap_or("csv");
+    let extension = source_extension(source);
 
     // --- Execute validations ---
-    let results = execute_validation(&data, extension, &contracts, executor).await?;
-    let pass_count = results.iter().filter(|r| r.result == "pass").count();
-    let fail_count = results.iter().filter(|r| r.result == "fail").count();
-    let validation_passed = fail_count == 0;
+    let read_only = ReadOnlyHistory(env.history.as_ref());
+    let history: &dyn RunHistory = if env.dry_run {
+        &read_only
+    } else {
+        env.history.as_ref()
+    };
+    checked.results = blocking(|| {
+        execute_validation(
+            logger,
+            history,
+            data,
+            extension,
+            plan,
+            executor,
+            run.run_id,
+            &mut checked.timings,
+            &*env.progress,
+        )
+    })?;
+    // Inputs over `[limits] max_bytes` are never parsed, so they can't be moved either.
+    checked.over_byte_limit = exceeds_max_bytes(contracts, data.len());
+    // Nor are inputs over `[limits] max_memory_bytes` loaded whole, even when streamed.
+    checked.over_memory_limit =
+        !checked.over_byte_limit && memory_limit_exceeded("bytes", data.len(), plan).is_some();
 
     // --- Load DataFrame for movement ---
-    let original_location = source.location.as_deref().unwrap_or("unknown");
-    let driver = crate::drivers::get_driver(extension)?;
-    let df = driver.load(&data)?;
+    let driver =
+        crate::drivers::contract_driver(extension, &source.options, &source.csv, contracts)
+            .map_err(|e| ValidationError::driver_load(extension, e))?;
+    if !checked.over_byte_limit && !checked.over_memory_limit {
+        checked.df = blocking(|| data.load(driver.as_ref()))
+            .map_err(|e| ValidationError::driver_load(extension, e))?;
+    }
+    if memory_limit_exceeded("frame_bytes", checked.df.estimated_size(), plan).is_some() {
+        checked.over_memory_limit = true;
+        checked.df = DataFrame::empty();
+    }
+
+    move_and_complete(run, &[source], checked).await
+}
+
+/// Fetch a zip or tar archive source and validate the members matching its
+/// `archive_member` glob, each on its own or concatenated per its
+/// `archive_mode`.
+///
+/// The archive is fetched once and its members extracted in memory (see
+/// `connectors::archive`). With `archive_mode = "each"` every member runs
+/// like an object of a glob source, at `<archive>/<member>`; with
+/// `"concat"` their rows are stacked and run as one input named after the
+/// archive. Either way `on_duplicate` looks at the archive's content, and
+/// `on_success` applies to the archive once every member's data has
+/// reached the destination.
+async fn run_archive<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+    kind: ArchiveKind,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let Run {
+        env,
+        logger,
+        contract_name,
+        plan,
+        plan_cached,
+        log_to_console,
+        ..
+    } = *run;
+    let contracts = &plan.contracts;
+    let location = source.location.as_deref().unwrap_or_default();
+    let started = Instant::now();
+
+    let start_message = log_action(
+        logger,
+        "contract_validation_started",
+        Some(&format!(
+            "plan_cached={}, contract_hash={}, archive_mode={:?}",
+            plan_cached, plan.hash, source.archive_mode
+        )),
+        Some(contract_name),
+        None,
+        None,
+    );
+    if log_to_console {
+        println!("{}", start_message);
+    }
+
+    // --- Fetch the archive; skip or fail it per `on_duplicate` ---
+    let mut transfer = TransferStats::default();
+    let mut timings = RunTimings::default();
+    let (data, sha256) = fetch_input(run, source, &mut transfer, &mut timings).await?;
+    if let Some(message) = check_duplicate(run, source, &sha256)? {
+        return Ok(skipped_outcome(run, message, transfer, timings, started));
+    }
+
+    // --- Extract matching members in memory ---
+    let parse_started = Instant::now();
+    let members = extract_members(kind, &data, source.archive_member.as_deref())
+        .map_err(|e| ValidationError::driver_load(kind.name(), e))?;
+    timings.parse += parse_started.elapsed();
+    if members.is_empty() {
+        return Err(ValidationError::Config(format!(
+            "No members of '{}' match archive_member '{}'",
+            location,
+            source.archive_member.as_deref().unwrap_or("*")
+        )));
+    }
+    let message = log_action(
+        logger,
+        "archive_extracted",
+        Some(&format!(
+            "members={}, archive_mode={:?}",
+            members.len(),
+            source.archive_mode
+        )),
+        Some(&contracts.contract.name),
+        Some(&contracts.contract.version),
+        Some(location),
+    );
+    if log_to_console {
+        println!("{}", message);
+    }
+
+    // Members are read like sources of their own, named `<archive>/<member>`;
+    // settling and duplicate checks stay with the archive.
+    let mut inputs = Vec::with_capacity(members.len());
+    for member in members {
+        let member_source = Source {
+            location: Some(format!("{}/{}", location, member.name)),
+            on_success: OnSuccess::Keep,
+            on_duplicate: OnDuplicate::Allow,
+            archive_member: None,
+            ..source.clone()
+        };
+        let data = Payload::Memory(member.data);
+        let sha256 = data.sha256()?;
+        let _ = log_action(
+            logger,
+            "file_read",
+            Some(&format!(
+                "archive_member={}, bytes={}, sha256={}{}",
+                member.name,
+                data.len(),
+                sha256,
+                encoding_details(&member_source, &data)?
+            )),
+            None,
+            None,
+            member_source.location.as_deref(),
+        );
+        inputs.push((member_source, data, sha256));
+    }
+
+    if source.archive_mode == ArchiveMode::Concat {
+        return concat_members(
+            run, source, kind, &inputs, sha256, transfer, timings, started,
+        )
+        .await;
+    }
+
+    // --- Each member on its own; settle the archive once all were delivered ---
+    let mut objects = Vec::with_capacity(inputs.len());
+    let (mut moved, mut delivered) = (true, true);
+    for (member_source, data, member_sha256) in &inputs {
+        let completed = validate_and_move(
+            run,
+            member_source,
+            data,
+            Checked::input(
+                member_sha256.clone(),
+                TransferStats::default(),
+                RunTimings::default(),
+                Instant::now(),
+            ),
+        )
+        .await?;
+        moved &= completed.moved;
+        delivered &= completed.delivered;
+        objects.push(ObjectOutcome {
+            location: member_source.location.clone().unwrap_or_default(),
+            outcome: completed.outcome,
+        });
+    }
+    if moved {
+        settle_sources(run, &[source]).await;
+    }
+    if delivered {
+        env.history
+            .record_delivery(
+                &contracts.contract.name,
+                &Delivery::new(&sha256, location, run.run_id),
+            )
+            .map_err(|e| ValidationError::HistoryWrite(e.to_string()))?;
+    }
+    Ok(complete_objects(
+        run, objects, transfer, timings, started, location,
+    ))
+}
+
+/// Stack the rows of an archive's members (read with the driver for each
+/// member's extension), then validate and move them as one input named
+/// after the archive.
+#[allow(clippy::too_many_arguments)]
+async fn concat_members<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+    kind: ArchiveKind,
+    inputs: &[(Source, Payload, String)],
+    sha256: String,
+    transfer: TransferStats,
+    timings: RunTimings,
+    started: Instant,
+) -> ValidationResult<(ValidationOutcome, String)> {
+    let Run {
+        env,
+        logger,
+        plan,
+        executor,
+        ..
+    } = *run;
+    let contracts = &plan.contracts;
+    let mut checked = Checked::input(sha256, transfer, timings, started);
+
+    let bytes = inputs.iter().map(|(_, data, _)| data.len()).sum();
+    checked.over_byte_limit = exceeds_max_bytes(contracts, bytes);
+    checked.over_memory_limit =
+        !checked.over_byte_limit && memory_limit_exceeded("bytes", bytes, plan).is_some();
+
+    let parse_started = Instant::now();
+    let df = if checked.over_byte_limit || checked.over_memory_limit {
+        DataFrame::empty()
+    } else {
+        blocking(|| {
+            let mut frames = Vec::with_capacity(inputs.len());
+            for (member_source, data, _) in inputs {
+                let extension = source_extension(member_source);
+                let driver = crate::drivers::contract_driver(
+                    extension,
+                    &member_source.options,
+                    &member_source.csv,
+                    contracts,
+                )
+                .map_err(|e| ValidationError::driver_load(extension, e))?;
+                let df = data
+                    .load(driver.as_ref())
+                    .map_err(|e| ValidationError::driver_load(extension, e))?;
+                let name = member_source.location.as_deref().unwrap_or_default();
+                frames.push((name, df));
+            }
+            combine_frames(frames, &Combine::default())
+        })?
+    };
+    checked.timings.parse += parse_started.elapsed();
+
+    // --- Execute validations ---
+    let read_only = ReadOnlyHistory(env.history.as_ref());
+    let history: &dyn RunHistory = if env.dry_run {
+        &read_only
+    } else {
+        env.history.as_ref()
+    };
+    checked.results = blocking(|| {
+        execute_validation_frame(
+            logger,
+            history,
+            &df,
+            bytes,
+            plan,
+            executor,
+            run.run_id,
+            &mut checked.timings,
+            &*env.progress,
+        )
+    })?;
+    checked.over_memory_limit |=
+        memory_limit_exceeded("frame_bytes", df.estimated_size(), plan).is_some();
+    if !checked.over_memory_limit {
+        checked.df = df;
+    }
+
+    // Named after the archive, with the members' extension (batch.zip → batch.csv).
+    let location = source.location.as_deref().unwrap_or_default();
+    let extension = source_extension(&inputs[0].0);
+    checked.output_location = Some(format!("{}.{}", kind.strip(location), extension));
+
+    let completed = move_and_complete(run, &[source], checked).await?;
+    Ok((completed.outcome, completed.message))
+}
+
+/// `, encoding=<name>` for a CSV source's `file_read` entry: the encoding
+/// its data is read in, declared or detected. Empty for other formats.
+fn encoding_details(source: &Source, data: &Payload) -> ValidationResult<String> {
+    if source_extension(source) != "csv" {
+        return Ok(String::new());
+    }
+    let encoding = declared_encoding(&source.options)
+        .and_then(|declared| data.csv_encoding(declared))
+        .map_err(|e| ValidationError::driver_load("csv", e))?;
+    Ok(format!(", encoding={}", encoding.name()))
+}
+
+/// Apply `source.on_duplicate` when content `sha256` was delivered before.
+///
+/// Every fetched input is hashed, and inputs whose data passed are
+/// recorded as deliveries in the run history. With `skip` a redelivered
+/// input logs `duplicate_skipped` and the run ends as passed without
+/// validating or moving anything; with `fail` it logs `duplicate_rejected`.
+///
+/// Returns the logged `duplicate_skipped` message when the input is to be
+/// skipped, and a `DuplicateSource` error when it is to fail the run.
+fn check_duplicate<L: AuditLogger>(
+    run: &Run<'_, L>,
+    source: &Source,
+    sha256: &str,
+) -> ValidationResult<Option<String>> {
+    if source.on_duplicate == OnDuplicate::Allow {
+        return Ok(None);
+    }
+    let contracts = &run.plan.contracts;
+    let Some(previous) = run
+        .env
+        .history
+        .delivered(&contracts.contract.name, sha256)?
+    else {
+        return Ok(None);
+    };
+    let location = source.location.as_deref().unwrap_or("unknown");
+    let event = match source.on_duplicate {
+        OnDuplicate::Fail => "duplicate_rejected",
+        _ => "duplicate_skipped",
+    };
+    let message = log_action(
+        run.logger,
+        event,
+        Some(&format!(
+            "sha256={}, delivered_in={}, delivered_from={}",
+            sha256, previous.run_id, previous.location
+        )),
+        Some(&contracts.contract.name),
+        Some(&contracts.contract.version),
+        Some(location),
+    );
+    if run.log_to_console {
+        println!("{}", message);
+    }
+    if source.on_duplicate == OnDuplicate::Fail {
+        return Err(ValidationError::DuplicateSource {
+            location: location.to_string(),
+            run_id: previous.run_id,
+        });
+    }
+    Ok(Some(message))
+}
+
+/// Outcome of a run whose input was skipped as a duplicate: passed, with
+/// no results.
+fn skipped_outcome<L: AuditLogger>(
+    run: &Run<'_, L>,
+    message: String,
+    transfer: TransferStats,
+    mut timings: RunTimings,
+    started: Instant,
+) -> (ValidationOutcome, String) {
+    timings.total = started.elapsed();
+    let outcome = ValidationOutcome {
+        passed: true,
+        pass_count: 0,
+        fail_count: 0,
+        results: Vec::new(),
+        transfer,
+        objects: Vec::new(),
+        quarantined: false,
+        run_id: run.run_id.to_string(),
+        timings,
+    };
+    (outcome, message)
+}
+
+/// Results of validating one input, with the data to move.
+struct Checked {
+    results: Vec<RuleResult>,
+    df: DataFrame,
+    over_byte_limit: bool,
+    /// The data is over `[limits] max_memory_bytes`, so `df` is empty.
+    over_memory_limit: bool,
+    /// SHA-256 of each input's content, in input order.
+    hashes: Vec<String>,
+    transfer: TransferStats,
+    timings: RunTimings,
+    /// When the run of this input started (for `RunTimings::total`).
+    started: Instant,
+    /// Location the written object is named after, when not the first
+    /// input's (e.g. the members of an archive, concatenated).
+    output_location: Option<String>,
+}
+
+impl Checked {
+    /// A single input with content `sha256`, not yet validated.
+    fn input(
+        sha256: String,
+        transfer: TransferStats,
+        timings: RunTimings,
+        started: Instant,
+    ) -> Self {
+        Self {
+            results: Vec::new(),
+            df: DataFrame::empty(),
+            over_byte_limit: false,
+            over_memory_limit: false,
+            hashes: vec![sha256],
+            transfer,
+            timings,
+            started,
+            output_location: None,
+        }
+    }
+}
+
+/// A validated and moved input, as `move_and_complete` left it.
+struct Completed {
+    outcome: ValidationOutcome,
+    message: String,
+    /// The data was written to the destination.
+    moved: bool,
+    /// The input was recorded as delivered in the run history.
+    delivered: bool,
+}
+
+/// Apply each input's `on_success` after its data reached the destination.
+///
+/// The source is kept, deleted, or copied to its `archive_location` and
+/// then deleted (`source_deleted` / `source_archived`). A failure is logged
+/// as `source_cleanup_error` and doesn't fail the run, since the data has
+/// already been moved.
+async fn settle_sources<L: AuditLogger>(run: &Run<'_, L>, inputs: &[&Source]) {
+    let contracts = &run.plan.contracts;
+    for source in inputs.iter().filter(|s| s.on_success != OnSuccess::Keep) {
+        let (event, details) = match settle_source(run.env, source, run.profiles).await {
+            Ok(Some(archived_to)) => ("source_archived", format!("archived_to={}", archived_to)),
+            Ok(None) => ("source_deleted", "on_success=delete".to_string()),
+            Err(e) => ("source_cleanup_error", e.to_string()),
+        };
+        let message = log_action(
+            run.logger,
+            event,
+            Some(&details),
+            Some(&contracts.contract.name),
+            Some(&contracts.contract.version),
+            source.location.as_deref(),
+        );
+        if run.log_to_console {
+            println!("{}", message);
+        }
+    }
+}
+
+/// Delete or archive `source`, returning where it was archived to.
+async fn settle_source(
+    env: &Environment,
+    source: &Source,
+    profiles: &Profiles,
+) -> anyhow::Result<Option<String>> {
+    let archived_to = match source.on_success {
+        OnSuccess::Keep => return Ok(None),
+        OnSuccess::Delete => None,
+        OnSuccess::Archive => {
+            let archive = source.archive_location.as_deref().ok_or_else(|| {
+                anyhow::anyhow!("on_success = \"archive\" needs an archive_location")
+            })?;
+            let location = source.location.as_deref().unwrap_or_default();
+            let filename = std::path::Path::new(location)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| anyhow::anyhow!("Source location {} has no file name", location))?;
+            let to = FileMovement::build_destination_path(archive, filename);
+            env.storage.copy(source, &to, profiles).await?;
+            Some(to)
+        }
+    };
+    env.storage.delete(source, profiles).await?;
+    Ok(archived_to)
+}
+
+/// Whether `bytes` of input exceed the contract's `[limits] max_bytes`.
+fn exceeds_max_bytes(contracts: &SchemaContracts, bytes: usize) -> bool {
+    contracts
+        .limits
+        .as_ref()
+        .and_then(|l| l.max_bytes)
+        .is_some_and(|max_bytes| bytes > max_bytes)
+}
+
+/// Move validated data to the destination or quarantine and log completion.
+///
+/// The first of `inputs` is checked for profile connectivity and its
+/// location names the written object; every input's `on_success` is
+/// applied once the data reaches the destination.
+async fn move_and_complete<L: AuditLogger>(
+    run: &Run<'_, L>,
+    inputs: &[&Source],
+    checked: Checked,
+) -> ValidationResult<Completed> {
+    let source = inputs[0];
+    let Run {
+        env,
+        logger,
+        profiles,
+        executor,
+        log_to_console,
+        ..
+    } = *run;
+    let contracts = &run.plan.contracts;
+    let Checked {
+        results,
+        df,
+        over_byte_limit,
+        over_memory_limit,
+        hashes,
+        mut transfer,
+        mut timings,
+        started,
+        output_location,
+    } = checked;
+    let pass_count = results.iter().filter(|r| r.result == "pass").count();
+    let fail_count = results
+        .iter()
+        .filter(|r| r.result == "fail" || r.result == "aborted")
+        .count();
+    let validation_passed = fail_count == 0 && !over_byte_limit;
+    let mut quarantined = false;
+    let mut moved = false;
+    let mut movement_failed = false;
+    let original_location = output_location
+        .as_deref()
+        .or(source.location.as_deref())
+        .unwrap_or("unknown");
+    let provenance = Provenance {
+        contract: &contracts.contract.name,
+        run_id: run.run_id,
+        validated_at: Utc::now(),
+    };
+
+    // --- Last chance to stop: from here on the data is moved as a whole ---
+    ensure_not_cancelled(env, "before movement")?;
 
     // --- Validate profile connectivity before movement ---
     let (source_valid, dest_valid, quarantine_valid) = FileMovement::validate_profiles(
-        contracts.source.as_ref(),
+        Some(source),
         contracts.destination.as_ref(),
         contracts.quarantine.as_ref(),
-        &profiles,
+        env.storage.as_ref(),
+        profiles,
     )
     .await;
 
     if !source_valid {
-        return Err(ValidationError::Other(
-            "Source profile connectivity failed".to_string(),
+        return Err(ValidationError::source_fetch(
+            &source.r#type,
+            "profile connectivity check failed",
         ));
     }
 
     // --- Movement logic ---
-    if validation_passed {
+    // Oversized inputs fall through to the max_bytes and max_memory_bytes skips below.
+    if env.dry_run && !over_byte_limit && !over_memory_limit {
+        let (kind, target) = if validation_passed {
+            let dest = contracts.destination.as_ref();
+            ("destination", dest.map(|d| (&d.r#type, &d.location)))
+        } else {
+            let quarantine = contracts.quarantine.as_ref();
+            ("quarantine", quarantine.map(|q| (&q.r#type, &q.location)))
+        };
+        if let Some((_, location)) = target.filter(|(r#type, _)| *r#type != "not_moved") {
+            let details = format!(
+                "Dry run: would write to {} {}",
+                kind,
+                location.as_deref().unwrap_or("unknown")
+            );
+            logger.log_and_print(
+                &AuditLogEntry {
+                    timestamp: Utc::now().to_rfc3339(),
+                    level: "AUDIT",
+                    event: "movement_skipped",
+                    run_id: None,
+                    contract: Some(crate::logging::schema::Contract {
+                        name: &contracts.contract.name,
+                        version: &contracts.contract.version,
+                    }),
+                    target: None,
+                    results: None,
+                    executor: executor.clone(),
+                    details: Some(&details),
+                    summary: None,
+                },
+                &format!("🧪 Skipped movement (dry run): {}", details),
+            );
+        }
+    } else if over_memory_limit {
+        logger.log_and_print(
+            &AuditLogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                level: "AUDIT",
+                event: "movement_skipped",
+                run_id: None,
+                contract: Some(crate::logging::schema::Contract {
+                    name: &contracts.contract.name,
+                    version: &contracts.contract.version,
+                }),
+                target: None,
+                results: None,
+                executor: executor.clone(),
+                details: Some("Input exceeds max_memory_bytes limit"),
+                summary: None,
+            },
+            "⚠️ Skipped movement: input exceeds max_memory_bytes limit",
+        );
+    } else if validation_passed {
         if let Some(dest) = &contracts.destination {
             if dest.r#type != "not_moved" {
                 if !dest_valid {
@@ -130,6 +1637,7 @@ pub async fn run_contract_validation<L: AuditLogger>(
                             timestamp: Utc::now().to_rfc3339(),
                             level: "AUDIT",
                             event: "movement_skipped",
+                            run_id: None,
                             contract: Some(crate::logging::schema::Contract {
                                 name: &contracts.contract.name,
                                 version: &contracts.contract.version,
@@ -143,47 +1651,89 @@ pub async fn run_contract_validation<L: AuditLogger>(
                         "⚠️ Skipped movement: destination profile invalid",
                     );
                 } else {
-                    match FileMovement::write_success_data(&df, original_location, dest, &profiles)
-                        .await
+                    match FileMovement::write_success_data(
+                        &df,
+                        original_location,
+                        dest,
+                        &provenance,
+                        env.storage.as_ref(),
+                        profiles,
+                        logger,
+                        log_to_console,
+                    )
+                    .await
                     {
-                        Ok(_) => logger.log_and_print(
-                            &AuditLogEntry {
-                                timestamp: Utc::now().to_rfc3339(),
-                                level: "AUDIT",
-                                event: "movement_success",
-                                contract: Some(crate::logging::schema::Contract {
-                                    name: &contracts.contract.name,
-                                    version: &contracts.contract.version,
-                                }),
-                                target: None,
-                                results: None,
-                                executor: executor.clone(),
-                                details: Some("Data written to destination"),
-                                summary: None,
-                            },
-                            "✅ Data written to destination",
-                        ),
-                        Err(e) => logger.log_and_print(
-                            &AuditLogEntry {
-                                timestamp: Utc::now().to_rfc3339(),
-                                level: "AUDIT",
-                                event: "movement_error",
-                                contract: Some(crate::logging::schema::Contract {
-                                    name: &contracts.contract.name,
-                                    version: &contracts.contract.version,
-                                }),
-                                target: None,
-                                results: None,
-                                executor: executor.clone(),
-                                details: Some(&format!("Failed to write to destination: {}", e)),
-                                summary: None,
-                            },
-                            &format!("❌ Failed to write to destination: {}", e),
-                        ),
+                        Ok(bytes) => {
+                            run.moved.store(true, Ordering::Relaxed);
+                            transfer.record_upload(&dest.r#type, bytes);
+                            logger.log_and_print(
+                                &AuditLogEntry {
+                                    timestamp: Utc::now().to_rfc3339(),
+                                    level: "AUDIT",
+                                    event: "movement_success",
+                                    run_id: None,
+                                    contract: Some(crate::logging::schema::Contract {
+                                        name: &contracts.contract.name,
+                                        version: &contracts.contract.version,
+                                    }),
+                                    target: None,
+                                    results: None,
+                                    executor: executor.clone(),
+                                    details: Some("Data written to destination"),
+                                    summary: None,
+                                },
+                                "✅ Data written to destination",
+                            );
+                            moved = true;
+                            settle_sources(run, inputs).await;
+                        }
+                        Err(e) => {
+                            movement_failed = true;
+                            logger.log_and_print(
+                                &AuditLogEntry {
+                                    timestamp: Utc::now().to_rfc3339(),
+                                    level: "AUDIT",
+                                    event: "movement_error",
+                                    run_id: None,
+                                    contract: Some(crate::logging::schema::Contract {
+                                        name: &contracts.contract.name,
+                                        version: &contracts.contract.version,
+                                    }),
+                                    target: None,
+                                    results: None,
+                                    executor: executor.clone(),
+                                    details: Some(&format!(
+                                        "Failed to write to destination: {}",
+                                        e
+                                    )),
+                                    summary: None,
+                                },
+                                &format!("❌ Failed to write to destination: {}", e),
+                            )
+                        }
                     }
                 }
             }
         }
+    } else if over_byte_limit {
+        logger.log_and_print(
+            &AuditLogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                level: "AUDIT",
+                event: "movement_skipped",
+                run_id: None,
+                contract: Some(crate::logging::schema::Contract {
+                    name: &contracts.contract.name,
+                    version: &contracts.contract.version,
+                }),
+                target: None,
+                results: None,
+                executor: executor.clone(),
+                details: Some("Input exceeds max_bytes limit"),
+                summary: None,
+            },
+            "⚠️ Skipped movement: input exceeds max_bytes limit",
+        );
     } else {
         if let Some(quarantine) = &contracts.quarantine {
             if quarantine.r#type != "not_moved" {
@@ -193,6 +1743,7 @@ pub async fn run_contract_validation<L: AuditLogger>(
                             timestamp: Utc::now().to_rfc3339(),
                             level: "AUDIT",
                             event: "movement_skipped",
+                            run_id: None,
                             contract: Some(crate::logging::schema::Contract {
                                 name: &contracts.contract.name,
                                 version: &contracts.contract.version,
@@ -210,32 +1761,43 @@ pub async fn run_contract_validation<L: AuditLogger>(
                         &df,
                         original_location,
                         quarantine,
-                        &profiles,
+                        &provenance,
+                        env.storage.as_ref(),
+                        profiles,
+                        logger,
+                        log_to_console,
                     )
                     .await
                     {
-                        Ok(_) => logger.log_and_print(
-                            &AuditLogEntry {
-                                timestamp: Utc::now().to_rfc3339(),
-                                level: "AUDIT",
-                                event: "movement_quarantine",
-                                contract: Some(crate::logging::schema::Contract {
-                                    name: &contracts.contract.name,
-                                    version: &contracts.contract.version,
-                                }),
-                                target: None,
-                                results: None,
-                                executor: executor.clone(),
-                                details: Some("Data written to quarantine"),
-                                summary: None,
-                            },
-                            "⚠️ Data quarantined",
-                        ),
+                        Ok(bytes) => {
+                            run.moved.store(true, Ordering::Relaxed);
+                            transfer.record_upload(&quarantine.r#type, bytes);
+                            quarantined = true;
+                            logger.log_and_print(
+                                &AuditLogEntry {
+                                    timestamp: Utc::now().to_rfc3339(),
+                                    level: "AUDIT",
+                                    event: "movement_quarantine",
+                                    run_id: None,
+                                    contract: Some(crate::logging::schema::Contract {
+                                        name: &contracts.contract.name,
+                                        version: &contracts.contract.version,
+                                    }),
+                                    target: None,
+                                    results: None,
+                                    executor: executor.clone(),
+                                    details: Some("Data written to quarantine"),
+                                    summary: None,
+                                },
+                                "⚠️ Data quarantined",
+                            )
+                        }
                         Err(e) => logger.log_and_print(
                             &AuditLogEntry {
                                 timestamp: Utc::now().to_rfc3339(),
                                 level: "AUDIT",
                                 event: "movement_error",
+                                run_id: None,
                                 contract: Some(crate::logging::schema::Contract {
                                     name: &contracts.contract.name,
                                     version: &contracts.contract.version,
@@ -254,8 +1816,30 @@ pub async fn run_contract_validation<L: AuditLogger>(
         }
     }
 
+    // --- Record delivered inputs for `on_duplicate` ---
+    let delivered = validation_passed && !over_memory_limit && !movement_failed && !env.dry_run;
+    if delivered {
+        for (input, sha256) in inputs.iter().zip(&hashes) {
+            let location = input.location.as_deref().unwrap_or("unknown");
+            env.history
+                .record_delivery(
+                    &contracts.contract.name,
+                    &Delivery::new(sha256, location, run.run_id),
+                )
+                .map_err(|e| ValidationError::HistoryWrite(e.to_string()))?;
+        }
+    }
+
     // --- Completion log ---
-    let details = format!("pass={}, fail={}", pass_count, fail_count);
+    timings.total = started.elapsed();
+    let details = format!(
+        "pass={}, fail={}, {}, {}, contract_hash={}",
+        pass_count,
+        fail_count,
+        transfer.to_details(),
+        timings.to_details(),
+        run.plan.hash
+    );
     let message = log_action(
         logger,
         "contract_validation_completed",
@@ -268,13 +1852,20 @@ pub async fn run_contract_validation<L: AuditLogger>(
         println!("{}", message);
     }
 
-    Ok((
-        ValidationOutcome {
+    Ok(Completed {
+        outcome: ValidationOutcome {
             passed: validation_passed,
             pass_count,
             fail_count,
             results,
+            transfer,
+            objects: Vec::new(),
+            quarantined,
+            run_id: run.run_id.to_string(),
+            timings,
         },
         message,
-    ))
+        moved,
+        delivered,
+    })
 }
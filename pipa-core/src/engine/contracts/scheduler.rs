@@ -0,0 +1,273 @@
+//! Resident scheduler running contracts on their `[schedule] cron`.
+//!
+//! `Scheduler::run` sleeps until the next due minute, runs every contract
+//! due then (one after another), and repeats until shut down. Each trigger
+//! is logged as `schedule_triggered` before the contract runs. Triggers that
+//! fall due while earlier runs are still going are skipped, not queued.
+//!
+//! With `[sealing] cron` in `pipa.toml`, the scheduler also seals the
+//! previous day's log at those times (logged as `logs_sealed`), rather than
+//! leaving it to the first entry written on the new day.
+
+use crate::config::{PROJECT_FILE, contracts_pattern, load_project_config};
+use crate::contracts::{is_overlay, load_contract_for_file};
+use crate::engine::contracts::runner::run_contract_validation_with;
+use crate::engine::cron::CronSchedule;
+use crate::engine::log_action;
+use crate::engine::logs::seal_logs;
+use crate::environment::Environment;
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::logging::schema::Executor;
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::path::Path;
+
+/// A contract and when it runs.
+#[derive(Debug, Clone)]
+pub struct ScheduledContract {
+    pub contract: String,
+    pub cron: CronSchedule,
+}
+
+impl ScheduledContract {
+    /// When the contract next runs, from now.
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        self.cron.next_after(Utc::now())
+    }
+}
+
+/// Tallies over a scheduler session.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SchedulerSummary {
+    pub triggers: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub errors: usize,
+}
+
+/// Contracts in `contracts/*.toml` with a `[schedule]` section, by name.
+///
+/// An unparseable contract or invalid cron expression is an error rather
+/// than a contract that silently never runs.
+pub fn scheduled_contracts() -> ValidationResult<Vec<ScheduledContract>> {
+    let paths = glob::glob(&contracts_pattern())
+        .map_err(|e| ValidationError::Config(format!("Invalid contracts pattern: {}", e)))?;
+
+    let mut scheduled = Vec::new();
+    for path in paths {
+        let path = path.map_err(|e| ValidationError::Config(e.to_string()))?;
+        if is_overlay(&path) {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let contract = load_contract_for_file(&path)?;
+        if let Some(schedule) = &contract.schedule {
+            let cron = CronSchedule::parse(&schedule.cron)
+                .map_err(|e| ValidationError::Config(format!("Contract '{}': {}", name, e)))?;
+            scheduled.push(ScheduledContract {
+                contract: name.to_string(),
+                cron,
+            });
+        }
+    }
+    scheduled.sort_by(|a, b| a.contract.cmp(&b.contract));
+    Ok(scheduled)
+}
+
+/// The `[sealing] cron` in `pipa.toml`, if there is one.
+pub fn sealing_schedule() -> ValidationResult<Option<CronSchedule>> {
+    let project = load_project_config(Path::new(PROJECT_FILE))?;
+    project
+        .sealing
+        .map(|sealing| {
+            CronSchedule::parse(&sealing.cron)
+                .map_err(|e| ValidationError::Config(format!("{} [sealing]: {}", PROJECT_FILE, e)))
+        })
+        .transpose()
+}
+
+/// Runs scheduled contracts at their cron times.
+pub struct Scheduler {
+    env: Environment,
+    contracts: Vec<ScheduledContract>,
+    sealing: Option<CronSchedule>,
+}
+
+impl Scheduler {
+    pub fn new(env: Environment, contracts: Vec<ScheduledContract>) -> Self {
+        Self {
+            env,
+            contracts,
+            sealing: None,
+        }
+    }
+
+    /// Also seal every unsealed log older than today at `cron`'s times.
+    pub fn with_sealing(mut self, cron: CronSchedule) -> Self {
+        self.sealing = Some(cron);
+        self
+    }
+
+    /// The next minute after `after` at which any contract is due, with the
+    /// contracts due then.
+    pub fn next_due(
+        &self,
+        after: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, Vec<&ScheduledContract>)> {
+        let next = self
+            .contracts
+            .iter()
+            .filter_map(|scheduled| scheduled.cron.next_after(after))
+            .min()?;
+        let due = self
+            .contracts
+            .iter()
+            .filter(|scheduled| scheduled.cron.next_after(after) == Some(next))
+            .collect();
+        Some((next, due))
+    }
+
+    /// Run contracts as they fall due until `shutdown` completes.
+    pub async fn run<L: AuditLogger, F: Future<Output = ()>>(
+        &self,
+        logger: &L,
+        executor: &Executor,
+        log_to_console: bool,
+        shutdown: F,
+    ) -> SchedulerSummary {
+        let message = log_action(
+            logger,
+            "scheduler_started",
+            Some(&format!("contracts={}", self.contracts.len())),
+            None,
+            None,
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+
+        let mut summary = SchedulerSummary::default();
+        tokio::pin!(shutdown);
+        loop {
+            let now = Utc::now();
+            let contracts = self.next_due(now);
+            let seal_at = self.sealing.as_ref().and_then(|cron| cron.next_after(now));
+            let Some(at) = contracts.iter().map(|(at, _)| *at).chain(seal_at).min() else {
+                break;
+            };
+            let wait = (at - Utc::now()).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(wait) => {}
+            }
+
+            // Close yesterday before today's runs add to the new log
+            if seal_at == Some(at) {
+                match seal_logs(logger, None) {
+                    Ok((_, message)) if log_to_console => println!("{}", message),
+                    Ok(_) => {}
+                    Err(e) if log_to_console => println!("❌ Scheduled sealing failed: {}", e),
+                    Err(_) => {}
+                }
+            }
+
+            let due = contracts
+                .filter(|(due_at, _)| *due_at == at)
+                .map(|(_, due)| due)
+                .unwrap_or_default();
+            for scheduled in due {
+                let message = log_action(
+                    logger,
+                    "schedule_triggered",
+                    Some(&format!(
+                        "cron={}, scheduled_for={}",
+                        scheduled.cron,
+                        at.to_rfc3339()
+                    )),
+                    Some(&scheduled.contract),
+                    None,
+                    None,
+                );
+                if log_to_console {
+                    println!("{}", message);
+                }
+                summary.triggers += 1;
+                match run_contract_validation_with(
+                    &self.env,
+                    logger,
+                    &scheduled.contract,
+                    executor,
+                    log_to_console,
+                )
+                .await
+                {
+                    Ok((outcome, _)) if outcome.passed => summary.passed += 1,
+                    Ok(_) => summary.failed += 1,
+                    Err(e) => {
+                        summary.errors += 1;
+                        if log_to_console {
+                            println!("❌ Scheduled run of '{}' failed: {}", scheduled.contract, e);
+                        }
+                    }
+                }
+            }
+        }
+
+        let message = log_action(
+            logger,
+            "scheduler_stopped",
+            Some(&format!(
+                "triggers={}, passed={}, failed={}, errors={}",
+                summary.triggers, summary.passed, summary.failed, summary.errors
+            )),
+            None,
+            None,
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduled(contract: &str, cron: &str) -> ScheduledContract {
+        ScheduledContract {
+            contract: contract.to_string(),
+            cron: CronSchedule::parse(cron).unwrap(),
+        }
+    }
+
+    #[test]
+    fn groups_contracts_due_at_the_same_minute() {
+        let scheduler = Scheduler::new(
+            Environment::default(),
+            vec![
+                scheduled("orders", "0 6 * * *"),
+                scheduled("billing", "0 */2 * * *"),
+                scheduled("metrics", "30 5 * * *"),
+            ],
+        );
+        let after = DateTime::parse_from_rfc3339("2025-03-10T05:45:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let (at, due) = scheduler.next_due(after).unwrap();
+        assert_eq!(at.to_rfc3339(), "2025-03-10T06:00:00+00:00");
+        let names: Vec<&str> = due.iter().map(|s| s.contract.as_str()).collect();
+        assert_eq!(names, vec!["orders", "billing"]);
+        assert!(
+            Scheduler::new(Environment::default(), Vec::new())
+                .next_due(after)
+                .is_none()
+        );
+    }
+}
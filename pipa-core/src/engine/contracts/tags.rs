@@ -0,0 +1,146 @@
+//! Selecting and running contracts by tag.
+//!
+//! Contracts declare `tags = ["pii", "critical"]` under `[contract]`. A
+//! `TagFilter` keeps contracts carrying any of its `include` tags (all
+//! contracts when `include` is empty) and drops those carrying any of its
+//! `exclude` tags, e.g. only `critical` contracts on an hourly schedule.
+
+use crate::config::contracts_pattern;
+use crate::contracts::{is_overlay, load_contract_for_file};
+use crate::engine::contracts::runner::{ValidationOutcome, run_contract_validation};
+use crate::engine::log_action;
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::logging::schema::Executor;
+
+/// Tags a contract must (and must not) carry to be selected.
+#[derive(Debug, Clone, Default)]
+pub struct TagFilter {
+    /// Select contracts with any of these tags; empty selects every contract.
+    pub include: Vec<String>,
+    /// Skip contracts with any of these tags.
+    pub exclude: Vec<String>,
+}
+
+impl TagFilter {
+    /// Whether the filter restricts the selection at all.
+    pub fn is_active(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    /// Whether a contract with `tags` is selected.
+    pub fn matches(&self, tags: &[String]) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|t| tags.contains(t));
+        let excluded = self.exclude.iter().any(|t| tags.contains(t));
+        included && !excluded
+    }
+}
+
+/// Outcome of one contract in a tag-selected run.
+pub struct ContractRun {
+    pub contract: String,
+    pub result: ValidationResult<ValidationOutcome>,
+}
+
+/// Names of the contracts in `contracts/*.toml` selected by `filter`, sorted.
+///
+/// A contract that can't be parsed has unknown tags, so it is reported as
+/// a `ContractParse` error rather than silently left out.
+pub fn select_contracts(filter: &TagFilter) -> ValidationResult<Vec<String>> {
+    let paths = glob::glob(&contracts_pattern())
+        .map_err(|e| ValidationError::Config(format!("Invalid contracts pattern: {}", e)))?;
+
+    let mut selected = Vec::new();
+    for path in paths {
+        let path = path.map_err(|e| ValidationError::Config(e.to_string()))?;
+        if is_overlay(&path) {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let contract = load_contract_for_file(&path)?;
+        if filter.matches(&contract.contract.tags) {
+            selected.push(name.to_string());
+        }
+    }
+    selected.sort();
+    Ok(selected)
+}
+
+/// Run every contract selected by `filter`, one after another.
+///
+/// # Arguments
+/// * `logger` - The audit logger implementation to use
+/// * `filter` - Tags to include and exclude
+/// * `executor` - Executor context (user/host info)
+/// * `log_to_console` - Whether to print messages to console
+///
+/// A failing contract doesn't stop the others; each outcome is returned
+/// in selection order.
+pub async fn run_contracts_by_tag<L: AuditLogger>(
+    logger: &L,
+    filter: &TagFilter,
+    executor: &Executor,
+    log_to_console: bool,
+) -> ValidationResult<(Vec<ContractRun>, String)> {
+    let selected = select_contracts(filter)?;
+    let message = log_action(
+        logger,
+        "contracts_selected",
+        Some(&format!(
+            "tags={}, exclude_tags={}, selected={}",
+            filter.include.join("|"),
+            filter.exclude.join("|"),
+            selected.len()
+        )),
+        None,
+        None,
+        None,
+    );
+    if log_to_console {
+        println!("{}", message);
+    }
+
+    let mut runs = Vec::with_capacity(selected.len());
+    for contract in selected {
+        let result = run_contract_validation(logger, &contract, executor, log_to_console)
+            .await
+            .map(|(outcome, _)| outcome);
+        runs.push(ContractRun { contract, result });
+    }
+    Ok((runs, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(values: &[&str]) -> Vec<String> {
+        values.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn includes_any_and_excludes_any() {
+        let filter = TagFilter {
+            include: tags(&["pii", "critical"]),
+            exclude: tags(&["slow"]),
+        };
+        assert!(filter.matches(&tags(&["critical"])));
+        assert!(filter.matches(&tags(&["pii", "finance"])));
+        assert!(!filter.matches(&tags(&["finance"])));
+        assert!(!filter.matches(&tags(&["critical", "slow"])));
+    }
+
+    #[test]
+    fn empty_include_selects_everything_not_excluded() {
+        let filter = TagFilter {
+            include: Vec::new(),
+            exclude: tags(&["slow"]),
+        };
+        assert!(filter.is_active());
+        assert!(filter.matches(&[]));
+        assert!(!filter.matches(&tags(&["slow"])));
+        assert!(!TagFilter::default().is_active());
+    }
+}
@@ -0,0 +1,62 @@
+//! Per-run duration accounting.
+//!
+//! Tracks how long each stage of a contract run took, so validation
+//! performance can be followed across releases and as data grows. Times
+//! per rule are recorded on each `RuleResult` (`duration_ms`).
+
+use serde::{Serialize, Serializer};
+use std::time::Duration;
+
+/// Time spent in each stage of one run.
+///
+/// `parse` is zero when the input is validated with `[streaming]`, where
+/// parsing and rules run batch by batch and are counted in `validate`.
+///
+/// Serializes as milliseconds: `fetch_ms`, `parse_ms`, `validate_ms`,
+/// `total_ms`.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct RunTimings {
+    /// Fetching the source data.
+    #[serde(rename = "fetch_ms", serialize_with = "serialize_millis")]
+    pub fetch: Duration,
+    /// Parsing it into a DataFrame (and combining `[[sources]]`).
+    #[serde(rename = "parse_ms", serialize_with = "serialize_millis")]
+    pub parse: Duration,
+    /// Running the contract's rules.
+    #[serde(rename = "validate_ms", serialize_with = "serialize_millis")]
+    pub validate: Duration,
+    /// The whole run, from the start log to the completion log.
+    #[serde(rename = "total_ms", serialize_with = "serialize_millis")]
+    pub total: Duration,
+}
+
+impl RunTimings {
+    /// Add another run's stage times to these (`total` included).
+    pub fn merge(&mut self, other: &RunTimings) {
+        self.fetch += other.fetch;
+        self.parse += other.parse;
+        self.validate += other.validate;
+        self.total += other.total;
+    }
+
+    /// Render as an audit-log detail fragment, e.g.
+    /// `fetch_ms=12, parse_ms=40, validate_ms=85, total_ms=150`.
+    pub fn to_details(&self) -> String {
+        format!(
+            "fetch_ms={}, parse_ms={}, validate_ms={}, total_ms={}",
+            self.fetch.as_millis(),
+            self.parse.as_millis(),
+            self.validate.as_millis(),
+            self.total.as_millis()
+        )
+    }
+}
+
+/// `duration` in milliseconds, to microsecond precision.
+pub(crate) fn millis(duration: Duration) -> f64 {
+    (duration.as_micros() as f64) / 1000.0
+}
+
+fn serialize_millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(millis(*duration))
+}
@@ -0,0 +1,103 @@
+//! Per-run transfer accounting.
+//!
+//! Tracks how many bytes each connector type downloaded (source fetches)
+//! and uploaded (destination/quarantine writes) during a single contract
+//! run, so cloud egress can be attributed to the contract that caused it.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Bytes moved through connectors during one run, keyed by connector
+/// type (`"local"`, `"s3"`, `"azure"`, `"gcs"`).
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TransferStats {
+    pub downloaded: BTreeMap<String, u64>,
+    pub uploaded: BTreeMap<String, u64>,
+}
+
+impl TransferStats {
+    /// Record bytes fetched from a source of the given connector type.
+    pub fn record_download(&mut self, connector: &str, bytes: usize) {
+        *self.downloaded.entry(connector.to_string()).or_insert(0) += bytes as u64;
+    }
+
+    /// Record bytes written to a sink of the given connector type.
+    pub fn record_upload(&mut self, connector: &str, bytes: usize) {
+        *self.uploaded.entry(connector.to_string()).or_insert(0) += bytes as u64;
+    }
+
+    /// Add another run's transfers to these.
+    pub fn merge(&mut self, other: &TransferStats) {
+        for (connector, bytes) in &other.downloaded {
+            *self.downloaded.entry(connector.clone()).or_insert(0) += bytes;
+        }
+        for (connector, bytes) in &other.uploaded {
+            *self.uploaded.entry(connector.clone()).or_insert(0) += bytes;
+        }
+    }
+
+    /// Total bytes downloaded across all connectors.
+    pub fn total_downloaded(&self) -> u64 {
+        self.downloaded.values().sum()
+    }
+
+    /// Total bytes uploaded across all connectors.
+    pub fn total_uploaded(&self) -> u64 {
+        self.uploaded.values().sum()
+    }
+
+    /// Render as an audit-log detail fragment, e.g.
+    /// `bytes_downloaded=1024, bytes_uploaded=2048, s3_down=1024, local_up=2048`.
+    pub fn to_details(&self) -> String {
+        let mut parts = vec![
+            format!("bytes_downloaded={}", self.total_downloaded()),
+            format!("bytes_uploaded={}", self.total_uploaded()),
+        ];
+        for (connector, bytes) in &self.downloaded {
+            parts.push(format!("{}_down={}", connector, bytes));
+        }
+        for (connector, bytes) in &self.uploaded {
+            parts.push(format!("{}_up={}", connector, bytes));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn details_list_totals_then_each_connector() {
+        let mut stats = TransferStats::default();
+        assert_eq!(stats.to_details(), "bytes_downloaded=0, bytes_uploaded=0");
+
+        stats.record_download("s3", 1024);
+        stats.record_download("local", 10);
+        stats.record_upload("local", 2048);
+        assert_eq!(
+            stats.to_details(),
+            "bytes_downloaded=1034, bytes_uploaded=2048, local_down=10, s3_down=1024, \
+             local_up=2048"
+        );
+    }
+
+    #[test]
+    fn records_and_merges_per_connector() {
+        let mut first = TransferStats::default();
+        first.record_download("s3", 100);
+        first.record_download("s3", 50);
+        first.record_upload("gcs", 70);
+        let mut second = TransferStats::default();
+        second.record_download("azure", 5);
+        second.record_upload("gcs", 30);
+
+        first.merge(&second);
+
+        assert_eq!(first.downloaded["s3"], 150);
+        assert_eq!(first.downloaded["azure"], 5);
+        assert_eq!(first.uploaded["gcs"], 100);
+        assert_eq!(first.total_downloaded(), 155);
+        assert_eq!(first.total_uploaded(), 100);
+    }
+}
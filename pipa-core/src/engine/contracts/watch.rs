@@ -0,0 +1,428 @@
+//! Watch mode: run contracts as new files land in their sources.
+//!
+//! A `Watcher` polls each contract's source location through the
+//! environment's `ObjectStore::list`:
+//! - a directory or prefix (`data/orders/`, `s3://landing/orders/`) runs the
+//!   contract against every new object under it, with `[source] location`
+//!   pointed at that object;
+//! - a single file runs the contract whenever the file appears or changes.
+//!
+//! Objects already present when watching starts are not run. An object is
+//! only run once its size has held steady for the debounce period, so
+//! files still being written are picked up when complete; files staged
+//! under pipa's temporary name (see `movement`) are never run.
+
+use crate::engine::contracts::runner::{ValidationOutcome, run_contract_validation_with};
+use crate::engine::log_action;
+use crate::engine::plan::ExecutionPlan;
+use crate::environment::{ConfigStore, Environment};
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::logging::schema::Executor;
+use crate::movement::is_temporary;
+use crate::profiles::Profiles;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default time between polls.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default time an object's size must hold steady before it is run.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_secs(5);
+
+/// What to watch and how often.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Contracts to watch, by name.
+    pub contracts: Vec<String>,
+    /// Time between polls.
+    pub interval: Duration,
+    /// Time an object's size must hold steady before it is run.
+    pub debounce: Duration,
+}
+
+impl WatchOptions {
+    /// Watch `contracts` with the default interval and debounce.
+    pub fn new(contracts: Vec<String>) -> Self {
+        Self {
+            contracts,
+            interval: DEFAULT_POLL_INTERVAL,
+            debounce: DEFAULT_DEBOUNCE,
+        }
+    }
+}
+
+/// A contract run triggered by a new or changed object.
+pub struct WatchRun {
+    pub contract: String,
+    pub location: String,
+    pub result: ValidationResult<ValidationOutcome>,
+}
+
+/// Tallies over a watch session.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WatchSummary {
+    pub polls: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub errors: usize,
+}
+
+/// Per-contract view of its source.
+#[derive(Default)]
+struct WatchState {
+    /// Whether the first listing (the baseline) has been taken.
+    primed: bool,
+    /// Sizes of objects already run or present at startup.
+    known: HashMap<String, u64>,
+    /// Unseen objects waiting out the debounce: size and when first seen at it.
+    pending: HashMap<String, (u64, Instant)>,
+}
+
+/// Polls contract sources and runs contracts on new objects.
+pub struct Watcher {
+    env: Environment,
+    options: WatchOptions,
+    state: HashMap<String, WatchState>,
+}
+
+impl Watcher {
+    pub fn new(env: Environment, options: WatchOptions) -> Self {
+        Self {
+            env,
+            options,
+            state: HashMap::new(),
+        }
+    }
+
+    /// Poll every watched contract once, running those with new objects.
+    ///
+    /// The first poll of a contract only records what is already there.
+    /// Listing and config errors are logged as `watch_error` and the
+    /// contract is retried on the next poll.
+    pub async fn poll<L: AuditLogger>(
+        &mut self,
+        logger: &L,
+        executor: &Executor,
+        log_to_console: bool,
+    ) -> Vec<WatchRun> {
+        let mut runs = Vec::new();
+        for contract in self.options.contracts.clone() {
+            match self.ready_objects(&contract).await {
+                Ok(ready) => {
+                    for (location, size) in ready {
+                        let message = log_action(
+                            logger,
+                            "watch_triggered",
+                            Some(&format!("size={}", size)),
+                            Some(&contract),
+                            None,
+                            Some(&location),
+                        );
+                        if log_to_console {
+                            println!("{}", message);
+                        }
+                        let result = self
+                            .run_at(logger, &contract, &location, executor, log_to_console)
+                            .await;
+                        runs.push(WatchRun {
+                            contract: contract.clone(),
+                            location,
+                            result,
+                        });
+                    }
+                }
+                Err(e) => {
+                    let message = log_action(
+                        logger,
+                        "watch_error",
+                        Some(&e.to_string()),
+                        Some(&contract),
+                        None,
+                        None,
+                    );
+                    if log_to_console {
+                        println!("{}", message);
+                    }
+                }
+            }
+        }
+        runs
+    }
+
+    /// Poll every `interval` until `shutdown` completes.
+    pub async fn run<L: AuditLogger, F: Future<Output = ()>>(
+        mut self,
+        logger: &L,
+        executor: &Executor,
+        log_to_console: bool,
+        shutdown: F,
+    ) -> WatchSummary {
+        let message = log_action(
+            logger,
+            "watch_started",
+            Some(&format!(
+                "contracts={}, interval_secs={}, debounce_secs={}",
+                self.options.contracts.len(),
+                self.options.interval.as_secs_f64(),
+                self.options.debounce.as_secs_f64()
+            )),
+            None,
+            None,
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+
+        let mut summary = WatchSummary::default();
+        tokio::pin!(shutdown);
+        loop {
+            for run in self.poll(logger, executor, log_to_console).await {
+                match run.result {
+                    Ok(outcome) if outcome.passed => summary.passed += 1,
+                    Ok(_) => summary.failed += 1,
+                    Err(_) => summary.errors += 1,
+                }
+            }
+            summary.polls += 1;
+
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(self.options.interval) => {}
+            }
+        }
+
+        let message = log_action(
+            logger,
+            "watch_stopped",
+            Some(&format!(
+                "polls={}, passed={}, failed={}, errors={}",
+                summary.polls, summary.passed, summary.failed, summary.errors
+            )),
+            None,
+            None,
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+        summary
+    }
+
+    /// List `contract`'s source and return the objects due to run.
+    async fn ready_objects(&mut self, contract: &str) -> ValidationResult<Vec<(String, u64)>> {
+        let (plan, _) = ExecutionPlan::cached(&self.env.config.contract_toml(contract)?)?;
+        let source = &self.env.dated_source(
+            plan.contracts
+                .source
+                .as_ref()
+                .ok_or_else(|| ValidationError::Config("Contract missing source".to_string()))?,
+        )?;
+        let location = source
+            .location
+            .as_deref()
+            .ok_or_else(|| ValidationError::Config("Source missing location".to_string()))?;
+        let profiles = self.env.config.profiles()?;
+
+        let is_prefix =
+            location.ends_with('/') || (source.r#type == "local" && Path::new(location).is_dir());
+        let objects = self.env.storage.list(source, location, &profiles).await?;
+        let objects: HashMap<String, u64> = objects
+            .into_iter()
+            .filter(|object| is_prefix || object.location == location)
+            .filter(|object| !is_temporary(&object.location))
+            .map(|object| (object.location, object.size))
+            .collect();
+
+        let state = self.state.entry(contract.to_string()).or_default();
+        if !state.primed {
+            state.primed = true;
+            state.known = objects;
+            return Ok(Vec::new());
+        }
+
+        let now = Instant::now();
+        let mut ready = Vec::new();
+        for (location, size) in &objects {
+            if state.known.get(location) == Some(size) {
+                continue;
+            }
+            let (pending_size, since) = state
+                .pending
+                .entry(location.clone())
+                .or_insert((*size, now));
+            if *pending_size != *size {
+                *pending_size = *size;
+                *since = now;
+            }
+            if now.duration_since(*since) >= self.options.debounce {
+                ready.push((location.clone(), *size));
+            }
+        }
+        for (location, size) in &ready {
+            state.pending.remove(location);
+            state.known.insert(location.clone(), *size);
+        }
+        // Forget vanished objects so they run again if they reappear.
+        state
+            .known
+            .retain(|location, _| objects.contains_key(location));
+        state
+            .pending
+            .retain(|location, _| objects.contains_key(location));
+
+        ready.sort();
+        Ok(ready)
+    }
+
+    /// Run `contract` with its source location pointed at `location`.
+    async fn run_at<L: AuditLogger>(
+        &self,
+        logger: &L,
+        contract: &str,
+        location: &str,
+        executor: &Executor,
+        log_to_console: bool,
+    ) -> ValidationResult<ValidationOutcome> {
+        let env = Environment {
+            config: Arc::new(SourceOverride {
+                inner: Arc::clone(&self.env.config),
+                contract: contract.to_string(),
+                location: location.to_string(),
+            }),
+            ..self.env.clone()
+        };
+        run_contract_validation_with(&env, logger, contract, executor, log_to_console)
+            .await
+            .map(|(outcome, _)| outcome)
+    }
+}
+
+/// Serves a contract with its `[source] location` replaced.
+struct SourceOverride {
+    inner: Arc<dyn ConfigStore>,
+    contract: String,
+    location: String,
+}
+
+impl ConfigStore for SourceOverride {
+    fn contract_toml(&self, name: &str) -> ValidationResult<String> {
+        let toml_str = self.inner.contract_toml(name)?;
+        if name != self.contract {
+            return Ok(toml_str);
+        }
+
+        let mut contract: toml::Table = toml::from_str(&toml_str).map_err(|e| {
+            ValidationError::ContractParse(format!("Failed to parse contract TOML: {}", e))
+        })?;
+        if let Some(toml::Value::Table(source)) = contract.get_mut("source") {
+            source.insert(
+                "location".to_string(),
+                toml::Value::String(self.location.clone()),
+            );
+        }
+        toml::to_string(&contract).map_err(|e| {
+            ValidationError::ContractParse(format!("Failed to serialize contract TOML: {}", e))
+        })
+    }
+
+    fn profiles(&self) -> ValidationResult<Profiles> {
+        self.inner.profiles()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::InMemory;
+    use crate::logging::MemoryLogger;
+    use crate::profiles::Profile;
+
+    const CONTRACT: &str = r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "s3"
+location = "s3://landing/orders/"
+profile = "landing"
+
+[destination]
+type = "local"
+location = "clean/"
+"#;
+
+    fn executor() -> Executor {
+        Executor {
+            user: "test".to_string(),
+            host: "test".to_string(),
+        }
+    }
+
+    fn watcher(debounce: Duration) -> (InMemory, Watcher) {
+        let memory = InMemory::new();
+        memory.add_contract("orders", CONTRACT);
+        memory.add_profile(
+            "landing",
+            toml::from_str::<Profile>(r#"provider = "s3""#).unwrap(),
+        );
+        memory.put_object("s3://landing/orders/old.csv", "id\n1\n");
+        let options = WatchOptions {
+            contracts: vec!["orders".to_string()],
+            interval: Duration::ZERO,
+            debounce,
+        };
+        let watcher = Watcher::new(memory.environment(), options);
+        (memory, watcher)
+    }
+
+    #[tokio::test]
+    async fn runs_contract_on_new_objects_only() {
+        let (memory, mut watcher) = watcher(Duration::ZERO);
+        let logger = MemoryLogger::new();
+
+        assert!(watcher.poll(&logger, &executor(), false).await.is_empty());
+        memory.put_object("s3://landing/orders/new.csv", "id\n2\n");
+        memory.put_object("s3://landing/other.csv", "id\n3\n");
+        memory.put_object("s3://landing/orders/.pipa-tmp-part.csv", "id\n");
+
+        let runs = watcher.poll(&logger, &executor(), false).await;
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].location, "s3://landing/orders/new.csv");
+        assert!(runs[0].result.as_ref().unwrap().passed);
+        assert!(
+            memory
+                .locations()
+                .iter()
+                .any(|location| location.starts_with("clean/new_"))
+        );
+
+        assert!(watcher.poll(&logger, &executor(), false).await.is_empty());
+        assert!(logger.events().iter().any(|e| e == "watch_triggered"));
+    }
+
+    #[tokio::test]
+    async fn waits_for_size_to_settle() {
+        let (memory, mut watcher) = watcher(Duration::from_millis(50));
+        let logger = MemoryLogger::new();
+
+        watcher.poll(&logger, &executor(), false).await;
+        memory.put_object("s3://landing/orders/new.csv", "id\n");
+        assert!(watcher.poll(&logger, &executor(), false).await.is_empty());
+        memory.put_object("s3://landing/orders/new.csv", "id\n2\n");
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(watcher.poll(&logger, &executor(), false).await.is_empty());
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(watcher.poll(&logger, &executor(), false).await.len(), 1);
+    }
+}
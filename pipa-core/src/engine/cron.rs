@@ -0,0 +1,259 @@
+//! Cron expressions for contract schedules.
+//!
+//! Standard five-field syntax, evaluated in UTC:
+//!
+//! ```text
+//! ┌──────── minute        0-59
+//! │ ┌────── hour          0-23
+//! │ │ ┌──── day of month  1-31
+//! │ │ │ ┌── month         1-12 or jan-dec
+//! │ │ │ │ ┌ day of week   0-7 or sun-sat (0 and 7 are Sunday)
+//! 0 6 * * mon-fri
+//! ```
+//!
+//! Each field accepts `*`, values, ranges (`1-5`), steps (`*/15`, `0-30/10`)
+//! and comma-separated lists. `@hourly`, `@daily`, `@weekly`, `@monthly`,
+//! and `@yearly` are accepted as shorthands. As in cron, when both day of
+//! month and day of week are restricted, a day matching either fires.
+
+use crate::logging::error::{ValidationError, ValidationResult};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use std::fmt;
+
+/// How far ahead `next_after` searches before giving up (e.g. `0 0 30 2 *`).
+const SEARCH_YEARS: i32 = 5;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether day of month / day of week were restricted (not `*`).
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression.
+    ///
+    /// # Errors
+    /// Returns `ValidationError::Config` describing the offending field.
+    pub fn parse(expression: &str) -> ValidationResult<Self> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(invalid(
+                expression,
+                format!("expected 5 fields, found {}", fields.len()),
+            ));
+        }
+
+        let field = |index: usize, name: &str, min: u32, max: u32, names: &[&str]| {
+            parse_field(fields[index], min, max, names)
+                .map_err(|e| invalid(expression, format!("{}: {}", name, e)))
+        };
+        let mut weekdays = field(4, "day of week", 0, 7, &WEEKDAYS)?;
+        // 7 is an alias for Sunday.
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            minutes: field(0, "minute", 0, 59, &[])?,
+            hours: field(1, "hour", 0, 23, &[])?,
+            days: field(2, "day of month", 1, 31, &[])?,
+            months: field(3, "month", 1, 12, &MONTHS)?,
+            weekdays,
+            days_restricted: fields[2] != "*",
+            weekdays_restricted: fields[4] != "*",
+        })
+    }
+
+    /// The first minute strictly after `after` that matches the schedule.
+    ///
+    /// Returns `None` if nothing matches within the next few years (e.g.
+    /// February 30th).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = after.year() + SEARCH_YEARS;
+
+        while t.year() <= limit {
+            if !has(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.day_matches(&t) {
+                t = Utc
+                    .with_ymd_and_hms(t.year(), t.month(), t.day(), 0, 0, 0)
+                    .single()?
+                    + Duration::days(1);
+            } else if !has(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+            } else if !has(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, t: &DateTime<Utc>) -> bool {
+        let day = has(self.days, t.day());
+        let weekday = has(self.weekdays, t.weekday().num_days_from_sunday());
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn invalid(expression: &str, reason: String) -> ValidationError {
+    ValidationError::Config(format!(
+        "Invalid cron expression '{}': {}",
+        expression, reason
+    ))
+}
+
+/// Parse one field into a bitmask of allowed values.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("step must be at least 1".to_string());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, min, names)?,
+                parse_value(end, min, names)?,
+            )
+        } else {
+            let start = parse_value(range, min, names)?;
+            // `5/15` means "from 5, every 15".
+            (start, if step > 1 { max } else { start })
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("'{}' is outside {}-{}", range, min, max));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(value: &str, min: u32, names: &[&str]) -> Result<u32, String> {
+    if let Some(index) = names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(value))
+    {
+        // Month names start at 1, weekday names at 0.
+        return Ok(index as u32 + min);
+    }
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        CronSchedule::parse(expression)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+            .to_rfc3339()
+    }
+
+    #[test]
+    fn finds_next_matching_minute() {
+        assert_eq!(
+            next("0 6 * * *", "2025-03-10T05:59:30Z"),
+            "2025-03-10T06:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 6 * * *", "2025-03-10T06:00:00Z"),
+            "2025-03-11T06:00:00+00:00"
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2025-03-10T06:01:00Z"),
+            "2025-03-10T06:15:00+00:00"
+        );
+        assert_eq!(
+            next("30 9 * * mon-fri", "2025-03-08T00:00:00Z"),
+            "2025-03-10T09:30:00+00:00"
+        );
+        assert_eq!(
+            next("@monthly", "2025-12-15T00:00:00Z"),
+            "2026-01-01T00:00:00+00:00"
+        );
+        // Day of month or day of week when both are restricted.
+        assert_eq!(
+            next("0 0 13 * fri", "2025-03-01T00:00:00Z"),
+            "2025-03-07T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 * * 7", "2025-03-10T00:00:00Z"),
+            "2025-03-16T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in ["0 6 * *", "60 * * * *", "* * * * mon-xyz", "*/0 * * * *"] {
+            let err = CronSchedule::parse(expression).unwrap_err();
+            assert!(err.is_config(), "{}", expression);
+        }
+        let never = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert_eq!(never.next_after(at("2025-01-01T00:00:00Z")), None);
+    }
+}
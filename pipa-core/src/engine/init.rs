@@ -1,3 +1,4 @@
+use crate::config::contracts_dir;
 use crate::logging::init::init_logging;
 use crate::logging::ledger::ensure_ledger_key_exists;
 use std::error::Error;
@@ -49,7 +50,7 @@ pub fn init_project() -> Result<String, Box<dyn Error>> {
     let mut actions = Vec::new();
 
     // --- Create Directories ---
-    let contracts_dir = Path::new("contracts");
+    let contracts_dir = &contracts_dir();
     fs::create_dir_all(contracts_dir)?;
 
     let data_dir = Path::new("data");
@@ -118,9 +119,9 @@ validation = [
 ]
 
 [[columns]]
-name = "email
+name = "email"
 validation = [
-  { rule = "pattern", pattern = "^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$" }
+  { rule = "pattern", pattern = '^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$' }
 ]
 
 [source]
@@ -0,0 +1,561 @@
+//! Semantic checks on contract TOML (`pipa contract lint`).
+//!
+//! `pipa contract validate` answers "does this parse"; the lint pass
+//! looks for contracts that parse but won't do what their author meant.
+//! Every finding carries a stable code and a suggested fix:
+//!
+//! | Code | Severity | Problem                                                   |
+//! |------|----------|-----------------------------------------------------------|
+//! | L001 | error    | TOML syntax or schema error                               |
+//! | L002 | error    | Unknown rule name                                         |
+//! | L003 | error    | Rule parameters missing or of the wrong type              |
+//! | L004 | error    | Rule declared where the engine doesn't implement it       |
+//! | L005 | error    | `pattern` regex doesn't compile                           |
+//! | L006 | error    | Range with `min` greater than `max`                       |
+//! | L007 | warning  | Rule can't evaluate its column's declared `dtype`         |
+//! | L008 | warning  | Rule or `partition_by` names an undeclared column         |
+//! | L009 | warning  | No `[source]` or `[[sources]]` section                    |
+//! | L010 | error    | `on_success = "archive"` without an `archive_location`    |
+//! | L011 | error    | `filename_template` with an unknown or unterminated token |
+//!
+//! Rule-level problems (L002, L003) are found on the raw TOML, so one bad
+//! rule doesn't hide the others; the remaining checks need the contract
+//! to parse and only run once it does.
+
+use crate::contracts::filename::check_filename_template;
+use crate::contracts::{
+    ContractParseError, ContractType, OnSuccess, SchemaContracts, parse_contract,
+};
+use crate::engine::rules::{catalog, type_mismatches, unrecognized_rules};
+use serde::Serialize;
+use std::fmt;
+
+/// How serious a lint finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// The contract won't run as written.
+    Error,
+    /// The contract runs, but part of it is ignored or suspicious.
+    Warning,
+}
+
+/// A single lint finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintDiagnostic {
+    /// Stable code, `L001`..`L011`.
+    pub code: &'static str,
+    pub severity: LintSeverity,
+    /// Where in the contract: `column 'amount'`, `[file]`, `[source]`, ...
+    pub location: String,
+    pub message: String,
+    /// What to change to fix it.
+    pub suggestion: String,
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.code, self.location, self.message)
+    }
+}
+
+fn diagnostic(
+    code: &'static str,
+    severity: LintSeverity,
+    location: String,
+    message: String,
+    suggestion: String,
+) -> LintDiagnostic {
+    LintDiagnostic {
+        code,
+        severity,
+        location,
+        message,
+        suggestion,
+    }
+}
+
+/// Lint contract TOML, returning findings in contract order.
+pub fn lint_toml(toml_str: &str) -> Vec<LintDiagnostic> {
+    if let Ok(raw) = toml::from_str::<toml::Table>(toml_str) {
+        let rule_findings = lint_raw_rules(&raw);
+        if !rule_findings.is_empty() {
+            return rule_findings;
+        }
+    }
+    match parse_contract(toml_str) {
+        Ok(contracts) => lint_schema(&contracts),
+        Err(error) => vec![parse_diagnostic(error)],
+    }
+}
+
+/// Lint a parsed contract (every check except L001-L003).
+pub fn lint_schema(contracts: &SchemaContracts) -> Vec<LintDiagnostic> {
+    let mut findings = Vec::new();
+
+    for rule in unrecognized_rules(contracts) {
+        let (location, elsewhere) = if rule.column == "file" {
+            ("[file]".to_string(), "under [[columns]]")
+        } else {
+            (format!("column '{}'", rule.column), "under [file]")
+        };
+        let supported = catalog().iter().any(|info| info.name == rule.rule);
+        findings.push(diagnostic(
+            "L004",
+            LintSeverity::Error,
+            location,
+            rule.to_string(),
+            if supported {
+                format!("move `{}` {} or remove it", rule.rule, elsewhere)
+            } else {
+                format!(
+                    "remove `{}`; the engine has no implementation for it",
+                    rule.rule
+                )
+            },
+        ));
+    }
+
+    let size_limit = contracts.limits.as_ref().and_then(|l| l.max_regex_size);
+    for col in &contracts.columns {
+        let location = format!("column '{}'", col.name);
+        for rule in &col.validation {
+            if let ContractType::Pattern { pattern } = rule {
+                let mut builder = regex::RegexBuilder::new(pattern);
+                if let Some(limit) = size_limit {
+                    builder.size_limit(limit);
+                }
+                match builder.build() {
+                    Ok(_) => {}
+                    Err(regex::Error::CompiledTooBig(limit)) => findings.push(diagnostic(
+                        "L005",
+                        LintSeverity::Error,
+                        location.clone(),
+                        format!(
+                            "pattern '{}' compiles to more than max_regex_size ({} bytes)",
+                            pattern, limit
+                        ),
+                        "simplify the pattern or raise `[limits] max_regex_size`".to_string(),
+                    )),
+                    Err(e) => findings.push(diagnostic(
+                        "L005",
+                        LintSeverity::Error,
+                        location.clone(),
+                        format!(
+                            "pattern '{}' doesn't compile: {}",
+                            pattern,
+                            e.to_string().lines().last().unwrap_or_default().trim()
+                        ),
+                        "fix the regex (Rust `regex` syntax; no look-around or backreferences)"
+                            .to_string(),
+                    )),
+                }
+            }
+        }
+        findings.extend(inverted_ranges(&location, &col.validation));
+    }
+    if let Some(file) = &contracts.file {
+        findings.extend(inverted_ranges("[file]", &file.validation));
+    }
+
+    for mismatch in type_mismatches(contracts) {
+        findings.push(diagnostic(
+            "L007",
+            LintSeverity::Warning,
+            format!("column '{}'", mismatch.column),
+            mismatch.to_string(),
+            format!(
+                "declare '{}' with {} dtype or use a rule that accepts {}",
+                mismatch.column,
+                mismatch.expected.as_str(),
+                mismatch.declared
+            ),
+        ));
+    }
+
+    findings.extend(undeclared_columns(contracts));
+
+    if contracts.source.is_none() && contracts.sources.is_none() {
+        findings.push(diagnostic(
+            "L009",
+            LintSeverity::Warning,
+            "[source]".to_string(),
+            "contract has no [source]; `pipa run` has no data to validate".to_string(),
+            "add a [source] section with `type` and `location`".to_string(),
+        ));
+    }
+
+    let sources = contracts
+        .source
+        .iter()
+        .map(|source| ("[source]".to_string(), source))
+        .chain(
+            contracts
+                .sources
+                .iter()
+                .flatten()
+                .map(|named| (format!("[[sources]] '{}'", named.name), &named.source)),
+        );
+    for (location, source) in sources {
+        if source.on_success == OnSuccess::Archive && source.archive_location.is_none() {
+            findings.push(diagnostic(
+                "L010",
+                LintSeverity::Error,
+                location,
+                "on_success = \"archive\" has no archive_location".to_string(),
+                "add `archive_location` or use on_success = \"delete\"".to_string(),
+            ));
+        }
+    }
+
+    let templates = [
+        (
+            "[destination]",
+            contracts
+                .destination
+                .as_ref()
+                .and_then(|d| d.filename_template.as_deref()),
+        ),
+        (
+            "[quarantine]",
+            contracts
+                .quarantine
+                .as_ref()
+                .and_then(|q| q.filename_template.as_deref()),
+        ),
+    ];
+    for (location, template) in templates {
+        if let Some(Err(e)) = template.map(check_filename_template) {
+            findings.push(diagnostic(
+                "L011",
+                LintSeverity::Error,
+                location.to_string(),
+                e,
+                "use only {stem}, {ext}, {date}, {time}, {contract}, {uuid}, and {status}"
+                    .to_string(),
+            ));
+        }
+    }
+
+    findings
+}
+
+/// The L001 finding for TOML that doesn't parse.
+fn parse_diagnostic(error: ContractParseError) -> LintDiagnostic {
+    let location = match (error.line, error.column) {
+        (Some(line), Some(column)) => format!("line {}, column {}", line, column),
+        _ => "contract".to_string(),
+    };
+    let suggestion = match &error.field {
+        Some(field) => format!("fix `{}`", field),
+        None => "fix the TOML syntax".to_string(),
+    };
+    diagnostic(
+        "L001",
+        LintSeverity::Error,
+        location,
+        error.message,
+        suggestion,
+    )
+}
+
+/// L002/L003: rules whose name is unknown or whose parameters don't parse.
+fn lint_raw_rules(raw: &toml::Table) -> Vec<LintDiagnostic> {
+    let mut findings = Vec::new();
+
+    let columns = raw.get("columns").and_then(|c| c.as_array());
+    for (i, col) in columns.into_iter().flatten().enumerate() {
+        let location = match col.get("name").and_then(|n| n.as_str()) {
+            Some(name) => format!("column '{}'", name),
+            None => format!("columns[{}]", i),
+        };
+        let rules = col.get("validation").and_then(|v| v.as_array());
+        for rule in rules.into_iter().flatten() {
+            findings.extend(lint_raw_rule(&location, rule));
+        }
+    }
+
+    let file_rules = raw
+        .get("file")
+        .and_then(|f| f.get("validation"))
+        .and_then(|v| v.as_array());
+    for rule in file_rules.into_iter().flatten() {
+        findings.extend(lint_raw_rule("[file]", rule));
+    }
+
+    findings
+}
+
+fn lint_raw_rule(location: &str, rule: &toml::Value) -> Option<LintDiagnostic> {
+    let error = rule.clone().try_into::<ContractType>().err()?;
+    let Some(name) = rule.get("rule").and_then(|r| r.as_str()) else {
+        return Some(diagnostic(
+            "L002",
+            LintSeverity::Error,
+            location.to_string(),
+            "validation entry has no `rule` name".to_string(),
+            "add `rule = \"...\"`; see `pipa rules list`".to_string(),
+        ));
+    };
+
+    if error.message().starts_with("unknown variant") {
+        let suggestion = match closest_rule(name) {
+            Some(close) => format!("did you mean `{}`?", close),
+            None => "see `pipa rules list` for supported rules".to_string(),
+        };
+        return Some(diagnostic(
+            "L002",
+            LintSeverity::Error,
+            location.to_string(),
+            format!("unknown rule '{}'", name),
+            suggestion,
+        ));
+    }
+
+    Some(diagnostic(
+        "L003",
+        LintSeverity::Error,
+        location.to_string(),
+        format!("rule '{}': {}", name, error.message().trim()),
+        format!("see `pipa rules describe {}` for its parameters", name),
+    ))
+}
+
+/// L006: range-style rules whose lower bound is above the upper bound.
+fn inverted_ranges(location: &str, rules: &[ContractType]) -> Vec<LintDiagnostic> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let (min, max) = match rule {
+                ContractType::Range { min, max }
+                | ContractType::MinBetween { min, max }
+                | ContractType::MaxBetween { min, max } => (*min as f64, *max as f64),
+                ContractType::MeanBetween { min, max }
+                | ContractType::StdevBetween { min, max } => (*min, *max),
+                ContractType::RowCount {
+                    min,
+                    max: Some(max),
+                } => (*min as f64, *max as f64),
+                _ => return None,
+            };
+            (min > max).then(|| {
+                diagnostic(
+                    "L006",
+                    LintSeverity::Error,
+                    location.to_string(),
+                    format!(
+                        "rule '{}' has min ({}) greater than max ({}); no value can pass",
+                        rule.name(),
+                        min,
+                        max
+                    ),
+                    "swap `min` and `max`".to_string(),
+                )
+            })
+        })
+        .collect()
+}
+
+/// L008: `compound_unique` and `expected_columns` entries that aren't
+/// declared under `[[columns]]`. Skipped when no columns are declared.
+fn undeclared_columns(contracts: &SchemaContracts) -> Vec<LintDiagnostic> {
+    let declared: Vec<&str> = contracts.columns.iter().map(|c| c.name.as_str()).collect();
+    if declared.is_empty() {
+        return Vec::new();
+    }
+
+    let compound = contracts
+        .compound_unique
+        .iter()
+        .flatten()
+        .flat_map(|rule| rule.columns.iter().map(|c| ("[[compound_unique]]", c)));
+    let expected = contracts
+        .file
+        .iter()
+        .flat_map(|file| &file.validation)
+        .filter_map(|rule| match rule {
+            ContractType::ExpectedColumns { columns, .. } => Some(columns),
+            _ => None,
+        })
+        .flatten()
+        .map(|c| ("[file] expected_columns", c));
+    let partitions = contracts
+        .destination
+        .iter()
+        .flat_map(|d| &d.partition_by)
+        .map(|c| ("[destination] partition_by", c));
+
+    compound
+        .chain(expected)
+        .chain(partitions)
+        .filter(|(_, column)| !declared.contains(&column.as_str()))
+        .map(|(location, column)| {
+            let suggestion = match closest(column, declared.iter().copied()) {
+                Some(close) => format!("did you mean '{}'?", close),
+                None => format!("declare a [[columns]] entry named '{}'", column),
+            };
+            diagnostic(
+                "L008",
+                LintSeverity::Warning,
+                location.to_string(),
+                format!("column '{}' is not declared under [[columns]]", column),
+                suggestion,
+            )
+        })
+        .collect()
+}
+
+/// The catalog rule name closest to `name`, if any is close.
+fn closest_rule(name: &str) -> Option<&'static str> {
+    closest(name, catalog().into_iter().map(|info| info.name))
+}
+
+/// The candidate within a small edit distance of `name`, if any.
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let threshold = name.chars().count().div_ceil(3);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(current)
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = r#"
+[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[source]
+type = "local"
+location = "data/orders.csv"
+"#;
+
+    fn codes(findings: &[LintDiagnostic]) -> Vec<&'static str> {
+        findings.iter().map(|f| f.code).collect()
+    }
+
+    #[test]
+    fn clean_contract_has_no_findings() {
+        let toml = format!(
+            "{}\n[[columns]]\nname = \"id\"\ndtype = \"Int64\"\n\
+             validation = [{{ rule = \"not_null\" }}, {{ rule = \"range\", min = 0, max = 9 }}]\n",
+            HEADER
+        );
+        assert!(lint_toml(&toml).is_empty());
+    }
+
+    #[test]
+    fn unknown_rules_and_bad_params_are_reported_together() {
+        let toml = format!(
+            "{}\n[[columns]]\nname = \"id\"\n\
+             validation = [{{ rule = \"not_nul\" }}, {{ rule = \"range\", min = \"zero\", max = 9 }}]\n",
+            HEADER
+        );
+        let findings = lint_toml(&toml);
+        assert_eq!(codes(&findings), vec!["L002", "L003"]);
+        assert_eq!(findings[0].suggestion, "did you mean `not_null`?");
+        assert_eq!(findings[1].location, "column 'id'");
+    }
+
+    #[test]
+    fn semantic_problems_each_get_a_code() {
+        let toml = r#"
+[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[file]
+validation = [{ rule = "row_count", min = 10, max = 1 }, { rule = "exists" }]
+
+[[columns]]
+name = "code"
+dtype = "Float64"
+validation = [{ rule = "pattern", pattern = "([a-z]" }, { rule = "mean_between", min = 5.0, max = 1.0 }]
+
+[[columns]]
+name = "label"
+dtype = "Float64"
+validation = [{ rule = "pattern", pattern = "^[a-z]+$" }]
+
+[[compound_unique]]
+columns = ["code", "lable"]
+"#;
+        let findings = lint_toml(toml);
+        assert_eq!(
+            codes(&findings),
+            vec![
+                "L004", "L005", "L006", "L006", "L007", "L007", "L008", "L009"
+            ]
+        );
+        let undeclared = &findings[6];
+        assert_eq!(undeclared.suggestion, "did you mean 'label'?");
+        assert_eq!(undeclared.severity, LintSeverity::Warning);
+    }
+
+    #[test]
+    fn archive_without_location_is_l010() {
+        let toml = format!(
+            "{}\n[[columns]]\nname = \"id\"\nvalidation = []\n",
+            HEADER.replace(
+                "location = \"data/orders.csv\"",
+                "location = \"data/orders.csv\"\non_success = \"archive\"",
+            )
+        );
+        let findings = lint_toml(&toml);
+        assert_eq!(codes(&findings), vec!["L010"]);
+        assert_eq!(findings[0].location, "[source]");
+    }
+
+    #[test]
+    fn unknown_filename_token_is_l011() {
+        let toml = format!(
+            "{}\n[[columns]]\nname = \"id\"\nvalidation = []\n\n\
+             [destination]\ntype = \"local\"\nlocation = \"clean/\"\n\
+             filename_template = \"{{stem}}_{{batch}}.{{ext}}\"\n",
+            HEADER
+        );
+        let findings = lint_toml(&toml);
+        assert_eq!(codes(&findings), vec!["L011"]);
+        assert_eq!(findings[0].location, "[destination]");
+    }
+
+    #[test]
+    fn syntax_errors_are_l001() {
+        let findings = lint_toml("[contract\nname = 1\n");
+        assert_eq!(codes(&findings), vec!["L001"]);
+        assert!(findings[0].location.starts_with("line 1"));
+    }
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("not_nul", "not_null"), 1);
+        assert_eq!(edit_distance("range", "range"), 0);
+        assert_eq!(edit_distance("abc", "xyz"), 3);
+    }
+}
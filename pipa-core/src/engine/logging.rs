@@ -40,6 +40,7 @@ pub fn log_action<L: AuditLogger>(
         timestamp: Utc::now().to_rfc3339(),
         level: "AUDIT",
         event,
+        run_id: None,
         contract: contract.map(|name| crate::logging::schema::Contract {
             name,
             version: version.unwrap_or("N/A"),
@@ -60,13 +61,57 @@ pub fn log_action<L: AuditLogger>(
     // Return PII-safe message based on event type
     match event {
         "contracts_listed" => "📋 Contracts listed".to_string(),
+        "contracts_selected" => match details.and_then(|d| d.split("selected=").nth(1)) {
+            Some(count) => format!("🏷️  {} contracts selected by tag", count),
+            None => "🏷️  Contracts selected by tag".to_string(),
+        },
         "contract_retrieved" => {
             format!("📄 Contract '{}' retrieved", contract.unwrap_or("unknown"))
         }
-        "contract_validated" => {
-            format!("✅ Contract '{}' validated", contract.unwrap_or("unknown"))
+        "contract_generated" => {
+            format!(
+                "📝 Draft contract '{}' generated",
+                contract.unwrap_or("unknown")
+            )
         }
+        "contract_diffed" => {
+            format!(
+                "🔀 Compared versions of contract '{}'",
+                contract.unwrap_or("unknown")
+            )
+        }
+        "runs_diffed" => {
+            format!("🔀 Compared runs of '{}'", contract.unwrap_or("unknown"))
+        }
+        "baseline_set" => {
+            format!("📌 Baseline pinned for '{}'", contract.unwrap_or("unknown"))
+        }
+        "contract_validated" => match details {
+            Some(detail) if detail.starts_with("error=") => {
+                format!("❌ Contract '{}' is invalid", contract.unwrap_or("unknown"))
+            }
+            _ => format!("✅ Contract '{}' validated", contract.unwrap_or("unknown")),
+        },
+        "contract_checked" => {
+            format!(
+                "🧪 Contract '{}' checked against its source",
+                contract.unwrap_or("unknown")
+            )
+        }
+        "contract_linted" => match details {
+            Some("errors=0, warnings=0") => {
+                format!(
+                    "✅ Contract '{}' has no lint findings",
+                    contract.unwrap_or("unknown")
+                )
+            }
+            _ => format!("🔎 Contract '{}' linted", contract.unwrap_or("unknown")),
+        },
+        "rule_type_mismatch" => format!("⚠️  {}", details.unwrap_or("rule/dtype mismatch")),
         "profiles_listed" => "👤 Profiles listed".to_string(),
+        "profile_added" => format!("➕ Profile '{}' added", target.unwrap_or("unknown")),
+        "profile_updated" => format!("✏️  Profile '{}' updated", target.unwrap_or("unknown")),
+        "profile_removed" => format!("🗑️  Profile '{}' removed", target.unwrap_or("unknown")),
         "profile_tested" => {
             if let Some(detail) = details {
                 if detail.contains("connected=true") {
@@ -102,7 +147,123 @@ pub fn log_action<L: AuditLogger>(
             "✅ Validation completed for '{}'",
             contract.unwrap_or("unknown")
         ),
+        "logs_sealed" => match details.and_then(|d| d.strip_prefix("sealed=")) {
+            Some(rest) => format!(
+                "🔒 Sealed {} daily logs",
+                rest.split(',').next().unwrap_or("0")
+            ),
+            None => "🔒 Sealed daily logs".to_string(),
+        },
+        "logs_pruned" => {
+            let field = |key: &str| {
+                details
+                    .and_then(|d| d.split(", ").find_map(|kv| kv.strip_prefix(key)))
+                    .unwrap_or("?")
+            };
+            let verb = if field("dry_run=") == "true" {
+                "Would prune"
+            } else {
+                "Pruned"
+            };
+            format!(
+                "🗑️  {} {} daily logs older than {} days",
+                verb,
+                field("pruned="),
+                field("keep_days=")
+            )
+        }
+        "logs_shipped" => match details.and_then(|d| d.strip_prefix("shipped=")) {
+            Some(rest) => format!(
+                "📦 Shipped {} sealed log files",
+                rest.split(',').next().unwrap_or("0")
+            ),
+            None => "📦 Shipped sealed log files".to_string(),
+        },
+        "logs_exported" => {
+            match details.and_then(|d| d.split(", ").find_map(|kv| kv.strip_prefix("rows="))) {
+                Some(rows) => format!(
+                    "📤 Exported {} audit rows to {}",
+                    rows,
+                    target.unwrap_or("unknown")
+                ),
+                None => format!("📤 Exported audit logs to {}", target.unwrap_or("unknown")),
+            }
+        }
+        "ledger_key_rotated" => {
+            match details.and_then(|d| d.split(", ").find_map(|kv| kv.strip_prefix("provider="))) {
+                Some(provider) => format!("🔑 Rotated the ledger key ({})", provider),
+                None => "🔑 Rotated the ledger key".to_string(),
+            }
+        }
         "health_check" => "🏥 Health check completed".to_string(),
+        "data_profiled" => format!("📊 Profiled '{}'", target.unwrap_or("unknown")),
+        "watch_started" => match details
+            .and_then(|d| d.split(',').next())
+            .and_then(|d| d.strip_prefix("contracts="))
+        {
+            Some(count) => format!("👀 Watching {} contracts", count),
+            None => "👀 Watching contracts".to_string(),
+        },
+        "watch_triggered" => format!(
+            "📥 New file for '{}': {}",
+            contract.unwrap_or("unknown"),
+            target.unwrap_or("unknown")
+        ),
+        "watch_error" => format!(
+            "⚠️  Watch error for '{}': {}",
+            contract.unwrap_or("unknown"),
+            details.unwrap_or("unknown error")
+        ),
+        "watch_stopped" => "👋 Watch stopped".to_string(),
+        "quarantine_revalidated" => {
+            let target = target.unwrap_or("unknown");
+            match details {
+                Some(d) if d.contains("released=true") => {
+                    format!("♻️  Released {} from quarantine", target)
+                }
+                Some(d) if d.contains("passed=true") => {
+                    format!("✅ {} now passes; left in quarantine", target)
+                }
+                _ => format!("⚠️  {} still fails validation; left in quarantine", target),
+            }
+        }
+        "source_deleted" => format!("🗑️  Deleted source {}", target.unwrap_or("unknown")),
+        "source_archived" => match details.and_then(|d| d.strip_prefix("archived_to=")) {
+            Some(to) => format!(
+                "📦 Archived source {} to {}",
+                target.unwrap_or("unknown"),
+                to
+            ),
+            None => format!("📦 Archived source {}", target.unwrap_or("unknown")),
+        },
+        "source_cleanup_error" => format!(
+            "⚠️  Source {} was kept: {}",
+            target.unwrap_or("unknown"),
+            details.unwrap_or("unknown error")
+        ),
+        "scheduler_started" => match details.and_then(|d| d.strip_prefix("contracts=")) {
+            Some(count) => format!("⏰ Scheduler started with {} contracts", count),
+            None => "⏰ Scheduler started".to_string(),
+        },
+        "schedule_triggered" => format!(
+            "⏰ Scheduled run of '{}' triggered",
+            contract.unwrap_or("unknown")
+        ),
+        "scheduler_stopped" => "👋 Scheduler stopped".to_string(),
+        "notification_sent" => format!(
+            "📣 Notified {} about '{}'",
+            details
+                .and_then(|d| d.strip_prefix("channel="))
+                .and_then(|d| d.split(',').next())
+                .unwrap_or("channel"),
+            contract.unwrap_or("unknown")
+        ),
+        "notification_failed" => {
+            format!(
+                "⚠️  Notification failed: {}",
+                details.unwrap_or("unknown error")
+            )
+        }
         _ => format!("📝 Action: {}", event),
     }
 }
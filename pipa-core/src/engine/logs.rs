@@ -1,10 +1,23 @@
-//! Log verification functions for the engine
+//! Log verification, sealing, pruning, shipping, export, and key rotation functions for the engine
 
+use crate::config::{PROJECT_FILE, load_project_config, logs_dir};
 use crate::engine::log_action;
 use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::logging::export::{LogExport, LogFormat, audit_table};
+use crate::logging::ledger;
+use crate::logging::retention::{self, LogPruning, RetentionPolicy};
+use crate::logging::sinks::{LogSink, ShippedFile, run_detached, ship_sealed_logs};
 use crate::logging::verify::{FileVerification, verify_all, verify_date};
+use crate::logging::writer;
+use crate::movement::FileMovement;
+use crate::profiles::{PROFILES_FILE, Profiles, load_profiles};
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use std::path::Path;
 
 /// Result of log verification
+#[derive(Serialize)]
 pub struct LogVerification {
     pub valid: bool,
     pub verified: usize,
@@ -12,9 +25,32 @@ pub struct LogVerification {
     pub missing: usize,
     pub malformed: usize,
     pub unsealed: usize,
+    /// Files with an entry that doesn't chain to the one before it.
+    pub chain_broken: usize,
+    /// Verified files whose hash was timestamped by a notary.
+    pub timestamped: usize,
+    /// Sealed files since removed by the retention policy.
+    pub pruned: usize,
     pub files: Vec<FileVerification>,
 }
 
+impl LogVerification {
+    /// Whether any sealed log fails verification: altered, missing, with a
+    /// malformed ledger entry, or with a broken chain. Unsealed logs aren't
+    /// failures, since today's log is always unsealed.
+    pub fn has_failures(&self) -> bool {
+        self.mismatched > 0 || self.missing > 0 || self.malformed > 0 || self.chain_broken > 0
+    }
+}
+
+/// Result of a ledger key rotation
+pub struct KeyRotation {
+    /// Provider now holding the key, e.g. `local` or `aws_kms`.
+    pub provider: String,
+    /// Ledger entries re-encrypted.
+    pub entries: usize,
+}
+
 /// Verify logs for a specific date or all logs
 pub fn verify_logs<L: AuditLogger>(logger: &L, date: Option<&str>) -> (LogVerification, String) {
     let summary = if let Some(date) = date {
@@ -26,16 +62,20 @@ pub fn verify_logs<L: AuditLogger>(logger: &L, date: Option<&str>) -> (LogVerifi
     let all_valid = summary.mismatched == 0
         && summary.missing == 0
         && summary.malformed == 0
-        && summary.unsealed == 0;
+        && summary.unsealed == 0
+        && summary.chain_broken == 0;
 
     let details = format!(
-        "valid={}, verified={}, mismatched={}, missing={}, malformed={}, unsealed={}",
+        "valid={}, verified={}, mismatched={}, missing={}, malformed={}, unsealed={}, chain_broken={}, timestamped={}, pruned={}",
         all_valid,
         summary.verified,
         summary.mismatched,
         summary.missing,
         summary.malformed,
-        summary.unsealed
+        summary.unsealed,
+        summary.chain_broken,
+        summary.timestamped,
+        summary.pruned
     );
 
     let message = log_action(logger, "logs_verified", Some(&details), None, None, None);
@@ -48,8 +88,250 @@ pub fn verify_logs<L: AuditLogger>(logger: &L, date: Option<&str>) -> (LogVerifi
             missing: summary.missing,
             malformed: summary.malformed,
             unsealed: summary.unsealed,
+            chain_broken: summary.chain_broken,
+            timestamped: summary.timestamped,
+            pruned: summary.pruned,
             files: summary.files,
         },
         message,
     )
 }
+
+/// Result of sealing daily logs
+pub struct LogSealing {
+    /// Files sealed by this call.
+    pub sealed: Vec<String>,
+    /// The requested file, if it had been sealed already.
+    pub already_sealed: Option<String>,
+    /// Uploads of the newly sealed files to the storage `[[log_sinks]]`.
+    pub shipped: Vec<ShippedFile>,
+}
+
+/// Seal the daily log for `date`, or every unsealed log older than today,
+/// into the hash ledger (notarized per `[notarization]` in `pipa.toml`),
+/// and ship the sealed files to the storage `[[log_sinks]]`.
+///
+/// Logs are otherwise sealed by the first entry written on a later day;
+/// sealing explicitly closes a day without waiting for the next run.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if `date` is today or later (its log
+/// is still being written) or has no log, or `pipa.toml` can't be loaded.
+pub fn seal_logs<L: AuditLogger>(
+    logger: &L,
+    date: Option<NaiveDate>,
+) -> ValidationResult<(LogSealing, String)> {
+    let logs_dir = &logs_dir();
+    let filename = match date {
+        Some(date) if date >= Utc::now().date_naive() => {
+            return Err(ValidationError::Config(format!(
+                "The log for {} is still open; only earlier days can be sealed",
+                date
+            )));
+        }
+        Some(date) => {
+            let filename = format!("audit-{}.jsonl", date);
+            if !logs_dir.join(&filename).is_file() {
+                return Err(ValidationError::Config(format!(
+                    "No audit log for {}",
+                    date
+                )));
+            }
+            Some(filename)
+        }
+        None => None,
+    };
+    let project = load_project_config(Path::new(PROJECT_FILE))?;
+
+    let sealed = writer::seal_logs(logs_dir, filename.as_deref(), project.notarization.as_ref());
+    let already_sealed = filename.clone().filter(|_| sealed.is_empty());
+    let shipped = if !sealed.is_empty() && !project.log_sinks.iter().all(LogSink::is_streaming) {
+        let profiles = load_profiles().unwrap_or_default();
+        run_detached(ship_sealed_logs(logs_dir, &project.log_sinks, &profiles))
+    } else {
+        Vec::new()
+    };
+    let details = format!(
+        "sealed={}, already_sealed={}",
+        sealed.len(),
+        usize::from(already_sealed.is_some())
+    );
+    let message = log_action(
+        logger,
+        "logs_sealed",
+        Some(&details),
+        None,
+        None,
+        filename.as_deref(),
+    );
+    Ok((
+        LogSealing {
+            sealed,
+            already_sealed,
+            shipped,
+        },
+        message,
+    ))
+}
+
+/// Remove sealed daily logs older than the `[retention]` policy in
+/// `pipa.toml` keeps (`keep_days`, if given, overrides its `keep_days`),
+/// deleting or archiving them and recording each in the ledger; with
+/// `dry_run`, only report what would be removed.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if no retention period is configured
+/// or given, `pipa.toml` can't be loaded, or an archived file would
+/// overwrite one already in the archive.
+pub fn prune_logs<L: AuditLogger>(
+    logger: &L,
+    keep_days: Option<u32>,
+    dry_run: bool,
+) -> ValidationResult<(LogPruning, String)> {
+    let project = load_project_config(Path::new(PROJECT_FILE))?;
+    let policy = match (project.retention, keep_days) {
+        (Some(policy), Some(keep_days)) => RetentionPolicy {
+            keep_days,
+            ..policy
+        },
+        (Some(policy), None) => policy,
+        (None, Some(keep_days)) => RetentionPolicy {
+            keep_days,
+            archive_dir: None,
+        },
+        (None, None) => {
+            return Err(ValidationError::Config(format!(
+                "No retention period: set [retention] keep_days in {} or pass --keep-days",
+                PROJECT_FILE
+            )));
+        }
+    };
+
+    let pruning = retention::prune_logs(&logs_dir(), &policy, Utc::now().date_naive(), dry_run)?;
+    let archived = pruning
+        .pruned
+        .iter()
+        .filter(|p| p.archived_to.is_some())
+        .count();
+    let details = format!(
+        "pruned={}, archived={}, unsealed={}, mismatched={}, keep_days={}, dry_run={}",
+        pruning.pruned.len(),
+        archived,
+        pruning.unsealed.len(),
+        pruning.mismatched.len(),
+        policy.keep_days,
+        dry_run
+    );
+    let message = log_action(logger, "logs_pruned", Some(&details), None, None, None);
+    Ok((pruning, message))
+}
+
+/// Upload sealed daily files not yet shipped to the storage `[[log_sinks]]`
+/// in `pipa.toml`, e.g. after a sink was down when the file was sealed.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if `pipa.toml` or `profiles.toml`
+/// can't be loaded.
+pub async fn ship_logs<L: AuditLogger>(logger: &L) -> ValidationResult<(Vec<ShippedFile>, String)> {
+    let project = load_project_config(Path::new(PROJECT_FILE))?;
+    let profiles = if Path::new(PROFILES_FILE).exists() {
+        load_profiles()
+            .map_err(|e| ValidationError::Config(format!("Failed to load profiles.toml: {}", e)))?
+    } else {
+        Profiles::new()
+    };
+
+    let shipped = ship_sealed_logs(&logs_dir(), &project.log_sinks, &profiles).await;
+    let failed = shipped.iter().filter(|f| f.error.is_some()).count();
+    let details = format!("shipped={}, failed={}", shipped.len() - failed, failed);
+    let message = log_action(logger, "logs_shipped", Some(&details), None, None, None);
+    Ok((shipped, message))
+}
+
+/// Write the audit entries of the daily files dated `from..=to` (either end
+/// open) to `output` as one flat table, a row per rule result. With
+/// `run_id`, only that run's entries are written.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if `from` is after `to`, and
+/// `ValidationError::MovementFailed` if the table can't be written.
+pub fn export_logs<L: AuditLogger>(
+    logger: &L,
+    format: LogFormat,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    run_id: Option<&str>,
+    output: &Path,
+) -> ValidationResult<(LogExport, String)> {
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return Err(ValidationError::Config(format!(
+            "--from {} is after --to {}",
+            from, to
+        )));
+    }
+    let (df, export) = audit_table(&logs_dir(), from, to, run_id)?;
+    let target = output.display().to_string();
+    let data = FileMovement::serialize_dataframe(&df, format.name()).map_err(|e| {
+        ValidationError::MovementFailed {
+            target: target.clone(),
+            cause: e.to_string(),
+        }
+    })?;
+    std::fs::write(output, data).map_err(|e| ValidationError::MovementFailed {
+        target: target.clone(),
+        cause: e.to_string(),
+    })?;
+
+    let details = format!(
+        "format={}, files={}, entries={}, rows={}, malformed={}",
+        format.name(),
+        export.files,
+        export.entries,
+        export.rows,
+        export.malformed
+    );
+    let message = log_action(
+        logger,
+        "logs_exported",
+        Some(&details),
+        None,
+        None,
+        Some(&target),
+    );
+    Ok((export, message))
+}
+
+/// Re-encrypt the hash ledger under a new key, stored through the
+/// `[ledger_key]` provider in `pipa.toml` (a local key file by default).
+///
+/// Run after adding `[ledger_key]` to move an existing local key into the
+/// KMS, or on whatever schedule key rotation policy requires.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if `pipa.toml` can't be loaded, and
+/// `ValidationError::Anyhow` if the new key can't be stored.
+pub fn rotate_ledger_key<L: AuditLogger>(logger: &L) -> ValidationResult<(KeyRotation, String)> {
+    let provider = load_project_config(Path::new(PROJECT_FILE))?
+        .ledger_key
+        .unwrap_or_default();
+    let entries = ledger::rotate_ledger_key(&provider)?;
+    let rotation = KeyRotation {
+        provider: provider.name().to_string(),
+        entries,
+    };
+    let details = format!(
+        "provider={}, entries={}",
+        rotation.provider, rotation.entries
+    );
+    let message = log_action(
+        logger,
+        "ledger_key_rotated",
+        Some(&details),
+        None,
+        None,
+        None,
+    );
+    Ok((rotation, message))
+}
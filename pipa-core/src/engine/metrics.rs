@@ -0,0 +1,319 @@
+//! Single-pass rule metrics.
+//!
+//! Most column rules reduce a column to one or two numbers: a null count, a
+//! distinct count, a mean, the number of values outside a range or set.
+//! `RuleMetrics::compute` builds an aggregate expression for each of those
+//! numbers across the whole contract and evaluates them in one Polars lazy
+//! `select`, so the DataFrame is scanned once instead of once per rule. The
+//! numbers are turned into reports by the validators' own `report` helpers,
+//! so results match running each validator on its own.
+//!
+//! Rules with no aggregate form (`boolean`, `date_format`, `type`, `drift`,
+//! `wasm`, custom rules), rules on missing columns or on types the validator
+//! would skip, and sampled rules are left to their validators.
+
+use crate::contracts::ContractType;
+use crate::engine::plan::ExecutionPlan;
+use crate::logging::error::ValidationResult;
+use crate::validators::ValidationReport;
+use crate::validators::column::{
+    CompletenessValidator, DistinctnessValidator, InSetValidator, MaxLengthValidator,
+    MeanBetweenValidator, NotInSetValidator, NotNullValidator, OutlierSigmaValidator,
+    PatternValidator, RangeValidator, StdevBetweenValidator, UniqueValidator,
+};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// Turns a rule's metric values into its report.
+type Finish = Box<dyn Fn(&[Option<f64>]) -> ValidationReport>;
+
+/// Reports for rules computed in the single pass, by column and rule index.
+#[derive(Default)]
+pub(crate) struct RuleMetrics {
+    reports: HashMap<(usize, usize), ValidationReport>,
+}
+
+impl RuleMetrics {
+    /// Compute every supported column rule of `plan` in one query over `df`.
+    ///
+    /// Rules for which `skip` returns true are left out.
+    pub(crate) fn compute(
+        df: &DataFrame,
+        plan: &ExecutionPlan,
+        skip: impl Fn(&ContractType) -> bool,
+    ) -> ValidationResult<Self> {
+        let total = df.height();
+        let mut exprs: Vec<Expr> = Vec::new();
+        let mut pending: Vec<((usize, usize), usize, Finish)> = Vec::new();
+
+        for (col_idx, column) in plan.contracts.columns.iter().enumerate() {
+            let Ok(series) = df.column(&column.name) else {
+                continue;
+            };
+            for (rule_idx, rule) in column.validation.iter().enumerate() {
+                if skip(rule) {
+                    continue;
+                }
+                let Some((metrics, finish)) =
+                    rule_metrics(rule, col(column.name.as_str()), series.dtype(), plan, total)
+                else {
+                    continue;
+                };
+                let first = exprs.len();
+                for (i, metric) in metrics.into_iter().enumerate() {
+                    exprs.push(metric.alias(format!("m{}", first + i)));
+                }
+                pending.push(((col_idx, rule_idx), first, finish));
+            }
+        }
+
+        if exprs.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let out = df.clone().lazy().select(exprs).collect()?;
+        let values = out
+            .get_columns()
+            .iter()
+            .map(|c| Ok(c.cast(&DataType::Float64)?.f64()?.get(0)))
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let reports = pending
+            .into_iter()
+            .map(|(key, first, finish)| (key, finish(&values[first..])))
+            .collect();
+        Ok(Self { reports })
+    }
+
+    /// Take the report for rule `rule_idx` of column `col_idx`, if computed.
+    pub(crate) fn take(&mut self, col_idx: usize, rule_idx: usize) -> Option<ValidationReport> {
+        self.reports.remove(&(col_idx, rule_idx))
+    }
+}
+
+/// The aggregates a rule needs and how to report on them, or `None` if the
+/// rule is left to its validator.
+fn rule_metrics(
+    rule: &ContractType,
+    column: Expr,
+    dtype: &DataType,
+    plan: &ExecutionPlan,
+    total: usize,
+) -> Option<(Vec<Expr>, Finish)> {
+    let numeric = dtype.is_primitive_numeric();
+    let string = dtype.is_string();
+
+    let metrics: (Vec<Expr>, Finish) = match rule {
+        ContractType::NotNull => (
+            vec![column.null_count()],
+            Box::new(|v| NotNullValidator.report(count(v, 0))),
+        ),
+        ContractType::Completeness { min_ratio } => {
+            let validator = CompletenessValidator {
+                min_ratio: *min_ratio,
+            };
+            (
+                vec![column.null_count()],
+                Box::new(move |v| validator.report(count(v, 0), total)),
+            )
+        }
+        ContractType::Unique => (
+            vec![column.n_unique()],
+            Box::new(move |v| UniqueValidator.report(count(v, 0), total)),
+        ),
+        ContractType::Distinctness { min_ratio } => {
+            let validator = DistinctnessValidator {
+                min_ratio: *min_ratio,
+            };
+            (
+                vec![column.n_unique()],
+                Box::new(move |v| validator.report(count(v, 0), total)),
+            )
+        }
+        ContractType::Range { min, max } if *dtype == DataType::Int64 => {
+            let outside = column.clone().lt(lit(*min)).or(column.gt(lit(*max)));
+            let validator = RangeValidator {
+                min: *min,
+                max: *max,
+            };
+            (
+                vec![outside.sum()],
+                Box::new(move |v| validator.report(count(v, 0))),
+            )
+        }
+        ContractType::MaxLength { value } if string => {
+            let too_long = column.str().len_chars().gt(lit(*value as u32));
+            let validator = MaxLengthValidator { value: *value };
+            (
+                vec![too_long.sum()],
+                Box::new(move |v| validator.report(count(v, 0))),
+            )
+        }
+        ContractType::InSet { values } if string => {
+            let allowed = lit(Series::new("allowed".into(), values.clone())).implode();
+            let validator = InSetValidator {
+                values: values.iter().cloned().collect(),
+            };
+            (
+                vec![column.is_in(allowed, false).not().sum()],
+                Box::new(move |v| validator.report(count(v, 0))),
+            )
+        }
+        ContractType::NotInSet { values } if string => {
+            let disallowed = lit(Series::new("disallowed".into(), values.clone())).implode();
+            let validator = NotInSetValidator {
+                values: values.iter().cloned().collect(),
+            };
+            (
+                vec![column.is_in(disallowed, false).sum()],
+                Box::new(move |v| validator.report(count(v, 0))),
+            )
+        }
+        ContractType::Pattern { pattern } if string => {
+            let re = plan.pattern(pattern)?.clone();
+            let validator = PatternValidator {
+                pattern: pattern.clone(),
+                size_limit: plan
+                    .contracts
+                    .limits
+                    .as_ref()
+                    .and_then(|l| l.max_regex_size),
+                compiled: Some(re.clone()),
+            };
+            // Matched in Polars' string kernel; null values stay null and
+            // don't fail a pattern match.
+            let mismatched = column
+                .str()
+                .contains(lit(re.as_str().to_string()), true)
+                .not();
+            (
+                vec![mismatched.sum()],
+                Box::new(move |v| validator.report(count(v, 0))),
+            )
+        }
+        ContractType::MeanBetween { min, max } if numeric => {
+            let validator = MeanBetweenValidator {
+                min: *min,
+                max: *max,
+            };
+            (
+                vec![column.cast(DataType::Float64).mean()],
+                Box::new(move |v| validator.report(v[0])),
+            )
+        }
+        ContractType::StdevBetween { min, max } if numeric => {
+            let validator = StdevBetweenValidator {
+                min: *min,
+                max: *max,
+            };
+            (
+                vec![column.cast(DataType::Float64).std(1)],
+                Box::new(move |v| validator.report(v[0])),
+            )
+        }
+        ContractType::OutlierSigma { sigma } if numeric => {
+            let values = column.cast(DataType::Float64);
+            let outliers = (values.clone() - values.clone().mean())
+                .abs()
+                .gt(lit(*sigma) * values.clone().std(1))
+                .sum();
+            let validator = OutlierSigmaValidator { sigma: *sigma };
+            (
+                vec![values.clone().mean(), values.std(1), outliers],
+                Box::new(move |v| match validator.bounds(v[0], v[1]) {
+                    Ok(_) => validator.report(count(v, 2)),
+                    Err(report) => report,
+                }),
+            )
+        }
+        _ => return None,
+    };
+    Some(metrics)
+}
+
+/// Metric `i` as a count; aggregates over no rows count as zero.
+fn count(values: &[Option<f64>], i: usize) -> usize {
+    values[i].unwrap_or(0.0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::validation::column_validator;
+    use crate::history::MemoryHistory;
+
+    const CONTRACT: &str = r#"
+[contract]
+name = "metrics_test"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [
+    { rule = "not_null" },
+    { rule = "unique" },
+    { rule = "range", min = 1, max = 4 },
+    { rule = "mean_between", min = 0.0, max = 2.0 },
+    { rule = "stdev_between", min = 0.0, max = 10.0 },
+    { rule = "outlier_sigma", sigma = 1.0 },
+    { rule = "boolean" },
+]
+
+[[columns]]
+name = "code"
+validation = [
+    { rule = "completeness", min_ratio = 0.9 },
+    { rule = "distinctness", min_ratio = 0.5 },
+    { rule = "max_length", value = 2 },
+    { rule = "in_set", values = ["a", "bb"] },
+    { rule = "not_in_set", values = ["ccc"] },
+    { rule = "pattern", pattern = "^[a-b]+$" },
+]
+
+[[columns]]
+name = "missing"
+validation = [{ rule = "not_null" }]
+"#;
+
+    #[test]
+    fn single_pass_matches_validators() {
+        let plan = ExecutionPlan::from_toml(CONTRACT).unwrap();
+        let df = df!(
+            "id" => [Some(1i64), Some(2), Some(2), None, Some(9)],
+            "code" => [Some("a"), Some("bb"), Some("ccc"), None, Some("a")],
+        )
+        .unwrap();
+
+        let mut metrics = RuleMetrics::compute(&df, &plan, |_| false).unwrap();
+        let mut references = HashMap::new();
+        let history = MemoryHistory::default();
+        for (col_idx, column) in plan.contracts.columns.iter().enumerate() {
+            for (rule_idx, rule) in column.validation.iter().enumerate() {
+                let report = metrics.take(col_idx, rule_idx);
+                if column.name == "missing" || matches!(rule, ContractType::Boolean) {
+                    assert!(report.is_none(), "{:?}", rule);
+                    continue;
+                }
+                let validator =
+                    column_validator(rule, &column.name, &plan, &history, &mut references)
+                        .unwrap()
+                        .unwrap();
+                let expected = validator.validate(&df, &column.name).unwrap();
+                let report = report.unwrap_or_else(|| panic!("{:?} not computed", rule));
+                assert_eq!(report.status, expected.status, "{:?}", rule);
+                assert_eq!(report.details, expected.details, "{:?}", rule);
+            }
+        }
+    }
+
+    #[test]
+    fn skipped_rules_are_left_to_validators() {
+        let plan = ExecutionPlan::from_toml(CONTRACT).unwrap();
+        let df = df!("id" => [1i64, 2], "code" => ["a", "b"]).unwrap();
+        let mut metrics =
+            RuleMetrics::compute(&df, &plan, |rule| matches!(rule, ContractType::NotNull)).unwrap();
+        assert!(metrics.take(0, 0).is_none());
+        assert!(metrics.take(0, 1).is_some());
+    }
+}
@@ -0,0 +1,176 @@
+//! Cached execution plans.
+//!
+//! For small files, parsing a contract and compiling its regexes can cost
+//! more than validating the data. An `ExecutionPlan` holds that work — the
+//! parsed contract, every `pattern` rule's compiled regex, and the thread
+//! pool `[limits] max_threads` asks for — and plans are
+//! cached per SHA-256 of the contract TOML, so a long-lived process
+//! (scheduled runs, `pipa run --all`, services embedding the engine) only
+//! redoes it when a contract actually changes.
+//!
+//! The cache is process-wide and holds at most `MAX_CACHED_PLANS` plans;
+//! it is emptied when full.
+
+use crate::contracts::vars::placeholders;
+use crate::contracts::{ContractType, SchemaContracts, parse_contract};
+use crate::logging::error::{ValidationError, ValidationResult};
+use once_cell::sync::{Lazy, OnceCell};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Maximum number of plans kept in the cache.
+pub const MAX_CACHED_PLANS: usize = 128;
+
+static PLANS: Lazy<Mutex<HashMap<String, Arc<ExecutionPlan>>>> = Lazy::new(Default::default);
+
+/// A parsed contract plus its precompiled rule state.
+#[derive(Debug)]
+pub struct ExecutionPlan {
+    /// SHA-256 (hex) of the contract TOML the plan was built from (and of
+    /// the environment variables it references).
+    pub hash: String,
+    pub contracts: SchemaContracts,
+    /// Compiled `pattern` regexes by pattern; patterns that fail to compile
+    /// (or exceed `max_regex_size`) are left to the validator to report.
+    patterns: HashMap<String, Regex>,
+    /// Pool for column rules under `[limits] max_threads`, built on first use.
+    pool: OnceCell<ThreadPool>,
+}
+
+impl ExecutionPlan {
+    /// Build a plan for already-parsed contracts.
+    pub fn new(contracts: SchemaContracts, hash: String) -> Self {
+        let size_limit = contracts.limits.as_ref().and_then(|l| l.max_regex_size);
+        let mut patterns = HashMap::new();
+        for rule in contracts.columns.iter().flat_map(|c| &c.validation) {
+            if let ContractType::Pattern { pattern } = rule {
+                let mut builder = regex::RegexBuilder::new(pattern);
+                if let Some(limit) = size_limit {
+                    builder.size_limit(limit);
+                }
+                if let Ok(re) = builder.build() {
+                    patterns.insert(pattern.clone(), re);
+                }
+            }
+        }
+        Self {
+            hash,
+            contracts,
+            patterns,
+            pool: OnceCell::new(),
+        }
+    }
+
+    /// Parse contract TOML into a plan (uncached).
+    pub fn from_toml(toml_str: &str) -> ValidationResult<Self> {
+        let contracts = parse_contract(toml_str).map_err(|e| {
+            ValidationError::ContractParse(format!("Failed to parse contract TOML: {}", e))
+        })?;
+        Ok(Self::new(contracts, contract_hash(toml_str)))
+    }
+
+    /// The cached plan for contract TOML, building it on a miss.
+    ///
+    /// Returns the plan and whether it came from the cache.
+    pub fn cached(toml_str: &str) -> ValidationResult<(Arc<Self>, bool)> {
+        let hash = contract_hash(toml_str);
+        if let Some(plan) = lock_plans().get(&hash) {
+            return Ok((Arc::clone(plan), true));
+        }
+
+        let plan = Arc::new(Self::from_toml(toml_str)?);
+        let mut plans = lock_plans();
+        if plans.len() >= MAX_CACHED_PLANS {
+            plans.clear();
+        }
+        plans.insert(hash, Arc::clone(&plan));
+        Ok((plan, false))
+    }
+
+    /// Compiled regex for a `pattern` rule, if it compiled.
+    pub fn pattern(&self, pattern: &str) -> Option<&Regex> {
+        self.patterns.get(pattern)
+    }
+
+    /// Thread pool of `[limits] max_threads` threads, shared by every run
+    /// of the plan; `None` when no cap is set.
+    pub(crate) fn thread_pool(&self) -> ValidationResult<Option<&ThreadPool>> {
+        let Some(threads) = self.contracts.limits.as_ref().and_then(|l| l.max_threads) else {
+            return Ok(None);
+        };
+        self.pool
+            .get_or_try_init(|| ThreadPoolBuilder::new().num_threads(threads).build())
+            .map(Some)
+            .map_err(|e| {
+                ValidationError::Config(format!("failed to start validation threads: {}", e))
+            })
+    }
+}
+
+/// Drop every cached plan.
+pub fn clear_plan_cache() {
+    lock_plans().clear();
+}
+
+/// SHA-256 (hex) of contract TOML.
+///
+/// For contracts with `${NAME}` references the environment variables they
+/// name are hashed too, so a changed variable yields a fresh plan.
+pub(crate) fn contract_hash(toml_str: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(toml_str.as_bytes());
+    for name in placeholders(toml_str) {
+        let value = std::env::var(&name).ok();
+        hasher.update(format!("\0{}={:?}", name, value).as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn lock_plans() -> std::sync::MutexGuard<'static, HashMap<String, Arc<ExecutionPlan>>> {
+    PLANS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(pattern: &str) -> String {
+        format!(
+            r#"
+[contract]
+name = "plan_test"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "code"
+validation = [{{ rule = "pattern", pattern = "{}" }}]
+"#,
+            pattern
+        )
+    }
+
+    #[test]
+    fn reuses_plan_until_contract_changes() {
+        let toml = contract("^plan-[0-9]+$");
+        let (first, first_cached) = ExecutionPlan::cached(&toml).unwrap();
+        let (second, second_cached) = ExecutionPlan::cached(&toml).unwrap();
+        assert!(!first_cached);
+        assert!(second_cached);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(first.pattern("^plan-[0-9]+$").unwrap().is_match("plan-42"));
+
+        let (changed, changed_cached) = ExecutionPlan::cached(&contract("^plan-[a-z]+$")).unwrap();
+        assert!(!changed_cached);
+        assert_ne!(changed.hash, first.hash);
+    }
+
+    #[test]
+    fn invalid_toml_is_a_parse_error() {
+        let err = ExecutionPlan::cached("not = [valid").err().unwrap();
+        assert!(matches!(err, ValidationError::ContractParse(_)));
+    }
+}
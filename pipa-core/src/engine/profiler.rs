@@ -0,0 +1,241 @@
+//! Data profiling: per-column statistics without a contract.
+//!
+//! Profiling reuses the same connector/driver path as validation. Given a
+//! source location, it fetches the data, parses it into a `DataFrame`, and
+//! computes for every column:
+//! - inferred dtype (same spelling as `dtype` in contracts),
+//! - null and distinct ratios,
+//! - min/max,
+//! - mean/stdev (numeric columns only),
+//! - the most frequent values.
+//!
+//! The resulting `DataProfile` is the natural starting point for writing
+//! a contract.
+
+use crate::connectors::fetch::{fetch_data_from_source, source_type_for_location};
+use crate::contracts::schema::Source;
+use crate::drivers::get_driver;
+use crate::engine::log_action;
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::profiles::load_profiles;
+use polars::prelude::*;
+use polars_ops::series::SeriesMethods;
+use serde::Serialize;
+use std::path::Path;
+
+/// Default number of most frequent values reported per column.
+pub const DEFAULT_TOP_N: usize = 5;
+
+/// Statistics for a single column.
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnProfile {
+    pub name: String,
+    pub inferred_dtype: String,
+    pub null_ratio: f64,
+    pub distinct_ratio: f64,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub mean: Option<f64>,
+    pub stdev: Option<f64>,
+    pub top_values: Vec<(String, usize)>,
+}
+
+/// Profile report for a whole dataset.
+#[derive(Debug, Clone, Serialize)]
+pub struct DataProfile {
+    pub location: String,
+    pub source_type: String,
+    pub rows: usize,
+    pub columns: Vec<ColumnProfile>,
+}
+
+/// Fetch data from `location` and profile every column.
+///
+/// The connector is chosen from the named `profile`'s provider, or from the
+/// location's scheme when no profile is given (`s3://`, `gs://`, Azure blob
+/// URLs, otherwise a local path).
+pub async fn profile_data<L: AuditLogger>(
+    logger: &L,
+    location: &str,
+    profile: Option<&str>,
+    top_n: usize,
+) -> ValidationResult<(DataProfile, String)> {
+    let profiles = match profile {
+        Some(_) => load_profiles()?,
+        None => Default::default(),
+    };
+    let source_type = match profile {
+        Some(name) => profiles
+            .get(name)
+            .map(|p| p.provider.clone())
+            .ok_or_else(|| ValidationError::ProfileNotFound(name.to_string()))?,
+        None => source_type_for_location(location).to_string(),
+    };
+    let source = Source {
+        r#type: source_type.clone(),
+        location: Some(location.to_string()),
+        profile: profile.map(|p| p.to_string()),
+        format: None,
+        options: Default::default(),
+        csv: Default::default(),
+        on_success: Default::default(),
+        archive_location: None,
+        on_duplicate: Default::default(),
+        archive_member: None,
+        archive_mode: Default::default(),
+    };
+
+    let fetched = fetch_data_from_source(&source, &profiles).await?;
+    let extension = Path::new(location)
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("csv");
+    let driver = get_driver(extension, &source.options, &source.csv, &[])
+        .map_err(|e| ValidationError::driver_load(extension, e))?;
+    let df = fetched
+        .payload
+        .load(driver.as_ref())
+        .map_err(|e| ValidationError::driver_load(extension, e))?;
+
+    let report = DataProfile {
+        location: location.to_string(),
+        source_type,
+        rows: df.height(),
+        columns: profile_dataframe(&df, top_n)?,
+    };
+
+    let details = format!(
+        "rows={}, cols={}, bytes={}",
+        report.rows,
+        report.columns.len(),
+        fetched.payload.len()
+    );
+    let message = log_action(
+        logger,
+        "data_profiled",
+        Some(&details),
+        None,
+        None,
+        Some(location),
+    );
+    Ok((report, message))
+}
+
+/// Compute a `ColumnProfile` for every column of `df`.
+pub fn profile_dataframe(df: &DataFrame, top_n: usize) -> ValidationResult<Vec<ColumnProfile>> {
+    df.get_columns()
+        .iter()
+        .map(|column| profile_column(column.as_materialized_series(), top_n))
+        .collect()
+}
+
+fn profile_column(series: &Series, top_n: usize) -> ValidationResult<ColumnProfile> {
+    let rows = series.len();
+    let ratio = |count: usize| {
+        if rows == 0 {
+            0.0
+        } else {
+            count as f64 / rows as f64
+        }
+    };
+
+    let non_null = series.drop_nulls();
+    let (min, max) = if non_null.is_empty() {
+        (None, None)
+    } else {
+        (
+            scalar_to_string(non_null.min_reduce()?.value()),
+            scalar_to_string(non_null.max_reduce()?.value()),
+        )
+    };
+
+    let (mean, stdev) = if series.dtype().is_primitive_numeric() {
+        let values = series.cast(&DataType::Float64)?;
+        let values = values.f64()?;
+        (values.mean(), values.std(1))
+    } else {
+        (None, None)
+    };
+
+    Ok(ColumnProfile {
+        name: series.name().to_string(),
+        inferred_dtype: format!("{:?}", series.dtype()),
+        null_ratio: ratio(series.null_count()),
+        distinct_ratio: ratio(non_null.n_unique()?),
+        min,
+        max,
+        mean,
+        stdev,
+        top_values: top_values(&non_null, top_n)?,
+    })
+}
+
+/// Most frequent values, highest count first.
+fn top_values(series: &Series, top_n: usize) -> ValidationResult<Vec<(String, usize)>> {
+    if top_n == 0 || series.is_empty() {
+        return Ok(Vec::new());
+    }
+    let counts = series.value_counts(true, false, "__pipa_count".into(), false)?;
+    let counts = counts.head(Some(top_n));
+    let values = counts.column(series.name())?;
+    let totals = counts.column("__pipa_count")?.cast(&DataType::UInt64)?;
+    let totals = totals.u64()?;
+
+    let mut top = Vec::with_capacity(counts.height());
+    for i in 0..counts.height() {
+        if let Some(value) = scalar_to_string(&values.get(i)?) {
+            top.push((value, totals.get(i).unwrap_or(0) as usize));
+        }
+    }
+    Ok(top)
+}
+
+/// Render an `AnyValue` without the quoting polars applies to strings.
+fn scalar_to_string(value: &AnyValue) -> Option<String> {
+    match value {
+        AnyValue::Null => None,
+        AnyValue::String(s) => Some(s.to_string()),
+        AnyValue::StringOwned(s) => Some(s.to_string()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_numeric_and_string_columns() {
+        let df = df!(
+            "age" => &[Some(10i64), Some(20), None, Some(30)],
+            "tier" => &["gold", "gold", "silver", "gold"],
+        )
+        .unwrap();
+
+        let profiles = profile_dataframe(&df, 2).unwrap();
+        let age = &profiles[0];
+        assert_eq!(age.inferred_dtype, "Int64");
+        assert_eq!(age.null_ratio, 0.25);
+        assert_eq!(age.distinct_ratio, 0.75);
+        assert_eq!(age.min.as_deref(), Some("10"));
+        assert_eq!(age.max.as_deref(), Some("30"));
+        assert_eq!(age.mean, Some(20.0));
+
+        let tier = &profiles[1];
+        assert_eq!(tier.inferred_dtype, "String");
+        assert_eq!(tier.mean, None);
+        assert_eq!(tier.min.as_deref(), Some("gold"));
+        assert_eq!(tier.top_values[0], ("gold".to_string(), 3));
+        assert_eq!(tier.top_values.len(), 2);
+    }
+
+    #[test]
+    fn handles_all_null_column() {
+        let df = df!("empty" => &[None::<i64>, None]).unwrap();
+        let profiles = profile_dataframe(&df, 3).unwrap();
+        assert_eq!(profiles[0].null_ratio, 1.0);
+        assert_eq!(profiles[0].min, None);
+        assert!(profiles[0].top_values.is_empty());
+    }
+}
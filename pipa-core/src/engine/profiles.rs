@@ -1,8 +1,12 @@
 //! Profile management functions for the engine
 
+use crate::connectors::{AzureCredential, GcsCredential};
 use crate::engine::log_action;
 use crate::logging::AuditLogger;
-use crate::profiles::{Profile, Profiles, load_profiles};
+use crate::profiles::{
+    PROFILES_FILE, Profile, Profiles, add_profile_section, edit_profile_section, load_profiles,
+    parse_field_value, remove_profile_section,
+};
 
 /// Result of listing profiles
 pub struct ProfileList {
@@ -33,6 +37,98 @@ pub fn list_profiles<L: AuditLogger>(logger: &L) -> Result<(ProfileList, String)
     }
 }
 
+/// Add a profile to `profiles.toml`, creating the file if needed.
+///
+/// `fields` are `(key, value)` pairs in the order they are written; values
+/// are TOML literals or plain strings (see `parse_field_value`). Only the
+/// field names are logged, never their values.
+pub fn add_profile<L: AuditLogger>(
+    logger: &L,
+    profile_name: &str,
+    fields: &[(String, String)],
+) -> Result<String, String> {
+    let content = match std::fs::read_to_string(PROFILES_FILE) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", PROFILES_FILE, e)),
+    };
+    let updated = add_profile_section(&content, profile_name, &parse_fields(fields))?;
+    write_profiles(&updated)?;
+
+    let details = format!("fields={}", field_names(fields));
+    Ok(log_action(
+        logger,
+        "profile_added",
+        Some(&details),
+        None,
+        None,
+        Some(profile_name),
+    ))
+}
+
+/// Set and remove fields of a profile in `profiles.toml`.
+pub fn edit_profile<L: AuditLogger>(
+    logger: &L,
+    profile_name: &str,
+    set: &[(String, String)],
+    unset: &[String],
+) -> Result<String, String> {
+    let content = read_profiles()?;
+    let updated = edit_profile_section(&content, profile_name, &parse_fields(set), unset)?;
+    write_profiles(&updated)?;
+
+    let details = format!("set={}, unset={}", field_names(set), unset.join(";"));
+    Ok(log_action(
+        logger,
+        "profile_updated",
+        Some(&details),
+        None,
+        None,
+        Some(profile_name),
+    ))
+}
+
+/// Remove a profile from `profiles.toml`.
+pub fn remove_profile<L: AuditLogger>(logger: &L, profile_name: &str) -> Result<String, String> {
+    let content = read_profiles()?;
+    let updated = remove_profile_section(&content, profile_name)?;
+    write_profiles(&updated)?;
+
+    Ok(log_action(
+        logger,
+        "profile_removed",
+        None,
+        None,
+        None,
+        Some(profile_name),
+    ))
+}
+
+fn read_profiles() -> Result<String, String> {
+    std::fs::read_to_string(PROFILES_FILE)
+        .map_err(|e| format!("Failed to read {}: {}", PROFILES_FILE, e))
+}
+
+fn write_profiles(content: &str) -> Result<(), String> {
+    std::fs::write(PROFILES_FILE, content)
+        .map_err(|e| format!("Failed to write {}: {}", PROFILES_FILE, e))
+}
+
+fn parse_fields(fields: &[(String, String)]) -> Vec<(String, toml::Value)> {
+    fields
+        .iter()
+        .map(|(key, value)| (key.clone(), parse_field_value(value)))
+        .collect()
+}
+
+fn field_names(fields: &[(String, String)]) -> String {
+    fields
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
 /// Test a profile's connectivity
 pub async fn test_profile<L: AuditLogger>(logger: &L, profile_name: &str) -> (ProfileTestResult, String) {
     let profiles = match load_profiles() {
@@ -98,72 +194,74 @@ pub async fn test_profile<L: AuditLogger>(logger: &L, profile_name: &str) -> (Pr
 
 // Extracted for reuse - test profile connectivity
 pub async fn test_profile_internal(profile_name: &str, profiles: &Profiles) -> bool {
-    if let Some(profile) = profiles.get(profile_name) {
-        match profile.provider.as_str() {
-            "s3" => test_s3_profile_internal(profile).await,
-            "local" => true, // Local always works if profile exists
-            "azure" => test_azure_profile_internal(profile).await,
-            "gcs" => test_gcs_profile_internal(profile).await,
-            "sftp" => false, // Not implemented yet
-            _ => false,
-        }
-    } else {
-        false
+    match profiles.get(profile_name) {
+        Some(profile) => check_profile(profile).await.is_ok(),
+        None => false,
     }
 }
 
-async fn test_s3_profile_internal(profile: &Profile) -> bool {
-    use aws_sdk_s3::config::Credentials;
-
-    let region = profile
-        .region
-        .clone()
-        .unwrap_or_else(|| "us-east-1".to_string());
-    let mut cfg_loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
-        .region(aws_config::Region::new(region));
-
-    if let Some(endpoint) = &profile.endpoint {
-        cfg_loader = cfg_loader.endpoint_url(endpoint);
-    }
-
-    let base = cfg_loader.load().await;
-    let mut s3b = aws_sdk_s3::config::Builder::from(&base);
-
-    if profile.path_style.unwrap_or(false) {
-        s3b = s3b.force_path_style(true);
-    }
-
-    // Handle optional credentials
-    if let (Some(access_key), Some(secret_key)) = (&profile.access_key, &profile.secret_key) {
-        if !access_key.is_empty() && !secret_key.is_empty() {
-            let creds = Credentials::new(
-                access_key.clone(),
-                secret_key.clone(),
-                None,
-                None,
-                "profile",
-            );
-            s3b = s3b.credentials_provider(creds);
-        }
+/// Test one profile's connectivity, returning why it failed.
+pub async fn check_profile(profile: &Profile) -> Result<(), String> {
+    match profile.provider.as_str() {
+        "s3" => test_s3_profile_internal(profile).await,
+        "local" => Ok(()), // Local always works if profile exists
+        "azure" => test_azure_profile_internal(profile).await,
+        "gcs" => test_gcs_profile_internal(profile).await,
+        "kafka" => test_kafka_profile_internal(profile).await,
+        "sftp" => Err("sftp profiles are not supported yet".to_string()),
+        other => Err(format!("unknown provider '{}'", other)),
     }
+}
 
+async fn test_s3_profile_internal(profile: &Profile) -> Result<(), String> {
+    let s3b = crate::connectors::s3::client_config(profile).await;
     let client = aws_sdk_s3::Client::from_conf(s3b.build());
-    client.list_buckets().send().await.is_ok()
+    client
+        .list_buckets()
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|e| aws_sdk_s3::error::DisplayErrorContext(e).to_string())
 }
 
-async fn test_azure_profile_internal(profile: &Profile) -> bool {
-    if let Some(connection_string) = &profile.connection_string {
-        !connection_string.is_empty()
-    } else {
-        false
-    }
+async fn test_azure_profile_internal(profile: &Profile) -> Result<(), String> {
+    AzureCredential::from_profile(profile)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
 }
 
-async fn test_gcs_profile_internal(profile: &Profile) -> bool {
+async fn test_gcs_profile_internal(profile: &Profile) -> Result<(), String> {
     if let Some(service_account_json) = &profile.service_account_json {
         return test_gcs_service_account(service_account_json).await;
     }
-    false
+    if profile.application_default_credentials.unwrap_or(false) {
+        return test_gcs_application_default().await;
+    }
+    Err("missing service_account_json (or application_default_credentials = true)".to_string())
+}
+
+/// ADC work if they resolve and yield an access token.
+async fn test_gcs_application_default() -> Result<(), String> {
+    let credential = GcsCredential::application_default().map_err(|e| e.to_string())?;
+    credential
+        .access_token(&reqwest::Client::new())
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// A Kafka profile is reachable if its REST Proxy lists topics.
+async fn test_kafka_profile_internal(profile: &Profile) -> Result<(), String> {
+    let Some(endpoint) = &profile.endpoint else {
+        return Err("missing endpoint".to_string());
+    };
+    let url = format!("{}/topics", endpoint.trim_end_matches('/'));
+    let response = reqwest::Client::new()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    success_or_status(response.status(), "REST Proxy topic list")
 }
 
 fn parse_gcs_service_account(
@@ -191,27 +289,13 @@ fn parse_gcs_service_account(
     Ok((project_id, client_email, private_key))
 }
 
-async fn test_gcs_service_account(service_account_json: &str) -> bool {
-    println!(
-        "Debug: GCS service account JSON length: {}",
-        service_account_json.len()
-    );
-    // Don't log sensitive service account details
-    // println!(
-    //     "Debug: GCS service account JSON first 100 chars: {}",
-    //     &service_account_json.chars().take(100).collect::<String>()
-    // );
+async fn test_gcs_service_account(service_account_json: &str) -> Result<(), String> {
     use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
     use serde_json::json;
 
     let (project_id, client_email, private_key) =
-        match parse_gcs_service_account(service_account_json) {
-            Ok((pid, email, key)) => (pid, email, key),
-            Err(e) => {
-                println!("Debug: GCS service account parsing failed: {}", e);
-                return false;
-            }
-        };
+        parse_gcs_service_account(service_account_json)
+            .map_err(|e| format!("service account parsing failed: {}", e))?;
 
     // Create JWT claims
     let now = chrono::Utc::now().timestamp();
@@ -225,28 +309,15 @@ async fn test_gcs_service_account(service_account_json: &str) -> bool {
 
     // Generate JWT token
     let header = Header::new(Algorithm::RS256);
-    let encoding_key = match EncodingKey::from_rsa_pem(private_key.as_bytes()) {
-        Ok(key) => key,
-        Err(e) => {
-            println!("Debug: GCS private key parsing failed: {}", e);
-            return false;
-        }
-    };
-
-    let jwt_token = match encode(&header, &claims, &encoding_key) {
-        Ok(token) => token,
-        Err(e) => {
-            println!("Debug: GCS JWT generation failed: {}", e);
-            return false;
-        }
-    };
-
-    println!("Debug: GCS JWT generated successfully");
+    let encoding_key = EncodingKey::from_rsa_pem(private_key.as_bytes())
+        .map_err(|e| format!("private key parsing failed: {}", e))?;
+    let jwt_token = encode(&header, &claims, &encoding_key)
+        .map_err(|e| format!("JWT generation failed: {}", e))?;
 
     // Exchange JWT for access token
     let client = reqwest::Client::new();
 
-    let token_response = match client
+    let token_response = client
         .post("https://oauth2.googleapis.com/token")
         .header("Content-Type", "application/x-www-form-urlencoded")
         .form(&[
@@ -255,35 +326,16 @@ async fn test_gcs_service_account(service_account_json: &str) -> bool {
         ])
         .send()
         .await
-    {
-        Ok(response) => response,
-        Err(e) => {
-            println!("Debug: GCS token exchange request failed: {}", e);
-            return false;
-        }
-    };
-
-    if !token_response.status().is_success() {
-        println!(
-            "Debug: GCS token exchange failed: {}",
-            token_response.status()
-        );
-        return false;
-    }
+        .map_err(|e| format!("token exchange request failed: {}", e))?;
+    success_or_status(token_response.status(), "token exchange")?;
 
-    let access_token = match token_response.json::<serde_json::Value>().await {
-        Ok(json) => match json["access_token"].as_str() {
-            Some(token) => token.to_string(),
-            None => {
-                println!("Debug: GCS access_token not found in response");
-                return false;
-            }
-        },
-        Err(e) => {
-            println!("Debug: GCS token response parsing failed: {}", e);
-            return false;
-        }
-    };
+    let token_json = token_response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("token response parsing failed: {}", e))?;
+    let access_token = token_json["access_token"]
+        .as_str()
+        .ok_or("access_token not found in token response")?;
 
     // Test bucket list API
     let bucket_list_url = format!(
@@ -291,19 +343,20 @@ async fn test_gcs_service_account(service_account_json: &str) -> bool {
         project_id
     );
 
-    match client
+    let response = client
         .get(&bucket_list_url)
         .header("Authorization", format!("Bearer {}", access_token))
         .send()
         .await
-    {
-        Ok(response) => {
-            println!("Debug: GCS bucket list response: {}", response.status());
-            response.status().is_success()
-        }
-        Err(e) => {
-            println!("Debug: GCS bucket list failed: {}", e);
-            false
-        }
+        .map_err(|e| format!("bucket list failed: {}", e))?;
+    success_or_status(response.status(), "bucket list")
+}
+
+/// `Ok` for a success status, else an error naming the request.
+fn success_or_status(status: reqwest::StatusCode, request: &str) -> Result<(), String> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("{} returned {}", request, status))
     }
 }
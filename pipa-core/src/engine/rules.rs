@@ -0,0 +1,807 @@
+//! Catalog of supported contract rules.
+//!
+//! `catalog()` describes every rule the engine can dispatch: its contract
+//! name (`rule = "..."`), where it may be declared, its parameters, notes
+//! on semantics (null handling, skipped cases), and a TOML example.
+//! Consumed by `pipa rules list` / `pipa rules describe` and by contract
+//! editors for validation and autocomplete.
+//!
+//! Keep this in sync with `ContractType` and the dispatch in
+//! `engine/validation.rs`; the tests below parse every entry.
+
+use crate::contracts::SchemaContracts;
+use serde::Serialize;
+use std::fmt;
+
+/// Where a rule may be declared in a contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleScope {
+    /// Under `[[columns]] validation`.
+    Column,
+    /// Under `[file] validation`.
+    File,
+    /// Either of the above.
+    Both,
+}
+
+/// Column dtypes a rule can evaluate; other dtypes make it `skipped`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleInput {
+    /// Any dtype.
+    Any,
+    /// `String` columns.
+    String,
+    /// Integer and float columns.
+    Numeric,
+    /// `Int64` columns only.
+    Int64,
+}
+
+impl RuleInput {
+    /// Whether a column declared with `dtype` (Polars spelling) is accepted.
+    pub fn accepts(&self, dtype: &str) -> bool {
+        match self {
+            RuleInput::Any => true,
+            RuleInput::String => dtype == "String",
+            RuleInput::Numeric => ["Int", "UInt", "Float"]
+                .iter()
+                .any(|prefix| dtype.starts_with(prefix)),
+            RuleInput::Int64 => dtype == "Int64",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleInput::Any => "any",
+            RuleInput::String => "a String",
+            RuleInput::Numeric => "a numeric",
+            RuleInput::Int64 => "an Int64",
+        }
+    }
+}
+
+/// A single rule parameter.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleParam {
+    pub name: &'static str,
+    /// TOML type: `string`, `integer`, `float`, `bool`, `string[]`, or `table`.
+    #[serde(rename = "type")]
+    pub ty: &'static str,
+    pub required: bool,
+    pub description: &'static str,
+    /// Allowed values for enumerated parameters; empty when free-form.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub values: &'static [&'static str],
+}
+
+impl RuleParam {
+    /// Restrict the parameter to a fixed set of values.
+    const fn one_of(self, values: &'static [&'static str]) -> Self {
+        Self { values, ..self }
+    }
+}
+
+/// Metadata describing a supported rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleInfo {
+    pub name: &'static str,
+    pub scope: RuleScope,
+    pub description: &'static str,
+    pub params: Vec<RuleParam>,
+    /// Column dtypes the rule evaluates (column scope only).
+    pub input: RuleInput,
+    /// Semantics worth knowing when authoring: null handling, skipped cases.
+    pub notes: &'static str,
+    /// A TOML rule entry, as written inside `validation = [...]`.
+    pub example: &'static str,
+}
+
+impl RuleInfo {
+    fn input(self, input: RuleInput) -> Self {
+        Self { input, ..self }
+    }
+
+    fn doc(self, notes: &'static str, example: &'static str) -> Self {
+        Self {
+            notes,
+            example,
+            ..self
+        }
+    }
+}
+
+const fn param(
+    name: &'static str,
+    ty: &'static str,
+    required: bool,
+    description: &'static str,
+) -> RuleParam {
+    RuleParam {
+        name,
+        ty,
+        required,
+        description,
+        values: &[],
+    }
+}
+
+/// A rule declared where the engine has no implementation for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnrecognizedRule {
+    /// Column name, or `"file"` for `[file]` rules.
+    pub column: String,
+    pub rule: &'static str,
+}
+
+impl fmt::Display for UnrecognizedRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.column == "file" {
+            write!(f, "rule '{}' is not implemented under [file]", self.rule)
+        } else {
+            write!(
+                f,
+                "rule '{}' is not implemented for columns (column '{}')",
+                self.rule, self.column
+            )
+        }
+    }
+}
+
+/// Whether the engine implements `rule` at the given scope.
+pub fn is_supported(rule: &str, scope: RuleScope) -> bool {
+    catalog()
+        .iter()
+        .any(|info| info.name == rule && (info.scope == scope || info.scope == RuleScope::Both))
+}
+
+/// Every rule in `contracts` the engine would not evaluate.
+///
+/// Used before fetching data for `strict_rules` contracts and by
+/// `pipa contract validate`.
+pub fn unrecognized_rules(contracts: &SchemaContracts) -> Vec<UnrecognizedRule> {
+    let file_rules = contracts
+        .file
+        .iter()
+        .flat_map(|file| &file.validation)
+        .map(|rule| ("file", rule, RuleScope::File));
+    let column_rules = contracts.columns.iter().flat_map(|col| {
+        col.validation
+            .iter()
+            .map(move |rule| (col.name.as_str(), rule, RuleScope::Column))
+    });
+
+    file_rules
+        .chain(column_rules)
+        .filter(|(_, rule, scope)| !is_supported(rule.name(), *scope))
+        .map(|(column, rule, _)| UnrecognizedRule {
+            column: column.to_string(),
+            rule: rule.name(),
+        })
+        .collect()
+}
+
+/// A column rule attached to a column whose declared `dtype` it can't evaluate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTypeMismatch {
+    pub column: String,
+    pub rule: &'static str,
+    pub declared: String,
+    pub expected: RuleInput,
+}
+
+impl fmt::Display for RuleTypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule '{}' expects {} column but '{}' is declared {}; it would be skipped",
+            self.rule,
+            self.expected.as_str(),
+            self.column,
+            self.declared
+        )
+    }
+}
+
+/// Column rules that can't evaluate their column's declared `dtype`.
+///
+/// Columns without a declared `dtype` are not checked. Reported before
+/// data is fetched, instead of surfacing as `skipped` results at run time.
+pub fn type_mismatches(contracts: &SchemaContracts) -> Vec<RuleTypeMismatch> {
+    let catalog = catalog();
+    let mut mismatches = Vec::new();
+    for col in &contracts.columns {
+        let Some(declared) = &col.dtype else {
+            continue;
+        };
+        for rule in &col.validation {
+            let Some(info) = catalog.iter().find(|info| info.name == rule.name()) else {
+                continue;
+            };
+            if !info.input.accepts(declared) {
+                mismatches.push(RuleTypeMismatch {
+                    column: col.name.clone(),
+                    rule: info.name,
+                    declared: declared.clone(),
+                    expected: info.input,
+                });
+            }
+        }
+    }
+    mismatches
+}
+
+/// Look up a single rule by its contract name.
+pub fn describe(name: &str) -> Option<RuleInfo> {
+    catalog().into_iter().find(|rule| rule.name == name)
+}
+
+/// Every rule supported by the engine, in contract-name order.
+pub fn catalog() -> Vec<RuleInfo> {
+    use RuleScope::*;
+
+    let rule = |name, scope, description, params| RuleInfo {
+        name,
+        scope,
+        description,
+        params,
+        input: RuleInput::Any,
+        notes: "",
+        example: "",
+    };
+
+    vec![
+        rule(
+            "boolean",
+            Column,
+            "Values are booleans (true/false, 1/0, yes/no).",
+            vec![],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "String columns only; other dtypes are skipped. Accepts true/false, t/f, yes/no, y/n, \
+            1/0 (case-insensitive). Nulls pass.",
+            r#"{ rule = "boolean" }"#,
+        ),
+        rule(
+            "completeness",
+            Both,
+            "Share of non-null values is at least min_ratio.",
+            vec![param(
+                "min_ratio",
+                "float",
+                true,
+                "Minimum non-null ratio (0.0-1.0).",
+            )],
+        )
+        .doc(
+            "Column scope: non-null values / rows. File scope: rows with no null in any column / \
+            rows. An empty column or file passes.",
+            r#"{ rule = "completeness", min_ratio = 0.95 }"#,
+        ),
+        rule(
+            "custom",
+            Both,
+            "User-defined rule registered through validator::register_custom.",
+            vec![
+                param("name", "string", true, "Registered rule name."),
+                param("params", "table", false, "Parameters passed to the rule."),
+            ],
+        )
+        .doc(
+            "Resolved at run time from validators registered by the embedding application; an \
+            unregistered name fails the run. Whether it applies to a column or the file depends \
+            on the registered validator.",
+            r#"{ rule = "custom", name = "my_rule", params = { threshold = 3 } }"#,
+        ),
+        rule(
+            "date_between",
+            Column,
+            "Dates lie within [min, max]; bounds may be relative to now.",
+            vec![
+                param(
+                    "min",
+                    "string",
+                    false,
+                    "Earliest date, e.g. 2024-01-01 or now-30d.",
+                ),
+                param("max", "string", false, "Latest date, e.g. now."),
+                param(
+                    "format",
+                    "string",
+                    false,
+                    "chrono format for String values; default: the column's date_format.",
+                ),
+            ],
+        )
+        .doc(
+            "String, Date, and Datetime columns; other dtypes are skipped. Relative bounds are \
+            `now` shifted by s, m, h, d, or w (`now-7d`), resolved in UTC when the run starts. \
+            Without a format or date_format rule, ISO-8601 dates and date-times are accepted. \
+            Bounds are inclusive. Nulls and values that don't parse pass.",
+            r#"{ rule = "date_between", min = "now-90d", max = "now" }"#,
+        ),
+        rule(
+            "date_format",
+            Column,
+            "Values parse with the given chrono format.",
+            vec![param(
+                "format",
+                "string",
+                true,
+                "chrono format string, e.g. %Y-%m-%d.",
+            )],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "String columns only; other dtypes are skipped. Values must parse as a full date-time \
+            with the format. Nulls pass.",
+            r#"{ rule = "date_format", format = "%Y-%m-%d %H:%M:%S" }"#,
+        ),
+        rule(
+            "distinctness",
+            Column,
+            "Share of distinct values is at least min_ratio.",
+            vec![param(
+                "min_ratio",
+                "float",
+                true,
+                "Minimum distinct ratio (0.0-1.0).",
+            )],
+        )
+        .doc(
+            "Ratio is distinct values / rows; null counts as one distinct value. An empty column \
+            passes.",
+            r#"{ rule = "distinctness", min_ratio = 0.9 }"#,
+        ),
+        rule(
+            "drift",
+            Column,
+            "Column statistic changed by at most max_change since a stored run.",
+            vec![
+                param("metric", "string", true, "Statistic to compare.").one_of(&[
+                    "row_count",
+                    "null_ratio",
+                    "distinct_ratio",
+                    "mean",
+                    "stdev",
+                ]),
+                param(
+                    "max_change",
+                    "float",
+                    true,
+                    "Allowed change: absolute for ratios, relative otherwise.",
+                ),
+                param(
+                    "against",
+                    "string",
+                    false,
+                    "Reference run (default: previous).",
+                )
+                .one_of(&["previous", "baseline"]),
+            ],
+        )
+        .doc(
+            "Compares with the previous recorded run (default) or the baseline pinned with `pipa \
+            contract baseline`. Ratios use absolute difference; row_count, mean, and stdev use \
+            change relative to the reference (absolute when the reference is 0). Stdev is the \
+            sample standard deviation (ddof=1); nulls are ignored for mean/stdev. Skipped when \
+            there is no reference yet.",
+            r#"{ rule = "drift", metric = "mean", max_change = 0.1, against = "previous" }"#,
+        ),
+        rule("email", Column, "Values are email addresses.", vec![])
+            .input(RuleInput::String)
+            .doc(
+                "local@domain with a dotted domain and an alphabetic top-level label; no quoted \
+                local parts, IP-literal domains, or display names. String columns only; other \
+                dtypes are skipped. Nulls pass. Failures report the number of invalid values and \
+                the first one, masked.",
+                r#"{ rule = "email" }"#,
+            ),
+        rule(
+            "expected_columns",
+            File,
+            "The file has exactly the declared columns.",
+            vec![
+                param("columns", "string[]", true, "Expected column names."),
+                param("ordered", "bool", false, "Also require the declared order."),
+                param(
+                    "allow_extra_columns",
+                    "bool",
+                    false,
+                    "Tolerate undeclared columns.",
+                ),
+                param(
+                    "allow_missing_columns",
+                    "bool",
+                    false,
+                    "Tolerate absent columns.",
+                ),
+            ],
+        )
+        .doc(
+            "Order is compared only across columns present on both sides, so an allowed extra or \
+            missing column never also fails the order check.",
+            r#"{ rule = "expected_columns", columns = ["id", "email"], ordered = true }"#,
+        ),
+        rule(
+            "in_set",
+            Column,
+            "Values are one of the allowed values.",
+            vec![param("values", "string[]", true, "Allowed values.")],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "String columns only; other dtypes are skipped. Comparison is exact and \
+            case-sensitive. Nulls pass.",
+            r#"{ rule = "in_set", values = ["active", "inactive"] }"#,
+        ),
+        rule("ipv4", Column, "Values are IPv4 addresses.", vec![])
+            .input(RuleInput::String)
+            .doc(
+                "Dotted-quad form, e.g. 192.168.0.1; no leading zeros, ports, or CIDR suffixes. \
+                String columns only; other dtypes are skipped. Nulls pass. Failures report the \
+                number of invalid values and the first one, masked.",
+                r#"{ rule = "ipv4" }"#,
+            ),
+        rule("ipv6", Column, "Values are IPv6 addresses.", vec![])
+            .input(RuleInput::String)
+            .doc(
+                "Any RFC 4291 text form, e.g. ::1 or 2001:db8::1; no brackets, ports, or zone IDs. \
+                String columns only; other dtypes are skipped. Nulls pass. Failures report the \
+                number of invalid values and the first one, masked.",
+                r#"{ rule = "ipv6" }"#,
+            ),
+        rule(
+            "max_length",
+            Column,
+            "String values are at most value characters long.",
+            vec![param("value", "integer", true, "Maximum length.")],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "String columns only; other dtypes are skipped. Length is counted in characters, not \
+            bytes. Nulls pass.",
+            r#"{ rule = "max_length", value = 255 }"#,
+        ),
+        rule(
+            "mean_between",
+            Column,
+            "Column mean lies within [min, max].",
+            vec![
+                param("min", "float", true, "Lower bound."),
+                param("max", "float", true, "Upper bound."),
+            ],
+        )
+        .input(RuleInput::Numeric)
+        .doc(
+            "Column is cast to float; non-numeric columns are skipped. Nulls are ignored; an \
+            all-null column is skipped. Bounds are inclusive.",
+            r#"{ rule = "mean_between", min = 10.0, max = 50.0 }"#,
+        ),
+        rule(
+            "no_future_dates",
+            Column,
+            "No date is later than the time of the run.",
+            vec![param(
+                "format",
+                "string",
+                false,
+                "chrono format for String values; default: the column's date_format.",
+            )],
+        )
+        .doc(
+            "For event-time columns. String, Date, and Datetime columns; other dtypes are \
+            skipped. Values are compared with the current UTC time. Nulls and values that don't \
+            parse pass; the failure reports the latest offending value.",
+            r#"{ rule = "no_future_dates" }"#,
+        ),
+        rule(
+            "not_in_set",
+            Column,
+            "Values are none of the forbidden values.",
+            vec![param("values", "string[]", true, "Forbidden values.")],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "String columns only; other dtypes are skipped. Comparison is exact and \
+            case-sensitive. Nulls pass.",
+            r#"{ rule = "not_in_set", values = ["N/A", "unknown"] }"#,
+        ),
+        rule("not_null", Column, "Column contains no nulls.", vec![]).doc(
+            "Fails with the number of null values.",
+            r#"{ rule = "not_null" }"#,
+        ),
+        rule(
+            "outlier_sigma",
+            Column,
+            "No value lies more than sigma standard deviations from the mean.",
+            vec![param(
+                "sigma",
+                "float",
+                true,
+                "Allowed standard deviations.",
+            )],
+        )
+        .input(RuleInput::Numeric)
+        .doc(
+            "Uses the sample mean and sample standard deviation (ddof=1) of non-null values. \
+            Non-numeric columns are skipped, as are columns where no stdev can be computed (e.g. \
+            all null); a zero stdev passes.",
+            r#"{ rule = "outlier_sigma", sigma = 3.0 }"#,
+        ),
+        rule(
+            "pattern",
+            Column,
+            "String values match the regular expression.",
+            vec![param("pattern", "string", true, "Regular expression.")],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "String columns only; other dtypes are skipped. Uses Rust regex syntax; the pattern \
+            is unanchored unless it uses ^ and $. Nulls pass. Aborted when the compiled regex \
+            exceeds [limits] max_regex_size.",
+            r#"{ rule = "pattern", pattern = "^[A-Z]{2}[0-9]{4}$" }"#,
+        ),
+        rule(
+            "phone_e164",
+            Column,
+            "Values are E.164 phone numbers.",
+            vec![],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "A + followed by 2-15 digits, the first not 0; no spaces or punctuation. String \
+                columns only; other dtypes are skipped. Nulls pass. Failures report the number of \
+                invalid values and the first one, masked.",
+            r#"{ rule = "phone_e164" }"#,
+        ),
+        rule(
+            "range",
+            Column,
+            "Numeric values lie within [min, max].",
+            vec![
+                param("min", "integer", true, "Lower bound."),
+                param("max", "integer", true, "Upper bound."),
+            ],
+        )
+        .input(RuleInput::Int64)
+        .doc(
+            "Int64 columns only; other dtypes are skipped. Bounds are inclusive. Nulls pass.",
+            r#"{ rule = "range", min = 0, max = 120 }"#,
+        ),
+        rule(
+            "row_count",
+            File,
+            "Row count is at least min (and at most max).",
+            vec![
+                param("min", "integer", true, "Minimum rows."),
+                param("max", "integer", false, "Maximum rows."),
+            ],
+        )
+        .doc(
+            "Bounds are inclusive; without max only the minimum is checked.",
+            r#"{ rule = "row_count", min = 1, max = 1_000_000 }"#,
+        ),
+        rule(
+            "stdev_between",
+            Column,
+            "Column standard deviation lies within [min, max].",
+            vec![
+                param("min", "float", true, "Lower bound."),
+                param("max", "float", true, "Upper bound."),
+            ],
+        )
+        .input(RuleInput::Numeric)
+        .doc(
+            "Sample standard deviation (ddof=1) of non-null values. Non-numeric columns are \
+            skipped, as are columns where no stdev can be computed (e.g. all null). Bounds are \
+            inclusive.",
+            r#"{ rule = "stdev_between", min = 0.5, max = 5.0 }"#,
+        ),
+        rule(
+            "type",
+            Column,
+            "Column has the given Polars dtype.",
+            vec![param(
+                "dtype",
+                "string",
+                true,
+                "Dtype name, e.g. Int64 or String.",
+            )],
+        )
+        .doc(
+            "Compares with the dtype Polars inferred while parsing, spelled as in `pipa \
+            profile-data` (e.g. Int64, Float64, String, Boolean).",
+            r#"{ rule = "type", dtype = "Int64" }"#,
+        ),
+        rule(
+            "unique",
+            Column,
+            "Column contains no duplicate values.",
+            vec![],
+        )
+        .doc(
+            "Null counts as a value, so more than one null fails.",
+            r#"{ rule = "unique" }"#,
+        ),
+        rule(
+            "url",
+            Column,
+            "Values are absolute URLs with a host.",
+            vec![],
+        )
+        .input(RuleInput::String)
+        .doc(
+            "Parsed per the WHATWG URL standard; any scheme, but a host is required (so \
+                mailto: and bare domains fail). String columns only; other dtypes are skipped. \
+                Nulls pass. Failures report the number of invalid values and the first one, \
+                masked.",
+            r#"{ rule = "url" }"#,
+        ),
+        rule("uuid", Column, "Values are hyphenated UUIDs.", vec![])
+            .input(RuleInput::String)
+            .doc(
+                "8-4-4-4-12 hex digits in either case, any version; braced and unhyphenated forms \
+                fail. String columns only; other dtypes are skipped. Nulls pass. Failures report \
+                the number of invalid values and the first one, masked.",
+                r#"{ rule = "uuid" }"#,
+            ),
+        rule(
+            "wasm",
+            Column,
+            "Sandboxed WebAssembly module returns pass/fail counts for the column.",
+            vec![
+                param("module", "string", true, "Path to the .wasm module."),
+                param("fuel", "integer", false, "Execution budget for the module."),
+            ],
+        )
+        .doc(
+            "The module runs without imports under a fuel budget and memory cap; it must export \
+            memory, alloc, and validate. See the Wasm validator docs for the ABI.",
+            r#"{ rule = "wasm", module = "validators/check.wasm", fuel = 1_000_000_000 }"#,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::ContractType;
+
+    fn sample(ty: &str) -> &'static str {
+        match ty {
+            "string" => "\"x\"",
+            "integer" => "1",
+            "float" => "0.5",
+            "bool" => "true",
+            "string[]" => "[\"x\"]",
+            "table" => "{}",
+            other => panic!("unknown param type {}", other),
+        }
+    }
+
+    #[test]
+    fn every_catalog_entry_parses_as_a_contract_rule() {
+        for info in catalog() {
+            let mut fields = vec![format!("rule = \"{}\"", info.name)];
+            for p in info.params.iter().filter(|p| p.required) {
+                let value = match p.values.first() {
+                    Some(v) => format!("\"{}\"", v),
+                    None => sample(p.ty).to_string(),
+                };
+                fields.push(format!("{} = {}", p.name, value));
+            }
+            let src = format!("r = {{ {} }}", fields.join(", "));
+            let parsed: toml::Table = toml::from_str(&src).unwrap();
+            let value = parsed.get("r").unwrap().clone();
+            assert!(
+                value.try_into::<ContractType>().is_ok(),
+                "catalog entry '{}' does not parse",
+                info.name
+            );
+        }
+    }
+
+    #[test]
+    fn every_example_parses_as_its_rule() {
+        for info in catalog() {
+            let src = format!("r = {}", info.example);
+            let parsed: toml::Table = toml::from_str(&src)
+                .unwrap_or_else(|e| panic!("example for '{}' is invalid: {}", info.name, e));
+            let value = parsed.get("r").unwrap();
+            assert_eq!(value.get("rule").and_then(|r| r.as_str()), Some(info.name));
+            assert!(value.clone().try_into::<ContractType>().is_ok());
+            assert!(!info.notes.is_empty(), "'{}' has no notes", info.name);
+        }
+    }
+
+    #[test]
+    fn describes_a_single_rule() {
+        let rule = describe("outlier_sigma").unwrap();
+        assert_eq!(rule.params[0].name, "sigma");
+        assert!(rule.notes.contains("ddof=1"));
+        assert!(describe("no_such_rule").is_none());
+    }
+
+    #[test]
+    fn finds_rules_without_an_implementation() {
+        let contracts: SchemaContracts = toml::from_str(
+            r#"
+            [contract]
+            name = "t"
+            version = "1.0.0"
+            tags = []
+
+            [file]
+            validation = [{ rule = "row_count", min = 1 }, { rule = "exists" }]
+
+            [[columns]]
+            name = "id"
+            validation = [{ rule = "not_null" }, { rule = "row_count", min = 1 }]
+            "#,
+        )
+        .unwrap();
+
+        let found = unrecognized_rules(&contracts);
+        assert_eq!(
+            found,
+            vec![
+                UnrecognizedRule {
+                    column: "file".to_string(),
+                    rule: "exists"
+                },
+                UnrecognizedRule {
+                    column: "id".to_string(),
+                    rule: "row_count"
+                },
+            ]
+        );
+        assert!(is_supported("completeness", RuleScope::File));
+        assert!(!is_supported("min_between", RuleScope::Column));
+    }
+
+    #[test]
+    fn finds_rules_incompatible_with_declared_dtypes() {
+        let contracts: SchemaContracts = toml::from_str(
+            r#"
+            [contract]
+            name = "t"
+            version = "1.0.0"
+            tags = []
+
+            [[columns]]
+            name = "name"
+            dtype = "String"
+            validation = [{ rule = "mean_between", min = 0.0, max = 1.0 }, { rule = "not_null" }]
+
+            [[columns]]
+            name = "amount"
+            dtype = "Float64"
+            validation = [{ rule = "pattern", pattern = "^[0-9]+$" }, { rule = "outlier_sigma", sigma = 3.0 }]
+
+            [[columns]]
+            name = "untyped"
+            validation = [{ rule = "range", min = 0, max = 1 }]
+            "#,
+        )
+        .unwrap();
+
+        let found = type_mismatches(&contracts);
+        let rules: Vec<_> = found.iter().map(|m| (m.column.as_str(), m.rule)).collect();
+        assert_eq!(rules, vec![("name", "mean_between"), ("amount", "pattern")]);
+        assert!(found[0].to_string().contains("expects a numeric column"));
+        assert!(RuleInput::Numeric.accepts("UInt8"));
+        assert!(!RuleInput::Int64.accepts("Int32"));
+    }
+
+    #[test]
+    fn names_are_unique_and_sorted() {
+        let names: Vec<_> = catalog().iter().map(|r| r.name).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+    }
+}
@@ -0,0 +1,421 @@
+//! Adaptive sampling (`[sampling]`).
+//!
+//! For datasets above `min_rows`, rules that can be estimated from a random
+//! sample are evaluated on one, sized with Cochran's formula for the
+//! contract's `confidence` and `margin`. Each sampled result is annotated
+//! with a confidence interval:
+//! - row-level rules (`not_null`, `pattern`, `range`, ...) report the
+//!   violation rate; a violation in the sample is a violation in the data,
+//!   so sample failures are final, and sample passes report the upper bound,
+//! - threshold rules (`completeness`, `mean_between`, `stdev_between`)
+//!   report the estimated statistic and are re-run on the full data when
+//!   the interval straddles the threshold.
+//!
+//! Every other rule runs on the full data.
+
+use crate::contracts::{ContractType, Sampling};
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::validators::ValidationReport;
+use polars::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::fmt;
+
+/// Number of rows to sample from `rows`, or `None` when the dataset is
+/// small enough to validate in full.
+pub fn sample_size(rows: usize, sampling: &Sampling) -> Option<usize> {
+    if rows <= sampling.min_rows {
+        return None;
+    }
+    let z = z_score(sampling.confidence);
+    // Worst case p = 0.5, with finite population correction
+    let n0 = z * z * 0.25 / (sampling.margin * sampling.margin);
+    let n = n0 / (1.0 + (n0 - 1.0) / rows as f64);
+    Some((n.ceil() as usize).clamp(1, rows))
+}
+
+/// Whether `rule` is evaluated on the sample rather than the full data.
+pub fn is_sampled(rule: &ContractType) -> bool {
+    matches!(
+        rule,
+        ContractType::NotNull
+            | ContractType::Boolean
+            | ContractType::Range { .. }
+            | ContractType::Pattern { .. }
+            | ContractType::MaxLength { .. }
+            | ContractType::InSet { .. }
+            | ContractType::NotInSet { .. }
+            | ContractType::DateFormat { .. }
+            | ContractType::DateBetween { .. }
+            | ContractType::NoFutureDates { .. }
+            | ContractType::Uuid
+            | ContractType::Email
+            | ContractType::Url
+            | ContractType::Ipv4
+            | ContractType::Ipv6
+            | ContractType::PhoneE164
+            | ContractType::Completeness { .. }
+            | ContractType::MeanBetween { .. }
+            | ContractType::StdevBetween { .. }
+    )
+}
+
+/// A random sample of the dataset under validation.
+pub struct Sample {
+    df: DataFrame,
+    population: usize,
+    confidence: f64,
+    z: f64,
+}
+
+/// A confidence interval for a sampled statistic.
+struct Interval {
+    metric: &'static str,
+    estimate: f64,
+    low: f64,
+    high: f64,
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}={:.4} [{:.4}, {:.4}]",
+            self.metric, self.estimate, self.low, self.high
+        )
+    }
+}
+
+/// Whether a sampled threshold result settles the rule.
+enum Verdict {
+    Conclusive,
+    Escalate,
+}
+
+impl Sample {
+    /// Draw a sample of `df` when `sampling` applies to it.
+    pub fn draw(df: &DataFrame, sampling: &Sampling) -> ValidationResult<Option<Self>> {
+        if !(sampling.confidence > 0.0 && sampling.confidence < 1.0) {
+            return Err(ValidationError::Config(format!(
+                "sampling: confidence must be between 0 and 1, got {}",
+                sampling.confidence
+            )));
+        }
+        if !(sampling.margin > 0.0 && sampling.margin < 1.0) {
+            return Err(ValidationError::Config(format!(
+                "sampling: margin must be between 0 and 1, got {}",
+                sampling.margin
+            )));
+        }
+        let Some(n) = sample_size(df.height(), sampling) else {
+            return Ok(None);
+        };
+
+        let mut rng = match sampling.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut indices: Vec<IdxSize> = rand::seq::index::sample(&mut rng, df.height(), n)
+            .into_iter()
+            .map(|i| i as IdxSize)
+            .collect();
+        indices.sort_unstable();
+        let indices = IdxCa::from_vec("idx".into(), indices);
+
+        Ok(Some(Self {
+            df: df.take(&indices)?,
+            population: df.height(),
+            confidence: sampling.confidence,
+            z: z_score(sampling.confidence),
+        }))
+    }
+
+    /// Evaluate `rule` on the sample, re-running `validate` on `full` when a
+    /// threshold result is inconclusive. `column` is `None` for file rules.
+    pub fn evaluate<F>(
+        &self,
+        rule: &ContractType,
+        column: Option<&str>,
+        full: &DataFrame,
+        validate: F,
+    ) -> ValidationResult<ValidationReport>
+    where
+        F: Fn(&DataFrame) -> ValidationResult<ValidationReport>,
+    {
+        let report = validate(&self.df)?;
+        if report.status == "skipped" {
+            return Ok(report);
+        }
+
+        let (interval, verdict) = match rule {
+            ContractType::Completeness { min_ratio } => {
+                let interval = self.completeness(column)?;
+                let verdict = threshold_verdict(&interval, *min_ratio, f64::INFINITY);
+                (interval, verdict)
+            }
+            ContractType::MeanBetween { min, max } => match self.mean(column)? {
+                Some(interval) => {
+                    let verdict = threshold_verdict(&interval, *min, *max);
+                    (interval, verdict)
+                }
+                None => return Ok(report),
+            },
+            ContractType::StdevBetween { min, max } => match self.stdev(column)? {
+                Some(interval) => {
+                    let verdict = threshold_verdict(&interval, *min, *max);
+                    (interval, verdict)
+                }
+                None => return Ok(report),
+            },
+            _ => {
+                let violations = match report.status {
+                    "pass" => 0,
+                    _ => match violation_count(report.details.as_deref()) {
+                        Some(count) => count,
+                        None => return Ok(self.annotate(report, None)),
+                    },
+                };
+                let interval = self.proportion("violation_rate", violations, self.df.height());
+                (interval, Verdict::Conclusive)
+            }
+        };
+
+        match verdict {
+            Verdict::Conclusive => Ok(self.annotate(report, Some(&interval))),
+            Verdict::Escalate => {
+                let report = validate(full)?;
+                let note = format!(
+                    "escalated to full validation: sample {} at {}% confidence spans the threshold",
+                    interval,
+                    self.confidence * 100.0
+                );
+                Ok(ValidationReport {
+                    status: report.status,
+                    details: Some(join_details(report.details, note)),
+                })
+            }
+        }
+    }
+
+    /// Append the sample size and interval to a sampled report.
+    fn annotate(&self, report: ValidationReport, interval: Option<&Interval>) -> ValidationReport {
+        let mut note = format!("sampled rows={}/{}", self.df.height(), self.population);
+        if let Some(interval) = interval {
+            note.push_str(&format!(
+                ", {} at {}% confidence",
+                interval,
+                self.confidence * 100.0
+            ));
+        }
+        ValidationReport {
+            status: report.status,
+            details: Some(join_details(report.details, note)),
+        }
+    }
+
+    /// Wilson score interval for `successes` out of `trials`.
+    fn proportion(&self, metric: &'static str, successes: usize, trials: usize) -> Interval {
+        if trials == 0 {
+            return Interval {
+                metric,
+                estimate: 0.0,
+                low: 0.0,
+                high: 1.0,
+            };
+        }
+        let n = trials as f64;
+        let p = successes as f64 / n;
+        let z2 = self.z * self.z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half = self.z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+        Interval {
+            metric,
+            estimate: p,
+            low: (center - half).max(0.0),
+            high: (center + half).min(1.0),
+        }
+    }
+
+    /// Non-null ratio of a column, or of all cells for file-level rules.
+    fn completeness(&self, column: Option<&str>) -> ValidationResult<Interval> {
+        let (non_null, total) = match column {
+            Some(name) => {
+                let series = self.df.column(name)?;
+                (series.len() - series.null_count(), series.len())
+            }
+            None => {
+                let total = self.df.height() * self.df.width();
+                let nulls: usize = self.df.get_columns().iter().map(|c| c.null_count()).sum();
+                (total - nulls, total)
+            }
+        };
+        Ok(self.proportion("ratio", non_null, total))
+    }
+
+    /// Non-null values of a column as `Float64`, if numeric.
+    fn values(&self, column: Option<&str>) -> ValidationResult<Option<Float64Chunked>> {
+        let Some(name) = column else {
+            return Ok(None);
+        };
+        let series = self.df.column(name)?.as_materialized_series();
+        if !series.dtype().is_primitive_numeric() {
+            return Ok(None);
+        }
+        Ok(Some(series.cast(&DataType::Float64)?.f64()?.clone()))
+    }
+
+    /// Mean with a normal-approximation interval.
+    fn mean(&self, column: Option<&str>) -> ValidationResult<Option<Interval>> {
+        let Some(values) = self.values(column)? else {
+            return Ok(None);
+        };
+        let count = (values.len() - values.null_count()) as f64;
+        let (Some(mean), Some(stdev)) = (values.mean(), values.std(1)) else {
+            return Ok(None);
+        };
+        let half = self.z * stdev / count.sqrt();
+        Ok(Some(Interval {
+            metric: "mean",
+            estimate: mean,
+            low: mean - half,
+            high: mean + half,
+        }))
+    }
+
+    /// Sample standard deviation with a large-sample interval.
+    fn stdev(&self, column: Option<&str>) -> ValidationResult<Option<Interval>> {
+        let Some(values) = self.values(column)? else {
+            return Ok(None);
+        };
+        let count = (values.len() - values.null_count()) as f64;
+        let Some(stdev) = values.std(1).filter(|_| count > 1.0) else {
+            return Ok(None);
+        };
+        let half = self.z * stdev / (2.0 * (count - 1.0)).sqrt();
+        Ok(Some(Interval {
+            metric: "stdev",
+            estimate: stdev,
+            low: (stdev - half).max(0.0),
+            high: stdev + half,
+        }))
+    }
+}
+
+/// Conclusive when the interval lies entirely inside or outside `[min, max]`.
+fn threshold_verdict(interval: &Interval, min: f64, max: f64) -> Verdict {
+    let inside = interval.low >= min && interval.high <= max;
+    let outside = interval.high < min || interval.low > max;
+    if inside || outside {
+        Verdict::Conclusive
+    } else {
+        Verdict::Escalate
+    }
+}
+
+/// Number of violating rows reported by a row-level validator.
+fn violation_count(details: Option<&str>) -> Option<usize> {
+    details?.split(", ").find_map(|part| {
+        part.strip_prefix("bad_count=")
+            .or_else(|| part.strip_prefix("null_count="))?
+            .parse()
+            .ok()
+    })
+}
+
+fn join_details(details: Option<String>, note: String) -> String {
+    match details {
+        Some(details) => format!("{}; {}", details, note),
+        None => note,
+    }
+}
+
+/// Two-sided standard normal quantile for `confidence`
+/// (Abramowitz & Stegun 26.2.23, error < 4.5e-4).
+fn z_score(confidence: f64) -> f64 {
+    let p = (1.0 - confidence) / 2.0;
+    let t = (-2.0 * p.ln()).sqrt();
+    t - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+        / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::Validator;
+    use crate::validators::column::{CompletenessValidator, NotNullValidator};
+
+    fn sampling(min_rows: usize) -> Sampling {
+        Sampling {
+            min_rows,
+            seed: Some(7),
+            ..Sampling::default()
+        }
+    }
+
+    #[test]
+    fn sizes_sample_for_confidence_and_margin() {
+        assert!((z_score(0.95) - 1.96).abs() < 1e-3);
+        assert_eq!(sample_size(1_000, &sampling(1_000)), None);
+        // 1.96² · 0.25 / 0.01² ≈ 9604, reduced by the finite population correction
+        let n = sample_size(10_000_000, &sampling(0)).unwrap();
+        assert!((9_590..=9_610).contains(&n), "n = {}", n);
+        assert!(sample_size(20_000, &sampling(0)).unwrap() < 9_604);
+    }
+
+    #[test]
+    fn sampled_pass_reports_violation_bound() {
+        let values: Vec<i64> = (0..50_000).collect();
+        let df = df!("id" => values).unwrap();
+        let sample = Sample::draw(&df, &sampling(0)).unwrap().unwrap();
+
+        let report = sample
+            .evaluate(&ContractType::NotNull, Some("id"), &df, |df| {
+                NotNullValidator.validate(df, "id")
+            })
+            .unwrap();
+        assert_eq!(report.status, "pass");
+        let details = report.details.unwrap();
+        assert!(details.starts_with("sampled rows="), "{}", details);
+        assert!(details.contains("violation_rate=0.0000 [0.0000, 0.0"));
+    }
+
+    #[test]
+    fn escalates_when_interval_spans_threshold() {
+        // Exactly 95% non-null: the sample interval straddles min_ratio = 0.95
+        let values: Vec<Option<i64>> = (0..50_000)
+            .map(|i| if i % 20 == 0 { None } else { Some(i) })
+            .collect();
+        let df = df!("id" => values).unwrap();
+        let sample = Sample::draw(&df, &sampling(0)).unwrap().unwrap();
+
+        let rule = ContractType::Completeness { min_ratio: 0.95 };
+        let report = sample
+            .evaluate(&rule, Some("id"), &df, |df| {
+                CompletenessValidator { min_ratio: 0.95 }.validate(df, "id")
+            })
+            .unwrap();
+        assert_eq!(report.status, "pass");
+        assert!(
+            report
+                .details
+                .unwrap()
+                .contains("escalated to full validation")
+        );
+
+        // Far from the threshold the sample result stands
+        let rule = ContractType::Completeness { min_ratio: 0.5 };
+        let report = sample
+            .evaluate(&rule, Some("id"), &df, |df| {
+                CompletenessValidator { min_ratio: 0.5 }.validate(df, "id")
+            })
+            .unwrap();
+        assert!(report.details.unwrap().contains("ratio=0.9"));
+    }
+
+    #[test]
+    fn parses_violation_counts() {
+        assert_eq!(violation_count(Some("bad_count=3, min=0, max=5")), Some(3));
+        assert_eq!(violation_count(Some("null_count=12")), Some(12));
+        assert_eq!(violation_count(Some("outliers=2")), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! Combining a contract's `[[sources]]` into one dataset.
+//!
+//! Each source is fetched and parsed on its own (with its own driver and
+//! `[sources.options]`), then the DataFrames are folded left to right in
+//! `[[sources]]` order according to `[combine]`:
+//! - `concat` stacks rows; every source must have the same column names.
+//! - `join` joins on the `on` columns with the `how` join type. Key columns
+//!   appear once; other columns already present from an earlier source get
+//!   a `_<name>` suffix from the later source.
+//!
+//! Rules, statistics, and movement then see the combined DataFrame as if it
+//! were a single input. `[streaming]` does not apply to combined sources.
+
+use crate::contracts::{Combine, CombineMode, JoinHow, NamedSource, SchemaContracts};
+use crate::logging::error::{ValidationError, ValidationResult};
+use polars::prelude::*;
+
+/// The contract's `[[sources]]`, checked for a usable `[combine]`, or
+/// `None` when it declares a single `[source]`.
+pub fn declared_sources(contracts: &SchemaContracts) -> ValidationResult<Option<&[NamedSource]>> {
+    let Some(sources) = contracts.sources.as_deref() else {
+        return Ok(None);
+    };
+    if contracts.source.is_some() {
+        return Err(ValidationError::Config(
+            "Contract declares both [source] and [[sources]]; use one".to_string(),
+        ));
+    }
+    if sources.is_empty() {
+        return Err(ValidationError::Config(
+            "[[sources]] declares no sources".to_string(),
+        ));
+    }
+    for (i, source) in sources.iter().enumerate() {
+        if sources[..i].iter().any(|other| other.name == source.name) {
+            return Err(ValidationError::Config(format!(
+                "Duplicate source name '{}' in [[sources]]",
+                source.name
+            )));
+        }
+    }
+    let combine = contracts.combine.clone().unwrap_or_default();
+    if combine.mode == CombineMode::Join && combine.on.is_empty() {
+        return Err(ValidationError::Config(
+            "[combine] mode = \"join\" needs at least one `on` column".to_string(),
+        ));
+    }
+    Ok(Some(sources))
+}
+
+/// Fold `frames` (source name, parsed data) into one DataFrame.
+pub fn combine_frames(
+    frames: Vec<(&str, DataFrame)>,
+    combine: &Combine,
+) -> ValidationResult<DataFrame> {
+    let mut frames = frames.into_iter();
+    let Some((_, mut combined)) = frames.next() else {
+        return Ok(DataFrame::empty());
+    };
+    for (name, df) in frames {
+        combined = match combine.mode {
+            CombineMode::Concat => concat(combined, &df, name)?,
+            CombineMode::Join => join(&combined, &df, name, combine)?,
+        };
+    }
+    Ok(combined)
+}
+
+fn concat(mut combined: DataFrame, df: &DataFrame, name: &str) -> ValidationResult<DataFrame> {
+    let columns = combined.get_column_names_str();
+    let mut expected: Vec<&str> = columns.clone();
+    let mut actual = df.get_column_names_str();
+    expected.sort_unstable();
+    actual.sort_unstable();
+    if expected != actual {
+        return Err(ValidationError::Config(format!(
+            "Can't concat source '{}': columns [{}] differ from [{}]",
+            name,
+            actual.join(", "),
+            expected.join(", ")
+        )));
+    }
+    combined
+        .vstack_mut(&df.select(columns)?)
+        .map_err(|e| ValidationError::Config(format!("Can't concat source '{}': {}", name, e)))?;
+    Ok(combined)
+}
+
+fn join(
+    combined: &DataFrame,
+    df: &DataFrame,
+    name: &str,
+    combine: &Combine,
+) -> ValidationResult<DataFrame> {
+    let how = match combine.how {
+        JoinHow::Inner => JoinType::Inner,
+        JoinHow::Left => JoinType::Left,
+        JoinHow::Full => JoinType::Full,
+    };
+    let args = JoinArgs::new(how)
+        .with_coalesce(JoinCoalesce::CoalesceColumns)
+        .with_suffix(Some(format!("_{}", name).into()));
+    combined
+        .join(df, &combine.on, &combine.on, args, None)
+        .map_err(|e| ValidationError::Config(format!("Can't join source '{}': {}", name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> DataFrame {
+        df!("order_id" => [1i64, 2, 3], "amount" => [10i64, 20, 30]).unwrap()
+    }
+
+    fn detail() -> DataFrame {
+        df!("order_id" => [1i64, 1, 4], "amount" => [4i64, 6, 9]).unwrap()
+    }
+
+    #[test]
+    fn concat_stacks_rows_in_first_source_column_order() {
+        let reordered = detail().select(["amount", "order_id"]).unwrap();
+        let combined = combine_frames(
+            vec![("header", header()), ("detail", reordered)],
+            &Combine::default(),
+        )
+        .unwrap();
+        assert_eq!(combined.height(), 6);
+        assert_eq!(combined.get_column_names_str(), vec!["order_id", "amount"]);
+
+        let other = df!("id" => [1i64]).unwrap();
+        let err = combine_frames(vec![("a", header()), ("b", other)], &Combine::default());
+        assert!(
+            err.unwrap_err()
+                .to_string()
+                .contains("Can't concat source 'b'")
+        );
+    }
+
+    #[test]
+    fn left_join_suffixes_clashing_columns_and_nulls_orphans() {
+        let combine = Combine {
+            mode: CombineMode::Join,
+            on: vec!["order_id".to_string()],
+            how: JoinHow::Left,
+        };
+        let combined =
+            combine_frames(vec![("detail", detail()), ("header", header())], &combine).unwrap();
+        assert_eq!(
+            combined.get_column_names_str(),
+            vec!["order_id", "amount", "amount_header"]
+        );
+        assert_eq!(combined.height(), 3);
+        assert_eq!(combined.column("amount_header").unwrap().null_count(), 1);
+    }
+}
@@ -0,0 +1,962 @@
+//! Streaming validation for inputs larger than memory.
+//!
+//! With `[streaming]`, `execute_validation` reads inputs of at least
+//! `min_bytes` in batches of `chunk_rows` rows (`Payload::load_batches`)
+//! instead of parsing them into one DataFrame:
+//! - row-level rules (`not_null`, `pattern`, `range`, `in_set`, ...), and
+//!   `custom`/`wasm` rules, run on every batch; the counts in their details
+//!   (`null_count`, `bad_count`, ...) are summed across batches;
+//! - aggregate rules (`unique`, `distinctness`, `completeness`,
+//!   `mean_between`, `stdev_between`, `drift`, `row_count`, file
+//!   `completeness`, `compound_unique`) are evaluated once from statistics
+//!   accumulated across batches;
+//! - `outlier_sigma` takes a second pass once the mean and standard
+//!   deviation are known.
+//!
+//! Exact distinct counts (`unique`, `distinctness`, `compound_unique`, and
+//! `drift` on `distinct_ratio`) keep a 64-bit hash per distinct value of
+//! the columns involved. Distinct ratios recorded in run history for other
+//! columns are HyperLogLog estimates (about 1% error), so memory stays flat
+//! however many rows are read.
+//!
+//! `max_rows` and `max_wall_time_secs` are checked after every batch; once
+//! one is hit, reading stops and every rule is reported as aborted.
+//! `[sampling]` does not apply to streamed inputs.
+
+use crate::connectors::spool::Payload;
+use crate::contracts::{ContractType, DriftMetric};
+use crate::drivers::Driver;
+use crate::engine::plan::ExecutionPlan;
+use crate::engine::validation::{
+    Budget, column_validator, drift_validator, file_validator, run_rules, schema_conformance,
+    unrecognized, validate_dataframe_with,
+};
+use crate::environment::RunProgress;
+use crate::history::{ColumnStats, RunHistory, RunStats};
+use crate::logging::error::ValidationResult;
+use crate::logging::schema::RuleResult;
+use crate::validators::column::{
+    CompletenessValidator, DistinctnessValidator, DriftValidator, MeanBetweenValidator,
+    OutlierSigmaValidator, StdevBetweenValidator, UniqueValidator,
+};
+use crate::validators::compound::CompoundUniqueValidator;
+use crate::validators::file::{FileCompletenessValidator, RowCountValidator};
+use crate::validators::{CompoundValidator, FileValidator, ValidationReport, Validator};
+use polars::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::ControlFlow;
+
+/// Counts summed across batches when merging per-batch reports.
+const COUNT_FIELDS: [&str; 4] = ["null_count", "bad_count", "pass", "fail"];
+
+/// Register index bits of the HyperLogLog estimator (2^14 registers).
+const HLL_BITS: u32 = 14;
+
+/// Outcome of validating a streamed input.
+pub(crate) struct Streamed {
+    pub results: Vec<RuleResult>,
+    /// Statistics for run history; `None` when a limit stopped reading early.
+    pub stats: Option<RunStats>,
+    pub rows: usize,
+    pub batches: usize,
+}
+
+/// Validate `data` batch by batch.
+///
+/// Results are in the same order, and carry the same details, as
+/// `validate_plan` on the whole input. An input that yields no
+/// batches (e.g. a header-only CSV) is validated whole.
+pub(crate) fn validate_stream(
+    data: &Payload,
+    driver: &dyn Driver,
+    plan: &ExecutionPlan,
+    history: &dyn RunHistory,
+    run_id: &str,
+    chunk_rows: usize,
+    progress: &dyn RunProgress,
+) -> ValidationResult<Streamed> {
+    let contracts = &plan.contracts;
+    let mut checks = plan_checks(plan, history)?;
+    let mut stream = Stream::new(exact_columns(plan));
+    let mut budget = Budget::new(contracts.limits.as_ref());
+    let max_rows = contracts.limits.as_ref().and_then(|l| l.max_rows);
+
+    let mut failure = None;
+    let mut stopped: Option<String> = None;
+    data.load_batches(driver, chunk_rows, &mut |batch| {
+        progress.parsed(batch.height());
+        if let Err(e) = stream.push(&batch, &mut checks) {
+            failure = Some(e);
+            return Ok(ControlFlow::Break(()));
+        }
+        if let Some(max_rows) = max_rows.filter(|max_rows| stream.rows > *max_rows) {
+            stopped = Some(format!(
+                "limit exceeded: rows={} > max_rows={}",
+                stream.rows, max_rows
+            ));
+            return Ok(ControlFlow::Break(()));
+        }
+        if let Some(reason) = budget.check() {
+            stopped = Some(reason.to_string());
+            return Ok(ControlFlow::Break(()));
+        }
+        Ok(ControlFlow::Continue(()))
+    })?;
+    if let Some(e) = failure {
+        return Err(e);
+    }
+
+    if let Some(reason) = stopped {
+        let results = run_rules(
+            &DataFrame::empty(),
+            plan,
+            history,
+            Budget::exhausted(reason),
+            None,
+            progress,
+        )?;
+        return Ok(Streamed {
+            results,
+            stats: None,
+            rows: stream.rows,
+            batches: stream.batches,
+        });
+    }
+
+    let Some(schema) = stream.schema.take() else {
+        let df = data.load(driver)?;
+        return Ok(Streamed {
+            results: validate_dataframe_with(&df, plan, history, progress)?,
+            stats: Some(RunStats::from_dataframe(
+                &df,
+                &contracts.contract.version,
+                run_id,
+            )?),
+            rows: df.height(),
+            batches: 0,
+        });
+    };
+
+    count_outliers(data, driver, chunk_rows, &mut checks, &stream.profiles)?;
+
+    let mut results = Vec::new();
+    if contracts.contract.strict_types {
+        results.extend(schema_conformance(&schema, contracts));
+    }
+    for planned in checks {
+        results.push(planned.finish(&stream)?);
+    }
+
+    let columns = stream
+        .profiles
+        .iter()
+        .map(|(name, profile)| (name.clone(), profile.stats()))
+        .collect();
+    Ok(Streamed {
+        results,
+        stats: Some(RunStats::new(
+            stream.rows,
+            columns,
+            &contracts.contract.version,
+            run_id,
+        )),
+        rows: stream.rows,
+        batches: stream.batches,
+    })
+}
+
+/// One declared rule and how it is evaluated over the batches.
+struct Planned {
+    /// `"file"`, `"compound"`, or the column the rule is declared on.
+    column: String,
+    check: Check,
+}
+
+enum Check {
+    /// Decided before reading, e.g. a rule with no implementation.
+    Settled(RuleResult),
+    /// Run on every batch; the reports are merged at the end.
+    Column {
+        validator: Box<dyn Validator>,
+        reports: Vec<ValidationReport>,
+    },
+    File {
+        validator: Box<dyn FileValidator>,
+        reports: Vec<ValidationReport>,
+    },
+    /// Evaluated once from accumulated statistics.
+    Aggregate(Aggregate),
+}
+
+enum Aggregate {
+    RowCount(RowCountValidator),
+    /// With the number of fully populated rows so far.
+    FileCompleteness(FileCompletenessValidator, usize),
+    Completeness(CompletenessValidator),
+    Unique(UniqueValidator),
+    Distinctness(DistinctnessValidator),
+    MeanBetween(MeanBetweenValidator),
+    StdevBetween(StdevBetweenValidator),
+    /// With the outliers counted by the second pass, once it has run.
+    OutlierSigma(OutlierSigmaValidator, Option<usize>),
+    Drift(DriftValidator),
+    /// With hashes of the distinct value combinations so far.
+    CompoundUnique(CompoundUniqueValidator, HashSet<u64>),
+}
+
+impl Aggregate {
+    fn name(&self) -> &'static str {
+        match self {
+            Aggregate::RowCount(v) => v.name(),
+            Aggregate::FileCompleteness(v, _) => v.name(),
+            Aggregate::Completeness(v) => v.name(),
+            Aggregate::Unique(v) => v.name(),
+            Aggregate::Distinctness(v) => v.name(),
+            Aggregate::MeanBetween(v) => v.name(),
+            Aggregate::StdevBetween(v) => v.name(),
+            Aggregate::OutlierSigma(v, _) => v.name(),
+            Aggregate::Drift(v) => Validator::name(v),
+            Aggregate::CompoundUnique(v, _) => v.name(),
+        }
+    }
+}
+
+impl Planned {
+    /// The rule's result over the whole input.
+    fn finish(self, stream: &Stream) -> ValidationResult<RuleResult> {
+        let (rule, report) = match self.check {
+            Check::Settled(result) => return Ok(result),
+            Check::Column { validator, reports } => (validator.name(), merge_reports(reports)),
+            Check::File { validator, reports } => (validator.name(), merge_reports(reports)),
+            Check::Aggregate(aggregate) => {
+                let profile = stream.profiles.get(&self.column);
+                (
+                    aggregate.name(),
+                    aggregate_report(&aggregate, profile, stream.rows),
+                )
+            }
+        };
+        Ok(RuleResult {
+            column: self.column,
+            rule: rule.to_string(),
+            result: report.status.to_string(),
+            details: report.details,
+            duration_ms: None,
+        })
+    }
+}
+
+/// Report for an aggregate rule from the statistics of its column.
+///
+/// Column-level rules always have a profile: a missing column fails the
+/// first batch, as it would fail `validate_plan`.
+fn aggregate_report(
+    aggregate: &Aggregate,
+    profile: Option<&ColumnProfile>,
+    rows: usize,
+) -> ValidationReport {
+    let numeric = |report: &dyn Fn(&ColumnProfile) -> ValidationReport| match profile {
+        Some(profile) if profile.castable => report(profile),
+        _ => ValidationReport::not_numeric(),
+    };
+    match aggregate {
+        Aggregate::RowCount(v) => v.report(rows),
+        Aggregate::FileCompleteness(v, complete) => v.report(*complete, rows),
+        Aggregate::CompoundUnique(v, seen) => v.report(seen.len(), rows),
+        Aggregate::Completeness(v) => {
+            let nulls = profile.map_or(0, |p| p.nulls);
+            v.report(nulls, rows)
+        }
+        Aggregate::Unique(v) => v.report(profile.map_or(0, ColumnProfile::n_unique), rows),
+        Aggregate::Distinctness(v) => v.report(profile.map_or(0, ColumnProfile::n_unique), rows),
+        Aggregate::MeanBetween(v) => numeric(&|p| v.report(p.moments.mean())),
+        Aggregate::StdevBetween(v) => numeric(&|p| v.report(p.moments.std())),
+        Aggregate::OutlierSigma(v, outliers) => numeric(&|p| match (
+            v.bounds(p.moments.mean(), p.moments.std()),
+            outliers,
+        ) {
+            (Err(report), _) => report,
+            (Ok(_), Some(outliers)) => v.report(*outliers),
+            (Ok(_), None) => ValidationReport {
+                status: "aborted",
+                details: Some("outliers were not counted".to_string()),
+            },
+        }),
+        Aggregate::Drift(v) => {
+            let current = match v.metric {
+                DriftMetric::RowCount => Some(rows as f64),
+                metric => profile.and_then(|p| p.stats().metric(metric)),
+            };
+            v.report(current)
+        }
+    }
+}
+
+/// Decide how each declared rule is evaluated, in `run_rules` order.
+fn plan_checks(plan: &ExecutionPlan, history: &dyn RunHistory) -> ValidationResult<Vec<Planned>> {
+    let contracts = &plan.contracts;
+    let strict_rules = contracts.contract.strict_rules;
+    let mut references = HashMap::new();
+    let mut checks = Vec::new();
+
+    if let Some(file_contracts) = &contracts.file {
+        for rule in &file_contracts.validation {
+            let check = match rule {
+                ContractType::RowCount { min, max } => {
+                    Check::Aggregate(Aggregate::RowCount(RowCountValidator {
+                        min: *min,
+                        max: *max,
+                    }))
+                }
+                ContractType::Completeness { min_ratio } => {
+                    Check::Aggregate(Aggregate::FileCompleteness(
+                        FileCompletenessValidator {
+                            min_ratio: *min_ratio,
+                        },
+                        0,
+                    ))
+                }
+                _ => match file_validator(rule)? {
+                    Some(validator) => Check::File {
+                        validator,
+                        reports: Vec::new(),
+                    },
+                    None => Check::Settled(unrecognized("file", rule, strict_rules)?),
+                },
+            };
+            checks.push(Planned {
+                column: "file".to_string(),
+                check,
+            });
+        }
+    }
+
+    for col in &contracts.columns {
+        for rule in &col.validation {
+            let aggregate = match rule {
+                ContractType::Unique => Some(Aggregate::Unique(UniqueValidator)),
+                ContractType::Distinctness { min_ratio } => {
+                    Some(Aggregate::Distinctness(DistinctnessValidator {
+                        min_ratio: *min_ratio,
+                    }))
+                }
+                ContractType::Completeness { min_ratio } => {
+                    Some(Aggregate::Completeness(CompletenessValidator {
+                        min_ratio: *min_ratio,
+                    }))
+                }
+                ContractType::MeanBetween { min, max } => {
+                    Some(Aggregate::MeanBetween(MeanBetweenValidator {
+                        min: *min,
+                        max: *max,
+                    }))
+                }
+                ContractType::StdevBetween { min, max } => {
+                    Some(Aggregate::StdevBetween(StdevBetweenValidator {
+                        min: *min,
+                        max: *max,
+                    }))
+                }
+                ContractType::OutlierSigma { sigma } => Some(Aggregate::OutlierSigma(
+                    OutlierSigmaValidator { sigma: *sigma },
+                    None,
+                )),
+                ContractType::Drift {
+                    metric,
+                    max_change,
+                    against,
+                } => Some(Aggregate::Drift(drift_validator(
+                    *metric,
+                    *max_change,
+                    *against,
+                    &col.name,
+                    plan,
+                    history,
+                    &mut references,
+                )?)),
+                _ => None,
+            };
+            let check = match aggregate {
+                Some(aggregate) => Check::Aggregate(aggregate),
+                None => match column_validator(rule, &col.name, plan, history, &mut references)? {
+                    Some(validator) => Check::Column {
+                        validator,
+                        reports: Vec::new(),
+                    },
+                    None => Check::Settled(unrecognized(&col.name, rule, strict_rules)?),
+                },
+            };
+            checks.push(Planned {
+                column: col.name.clone(),
+                check,
+            });
+        }
+    }
+
+    for cu in contracts.compound_unique.iter().flatten() {
+        checks.push(Planned {
+            column: "compound".to_string(),
+            check: Check::Aggregate(Aggregate::CompoundUnique(
+                CompoundUniqueValidator {
+                    columns: cu.columns.clone(),
+                },
+                HashSet::new(),
+            )),
+        });
+    }
+    Ok(checks)
+}
+
+/// Columns whose distinct values are counted exactly.
+fn exact_columns(plan: &ExecutionPlan) -> HashSet<String> {
+    plan.contracts
+        .columns
+        .iter()
+        .filter(|col| {
+            col.validation.iter().any(|rule| {
+                matches!(
+                    rule,
+                    ContractType::Unique
+                        | ContractType::Distinctness { .. }
+                        | ContractType::Drift {
+                            metric: DriftMetric::DistinctRatio,
+                            ..
+                        }
+                )
+            })
+        })
+        .map(|col| col.name.clone())
+        .collect()
+}
+
+/// State accumulated over the batches read so far.
+struct Stream {
+    exact: HashSet<String>,
+    /// The first batch with its rows removed, for schema-level checks.
+    schema: Option<DataFrame>,
+    profiles: BTreeMap<String, ColumnProfile>,
+    rows: usize,
+    batches: usize,
+}
+
+impl Stream {
+    fn new(exact: HashSet<String>) -> Self {
+        Self {
+            exact,
+            schema: None,
+            profiles: BTreeMap::new(),
+            rows: 0,
+            batches: 0,
+        }
+    }
+
+    /// Fold one batch into the column statistics and every rule.
+    fn push(&mut self, batch: &DataFrame, checks: &mut [Planned]) -> ValidationResult<()> {
+        if self.schema.is_none() {
+            for planned in checks.iter() {
+                if let Check::Aggregate(aggregate) = &planned.check {
+                    match aggregate {
+                        Aggregate::RowCount(_) | Aggregate::FileCompleteness(..) => {}
+                        Aggregate::CompoundUnique(v, _) => {
+                            batch.select(v.columns.iter().cloned())?;
+                        }
+                        _ => {
+                            batch.column(&planned.column)?;
+                        }
+                    }
+                }
+            }
+            self.schema = Some(batch.clear());
+        }
+        self.rows += batch.height();
+        self.batches += 1;
+
+        for column in batch.get_columns() {
+            let series = column.as_materialized_series();
+            let name = series.name().to_string();
+            let exact = self.exact.contains(&name);
+            self.profiles
+                .entry(name)
+                .or_insert_with(|| ColumnProfile::new(series.dtype(), exact))
+                .update(series)?;
+        }
+
+        for planned in checks.iter_mut() {
+            match &mut planned.check {
+                Check::Column { validator, reports } => {
+                    reports.push(validator.validate(batch, &planned.column)?)
+                }
+                Check::File { validator, reports } => reports.push(validator.validate(batch)?),
+                Check::Aggregate(Aggregate::FileCompleteness(_, complete))
+                    if batch.height() > 0 =>
+                {
+                    *complete += FileCompletenessValidator::complete_rows(batch)?;
+                }
+                Check::Aggregate(Aggregate::CompoundUnique(v, seen)) => {
+                    seen.extend(row_hashes(batch, &v.columns)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Second pass for `outlier_sigma` rules whose bounds are known.
+fn count_outliers(
+    data: &Payload,
+    driver: &dyn Driver,
+    chunk_rows: usize,
+    checks: &mut [Planned],
+    profiles: &BTreeMap<String, ColumnProfile>,
+) -> ValidationResult<()> {
+    let mut pending: Vec<(&mut Option<usize>, &str, f64, f64)> = Vec::new();
+    for planned in checks.iter_mut() {
+        if let Check::Aggregate(Aggregate::OutlierSigma(v, outliers)) = &mut planned.check {
+            let Some(profile) = profiles.get(&planned.column).filter(|p| p.castable) else {
+                continue;
+            };
+            if let Ok((mean, threshold)) = v.bounds(profile.moments.mean(), profile.moments.std()) {
+                *outliers = Some(0);
+                pending.push((outliers, &planned.column, mean, threshold));
+            }
+        }
+    }
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let mut failure = None;
+    data.load_batches(driver, chunk_rows, &mut |batch| {
+        for (outliers, column, mean, threshold) in pending.iter_mut() {
+            let counted = batch
+                .column(column)
+                .and_then(|series| series.strict_cast(&DataType::Float64))
+                .map_err(Into::into)
+                .and_then(|values| {
+                    OutlierSigmaValidator::count_outliers(values.f64()?, *mean, *threshold)
+                });
+            match counted {
+                Ok(count) => {
+                    if let Some(total) = outliers.as_mut() {
+                        *total += count;
+                    }
+                }
+                Err(e) => {
+                    failure = Some(e);
+                    return Ok(ControlFlow::Break(()));
+                }
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    })?;
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Combine one rule's per-batch reports into a report for the whole input.
+///
+/// The most severe status wins (`aborted`, then `fail`, `pass`,
+/// `skipped`); the first report with it is kept, with every count field
+/// in its details summed over all batches.
+fn merge_reports(reports: Vec<ValidationReport>) -> ValidationReport {
+    let severity = |status: &str| match status {
+        "aborted" => 3,
+        "fail" => 2,
+        "pass" => 1,
+        _ => 0,
+    };
+    let Some(merged) =
+        reports
+            .iter()
+            .fold(None, |worst: Option<&ValidationReport>, r| match worst {
+                Some(w) if severity(w.status) >= severity(r.status) => Some(w),
+                _ => Some(r),
+            })
+    else {
+        return ValidationReport {
+            status: "skipped",
+            details: Some("no rows were read".to_string()),
+        };
+    };
+
+    let total = |key: &str| -> u64 {
+        reports
+            .iter()
+            .filter_map(|r| r.details.as_deref().and_then(|d| count_field(d, key)))
+            .sum()
+    };
+    let details = merged.details.as_ref().map(|details| {
+        details
+            .split(", ")
+            .map(|field| match field.split_once('=') {
+                Some((key, _)) if COUNT_FIELDS.contains(&key) => {
+                    format!("{}={}", key, total(key))
+                }
+                _ => field.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+    ValidationReport {
+        status: merged.status,
+        details,
+    }
+}
+
+/// Value of a `key=<integer>` field in report details.
+fn count_field(details: &str, key: &str) -> Option<u64> {
+    details.split(", ").find_map(|field| {
+        field
+            .split_once('=')
+            .filter(|(k, _)| *k == key)
+            .and_then(|(_, v)| v.parse().ok())
+    })
+}
+
+/// Statistics for one column, accumulated batch by batch.
+struct ColumnProfile {
+    /// Primitive numeric dtype: run history records its mean and stdev.
+    numeric: bool,
+    /// Every batch cast to Float64, as numeric rules require.
+    castable: bool,
+    rows: usize,
+    nulls: usize,
+    moments: Moments,
+    distinct: Distinct,
+}
+
+impl ColumnProfile {
+    fn new(dtype: &DataType, exact: bool) -> Self {
+        Self {
+            numeric: dtype.is_primitive_numeric(),
+            castable: true,
+            rows: 0,
+            nulls: 0,
+            moments: Moments::default(),
+            distinct: if exact {
+                Distinct::Exact(HashSet::new())
+            } else {
+                Distinct::Estimated(HyperLogLog::new())
+            },
+        }
+    }
+
+    fn update(&mut self, series: &Series) -> ValidationResult<()> {
+        self.rows += series.len();
+        self.nulls += series.null_count();
+        if self.castable {
+            match series.strict_cast(&DataType::Float64) {
+                Ok(values) => self.moments.merge(&Moments::of(values.f64()?)),
+                Err(_) => self.castable = false,
+            }
+        }
+        let text = series.cast(&DataType::String)?;
+        for value in text.str()?.into_iter().flatten() {
+            self.distinct.insert(hash_value(&value));
+        }
+        Ok(())
+    }
+
+    /// Distinct values, counting null as one value like `Series::n_unique`.
+    fn n_unique(&self) -> usize {
+        self.distinct.count() + usize::from(self.nulls > 0)
+    }
+
+    /// Statistics as recorded in run history (see `ColumnStats::from_series`).
+    fn stats(&self) -> ColumnStats {
+        let ratio = |count: usize| {
+            if self.rows == 0 {
+                0.0
+            } else {
+                count as f64 / self.rows as f64
+            }
+        };
+        ColumnStats {
+            null_ratio: ratio(self.nulls),
+            distinct_ratio: ratio(self.distinct.count()),
+            mean: self.moments.mean().filter(|_| self.numeric),
+            stdev: self.moments.std().filter(|_| self.numeric),
+        }
+    }
+}
+
+/// Running count, mean, and sum of squared deviations, merged per batch
+/// with Chan et al.'s parallel update.
+#[derive(Default)]
+struct Moments {
+    count: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    fn of(values: &Float64Chunked) -> Self {
+        let count = (values.len() - values.null_count()) as f64;
+        match (values.mean(), values.var(0)) {
+            (Some(mean), Some(var)) => Self {
+                count,
+                mean,
+                m2: var * count,
+            },
+            _ => Self::default(),
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.count == 0.0 {
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count / count;
+        self.m2 += other.m2 + delta * delta * self.count * other.count / count;
+        self.count = count;
+    }
+
+    fn mean(&self) -> Option<f64> {
+        (self.count > 0.0).then_some(self.mean)
+    }
+
+    /// Sample standard deviation (ddof = 1).
+    fn std(&self) -> Option<f64> {
+        (self.count > 1.0).then(|| (self.m2 / (self.count - 1.0)).max(0.0).sqrt())
+    }
+}
+
+/// Distinct non-null values of a column.
+enum Distinct {
+    /// A hash per distinct value.
+    Exact(HashSet<u64>),
+    Estimated(HyperLogLog),
+}
+
+impl Distinct {
+    fn insert(&mut self, hash: u64) {
+        match self {
+            Distinct::Exact(hashes) => {
+                hashes.insert(hash);
+            }
+            Distinct::Estimated(hll) => hll.insert(hash),
+        }
+    }
+
+    fn count(&self) -> usize {
+        match self {
+            Distinct::Exact(hashes) => hashes.len(),
+            Distinct::Estimated(hll) => hll.estimate(),
+        }
+    }
+}
+
+/// HyperLogLog distinct-count estimate in fixed memory.
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new() -> Self {
+        Self {
+            registers: vec![0; 1 << HLL_BITS],
+        }
+    }
+
+    fn insert(&mut self, hash: u64) {
+        let index = (hash >> (64 - HLL_BITS)) as usize;
+        let rank = ((hash << HLL_BITS) | (1 << (HLL_BITS - 1))).leading_zeros() + 1;
+        self.registers[index] = self.registers[index].max(rank as u8);
+    }
+
+    fn estimate(&self) -> usize {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&r| 2f64.powi(-i32::from(r)))
+            .sum();
+        let estimate = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        // Linear counting is more accurate while many registers are empty.
+        if estimate <= 2.5 * m && zeros > 0 {
+            (m * (m / zeros as f64).ln()).round() as usize
+        } else {
+            estimate.round() as usize
+        }
+    }
+}
+
+fn hash_value(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// One hash per row of the values in `columns`, nulls included.
+fn row_hashes(batch: &DataFrame, columns: &[String]) -> ValidationResult<Vec<u64>> {
+    let texts = columns
+        .iter()
+        .map(|name| batch.column(name)?.cast(&DataType::String))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let values = texts
+        .iter()
+        .map(|text| text.str())
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    Ok((0..batch.height())
+        .map(|row| {
+            let mut hasher = DefaultHasher::new();
+            for column in &values {
+                column.get(row).hash(&mut hasher);
+            }
+            hasher.finish()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::csv::CsvDriver;
+    use crate::engine::validation::validate_plan;
+    use crate::environment::NoProgress;
+    use crate::history::MemoryHistory;
+
+    const CONTRACT: &str = r#"
+[contract]
+name = "streamed"
+version = "1.0.0"
+tags = []
+
+[file]
+validation = [{ rule = "row_count", min = 1 }, { rule = "completeness", min_ratio = 0.5 }]
+
+[[columns]]
+name = "id"
+validation = [{ rule = "unique" }, { rule = "not_null" }]
+
+[[columns]]
+name = "amount"
+validation = [
+  { rule = "mean_between", min = 0.0, max = 100.0 },
+  { rule = "stdev_between", min = 0.0, max = 100.0 },
+  { rule = "outlier_sigma", sigma = 2.0 },
+  { rule = "completeness", min_ratio = 0.5 },
+]
+
+[[columns]]
+name = "code"
+validation = [{ rule = "pattern", pattern = "^[A-C]$" }, { rule = "distinctness", min_ratio = 0.1 }]
+
+[[compound_unique]]
+columns = ["id", "code"]
+"#;
+
+    fn data() -> String {
+        let mut csv = String::from("id,amount,code\n");
+        for i in 0..250 {
+            let amount = if i == 7 { 500 } else { i % 50 };
+            let code = ["A", "B", "C", "D"][i % 4];
+            let id = if i == 249 { 0 } else { i };
+            csv.push_str(&format!("{},{},{}\n", id, amount, code));
+        }
+        csv.push_str("250,,A\n");
+        csv
+    }
+
+    #[test]
+    fn batched_results_match_whole_dataframe() {
+        let plan = ExecutionPlan::from_toml(CONTRACT).unwrap();
+        let history = MemoryHistory::default();
+        let driver = CsvDriver::default();
+        let data = Payload::Memory(data().into_bytes());
+
+        let whole = validate_plan(&data.load(&driver).unwrap(), &plan, &history).unwrap();
+        let streamed =
+            validate_stream(&data, &driver, &plan, &history, "run-1", 40, &NoProgress).unwrap();
+
+        assert_eq!(streamed.batches, 7);
+        assert_eq!(streamed.rows, 251);
+        assert_eq!(streamed.results.len(), whole.len());
+        for (streamed, whole) in streamed.results.iter().zip(&whole) {
+            assert_eq!(
+                (&streamed.rule, &streamed.result, &streamed.details),
+                (&whole.rule, &whole.result, &whole.details),
+                "{}",
+                whole.column
+            );
+        }
+        let stats = streamed.stats.unwrap();
+        assert_eq!(stats.rows, 251);
+        assert!((stats.columns["code"].distinct_ratio - 4.0 / 251.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn row_limit_stops_reading_and_aborts_rules() {
+        let toml = format!("{}\n[limits]\nmax_rows = 100\n", CONTRACT);
+        let plan = ExecutionPlan::from_toml(&toml).unwrap();
+        let data = Payload::Memory(data().into_bytes());
+
+        let streamed = validate_stream(
+            &data,
+            &CsvDriver::default(),
+            &plan,
+            &MemoryHistory::default(),
+            "run-1",
+            40,
+            &NoProgress,
+        )
+        .unwrap();
+
+        assert_eq!(streamed.batches, 3);
+        assert!(streamed.stats.is_none());
+        assert!(streamed.results.iter().all(|r| r.result == "aborted"));
+    }
+
+    #[test]
+    fn strict_types_reports_schema_conformance_from_the_streamed_schema() {
+        let toml = CONTRACT
+            .replace("tags = []", "tags = []\nstrict_types = true")
+            .replace("name = \"id\"\n", "name = \"id\"\ndtype = \"Int64\"\n")
+            .replace("name = \"code\"\n", "name = \"code\"\ndtype = \"Int64\"\n");
+        let plan = ExecutionPlan::from_toml(&toml).unwrap();
+        let data = Payload::Memory(data().into_bytes());
+
+        let streamed = validate_stream(
+            &data,
+            &CsvDriver::default(),
+            &plan,
+            &MemoryHistory::default(),
+            "run-1",
+            40,
+            &NoProgress,
+        )
+        .unwrap();
+
+        let conformance: Vec<_> = streamed
+            .results
+            .iter()
+            .take(3)
+            .map(|r| {
+                assert_eq!(r.rule, "SchemaConformance");
+                (r.column.as_str(), r.result.as_str(), r.details.as_deref())
+            })
+            .collect();
+        assert_eq!(
+            conformance,
+            vec![
+                ("id", "pass", Some("declared=Int64, actual=Int64")),
+                (
+                    "amount",
+                    "fail",
+                    Some("declared=none, actual=Int64 (strict_types requires dtype)")
+                ),
+                ("code", "fail", Some("declared=Int64, actual=String")),
+            ]
+        );
+        assert_ne!(streamed.results[3].rule, "SchemaConformance");
+    }
+}
@@ -1,22 +1,40 @@
+use crate::config::{contracts_dir, logs_dir};
+use crate::engine::profiles::check_profile;
 use crate::logging::AuditLogger;
-use crate::logging::schema::{AuditLogEntry, Executor};
-use crate::profiles::load_profiles;
+use crate::logging::schema::{AuditLogEntry, Executor, Target};
+use crate::profiles::{Profiles, load_profiles};
 use chrono::Utc;
 use hostname;
-use std::path::Path as StdPath;
+use std::time::{Duration, Instant};
 use whoami;
 
+/// How long a deep health check waits for one profile.
+const PROFILE_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
 #[derive(Debug)]
 pub struct HealthStatus {
     pub healthy: bool,
     pub contracts_dir_exists: bool,
     pub logs_dir_exists: bool,
     pub profile_count: usize,
+    /// Per-profile connectivity; empty unless the check was deep.
+    pub profiles: Vec<ProfileHealth>,
+}
+
+/// Connectivity of one profile in a deep health check.
+#[derive(Debug, Clone)]
+pub struct ProfileHealth {
+    pub name: String,
+    pub provider: String,
+    pub connected: bool,
+    pub latency: Duration,
+    /// Why the connection test failed.
+    pub error: Option<String>,
 }
 
 pub fn check_system_health() -> HealthStatus {
-    let contracts_exist = StdPath::new("contracts").exists();
-    let logs_exist = StdPath::new("logs").exists();
+    let contracts_exist = contracts_dir().exists();
+    let logs_exist = logs_dir().exists();
     let profile_count = load_profiles().map(|p| p.len()).unwrap_or(0);
 
     HealthStatus {
@@ -24,10 +42,62 @@ pub fn check_system_health() -> HealthStatus {
         contracts_dir_exists: contracts_exist,
         logs_dir_exists: logs_exist,
         profile_count,
+        profiles: Vec::new(),
     }
 }
 
+/// Test every profile's connectivity concurrently, sorted by name.
+///
+/// Each test is timed and gives up after `PROFILE_CHECK_TIMEOUT`.
+pub async fn check_profiles_health(profiles: &Profiles) -> Vec<ProfileHealth> {
+    let checks = profiles.iter().map(|(name, profile)| async move {
+        let started = Instant::now();
+        let result = tokio::time::timeout(PROFILE_CHECK_TIMEOUT, check_profile(profile))
+            .await
+            .unwrap_or_else(|_| {
+                Err(format!(
+                    "timed out after {}s",
+                    PROFILE_CHECK_TIMEOUT.as_secs()
+                ))
+            });
+        ProfileHealth {
+            name: name.clone(),
+            provider: profile.provider.clone(),
+            connected: result.is_ok(),
+            latency: started.elapsed(),
+            error: result.err(),
+        }
+    });
+    let mut results = futures::future::join_all(checks).await;
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    results
+}
+
 pub fn run_health_check<L: AuditLogger>(logger: &L, log_to_console: bool) -> (HealthStatus, String) {
+    report_health(logger, check_system_health(), log_to_console)
+}
+
+/// Run the health check and also test every profile's connectivity.
+///
+/// The system is unhealthy if any profile fails its test.
+pub async fn run_deep_health_check<L: AuditLogger>(
+    logger: &L,
+    log_to_console: bool,
+) -> (HealthStatus, String) {
+    let mut status = check_system_health();
+    if let Ok(profiles) = load_profiles() {
+        status.profiles = check_profiles_health(&profiles).await;
+        status.healthy &= status.profiles.iter().all(|profile| profile.connected);
+    }
+    report_health(logger, status, log_to_console)
+}
+
+/// Log each health check result and the overall verdict.
+fn report_health<L: AuditLogger>(
+    logger: &L,
+    status: HealthStatus,
+    log_to_console: bool,
+) -> (HealthStatus, String) {
     let hostname = hostname::get()
         .unwrap_or_default()
         .to_string_lossy()
@@ -37,8 +107,6 @@ pub fn run_health_check<L: AuditLogger>(logger: &L, log_to_console: bool) -> (He
         host: hostname,
     };
 
-    let status = check_system_health();
-
     let log_fn = |entry: &AuditLogEntry, msg: &str| {
         if log_to_console {
             logger.log_and_print(entry, msg);
@@ -53,6 +121,7 @@ pub fn run_health_check<L: AuditLogger>(logger: &L, log_to_console: bool) -> (He
                 timestamp: Utc::now().to_rfc3339(),
                 level: "AUDIT",
                 event: "health_check",
+                run_id: None,
                 contract: None,
                 target: None,
                 results: None,
@@ -70,6 +139,7 @@ pub fn run_health_check<L: AuditLogger>(logger: &L, log_to_console: bool) -> (He
                 timestamp: Utc::now().to_rfc3339(),
                 level: "AUDIT",
                 event: "health_check",
+                run_id: None,
                 contract: None,
                 target: None,
                 results: None,
@@ -86,6 +156,7 @@ pub fn run_health_check<L: AuditLogger>(logger: &L, log_to_console: bool) -> (He
             timestamp: Utc::now().to_rfc3339(),
             level: "AUDIT",
             event: "health_check",
+            run_id: None,
             contract: None,
             target: None,
             results: None,
@@ -96,6 +167,51 @@ pub fn run_health_check<L: AuditLogger>(logger: &L, log_to_console: bool) -> (He
         &format!("✅ {} profiles loaded", status.profile_count),
     );
 
+    for profile in &status.profiles {
+        let latency_ms = profile.latency.as_millis();
+        let (details, msg) = match &profile.error {
+            None => (
+                format!(
+                    "provider={}, connected=true, latency_ms={}",
+                    profile.provider, latency_ms
+                ),
+                format!(
+                    "✅ profile '{}' ({}) reachable in {} ms",
+                    profile.name, profile.provider, latency_ms
+                ),
+            ),
+            Some(error) => (
+                format!(
+                    "provider={}, connected=false, latency_ms={}, error={}",
+                    profile.provider, latency_ms, error
+                ),
+                format!(
+                    "❌ profile '{}' ({}) failed after {} ms: {}",
+                    profile.name, profile.provider, latency_ms, error
+                ),
+            ),
+        };
+        log_fn(
+            &AuditLogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                level: "AUDIT",
+                event: "health_check",
+                run_id: None,
+                contract: None,
+                target: Some(Target {
+                    file: &profile.name,
+                    column: None,
+                    rule: None,
+                }),
+                results: None,
+                executor: executor.clone(),
+                details: Some(&details),
+                summary: None,
+            },
+            &msg,
+        );
+    }
+
     let summary_msg = if status.healthy {
         "system healthy"
     } else {
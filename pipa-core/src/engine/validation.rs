@@ -15,32 +15,54 @@
 //! trail. Console output is handled by higher-level orchestration
 //! (`engine/contracts/runner.rs`).
 
-use crate::contracts::{ContractType, SchemaContracts};
-use crate::drivers::get_driver;
-use crate::logging::error::ValidationResult;
+use crate::connectors::spool::Payload;
+use crate::contracts::{ContractType, DriftMetric, DriftReference, Limits, SchemaContracts};
+use crate::drivers::contract_driver;
+use crate::engine::contracts::timings::{RunTimings, millis};
+use crate::engine::metrics::RuleMetrics;
+use crate::engine::plan::ExecutionPlan;
+use crate::engine::rules::UnrecognizedRule;
+use crate::engine::sampling::{Sample, is_sampled, sample_size};
+use crate::engine::streaming::validate_stream;
+use crate::environment::{NoProgress, RunProgress};
+use crate::history::{MemoryHistory, PriorRuns, RunHistory, RunStats};
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
 use crate::logging::schema::{AuditLogEntry, Contract, Executor, RuleResult};
-use crate::logging::writer::log_event;
+use crate::validators::column::date_between::resolve_date_bound;
 use crate::validators::column::{
-    BooleanValidator, CompletenessValidator, DateFormatValidator, DistinctnessValidator,
-    InSetValidator, MaxLengthValidator, MeanBetweenValidator, NotInSetValidator, NotNullValidator,
-    OutlierSigmaValidator, PatternValidator, RangeValidator, StdevBetweenValidator, TypeValidator,
-    UniqueValidator,
+    BooleanValidator, CompletenessValidator, DateBetweenValidator, DateFormatValidator,
+    DistinctnessValidator, DriftValidator, InSetValidator, MaxLengthValidator,
+    MeanBetweenValidator, NoFutureDatesValidator, NotInSetValidator, NotNullValidator,
+    OutlierSigmaValidator, PatternValidator, RangeValidator, SemanticKind, SemanticValidator,
+    StdevBetweenValidator, TypeValidator, UniqueValidator, WasmValidator,
 };
 use crate::validators::compound::CompoundUniqueValidator;
-use crate::validators::file::{FileCompletenessValidator, RowCountValidator};
-use crate::validators::{CompoundValidator, FileValidator, Validator};
-use anyhow::Context;
+use crate::validators::custom::{CustomValidator, resolve_custom};
+use crate::validators::file::{
+    ExpectedColumnsValidator, FileCompletenessValidator, RowCountValidator,
+};
+use crate::validators::{CompoundValidator, FileValidator, ValidationReport, Validator};
 use chrono::Utc;
 use polars::prelude::*;
-use std::collections::HashSet;
+use rayon::prelude::*;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Execute validation end-to-end against raw data bytes.
 ///
 /// # Arguments
-/// * `data` - Raw file contents (CSV, Parquet, etc.).
+/// * `logger` - The audit logger implementation to use.
+/// * `history` - Run-history store for drift references and run statistics.
+/// * `data` - Raw file contents (CSV, Parquet, etc.), in memory or spooled to disk.
 /// * `extension` - File extension (used to select driver).
-/// * `contracts` - Parsed schema contracts to enforce.
+/// * `plan` - Parsed schema contracts to enforce, with precompiled rules.
 /// * `executor` - Metadata about who/where is running validation.
+/// * `run_id` - The run whose column statistics are recorded.
+/// * `timings` - Receives the time spent parsing and running rules.
+/// * `progress` - Told about rows parsed and rules completed.
 ///
 /// # Returns
 /// * `ValidationResult<Vec<RuleResult>>` - A vector of rule outcomes,
@@ -50,19 +72,38 @@ use std::collections::HashSet;
 /// Emits the following audit events:
 /// - `validation_start`
 /// - `driver_found`
-/// - `dataframe_parsed`
+/// - `dataframe_parsed`, or `dataframe_streamed` (when `[streaming]` applies)
+/// - `sampling_applied` (when `[sampling]` applies)
+/// - `run_stats_recorded`
 /// - `validation_summary`
-pub async fn execute_validation(
-    data: &[u8],
+///
+/// # Memory limit
+/// An input over `[limits] max_memory_bytes` is streamed when the contract
+/// has `[streaming]` (whatever its `min_bytes`); without it, every rule is
+/// aborted without parsing. A parsed DataFrame over the limit is dropped
+/// and every rule aborted before any rule runs.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_validation<L: AuditLogger>(
+    logger: &L,
+    history: &dyn RunHistory,
+    data: &Payload,
     extension: &str,
-    contracts: &SchemaContracts,
+    plan: &ExecutionPlan,
     executor: &Executor,
+    run_id: &str,
+    timings: &mut RunTimings,
+    progress: &dyn RunProgress,
 ) -> ValidationResult<Vec<RuleResult>> {
+    let contracts = &plan.contracts;
+    // Drift compares against earlier runs, not this run's earlier attempts or objects.
+    let history = &PriorRuns { history, run_id };
+
     // --- Start log ---
-    log_event(&AuditLogEntry {
+    logger.log_event(&AuditLogEntry {
         timestamp: Utc::now().to_rfc3339(),
         level: "AUDIT",
         event: "validation_start",
+        run_id: None,
         contract: Some(Contract {
             name: &contracts.contract.name,
             version: &contracts.contract.version,
@@ -70,63 +111,376 @@ pub async fn execute_validation(
         target: None,
         results: None,
         executor: executor.clone(),
-        details: Some(&format!("bytes={}, extension={}", data.len(), extension)),
+        details: Some(&format!(
+            "bytes={}, extension={}, spooled={}",
+            data.len(),
+            extension,
+            data.is_spooled()
+        )),
         summary: None,
     });
 
-    // --- Driver selection ---
-    let driver =
-        get_driver(extension).context("Failed to find a suitable driver for the extension")?;
+    // --- Input size limits: abort every rule without parsing ---
+    let over_memory_limit = memory_limit_exceeded("bytes", data.len(), plan);
+    let too_large = byte_limit_exceeded(data.len(), plan).or_else(|| {
+        over_memory_limit
+            .as_ref()
+            .filter(|_| contracts.streaming.is_none())
+            .map(|reason| format!("{}; add [streaming] to validate it in batches", reason))
+    });
+    let results: Vec<RuleResult> = match too_large {
+        Some(reason) => run_rules(
+            &DataFrame::empty(),
+            plan,
+            history,
+            Budget::exhausted(reason),
+            None,
+            progress,
+        )?,
+        None => {
+            // --- Driver selection ---
+            let (options, csv) = contracts
+                .source
+                .as_ref()
+                .map(|source| (source.options.clone(), source.csv.clone()))
+                .unwrap_or_default();
+            let driver = contract_driver(extension, &options, &csv, contracts)
+                .map_err(|e| ValidationError::driver_load(extension, e))?;
+
+            logger.log_event(&AuditLogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                level: "AUDIT",
+                event: "driver_found",
+                run_id: None,
+                contract: None,
+                target: None,
+                results: None,
+                executor: executor.clone(),
+                details: Some(&format!("extension={}", extension)),
+                summary: None,
+            });
+
+            let streaming = contracts
+                .streaming
+                .as_ref()
+                .filter(|streaming| streaming.applies(data.len()) || over_memory_limit.is_some());
+            let (results, stats) = match streaming {
+                // --- Stream in batches ---
+                Some(streaming) => {
+                    let started = Instant::now();
+                    let streamed = validate_stream(
+                        data,
+                        driver.as_ref(),
+                        plan,
+                        history,
+                        run_id,
+                        streaming.chunk_rows,
+                        progress,
+                    )?;
+                    timings.validate += started.elapsed();
+
+                    logger.log_event(&AuditLogEntry {
+                        timestamp: Utc::now().to_rfc3339(),
+                        level: "AUDIT",
+                        event: "dataframe_streamed",
+                        run_id: None,
+                        contract: None,
+                        target: None,
+                        results: None,
+                        executor: executor.clone(),
+                        details: Some(&format!(
+                            "rows={}, batches={}, chunk_rows={}",
+                            streamed.rows, streamed.batches, streaming.chunk_rows
+                        )),
+                        summary: None,
+                    });
+
+                    (streamed.results, streamed.stats)
+                }
+                None => {
+                    // --- Parse into DataFrame ---
+                    let started = Instant::now();
+                    let df = data
+                        .load(driver.as_ref())
+                        .map_err(|e| ValidationError::driver_load(extension, e))?;
+                    let parse = started.elapsed();
+                    timings.parse += parse;
+                    progress.parsed(df.height());
+
+                    logger.log_event(&AuditLogEntry {
+                        timestamp: Utc::now().to_rfc3339(),
+                        level: "AUDIT",
+                        event: "dataframe_parsed",
+                        run_id: None,
+                        contract: None,
+                        target: None,
+                        results: None,
+                        executor: executor.clone(),
+                        details: Some(&format!(
+                            "rows={}, cols={}, duration_ms={}",
+                            df.height(),
+                            df.width(),
+                            parse.as_millis()
+                        )),
+                        summary: None,
+                    });
+
+                    // --- Parsed size limit: free the frame before any rule runs ---
+                    if let Some(reason) =
+                        memory_limit_exceeded("frame_bytes", df.estimated_size(), plan)
+                    {
+                        drop(df);
+                        let empty = DataFrame::empty();
+                        let budget = Budget::exhausted(reason);
+                        let results = run_rules(&empty, plan, history, budget, None, progress)?;
+                        (results, None)
+                    } else {
+                        // --- Apply all validators (sampled when `[sampling]` applies) ---
+                        let started = Instant::now();
+                        let results =
+                            validate_loaded(logger, &df, plan, history, executor, progress)?;
+                        timings.validate += started.elapsed();
+                        let stats =
+                            RunStats::from_dataframe(&df, &contracts.contract.version, run_id)?;
+                        (results, Some(stats))
+                    }
+                }
+            };
 
-    log_event(&AuditLogEntry {
+            // --- Record column statistics for drift rules ---
+            if let Some(stats) = stats {
+                record_stats(logger, history, &stats, plan, executor)?;
+            }
+
+            results
+        }
+    };
+
+    log_summary(logger, &results, plan, executor);
+    Ok(results)
+}
+
+/// Execute validation against a DataFrame assembled from a contract's
+/// `[[sources]]` (see `engine::sources`).
+///
+/// Like `execute_validation` without the parse step. `bytes` is the total
+/// size of the inputs `df` was built from: over `[limits] max_bytes`, every
+/// rule is aborted and `df` is not read, as it is when the inputs or `df`
+/// itself are over `[limits] max_memory_bytes`. `[streaming]` does not apply.
+///
+/// # Logging
+/// Emits `validation_start`, `sampling_applied` (when `[sampling]`
+/// applies), `run_stats_recorded`, and `validation_summary`.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_validation_frame<L: AuditLogger>(
+    logger: &L,
+    history: &dyn RunHistory,
+    df: &DataFrame,
+    bytes: usize,
+    plan: &ExecutionPlan,
+    executor: &Executor,
+    run_id: &str,
+    timings: &mut RunTimings,
+    progress: &dyn RunProgress,
+) -> ValidationResult<Vec<RuleResult>> {
+    let contracts = &plan.contracts;
+    // Drift compares against earlier runs, not this run's earlier attempts or objects.
+    let history = &PriorRuns { history, run_id };
+
+    // --- Start log ---
+    logger.log_event(&AuditLogEntry {
         timestamp: Utc::now().to_rfc3339(),
         level: "AUDIT",
-        event: "driver_found",
-        contract: None,
+        event: "validation_start",
+        run_id: None,
+        contract: Some(Contract {
+            name: &contracts.contract.name,
+            version: &contracts.contract.version,
+        }),
         target: None,
         results: None,
         executor: executor.clone(),
-        details: Some(&format!("extension={}", extension)),
+        details: Some(&format!(
+            "bytes={}, rows={}, cols={}",
+            bytes,
+            df.height(),
+            df.width()
+        )),
         summary: None,
     });
 
-    // --- Parse into DataFrame ---
-    let df = driver
-        .load(data)
-        .context("Failed to parse data from memory")?;
+    let too_large = byte_limit_exceeded(bytes, plan)
+        .or_else(|| memory_limit_exceeded("bytes", bytes, plan))
+        .or_else(|| memory_limit_exceeded("frame_bytes", df.estimated_size(), plan));
+    let results = match too_large {
+        Some(reason) => run_rules(
+            &DataFrame::empty(),
+            plan,
+            history,
+            Budget::exhausted(reason),
+            None,
+            progress,
+        )?,
+        None => {
+            let started = Instant::now();
+            let results = validate_loaded(logger, df, plan, history, executor, progress)?;
+            timings.validate += started.elapsed();
+            let stats = RunStats::from_dataframe(df, &contracts.contract.version, run_id)?;
+            record_stats(logger, history, &stats, plan, executor)?;
+            results
+        }
+    };
+
+    log_summary(logger, &results, plan, executor);
+    Ok(results)
+}
 
-    log_event(&AuditLogEntry {
+/// Why an input of `bytes` bytes is over `[limits] max_bytes`, if it is.
+fn byte_limit_exceeded(bytes: usize, plan: &ExecutionPlan) -> Option<String> {
+    let max_bytes = plan.contracts.limits.as_ref().and_then(|l| l.max_bytes)?;
+    (bytes > max_bytes)
+        .then(|| format!("limit exceeded: bytes={} > max_bytes={}", bytes, max_bytes))
+}
+
+/// Why `bytes` of data are over `[limits] max_memory_bytes`, if they are.
+///
+/// `measure` names what was measured in the reason: `bytes` for a fetched
+/// input, `frame_bytes` for a parsed DataFrame.
+pub(crate) fn memory_limit_exceeded(
+    measure: &str,
+    bytes: usize,
+    plan: &ExecutionPlan,
+) -> Option<String> {
+    let max_memory_bytes = plan
+        .contracts
+        .limits
+        .as_ref()
+        .and_then(|l| l.max_memory_bytes)?;
+    (bytes > max_memory_bytes).then(|| {
+        format!(
+            "limit exceeded: {}={} > max_memory_bytes={}",
+            measure, bytes, max_memory_bytes
+        )
+    })
+}
+
+/// Apply all validators to a parsed DataFrame, announcing when rules will
+/// run on a sample.
+fn validate_loaded<L: AuditLogger>(
+    logger: &L,
+    df: &DataFrame,
+    plan: &ExecutionPlan,
+    history: &dyn RunHistory,
+    executor: &Executor,
+    progress: &dyn RunProgress,
+) -> ValidationResult<Vec<RuleResult>> {
+    let contracts = &plan.contracts;
+    let planned = contracts
+        .sampling
+        .as_ref()
+        .and_then(|sampling| sample_size(df.height(), sampling).map(|n| (n, sampling)));
+    if let Some((n, sampling)) = planned {
+        logger.log_event(&AuditLogEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            level: "AUDIT",
+            event: "sampling_applied",
+            run_id: None,
+            contract: Some(Contract {
+                name: &contracts.contract.name,
+                version: &contracts.contract.version,
+            }),
+            target: None,
+            results: None,
+            executor: executor.clone(),
+            details: Some(&format!(
+                "rows={}, sample={}, confidence={}, margin={}",
+                df.height(),
+                n,
+                sampling.confidence,
+                sampling.margin
+            )),
+            summary: None,
+        });
+    }
+
+    validate_dataframe_with(df, plan, history, progress)
+}
+
+/// Record column statistics for drift rules.
+fn record_stats<L: AuditLogger>(
+    logger: &L,
+    history: &dyn RunHistory,
+    stats: &RunStats,
+    plan: &ExecutionPlan,
+    executor: &Executor,
+) -> ValidationResult<()> {
+    let contracts = &plan.contracts;
+    history.record(&contracts.contract.name, stats)?;
+
+    logger.log_event(&AuditLogEntry {
         timestamp: Utc::now().to_rfc3339(),
         level: "AUDIT",
-        event: "dataframe_parsed",
-        contract: None,
+        event: "run_stats_recorded",
+        run_id: None,
+        contract: Some(Contract {
+            name: &contracts.contract.name,
+            version: &contracts.contract.version,
+        }),
         target: None,
         results: None,
         executor: executor.clone(),
-        details: Some(&format!("rows={}, cols={}", df.height(), df.width())),
+        details: Some(&format!(
+            "run_id={}, cols={}",
+            stats.run_id,
+            stats.columns.len()
+        )),
         summary: None,
     });
+    Ok(())
+}
 
-    // --- Apply all validators ---
-    let results: Vec<RuleResult> = validate_dataframe(&df, contracts)?;
-
-    // --- Summary log ---
-    log_event(&AuditLogEntry {
+fn log_summary<L: AuditLogger>(
+    logger: &L,
+    results: &[RuleResult],
+    plan: &ExecutionPlan,
+    executor: &Executor,
+) {
+    let contracts = &plan.contracts;
+    logger.log_event(&AuditLogEntry {
         timestamp: Utc::now().to_rfc3339(),
         level: "AUDIT",
         event: "validation_summary",
+        run_id: None,
         contract: Some(Contract {
             name: &contracts.contract.name,
             version: &contracts.contract.version,
         }),
         target: None,
-        results: Some(results.clone()),
+        results: Some(results.to_vec()),
         executor: executor.clone(),
         details: None,
         summary: None,
     });
+}
 
-    Ok(results)
+/// Validate a DataFrame already in memory against `contracts`, e.g. one
+/// built with `SchemaContracts::builder`, without a contract file, audit
+/// log, or run history.
+///
+/// Compiles the contract's plan on every call; to validate many frames
+/// against one contract, build an `ExecutionPlan` once and call
+/// `validate_plan`. `drift` rules have no history to compare against here,
+/// so they are evaluated as on a contract's first run.
+///
+/// # Returns
+/// * `ValidationResult<Vec<RuleResult>>` - One `RuleResult` per rule applied,
+///   as `validate_plan` returns them.
+pub fn validate_dataframe(
+    df: &DataFrame,
+    contracts: &SchemaContracts,
+) -> ValidationResult<Vec<RuleResult>> {
+    let plan = ExecutionPlan::new(contracts.clone(), String::new());
+    validate_plan(df, &plan, &MemoryHistory::default())
 }
 
 /// Apply all file-level, column-level, and compound-level validators
@@ -134,100 +488,164 @@ pub async fn execute_validation(
 ///
 /// # Arguments
 /// * `df` - Polars DataFrame containing the dataset.
-/// * `contracts` - Schema contracts specifying rules.
+/// * `plan` - Schema contracts specifying rules, with precompiled regexes.
+/// * `history` - Run-history store `drift` rules compare against.
 ///
 /// # Returns
 /// * `ValidationResult<Vec<RuleResult>>` - One `RuleResult` per rule applied.
 ///
 /// # Notes
+/// - With `strict_types`, a schema conformance result is emitted per column first.
 /// - File-level rules apply to the dataset as a whole.
 /// - Column-level rules apply to individual columns.
 /// - Compound rules apply across multiple columns.
-pub fn validate_dataframe(
+/// - `custom` rules are resolved from the registry in `validators::custom`.
+/// - `drift` rules compare against `history` (by default `.pipa/state`).
+/// - Rules without an implementation at their scope are reported as
+///   `rule_unrecognized`, or fail validation with `strict_rules`.
+/// - `[limits]` are enforced here: rules past a limit are reported as `aborted`.
+/// - With `[sampling]`, large datasets are validated on a random sample
+///   where the rule allows it (see `engine::sampling`).
+pub fn validate_plan(
     df: &DataFrame,
-    contracts: &SchemaContracts,
+    plan: &ExecutionPlan,
+    history: &dyn RunHistory,
+) -> ValidationResult<Vec<RuleResult>> {
+    validate_dataframe_with(df, plan, history, &NoProgress)
+}
+
+/// `validate_plan`, telling `progress` as rules complete.
+pub(crate) fn validate_dataframe_with(
+    df: &DataFrame,
+    plan: &ExecutionPlan,
+    history: &dyn RunHistory,
+    progress: &dyn RunProgress,
+) -> ValidationResult<Vec<RuleResult>> {
+    let contracts = &plan.contracts;
+    let mut budget = Budget::new(contracts.limits.as_ref());
+    let max_rows = contracts.limits.as_ref().and_then(|l| l.max_rows);
+    if let Some(max_rows) = max_rows.filter(|max_rows| df.height() > *max_rows) {
+        budget.exhaust(format!(
+            "limit exceeded: rows={} > max_rows={}",
+            df.height(),
+            max_rows
+        ));
+    }
+    let sample = match &contracts.sampling {
+        Some(sampling) => Sample::draw(df, sampling)?,
+        None => None,
+    };
+    run_rules(df, plan, history, budget, sample.as_ref(), progress)
+}
+
+/// Run every declared rule, consulting `budget` before each one.
+///
+/// Rules that can be estimated from a sample run on `sample` when given.
+/// `progress` hears of each declared rule as it completes.
+pub(crate) fn run_rules(
+    df: &DataFrame,
+    plan: &ExecutionPlan,
+    history: &dyn RunHistory,
+    mut budget: Budget,
+    sample: Option<&Sample>,
+    progress: &dyn RunProgress,
 ) -> ValidationResult<Vec<RuleResult>> {
+    let contracts = &plan.contracts;
+    progress.rules_started(declared_rules(contracts));
     let mut results: Vec<RuleResult> = Vec::new();
+    let strict_rules = contracts.contract.strict_rules;
+    // Drift references, loaded from the run-history store on first use
+    let mut references: HashMap<DriftReference, Option<RunStats>> = HashMap::new();
+
+    // --- Strict Schema Conformance ---
+    if contracts.contract.strict_types {
+        match budget.check() {
+            Some(reason) => results.extend(
+                contracts
+                    .columns
+                    .iter()
+                    .map(|col| aborted(&col.name, "SchemaConformance", reason)),
+            ),
+            None => results.extend(schema_conformance(df, contracts)),
+        }
+    }
 
     // --- File-Level Validation ---
     if let Some(file_contracts) = &contracts.file {
         for contract_rule in &file_contracts.validation {
-            let validator: Box<dyn FileValidator> = match contract_rule {
-                ContractType::RowCount { min, max } => Box::new(RowCountValidator {
-                    min: *min,
-                    max: *max,
-                }),
-                ContractType::Completeness { min_ratio } => Box::new(FileCompletenessValidator {
-                    min_ratio: *min_ratio,
-                }),
-                _ => continue, // skip unsupported rules at file level
+            let Some(validator) = file_validator(contract_rule)? else {
+                results.push(unrecognized("file", contract_rule, strict_rules)?);
+                progress.rule_completed();
+                continue;
+            };
+            if let Some(reason) = budget.check() {
+                results.push(aborted("file", validator.name(), reason));
+                progress.rule_completed();
+                continue;
+            }
+            let started = Instant::now();
+            let report = match sample.filter(|_| is_sampled(contract_rule)) {
+                Some(sample) => {
+                    sample.evaluate(contract_rule, None, df, |df| validator.validate(df))?
+                }
+                None => validator.validate(df)?,
             };
-            let report = validator.validate(df)?;
             results.push(RuleResult {
                 column: "file".to_string(),
                 rule: validator.name().to_string(),
                 result: report.status.to_string(),
                 details: report.details.clone(),
+                duration_ms: Some(millis(started.elapsed())),
             });
+            progress.rule_completed();
         }
     }
 
     // --- Column-Level Validation ---
-    for col in &contracts.columns {
-        for contract_rule in &col.validation {
-            let validator: Box<dyn Validator> = match contract_rule {
-                ContractType::NotNull => Box::new(NotNullValidator),
-                ContractType::Unique => Box::new(UniqueValidator),
-                ContractType::Boolean => Box::new(BooleanValidator),
-                ContractType::Range { min, max } => Box::new(RangeValidator {
-                    min: *min,
-                    max: *max,
-                }),
-                ContractType::Pattern { pattern } => Box::new(PatternValidator {
-                    pattern: pattern.clone(),
-                }),
-                ContractType::MaxLength { value } => Box::new(MaxLengthValidator { value: *value }),
-                ContractType::MeanBetween { min, max } => Box::new(MeanBetweenValidator {
-                    min: *min,
-                    max: *max,
-                }),
-                ContractType::StdevBetween { min, max } => Box::new(StdevBetweenValidator {
-                    min: *min,
-                    max: *max,
-                }),
-                ContractType::Completeness { min_ratio } => Box::new(CompletenessValidator {
-                    min_ratio: *min_ratio,
-                }),
-                ContractType::InSet { values } => Box::new(InSetValidator {
-                    values: values.iter().cloned().collect::<HashSet<String>>(),
-                }),
-                ContractType::NotInSet { values } => Box::new(NotInSetValidator {
-                    values: values.iter().cloned().collect::<HashSet<String>>(),
-                }),
-                ContractType::Type { dtype } => Box::new(TypeValidator {
-                    dtype: dtype.clone(),
-                }),
-                ContractType::OutlierSigma { sigma } => {
-                    Box::new(OutlierSigmaValidator { sigma: *sigma })
-                }
-                ContractType::DateFormat { format } => Box::new(DateFormatValidator {
-                    format: format.clone(),
-                }),
-                ContractType::Distinctness { min_ratio } => Box::new(DistinctnessValidator {
-                    min_ratio: *min_ratio,
-                }),
-                _ => continue, // skip unsupported rules at column level
+    // Aggregate rules are computed together in one pass over the data; the
+    // rest fall through to their validators below.
+    let mut metrics = if budget.check().is_none() {
+        RuleMetrics::compute(df, plan, |rule| sample.is_some() && is_sampled(rule))?
+    } else {
+        RuleMetrics::default()
+    };
+    // Validators are built in order (drift references load on first use),
+    // then evaluated in parallel; results keep the declared order.
+    let mut jobs: Vec<ColumnJob> = Vec::new();
+    for (col_idx, col) in contracts.columns.iter().enumerate() {
+        for (rule_idx, contract_rule) in col.validation.iter().enumerate() {
+            let Some(validator) =
+                column_validator(contract_rule, &col.name, plan, history, &mut references)?
+            else {
+                jobs.push(ColumnJob::Done(unrecognized(
+                    &col.name,
+                    contract_rule,
+                    strict_rules,
+                )?));
+                continue;
             };
-
-            let report = validator.validate(df, &col.name)?;
-            results.push(RuleResult {
-                column: col.name.clone(),
-                rule: validator.name().to_string(),
-                result: report.status.to_string(),
-                details: report.details.clone(),
+            jobs.push(ColumnJob::Run {
+                column: &col.name,
+                rule: contract_rule,
+                validator,
+                report: metrics.take(col_idx, rule_idx),
             });
         }
     }
+    let budget = Mutex::new(budget);
+    let column_results = with_threads(plan.thread_pool()?, || {
+        jobs.into_par_iter()
+            .map(|job| {
+                let result = run_column_job(job, df, sample, &budget);
+                progress.rule_completed();
+                result
+            })
+            .collect::<ValidationResult<Vec<_>>>()
+    })?;
+    results.extend(column_results);
+    let mut budget = budget
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
 
     // --- Compound-Level Validation ---
     if let Some(compounds) = &contracts.compound_unique {
@@ -235,15 +653,653 @@ pub fn validate_dataframe(
             let validator: Box<dyn CompoundValidator> = Box::new(CompoundUniqueValidator {
                 columns: cu.columns.clone(),
             });
+            if let Some(reason) = budget.check() {
+                results.push(aborted("compound", validator.name(), reason));
+                progress.rule_completed();
+                continue;
+            }
+            let started = Instant::now();
             let report = validator.validate(df)?;
             results.push(RuleResult {
                 column: "compound".to_string(),
                 rule: validator.name().to_string(),
                 result: report.status.to_string(),
                 details: report.details.clone(),
+                duration_ms: Some(millis(started.elapsed())),
             });
+            progress.rule_completed();
         }
     }
 
     Ok(results)
 }
+
+/// Number of rules declared under `[file]`, `[[columns]]`, and
+/// `[[compound_unique]]`.
+fn declared_rules(contracts: &SchemaContracts) -> usize {
+    let file = contracts.file.as_ref().map_or(0, |f| f.validation.len());
+    let columns: usize = contracts.columns.iter().map(|c| c.validation.len()).sum();
+    let compound = contracts.compound_unique.as_ref().map_or(0, Vec::len);
+    file + columns + compound
+}
+
+/// A column rule ready to evaluate, or its result if there is nothing to run.
+enum ColumnJob<'a> {
+    Done(RuleResult),
+    Run {
+        column: &'a str,
+        rule: &'a ContractType,
+        validator: Box<dyn Validator>,
+        /// Report already computed by the shared aggregate pass.
+        report: Option<ValidationReport>,
+    },
+}
+
+/// Evaluate one column rule, consulting the shared `budget` first.
+fn run_column_job(
+    job: ColumnJob,
+    df: &DataFrame,
+    sample: Option<&Sample>,
+    budget: &Mutex<Budget>,
+) -> ValidationResult<RuleResult> {
+    let (column, rule, validator, report) = match job {
+        ColumnJob::Done(result) => return Ok(result),
+        ColumnJob::Run {
+            column,
+            rule,
+            validator,
+            report,
+        } => (column, rule, validator, report),
+    };
+    let reason = budget
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .check()
+        .map(str::to_string);
+    if let Some(reason) = reason {
+        return Ok(aborted(column, validator.name(), &reason));
+    }
+
+    // Rules from the shared aggregate pass have no time of their own.
+    let started = Instant::now();
+    let (report, timed) = match (report, sample) {
+        (Some(report), _) => (report, false),
+        (None, Some(sample)) if is_sampled(rule) => {
+            let report =
+                sample.evaluate(rule, Some(column), df, |df| validator.validate(df, column))?;
+            (report, true)
+        }
+        (None, _) => (validator.validate(df, column)?, true),
+    };
+    Ok(RuleResult {
+        column: column.to_string(),
+        rule: validator.name().to_string(),
+        result: report.status.to_string(),
+        details: report.details,
+        duration_ms: timed.then(|| millis(started.elapsed())),
+    })
+}
+
+/// Run `f` on `pool`, or on the global pool (one thread per CPU) when no
+/// cap is set.
+fn with_threads<T: Send>(pool: Option<&rayon::ThreadPool>, f: impl FnOnce() -> T + Send) -> T {
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+/// Build the validator for a rule declared under `[file]`.
+///
+/// Returns `None` for rules with no file-level implementation.
+pub(crate) fn file_validator(
+    rule: &ContractType,
+) -> ValidationResult<Option<Box<dyn FileValidator>>> {
+    let validator: Box<dyn FileValidator> = match rule {
+        ContractType::RowCount { min, max } => Box::new(RowCountValidator {
+            min: *min,
+            max: *max,
+        }),
+        ContractType::Completeness { min_ratio } => Box::new(FileCompletenessValidator {
+            min_ratio: *min_ratio,
+        }),
+        ContractType::ExpectedColumns {
+            columns,
+            ordered,
+            allow_extra_columns,
+            allow_missing_columns,
+        } => Box::new(ExpectedColumnsValidator {
+            columns: columns.clone(),
+            ordered: *ordered,
+            allow_extra_columns: *allow_extra_columns,
+            allow_missing_columns: *allow_missing_columns,
+        }),
+        ContractType::Custom { name, params } => match resolve_custom(name, params)? {
+            CustomValidator::File(validator) => validator,
+            CustomValidator::Column(_) => {
+                return Err(ValidationError::Config(format!(
+                    "custom rule '{}' is column-level and cannot be used under [file]",
+                    name
+                )));
+            }
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(validator))
+}
+
+/// Build the validator for a rule declared on `column`.
+///
+/// `drift` rules load their reference run from `history` into
+/// `references` on first use. Returns `None` for rules with no
+/// column-level implementation.
+pub(crate) fn column_validator(
+    rule: &ContractType,
+    column: &str,
+    plan: &ExecutionPlan,
+    history: &dyn RunHistory,
+    references: &mut HashMap<DriftReference, Option<RunStats>>,
+) -> ValidationResult<Option<Box<dyn Validator>>> {
+    let contracts = &plan.contracts;
+    let max_regex_size = contracts.limits.as_ref().and_then(|l| l.max_regex_size);
+    let validator: Box<dyn Validator> = match rule {
+        ContractType::NotNull => Box::new(NotNullValidator),
+        ContractType::Unique => Box::new(UniqueValidator),
+        ContractType::Boolean => Box::new(BooleanValidator),
+        ContractType::Range { min, max } => Box::new(RangeValidator {
+            min: *min,
+            max: *max,
+        }),
+        ContractType::Pattern { pattern } => Box::new(PatternValidator {
+            pattern: pattern.clone(),
+            size_limit: max_regex_size,
+            compiled: plan.pattern(pattern).cloned(),
+        }),
+        ContractType::MaxLength { value } => Box::new(MaxLengthValidator { value: *value }),
+        ContractType::MeanBetween { min, max } => Box::new(MeanBetweenValidator {
+            min: *min,
+            max: *max,
+        }),
+        ContractType::StdevBetween { min, max } => Box::new(StdevBetweenValidator {
+            min: *min,
+            max: *max,
+        }),
+        ContractType::Completeness { min_ratio } => Box::new(CompletenessValidator {
+            min_ratio: *min_ratio,
+        }),
+        ContractType::InSet { values } => Box::new(InSetValidator {
+            values: values.iter().cloned().collect::<HashSet<String>>(),
+        }),
+        ContractType::NotInSet { values } => Box::new(NotInSetValidator {
+            values: values.iter().cloned().collect::<HashSet<String>>(),
+        }),
+        ContractType::Type { dtype } => Box::new(TypeValidator {
+            dtype: dtype.clone(),
+        }),
+        ContractType::OutlierSigma { sigma } => Box::new(OutlierSigmaValidator { sigma: *sigma }),
+        ContractType::DateFormat { format } => Box::new(DateFormatValidator {
+            format: format.clone(),
+        }),
+        ContractType::DateBetween { min, max, format } => {
+            let format = date_format_for(format, column, contracts);
+            let now = Utc::now().naive_utc();
+            let bound = |bound: &Option<String>| {
+                bound
+                    .as_deref()
+                    .map(|b| resolve_date_bound(b, format.as_deref(), now))
+                    .transpose()
+                    .map_err(|e| {
+                        ValidationError::Config(format!(
+                            "date_between on column '{}': {}",
+                            column, e
+                        ))
+                    })
+            };
+            Box::new(DateBetweenValidator {
+                min: bound(min)?,
+                max: bound(max)?,
+                format,
+            })
+        }
+        ContractType::NoFutureDates { format } => Box::new(NoFutureDatesValidator {
+            now: Utc::now().naive_utc(),
+            format: date_format_for(format, column, contracts),
+        }),
+        ContractType::Uuid => Box::new(SemanticValidator {
+            kind: SemanticKind::Uuid,
+        }),
+        ContractType::Email => Box::new(SemanticValidator {
+            kind: SemanticKind::Email,
+        }),
+        ContractType::Url => Box::new(SemanticValidator {
+            kind: SemanticKind::Url,
+        }),
+        ContractType::Ipv4 => Box::new(SemanticValidator {
+            kind: SemanticKind::Ipv4,
+        }),
+        ContractType::Ipv6 => Box::new(SemanticValidator {
+            kind: SemanticKind::Ipv6,
+        }),
+        ContractType::PhoneE164 => Box::new(SemanticValidator {
+            kind: SemanticKind::PhoneE164,
+        }),
+        ContractType::Distinctness { min_ratio } => Box::new(DistinctnessValidator {
+            min_ratio: *min_ratio,
+        }),
+        ContractType::Drift {
+            metric,
+            max_change,
+            against,
+        } => Box::new(drift_validator(
+            *metric,
+            *max_change,
+            *against,
+            column,
+            plan,
+            history,
+            references,
+        )?),
+        ContractType::Wasm { module, fuel } => Box::new(WasmValidator {
+            module: module.clone(),
+            fuel: *fuel,
+        }),
+        ContractType::Custom { name, params } => match resolve_custom(name, params)? {
+            CustomValidator::Column(validator) => validator,
+            CustomValidator::File(_) => {
+                return Err(ValidationError::Config(format!(
+                    "custom rule '{}' is file-level and cannot be used on column '{}'",
+                    name, column
+                )));
+            }
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(validator))
+}
+
+/// The format a date rule on `column` parses values with: its own
+/// `format`, else the format of the column's `date_format` rule.
+fn date_format_for(
+    format: &Option<String>,
+    column: &str,
+    contracts: &SchemaContracts,
+) -> Option<String> {
+    format.clone().or_else(|| {
+        contracts
+            .columns
+            .iter()
+            .filter(|c| c.name == column)
+            .flat_map(|c| &c.validation)
+            .find_map(|rule| match rule {
+                ContractType::DateFormat { format } => Some(format.clone()),
+                _ => None,
+            })
+    })
+}
+
+/// Build a `drift` validator with its reference value for `column`.
+pub(crate) fn drift_validator(
+    metric: DriftMetric,
+    max_change: f64,
+    against: DriftReference,
+    column: &str,
+    plan: &ExecutionPlan,
+    history: &dyn RunHistory,
+    references: &mut HashMap<DriftReference, Option<RunStats>>,
+) -> ValidationResult<DriftValidator> {
+    let run = match references.entry(against) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => {
+            entry.insert(history.reference(&plan.contracts.contract.name, against)?)
+        }
+    };
+    Ok(DriftValidator {
+        metric,
+        max_change,
+        against,
+        reference: run.as_ref().and_then(|run| run.metric(column, metric)),
+    })
+}
+
+/// Tracks `[limits]` while rules run.
+///
+/// Once a limit is hit, every remaining rule is reported as aborted with
+/// the reason instead of being evaluated.
+pub(crate) struct Budget {
+    deadline: Option<(Instant, u64)>,
+    exceeded: Option<String>,
+}
+
+impl Budget {
+    pub(crate) fn new(limits: Option<&Limits>) -> Self {
+        Self {
+            deadline: limits
+                .and_then(|l| l.max_wall_time_secs)
+                .map(|secs| (Instant::now() + Duration::from_secs(secs), secs)),
+            exceeded: None,
+        }
+    }
+
+    /// A budget that is already spent, e.g. because the input is too large.
+    pub(crate) fn exhausted(reason: String) -> Self {
+        Self {
+            deadline: None,
+            exceeded: Some(reason),
+        }
+    }
+
+    fn exhaust(&mut self, reason: String) {
+        self.exceeded.get_or_insert(reason);
+    }
+
+    /// Return the abort reason if any limit has been exceeded.
+    pub(crate) fn check(&mut self) -> Option<&str> {
+        let now = Instant::now();
+        if let Some((_, secs)) = self.deadline.filter(|(deadline, _)| now > *deadline) {
+            self.exhaust(format!(
+                "limit exceeded: wall time > max_wall_time_secs={}",
+                secs
+            ));
+        }
+        self.exceeded.as_deref()
+    }
+}
+
+/// Result recorded for a rule skipped because a run limit was exceeded.
+fn aborted(column: &str, rule: &str, reason: &str) -> RuleResult {
+    RuleResult {
+        column: column.to_string(),
+        rule: rule.to_string(),
+        result: "aborted".to_string(),
+        details: Some(reason.to_string()),
+        duration_ms: None,
+    }
+}
+
+/// Handle a rule declared where the engine has no implementation for it.
+///
+/// With `strict_rules` this is an error; otherwise the rule is reported as
+/// `rule_unrecognized` so it can't be mistaken for a passing check.
+pub(crate) fn unrecognized(
+    column: &str,
+    rule: &ContractType,
+    strict: bool,
+) -> ValidationResult<RuleResult> {
+    let unrecognized = UnrecognizedRule {
+        column: column.to_string(),
+        rule: rule.name(),
+    };
+    if strict {
+        return Err(ValidationError::Config(format!(
+            "strict_rules: {}",
+            unrecognized
+        )));
+    }
+    Ok(RuleResult {
+        column: column.to_string(),
+        rule: rule.name().to_string(),
+        result: "rule_unrecognized".to_string(),
+        details: Some(unrecognized.to_string()),
+        duration_ms: None,
+    })
+}
+
+/// Build the schema conformance report for `strict_types` contracts.
+///
+/// Emits one `SchemaConformance` result per declared column comparing the
+/// declared `dtype` with the dtype Polars inferred. Columns without a
+/// declared dtype, or absent from the data, fail.
+pub(crate) fn schema_conformance(df: &DataFrame, contracts: &SchemaContracts) -> Vec<RuleResult> {
+    contracts
+        .columns
+        .iter()
+        .map(|col| {
+            let actual = df
+                .column(&col.name)
+                .map(|series| format!("{:?}", series.dtype()))
+                .unwrap_or_else(|_| "missing".to_string());
+
+            let (result, details) = match &col.dtype {
+                None => (
+                    "fail",
+                    format!(
+                        "declared=none, actual={} (strict_types requires dtype)",
+                        actual
+                    ),
+                ),
+                Some(declared) if *declared == actual => {
+                    ("pass", format!("declared={}, actual={}", declared, actual))
+                }
+                Some(declared) => ("fail", format!("declared={}, actual={}", declared, actual)),
+            };
+
+            RuleResult {
+                column: col.name.clone(),
+                rule: "SchemaConformance".to_string(),
+                result: result.to_string(),
+                details: Some(details),
+                duration_ms: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(limits: &str) -> String {
+        let mut toml =
+            String::from("[contract]\nname = \"wide\"\nversion = \"1.0.0\"\ntags = []\n");
+        for i in 0..24 {
+            toml.push_str(&format!(
+                "\n[[columns]]\nname = \"c{}\"\nvalidation = [\
+                 {{ rule = \"date_format\", format = \"%Y-%m-%d\" }}, \
+                 {{ rule = \"row_count\", min = 1 }}, \
+                 {{ rule = \"type\", dtype = \"string\" }}]\n",
+                i
+            ));
+        }
+        toml.push_str(limits);
+        toml
+    }
+
+    fn wide_frame() -> DataFrame {
+        let columns = (0..24)
+            .map(|i| {
+                let values: Vec<String> = (0..200)
+                    .map(|row| match (row + i) % 50 {
+                        0 => "not a date".to_string(),
+                        day => format!("2024-01-{:02}", day % 28 + 1),
+                    })
+                    .collect();
+                Column::new(format!("c{}", i).into(), values)
+            })
+            .collect();
+        DataFrame::new(columns).unwrap()
+    }
+
+    #[test]
+    fn parallel_column_rules_match_a_single_thread_in_order() {
+        let df = wide_frame();
+        let history = MemoryHistory::default();
+        let parallel = ExecutionPlan::from_toml(&contract("")).unwrap();
+        let single = ExecutionPlan::from_toml(&contract("\n[limits]\nmax_threads = 1\n")).unwrap();
+
+        let parallel = validate_plan(&df, &parallel, &history).unwrap();
+        let single = validate_plan(&df, &single, &history).unwrap();
+
+        let outcome = |results: &[RuleResult]| {
+            results
+                .iter()
+                .map(|r| {
+                    (
+                        r.column.clone(),
+                        r.rule.clone(),
+                        r.result.clone(),
+                        r.details.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(parallel.len(), 72);
+        assert_eq!(outcome(&parallel), outcome(&single));
+        assert_eq!(parallel[0].rule, "DateFormat");
+        assert_eq!(parallel[1].result, "rule_unrecognized");
+        assert_eq!(parallel[71].column, "c23");
+
+        let err = ExecutionPlan::from_toml(&contract("\n[limits]\nmax_threads = 0\n")).unwrap_err();
+        assert!(
+            err.to_string().contains("max_threads must be at least 1"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn validates_a_frame_against_a_built_contract() {
+        let df = df!("id" => [Some(1i64), Some(2), None], "age" => [30i64, 150, 40]).unwrap();
+        let contracts = SchemaContracts::builder("people", "1.0")
+            .file_rule(ContractType::RowCount { min: 1, max: None })
+            .column("id", [ContractType::NotNull])
+            .column("age", [ContractType::Range { min: 0, max: 120 }])
+            .build();
+
+        let results = validate_dataframe(&df, &contracts).unwrap();
+
+        let outcomes: Vec<_> = results
+            .iter()
+            .map(|r| (r.column.as_str(), r.result.as_str()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![("file", "pass"), ("id", "fail"), ("age", "fail")]
+        );
+    }
+
+    #[test]
+    fn date_rules_parse_with_the_columns_date_format() {
+        let df = df!("shipped" => ["31/12/2023", "15/06/2024", "01/01/2999"]).unwrap();
+        let contracts = SchemaContracts::builder("orders", "1.0")
+            .column(
+                "shipped",
+                [
+                    ContractType::DateFormat {
+                        format: "%d/%m/%Y".to_string(),
+                    },
+                    ContractType::DateBetween {
+                        min: Some("2024-01-01".to_string()),
+                        max: Some("now+1d".to_string()),
+                        format: None,
+                    },
+                    ContractType::NoFutureDates { format: None },
+                ],
+            )
+            .build();
+
+        let results = validate_dataframe(&df, &contracts).unwrap();
+
+        assert_eq!(results[1].rule, "DateBetween");
+        assert_eq!(results[1].details.as_deref().unwrap()[..11], *"bad_count=2");
+        assert_eq!(results[2].rule, "NoFutureDates");
+        assert!(
+            results[2]
+                .details
+                .as_deref()
+                .unwrap()
+                .contains("future_count=1")
+        );
+
+        let invalid = SchemaContracts::builder("orders", "1.0")
+            .column(
+                "shipped",
+                [ContractType::DateBetween {
+                    min: Some("now-1q".to_string()),
+                    max: None,
+                    format: None,
+                }],
+            )
+            .build();
+        let err = validate_dataframe(&df, &invalid).unwrap_err();
+        assert!(err.to_string().contains("invalid relative date 'now-1q'"));
+    }
+
+    #[test]
+    fn schema_conformance_compares_declared_and_actual_dtypes() {
+        let df = df!("id" => [1i64, 2], "name" => ["a", "b"], "note" => ["x", "y"]).unwrap();
+        let plan = ExecutionPlan::from_toml(
+            r#"
+[contract]
+name = "typed"
+version = "1.0.0"
+tags = []
+strict_types = true
+
+[[columns]]
+name = "id"
+dtype = "Int64"
+
+[[columns]]
+name = "name"
+dtype = "Int64"
+
+[[columns]]
+name = "note"
+
+[[columns]]
+name = "gone"
+dtype = "String"
+"#,
+        )
+        .unwrap();
+
+        let results = schema_conformance(&df, &plan.contracts);
+
+        let outcomes: Vec<_> = results
+            .iter()
+            .map(|r| {
+                assert_eq!(r.rule, "SchemaConformance");
+                (
+                    r.column.as_str(),
+                    r.result.as_str(),
+                    r.details.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("id", "pass", "declared=Int64, actual=Int64"),
+                ("name", "fail", "declared=Int64, actual=String"),
+                (
+                    "note",
+                    "fail",
+                    "declared=none, actual=String (strict_types requires dtype)"
+                ),
+                ("gone", "fail", "declared=String, actual=missing"),
+            ]
+        );
+
+        // Conformance results come before the declared rules.
+        let validated = validate_plan(&df, &plan, &MemoryHistory::default()).unwrap();
+        assert_eq!(validated.len(), 4);
+        assert!(validated.iter().all(|r| r.rule == "SchemaConformance"));
+
+        // Over a limit, conformance is aborted like any other rule.
+        let budget = Budget::exhausted("limit exceeded: rows=3 > max_rows=2".to_string());
+        let aborted = run_rules(
+            &df,
+            &plan,
+            &MemoryHistory::default(),
+            budget,
+            None,
+            &NoProgress,
+        )
+        .unwrap();
+        assert_eq!(aborted.len(), 4);
+        assert!(
+            aborted
+                .iter()
+                .all(|r| r.rule == "SchemaConformance" && r.result == "aborted")
+        );
+    }
+}
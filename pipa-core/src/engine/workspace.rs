@@ -0,0 +1,97 @@
+//! Multi-project workspaces.
+//!
+//! A workspace groups several pipa projects (each with its own `contracts/`,
+//! `profiles.toml`, logs, and run history) so one scheduler invocation can
+//! run them all. Projects are listed in `workspace.toml`:
+//!
+//! ```toml
+//! projects = ["pipelines/orders", "pipelines/billing"]
+//! ```
+//!
+//! Relative project paths are resolved against the directory holding
+//! `workspace.toml`.
+
+use crate::logging::error::{ValidationError, ValidationResult};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default workspace file name.
+pub const WORKSPACE_FILE: &str = "workspace.toml";
+
+/// Projects making up a workspace.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workspace {
+    /// Project directories, in run order.
+    pub projects: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// A workspace of `dirs`, relative to the working directory.
+    pub fn from_dirs<P: AsRef<Path>>(dirs: &[P]) -> Self {
+        Self {
+            projects: dirs.iter().map(|d| d.as_ref().to_path_buf()).collect(),
+        }
+    }
+}
+
+/// Load a workspace file, resolving project paths against its directory.
+///
+/// # Errors
+/// Returns `ValidationError::Config` if the file can't be read or parsed,
+/// or lists no projects.
+pub fn load_workspace(path: &Path) -> ValidationResult<Workspace> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        ValidationError::Config(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    let mut workspace: Workspace = toml::from_str(&content).map_err(|e| {
+        ValidationError::Config(format!("Failed to parse {}: {}", path.display(), e))
+    })?;
+    if workspace.projects.is_empty() {
+        return Err(ValidationError::Config(format!(
+            "{} lists no projects",
+            path.display()
+        )));
+    }
+
+    let root = path.parent().unwrap_or(Path::new(""));
+    for project in &mut workspace.projects {
+        if project.is_relative() {
+            *project = root.join(&*project);
+        }
+    }
+    Ok(workspace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolves_projects_against_workspace_dir() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(WORKSPACE_FILE);
+        fs::write(&path, r#"projects = ["orders", "/srv/billing"]"#).unwrap();
+
+        let workspace = load_workspace(&path).unwrap();
+        assert_eq!(
+            workspace.projects,
+            vec![dir.path().join("orders"), PathBuf::from("/srv/billing")]
+        );
+    }
+
+    #[test]
+    fn empty_workspace_is_a_config_error() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(WORKSPACE_FILE);
+        fs::write(&path, "projects = []").unwrap();
+
+        assert!(load_workspace(&path).unwrap_err().is_config());
+        assert!(
+            load_workspace(&dir.path().join("missing.toml"))
+                .unwrap_err()
+                .is_config()
+        );
+    }
+}
@@ -0,0 +1,1727 @@
+//! Pluggable backends for a validation run.
+//!
+//! `run_contract_validation_with` takes an `Environment` deciding where a
+//! run reads and writes; `run_contract_validation` uses the default one.
+//!
+//! | Concern                                      | Trait         | Default          | In-memory       |
+//! |----------------------------------------------|---------------|------------------|-----------------|
+//! | Contracts, profiles, and `pipa.toml`         | `ConfigStore` | `FsConfig`       | `InMemory`      |
+//! | Source reads/listing, destination/quarantine | `ObjectStore` | `ConnectorStore` | `InMemory`      |
+//! | Run history (drift rules)                    | `RunHistory`  | `HistoryStore`   | `MemoryHistory` |
+//! | Live progress (bytes, rows, rules)           | `RunProgress` | `NoProgress`     | `NoProgress`    |
+//! | Audit log and hash ledger                    | `AuditLogger` | `JsonlLogger`    | `MemoryLogger`  |
+//!
+//! The audit logger is passed alongside the environment, as for every
+//! other engine entry point; the ledger is sealed by `JsonlLogger`, so a
+//! `MemoryLogger` leaves nothing to seal.
+//!
+//! `InMemory` (feature `test-util`) serves contracts, profiles, and objects
+//! from memory, so a run needs no network and no disk:
+//! ```ignore
+//! let memory = InMemory::new();
+//! memory.add_contract("orders", CONTRACT_TOML);
+//! memory.put_object("data/orders.csv", "id\n1\n");
+//! let logger = MemoryLogger::new();
+//! let (outcome, _) =
+//!     run_contract_validation_with(&memory.environment(), &logger, "orders", &executor, false)
+//!         .await?;
+//! ```
+
+use crate::config::{PROJECT_FILE, ProjectConfig, load_project_config};
+use crate::connectors::ObjectInfo;
+use crate::connectors::fetch::{
+    FetchedData, fetch_data_from_source, fetch_data_with_progress, list_source_objects,
+};
+use crate::contracts::apply_overlay;
+use crate::contracts::dates::{has_date_tokens, render_date_tokens};
+use crate::contracts::schema::Source;
+use crate::engine::profiles::test_profile_internal;
+use crate::history::{HistoryStore, RunHistory};
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::movement::FileMovement;
+use crate::profiles::{Profiles, load_profiles};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use std::fmt;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Where contracts and profiles come from.
+pub trait ConfigStore: Send + Sync {
+    /// TOML source of the contract called `name`.
+    ///
+    /// Parsing happens in the engine, which caches the resulting
+    /// `ExecutionPlan` per contract hash.
+    fn contract_toml(&self, name: &str) -> ValidationResult<String>;
+
+    /// All connection profiles.
+    fn profiles(&self) -> ValidationResult<Profiles>;
+
+    /// Project-wide settings; none unless the store has a `pipa.toml`.
+    fn project(&self) -> ValidationResult<ProjectConfig> {
+        Ok(ProjectConfig::default())
+    }
+}
+
+/// Where source data is read from and moved data is written to.
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    /// Fetch the data described by `source`.
+    async fn fetch(&self, source: &Source, profiles: &Profiles) -> ValidationResult<FetchedData>;
+
+    /// Fetch as `fetch`, telling `progress` about bytes as they arrive.
+    ///
+    /// The default reports the whole payload once it has been fetched.
+    async fn fetch_with_progress(
+        &self,
+        source: &Source,
+        profiles: &Profiles,
+        progress: &Progress,
+    ) -> ValidationResult<FetchedData> {
+        let fetched = self.fetch(source, profiles).await?;
+        progress.downloaded(fetched.payload.len() as u64);
+        Ok(fetched)
+    }
+
+    /// Objects under `prefix`, read with `source`'s connector type and
+    /// profile; used by watch mode to spot new files.
+    async fn list(
+        &self,
+        source: &Source,
+        prefix: &str,
+        profiles: &Profiles,
+    ) -> ValidationResult<Vec<ObjectInfo>>;
+
+    /// Write `data` to `target.location`; returns the bytes written.
+    async fn write(
+        &self,
+        target: &Source,
+        data: &[u8],
+        profiles: &Profiles,
+    ) -> anyhow::Result<usize>;
+
+    /// Whether a source/destination of type `kind` using `profile` can be
+    /// reached; checked before any data is moved.
+    async fn is_reachable(&self, kind: &str, profile: Option<&str>, profiles: &Profiles) -> bool;
+
+    /// Delete the object at `target.location` (a quarantined artifact once
+    /// it has been released).
+    async fn delete(&self, target: &Source, profiles: &Profiles) -> anyhow::Result<()>;
+
+    /// Copy the object at `source.location` to `to` with the same
+    /// connector and profile (archiving a source file).
+    async fn copy(&self, source: &Source, to: &str, profiles: &Profiles) -> anyhow::Result<()>;
+
+    /// Move the object at `source.location` to `to` with the same
+    /// connector and profile (publishing a file written under a temporary
+    /// name).
+    async fn rename(&self, source: &Source, to: &str, profiles: &Profiles) -> anyhow::Result<()>;
+}
+
+/// Contracts from `contracts/<name>.toml` (with the selected environment's
+/// overlay), profiles from `profiles.toml`, project settings from `pipa.toml`.
+pub struct FsConfig;
+
+impl ConfigStore for FsConfig {
+    fn contract_toml(&self, name: &str) -> ValidationResult<String> {
+        let contract_path = crate::config::contract_path(name).display().to_string();
+        if !Path::new(&contract_path).exists() {
+            return Err(ValidationError::ContractNotFound(name.to_string()));
+        }
+        let toml_str = std::fs::read_to_string(&contract_path).map_err(|_| {
+            ValidationError::ContractParse(format!("Missing contract file: {}", contract_path))
+        })?;
+        let (toml_str, _) = apply_overlay(name, toml_str)?;
+        Ok(toml_str)
+    }
+
+    fn profiles(&self) -> ValidationResult<Profiles> {
+        load_profiles()
+            .map_err(|e| ValidationError::Config(format!("Failed to load profiles.toml: {}", e)))
+    }
+
+    fn project(&self) -> ValidationResult<ProjectConfig> {
+        load_project_config(Path::new(PROJECT_FILE))
+    }
+}
+
+/// Reads and writes through the storage connectors (local, S3, Azure, GCS).
+pub struct ConnectorStore;
+
+#[async_trait]
+impl ObjectStore for ConnectorStore {
+    async fn fetch(&self, source: &Source, profiles: &Profiles) -> ValidationResult<FetchedData> {
+        fetch_data_from_source(source, profiles).await
+    }
+
+    async fn fetch_with_progress(
+        &self,
+        source: &Source,
+        profiles: &Profiles,
+        progress: &Progress,
+    ) -> ValidationResult<FetchedData> {
+        fetch_data_with_progress(source, profiles, progress).await
+    }
+
+    async fn list(
+        &self,
+        source: &Source,
+        prefix: &str,
+        profiles: &Profiles,
+    ) -> ValidationResult<Vec<ObjectInfo>> {
+        list_source_objects(source, prefix, profiles).await
+    }
+
+    async fn write(
+        &self,
+        target: &Source,
+        data: &[u8],
+        profiles: &Profiles,
+    ) -> anyhow::Result<usize> {
+        FileMovement::write_data_via_connector(data, target, profiles).await
+    }
+
+    async fn is_reachable(&self, kind: &str, profile: Option<&str>, profiles: &Profiles) -> bool {
+        match (kind, profile) {
+            ("local" | "not_moved", _) => true,
+            (_, Some(name)) => test_profile_internal(name, profiles).await,
+            (_, None) => false,
+        }
+    }
+
+    async fn delete(&self, target: &Source, profiles: &Profiles) -> anyhow::Result<()> {
+        FileMovement::delete_via_connector(target, profiles).await
+    }
+
+    async fn copy(&self, source: &Source, to: &str, profiles: &Profiles) -> anyhow::Result<()> {
+        FileMovement::copy_via_connector(source, to, profiles).await
+    }
+
+    async fn rename(&self, source: &Source, to: &str, profiles: &Profiles) -> anyhow::Result<()> {
+        FileMovement::rename_via_connector(source, to, profiles).await
+    }
+}
+
+/// Hears how far a validation run has got while it runs, e.g. to draw a
+/// progress bar. Every method defaults to doing nothing.
+///
+/// Called from the run's task and, for rules, from validation threads.
+pub trait RunProgress: Send + Sync {
+    /// `bytes` more of a source have been received.
+    fn downloaded(&self, _bytes: u64) {}
+
+    /// `rows` more rows have been parsed (a whole input, or one streamed batch).
+    fn parsed(&self, _rows: usize) {}
+
+    /// `total` declared rules are about to be evaluated.
+    fn rules_started(&self, _total: usize) {}
+
+    /// One more declared rule has a result.
+    fn rule_completed(&self) {}
+}
+
+/// Ignores progress.
+pub struct NoProgress;
+
+impl RunProgress for NoProgress {}
+
+/// Shared handle to a run's `RunProgress`; `NoProgress` by default.
+#[derive(Clone)]
+pub struct Progress(Arc<dyn RunProgress>);
+
+impl Progress {
+    pub fn new(progress: impl RunProgress + 'static) -> Self {
+        Self(Arc::new(progress))
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new(NoProgress)
+    }
+}
+
+impl Deref for Progress {
+    type Target = dyn RunProgress;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Progress")
+    }
+}
+
+/// Backends used by a validation run.
+#[derive(Clone)]
+pub struct Environment {
+    pub config: Arc<dyn ConfigStore>,
+    pub storage: Arc<dyn ObjectStore>,
+    pub history: Arc<dyn RunHistory>,
+    /// Told about bytes fetched, rows parsed, and rules completed.
+    pub progress: Progress,
+    /// Fetch and validate without writing to destination or quarantine or
+    /// recording run history; audit entries are tagged `dry_run=true`.
+    pub dry_run: bool,
+    /// Date substituted for `{{ today }}`-style tokens in source locations;
+    /// `None` uses the current UTC date.
+    pub run_date: Option<NaiveDate>,
+    /// Retries after a transient error, overriding the contract's
+    /// `[retry] retries`.
+    pub retries: Option<u32>,
+    /// Wait between attempts, overriding the contract's `[retry] delay`.
+    pub retry_delay: Option<Duration>,
+    /// Longest a source fetch may take, overriding the contract's
+    /// `[limits] fetch_timeout_secs`.
+    pub fetch_timeout: Option<Duration>,
+    /// Longest the whole run may take, overriding the contract's
+    /// `[limits] run_timeout_secs`.
+    pub run_timeout: Option<Duration>,
+    /// Cancel to stop the run at its next safe point: a fetch or retry wait
+    /// in progress is abandoned, but data already being moved finishes
+    /// moving. The run then logs `run_cancelled` and returns
+    /// `ValidationError::Cancelled`.
+    pub cancel: CancellationToken,
+}
+
+impl Default for Environment {
+    /// Files in the working directory and the real storage connectors.
+    fn default() -> Self {
+        Self {
+            config: Arc::new(FsConfig),
+            storage: Arc::new(ConnectorStore),
+            history: Arc::new(HistoryStore::default()),
+            progress: Progress::default(),
+            dry_run: false,
+            run_date: None,
+            retries: None,
+            retry_delay: None,
+            fetch_timeout: None,
+            run_timeout: None,
+            cancel: CancellationToken::new(),
+        }
+    }
+}
+
+impl Environment {
+    /// `source` with run-date tokens in its location rendered for this run.
+    pub fn dated_source(&self, source: &Source) -> ValidationResult<Source> {
+        let Some(location) = source.location.as_deref().filter(|l| has_date_tokens(l)) else {
+            return Ok(source.clone());
+        };
+        let date = self.run_date.unwrap_or_else(|| Utc::now().date_naive());
+        let location = render_date_tokens(location, date)
+            .map_err(|e| ValidationError::Config(format!("source.location: {}", e)))?;
+        Ok(Source {
+            location: Some(location),
+            ..source.clone()
+        })
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+pub use self::memory::InMemory;
+
+#[cfg(any(test, feature = "test-util"))]
+mod memory {
+    use super::*;
+    use crate::connectors::Connector;
+    use crate::connectors::memory::MemoryConnector;
+    use crate::connectors::spool::Payload;
+    use crate::history::MemoryHistory;
+    use crate::profiles::Profile;
+    use std::collections::HashMap;
+    use std::sync::RwLock;
+    use tokio::io::AsyncReadExt;
+
+    /// Contracts, profiles, objects, and run history held in memory.
+    ///
+    /// Objects are keyed by location whatever the source type, so a
+    /// contract reading `s3://bucket/orders.csv` runs unchanged once that
+    /// location has been put. Clones share the same state.
+    #[derive(Clone, Default)]
+    pub struct InMemory {
+        contracts: Arc<RwLock<HashMap<String, String>>>,
+        profiles: Arc<RwLock<Profiles>>,
+        objects: MemoryConnector,
+        history: MemoryHistory,
+    }
+
+    impl InMemory {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Register contract TOML under `name`.
+        pub fn add_contract(&self, name: &str, toml: &str) {
+            self.contracts
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(name.to_string(), toml.to_string());
+        }
+
+        /// Register a connection profile.
+        pub fn add_profile(&self, name: &str, profile: Profile) {
+            self.profiles
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(name.to_string(), profile);
+        }
+
+        /// Store an object at `location`.
+        pub fn put_object(&self, location: &str, data: impl Into<Vec<u8>>) {
+            self.objects.insert(location, data);
+        }
+
+        /// The object stored at `location`, e.g. moved or quarantined data.
+        pub fn object(&self, location: &str) -> Option<Vec<u8>> {
+            self.objects.get(location)
+        }
+
+        /// Locations of all stored objects, sorted.
+        pub fn locations(&self) -> Vec<String> {
+            self.objects.locations()
+        }
+
+        /// Recorded run history.
+        pub fn history(&self) -> &MemoryHistory {
+            &self.history
+        }
+
+        /// An environment backed entirely by this store.
+        pub fn environment(&self) -> Environment {
+            Environment {
+                config: Arc::new(self.clone()),
+                storage: Arc::new(self.clone()),
+                history: Arc::new(self.history.clone()),
+                progress: Progress::default(),
+                dry_run: false,
+                run_date: None,
+                retries: None,
+                retry_delay: None,
+                fetch_timeout: None,
+                run_timeout: None,
+                cancel: CancellationToken::new(),
+            }
+        }
+    }
+
+    impl ConfigStore for InMemory {
+        fn contract_toml(&self, name: &str) -> ValidationResult<String> {
+            self.contracts
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ValidationError::ContractNotFound(name.to_string()))
+        }
+
+        fn profiles(&self) -> ValidationResult<Profiles> {
+            Ok(self
+                .profiles
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone())
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for InMemory {
+        async fn fetch(
+            &self,
+            source: &Source,
+            _profiles: &Profiles,
+        ) -> ValidationResult<FetchedData> {
+            let location = source
+                .location
+                .as_ref()
+                .ok_or_else(|| ValidationError::Config("Source missing location".to_string()))?;
+            let mut reader = self
+                .objects
+                .fetch(location)
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            let mut buf = Vec::new();
+            reader
+                .read_to_end(&mut buf)
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))?;
+            Ok(FetchedData {
+                payload: Payload::Memory(buf),
+                resumes: Vec::new(),
+            })
+        }
+
+        async fn list(
+            &self,
+            source: &Source,
+            prefix: &str,
+            _profiles: &Profiles,
+        ) -> ValidationResult<Vec<ObjectInfo>> {
+            self.objects
+                .list(prefix)
+                .await
+                .map_err(|e| ValidationError::connector_fetch(&source.r#type, e))
+        }
+
+        async fn write(
+            &self,
+            target: &Source,
+            data: &[u8],
+            _profiles: &Profiles,
+        ) -> anyhow::Result<usize> {
+            let location = target
+                .location
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Write target missing location"))?;
+            self.objects.insert(location, data);
+            Ok(data.len())
+        }
+
+        async fn is_reachable(
+            &self,
+            _kind: &str,
+            _profile: Option<&str>,
+            _profiles: &Profiles,
+        ) -> bool {
+            true
+        }
+
+        async fn delete(&self, target: &Source, _profiles: &Profiles) -> anyhow::Result<()> {
+            let location = target
+                .location
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Delete target missing location"))?;
+            self.objects
+                .remove(location)
+                .map(|_| ())
+                .ok_or_else(|| anyhow::anyhow!("No object at {}", location))
+        }
+
+        async fn copy(
+            &self,
+            source: &Source,
+            to: &str,
+            _profiles: &Profiles,
+        ) -> anyhow::Result<()> {
+            let location = source
+                .location
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Copy source missing location"))?;
+            let data = self
+                .objects
+                .get(location)
+                .ok_or_else(|| anyhow::anyhow!("No object at {}", location))?;
+            self.objects.insert(to, data);
+            Ok(())
+        }
+
+        async fn rename(
+            &self,
+            source: &Source,
+            to: &str,
+            _profiles: &Profiles,
+        ) -> anyhow::Result<()> {
+            let location = source
+                .location
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Rename source missing location"))?;
+            let data = self
+                .objects
+                .remove(location)
+                .ok_or_else(|| anyhow::anyhow!("No object at {}", location))?;
+            self.objects.insert(to, data);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::retry::classify;
+    use crate::engine::contracts::run_contract_validation_with;
+    use crate::history::{Delivery, MemoryHistory, RunStats};
+    use crate::logging::MemoryLogger;
+    use crate::logging::schema::Executor;
+    use crate::profiles::Profile;
+
+    const CONTRACT: &str = r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "s3"
+location = "s3://landing/orders.csv"
+profile = "landing"
+
+[destination]
+type = "local"
+location = "clean/"
+
+[quarantine]
+type = "local"
+location = "quarantine/"
+"#;
+
+    fn executor() -> Executor {
+        Executor {
+            user: "test".to_string(),
+            host: "test".to_string(),
+        }
+    }
+
+    fn memory() -> InMemory {
+        let memory = InMemory::new();
+        memory.add_contract("orders", CONTRACT);
+        memory.add_profile(
+            "landing",
+            toml::from_str::<Profile>(r#"provider = "s3""#).unwrap(),
+        );
+        memory
+    }
+
+    #[tokio::test]
+    async fn passing_run_archives_source_on_success() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace(
+                "profile = \"landing\"",
+                "profile = \"landing\"\non_success = \"archive\"\n\
+                 archive_location = \"s3://landing/processed/\"",
+            ),
+        );
+        memory.put_object("s3://landing/orders.csv", "id\n1\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(outcome.passed);
+        assert!(memory.object("s3://landing/orders.csv").is_none());
+        assert_eq!(
+            memory.object("s3://landing/processed/orders.csv").unwrap(),
+            b"id\n1\n"
+        );
+        assert!(logger.events().iter().any(|e| e == "source_archived"));
+    }
+
+    #[tokio::test]
+    async fn run_records_stage_and_rule_timings() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace(
+                "[[columns]]",
+                "[file]\nvalidation = [{ rule = \"row_count\", min = 1 }]\n\n[[columns]]",
+            ),
+        );
+        memory.put_object("s3://landing/orders.csv", "id\n1\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let timings = outcome.timings;
+        assert!(timings.total >= timings.fetch + timings.parse + timings.validate);
+        let timed: Vec<&str> = outcome
+            .results
+            .iter()
+            .filter(|r| r.duration_ms.is_some())
+            .map(|r| r.rule.as_str())
+            .collect();
+        // `not_null` comes from the shared aggregate pass, untimed.
+        assert_eq!(timed, vec!["RowCount"]);
+        let completed = logger
+            .entries()
+            .into_iter()
+            .find(|e| e["event"] == "contract_validation_completed")
+            .unwrap();
+        assert!(completed["details"].as_str().unwrap().contains("total_ms="));
+    }
+
+    #[tokio::test]
+    async fn passing_run_moves_data_in_memory() {
+        let memory = memory();
+        memory.put_object("s3://landing/orders.csv", "id,amount\n1,10\n2,12\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(outcome.passed);
+        let moved: Vec<String> = memory
+            .locations()
+            .into_iter()
+            .filter(|location| location.starts_with("clean/orders_"))
+            .collect();
+        assert_eq!(moved.len(), 1);
+        assert_eq!(
+            memory.object(&moved[0]).unwrap(),
+            b"id,amount\n1,10\n2,12\n"
+        );
+        let runs = memory.history().runs("orders").unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].run_id, outcome.run_id);
+
+        let events = logger.events();
+        for event in [
+            "validation_summary",
+            "movement_success",
+            "contract_validation_completed",
+        ] {
+            assert!(events.iter().any(|e| e == event), "missing {}", event);
+        }
+    }
+
+    #[tokio::test]
+    async fn redelivered_files_are_skipped_or_rejected() {
+        let memory = memory();
+        let skip = CONTRACT.replace(
+            "profile = \"landing\"",
+            "profile = \"landing\"\non_duplicate = \"skip\"",
+        );
+        memory.add_contract("orders", &skip);
+        memory.put_object("s3://landing/orders.csv", "id\n1\n");
+        let env = memory.environment();
+        let (logger, executor) = (MemoryLogger::new(), executor());
+        let run = || run_contract_validation_with(&env, &logger, "orders", &executor, false);
+
+        let (first, _) = run().await.unwrap();
+        assert_eq!(first.pass_count, 1);
+        let delivered = memory.history().deliveries("orders").unwrap();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].run_id, first.run_id);
+
+        // The same content again, as re-sent by upstream
+        let (second, _) = run().await.unwrap();
+        assert!(second.passed);
+        assert_eq!(second.pass_count + second.fail_count, 0);
+        assert!(logger.events().iter().any(|e| e == "duplicate_skipped"));
+        assert_eq!(memory.history().deliveries("orders").unwrap().len(), 1);
+
+        memory.add_contract("orders", &skip.replace("\"skip\"", "\"fail\""));
+        let error = run().await.err().unwrap();
+        assert!(matches!(
+            error,
+            ValidationError::DuplicateSource { run_id, .. } if run_id == first.run_id
+        ));
+
+        // New content is validated as usual
+        memory.put_object("s3://landing/orders.csv", "id\n2\n");
+        assert_eq!(run().await.unwrap().0.pass_count, 1);
+    }
+
+    #[tokio::test]
+    async fn fixed_width_sources_are_read_at_declared_offsets() {
+        let memory = memory();
+        let contract = CONTRACT
+            .replace("name = \"id\"\n", "name = \"id\"\nstart = 0\nwidth = 4\n")
+            .replace(
+                "location = \"s3://landing/orders.csv\"",
+                "location = \"s3://landing/orders.dat\"\nformat = \"fixed_width\"",
+            )
+            + "\n[[columns]]\nname = \"amount\"\nstart = 4\nwidth = 6\n\
+               validation = [{ rule = \"range\", min = 0, max = 100 }]\n";
+        memory.add_contract("orders", &contract);
+        memory.put_object("s3://landing/orders.dat", "0001    12\n0002   250\n");
+        let env = memory.environment();
+
+        let (outcome, _) =
+            run_contract_validation_with(&env, &MemoryLogger::new(), "orders", &executor(), false)
+                .await
+                .unwrap();
+        assert_eq!((outcome.pass_count, outcome.fail_count), (1, 1));
+    }
+
+    /// Tallies what a run reports.
+    #[derive(Clone, Default)]
+    struct Tally(Arc<std::sync::Mutex<(u64, usize, usize, usize)>>);
+
+    impl RunProgress for Tally {
+        fn downloaded(&self, bytes: u64) {
+            self.0.lock().unwrap().0 += bytes;
+        }
+
+        fn parsed(&self, rows: usize) {
+            self.0.lock().unwrap().1 += rows;
+        }
+
+        fn rules_started(&self, total: usize) {
+            self.0.lock().unwrap().2 += total;
+        }
+
+        fn rule_completed(&self) {
+            self.0.lock().unwrap().3 += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn run_reports_bytes_rows_and_rules_to_progress() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace(
+                "[[columns]]",
+                "[file]\nvalidation = [{ rule = \"row_count\", min = 1 }]\n\n[[columns]]",
+            ),
+        );
+        memory.put_object("s3://landing/orders.csv", "id,amount\n1,10\n2,12\n");
+        let tally = Tally::default();
+        let env = Environment {
+            progress: Progress::new(tally.clone()),
+            ..memory.environment()
+        };
+
+        run_contract_validation_with(&env, &MemoryLogger::new(), "orders", &executor(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(*tally.0.lock().unwrap(), (20, 2, 2, 2));
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried_per_contract() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &format!("{}\n[retry]\nretries = 2\ndelay = \"0s\"\n", CONTRACT),
+        );
+        let retried = |logger: &MemoryLogger| -> Vec<String> {
+            logger
+                .entries()
+                .into_iter()
+                .filter(|e| e["event"] == "run_retried")
+                .map(|e| e["details"].as_str().unwrap_or_default().to_string())
+                .collect()
+        };
+
+        // Nothing at the source location: every fetch fails
+        let logger = MemoryLogger::new();
+        let error = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(error.is_transient());
+        let details = retried(&logger);
+        assert_eq!(details.len(), 2);
+        assert!(details[1].starts_with("attempt=3/3, delay=0s, error="));
+
+        let logger = MemoryLogger::new();
+        let env = Environment {
+            retries: Some(0),
+            ..memory.environment()
+        };
+        let result =
+            run_contract_validation_with(&env, &logger, "orders", &executor(), false).await;
+        assert!(result.is_err());
+        assert!(retried(&logger).is_empty());
+    }
+
+    /// Run history whose delivery records can't be written.
+    struct UnwritableDeliveries(MemoryHistory);
+
+    impl RunHistory for UnwritableDeliveries {
+        fn runs(&self, contract: &str) -> std::io::Result<Vec<RunStats>> {
+            self.0.runs(contract)
+        }
+
+        fn record(&self, contract: &str, stats: &RunStats) -> std::io::Result<()> {
+            self.0.record(contract, stats)
+        }
+
+        fn baseline(&self, contract: &str) -> std::io::Result<Option<RunStats>> {
+            self.0.baseline(contract)
+        }
+
+        fn set_baseline(&self, contract: &str, stats: &RunStats) -> std::io::Result<()> {
+            self.0.set_baseline(contract, stats)
+        }
+
+        fn deliveries(&self, contract: &str) -> std::io::Result<Vec<Delivery>> {
+            self.0.deliveries(contract)
+        }
+
+        fn record_delivery(&self, _: &str, _: &Delivery) -> std::io::Result<()> {
+            Err(std::io::Error::other("disk full"))
+        }
+    }
+
+    #[tokio::test]
+    async fn history_errors_after_movement_are_not_retried() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &format!("{}\n[retry]\nretries = 2\ndelay = \"0s\"\n", CONTRACT),
+        );
+        memory.put_object("s3://landing/orders.csv", "id\n1\n");
+        let env = Environment {
+            history: Arc::new(UnwritableDeliveries(MemoryHistory::default())),
+            ..memory.environment()
+        };
+        let logger = MemoryLogger::new();
+
+        let error = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ValidationError::HistoryWrite(_)));
+        assert!(!error.is_transient());
+        let events = logger.events();
+        assert_eq!(
+            events.iter().filter(|e| *e == "movement_success").count(),
+            1
+        );
+        assert!(!events.iter().any(|e| e == "run_retried"));
+    }
+
+    /// Storage whose fetches of one location fail with an HTTP status.
+    struct Flaky(InMemory, &'static str, u16);
+
+    #[async_trait]
+    impl ObjectStore for Flaky {
+        async fn fetch(
+            &self,
+            source: &Source,
+            profiles: &Profiles,
+        ) -> ValidationResult<FetchedData> {
+            if source.location.as_deref() == Some(self.1) {
+                let error = anyhow::anyhow!("status {}", self.2);
+                return Err(ValidationError::connector_fetch(
+                    &source.r#type,
+                    classify(self.2, error),
+                ));
+            }
+            self.0.fetch(source, profiles).await
+        }
+
+        async fn list(
+            &self,
+            source: &Source,
+            prefix: &str,
+            profiles: &Profiles,
+        ) -> ValidationResult<Vec<ObjectInfo>> {
+            self.0.list(source, prefix, profiles).await
+        }
+
+        async fn write(
+            &self,
+            target: &Source,
+            data: &[u8],
+            profiles: &Profiles,
+        ) -> anyhow::Result<usize> {
+            self.0.write(target, data, profiles).await
+        }
+
+        async fn is_reachable(
+            &self,
+            kind: &str,
+            profile: Option<&str>,
+            profiles: &Profiles,
+        ) -> bool {
+            self.0.is_reachable(kind, profile, profiles).await
+        }
+
+        async fn delete(&self, target: &Source, profiles: &Profiles) -> anyhow::Result<()> {
+            self.0.delete(target, profiles).await
+        }
+
+        async fn copy(&self, source: &Source, to: &str, profiles: &Profiles) -> anyhow::Result<()> {
+            self.0.copy(source, to, profiles).await
+        }
+
+        async fn rename(
+            &self,
+            source: &Source,
+            to: &str,
+            profiles: &Profiles,
+        ) -> anyhow::Result<()> {
+            self.0.rename(source, to, profiles).await
+        }
+    }
+
+    #[tokio::test]
+    async fn permanent_fetch_errors_are_not_retried() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &format!("{}\n[retry]\nretries = 2\ndelay = \"0s\"\n", CONTRACT),
+        );
+        let env = Environment {
+            storage: Arc::new(Flaky(memory.clone(), "s3://landing/orders.csv", 404)),
+            ..memory.environment()
+        };
+        let logger = MemoryLogger::new();
+
+        let error = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            error,
+            ValidationError::SourceFetch {
+                permanent: true,
+                ..
+            }
+        ));
+        assert!(!logger.events().iter().any(|e| e == "run_retried"));
+    }
+
+    #[tokio::test]
+    async fn glob_runs_are_not_retried_once_an_object_was_moved() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &format!(
+                "{}\n[retry]\nretries = 2\ndelay = \"0s\"\n",
+                CONTRACT.replace("s3://landing/orders.csv", "s3://landing/orders/*.csv")
+            ),
+        );
+        memory.put_object("s3://landing/orders/a.csv", "id\n1\n");
+        memory.put_object("s3://landing/orders/b.csv", "id\n2\n");
+        let env = Environment {
+            storage: Arc::new(Flaky(memory.clone(), "s3://landing/orders/b.csv", 503)),
+            ..memory.environment()
+        };
+        let logger = MemoryLogger::new();
+
+        let error = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+
+        assert!(error.is_transient());
+        let events = logger.events();
+        assert_eq!(
+            events.iter().filter(|e| *e == "movement_success").count(),
+            1
+        );
+        assert!(!events.iter().any(|e| e == "run_retried"));
+    }
+
+    /// Storage whose fetches never finish, like a hung connection.
+    struct Stalled(InMemory);
+
+    #[async_trait]
+    impl ObjectStore for Stalled {
+        async fn fetch(&self, _: &Source, _: &Profiles) -> ValidationResult<FetchedData> {
+            std::future::pending().await
+        }
+
+        async fn list(
+            &self,
+            source: &Source,
+            prefix: &str,
+            profiles: &Profiles,
+        ) -> ValidationResult<Vec<ObjectInfo>> {
+            self.0.list(source, prefix, profiles).await
+        }
+
+        async fn write(
+            &self,
+            target: &Source,
+            data: &[u8],
+            profiles: &Profiles,
+        ) -> anyhow::Result<usize> {
+            self.0.write(target, data, profiles).await
+        }
+
+        async fn is_reachable(
+            &self,
+            kind: &str,
+            profile: Option<&str>,
+            profiles: &Profiles,
+        ) -> bool {
+            self.0.is_reachable(kind, profile, profiles).await
+        }
+
+        async fn delete(&self, target: &Source, profiles: &Profiles) -> anyhow::Result<()> {
+            self.0.delete(target, profiles).await
+        }
+
+        async fn copy(&self, source: &Source, to: &str, profiles: &Profiles) -> anyhow::Result<()> {
+            self.0.copy(source, to, profiles).await
+        }
+
+        async fn rename(
+            &self,
+            source: &Source,
+            to: &str,
+            profiles: &Profiles,
+        ) -> anyhow::Result<()> {
+            self.0.rename(source, to, profiles).await
+        }
+    }
+
+    #[tokio::test]
+    async fn hung_fetches_and_runs_time_out() {
+        let memory = memory();
+        let stalled = Environment {
+            storage: Arc::new(Stalled(memory.clone())),
+            ..memory.environment()
+        };
+
+        let env = Environment {
+            fetch_timeout: Some(Duration::from_millis(20)),
+            ..stalled.clone()
+        };
+        let logger = MemoryLogger::new();
+        let error = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+        assert!(error.is_transient());
+        assert!(error.to_string().ends_with("timed out after 20ms"));
+
+        let env = Environment {
+            run_timeout: Some(Duration::from_millis(20)),
+            ..stalled
+        };
+        let logger = MemoryLogger::new();
+        let error = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, ValidationError::RunTimeout(_)));
+        let timeouts: Vec<_> = logger
+            .entries()
+            .into_iter()
+            .filter(|e| e["event"] == "run_timeout")
+            .collect();
+        assert_eq!(timeouts.len(), 1);
+        assert_eq!(timeouts[0]["details"], "timeout=20ms");
+    }
+
+    #[tokio::test]
+    async fn cancelled_runs_stop_before_moving_data() {
+        let memory = memory();
+        let cancelled = |logger: &MemoryLogger| -> Vec<String> {
+            logger
+                .entries()
+                .into_iter()
+                .filter(|e| e["event"] == "run_cancelled")
+                .map(|e| e["details"].as_str().unwrap_or_default().to_string())
+                .collect()
+        };
+
+        // Cancelled while the fetch hangs
+        let env = Environment {
+            storage: Arc::new(Stalled(memory.clone())),
+            ..memory.environment()
+        };
+        let cancel = env.cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            cancel.cancel();
+        });
+        let logger = MemoryLogger::new();
+        let error = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, ValidationError::Cancelled("during fetch")));
+        assert_eq!(cancelled(&logger), vec!["Run cancelled during fetch"]);
+
+        // Cancelled before it starts: nothing is fetched or moved
+        memory.put_object("s3://landing/orders.csv", "id\n1\n");
+        let env = memory.environment();
+        env.cancel.cancel();
+        let logger = MemoryLogger::new();
+        let result =
+            run_contract_validation_with(&env, &logger, "orders", &executor(), false).await;
+        assert!(matches!(
+            result,
+            Err(ValidationError::Cancelled("before start"))
+        ));
+        assert_eq!(cancelled(&logger).len(), 1);
+        assert_eq!(memory.locations(), vec!["s3://landing/orders.csv"]);
+    }
+
+    /// Blocks the run's thread for a while once the input is parsed.
+    struct SlowParse;
+
+    impl RunProgress for SlowParse {
+        fn parsed(&self, _rows: usize) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn runs_timing_out_during_validation_stop_before_moving_data() {
+        let memory = memory();
+        memory.put_object("s3://landing/orders.csv", "id\n1\n");
+        let env = Environment {
+            progress: Progress::new(SlowParse),
+            run_timeout: Some(Duration::from_millis(20)),
+            ..memory.environment()
+        };
+        let logger = MemoryLogger::new();
+
+        let error = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ValidationError::RunTimeout(_)));
+        let events = logger.events();
+        assert!(events.iter().any(|e| e == "validation_summary"));
+        assert!(events.iter().any(|e| e == "run_timeout"));
+        assert!(!events.iter().any(|e| e.starts_with("movement_")));
+        assert!(!events.iter().any(|e| e == "run_cancelled"));
+        assert_eq!(memory.locations(), vec!["s3://landing/orders.csv"]);
+    }
+
+    #[tokio::test]
+    async fn input_over_memory_limit_aborts_rules_without_parsing() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &format!("{}\n[limits]\nmax_memory_bytes = 4\n", CONTRACT),
+        );
+        memory.put_object("s3://landing/orders.csv", "id\n1\n2\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(!outcome.passed);
+        let aborted = &outcome.results[0];
+        assert_eq!(aborted.result, "aborted");
+        assert_eq!(
+            aborted.details.as_deref(),
+            Some(
+                "limit exceeded: bytes=7 > max_memory_bytes=4; \
+                 add [streaming] to validate it in batches"
+            )
+        );
+        assert_eq!(memory.locations(), vec!["s3://landing/orders.csv"]);
+        let events = logger.events();
+        assert!(!events.iter().any(|e| e == "dataframe_parsed"));
+        for event in ["movement_skipped", "contract_validation_completed"] {
+            assert!(events.iter().any(|e| e == event), "missing {}", event);
+        }
+    }
+
+    #[tokio::test]
+    async fn input_over_memory_limit_is_streamed_but_not_moved() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &format!(
+                "{}\n[limits]\nmax_memory_bytes = 4\n\n\
+                 [streaming]\nchunk_rows = 1\nmin_bytes = 1_000_000\n",
+                CONTRACT
+            ),
+        );
+        memory.put_object("s3://landing/orders.csv", "id\n1\n2\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(outcome.passed);
+        assert_eq!(memory.locations(), vec!["s3://landing/orders.csv"]);
+        let skipped = logger
+            .entries()
+            .into_iter()
+            .find(|e| e["event"] == "movement_skipped")
+            .unwrap();
+        assert_eq!(skipped["details"], "Input exceeds max_memory_bytes limit");
+        assert!(logger.events().iter().any(|e| e == "dataframe_streamed"));
+        let runs = memory.history().runs("orders").unwrap();
+        assert_eq!(runs[0].run_id, outcome.run_id);
+    }
+
+    #[tokio::test]
+    async fn failing_run_quarantines_in_memory() {
+        let memory = memory();
+        memory.put_object("s3://landing/orders.csv", "id,amount\n1,10\n,12\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(!outcome.passed);
+        assert!(
+            memory
+                .locations()
+                .iter()
+                .any(|location| location.starts_with("quarantine/orders_"))
+        );
+        assert!(logger.events().iter().any(|e| e == "movement_quarantine"));
+    }
+
+    #[tokio::test]
+    async fn missing_contract_and_source_are_typed_errors() {
+        let memory = memory();
+        let logger = MemoryLogger::new();
+        let env = memory.environment();
+
+        let err = run_contract_validation_with(&env, &logger, "invoices", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, ValidationError::ContractNotFound(ref name) if name == "invoices"));
+        assert!(err.is_config());
+
+        let err = run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, ValidationError::SourceFetch { ref provider, .. } if provider == "s3")
+        );
+        assert!(!err.is_config());
+    }
+
+    #[tokio::test]
+    async fn dry_run_moves_nothing_and_tags_entries() {
+        let memory = memory();
+        memory.put_object("s3://landing/orders.csv", "id,amount\n1,10\n,12\n");
+        let logger = MemoryLogger::new();
+        let env = Environment {
+            dry_run: true,
+            ..memory.environment()
+        };
+
+        let (outcome, _) =
+            run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+                .await
+                .unwrap();
+
+        assert!(!outcome.passed);
+        assert_eq!(memory.locations(), vec!["s3://landing/orders.csv"]);
+        assert!(memory.history().runs("orders").unwrap().is_empty());
+        let entries = logger.entries();
+        assert!(entries.iter().all(|entry| {
+            entry["details"]
+                .as_str()
+                .is_some_and(|d| d.starts_with("dry_run=true"))
+        }));
+        assert!(
+            entries
+                .iter()
+                .any(|entry| entry["event"] == "movement_skipped")
+        );
+    }
+
+    #[tokio::test]
+    async fn transfers_accumulate_per_connector_over_a_run() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace("s3://landing/orders.csv", "s3://landing/orders/*.csv"),
+        );
+        memory.put_object("s3://landing/orders/a.csv", "id\n1\n");
+        memory.put_object("s3://landing/orders/b.csv", "id\n2\n3\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let written: usize = memory
+            .locations()
+            .iter()
+            .filter(|l| l.starts_with("clean/"))
+            .map(|l| memory.object(l).unwrap().len())
+            .sum();
+        assert_eq!(outcome.objects.len(), 2);
+        assert_eq!(outcome.transfer.downloaded["s3"], 12);
+        assert_eq!(outcome.transfer.uploaded["local"], written as u64);
+        assert!(!outcome.transfer.downloaded.contains_key("local"));
+        assert!(!outcome.transfer.uploaded.contains_key("s3"));
+        // The whole run's completion entry follows each object's.
+        let completed = logger
+            .entries()
+            .into_iter()
+            .rev()
+            .find(|e| e["event"] == "contract_validation_completed")
+            .unwrap();
+        assert!(
+            completed["details"]
+                .as_str()
+                .unwrap()
+                .contains(&format!("bytes_downloaded=12, bytes_uploaded={}", written))
+        );
+    }
+
+    #[tokio::test]
+    async fn glob_source_runs_each_matching_object() {
+        let memory = InMemory::new();
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace("s3://landing/orders.csv", "s3://landing/orders/*.csv"),
+        );
+        memory.add_profile(
+            "landing",
+            toml::from_str::<Profile>(r#"provider = "s3""#).unwrap(),
+        );
+        memory.put_object("s3://landing/orders/a.csv", "id\n1\n");
+        memory.put_object("s3://landing/orders/b.csv", "id\n\n2\n");
+        memory.put_object("s3://landing/orders/c.json", "[]");
+        memory.put_object("s3://landing/orders/.pipa-tmp-c.csv", "id\n");
+        memory.put_object("s3://landing/orders/old/d.csv", "id\n4\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let objects: Vec<(&str, bool)> = outcome
+            .objects
+            .iter()
+            .map(|o| (o.location.as_str(), o.outcome.passed))
+            .collect();
+        assert_eq!(
+            objects,
+            [
+                ("s3://landing/orders/a.csv", true),
+                ("s3://landing/orders/b.csv", false),
+            ]
+        );
+        assert!(!outcome.passed);
+        assert_eq!(outcome.fail_count, 1);
+        // One record per run, with the last object's statistics
+        let runs = memory.history().runs("orders").unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(
+            (runs[0].run_id.as_str(), runs[0].rows),
+            (outcome.run_id.as_str(), 2)
+        );
+        assert!(logger.events().iter().any(|e| e == "source_expanded"));
+
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace("s3://landing/orders.csv", "s3://landing/none/*.csv"),
+        );
+        let err = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(err.is_config());
+    }
+
+    #[tokio::test]
+    async fn archive_members_are_validated_each_or_concatenated() {
+        use std::io::Write;
+
+        let memory = memory();
+        let contract = CONTRACT.replace(
+            "location = \"s3://landing/orders.csv\"",
+            "location = \"s3://landing/batch.zip\"\narchive_member = \"orders_*.csv\"\n\
+             on_success = \"delete\"",
+        );
+        memory.add_contract("orders", &contract);
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, content) in [
+            ("orders_1.csv", "id\n1\n"),
+            ("orders_2.csv", "id\n2\n"),
+            ("README.txt", "daily bundle"),
+        ] {
+            zip.start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        let bundle = zip.finish().unwrap().into_inner();
+        memory.put_object("s3://landing/batch.zip", bundle.clone());
+        let (logger, executor) = (MemoryLogger::new(), executor());
+        let env = memory.environment();
+
+        let (outcome, _) = run_contract_validation_with(&env, &logger, "orders", &executor, false)
+            .await
+            .unwrap();
+        let objects: Vec<&str> = outcome
+            .objects
+            .iter()
+            .map(|o| o.location.as_str())
+            .collect();
+        assert_eq!(
+            objects,
+            [
+                "s3://landing/batch.zip/orders_1.csv",
+                "s3://landing/batch.zip/orders_2.csv",
+            ]
+        );
+        assert!(outcome.passed);
+        assert!(logger.events().iter().any(|e| e == "archive_extracted"));
+        let moved = memory
+            .locations()
+            .into_iter()
+            .filter(|l| l.starts_with("clean/orders_"));
+        assert_eq!(moved.count(), 2);
+        // The archive is settled once, after every member was delivered
+        assert!(memory.object("s3://landing/batch.zip").is_none());
+
+        memory.add_contract(
+            "orders",
+            &contract.replace("on_success = \"delete\"", "archive_mode = \"concat\""),
+        );
+        memory.put_object("s3://landing/batch.zip", bundle);
+        let (outcome, _) = run_contract_validation_with(&env, &logger, "orders", &executor, false)
+            .await
+            .unwrap();
+        assert!(outcome.passed && outcome.objects.is_empty());
+        assert_eq!(outcome.pass_count, 1);
+        let concatenated: Vec<String> = memory
+            .locations()
+            .into_iter()
+            .filter(|l| l.starts_with("clean/batch_"))
+            .collect();
+        assert_eq!(concatenated.len(), 1);
+        assert_eq!(memory.object(&concatenated[0]).unwrap(), b"id\n1\n2\n");
+    }
+
+    #[tokio::test]
+    async fn mapped_columns_are_validated_and_moved_by_canonical_name() {
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace("[source]", "[mapping]\n\"Order No\" = \"id\"\n\n[source]"),
+        );
+        memory.put_object("s3://landing/orders.csv", "Order No,Amount\n1,10\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(outcome.passed);
+        assert_eq!(outcome.results[0].column, "id");
+        let moved = memory
+            .locations()
+            .into_iter()
+            .find(|location| location.starts_with("clean/orders_"))
+            .unwrap();
+        assert_eq!(memory.object(&moved).unwrap(), b"id,Amount\n1,10\n");
+    }
+
+    #[tokio::test]
+    async fn source_location_renders_run_date_tokens() {
+        let memory = InMemory::new();
+        memory.add_contract(
+            "orders",
+            &CONTRACT.replace(
+                "s3://landing/orders.csv",
+                "s3://landing/{{ yesterday }}/orders.csv",
+            ),
+        );
+        memory.add_profile(
+            "landing",
+            toml::from_str::<Profile>(r#"provider = "s3""#).unwrap(),
+        );
+        memory.put_object("s3://landing/2025-01-14/orders.csv", "id\n1\n");
+        let logger = MemoryLogger::new();
+        let env = Environment {
+            run_date: NaiveDate::from_ymd_opt(2025, 1, 15),
+            ..memory.environment()
+        };
+
+        let (outcome, _) =
+            run_contract_validation_with(&env, &logger, "orders", &executor(), false)
+                .await
+                .unwrap();
+
+        assert!(outcome.passed);
+        let locations = memory.locations();
+        assert!(locations.iter().any(|l| l.starts_with("clean/orders_")));
+        assert!(locations.contains(&"s3://landing/2025-01-14/orders.csv".to_string()));
+    }
+
+    #[tokio::test]
+    async fn joined_sources_are_validated_as_one_dataset() {
+        let memory = InMemory::new();
+        memory.add_contract(
+            "orders",
+            r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "customer"
+validation = [{ rule = "not_null" }]
+
+[[sources]]
+name = "detail"
+type = "local"
+location = "landing/detail.csv"
+
+[[sources]]
+name = "header"
+type = "local"
+location = "landing/header.csv"
+
+[combine]
+mode = "join"
+on = ["order_id"]
+how = "left"
+
+[quarantine]
+type = "local"
+location = "quarantine/"
+"#,
+        );
+        memory.put_object("landing/header.csv", "order_id,customer\n1,ann\n2,bob\n");
+        memory.put_object("landing/detail.csv", "order_id,sku\n1,a\n1,b\n3,c\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        // Detail row 3 has no header, so its joined `customer` is null.
+        assert!(!outcome.passed);
+        assert_eq!(outcome.fail_count, 1);
+        assert_eq!(outcome.transfer.total_downloaded(), 55);
+        assert!(
+            memory
+                .locations()
+                .iter()
+                .any(|l| l.starts_with("quarantine/detail_"))
+        );
+        assert!(logger.events().contains(&"sources_combined".to_string()));
+    }
+
+    #[tokio::test]
+    async fn failed_notification_is_logged_without_failing_the_run() {
+        // A port nothing listens on: delivery is refused.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        let memory = memory();
+        memory.add_contract(
+            "orders",
+            &format!(
+                "{}\n[[notifications.channels]]\ntype = \"webhook\"\nurl = \"{}\"\n",
+                CONTRACT, url
+            ),
+        );
+        memory.put_object("s3://landing/orders.csv", "id,amount\n1,10\n,12\n");
+        let logger = MemoryLogger::new();
+
+        let (outcome, _) = run_contract_validation_with(
+            &memory.environment(),
+            &logger,
+            "orders",
+            &executor(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(!outcome.passed);
+        assert!(outcome.quarantined);
+        assert_eq!(logger.events().last().unwrap(), "notification_failed");
+    }
+
+    #[tokio::test]
+    async fn unknown_contract_is_a_config_error() {
+        let err = run_contract_validation_with(
+            &memory().environment(),
+            &MemoryLogger::new(),
+            "missing",
+            &executor(),
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(err.is_config());
+    }
+}
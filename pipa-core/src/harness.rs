@@ -0,0 +1,294 @@
+//! Docker harness for connector integration tests (`test-util` feature).
+//!
+//! Starts a storage emulator in a throwaway container, waits until it
+//! answers HTTP, and hands out a matching `Profile` and source locations:
+//!
+//! | Emulator    | Image                                    | Provider |
+//! |-------------|------------------------------------------|----------|
+//! | LocalStack  | `localstack/localstack`                  | `s3`     |
+//! | Azurite     | `mcr.microsoft.com/azure-storage/azurite`| `azure`  |
+//! | fake-gcs    | `fsouza/fake-gcs-server`                 | `gcs`    |
+//!
+//! ```ignore
+//! let harness = EmulatorHarness::start(Emulator::LocalStack).await?;
+//! harness.seed("bucket", "orders.csv", b"id\n1\n").await?;
+//! let location = harness.location("bucket", "orders.csv");
+//! ```
+//!
+//! The container is removed when the harness is dropped.
+
+use crate::connectors::{AzureConnector, GCSConnector, S3Connector};
+use crate::profiles::Profile;
+use anyhow::{Context, Result, bail};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Well-known Azurite development account.
+const AZURITE_ACCOUNT: &str = "devstoreaccount1";
+const AZURITE_KEY: &str =
+    "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+
+/// How long to wait for an emulator to come up.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Storage emulators the harness can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emulator {
+    /// S3 via LocalStack.
+    LocalStack,
+    /// Azure Blob Storage via Azurite.
+    Azurite,
+    /// Google Cloud Storage via fake-gcs-server.
+    FakeGcs,
+}
+
+impl Emulator {
+    /// Docker image to run.
+    pub fn image(self) -> &'static str {
+        match self {
+            Emulator::LocalStack => "localstack/localstack",
+            Emulator::Azurite => "mcr.microsoft.com/azure-storage/azurite",
+            Emulator::FakeGcs => "fsouza/fake-gcs-server",
+        }
+    }
+
+    /// Port the emulator listens on inside the container.
+    fn container_port(self) -> u16 {
+        match self {
+            Emulator::LocalStack => 4566,
+            Emulator::Azurite => 10000,
+            Emulator::FakeGcs => 4443,
+        }
+    }
+
+    fn env(self) -> &'static [&'static str] {
+        match self {
+            Emulator::LocalStack => &["SERVICES=s3"],
+            Emulator::Azurite | Emulator::FakeGcs => &[],
+        }
+    }
+
+    fn args(self) -> &'static [&'static str] {
+        match self {
+            Emulator::LocalStack => &[],
+            Emulator::Azurite => &[
+                "azurite-blob",
+                "--blobHost",
+                "0.0.0.0",
+                "--skipApiVersionCheck",
+            ],
+            Emulator::FakeGcs => &["-scheme", "http", "-port", "4443"],
+        }
+    }
+
+    /// Path answering once the emulator is ready.
+    fn health_path(self) -> &'static str {
+        match self {
+            Emulator::LocalStack => "/_localstack/health",
+            Emulator::Azurite => "/devstoreaccount1?comp=list",
+            Emulator::FakeGcs => "/storage/v1/b",
+        }
+    }
+
+    /// Provider name used in profiles and `[source] type`.
+    pub fn provider(self) -> &'static str {
+        match self {
+            Emulator::LocalStack => "s3",
+            Emulator::Azurite => "azure",
+            Emulator::FakeGcs => "gcs",
+        }
+    }
+}
+
+/// Whether a usable `docker` CLI and daemon are available.
+pub fn docker_available() -> bool {
+    Command::new("docker")
+        .args(["info", "--format", "{{.ServerVersion}}"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// A running emulator container.
+pub struct EmulatorHarness {
+    emulator: Emulator,
+    container_id: String,
+    port: u16,
+}
+
+impl EmulatorHarness {
+    /// Start `emulator` on a free local port and wait until it is ready.
+    pub async fn start(emulator: Emulator) -> Result<Self> {
+        let port = free_port()?;
+        let mut command = Command::new("docker");
+        command.args(["run", "-d", "--rm", "-p"]);
+        command.arg(format!("127.0.0.1:{}:{}", port, emulator.container_port()));
+        for env in emulator.env() {
+            command.args(["-e", env]);
+        }
+        command.arg(emulator.image()).args(emulator.args());
+
+        let output = command.output().context("Failed to run docker")?;
+        if !output.status.success() {
+            bail!(
+                "Failed to start {}: {}",
+                emulator.image(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let harness = EmulatorHarness {
+            emulator,
+            container_id: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            port,
+        };
+        harness.wait_ready().await?;
+        Ok(harness)
+    }
+
+    async fn wait_ready(&self) -> Result<()> {
+        let client = reqwest::Client::new();
+        let url = format!("{}{}", self.endpoint(), self.emulator.health_path());
+        let started = Instant::now();
+
+        // Any HTTP response (even 403 from Azurite) means the server is up
+        while started.elapsed() < STARTUP_TIMEOUT {
+            if client.get(&url).send().await.is_ok() {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        bail!(
+            "{} did not become ready within {}s",
+            self.emulator.image(),
+            STARTUP_TIMEOUT.as_secs()
+        )
+    }
+
+    /// The emulator being run.
+    pub fn emulator(&self) -> Emulator {
+        self.emulator
+    }
+
+    /// Base HTTP endpoint of the emulator.
+    pub fn endpoint(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    /// Profile pointing the matching connector at the emulator.
+    pub fn profile(&self) -> Profile {
+        let mut profile = Profile {
+            provider: self.emulator.provider().to_string(),
+            endpoint: None,
+            max_download_bytes_per_sec: None,
+            resume_attempts: None,
+            spool_threshold_bytes: None,
+            retry_attempts: None,
+            retry_backoff_ms: None,
+            retry_max_backoff_ms: None,
+            multipart_threshold_bytes: None,
+            multipart_part_bytes: None,
+            region: None,
+            access_key: None,
+            secret_key: None,
+            path_style: None,
+            session_token: None,
+            role_arn: None,
+            external_id: None,
+            role_session_name: None,
+            account_name: None,
+            connection_string: None,
+            sas_token: None,
+            tenant_id: None,
+            client_id: None,
+            client_secret: None,
+            managed_identity: None,
+            service_account_json: None,
+            application_default_credentials: None,
+            consumer_group: None,
+        };
+        match self.emulator {
+            Emulator::LocalStack => {
+                profile.endpoint = Some(self.endpoint());
+                profile.region = Some("us-east-1".to_string());
+                profile.access_key = Some("test".to_string());
+                profile.secret_key = Some("test".to_string());
+                profile.path_style = Some(true);
+            }
+            Emulator::Azurite => {
+                profile.account_name = Some(AZURITE_ACCOUNT.to_string());
+                profile.connection_string = Some(format!(
+                    "DefaultEndpointsProtocol=http;AccountName={};AccountKey={};BlobEndpoint={}/{}",
+                    AZURITE_ACCOUNT,
+                    AZURITE_KEY,
+                    self.endpoint(),
+                    AZURITE_ACCOUNT
+                ));
+            }
+            Emulator::FakeGcs => {
+                profile.endpoint = Some(self.endpoint());
+            }
+        }
+        profile
+    }
+
+    /// Source location for `object` in `bucket` (container for Azurite).
+    pub fn location(&self, bucket: &str, object: &str) -> String {
+        match self.emulator {
+            Emulator::LocalStack => format!("s3://{}/{}", bucket, object),
+            Emulator::Azurite => format!(
+                "{}/{}/{}/{}",
+                self.endpoint(),
+                AZURITE_ACCOUNT,
+                bucket,
+                object
+            ),
+            Emulator::FakeGcs => format!("gs://{}/{}", bucket, object),
+        }
+    }
+
+    /// Create `bucket` if needed and upload `data` as `object`.
+    pub async fn seed(&self, bucket: &str, object: &str, data: &[u8]) -> Result<()> {
+        let profile = self.profile();
+        let location = self.location(bucket, object);
+        let url = Url::parse(&location)?;
+
+        match self.emulator {
+            Emulator::LocalStack => {
+                let connector = S3Connector::from_profile_and_url(&profile, &url).await?;
+                connector.create_bucket().await?;
+                connector.put_object_from_url(&location, data).await
+            }
+            Emulator::Azurite => {
+                let connector = AzureConnector::from_profile_and_url(&profile, &url).await?;
+                let container_url = format!("{}/{}/{}", self.endpoint(), AZURITE_ACCOUNT, bucket);
+                connector.create_container(&container_url).await?;
+                connector.put_object_from_url(&location, data).await
+            }
+            Emulator::FakeGcs => {
+                let connector = GCSConnector::from_profile_and_url(&profile, &url).await?;
+                connector.create_bucket(bucket, "test").await?;
+                connector.put_object_from_url(&location, data).await
+            }
+        }
+    }
+}
+
+impl Drop for EmulatorHarness {
+    fn drop(&mut self) {
+        let _ = Command::new("docker")
+            .args(["rm", "-f", &self.container_id])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Ask the OS for a currently free TCP port.
+fn free_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    Ok(listener.local_addr()?.port())
+}
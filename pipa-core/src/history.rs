@@ -0,0 +1,628 @@
+//! Run-history store.
+//!
+//! Every validated run records column-level statistics per contract under
+//! `.pipa/state/<contract>/`:
+//! - `history.jsonl` — one `RunStats` per run, oldest first, capped at
+//!   `MAX_HISTORY` entries,
+//! - `baseline.json` — a pinned `RunStats` (see `pipa contract baseline`),
+//! - `deliveries.jsonl` — one `Delivery` per source file whose data passed
+//!   validation, oldest first, capped at `MAX_DELIVERIES` entries.
+//!
+//! `drift` rules read their reference statistics from here, and a source's
+//! `on_duplicate` looks up earlier deliveries of the same content.
+//!
+//! Storage goes through the `RunHistory` trait: `HistoryStore` is the
+//! file-backed default, `MemoryHistory` keeps everything in memory for
+//! hermetic runs (see `environment`).
+
+use crate::contracts::{DriftMetric, DriftReference};
+use crate::logging::error::ValidationResult;
+use chrono::Utc;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Default location of the run-history store, relative to the project root.
+pub const STATE_DIR: &str = ".pipa/state";
+
+/// Number of runs kept in `history.jsonl` per contract.
+pub const MAX_HISTORY: usize = 100;
+
+/// Number of deliveries kept in `deliveries.jsonl` per contract.
+pub const MAX_DELIVERIES: usize = 1000;
+
+/// Statistics recorded for a single column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnStats {
+    pub null_ratio: f64,
+    pub distinct_ratio: f64,
+    pub mean: Option<f64>,
+    pub stdev: Option<f64>,
+}
+
+impl ColumnStats {
+    /// Compute statistics for one column.
+    pub fn from_series(series: &Series) -> ValidationResult<Self> {
+        let rows = series.len();
+        let ratio = |count: usize| {
+            if rows == 0 {
+                0.0
+            } else {
+                count as f64 / rows as f64
+            }
+        };
+
+        let (mean, stdev) = if series.dtype().is_primitive_numeric() {
+            let values = series.cast(&DataType::Float64)?;
+            let values = values.f64()?;
+            (values.mean(), values.std(1))
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            null_ratio: ratio(series.null_count()),
+            distinct_ratio: ratio(series.drop_nulls().n_unique()?),
+            mean,
+            stdev,
+        })
+    }
+
+    /// Value of a column-level `metric`; `None` for `row_count`, which is
+    /// recorded per run.
+    pub fn metric(&self, metric: DriftMetric) -> Option<f64> {
+        match metric {
+            DriftMetric::RowCount => None,
+            DriftMetric::NullRatio => Some(self.null_ratio),
+            DriftMetric::DistinctRatio => Some(self.distinct_ratio),
+            DriftMetric::Mean => self.mean,
+            DriftMetric::Stdev => self.stdev,
+        }
+    }
+}
+
+/// Statistics recorded for one validated run of a contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStats {
+    pub run_id: String,
+    pub timestamp: String,
+    pub contract_version: String,
+    pub rows: usize,
+    pub columns: BTreeMap<String, ColumnStats>,
+}
+
+impl RunStats {
+    /// Compute statistics for every column of `df`, for run `run_id`.
+    pub fn from_dataframe(
+        df: &DataFrame,
+        contract_version: &str,
+        run_id: &str,
+    ) -> ValidationResult<Self> {
+        let mut columns = BTreeMap::new();
+        for column in df.get_columns() {
+            let series = column.as_materialized_series();
+            columns.insert(series.name().to_string(), ColumnStats::from_series(series)?);
+        }
+        Ok(Self::new(df.height(), columns, contract_version, run_id))
+    }
+
+    /// Run `run_id` of `rows` rows with already computed column statistics,
+    /// stamped with the current time.
+    pub fn new(
+        rows: usize,
+        columns: BTreeMap<String, ColumnStats>,
+        contract_version: &str,
+        run_id: &str,
+    ) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            contract_version: contract_version.to_string(),
+            rows,
+            columns,
+        }
+    }
+
+    /// Value of `metric` for `column`, if recorded.
+    pub fn metric(&self, column: &str, metric: DriftMetric) -> Option<f64> {
+        match metric {
+            DriftMetric::RowCount => Some(self.rows as f64),
+            _ => self.columns.get(column)?.metric(metric),
+        }
+    }
+}
+
+/// A source file whose data passed validation, identified by the SHA-256
+/// of its content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Delivery {
+    pub sha256: String,
+    pub location: String,
+    pub run_id: String,
+    pub timestamp: String,
+}
+
+impl Delivery {
+    /// A delivery of content `sha256` from `location` in run `run_id`,
+    /// stamped with the current time.
+    pub fn new(sha256: &str, location: &str, run_id: &str) -> Self {
+        Self {
+            sha256: sha256.to_string(),
+            location: location.to_string(),
+            run_id: run_id.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Storage for recorded runs, pinned baselines, and source deliveries.
+pub trait RunHistory: Send + Sync {
+    /// All recorded runs, oldest first.
+    fn runs(&self, contract: &str) -> io::Result<Vec<RunStats>>;
+
+    /// Append a run, dropping the oldest entries beyond `MAX_HISTORY`. A run
+    /// already recorded under the same `run_id` (an earlier object of a
+    /// glob or archive source) is replaced in place, so each run is
+    /// recorded once, with the statistics of its last input.
+    fn record(&self, contract: &str, stats: &RunStats) -> io::Result<()>;
+
+    /// The pinned baseline run.
+    fn baseline(&self, contract: &str) -> io::Result<Option<RunStats>>;
+
+    /// Pin `stats` as the baseline for `contract`.
+    fn set_baseline(&self, contract: &str, stats: &RunStats) -> io::Result<()>;
+
+    /// All recorded deliveries, oldest first.
+    fn deliveries(&self, contract: &str) -> io::Result<Vec<Delivery>>;
+
+    /// Append a delivery, dropping the oldest entries beyond `MAX_DELIVERIES`.
+    fn record_delivery(&self, contract: &str, delivery: &Delivery) -> io::Result<()>;
+
+    /// The most recent delivery of content `sha256`.
+    fn delivered(&self, contract: &str, sha256: &str) -> io::Result<Option<Delivery>> {
+        Ok(self
+            .deliveries(contract)?
+            .into_iter()
+            .rev()
+            .find(|delivery| delivery.sha256 == sha256))
+    }
+
+    /// The most recent recorded run.
+    fn latest(&self, contract: &str) -> io::Result<Option<RunStats>> {
+        Ok(self.runs(contract)?.pop())
+    }
+
+    /// A recorded run by id.
+    fn run(&self, contract: &str, run_id: &str) -> io::Result<Option<RunStats>> {
+        Ok(self
+            .runs(contract)?
+            .into_iter()
+            .find(|run| run.run_id == run_id))
+    }
+
+    /// Reference run for a `drift` rule.
+    fn reference(&self, contract: &str, against: DriftReference) -> io::Result<Option<RunStats>> {
+        match against {
+            DriftReference::Previous => self.latest(contract),
+            DriftReference::Baseline => self.baseline(contract),
+        }
+    }
+}
+
+/// File-backed run-history store.
+pub struct HistoryStore {
+    root: PathBuf,
+}
+
+impl Default for HistoryStore {
+    fn default() -> Self {
+        Self::new(STATE_DIR)
+    }
+}
+
+impl HistoryStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn contract_dir(&self, contract: &str) -> PathBuf {
+        let safe: String = contract
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.root.join(safe)
+    }
+
+    fn history_path(&self, contract: &str) -> PathBuf {
+        self.contract_dir(contract).join("history.jsonl")
+    }
+
+    fn baseline_path(&self, contract: &str) -> PathBuf {
+        self.contract_dir(contract).join("baseline.json")
+    }
+
+    fn deliveries_path(&self, contract: &str) -> PathBuf {
+        self.contract_dir(contract).join("deliveries.jsonl")
+    }
+}
+
+/// Append `entry` to the JSON lines file at `path`, rewriting it without
+/// the oldest entries when it already holds `max`.
+fn append_capped<T: Serialize>(
+    path: PathBuf,
+    mut entries: Vec<T>,
+    entry: T,
+    max: usize,
+) -> io::Result<()> {
+    if entries.len() >= max {
+        entries.drain(..=entries.len() - max);
+        entries.push(entry);
+        return write_lines(path, &entries);
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)
+}
+
+/// Replace the JSON lines file at `path` with `entries`.
+fn write_lines<T: Serialize>(path: PathBuf, entries: &[T]) -> io::Result<()> {
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&serde_json::to_string(entry)?);
+        out.push('\n');
+    }
+    fs::write(path, out)
+}
+
+/// Read a JSON lines file; a missing file is empty and unreadable lines are
+/// skipped.
+fn read_lines<T: for<'de> Deserialize<'de>>(path: PathBuf) -> io::Result<Vec<T>> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(entry) = serde_json::from_str::<T>(&line?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+impl RunHistory for HistoryStore {
+    fn record(&self, contract: &str, stats: &RunStats) -> io::Result<()> {
+        fs::create_dir_all(self.contract_dir(contract))?;
+        let mut runs = self.runs(contract)?;
+        if let Some(recorded) = runs.iter_mut().find(|run| run.run_id == stats.run_id) {
+            *recorded = stats.clone();
+            return write_lines(self.history_path(contract), &runs);
+        }
+        append_capped(
+            self.history_path(contract),
+            runs,
+            stats.clone(),
+            MAX_HISTORY,
+        )
+    }
+
+    /// Unreadable lines are skipped.
+    fn runs(&self, contract: &str) -> io::Result<Vec<RunStats>> {
+        read_lines(self.history_path(contract))
+    }
+
+    fn baseline(&self, contract: &str) -> io::Result<Option<RunStats>> {
+        match fs::read_to_string(self.baseline_path(contract)) {
+            Ok(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn set_baseline(&self, contract: &str, stats: &RunStats) -> io::Result<()> {
+        fs::create_dir_all(self.contract_dir(contract))?;
+        fs::write(
+            self.baseline_path(contract),
+            serde_json::to_string_pretty(stats)?,
+        )
+    }
+
+    /// Unreadable lines are skipped.
+    fn deliveries(&self, contract: &str) -> io::Result<Vec<Delivery>> {
+        read_lines(self.deliveries_path(contract))
+    }
+
+    fn record_delivery(&self, contract: &str, delivery: &Delivery) -> io::Result<()> {
+        fs::create_dir_all(self.contract_dir(contract))?;
+        let deliveries = self.deliveries(contract)?;
+        append_capped(
+            self.deliveries_path(contract),
+            deliveries,
+            delivery.clone(),
+            MAX_DELIVERIES,
+        )
+    }
+}
+
+/// In-memory run-history store; clones share the same runs.
+#[derive(Clone, Default)]
+pub struct MemoryHistory {
+    contracts: Arc<Mutex<HashMap<String, ContractHistory>>>,
+}
+
+#[derive(Default)]
+struct ContractHistory {
+    runs: Vec<RunStats>,
+    baseline: Option<RunStats>,
+    deliveries: Vec<Delivery>,
+}
+
+impl MemoryHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with<T>(&self, contract: &str, f: impl FnOnce(&mut ContractHistory) -> T) -> T {
+        let mut contracts = self.contracts.lock().unwrap_or_else(|e| e.into_inner());
+        f(contracts.entry(contract.to_string()).or_default())
+    }
+}
+
+impl RunHistory for MemoryHistory {
+    fn runs(&self, contract: &str) -> io::Result<Vec<RunStats>> {
+        Ok(self.with(contract, |history| history.runs.clone()))
+    }
+
+    fn record(&self, contract: &str, stats: &RunStats) -> io::Result<()> {
+        self.with(contract, |history| {
+            if let Some(recorded) = history.runs.iter_mut().find(|r| r.run_id == stats.run_id) {
+                *recorded = stats.clone();
+                return;
+            }
+            history.runs.push(stats.clone());
+            if history.runs.len() > MAX_HISTORY {
+                history.runs.drain(..history.runs.len() - MAX_HISTORY);
+            }
+        });
+        Ok(())
+    }
+
+    fn baseline(&self, contract: &str) -> io::Result<Option<RunStats>> {
+        Ok(self.with(contract, |history| history.baseline.clone()))
+    }
+
+    fn set_baseline(&self, contract: &str, stats: &RunStats) -> io::Result<()> {
+        self.with(contract, |history| history.baseline = Some(stats.clone()));
+        Ok(())
+    }
+
+    fn deliveries(&self, contract: &str) -> io::Result<Vec<Delivery>> {
+        Ok(self.with(contract, |history| history.deliveries.clone()))
+    }
+
+    fn record_delivery(&self, contract: &str, delivery: &Delivery) -> io::Result<()> {
+        self.with(contract, |history| {
+            history.deliveries.push(delivery.clone());
+            if history.deliveries.len() > MAX_DELIVERIES {
+                history
+                    .deliveries
+                    .drain(..history.deliveries.len() - MAX_DELIVERIES);
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Reads through to another store but records nothing; used by dry runs so
+/// `drift` rules still see real history without a rehearsal becoming the
+/// next reference run.
+pub(crate) struct ReadOnlyHistory<'a>(pub(crate) &'a dyn RunHistory);
+
+impl RunHistory for ReadOnlyHistory<'_> {
+    fn runs(&self, contract: &str) -> io::Result<Vec<RunStats>> {
+        self.0.runs(contract)
+    }
+
+    fn record(&self, _contract: &str, _stats: &RunStats) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn baseline(&self, contract: &str) -> io::Result<Option<RunStats>> {
+        self.0.baseline(contract)
+    }
+
+    fn set_baseline(&self, _contract: &str, _stats: &RunStats) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn deliveries(&self, contract: &str) -> io::Result<Vec<Delivery>> {
+        self.0.deliveries(contract)
+    }
+
+    fn record_delivery(&self, _contract: &str, _delivery: &Delivery) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads and records through to another store, leaving out the runs
+/// recorded under `run_id`; used while validating so a `drift` rule never
+/// compares a run with itself (an earlier attempt, or an earlier object of
+/// the same glob source).
+pub(crate) struct PriorRuns<'a> {
+    pub(crate) history: &'a dyn RunHistory,
+    pub(crate) run_id: &'a str,
+}
+
+impl RunHistory for PriorRuns<'_> {
+    fn runs(&self, contract: &str) -> io::Result<Vec<RunStats>> {
+        let mut runs = self.history.runs(contract)?;
+        runs.retain(|run| run.run_id != self.run_id);
+        Ok(runs)
+    }
+
+    fn record(&self, contract: &str, stats: &RunStats) -> io::Result<()> {
+        self.history.record(contract, stats)
+    }
+
+    fn baseline(&self, contract: &str) -> io::Result<Option<RunStats>> {
+        self.history.baseline(contract)
+    }
+
+    fn set_baseline(&self, contract: &str, stats: &RunStats) -> io::Result<()> {
+        self.history.set_baseline(contract, stats)
+    }
+
+    fn deliveries(&self, contract: &str) -> io::Result<Vec<Delivery>> {
+        self.history.deliveries(contract)
+    }
+
+    fn record_delivery(&self, contract: &str, delivery: &Delivery) -> io::Result<()> {
+        self.history.record_delivery(contract, delivery)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn stats(run_id: &str, mean: f64) -> RunStats {
+        let df = df!("amount" => &[mean - 1.0, mean, mean + 1.0]).unwrap();
+        RunStats::from_dataframe(&df, "1.0.0", run_id).unwrap()
+    }
+
+    #[test]
+    fn computes_column_stats() {
+        let df = df!("a" => &[Some(1i64), Some(1), None, Some(4)]).unwrap();
+        let run = RunStats::from_dataframe(&df, "1.0.0", "run-1").unwrap();
+        assert_eq!(run.rows, 4);
+        let a = &run.columns["a"];
+        assert_eq!(a.null_ratio, 0.25);
+        assert_eq!(a.distinct_ratio, 0.5);
+        assert_eq!(a.mean, Some(2.0));
+        assert_eq!(run.metric("a", DriftMetric::RowCount), Some(4.0));
+        assert_eq!(run.metric("missing", DriftMetric::Mean), None);
+    }
+
+    #[test]
+    fn records_runs_and_baseline() {
+        let dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(dir.path());
+        assert!(store.latest("orders").unwrap().is_none());
+
+        store.record("orders", &stats("r1", 10.0)).unwrap();
+        store.record("orders", &stats("r2", 20.0)).unwrap();
+        assert_eq!(store.runs("orders").unwrap().len(), 2);
+        assert_eq!(store.latest("orders").unwrap().unwrap().run_id, "r2");
+
+        let first = store.run("orders", "r1").unwrap().unwrap();
+        store.set_baseline("orders", &first).unwrap();
+        let baseline = store
+            .reference("orders", DriftReference::Baseline)
+            .unwrap()
+            .unwrap();
+        assert_eq!(baseline.metric("amount", DriftMetric::Mean), Some(10.0));
+    }
+
+    #[test]
+    fn prior_runs_leave_out_the_current_run() {
+        let store = MemoryHistory::new();
+        store.record("orders", &stats("r1", 10.0)).unwrap();
+        let prior = PriorRuns {
+            history: &store,
+            run_id: "r2",
+        };
+        prior.record("orders", &stats("r2", 20.0)).unwrap();
+
+        let previous = prior
+            .reference("orders", DriftReference::Previous)
+            .unwrap()
+            .unwrap();
+        assert_eq!(previous.run_id, "r1");
+        assert_eq!(store.latest("orders").unwrap().unwrap().run_id, "r2");
+    }
+
+    #[test]
+    fn records_each_run_once() {
+        let dir = TempDir::new().unwrap();
+        let file = HistoryStore::new(dir.path());
+        let memory = MemoryHistory::new();
+        for store in [&file as &dyn RunHistory, &memory] {
+            store.record("orders", &stats("r1", 10.0)).unwrap();
+            store.record("orders", &stats("r2", 20.0)).unwrap();
+            store.record("orders", &stats("r2", 30.0)).unwrap();
+
+            let runs = store.runs("orders").unwrap();
+            assert_eq!(runs.len(), 2);
+            let r2 = store.run("orders", "r2").unwrap().unwrap();
+            assert_eq!(r2.metric("amount", DriftMetric::Mean), Some(30.0));
+        }
+    }
+
+    #[test]
+    fn memory_history_matches_file_store() {
+        let store = MemoryHistory::new();
+        for i in 0..MAX_HISTORY + 3 {
+            store
+                .record("orders", &stats(&format!("r{}", i), 1.0))
+                .unwrap();
+        }
+        assert_eq!(store.runs("orders").unwrap().len(), MAX_HISTORY);
+        assert_eq!(store.runs("orders").unwrap()[0].run_id, "r3");
+
+        let latest = store.latest("orders").unwrap().unwrap();
+        store.clone().set_baseline("orders", &latest).unwrap();
+        assert_eq!(
+            store
+                .reference("orders", DriftReference::Baseline)
+                .unwrap()
+                .unwrap()
+                .run_id,
+            latest.run_id
+        );
+        assert!(store.runs("customers").unwrap().is_empty());
+    }
+
+    #[test]
+    fn caps_history_length() {
+        let dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(dir.path());
+        for i in 0..MAX_HISTORY + 3 {
+            store
+                .record("orders", &stats(&format!("r{}", i), 1.0))
+                .unwrap();
+        }
+        let runs = store.runs("orders").unwrap();
+        assert_eq!(runs.len(), MAX_HISTORY);
+        assert_eq!(runs[0].run_id, "r3");
+    }
+
+    #[test]
+    fn finds_earlier_deliveries() {
+        let dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(dir.path());
+        assert!(store.delivered("orders", "abc").unwrap().is_none());
+
+        store
+            .record_delivery("orders", &Delivery::new("abc", "in/orders.csv", "r1"))
+            .unwrap();
+        store
+            .record_delivery("orders", &Delivery::new("abc", "in/orders-2.csv", "r2"))
+            .unwrap();
+        let delivered = store.delivered("orders", "abc").unwrap().unwrap();
+        assert_eq!(delivered.run_id, "r2");
+        assert!(store.delivered("orders", "def").unwrap().is_none());
+        assert!(store.delivered("customers", "abc").unwrap().is_none());
+    }
+}
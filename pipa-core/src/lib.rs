@@ -11,12 +11,18 @@
 // --- Internal modules (not re-exported directly) ---
 // These are the building blocks of the engine. They remain private
 // so their APIs can change without breaking downstream users.
+mod config;
 mod connectors;
 mod contracts;
 mod drivers;
 mod engine;
+mod environment;
+#[cfg(feature = "test-util")]
+mod harness;
+mod history;
 mod logging;
 mod movement;
+mod notify;
 mod profiles;
 mod validators;
 
@@ -29,25 +35,55 @@ mod validators;
 // API for consumers (CLI or programmatic) while insulating them from
 // internal refactors.
 
-/// Contract management: list, validate, show, and run contracts.
+/// Contract management: list, validate, lint, check, diff, show, and run contracts.
 ///
 /// Exposes contract-related types and functions from `engine::contracts`.
 /// Also re-exports the `Executor` type from logging for contract execution context.
 pub mod contract {
+    pub use crate::contracts::{ContractBuilder, ContractParseError, ContractType};
     pub use crate::engine::contracts::{
-        ContractInfo, ContractList, ContractValidation, ValidationOutcome, get_contract,
-        list_contracts, run_contract_validation, validate_contract,
+        ChangeKind, ColumnChange, ContractChange, ContractDiff, ContractDraft, ContractExport,
+        ContractFormat, ContractInfo, ContractLint, ContractList, ContractValidation, DraftColumn,
+        DtypeMismatch, ExportFormat, GeneratedContract, ObjectOutcome, RuleFix, RunDiff,
+        RunTimings, SchemaCheck, TransferStats, ValidationOutcome, check_contract,
+        check_contract_with, diff_contract_toml, diff_contracts, diff_runs, export_contract,
+        format_contract, format_contract_toml, generate_contract, get_contract, json_schema,
+        lint_contract, list_contracts, run_contract_validation, set_baseline, validate_contract,
     };
+    pub use crate::engine::lint::{LintDiagnostic, LintSeverity, lint_toml};
+    pub use crate::history::{ColumnStats, RunStats};
     pub use crate::logging::schema::Executor;
 }
 
-/// Profile management: list and test profiles.
+/// Profile management: list, test, add, edit, and remove profiles.
 ///
 /// Provides access to profile definitions and testing utilities.
 /// Profiles are typically used to parameterize contract runs.
 pub mod profile {
     pub use crate::engine::profiles::{
-        ProfileList, ProfileTestResult, list_profiles, test_profile,
+        ProfileList, ProfileTestResult, add_profile, edit_profile, list_profiles, remove_profile,
+        test_profile,
+    };
+    pub use crate::profiles::{PROFILES_FILE, SECRET_FIELDS};
+}
+
+/// Data profiling: per-column statistics for a source, no contract required.
+///
+/// Shares the connector/driver path with validation and is typically used
+/// as the first step when writing a new contract.
+pub mod profiler {
+    pub use crate::engine::profiler::{
+        ColumnProfile, DEFAULT_TOP_N, DataProfile, profile_data, profile_dataframe,
+    };
+}
+
+/// Rule catalog: every supported contract rule with its parameters.
+///
+/// Useful for listing rules and for editor validation/autocomplete.
+pub mod rules {
+    pub use crate::engine::rules::{
+        RuleInfo, RuleInput, RuleParam, RuleScope, RuleTypeMismatch, UnrecognizedRule, catalog,
+        describe, type_mismatches, unrecognized_rules,
     };
 }
 
@@ -55,17 +91,78 @@ pub mod profile {
 ///
 /// Thin wrapper that exposes the core validation runner directly.
 /// Useful for programmatic invocation without going through CLI.
+/// `run_contract_validation_with` takes an `Environment` whose contract,
+/// storage, and run-history backends can be replaced (see `ConfigStore`,
+/// `ObjectStore`, and `RunHistory`) and which reports live progress to a
+/// `RunProgress`. `validate_dataframe` applies a plan's rules to an
+/// already-loaded `DataFrame`.
 pub mod run {
-    pub use crate::engine::contracts::run_contract_validation;
+    pub use crate::config::{
+        OutputFormat, PROJECT_FILE, ProjectConfig, ProjectDefaults, ProjectPaths, SealingSchedule,
+        contract_path, contracts_dir, contracts_pattern, ledger_path, load_defaults,
+        load_project_config, logs_dir,
+    };
+    pub use crate::connectors::ObjectInfo;
+    pub use crate::connectors::fetch::{FetchedData, source_type_for_location};
+    pub use crate::connectors::spool::Payload;
+    pub use crate::contracts::dates::{parse_run_date, render_date_tokens};
+    pub use crate::contracts::overlay::{
+        ENV_VAR, apply_overlay, is_overlay, merge_overlay, overlay_path, selected_env,
+    };
+    pub use crate::contracts::retry::{DEFAULT_RETRY_DELAY, Retry, format_delay, parse_delay};
+    pub use crate::contracts::schema::{CsvDialect, OnDuplicate, OnSuccess, Source};
+    pub use crate::contracts::{ChannelTarget, NotificationChannel, Notifications, NotifyOn};
+    pub use crate::contracts::{
+        ContractBuilder, ContractType, DriftMetric, DriftReference, Limits, Sampling,
+        SchemaContracts,
+    };
+    pub use crate::engine::contracts::{
+        ContractRun, DEFAULT_DEBOUNCE, DEFAULT_POLL_INTERVAL, Revalidation, ScheduledContract,
+        Scheduler, SchedulerSummary, TagFilter, WatchOptions, WatchRun, WatchSummary, Watcher,
+        revalidate_quarantined, run_contract_validation, run_contract_validation_with,
+        run_contracts_by_tag, scheduled_contracts, sealing_schedule, select_contracts,
+    };
+    pub use crate::engine::cron::CronSchedule;
+    pub use crate::engine::plan::{ExecutionPlan, MAX_CACHED_PLANS, clear_plan_cache};
+    pub use crate::engine::validation::{validate_dataframe, validate_plan};
+    pub use crate::environment::{
+        ConfigStore, ConnectorStore, Environment, FsConfig, NoProgress, ObjectStore, Progress,
+        RunProgress,
+    };
+    pub use crate::history::{Delivery, HistoryStore, MemoryHistory, RunHistory};
+    pub use crate::logging::error::ValidationError;
+    pub use crate::notify::{RunNotification, send as send_notification};
+    pub use crate::profiles::{Profile, Profiles};
+    pub use chrono::NaiveDate;
+    pub use tokio_util::sync::CancellationToken;
 }
 
-/// Log management: verify log integrity.
+/// Multi-project workspaces: run several projects from one invocation.
+///
+/// Each project keeps its own contracts, profiles, logs, and run history;
+/// `load_workspace` reads the project list from a `workspace.toml`, and
+/// `Project` resolves one project directory's paths.
+pub mod workspace {
+    pub use crate::config::Project;
+    pub use crate::engine::workspace::{WORKSPACE_FILE, Workspace, load_workspace};
+}
+
+/// Log management: verify log integrity, ship logs off-host, and export
+/// them for BI tools.
 ///
 /// Surfaces log verification and integrity checking.
 /// Includes cryptographic verification of log chains.
 pub mod logs {
-    pub use crate::engine::logs::{LogVerification, verify_logs};
-    pub use crate::logging::verify::FileStatus;
+    pub use crate::engine::logs::{
+        KeyRotation, LogSealing, LogVerification, export_logs, prune_logs, rotate_ledger_key,
+        seal_logs, ship_logs, verify_logs,
+    };
+    pub use crate::logging::export::{LogExport, LogFormat};
+    pub use crate::logging::ledger::LedgerKeyProvider;
+    pub use crate::logging::notary::{NOTARY_TIMEOUT, Notary};
+    pub use crate::logging::retention::{LogPruning, PRUNED_PREFIX, PrunedLog, RetentionPolicy};
+    pub use crate::logging::sinks::{LogSink, SHIPPED_FILE, ShippedFile, SyslogProtocol};
+    pub use crate::logging::verify::{FileStatus, FileVerification};
 }
 
 /// System health checks.
@@ -73,7 +170,10 @@ pub mod logs {
 /// Provides system-level diagnostics (e.g., environment, connectors).
 /// Useful for pre-flight checks before running validations.
 pub mod health {
-    pub use crate::engine::system::{HealthStatus, check_system_health, run_health_check};
+    pub use crate::engine::system::{
+        HealthStatus, ProfileHealth, check_profiles_health, check_system_health,
+        run_deep_health_check, run_health_check,
+    };
 }
 
 /// Initialize project scaffolding.
@@ -84,11 +184,43 @@ pub mod init {
     pub use crate::engine::init::init_project;
 }
 
+/// Custom validators: implement your own rules.
+///
+/// Implement `Validator` (column-level) or `FileValidator` (file-level),
+/// then register a factory with `register_custom` so contracts can refer
+/// to it as `{ rule = "custom", name = "...", params = { ... } }`.
+pub mod validator {
+    pub use crate::logging::error::{ValidationError, ValidationResult};
+    pub use crate::validators::custom::{CustomParams, CustomValidator, register_custom};
+    pub use crate::validators::{FileValidator, ValidationReport, Validator};
+}
+
+/// Test support: in-memory connectors and emulator-backed harnesses.
+///
+/// Enabled with the `test-util` feature. `MemoryConnector` and
+/// `FailingConnector` need nothing external; `EmulatorHarness` runs
+/// LocalStack, Azurite, or fake-gcs-server in Docker for connector
+/// integration tests.
+#[cfg(feature = "test-util")]
+pub mod test_util {
+    pub use crate::connectors::fetch::{FetchedData, fetch_data_from_source, list_source_objects};
+    pub use crate::connectors::{
+        AzureConnector, Connector, FailingConnector, GCSConnector, MemoryConnector, ObjectInfo,
+        S3Connector,
+    };
+    pub use crate::contracts::schema::{CsvDialect, Source, SourceOptions};
+    pub use crate::environment::InMemory;
+    pub use crate::harness::{Emulator, EmulatorHarness, docker_available};
+    pub use crate::profiles::{Profile, Profiles};
+}
+
 /// Logging infrastructure: pluggable audit logging.
 ///
 /// Provides the `AuditLogger` trait and implementations for different backends.
 /// Consumers can use the built-in JSONL logger or implement their own.
 pub mod audit_logging {
-    pub use crate::logging::{AuditLogger, JsonlLogger, NoOpLogger};
-    pub use crate::logging::schema::{AuditLogEntry, Contract, Target, RuleResult, Executor, ProcessSummary};
+    pub use crate::logging::schema::{
+        AuditLogEntry, Contract, Executor, ProcessSummary, RuleResult, Target,
+    };
+    pub use crate::logging::{AuditLogger, JsonlLogger, MemoryLogger, NoOpLogger};
 }
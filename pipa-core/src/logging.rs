@@ -1,22 +1,31 @@
 // Declare submodules
+pub(crate) mod chain;
 pub(crate) mod error;
+pub(crate) mod export;
 pub(crate) mod init;
 pub(crate) mod ledger;
+pub(crate) mod notary;
+pub(crate) mod retention;
 pub(crate) mod schema;
+pub(crate) mod sinks;
 pub(crate) mod verify;
 pub(crate) mod writer;
 
 // New: pluggable logging infrastructure
-pub(crate) mod logger_trait;
+pub(crate) mod dry_run_logger;
 pub(crate) mod jsonl_logger;
+pub(crate) mod logger_trait;
+pub(crate) mod memory_logger;
 pub(crate) mod noop_logger;
+pub(crate) mod run_logger;
 
 // Re-export the types and functions you want public
 pub(crate) use schema::AuditLogEntry;
-#[allow(unused_imports)]
-pub(crate) use writer::log_event; // Deprecated, will be removed in favor of trait
 
 // Public exports for the new logging trait system
-pub use logger_trait::AuditLogger;
+pub(crate) use dry_run_logger::DryRunLogger;
 pub use jsonl_logger::JsonlLogger;
+pub use logger_trait::AuditLogger;
+pub use memory_logger::MemoryLogger;
 pub use noop_logger::NoOpLogger;
+pub(crate) use run_logger::RunLogger;
@@ -0,0 +1,165 @@
+//! Hash chaining of entries within a daily log file.
+//!
+//! Sealing only protects a file once the day is over. To make earlier
+//! edits detectable too, each line written by `JsonlLogger` starts with
+//! `"prev_hash"`: the SHA-256 of the previous line of the same file (the
+//! first line of a file chains to `GENESIS_HASH`). Editing, removing, or
+//! reordering a line breaks the chain at the line after it; truncating
+//! the end of the file is only caught once the file is sealed.
+
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// `prev_hash` of the first entry in a file.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// How much of a file's end is read to find its last line.
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// Hex SHA-256 of one line, without its newline.
+fn line_hash(line: &str) -> String {
+    format!("{:x}", Sha256::digest(line.as_bytes()))
+}
+
+/// `json` (a serialized entry) with `"prev_hash"` chaining it to `prev_line`.
+pub(crate) fn chain_entry(prev_line: Option<&str>, json: &str) -> String {
+    let prev_hash = prev_line.map_or_else(|| GENESIS_HASH.to_string(), line_hash);
+    match json.strip_prefix('{') {
+        Some("}") => format!("{{\"prev_hash\":\"{}\"}}", prev_hash),
+        Some(rest) => format!("{{\"prev_hash\":\"{}\",{}", prev_hash, rest),
+        None => json.to_string(),
+    }
+}
+
+/// Last non-empty line of the file at `path`, if it has one.
+///
+/// The tail is read as bytes: its start may fall inside a multi-byte
+/// character, but only the part after a newline is decoded.
+pub(crate) fn last_line(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).ok()?;
+
+    let end = tail.iter().rposition(|b| *b != b'\n').map_or(0, |i| i + 1);
+    let trimmed = &tail[..end];
+    match trimmed.iter().rposition(|b| *b == b'\n') {
+        Some(i) => String::from_utf8(trimmed[i + 1..].to_vec()).ok(),
+        // The whole tail is one line; it is complete only if the file is
+        // small enough to have been read from the start.
+        None if start == 0 && !trimmed.is_empty() => String::from_utf8(trimmed.to_vec()).ok(),
+        None if start == 0 => None,
+        None => {
+            let content = std::fs::read_to_string(path).ok()?;
+            content.lines().rfind(|l| !l.is_empty()).map(str::to_string)
+        }
+    }
+}
+
+/// Check the chain through `content` (a daily file), returning the
+/// 1-based number of the first line whose `prev_hash` doesn't match.
+///
+/// Lines written before chaining existed carry no `prev_hash` and are
+/// accepted until the first chained line; after it, every line must chain.
+pub(crate) fn verify_chain(content: &str) -> Result<(), usize> {
+    let mut prev: Option<&str> = None;
+    let mut chained = false;
+    for (i, line) in content.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let prev_hash = serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .and_then(|entry| entry.get("prev_hash")?.as_str().map(str::to_string));
+        match prev_hash {
+            Some(hash) => {
+                let expected = prev.map_or_else(|| GENESIS_HASH.to_string(), line_hash);
+                if hash != expected {
+                    return Err(i + 1);
+                }
+                chained = true;
+            }
+            None if chained => return Err(i + 1),
+            None => {}
+        }
+        prev = Some(line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn chain(entries: &[&str]) -> String {
+        let mut content = String::new();
+        let mut prev: Option<String> = None;
+        for json in entries {
+            let line = chain_entry(prev.as_deref(), json);
+            content.push_str(&line);
+            content.push('\n');
+            prev = Some(line);
+        }
+        content
+    }
+
+    #[test]
+    fn detects_edited_and_removed_entries() {
+        let content = chain(&[r#"{"event":"a"}"#, r#"{"event":"b"}"#, r#"{"event":"c"}"#]);
+        assert!(content.starts_with(&format!("{{\"prev_hash\":\"{}\",", GENESIS_HASH)));
+        assert_eq!(verify_chain(&content), Ok(()));
+
+        let edited = content.replacen(r#""event":"b""#, r#""event":"x""#, 1);
+        assert_eq!(verify_chain(&edited), Err(3));
+
+        let lines: Vec<&str> = content.lines().collect();
+        let removed = format!("{}\n{}\n", lines[0], lines[2]);
+        assert_eq!(verify_chain(&removed), Err(2));
+
+        let legacy = format!("{{\"event\":\"old\"}}\n{}", content);
+        assert_eq!(verify_chain(&legacy), Err(2));
+        let upgraded = format!(
+            "{{\"event\":\"old\"}}\n{}\n",
+            chain_entry(Some(r#"{"event":"old"}"#), r#"{"event":"new"}"#)
+        );
+        assert_eq!(verify_chain(&upgraded), Ok(()));
+        let unchained = format!("{}{{\"event\":\"late\"}}\n", content);
+        assert_eq!(verify_chain(&unchained), Err(4));
+    }
+
+    #[test]
+    fn reads_the_last_line() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        assert_eq!(last_line(&path), None);
+
+        std::fs::write(&path, "{\"a\":1}\n{\"b\":2}\n").unwrap();
+        assert_eq!(last_line(&path).as_deref(), Some("{\"b\":2}"));
+
+        let long = format!("{{\"details\":\"{}\"}}", "x".repeat(TAIL_BYTES as usize));
+        std::fs::write(&path, format!("{{\"a\":1}}\n{}\n", long)).unwrap();
+        assert_eq!(last_line(&path), Some(long));
+    }
+
+    #[test]
+    fn reads_the_last_line_when_the_tail_starts_mid_character() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let last = "{\"location\":\"s3://b/ü.csv\"}";
+        // Pad the first line so the tail starts on the second byte of "é"
+        let padding = TAIL_BYTES as usize - 5 - last.len();
+        let first = format!("{{\"details\":\"é{}\"}}", "x".repeat(padding));
+        let content = format!("{}\n{}\n", first, last);
+        assert!(!content.is_char_boundary(content.len() - TAIL_BYTES as usize));
+        std::fs::write(&path, &content).unwrap();
+
+        assert_eq!(last_line(&path).as_deref(), Some(last));
+        let next = chain_entry(last_line(&path).as_deref(), "{}");
+        assert!(!next.contains(GENESIS_HASH));
+    }
+}
@@ -0,0 +1,61 @@
+//! Logger wrapper for dry runs.
+//!
+//! Forwards every entry to the wrapped logger with `dry_run=true` prepended
+//! to its details, so rehearsal runs can be told apart from real ones in
+//! the audit log.
+
+use crate::logging::logger_trait::AuditLogger;
+use crate::logging::schema::AuditLogEntry;
+
+/// Tags each entry with `dry_run=true` before passing it on.
+pub(crate) struct DryRunLogger<'a, L: AuditLogger> {
+    inner: &'a L,
+}
+
+impl<'a, L: AuditLogger> DryRunLogger<'a, L> {
+    pub(crate) fn new(inner: &'a L) -> Self {
+        Self { inner }
+    }
+}
+
+fn tagged_details(details: Option<&str>) -> String {
+    match details {
+        Some(details) => format!("dry_run=true, {}", details),
+        None => "dry_run=true".to_string(),
+    }
+}
+
+impl<L: AuditLogger> AuditLogger for DryRunLogger<'_, L> {
+    fn log_event(&self, entry: &AuditLogEntry) {
+        let details = tagged_details(entry.details);
+        let mut entry = entry.clone();
+        entry.details = Some(&details);
+        self.inner.log_event(&entry);
+    }
+
+    fn log_and_print(&self, entry: &AuditLogEntry, console_msg: &str) {
+        let details = tagged_details(entry.details);
+        let mut entry = entry.clone();
+        entry.details = Some(&details);
+        self.inner.log_and_print(&entry, console_msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::log_action;
+    use crate::logging::MemoryLogger;
+
+    #[test]
+    fn prepends_dry_run_to_details() {
+        let memory = MemoryLogger::new();
+        let logger = DryRunLogger::new(&memory);
+        let _ = log_action(&logger, "file_read", Some("bytes=12"), None, None, None);
+        let _ = log_action(&logger, "health_check", None, None, None, None);
+
+        let entries = memory.entries();
+        assert_eq!(entries[0]["details"], "dry_run=true, bytes=12");
+        assert_eq!(entries[1]["details"], "dry_run=true");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::connectors::retry::is_permanent;
 use polars::prelude::PolarsError;
 use thiserror::Error;
 
@@ -16,6 +17,10 @@ pub enum ValidationError {
     #[error("Polars operation failed: {0}")]
     Polars(#[from] PolarsError),
 
+    /// No contract with this name
+    #[error("Contract '{0}' not found")]
+    ContractNotFound(String),
+
     /// Contract TOML parsing error
     #[error("Contract parsing error: {0}")]
     ContractParse(String),
@@ -24,14 +29,40 @@ pub enum ValidationError {
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
 
-    /// Connector (S3, GCS, etc.) error
-    #[error("Connector error: {0}")]
-    Connector(String),
+    /// Reading the source (or listing its objects) failed; `permanent`
+    /// when the connector marked it as something retrying can't fix
+    /// (e.g. a `404 Not Found`)
+    #[error("Failed to fetch {provider} source: {cause}")]
+    SourceFetch {
+        provider: String,
+        cause: String,
+        permanent: bool,
+    },
+
+    /// No driver for the data's format, or the data didn't parse with it
+    #[error("Failed to load {format} data: {cause}")]
+    DriverLoad { format: String, cause: String },
+
+    /// Recording a run's deliveries failed after its data was moved
+    #[error("Failed to record run history: {0}")]
+    HistoryWrite(String),
+
+    /// Writing to a destination or quarantine failed
+    #[error("Failed to write {target}: {cause}")]
+    MovementFailed { target: String, cause: String },
 
     /// Profile lookup failed
     #[error("Profile not found: {0}")]
     ProfileNotFound(String),
 
+    /// Contract or profile configuration is missing or unusable
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    /// A recorded run (or any run) is missing from a contract's history
+    #[error("Run not found: {0}")]
+    RunNotFound(String),
+
     /// Regex compilation or execution error
     #[error("Regex pattern error: {0}")]
     Regex(#[from] regex::Error),
@@ -40,6 +71,20 @@ pub enum ValidationError {
     #[error("Internal Error: {0}")]
     Anyhow(#[from] anyhow::Error),
 
+    /// The run took longer than its run timeout and was cancelled
+    #[error("Run timed out after {0:?}")]
+    RunTimeout(std::time::Duration),
+
+    /// The run's cancellation token was cancelled; the stage says where it
+    /// stopped (never partway through movement)
+    #[error("Run cancelled {0}")]
+    Cancelled(&'static str),
+
+    /// The source file's content was already delivered in an earlier run
+    /// and its `on_duplicate` is `fail`
+    #[error("Source {location} was already delivered in run {run_id}")]
+    DuplicateSource { location: String, run_id: String },
+
     /// File size exceeded configured maximum
     #[error("File size {size} exceeds maximum {max} bytes")]
     FileTooLarge { size: usize, max: usize },
@@ -49,6 +94,65 @@ pub enum ValidationError {
     Other(String),
 }
 
+impl ValidationError {
+    /// Whether the error comes from contract or profile configuration
+    /// rather than from executing the run.
+    pub fn is_config(&self) -> bool {
+        matches!(
+            self,
+            ValidationError::Config(_)
+                | ValidationError::ContractNotFound(_)
+                | ValidationError::ContractParse(_)
+                | ValidationError::ProfileNotFound(_)
+        )
+    }
+
+    /// Whether the error may go away on its own (a connector or I/O
+    /// failure while reading the source), so a retry could succeed.
+    ///
+    /// A `SourceFetch` the connector marked permanent is not transient, and
+    /// neither are errors raised once data has been moved (`HistoryWrite`):
+    /// a retry would write the data again, or find the source already
+    /// settled.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ValidationError::SourceFetch {
+                permanent: false,
+                ..
+            } | ValidationError::Io(_)
+        )
+    }
+
+    /// A transient `SourceFetch` error for `provider` (the source `type`).
+    pub fn source_fetch(provider: &str, cause: impl std::fmt::Display) -> Self {
+        ValidationError::SourceFetch {
+            provider: provider.to_string(),
+            cause: cause.to_string(),
+            permanent: false,
+        }
+    }
+
+    /// A `SourceFetch` error for `provider` from a connector `error`,
+    /// permanent when the connector marked it so (see `connectors::retry`).
+    pub fn connector_fetch(provider: &str, error: impl Into<anyhow::Error>) -> Self {
+        let error = error.into();
+        ValidationError::SourceFetch {
+            provider: provider.to_string(),
+            cause: error.to_string(),
+            permanent: is_permanent(&error),
+        }
+    }
+
+    /// A `DriverLoad` error for data in `format` (its driver extension).
+    pub fn driver_load(format: &str, cause: impl std::fmt::Display) -> Self {
+        ValidationError::DriverLoad {
+            format: format.to_string(),
+            cause: cause.to_string(),
+        }
+    }
+}
+
 /// Result type for validation operations
 pub type ValidationResult<T> = Result<T, ValidationError>;
 
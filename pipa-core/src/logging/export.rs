@@ -0,0 +1,281 @@
+//! Flattening audit logs into a table for BI tools.
+//!
+//! Each audit entry becomes one row per `RuleResult` (or a single row when
+//! it has none), with nested contract, target, executor, and summary fields
+//! spread into columns. The rows are read back through the JSON driver and
+//! written with the same serializer as destinations and quarantines.
+
+use crate::contracts::schema::{CsvDialect, SourceOptions};
+use crate::drivers::get_driver;
+use crate::logging::error::{ValidationError, ValidationResult};
+use chrono::NaiveDate;
+use polars::prelude::{Column, DataFrame, DataType, PlSmallStr};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Columns of an exported table, in order, with their types.
+const COLUMNS: [(&str, DataType); 20] = [
+    ("date", DataType::String),
+    ("timestamp", DataType::String),
+    ("level", DataType::String),
+    ("event", DataType::String),
+    ("contract_name", DataType::String),
+    ("contract_version", DataType::String),
+    ("target_file", DataType::String),
+    ("target_column", DataType::String),
+    ("target_rule", DataType::String),
+    ("details", DataType::String),
+    ("executor_user", DataType::String),
+    ("executor_host", DataType::String),
+    ("result_column", DataType::String),
+    ("result_rule", DataType::String),
+    ("result", DataType::String),
+    ("result_details", DataType::String),
+    ("contracts_run", DataType::Int64),
+    ("contracts_failed", DataType::Int64),
+    ("status", DataType::String),
+    ("run_id", DataType::String),
+];
+
+/// Output format for `export_logs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Csv,
+    Parquet,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            other => Err(format!(
+                "unknown log export format '{}' (supported: csv, parquet)",
+                other
+            )),
+        }
+    }
+}
+
+impl LogFormat {
+    /// Format name, as accepted by `--format`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogFormat::Csv => "csv",
+            LogFormat::Parquet => "parquet",
+        }
+    }
+
+    /// Format implied by `path`'s extension, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+/// Counts from one export.
+#[derive(Debug, Clone, Default)]
+pub struct LogExport {
+    /// Daily files read.
+    pub files: usize,
+    /// Audit entries exported.
+    pub entries: usize,
+    /// Rows written (entries with several rule results span several).
+    pub rows: usize,
+    /// Lines that weren't a JSON object and were skipped.
+    pub malformed: usize,
+}
+
+/// Read the daily files in `logs_dir` dated `from..=to` (either end open)
+/// into one table, keeping only the entries of `run_id` if given.
+pub(crate) fn audit_table(
+    logs_dir: &Path,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    run_id: Option<&str>,
+) -> ValidationResult<(DataFrame, LogExport)> {
+    let mut daily: Vec<(NaiveDate, String)> = fs::read_dir(logs_dir)?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let date = name.strip_prefix("audit-")?.strip_suffix(".jsonl")?;
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+            Some((date, name))
+        })
+        .filter(|(date, _)| from.is_none_or(|from| *date >= from))
+        .filter(|(date, _)| to.is_none_or(|to| *date <= to))
+        .collect();
+    daily.sort();
+
+    let mut export = LogExport {
+        files: daily.len(),
+        ..LogExport::default()
+    };
+    let mut ndjson = String::new();
+    for (date, name) in &daily {
+        let content = fs::read_to_string(logs_dir.join(name))?;
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            let Ok(Value::Object(entry)) = serde_json::from_str(line) else {
+                export.malformed += 1;
+                continue;
+            };
+            if run_id.is_some_and(|id| entry.get("run_id").and_then(Value::as_str) != Some(id)) {
+                continue;
+            }
+            export.entries += 1;
+            for row in flatten(&date.to_string(), &entry) {
+                ndjson.push_str(&Value::Object(row).to_string());
+                ndjson.push('\n');
+                export.rows += 1;
+            }
+        }
+    }
+
+    let df = if ndjson.is_empty() {
+        DataFrame::new(
+            COLUMNS
+                .iter()
+                .map(|(name, dtype)| Column::new_empty(PlSmallStr::from(*name), dtype))
+                .collect(),
+        )?
+    } else {
+        let driver = get_driver(
+            "jsonl",
+            &SourceOptions::default(),
+            &CsvDialect::default(),
+            &[],
+        )?;
+        let df = driver
+            .load(ndjson.as_bytes())
+            .map_err(|e| ValidationError::DriverLoad {
+                format: "jsonl".to_string(),
+                cause: e.to_string(),
+            })?;
+        // Columns that are null throughout load untyped; give every column
+        // its declared type so exports share one schema.
+        DataFrame::new(
+            COLUMNS
+                .iter()
+                .map(|(name, dtype)| df.column(name)?.cast(dtype))
+                .collect::<Result<_, _>>()?,
+        )?
+    };
+    Ok((df, export))
+}
+
+/// Rows for one entry: one per rule result, or one with empty result columns.
+fn flatten(date: &str, entry: &Map<String, Value>) -> Vec<Map<String, Value>> {
+    let field = |object: &str, key: &str| entry.get(object).and_then(|o| o.get(key)).cloned();
+
+    let mut base = Map::new();
+    let mut set = |name: &str, value: Option<Value>| {
+        base.insert(name.to_string(), value.unwrap_or(Value::Null));
+    };
+    set("date", Some(Value::String(date.to_string())));
+    set("timestamp", entry.get("timestamp").cloned());
+    set("level", entry.get("level").cloned());
+    set("event", entry.get("event").cloned());
+    set("run_id", entry.get("run_id").cloned());
+    set("contract_name", field("contract", "name"));
+    set("contract_version", field("contract", "version"));
+    set("target_file", field("target", "file"));
+    set("target_column", field("target", "column"));
+    set("target_rule", field("target", "rule"));
+    set("details", entry.get("details").cloned());
+    set("executor_user", field("executor", "user"));
+    set("executor_host", field("executor", "host"));
+    set("contracts_run", field("summary", "contracts_run"));
+    set("contracts_failed", field("summary", "contracts_failed"));
+    set("status", field("summary", "status"));
+
+    let results = entry
+        .get("results")
+        .and_then(Value::as_array)
+        .filter(|r| !r.is_empty());
+    let Some(results) = results else {
+        for name in ["result_column", "result_rule", "result", "result_details"] {
+            base.insert(name.to_string(), Value::Null);
+        }
+        return vec![base];
+    };
+    results
+        .iter()
+        .map(|result| {
+            let mut row = base.clone();
+            for (name, key) in [
+                ("result_column", "column"),
+                ("result_rule", "rule"),
+                ("result", "result"),
+                ("result_details", "details"),
+            ] {
+                row.insert(
+                    name.to_string(),
+                    result.get(key).cloned().unwrap_or(Value::Null),
+                );
+            }
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn explodes_rule_results_within_the_date_range() {
+        let dir = TempDir::new().unwrap();
+        let entry = r#"{"timestamp":"2025-01-02T00:00:00Z","level":"AUDIT","event":"validation_summary","contract":{"name":"orders","version":"1"},"executor":{"user":"etl","host":"w1"},"results":[{"column":"id","rule":"not_null","result":"pass"},{"column":"id","rule":"unique","result":"fail","details":"2 dupes"}]}"#;
+        fs::write(
+            dir.path().join("audit-2025-01-02.jsonl"),
+            format!("{}\nnot json\n{{\"event\":\"logs_verified\"}}\n", entry),
+        )
+        .unwrap();
+        fs::write(dir.path().join("audit-2025-01-05.jsonl"), "{}\n").unwrap();
+
+        let from = NaiveDate::from_ymd_opt(2025, 1, 1);
+        let to = NaiveDate::from_ymd_opt(2025, 1, 3);
+        let (df, export) = audit_table(dir.path(), from, to, None).unwrap();
+        assert_eq!(
+            (export.files, export.entries, export.rows, export.malformed),
+            (1, 2, 3, 1)
+        );
+        assert_eq!(df.shape(), (3, COLUMNS.len()));
+        let results: Vec<_> = df.column("result").unwrap().str().unwrap().iter().collect();
+        assert_eq!(results, vec![Some("pass"), Some("fail"), None]);
+        assert_eq!(
+            df.column("contracts_run").unwrap().dtype(),
+            &DataType::Int64
+        );
+        assert_eq!(df.column("status").unwrap().dtype(), &DataType::String);
+
+        let (empty, _) =
+            audit_table(dir.path(), NaiveDate::from_ymd_opt(2030, 1, 1), None, None).unwrap();
+        assert_eq!(empty.shape(), (0, COLUMNS.len()));
+    }
+
+    #[test]
+    fn keeps_only_the_requested_run() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("audit-2025-01-02.jsonl"),
+            concat!(
+                r#"{"event":"contract_validation_started","run_id":"a"}"#,
+                "\n",
+                r#"{"event":"contract_validation_started","run_id":"b"}"#,
+                "\n",
+                r#"{"event":"logs_verified"}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let (df, export) = audit_table(dir.path(), None, None, Some("b")).unwrap();
+        assert_eq!(export.entries, 1);
+        let run_ids: Vec<_> = df.column("run_id").unwrap().str().unwrap().iter().collect();
+        assert_eq!(run_ids, vec![Some("b")]);
+    }
+}
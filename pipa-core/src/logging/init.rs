@@ -1,7 +1,8 @@
 use super::ledger::ensure_ledger_key_exists;
+use crate::config::logs_dir;
 
 pub fn init_logging() {
-    std::fs::create_dir_all("logs").expect("Failed to create logs directory");
+    std::fs::create_dir_all(logs_dir()).expect("Failed to create logs directory");
 
     ensure_ledger_key_exists()
 }
@@ -1,17 +1,23 @@
 //! JSONL file logger implementation.
 //!
 //! This is the default logger used by the CLI. It writes audit logs
-//! to daily JSONL files and maintains an encrypted hash ledger for
-//! tamper-resistance.
+//! to daily JSONL files, hash-chaining each entry to the one before it
+//! (see `logging::chain`), and maintains an encrypted hash ledger for
+//! tamper-resistance, optionally timestamped by a notary (see
+//! `logging::notary`). Entries and sealed files are also shipped to any
+//! `[[log_sinks]]` (see `logging::sinks`).
 
-use crate::logging::ledger::seal_unsealed_logs;
+use crate::config::{PROJECT_FILE, load_project_config, logs_dir};
 use crate::logging::logger_trait::AuditLogger;
+use crate::logging::notary::Notary;
 use crate::logging::schema::AuditLogEntry;
+use crate::logging::sinks::{LogSink, run_detached, ship_entry, ship_sealed_logs};
+use crate::logging::writer::append_entry;
+use crate::profiles::load_profiles;
 use chrono::Utc;
 use serde_json;
-use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// JSONL file-based audit logger.
 ///
@@ -20,6 +26,8 @@ use std::path::PathBuf;
 /// the encrypted ledger.
 pub struct JsonlLogger {
     logs_dir: PathBuf,
+    sinks: Vec<LogSink>,
+    notary: Option<Notary>,
 }
 
 impl JsonlLogger {
@@ -35,12 +43,40 @@ impl JsonlLogger {
         if !logs_dir.exists() {
             fs::create_dir_all(&logs_dir).expect("cannot create logs directory");
         }
-        Self { logs_dir }
+        Self {
+            logs_dir,
+            sinks: Vec::new(),
+            notary: None,
+        }
+    }
+
+    /// Also ship entries and sealed files to `sinks`.
+    pub fn with_sinks(mut self, sinks: Vec<LogSink>) -> Self {
+        self.sinks = sinks;
+        self
+    }
+
+    /// Notarize the hash of each file as it is sealed.
+    pub fn with_notary(mut self, notary: Notary) -> Self {
+        self.notary = Some(notary);
+        self
     }
 
-    /// Create a new JSONL logger with default "logs" directory.
+    /// Create a new JSONL logger with default "logs" directory, shipping to
+    /// the `[[log_sinks]]` and notarizing with the `[notarization]` in
+    /// `pipa.toml` if there is one.
     pub fn default() -> Self {
-        Self::new(PathBuf::from("logs"))
+        let logger = Self::new(logs_dir());
+        match load_project_config(Path::new(PROJECT_FILE)) {
+            Ok(project) => Self {
+                notary: project.notarization,
+                ..logger.with_sinks(project.log_sinks)
+            },
+            Err(e) => {
+                eprintln!("⚠️  Audit log sinks and notarization disabled: {}", e);
+                logger
+            }
+        }
     }
 
     /// Get today's log file path.
@@ -58,18 +94,24 @@ impl AuditLogger for JsonlLogger {
         // Serialize to JSON
         let json = serde_json::to_string(entry).expect("failed to serialize log entry");
 
-        // Append to today's log file
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&log_path)
-            .expect("cannot open daily audit log file");
-
-        writeln!(file, "{}", json).expect("failed to write log entry");
+        // Append to today's log file, chained to the previous entry, then
+        // seal any unsealed logs (older than today); safe to call from
+        // concurrent tasks
+        let (line, sealed) = append_entry(&self.logs_dir, &log_path, &json, self.notary.as_ref());
 
-        // Seal any unsealed logs (older than today)
-        let today = Utc::now().format("%Y-%m-%d").to_string();
-        seal_unsealed_logs(&self.logs_dir, &today);
+        ship_entry(&self.sinks, entry.event, &line);
+        if !sealed.is_empty() && !self.sinks.iter().all(LogSink::is_streaming) {
+            let profiles = load_profiles().unwrap_or_default();
+            let shipped = run_detached(ship_sealed_logs(&self.logs_dir, &self.sinks, &profiles));
+            for file in shipped {
+                if let Some(e) = file.error {
+                    eprintln!(
+                        "⚠️  Failed to ship {} to {}: {}",
+                        file.filename, file.sink, e
+                    );
+                }
+            }
+        }
     }
 
     fn log_and_print(&self, entry: &AuditLogEntry, console_msg: &str) {
@@ -1,3 +1,11 @@
+mod keys;
+
+pub use keys::LedgerKeyProvider;
+
+use crate::config::ledger_path;
+use crate::logging::notary::{Notary, notarize};
+use crate::logging::retention::PRUNED_PREFIX;
+use crate::logging::sinks::run_detached;
 use aes_gcm::aead::{Aead, KeyInit};
 use aes_gcm::{Aes256Gcm, Key, Nonce};
 use chrono::Utc;
@@ -5,49 +13,39 @@ use rand::RngCore;
 use sha2::{Digest, Sha256};
 use std::fs::{self, File};
 use std::io::{BufReader, Read};
-use std::path::PathBuf;
-
-const LEDGER_PATH: &str = "logs/hash_ledger.enc";
-
-/// Location of the AES key (~/.lokryn/pipeaudit/ledger.key)
+use std::path::{Path, PathBuf};
 
 fn local_config_dir() -> PathBuf {
     PathBuf::from("config")
 }
 
-fn ledger_key_path() -> PathBuf {
-    local_config_dir().join("ledger.key")
-}
-
 /// Ensure the ledger key exists, creating it securely if missing.
 /// - 32 random bytes (AES‑256 key)
-/// - Stored with 0600 permissions on Unix
+/// - Stored with 0600 permissions on Unix, or wrapped by the KMS configured
+///   under `[ledger_key]` in `pipa.toml`
 /// - On Windows: stored with default ACLs; tighten with Windows APIs if stricter isolation is required.
 pub fn ensure_ledger_key_exists() {
-    let key_path = ledger_key_path();
-    if !key_path.exists() {
-        fs::create_dir_all(key_path.parent().unwrap()).expect("cannot create key directory");
-        let mut key = [0u8; 32];
-        rand::thread_rng().fill_bytes(&mut key);
-        fs::write(&key_path, &key).expect("cannot write ledger key");
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(&key_path).unwrap().permissions();
-            perms.set_mode(0o600);
-            fs::set_permissions(&key_path, perms).unwrap();
-        }
+    if !LedgerKeyProvider::key_exists(&local_config_dir()) {
+        let provider = LedgerKeyProvider::configured()
+            .unwrap_or_else(|e| panic!("cannot read ledger key settings: {}", e));
+        provider
+            .store(&local_config_dir(), &generate_key())
+            .unwrap_or_else(|e| panic!("cannot write ledger key: {}", e));
     }
 }
 
-/// Load the AES key from disk
+fn generate_key() -> [u8; 32] {
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    key
+}
+
+/// Load the AES key through the configured key provider
 fn load_ledger_key() -> Key<Aes256Gcm> {
-    let key_bytes = fs::read(ledger_key_path()).expect("missing ledger key");
-    assert!(
-        key_bytes.len() == 32,
-        "ledger key must be 32 bytes for AES-256-GCM"
-    );
-    Key::<Aes256Gcm>::from_slice(&key_bytes).clone()
+    let key = LedgerKeyProvider::configured()
+        .and_then(|provider| provider.load(&local_config_dir()))
+        .unwrap_or_else(|e| panic!("cannot load ledger key: {:#}", e));
+    Key::<Aes256Gcm>::from(key)
 }
 
 /// Compute SHA‑256 hash of a file (used for sealing logs)
@@ -68,23 +66,38 @@ pub fn compute_sha256(path: &PathBuf) -> String {
 
 /// Read and decrypt the ledger; returns plaintext bytes (or empty if not present)
 pub fn read_ledger_plaintext() -> Vec<u8> {
-    if !PathBuf::from(LEDGER_PATH).exists() {
+    let path = ledger_path();
+    if !path.exists() {
         return Vec::new();
     }
-    let data = fs::read(LEDGER_PATH).expect("cannot read encrypted ledger");
+    let data = fs::read(path).expect("cannot read encrypted ledger");
+    decrypt_ledger(&load_ledger_key(), &data).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Plaintext of `data` (nonce followed by ciphertext) under `key`
+fn decrypt_ledger(key: &Key<Aes256Gcm>, data: &[u8]) -> anyhow::Result<Vec<u8>> {
     if data.len() < 12 {
-        panic!("encrypted ledger corrupted (nonce missing)");
+        anyhow::bail!("encrypted ledger corrupted (nonce missing)");
     }
     let (nonce_bytes, ciphertext) = data.split_at(12);
-    let cipher = Aes256Gcm::new(&load_ledger_key());
-    cipher
+    Aes256Gcm::new(key)
         .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
-        .expect("ledger decryption failed")
+        .map_err(|_| anyhow::anyhow!("ledger decryption failed"))
 }
 
 /// Encrypt and write the full plaintext ledger
 fn write_ledger_plaintext(plaintext: &[u8]) {
-    let cipher = Aes256Gcm::new(&load_ledger_key());
+    let path = ledger_path();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).expect("cannot create ledger directory");
+    }
+    fs::write(path, encrypt_ledger(&load_ledger_key(), plaintext))
+        .expect("cannot write encrypted ledger");
+}
+
+/// Nonce followed by the AES-GCM ciphertext of `plaintext` under `key`
+fn encrypt_ledger(key: &Key<Aes256Gcm>, plaintext: &[u8]) -> Vec<u8> {
+    let cipher = Aes256Gcm::new(key);
     let mut nonce_bytes = [0u8; 12];
     rand::thread_rng().fill_bytes(&mut nonce_bytes);
     let nonce = Nonce::from_slice(&nonce_bytes);
@@ -96,18 +109,113 @@ fn write_ledger_plaintext(plaintext: &[u8]) {
     let mut out = Vec::with_capacity(12 + ciphertext.len());
     out.extend_from_slice(&nonce_bytes);
     out.extend_from_slice(&ciphertext);
-    fs::write(LEDGER_PATH, out).expect("cannot write encrypted ledger");
+    out
+}
+
+/// Re-encrypt the ledger under a new key, stored through `provider` in
+/// place of the current one; returns the number of ledger entries.
+///
+/// The old key stays in place until the ledger has been swapped:
+/// 1. the new key is stored in `config/ledger.key.staged/`;
+/// 2. the ledger is re-encrypted beside itself, backed up to
+///    `<ledger>.bak`, and the re-encrypted copy renamed over it;
+/// 3. the staged key replaces the old one, and the backup is removed.
+///
+/// A failure in any step undoes the earlier ones, leaving the ledger
+/// readable with the old key. If the process dies between steps 2 and 3,
+/// the ledger is readable with the staged key, and `<ledger>.bak` with the
+/// old one.
+pub(crate) fn rotate_ledger_key(provider: &LedgerKeyProvider) -> anyhow::Result<usize> {
+    rotate_key_at(provider, &local_config_dir(), &ledger_path(), |from, to| {
+        fs::rename(from, to)
+    })
+}
+
+/// `rotate_ledger_key` for the key in `config_dir` and the ledger at
+/// `ledger`, swapping in the re-encrypted ledger with `swap`.
+fn rotate_key_at(
+    provider: &LedgerKeyProvider,
+    config_dir: &Path,
+    ledger: &Path,
+    swap: impl Fn(&Path, &Path) -> std::io::Result<()>,
+) -> anyhow::Result<usize> {
+    if !LedgerKeyProvider::key_exists(config_dir) {
+        anyhow::bail!("no ledger key to rotate; run `pipa init` first");
+    }
+    let exists = ledger.exists();
+    let plaintext = if exists {
+        decrypt_ledger(
+            &Key::<Aes256Gcm>::from(provider.load(config_dir)?),
+            &fs::read(ledger)?,
+        )?
+    } else {
+        Vec::new()
+    };
+
+    let key = generate_key();
+    let staged_key = config_dir.join("ledger.key.staged");
+    let discard_key = || {
+        let _ = fs::remove_dir_all(&staged_key);
+    };
+    if let Err(e) = provider.store(&staged_key, &key) {
+        discard_key();
+        return Err(e);
+    }
+
+    let backup = ledger.with_extension("enc.bak");
+    if exists {
+        let staged = ledger.with_extension("enc.tmp");
+        let swapped = fs::write(
+            &staged,
+            encrypt_ledger(&Key::<Aes256Gcm>::from(key), &plaintext),
+        )
+        .and_then(|()| fs::copy(ledger, &backup).map(drop))
+        .and_then(|()| swap(&staged, ledger));
+        if let Err(e) = swapped {
+            let _ = fs::remove_file(&staged);
+            let _ = fs::remove_file(&backup);
+            discard_key();
+            return Err(e.into());
+        }
+    }
+
+    if let Err(e) = provider.commit_staged(&staged_key, config_dir) {
+        if exists {
+            fs::rename(&backup, ledger)?;
+        }
+        discard_key();
+        return Err(e);
+    }
+    let _ = fs::remove_file(&backup);
+    Ok(String::from_utf8_lossy(&plaintext).lines().count())
 }
 
 /// Append a line to the encrypted ledger
-/// Format: `<timestamp> <filename> <sha256>\n`
-pub fn append_to_ledger(filename: &str, hash: &str) {
+/// Format: `<timestamp> <filename> <sha256>[ <kind>:<token>]\n`, the last
+/// field holding the notary's token if the hash was notarized
+pub fn append_to_ledger(filename: &str, hash: &str, token: Option<&str>) {
     let mut ledger = read_ledger_plaintext();
-    let line = format!("{} {} {}\n", Utc::now().to_rfc3339(), filename, hash);
+    let mut line = format!("{} {} {}", Utc::now().to_rfc3339(), filename, hash);
+    if let Some(token) = token {
+        line.push(' ');
+        line.push_str(token);
+    }
+    line.push('\n');
     ledger.extend_from_slice(line.as_bytes());
     write_ledger_plaintext(&ledger);
 }
 
+/// Record that the sealed file `filename` (hash `hash`) was removed by the
+/// retention policy, `action` being `deleted` or `archived`
+/// Format: `<timestamp> <filename> <sha256> pruned:<action>\n`
+pub fn record_pruned(filename: &str, hash: &str, action: &str) {
+    append_to_ledger(
+        filename,
+        hash,
+        Some(&format!("{}{}", PRUNED_PREFIX, action)),
+    );
+}
+
 /// Check if a filename is already present in the ledger
 pub fn ledger_contains(filename: &str) -> bool {
     let ledger = read_ledger_plaintext();
@@ -115,8 +223,24 @@ pub fn ledger_contains(filename: &str) -> bool {
     s.contains(filename)
 }
 
-/// Seal all unsealed log files (older than today, not yet in encrypted ledger)
-pub fn seal_unsealed_logs(logs_dir: &PathBuf, today: &str) {
+/// Seal the daily file at `path` (named `filename`): append its hash to the
+/// ledger, notarized by `notary` if one is configured
+pub fn seal_log(path: &PathBuf, filename: &str, notary: Option<&Notary>) {
+    let hash = compute_sha256(path);
+    let token = notary.and_then(|notary| {
+        run_detached(notarize(notary, filename, &hash))
+            .map_err(|e| eprintln!("⚠️  Sealed {} without a timestamp: {}", filename, e))
+            .ok()
+    });
+    append_to_ledger(filename, &hash, token.as_deref());
+}
+
+/// Seal all unsealed log files (older than today, not yet in encrypted ledger),
+/// notarizing each hash with `notary` if one is configured
+///
+/// Returns the names of the files sealed by this call.
+pub fn seal_unsealed_logs(logs_dir: &PathBuf, today: &str, notary: Option<&Notary>) -> Vec<String> {
+    let mut sealed = Vec::new();
     for entry in fs::read_dir(logs_dir).expect("cannot read logs dir") {
         let entry = entry.expect("bad dir entry");
         let path = entry.path();
@@ -129,10 +253,83 @@ pub fn seal_unsealed_logs(logs_dir: &PathBuf, today: &str) {
                     if ledger_contains(fname) {
                         continue;
                     }
-                    let hash = compute_sha256(&path);
-                    append_to_ledger(fname, &hash);
+                    seal_log(&path, fname, notary);
+                    sealed.push(fname.to_string());
                 }
             }
         }
     }
+    sealed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ENTRIES: &[u8] = b"2025-01-01T00:00:00Z audit-2024-12-31.jsonl abc\n";
+
+    /// A local key in `<dir>/config` and a ledger under it in `<dir>/logs`.
+    fn ledger_in(dir: &TempDir) -> (PathBuf, PathBuf, [u8; 32]) {
+        let config_dir = dir.path().join("config");
+        let ledger = dir.path().join("logs").join("hash_ledger.enc");
+        let key = generate_key();
+        LedgerKeyProvider::Local.store(&config_dir, &key).unwrap();
+        fs::create_dir_all(ledger.parent().unwrap()).unwrap();
+        fs::write(
+            &ledger,
+            encrypt_ledger(&Key::<Aes256Gcm>::from(key), ENTRIES),
+        )
+        .unwrap();
+        (config_dir, ledger, key)
+    }
+
+    fn read_with_stored_key(config_dir: &Path, ledger: &Path) -> Vec<u8> {
+        let key = LedgerKeyProvider::Local.load(config_dir).unwrap();
+        decrypt_ledger(&Key::<Aes256Gcm>::from(key), &fs::read(ledger).unwrap()).unwrap()
+    }
+
+    fn leftovers(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| {
+                name.contains(".tmp") || name.contains(".bak") || name.contains("staged")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rotates_the_key_and_re_encrypts_the_ledger() {
+        let dir = TempDir::new().unwrap();
+        let (config_dir, ledger, old_key) = ledger_in(&dir);
+
+        let entries = rotate_key_at(&LedgerKeyProvider::Local, &config_dir, &ledger, |a, b| {
+            fs::rename(a, b)
+        })
+        .unwrap();
+
+        assert_eq!(entries, 1);
+        assert_ne!(LedgerKeyProvider::Local.load(&config_dir).unwrap(), old_key);
+        assert_eq!(read_with_stored_key(&config_dir, &ledger), ENTRIES);
+        assert!(leftovers(&config_dir).is_empty());
+        assert!(leftovers(ledger.parent().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn failed_ledger_swap_keeps_the_old_key() {
+        let dir = TempDir::new().unwrap();
+        let (config_dir, ledger, old_key) = ledger_in(&dir);
+
+        let err = rotate_key_at(&LedgerKeyProvider::Local, &config_dir, &ledger, |_, _| {
+            Err(std::io::Error::other("rename failed"))
+        })
+        .unwrap_err();
+
+        assert!(err.to_string().contains("rename failed"));
+        assert_eq!(LedgerKeyProvider::Local.load(&config_dir).unwrap(), old_key);
+        assert_eq!(read_with_stored_key(&config_dir, &ledger), ENTRIES);
+        assert!(leftovers(&config_dir).is_empty());
+        assert!(leftovers(ledger.parent().unwrap()).is_empty());
+    }
 }
@@ -2,8 +2,12 @@
 //!
 //! This trait allows consumers of `pipa-core` to provide their own
 //! logging implementation (e.g., JSONL files, DuckDB, in-memory, etc.).
+//! `JsonlLogger` is the default; references, `Box`es, and `Arc`s of a
+//! logger are loggers too, so a `Box<dyn AuditLogger>` chosen at runtime
+//! can be passed to any engine entry point.
 
 use crate::logging::schema::AuditLogEntry;
+use std::sync::Arc;
 
 /// Trait for audit logging implementations.
 ///
@@ -35,3 +39,56 @@ pub trait AuditLogger: Send + Sync {
         println!("{}", console_msg);
     }
 }
+
+impl<T: AuditLogger + ?Sized> AuditLogger for &T {
+    fn log_event(&self, entry: &AuditLogEntry) {
+        (**self).log_event(entry)
+    }
+
+    fn log_and_print(&self, entry: &AuditLogEntry, console_msg: &str) {
+        (**self).log_and_print(entry, console_msg)
+    }
+}
+
+impl<T: AuditLogger + ?Sized> AuditLogger for Box<T> {
+    fn log_event(&self, entry: &AuditLogEntry) {
+        (**self).log_event(entry)
+    }
+
+    fn log_and_print(&self, entry: &AuditLogEntry, console_msg: &str) {
+        (**self).log_and_print(entry, console_msg)
+    }
+}
+
+impl<T: AuditLogger + ?Sized> AuditLogger for Arc<T> {
+    fn log_event(&self, entry: &AuditLogEntry) {
+        (**self).log_event(entry)
+    }
+
+    fn log_and_print(&self, entry: &AuditLogEntry, console_msg: &str) {
+        (**self).log_and_print(entry, console_msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::log_action;
+    use crate::logging::MemoryLogger;
+
+    #[test]
+    fn trait_objects_forward_to_the_inner_logger() {
+        let memory = Arc::new(MemoryLogger::new());
+        let logger: Box<dyn AuditLogger> = Box::new(Arc::clone(&memory));
+        log_action(
+            &logger,
+            "contract_listed",
+            Some("count=2"),
+            None,
+            None,
+            None,
+        );
+        log_action(&&*logger, "contract_listed", None, None, None, None);
+        assert_eq!(memory.events(), vec!["contract_listed", "contract_listed"]);
+    }
+}
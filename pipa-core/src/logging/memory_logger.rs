@@ -0,0 +1,51 @@
+//! In-memory logger implementation.
+//!
+//! Keeps serialized entries in memory instead of writing JSONL files, so
+//! nothing touches disk and no ledger is sealed. Useful for:
+//! - Hermetic tests that assert on the audit trail
+//! - Embedding the engine where logs are forwarded elsewhere
+
+use crate::logging::logger_trait::AuditLogger;
+use crate::logging::schema::AuditLogEntry;
+use std::sync::Mutex;
+
+/// Logger that collects entries in memory.
+///
+/// Entries are stored as JSON values, in the order they were logged.
+#[derive(Default)]
+pub struct MemoryLogger {
+    entries: Mutex<Vec<serde_json::Value>>,
+}
+
+impl MemoryLogger {
+    /// Create a new, empty in-memory logger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All logged entries, oldest first.
+    pub fn entries(&self) -> Vec<serde_json::Value> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Event names of all logged entries, oldest first.
+    pub fn events(&self) -> Vec<String> {
+        self.entries()
+            .iter()
+            .filter_map(|entry| entry["event"].as_str().map(str::to_string))
+            .collect()
+    }
+}
+
+impl AuditLogger for MemoryLogger {
+    fn log_event(&self, entry: &AuditLogEntry) {
+        let value = serde_json::to_value(entry).expect("failed to serialize log entry");
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(value);
+    }
+}
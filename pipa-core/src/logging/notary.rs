@@ -0,0 +1,285 @@
+//! External timestamping of sealed log hashes.
+//!
+//! With `[notarization]` in `pipa.toml`, each daily file's hash is
+//! submitted to a third party as the file is sealed, and the returned
+//! token is stored in the ledger next to the hash:
+//!
+//! ```toml
+//! [notarization]
+//! type = "rfc3161"            # RFC 3161 timestamp authority
+//! url = "https://freetsa.org/tsr"
+//!
+//! # or a service of your own, sent {"file", "sha256", "sealed_at"} as JSON
+//! # [notarization]
+//! # type = "http"
+//! # url = "https://notary.example.com/hashes"
+//! # headers = { Authorization = "Bearer ${NOTARY_TOKEN}" }
+//! ```
+//!
+//! A timestamp token proves the hash existed at the authority's time, so
+//! a ledger rewritten later with new hashes no longer matches its tokens.
+//! `pipa logs verify` checks that each token covers the stored hash; the
+//! token's signature can be checked against the authority's certificate
+//! with `openssl ts -verify`.
+//!
+//! If the notary can't be reached, the file is sealed without a token and
+//! a warning is printed; sealing never waits longer than `NOTARY_TIMEOUT`.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::Utc;
+use rand::RngCore;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// How long one notarization request may take.
+pub const NOTARY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// DER encoding of the SHA-256 algorithm OID (2.16.840.1.101.3.4.2.1).
+const SHA256_OID: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+
+/// Where sealed hashes are notarized, selected by `type`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Notary {
+    /// RFC 3161 timestamp authority; the token is the `TimeStampToken`.
+    Rfc3161 { url: String },
+    /// JSON `POST` of the hash; the token is the response body.
+    Http {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+}
+
+impl Notary {
+    /// Name used as the token's prefix in the ledger.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Notary::Rfc3161 { .. } => "rfc3161",
+            Notary::Http { .. } => "http",
+        }
+    }
+}
+
+/// Notarize `hash` (hex SHA-256) of the daily file `filename`, returning
+/// the ledger token field: `<kind>:<base64 token>`.
+pub(crate) async fn notarize(
+    notary: &Notary,
+    filename: &str,
+    hash: &str,
+) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(NOTARY_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let token = match notary {
+        Notary::Rfc3161 { url } => {
+            let digest = hex_decode(hash).ok_or_else(|| format!("bad hash '{}'", hash))?;
+            let response = client
+                .post(url)
+                .header("Content-Type", "application/timestamp-query")
+                .body(timestamp_request(&digest))
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("HTTP {}", response.status()));
+            }
+            let body = response.bytes().await.map_err(|e| e.to_string())?;
+            granted_token(&body)?.to_vec()
+        }
+        Notary::Http { url, headers } => {
+            let mut request = client.post(url).json(&serde_json::json!({
+                "file": filename,
+                "sha256": hash,
+                "sealed_at": Utc::now().to_rfc3339(),
+            }));
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            let response = request.send().await.map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("HTTP {}", response.status()));
+            }
+            response.bytes().await.map_err(|e| e.to_string())?.to_vec()
+        }
+    };
+    if token.is_empty() {
+        return Err("empty token".to_string());
+    }
+    Ok(format!("{}:{}", notary.kind(), STANDARD.encode(token)))
+}
+
+/// Whether the ledger token field `token` refers to `hash`: an RFC 3161
+/// token embeds the digest, an HTTP receipt is expected to echo it.
+pub(crate) fn token_covers(token: &str, hash: &str) -> bool {
+    let Some((_, encoded)) = token.split_once(':') else {
+        return false;
+    };
+    let (Ok(bytes), Some(digest)) = (STANDARD.decode(encoded), hex_decode(hash)) else {
+        return false;
+    };
+    bytes.windows(digest.len()).any(|w| w == digest.as_slice())
+        || bytes
+            .windows(hash.len())
+            .any(|w| w.eq_ignore_ascii_case(hash.as_bytes()))
+}
+
+/// DER `TimeStampReq` for a SHA-256 `digest`, with a random nonce and the
+/// authority's certificate requested in the token.
+fn timestamp_request(digest: &[u8]) -> Vec<u8> {
+    let algorithm = der(0x30, &[der(0x06, &SHA256_OID), der(0x05, &[])].concat());
+    let imprint = der(0x30, &[algorithm, der(0x04, digest)].concat());
+    let mut nonce = [0u8; 8];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    // Positive and minimally encoded
+    nonce[0] = (nonce[0] & 0x7f) | 0x01;
+    der(
+        0x30,
+        &[
+            der(0x02, &[1]),
+            imprint,
+            der(0x02, &nonce),
+            der(0x01, &[0xff]),
+        ]
+        .concat(),
+    )
+}
+
+/// The `TimeStampToken` of a `TimeStampResp`, if the request was granted.
+fn granted_token(response: &[u8]) -> Result<&[u8], String> {
+    let malformed = || "malformed timestamp response".to_string();
+    let resp = read_tlv(response).ok_or_else(malformed)?;
+    let status_info = read_tlv(resp.content).ok_or_else(malformed)?;
+    let status = read_tlv(status_info.content).ok_or_else(malformed)?;
+    // SEQUENCE { SEQUENCE { INTEGER status, ... }, token }
+    if (resp.tag, status_info.tag, status.tag) != (0x30, 0x30, 0x02) {
+        return Err(malformed());
+    }
+    // 0 = granted, 1 = granted with modifications
+    match status.content {
+        [0] | [1] => {}
+        other => {
+            return Err(format!(
+                "timestamp authority rejected the request (status {})",
+                other.iter().fold(0u64, |n, b| n << 8 | u64::from(*b))
+            ));
+        }
+    }
+    let token = read_tlv(status_info.rest).ok_or_else(malformed)?;
+    Ok(token.element)
+}
+
+/// One DER element: tag, length, and content.
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes: Vec<u8> = len
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|b| *b == 0)
+            .collect();
+        out.push(0x80 | bytes.len() as u8);
+        out.extend(bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// A DER element read off the front of a buffer.
+struct Tlv<'a> {
+    tag: u8,
+    content: &'a [u8],
+    /// The whole element: tag, length, and content.
+    element: &'a [u8],
+    /// What follows the element.
+    rest: &'a [u8],
+}
+
+/// Split the first DER element off `data`.
+fn read_tlv(data: &[u8]) -> Option<Tlv<'_>> {
+    let (&tag, rest) = data.split_first()?;
+    let (&first, mut rest) = rest.split_first()?;
+    let len = if first < 0x80 {
+        usize::from(first)
+    } else {
+        let n = usize::from(first & 0x7f);
+        if n == 0 || n > 4 || rest.len() < n {
+            return None;
+        }
+        let (bytes, tail) = rest.split_at(n);
+        rest = tail;
+        bytes
+            .iter()
+            .fold(0usize, |len, b| len << 8 | usize::from(*b))
+    };
+    let header = data.len() - rest.len();
+    if rest.len() < len {
+        return None;
+    }
+    let (content, rest) = rest.split_at(len);
+    Some(Tlv {
+        tag,
+        content,
+        element: &data[..header + len],
+        rest,
+    })
+}
+
+fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn builds_request_and_reads_granted_token() {
+        let digest = hex_decode(HASH).unwrap();
+        let request = timestamp_request(&digest);
+        let req = read_tlv(&request).unwrap();
+        assert_eq!((req.tag, req.rest.len()), (0x30, 0));
+        let version = read_tlv(req.content).unwrap();
+        assert_eq!(version.content, [1]);
+        let imprint = read_tlv(version.rest).unwrap();
+        assert!(imprint.content.ends_with(&digest));
+
+        // A granted response whose token (here a stand-in) embeds the digest
+        let token = der(0x30, &[der(0x06, &SHA256_OID), der(0x04, &digest)].concat());
+        let status = der(0x30, &der(0x02, &[0]));
+        let response = der(0x30, &[status, token.clone()].concat());
+        assert_eq!(granted_token(&response).unwrap(), token.as_slice());
+
+        let field = format!("rfc3161:{}", STANDARD.encode(&token));
+        assert!(token_covers(&field, HASH));
+        assert!(!token_covers(&field, &HASH.replace('9', "8")));
+
+        let rejected = der(0x30, &der(0x30, &der(0x02, &[2])));
+        assert!(granted_token(&rejected).unwrap_err().contains("status 2"));
+    }
+
+    #[test]
+    fn encodes_long_lengths() {
+        let long = der(0x04, &[7u8; 300]);
+        assert_eq!(&long[..4], &[0x04, 0x82, 0x01, 0x2c]);
+        let tlv = read_tlv(&long).unwrap();
+        assert_eq!(
+            (tlv.content.len(), tlv.element.len(), tlv.rest.len()),
+            (300, 304, 0)
+        );
+    }
+}
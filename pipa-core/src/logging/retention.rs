@@ -0,0 +1,176 @@
+//! Retention of sealed daily logs.
+//!
+//! With `[retention]` in `pipa.toml`, `pipa logs prune` removes the daily
+//! logs older than `keep_days`, deleting them or, with `archive_dir`,
+//! moving them there:
+//!
+//! ```toml
+//! [retention]
+//! keep_days = 365
+//! archive_dir = "/mnt/archive/pipa"   # optional; delete when absent
+//! ```
+//!
+//! Only files whose hash still matches the ledger are pruned; unsealed or
+//! altered files are kept and reported. Each prune is recorded in the
+//! ledger as `<timestamp> <file> <sha256> pruned:<deleted|archived>`, so
+//! `pipa logs verify` reports the file as pruned rather than missing, and
+//! an archived copy can still be checked against the recorded hash.
+
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::logging::ledger::{compute_sha256, read_ledger_plaintext, record_pruned};
+use crate::logging::writer::write_lock;
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the ledger field marking a pruned file.
+pub const PRUNED_PREFIX: &str = "pruned:";
+
+/// `[retention]`: how long daily logs are kept.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionPolicy {
+    /// Days of logs to keep, counting back from today.
+    pub keep_days: u32,
+    /// Where expired logs are moved; they are deleted without one.
+    pub archive_dir: Option<PathBuf>,
+}
+
+impl RetentionPolicy {
+    /// The oldest day whose log is kept on `today`.
+    pub fn cutoff(&self, today: NaiveDate) -> NaiveDate {
+        today - Duration::days(i64::from(self.keep_days))
+    }
+}
+
+/// One expired daily log removed (or, in a dry run, to be removed).
+#[derive(Debug, Clone)]
+pub struct PrunedLog {
+    pub filename: String,
+    /// Where the file was moved, if it was archived rather than deleted.
+    pub archived_to: Option<PathBuf>,
+}
+
+/// Outcome of applying a retention policy.
+#[derive(Debug, Clone, Default)]
+pub struct LogPruning {
+    pub pruned: Vec<PrunedLog>,
+    /// Expired files kept because they aren't sealed.
+    pub unsealed: Vec<String>,
+    /// Expired files kept because their hash no longer matches the ledger.
+    pub mismatched: Vec<String>,
+}
+
+/// Remove the daily logs in `logs_dir` older than `policy` allows on
+/// `today`, recording each in the ledger; with `dry_run`, only report them.
+pub(crate) fn prune_logs(
+    logs_dir: &Path,
+    policy: &RetentionPolicy,
+    today: NaiveDate,
+    dry_run: bool,
+) -> ValidationResult<LogPruning> {
+    let _guard = write_lock();
+
+    let cutoff = policy.cutoff(today);
+    let mut expired: Vec<(NaiveDate, String)> = fs::read_dir(logs_dir)?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let date = name.strip_prefix("audit-")?.strip_suffix(".jsonl")?;
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+            Some((date, name))
+        })
+        .filter(|(date, _)| *date < cutoff)
+        .collect();
+    expired.sort();
+
+    let sealed = sealed_hashes();
+    let mut pruning = LogPruning::default();
+    for (_, filename) in expired {
+        let path = logs_dir.join(&filename);
+        let Some(stored_hash) = sealed.get(&filename) else {
+            pruning.unsealed.push(filename);
+            continue;
+        };
+        if compute_sha256(&path) != *stored_hash {
+            pruning.mismatched.push(filename);
+            continue;
+        }
+
+        let archived_to = policy
+            .archive_dir
+            .as_ref()
+            .map(|archive_dir| archive_dir.join(&filename));
+        if !dry_run {
+            match &archived_to {
+                Some(target) => move_file(&path, target)?,
+                None => fs::remove_file(&path)?,
+            }
+            let action = if archived_to.is_some() {
+                "archived"
+            } else {
+                "deleted"
+            };
+            record_pruned(&filename, stored_hash, action);
+        }
+        pruning.pruned.push(PrunedLog {
+            filename,
+            archived_to,
+        });
+    }
+    Ok(pruning)
+}
+
+/// Sealed hash of each file in the ledger, by filename.
+fn sealed_hashes() -> HashMap<String, String> {
+    let ledger = read_ledger_plaintext();
+    String::from_utf8_lossy(&ledger)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let pruned = parts.get(3).is_some_and(|f| f.starts_with(PRUNED_PREFIX));
+            match parts.as_slice() {
+                [_, filename, hash, ..] if !pruned => {
+                    Some((filename.to_string(), hash.to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Move `from` to `to`, copying across filesystems if a rename can't.
+fn move_file(from: &Path, to: &Path) -> ValidationResult<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if to.exists() {
+        return Err(ValidationError::Config(format!(
+            "{} already exists in the archive",
+            to.display()
+        )));
+    }
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_last_keep_days() {
+        let policy = RetentionPolicy {
+            keep_days: 30,
+            archive_dir: None,
+        };
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        assert_eq!(
+            policy.cutoff(today),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Logger wrapper for one contract run.
+//!
+//! Forwards every entry to the wrapped logger with the run's `run_id` set,
+//! so all audit events of a run can be found together rather than by
+//! timestamp proximity.
+
+use crate::logging::logger_trait::AuditLogger;
+use crate::logging::schema::AuditLogEntry;
+
+/// Tags each entry with its run's `run_id` before passing it on.
+pub(crate) struct RunLogger<'a, L: AuditLogger> {
+    inner: &'a L,
+    run_id: &'a str,
+}
+
+impl<'a, L: AuditLogger> RunLogger<'a, L> {
+    pub(crate) fn new(inner: &'a L, run_id: &'a str) -> Self {
+        Self { inner, run_id }
+    }
+
+    fn tagged<'e>(&'e self, entry: &AuditLogEntry<'e>) -> AuditLogEntry<'e> {
+        let mut entry = entry.clone();
+        entry.run_id = entry.run_id.or(Some(self.run_id));
+        entry
+    }
+}
+
+impl<L: AuditLogger> AuditLogger for RunLogger<'_, L> {
+    fn log_event(&self, entry: &AuditLogEntry) {
+        self.inner.log_event(&self.tagged(entry));
+    }
+
+    fn log_and_print(&self, entry: &AuditLogEntry, console_msg: &str) {
+        self.inner.log_and_print(&self.tagged(entry), console_msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::log_action;
+    use crate::logging::MemoryLogger;
+
+    #[test]
+    fn sets_the_run_id() {
+        let memory = MemoryLogger::new();
+        let logger = RunLogger::new(&memory, "run-1");
+        let _ = log_action(&logger, "file_read", Some("bytes=12"), None, None, None);
+
+        let entries = memory.entries();
+        assert_eq!(entries[0]["run_id"], "run-1");
+        assert_eq!(entries[0]["details"], "bytes=12");
+    }
+}
@@ -8,12 +8,14 @@ use serde::{Deserialize, Serialize};
 /// - leveled (INFO, AUDIT, ERROR, etc.)
 /// - typed by `event`
 /// - optionally tied to a contract, target, results, or summary
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct AuditLogEntry<'a> {
     pub timestamp: String, // RFC3339 timestamp
     pub level: &'a str,    // e.g. "AUDIT", "INFO", "ERROR"
     pub event: &'a str,    // semantic event name ("movement_success", "contract_validated")
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<&'a str>, // run the event belongs to (see `RunLogger`)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contract: Option<Contract<'a>>, // contract metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub target: Option<Target<'a>>, // file/column/rule context
@@ -27,14 +29,14 @@ pub struct AuditLogEntry<'a> {
 }
 
 /// Contract metadata (embedded in AuditLogEntry)
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct Contract<'a> {
     pub name: &'a str,
     pub version: &'a str,
 }
 
 /// Target of validation (file, column, rule)
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct Target<'a> {
     pub file: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -50,6 +52,8 @@ pub struct RuleResult {
     pub rule: String,            // rule name (e.g. "not_null")
     pub result: String,          // "pass" | "fail"
     pub details: Option<String>, // optional failure details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>, // time the rule took, when measured on its own
 }
 
 /// Executor metadata (who/where ran the validation)
@@ -60,7 +64,7 @@ pub struct Executor {
 }
 
 /// Summary of a full process run
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct ProcessSummary {
     pub contracts_run: usize,
     pub contracts_failed: usize,
@@ -0,0 +1,368 @@
+//! Shipping audit logs off-host.
+//!
+//! Sinks are declared as `[[log_sinks]]` in `pipa.toml`:
+//!
+//! ```toml
+//! [[log_sinks]]
+//! type = "s3"                  # or "azure", "gcs", "local"
+//! location = "s3://compliance-archive/pipa/"
+//! profile = "archive"
+//!
+//! [[log_sinks]]
+//! type = "syslog"
+//! address = "logs.internal:514"
+//! protocol = "tcp"             # default "udp"
+//!
+//! [[log_sinks]]
+//! type = "http"
+//! url = "https://siem.example.com/ingest"
+//! headers = { Authorization = "Bearer ${SIEM_TOKEN}" }
+//! ```
+//!
+//! - `syslog` and `http` sinks receive every entry as it is written: an
+//!   RFC 5424 message (facility local0, the entry JSON as its body) or a
+//!   JSON `POST` of the entry.
+//! - Storage sinks receive each daily file once it is sealed, uploaded
+//!   through the storage connectors to `<location>/audit-YYYY-MM-DD.jsonl`
+//!   with an `.sha256` sidecar holding the sealed hash. Uploads are recorded
+//!   in `logs/shipped.txt`; `pipa logs ship` retries any that failed.
+//!
+//! The local JSONL files and ledger stay the source of truth: a sink that
+//! is down is reported on stderr and never blocks or fails the write.
+
+use crate::contracts::schema::Source;
+use crate::logging::ledger::{compute_sha256, ledger_contains};
+use crate::movement::FileMovement;
+use crate::profiles::Profiles;
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::path::Path;
+use std::time::Duration;
+
+/// Record of daily files already uploaded, one `<sink> <file>` per line.
+pub const SHIPPED_FILE: &str = "shipped.txt";
+
+/// How long one delivery to a sink may take.
+const SINK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A destination for audit log entries or sealed daily files.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LogSink {
+    /// Every entry as an RFC 5424 syslog message.
+    Syslog {
+        /// `host:port` of the syslog collector.
+        address: String,
+        #[serde(default)]
+        protocol: SyslogProtocol,
+    },
+    /// Every entry `POST`ed as JSON.
+    Http {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    /// Sealed daily files uploaded to an S3 prefix.
+    S3 { location: String, profile: String },
+    /// Sealed daily files uploaded to an Azure Blob container path.
+    Azure { location: String, profile: String },
+    /// Sealed daily files uploaded to a GCS prefix.
+    Gcs { location: String, profile: String },
+    /// Sealed daily files copied to a local (e.g. mounted) directory.
+    Local { location: String },
+}
+
+/// Transport for syslog sinks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogProtocol {
+    #[default]
+    Udp,
+    /// Octet-counted framing (RFC 6587).
+    Tcp,
+}
+
+impl LogSink {
+    /// Name used in `shipped.txt` and messages: the address, URL, or location.
+    pub fn name(&self) -> &str {
+        match self {
+            LogSink::Syslog { address, .. } => address,
+            LogSink::Http { url, .. } => url,
+            LogSink::S3 { location, .. }
+            | LogSink::Azure { location, .. }
+            | LogSink::Gcs { location, .. }
+            | LogSink::Local { location } => location,
+        }
+    }
+
+    /// Whether the sink receives entries as they are written (vs sealed files).
+    pub fn is_streaming(&self) -> bool {
+        matches!(self, LogSink::Syslog { .. } | LogSink::Http { .. })
+    }
+
+    /// Connector target for `filename` under a storage sink's location.
+    fn target(&self, filename: &str) -> Option<Source> {
+        let (kind, location, profile) = match self {
+            LogSink::S3 { location, profile } => ("s3", location, Some(profile)),
+            LogSink::Azure { location, profile } => ("azure", location, Some(profile)),
+            LogSink::Gcs { location, profile } => ("gcs", location, Some(profile)),
+            LogSink::Local { location } => ("local", location, None),
+            LogSink::Syslog { .. } | LogSink::Http { .. } => return None,
+        };
+        Some(Source {
+            r#type: kind.to_string(),
+            location: Some(FileMovement::build_destination_path(location, filename)),
+            profile: profile.cloned(),
+            format: None,
+            options: Default::default(),
+            csv: Default::default(),
+            on_success: Default::default(),
+            archive_location: None,
+            on_duplicate: Default::default(),
+            archive_member: None,
+            archive_mode: Default::default(),
+        })
+    }
+}
+
+/// Send one serialized entry to every streaming sink, reporting failures on
+/// stderr.
+pub(crate) fn ship_entry(sinks: &[LogSink], event: &str, json: &str) {
+    for sink in sinks {
+        let result = match sink {
+            LogSink::Syslog { address, protocol } => {
+                send_syslog(address, *protocol, &syslog_message(event, json))
+            }
+            LogSink::Http { url, headers } => {
+                run_detached(post_entry(url, headers, json.to_string()))
+            }
+            _ => continue,
+        };
+        if let Err(e) = result {
+            eprintln!("⚠️  Audit log sink {}: {}", sink.name(), e);
+        }
+    }
+}
+
+/// RFC 5424 message for an entry: facility local0, severity info.
+fn syslog_message(event: &str, json: &str) -> String {
+    let host = hostname::get()
+        .map(|h| h.to_string_lossy().to_string())
+        .unwrap_or_else(|_| "-".to_string());
+    format!(
+        "<134>1 {} {} pipa {} {} - {}",
+        Utc::now().to_rfc3339(),
+        host,
+        std::process::id(),
+        event,
+        json
+    )
+}
+
+fn send_syslog(address: &str, protocol: SyslogProtocol, message: &str) -> Result<(), String> {
+    let addr = address
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("'{}' resolves to no address", address))?;
+    match protocol {
+        SyslogProtocol::Udp => {
+            let bind = if addr.is_ipv4() {
+                "0.0.0.0:0"
+            } else {
+                "[::]:0"
+            };
+            let socket = UdpSocket::bind(bind).map_err(|e| e.to_string())?;
+            socket
+                .send_to(message.as_bytes(), addr)
+                .map_err(|e| e.to_string())?;
+        }
+        SyslogProtocol::Tcp => {
+            let mut stream =
+                TcpStream::connect_timeout(&addr, SINK_TIMEOUT).map_err(|e| e.to_string())?;
+            stream
+                .set_write_timeout(Some(SINK_TIMEOUT))
+                .map_err(|e| e.to_string())?;
+            stream
+                .write_all(format!("{} {}", message.len(), message).as_bytes())
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+async fn post_entry(
+    url: &str,
+    headers: &BTreeMap<String, String>,
+    json: String,
+) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(SINK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(json);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+/// Run `future` to completion from synchronous logging code, on a
+/// short-lived runtime in its own thread (the caller may itself be running
+/// inside a Tokio runtime, where blocking on it would panic).
+pub(crate) fn run_detached<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("cannot start log shipping runtime")
+                    .block_on(future)
+            })
+            .join()
+            .expect("log shipping thread panicked")
+    })
+}
+
+/// One sealed daily file uploaded (or not) to one storage sink.
+#[derive(Debug, Clone)]
+pub struct ShippedFile {
+    pub sink: String,
+    pub filename: String,
+    /// Why the upload failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Upload every sealed daily file in `logs_dir` not yet shipped to each
+/// storage sink, recording successes in `shipped.txt`.
+pub async fn ship_sealed_logs(
+    logs_dir: &Path,
+    sinks: &[LogSink],
+    profiles: &Profiles,
+) -> Vec<ShippedFile> {
+    let mut shipped = Vec::new();
+    if sinks.iter().all(LogSink::is_streaming) {
+        return shipped;
+    }
+    let record_path = logs_dir.join(SHIPPED_FILE);
+    let record = fs::read_to_string(&record_path).unwrap_or_default();
+
+    let mut sealed: Vec<String> = fs::read_dir(logs_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter(|name| name.starts_with("audit-") && name.ends_with(".jsonl"))
+                .filter(|name| ledger_contains(name))
+                .collect()
+        })
+        .unwrap_or_default();
+    sealed.sort();
+
+    for sink in sinks {
+        for filename in &sealed {
+            let line = format!("{} {}", sink.name(), filename);
+            if record.lines().any(|l| l == line) {
+                continue;
+            }
+            let Some(target) = sink.target(filename) else {
+                continue;
+            };
+            let error = upload(&logs_dir.join(filename), &target, profiles)
+                .await
+                .err();
+            if error.is_none() {
+                let appended = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&record_path)
+                    .and_then(|mut f| f.write_all(format!("{}\n", line).as_bytes()));
+                if let Err(e) = appended {
+                    eprintln!("⚠️  Failed to record shipped log {}: {}", filename, e);
+                }
+            }
+            shipped.push(ShippedFile {
+                sink: sink.name().to_string(),
+                filename: filename.clone(),
+                error,
+            });
+        }
+    }
+    shipped
+}
+
+/// Upload a daily file and its `.sha256` sidecar.
+async fn upload(path: &Path, target: &Source, profiles: &Profiles) -> Result<(), String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let hash = compute_sha256(&path.to_path_buf());
+    FileMovement::write_data_via_connector(&data, target, profiles)
+        .await
+        .map_err(|e| e.to_string())?;
+    let sidecar = Source {
+        location: target.location.as_ref().map(|l| format!("{}.sha256", l)),
+        ..target.clone()
+    };
+    FileMovement::write_data_via_connector(format!("{}\n", hash).as_bytes(), &sidecar, profiles)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sinks_and_streams_entries_to_syslog() {
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        collector.set_read_timeout(Some(SINK_TIMEOUT)).unwrap();
+        #[derive(Deserialize)]
+        struct Config {
+            log_sinks: Vec<LogSink>,
+        }
+        let sinks = toml::from_str::<Config>(&format!(
+            "[[log_sinks]]\ntype = \"syslog\"\naddress = \"{}\"\n\n\
+             [[log_sinks]]\ntype = \"local\"\nlocation = \"archive/\"\n",
+            collector.local_addr().unwrap()
+        ))
+        .unwrap()
+        .log_sinks;
+        assert!(sinks[0].is_streaming() && !sinks[1].is_streaming());
+        assert_eq!(
+            sinks[1]
+                .target("audit-2025-01-01.jsonl")
+                .unwrap()
+                .location
+                .unwrap(),
+            "archive/audit-2025-01-01.jsonl"
+        );
+
+        ship_entry(
+            &sinks,
+            "contract_validation_completed",
+            r#"{"level":"AUDIT"}"#,
+        );
+        let mut buf = [0u8; 1024];
+        let n = collector.recv(&mut buf).unwrap();
+        let message = String::from_utf8_lossy(&buf[..n]);
+        assert!(message.starts_with("<134>1 "));
+        assert!(message.ends_with(r#" contract_validation_completed - {"level":"AUDIT"}"#));
+    }
+}
@@ -1,41 +1,63 @@
 //! Ledger sealing + verification
 //!
 //! - Sealing: decrypt ledger, append hashes for unsealed logs, re-encrypt.
-//! - Verification: decrypt ledger, recompute hashes, compare with stored.
+//! - Verification: decrypt ledger, recompute hashes, compare with stored
+//!   (and with the notary's timestamp token, if any), and check the hash
+//!   chain between entries of each file.
 //!
 //! This ensures logs are tamper-evident: once sealed, any modification
-//! or deletion will be detected by verification.
+//! or deletion will be detected by verification, and edits to a file that
+//! isn't sealed yet break its chain (see `logging::chain`). Files removed
+//! by the retention policy are recorded in the ledger and reported as
+//! pruned (see `logging::retention`).
 use chrono::{NaiveDate, Utc};
-use std::path::PathBuf;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::path::Path;
 
+use crate::config::logs_dir;
+use crate::logging::chain::verify_chain;
 use crate::logging::ledger::{compute_sha256, read_ledger_plaintext};
+use crate::logging::notary::token_covers;
+use crate::logging::retention::PRUNED_PREFIX;
 
 /// Result of verifying a single log file
+#[derive(Serialize)]
 pub struct FileVerification {
     pub filename: String,
+    #[serde(flatten)]
     pub status: FileStatus,
-    pub stored_hash: Option<String>,   // hash recorded in ledger
+    pub ledger_line: Option<usize>, // 1-based line of the ledger entry checked against
+    pub stored_hash: Option<String>, // hash recorded in ledger
     pub computed_hash: Option<String>, // hash recomputed from file
+    pub timestamp: Option<String>,  // notary token recorded in ledger (`<kind>:<base64>`)
 }
 
 /// Possible verification outcomes for a file
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
 pub enum FileStatus {
-    Verified,   // file exists and hash matches ledger
-    Mismatched, // file exists but hash differs from ledger
-    Missing,    // file referenced in ledger but missing on disk
-    Malformed,  // ledger entry malformed (not enough fields)
-    Unsealed,   // file exists but not present in ledger
+    Verified,                    // file exists and hash matches ledger
+    Mismatched,                  // file exists but hash differs from ledger
+    Missing,                     // file referenced in ledger but missing on disk
+    Malformed,                   // ledger entry malformed (not enough fields)
+    Unsealed,                    // file exists but not present in ledger
+    ChainBroken { line: usize }, // entry at `line` doesn't chain to the one before
+    Pruned,                      // file removed by the retention policy, as recorded in the ledger
 }
 
 /// Aggregated summary across all files checked
+#[derive(Serialize)]
 pub struct VerificationSummary {
     pub verified: usize,
     pub mismatched: usize,
     pub missing: usize,
     pub malformed: usize,
     pub unsealed: usize,
+    pub chain_broken: usize,
+    pub timestamped: usize, // verified files whose notary token covers their hash
+    pub pruned: usize,
     pub files: Vec<FileVerification>, // per-file results
 }
 
@@ -47,36 +69,108 @@ impl VerificationSummary {
             missing: 0,
             malformed: 0,
             unsealed: 0,
+            chain_broken: 0,
+            timestamped: 0,
+            pruned: 0,
             files: Vec::new(),
         }
     }
+
+    /// Record `file`, or a broken chain in it instead of `status` (a
+    /// hash mismatch takes precedence).
+    fn push(&mut self, path: &Path, mut file: FileVerification) {
+        if !matches!(file.status, FileStatus::Mismatched)
+            && let Some(line) = broken_chain_line(path)
+        {
+            file.status = FileStatus::ChainBroken { line };
+        }
+        if matches!(file.status, FileStatus::Verified) && file.timestamp.is_some() {
+            self.timestamped += 1;
+        }
+        match file.status {
+            FileStatus::Verified => self.verified += 1,
+            FileStatus::Mismatched => self.mismatched += 1,
+            FileStatus::Missing => self.missing += 1,
+            FileStatus::Malformed => self.malformed += 1,
+            FileStatus::Unsealed => self.unsealed += 1,
+            FileStatus::ChainBroken { .. } => self.chain_broken += 1,
+            FileStatus::Pruned => self.pruned += 1,
+        }
+        self.files.push(file);
+    }
+
+    /// Record a sealed file that isn't on disk: pruned if the ledger says
+    /// so, missing otherwise.
+    fn push_absent(
+        &mut self,
+        pruned_files: &HashSet<String>,
+        filename: String,
+        (stored_hash, token, ledger_line): SealRecord,
+    ) {
+        let status = if pruned_files.contains(&filename) {
+            self.pruned += 1;
+            FileStatus::Pruned
+        } else {
+            self.missing += 1;
+            FileStatus::Missing
+        };
+        self.files.push(FileVerification {
+            filename,
+            status,
+            ledger_line: Some(ledger_line),
+            stored_hash: Some(stored_hash),
+            computed_hash: None,
+            timestamp: token,
+        });
+    }
+}
+
+/// A file's seal in the ledger: stored hash, notary token, and line number.
+type SealRecord = (String, Option<String>, usize);
+
+/// Whether a sealed file checks out: its hash matches the ledger, and the
+/// ledger's notary token (if any) was issued for that hash.
+fn seal_holds(stored_hash: &str, computed_hash: &str, token: Option<&str>) -> bool {
+    stored_hash == computed_hash && token.is_none_or(|t| token_covers(t, stored_hash))
+}
+
+/// First line of the daily file at `path` that breaks its hash chain.
+fn broken_chain_line(path: &Path) -> Option<usize> {
+    let content = fs::read_to_string(path).ok()?;
+    verify_chain(&content).err()
 }
 
 /// Verify all sealed logs in the encrypted ledger
 pub fn verify_all() -> VerificationSummary {
-    let logs_dir = PathBuf::from("logs");
+    let logs_dir = logs_dir();
     let mut summary = VerificationSummary::new();
     
     // 1. Read all sealed files from the ledger into a HashMap for quick lookups.
-    // The map will store: filename -> stored_hash
-    let mut sealed_files: HashMap<String, String> = HashMap::new();
+    // The map will store: filename -> (stored_hash, notary token, ledger line)
+    let mut sealed_files: HashMap<String, SealRecord> = HashMap::new();
+    let mut pruned_files: HashSet<String> = HashSet::new();
     let ledger_plaintext = read_ledger_plaintext();
     if !ledger_plaintext.is_empty() {
         let ledger_str = String::from_utf8_lossy(&ledger_plaintext);
-        for line in ledger_str.lines() {
+        for (index, line) in ledger_str.lines().enumerate() {
             let parts: Vec<&str> = line.split_whitespace().collect();
             if parts.len() < 3 {
                 summary.malformed += 1;
                 summary.files.push(FileVerification {
                     filename: line.to_string(),
                     status: FileStatus::Malformed,
+                    ledger_line: Some(index + 1),
                     stored_hash: None,
                     computed_hash: None,
+                    timestamp: None,
                 });
+            } else if parts.get(3).is_some_and(|f| f.starts_with(PRUNED_PREFIX)) {
+                pruned_files.insert(parts[1].to_string());
             } else {
                 let filename = parts[1].to_string();
                 let stored_hash = parts[2].to_string();
-                sealed_files.insert(filename, stored_hash);
+                let token = parts.get(3).map(|t| t.to_string());
+                sealed_files.insert(filename, (stored_hash, token, index + 1));
             }
         }
     }
@@ -84,14 +178,8 @@ pub fn verify_all() -> VerificationSummary {
     // 2. If the logs directory doesn't exist, we can't find any files.
     // Any files in the ledger at this point must be missing.
     if !logs_dir.exists() {
-        for (filename, stored_hash) in sealed_files {
-            summary.missing += 1;
-            summary.files.push(FileVerification {
-                filename,
-                status: FileStatus::Missing,
-                stored_hash: Some(stored_hash),
-                computed_hash: None,
-            });
+        for (filename, seal) in sealed_files {
+            summary.push_absent(&pruned_files, filename, seal);
         }
         return summary;
     }
@@ -99,62 +187,68 @@ pub fn verify_all() -> VerificationSummary {
     // 3. Iterate through all log files on disk.
     for entry in fs::read_dir(logs_dir).expect("cannot read logs dir") {
         let path = entry.expect("bad dir entry").path();
-        if path.is_file() {
-            let filename = path.file_name().unwrap().to_string_lossy().to_string();
-            
+        let filename = path.file_name().unwrap().to_string_lossy().to_string();
+        // Only daily files are sealed; skip the ledger and other bookkeeping
+        if path.is_file() && filename.starts_with("audit-") && filename.ends_with(".jsonl") {
             // Check if this file was in our ledger map.
-            if let Some(stored_hash) = sealed_files.get(&filename) {
-                // The file is sealed. Now, verify the hash.
+            if let Some((stored_hash, token, ledger_line)) = sealed_files.get(&filename) {
+                // The file is sealed. Now, verify the hash (and its timestamp).
                 let computed_hash = compute_sha256(&path);
-                if *stored_hash == computed_hash {
-                    summary.verified += 1;
-                    summary.files.push(FileVerification {
-                        filename: filename.clone(),
-                        status: FileStatus::Verified,
-                        stored_hash: Some(stored_hash.clone()),
-                        computed_hash: Some(computed_hash),
-                    });
+                if seal_holds(stored_hash, &computed_hash, token.as_deref()) {
+                    summary.push(
+                        &path,
+                        FileVerification {
+                            filename: filename.clone(),
+                            status: FileStatus::Verified,
+                            ledger_line: Some(*ledger_line),
+                            stored_hash: Some(stored_hash.clone()),
+                            computed_hash: Some(computed_hash),
+                            timestamp: token.clone(),
+                        },
+                    );
                 } else {
-                    summary.mismatched += 1;
-                    summary.files.push(FileVerification {
-                        filename: filename.clone(),
-                        status: FileStatus::Mismatched,
-                        stored_hash: Some(stored_hash.clone()),
-                        computed_hash: Some(computed_hash),
-                    });
+                    summary.push(
+                        &path,
+                        FileVerification {
+                            filename: filename.clone(),
+                            status: FileStatus::Mismatched,
+                            ledger_line: Some(*ledger_line),
+                            stored_hash: Some(stored_hash.clone()),
+                            computed_hash: Some(computed_hash),
+                            timestamp: token.clone(),
+                        },
+                    );
                 }
                 // Remove the file from the map since we've processed it.
                 sealed_files.remove(&filename);
             } else {
                 // The file exists on disk but was not in the ledger. It's unsealed.
-                summary.unsealed += 1;
-                summary.files.push(FileVerification {
-                    filename,
-                    status: FileStatus::Unsealed,
-                    stored_hash: None,
-                    computed_hash: Some(compute_sha256(&path)), // Still useful to compute hash
-                });
+                summary.push(
+                    &path,
+                    FileVerification {
+                        filename,
+                        status: FileStatus::Unsealed,
+                        ledger_line: None,
+                        stored_hash: None,
+                        computed_hash: Some(compute_sha256(&path)), // Still useful to compute hash
+                        timestamp: None,
+                    },
+                );
             }
         }
     }
 
     // 4. Any files left in our map were in the ledger but not found on disk.
-    // These are missing files.
-    for (filename, stored_hash) in sealed_files {
-        summary.missing += 1;
-        summary.files.push(FileVerification {
-            filename,
-            status: FileStatus::Missing,
-            stored_hash: Some(stored_hash),
-            computed_hash: None,
-        });
+    // These are missing files, unless the retention policy pruned them.
+    for (filename, seal) in sealed_files {
+        summary.push_absent(&pruned_files, filename, seal);
     }
 
     summary
 }
 /// Verify logs for a specific date (YYYY-MM-DD). Defaults to yesterday if None.
 pub fn verify_date(date: Option<&str>) -> VerificationSummary {
-    let logs_dir = PathBuf::from("logs");
+    let logs_dir = logs_dir();
     let mut summary = VerificationSummary::new();
 
     if !logs_dir.exists() {
@@ -176,68 +270,105 @@ pub fn verify_date(date: Option<&str>) -> VerificationSummary {
     let log_filename = format!("audit-{}.jsonl", target_date);
     let log_path = logs_dir.join(&log_filename);
 
-    // 2. Decrypt ledger
+    // 2. Decrypt ledger (empty before the first file is sealed; today's
+    // file can still have its chain checked)
     let ledger_plaintext = read_ledger_plaintext();
-    if ledger_plaintext.is_empty() {
-        return summary;
-    }
     let ledger_str = String::from_utf8_lossy(&ledger_plaintext);
 
-    // 3. File missing entirely
+    // The entry sealing the file (a prune record is appended after it)
+    let seal = ledger_str
+        .lines()
+        .enumerate()
+        .find(|(_, l)| l.contains(&log_filename))
+        .map(|(index, line)| (index + 1, line));
+
+    // 3. File missing entirely (or pruned by the retention policy); a day
+    // the ledger has no record of just had no log
     if !log_path.exists() {
-        summary.missing += 1;
+        let Some((ledger_line, line)) = seal else {
+            return summary;
+        };
+        let pruned = ledger_str.lines().any(|l| {
+            let parts: Vec<&str> = l.split_whitespace().collect();
+            parts.get(1) == Some(&log_filename.as_str())
+                && parts.get(3).is_some_and(|f| f.starts_with(PRUNED_PREFIX))
+        });
+        let status = if pruned {
+            summary.pruned += 1;
+            FileStatus::Pruned
+        } else {
+            summary.missing += 1;
+            FileStatus::Missing
+        };
         summary.files.push(FileVerification {
             filename: log_filename,
-            status: FileStatus::Missing,
-            stored_hash: None,
+            status,
+            ledger_line: Some(ledger_line),
+            stored_hash: line.split_whitespace().nth(2).map(String::from),
             computed_hash: None,
+            timestamp: None,
         });
         return summary;
     }
 
     // 4. File exists, check if sealed in ledger
-    if let Some(line) = ledger_str.lines().find(|l| l.contains(&log_filename)) {
+    if let Some((ledger_line, line)) = seal {
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() < 3 {
             summary.malformed += 1;
             summary.files.push(FileVerification {
                 filename: log_filename,
                 status: FileStatus::Malformed,
+                ledger_line: Some(ledger_line),
                 stored_hash: None,
                 computed_hash: None,
+                timestamp: None,
             });
             return summary;
         }
 
         let stored_hash = parts[2].to_string();
+        let token = parts.get(3).map(|t| t.to_string());
         let computed_hash = compute_sha256(&log_path);
 
-        if stored_hash == computed_hash {
-            summary.verified += 1;
-            summary.files.push(FileVerification {
-                filename: log_filename,
-                status: FileStatus::Verified,
-                stored_hash: Some(stored_hash),
-                computed_hash: Some(computed_hash),
-            });
+        if seal_holds(&stored_hash, &computed_hash, token.as_deref()) {
+            summary.push(
+                &log_path,
+                FileVerification {
+                    filename: log_filename,
+                    status: FileStatus::Verified,
+                    ledger_line: Some(ledger_line),
+                    stored_hash: Some(stored_hash),
+                    computed_hash: Some(computed_hash),
+                    timestamp: token,
+                },
+            );
         } else {
-            summary.mismatched += 1;
-            summary.files.push(FileVerification {
-                filename: log_filename,
-                status: FileStatus::Mismatched,
-                stored_hash: Some(stored_hash),
-                computed_hash: Some(computed_hash),
-            });
+            summary.push(
+                &log_path,
+                FileVerification {
+                    filename: log_filename,
+                    status: FileStatus::Mismatched,
+                    ledger_line: Some(ledger_line),
+                    stored_hash: Some(stored_hash),
+                    computed_hash: Some(computed_hash),
+                    timestamp: token,
+                },
+            );
         }
     } else {
         // File exists but not sealed in ledger
-        summary.unsealed += 1;
-        summary.files.push(FileVerification {
-            filename: log_filename,
-            status: FileStatus::Unsealed,
-            stored_hash: None,
-            computed_hash: None,
-        });
+        summary.push(
+            &log_path,
+            FileVerification {
+                filename: log_filename,
+                status: FileStatus::Unsealed,
+                ledger_line: None,
+                stored_hash: None,
+                computed_hash: None,
+                timestamp: None,
+            },
+        );
     }
 
     summary
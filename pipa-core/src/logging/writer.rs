@@ -1,66 +1,79 @@
-//! Logging writer.
+//! Logging writer used by `JsonlLogger`.
 //!
-//! - Writes structured `AuditLogEntry`s to daily JSONL files.
+//! - Appends serialized entries to daily JSONL files.
 //! - After each write, automatically seals any unsealed logs into the encrypted ledger.
 //!   This ensures the ledger is always up to date without a separate cron job.
+//! - `seal_logs` seals on request (`pipa logs seal`, the scheduler's `[sealing]`).
 use chrono::Utc;
-use serde_json;
-use std::fs::{self, OpenOptions};
+use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
 
-use crate::logging::ledger::seal_unsealed_logs;
-use crate::logging::schema::AuditLogEntry; // 👈 bring in sealing
+use crate::logging::chain::{chain_entry, last_line};
+use crate::logging::ledger::{ledger_contains, seal_log, seal_unsealed_logs};
+use crate::logging::notary::Notary;
 
-/// Ensure `logs/` directory exists.
-/// Creates it if missing.
-fn ensure_logs_dir() -> PathBuf {
-    let dir = PathBuf::from("logs");
-    if !dir.exists() {
-        fs::create_dir_all(&dir).expect("cannot create logs directory");
-    }
-    dir
-}
+/// Serializes log appends (and the sealing that follows) across threads,
+/// so concurrent contract runs can't interleave entries or seal twice.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
 
-/// Get today’s log file path in JSONL format.
-/// Example: `logs/audit-2025-10-01.jsonl`
-fn today_log_path() -> PathBuf {
-    let logs_dir = ensure_logs_dir();
-    let today = Utc::now().format("%Y-%m-%d").to_string();
-    let log_filename = format!("audit-{}.jsonl", today);
-    logs_dir.join(log_filename)
+/// Hold the lock for other changes to the logs and ledger, such as pruning.
+pub(crate) fn write_lock() -> MutexGuard<'static, ()> {
+    WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner())
 }
 
-/// Append an `AuditLogEntry` to today’s JSONL file.
-/// Each entry is serialized as one line of JSON.
-/// After writing, automatically seal any unsealed logs.
-pub fn log_event(entry: &AuditLogEntry) {
-    let log_path = today_log_path();
+/// Append one serialized entry to `log_path`, chained to the line before it
+/// (see `logging::chain`), then seal any unsealed logs (older than today) in
+/// `logs_dir`, notarized by `notary`. Returns the line written and the files
+/// newly sealed.
+pub(crate) fn append_entry(
+    logs_dir: &Path,
+    log_path: &Path,
+    json: &str,
+    notary: Option<&Notary>,
+) -> (String, Vec<String>) {
+    let _guard = write_lock();
 
-    let json = serde_json::to_string(entry).expect("failed to serialize log entry");
+    let line = chain_entry(last_line(log_path).as_deref(), json);
 
     let mut file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(&log_path)
+        .open(log_path)
         .expect("cannot open daily audit log file");
 
-    writeln!(file, "{}", json).expect("failed to write log entry");
+    // One write per entry, so the line lands in the file as a whole
+    file.write_all(format!("{}\n", line).as_bytes())
+        .expect("failed to write log entry");
 
-    // 🔒 After writing, seal any unsealed logs (older than today)
     let today = Utc::now().format("%Y-%m-%d").to_string();
-    seal_unsealed_logs(&PathBuf::from("logs"), &today);
+    let sealed = seal_unsealed_logs(&logs_dir.to_path_buf(), &today, notary);
+    (line, sealed)
 }
 
-/// Append an `AuditLogEntry` to JSONL *and* print a console message.
+/// Seal the daily file `filename` in `logs_dir` if it isn't sealed yet, or
+/// with no `filename`, every unsealed file older than today; notarized by
+/// `notary`. Returns the files newly sealed.
 ///
-/// - `entry`: the structured audit log entry (full detail, sealed later)
-/// - `console_msg`: a curated, PII‑safe one‑liner for operator visibility
-#[allow(dead_code)]
-pub fn log_and_print(entry: &AuditLogEntry, console_msg: &str) {
-    // Write full detail to audit log + seal unsealed logs
-    log_event(entry);
+/// Takes the same lock as `append_entry`, so an explicit seal can't race
+/// the implicit one that follows each write.
+pub(crate) fn seal_logs(
+    logs_dir: &Path,
+    filename: Option<&str>,
+    notary: Option<&Notary>,
+) -> Vec<String> {
+    let _guard = write_lock();
 
-    // Print curated message to console
-    println!("{}", console_msg);
+    match filename {
+        Some(filename) if ledger_contains(filename) => Vec::new(),
+        Some(filename) => {
+            seal_log(&logs_dir.join(filename), filename, notary);
+            vec![filename.to_string()]
+        }
+        None => {
+            let today = Utc::now().format("%Y-%m-%d").to_string();
+            seal_unsealed_logs(&logs_dir.to_path_buf(), &today, notary)
+        }
+    }
 }
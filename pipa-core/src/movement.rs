@@ -5,10 +5,29 @@
 //! backends (local filesystem, S3, Azure, GCS) and integrates with
 //! configured profiles for authentication.
 //!
+//! Writes go through the run's `ObjectStore` (see `environment`); the
+//! connector-backed store calls `write_data_via_connector`, and deletes
+//! and copies (of re-validated quarantine artifacts and consumed source
+//! files) `delete_via_connector` and `copy_via_connector`.
+//!
+//! Local files are written atomically: the data goes to a hidden temporary
+//! name next to the target (`.pipa-tmp-<name>`) and is then renamed to its
+//! final name (`rename_via_connector`), so consumers never see a partial
+//! file. Both steps are audited: `movement_staged` once the temporary file
+//! is written, `movement_renamed` once it has its final name. A failed
+//! rename deletes the temporary file. Object stores are written straight to
+//! the final key, since an upload (a single PUT or a completed multipart
+//! upload) only becomes visible once it is whole.
+//!
 //! Responsibilities:
 //! - Validate profile connectivity before movement.
-//! - Generate unique filenames with timestamps (and quarantine suffix).
-//! - Serialize Polars DataFrames into CSV or Parquet.
+//! - Name written files from the target's `filename_template` (see
+//!   `contracts::filename`), by default with timestamps (and a quarantine
+//!   suffix); data released from quarantine gets its original stem back.
+//! - With `annotate`, add `_pipa_contract`, `_pipa_run_id`, and
+//!   `_pipa_validated_at` provenance columns to the written data.
+//! - Serialize Polars DataFrames into CSV, Parquet, or NDJSON, optionally
+//!   compressed with gzip or zstd (`format = "csv.gz"`, `"ndjson.zst"`).
 //! - Write data via the appropriate connector.
 //!
 //! ## Supported types
@@ -24,17 +43,44 @@
 //! - Use `FileMovement::validate_profiles` to pre‑check connectivity.
 
 use crate::connectors::{AzureConnector, GCSConnector, S3Connector};
+use crate::contracts::filename::{
+    DEFAULT_FILENAME_TEMPLATE, DEFAULT_QUARANTINE_FILENAME_TEMPLATE, FilenameValues,
+    render_filename,
+};
 use crate::contracts::schema::{Destination, Quarantine, Source};
-use crate::engine::profiles::test_profile_internal;
+use crate::engine::log_action;
+use crate::environment::ObjectStore;
+use crate::logging::AuditLogger;
+use crate::logging::error::{ValidationError, ValidationResult};
 use crate::profiles::Profiles;
 use anyhow::{Result, anyhow, bail};
-use chrono::Utc;
-use polars::prelude::{CsvWriter, DataFrame, ParquetWriter};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use polars::prelude::{AnyValue, CsvWriter, DataFrame, NamedFrom, ParquetWriter, Series};
 use polars_io::SerWriter;
-use std::io::Cursor;
+use std::io::{Cursor, Write};
 use std::path::Path;
 use url::Url;
 
+/// Where written data came from: its run and contract.
+pub struct Provenance<'a> {
+    pub contract: &'a str,
+    pub run_id: &'a str,
+    pub validated_at: DateTime<Utc>,
+}
+
+/// Prefix of the hidden name a file is written under before it is renamed
+/// into place.
+const TEMPORARY_PREFIX: &str = ".pipa-tmp-";
+
+/// Whether `location` names a file still being written under its
+/// temporary name.
+pub(crate) fn is_temporary(location: &str) -> bool {
+    let name = location.rsplit('/').next().unwrap_or(location);
+    name.starts_with(TEMPORARY_PREFIX)
+}
+
 /// File movement orchestrator.
 pub struct FileMovement;
 
@@ -46,25 +92,29 @@ impl FileMovement {
         source: Option<&Source>,
         destination: Option<&Destination>,
         quarantine: Option<&Quarantine>,
+        storage: &dyn ObjectStore,
         profiles: &Profiles,
     ) -> (bool, bool, bool) {
         let source_valid = Self::test_profile_connectivity(
-            source.and_then(|s| s.profile.as_ref()),
+            source.and_then(|s| s.profile.as_deref()),
             source.map(|s| s.r#type.as_str()),
+            storage,
             profiles,
         )
         .await;
 
         let dest_valid = Self::test_profile_connectivity(
-            destination.and_then(|d| d.profile.as_ref()),
+            destination.and_then(|d| d.profile.as_deref()),
             destination.map(|d| d.r#type.as_str()),
+            storage,
             profiles,
         )
         .await;
 
         let quarantine_valid = Self::test_profile_connectivity(
-            quarantine.and_then(|q| q.profile.as_ref()),
+            quarantine.and_then(|q| q.profile.as_deref()),
             quarantine.map(|q| q.r#type.as_str()),
+            storage,
             profiles,
         )
         .await;
@@ -74,93 +124,352 @@ impl FileMovement {
 
     /// Internal helper: test connectivity for a given profile/type.
     async fn test_profile_connectivity(
-        profile_name: Option<&String>,
+        profile_name: Option<&str>,
         destination_type: Option<&str>,
+        storage: &dyn ObjectStore,
         profiles: &Profiles,
     ) -> bool {
         match destination_type {
-            Some("local") | Some("not_moved") => true,
-            _ => {
-                if let Some(name) = profile_name {
-                    test_profile_internal(name, profiles).await
-                } else {
-                    false
-                }
-            }
+            Some(kind) => storage.is_reachable(kind, profile_name, profiles).await,
+            None => false,
         }
     }
 
-    /// Generate a unique filename with timestamp and optional quarantine suffix.
+    /// Name a file written for `contract` from `template`, or the default
+    /// timestamped name (with a quarantine suffix unless `passed`).
     fn generate_filename(
         original_location: &str,
-        is_quarantine: bool,
+        template: Option<&str>,
         format_override: Option<&str>,
-    ) -> String {
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+        contract: &str,
+        passed: bool,
+    ) -> Result<String> {
         let path = Path::new(original_location);
         let stem = path.file_stem().unwrap_or_default().to_string_lossy();
-
         let extension = format_override
             .or_else(|| path.extension().and_then(|s| s.to_str()))
             .unwrap_or("csv");
 
-        if is_quarantine {
-            format!("{}_{}_quarantine.{}", stem, timestamp, extension)
+        let template = template.unwrap_or(if passed {
+            DEFAULT_FILENAME_TEMPLATE
         } else {
-            format!("{}_{}.{}", stem, timestamp, extension)
+            DEFAULT_QUARANTINE_FILENAME_TEMPLATE
+        });
+        let values = FilenameValues {
+            stem: Self::unquarantined_stem(&stem),
+            ext: extension,
+            contract,
+            passed,
+            written_at: Utc::now(),
+        };
+        render_filename(template, &values).map_err(|e| anyhow!(e))
+    }
+
+    /// `stem` without the `_<timestamp>_quarantine` suffix a quarantined
+    /// file was given, so released data keeps its original name.
+    fn unquarantined_stem(stem: &str) -> &str {
+        let Some(rest) = stem.strip_suffix("_quarantine") else {
+            return stem;
+        };
+        // "_%Y%m%d_%H%M%S" is 16 characters.
+        match rest
+            .len()
+            .checked_sub(16)
+            .and_then(|at| rest.split_at_checked(at))
+        {
+            Some((original, timestamp))
+                if NaiveDateTime::parse_from_str(timestamp, "_%Y%m%d_%H%M%S").is_ok() =>
+            {
+                original
+            }
+            _ => stem,
         }
     }
 
     /// Write validated data to the configured **destination**.
-    pub async fn write_success_data(
+    ///
+    /// With `partition_by`, the data is split into one file per distinct
+    /// combination of those columns, written under hive-style
+    /// `<column>=<value>/` directories without the partition columns.
+    ///
+    /// Returns the number of bytes written.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn write_success_data<L: AuditLogger>(
         df: &DataFrame,
         original_location: &str,
         destination: &Destination,
+        provenance: &Provenance<'_>,
+        storage: &dyn ObjectStore,
         profiles: &Profiles,
-    ) -> Result<()> {
-        let filename =
-            Self::generate_filename(original_location, false, destination.format.as_deref());
+        logger: &L,
+        log_to_console: bool,
+    ) -> ValidationResult<usize> {
+        let filename = Self::generate_filename(
+            original_location,
+            destination.filename_template.as_deref(),
+            destination.format.as_deref(),
+            provenance.contract,
+            true,
+        )
+        .map_err(|e| ValidationError::MovementFailed {
+            target: destination.location.clone().unwrap_or_default(),
+            cause: e.to_string(),
+        })?;
+        let base = destination.location.as_ref().unwrap();
         let format = destination.format.as_deref().unwrap_or("csv");
-        let data = Self::serialize_dataframe(df, format)?;
+        let annotated;
+        let df = if destination.annotate {
+            annotated =
+                Self::annotate(df, provenance).map_err(|e| ValidationError::MovementFailed {
+                    target: base.clone(),
+                    cause: e.to_string(),
+                })?;
+            &annotated
+        } else {
+            df
+        };
 
-        let write_config = Source {
-            r#type: destination.r#type.clone(),
-            location: Some(Self::build_destination_path(
-                destination.location.as_ref().unwrap(),
-                &filename,
-            )),
-            profile: destination.profile.clone(),
+        let files = if destination.partition_by.is_empty() {
+            vec![(Self::build_destination_path(base, &filename), df.clone())]
+        } else {
+            Self::partitions(df, &destination.partition_by)
+                .map_err(|e| ValidationError::MovementFailed {
+                    target: base.clone(),
+                    cause: e.to_string(),
+                })?
+                .into_iter()
+                .map(|(dir, part)| {
+                    let dir = Self::build_destination_path(base, &dir);
+                    (Self::build_destination_path(&dir, &filename), part)
+                })
+                .collect()
         };
 
-        Self::write_data_via_connector(&data, &write_config, profiles).await
+        let mut written = 0;
+        for (location, part) in files {
+            let write_config = Source {
+                r#type: destination.r#type.clone(),
+                location: Some(location.clone()),
+                profile: destination.profile.clone(),
+                format: None,
+                options: Default::default(),
+                csv: Default::default(),
+                on_success: Default::default(),
+                archive_location: None,
+                on_duplicate: Default::default(),
+                archive_member: None,
+                archive_mode: Default::default(),
+            };
+            written += Self::write_serialized(
+                &part,
+                format,
+                &write_config,
+                provenance,
+                storage,
+                profiles,
+                logger,
+                log_to_console,
+            )
+            .await
+            .map_err(|e| ValidationError::MovementFailed {
+                target: location,
+                cause: e.to_string(),
+            })?;
+        }
+        Ok(written)
+    }
+
+    /// `df` with `_pipa_contract`, `_pipa_run_id`, and `_pipa_validated_at`
+    /// (RFC 3339) columns, replacing any it already has.
+    fn annotate(df: &DataFrame, provenance: &Provenance) -> Result<DataFrame> {
+        let validated_at = provenance.validated_at.to_rfc3339();
+        let mut df = df.clone();
+        for (name, value) in [
+            ("_pipa_contract", provenance.contract),
+            ("_pipa_run_id", provenance.run_id),
+            ("_pipa_validated_at", validated_at.as_str()),
+        ] {
+            df.with_column(Series::new(name.into(), vec![value; df.height()]))?;
+        }
+        Ok(df)
+    }
+
+    /// Split `df` by the values of `keys`, returning each part (without the
+    /// key columns) with its `key=value/...` directory.
+    fn partitions(df: &DataFrame, keys: &[String]) -> Result<Vec<(String, DataFrame)>> {
+        for key in keys {
+            if df.column(key).is_err() {
+                bail!("partition column '{}' is not in the data", key);
+            }
+        }
+        df.partition_by_stable(keys.iter().map(String::as_str), true)?
+            .into_iter()
+            .map(|part| {
+                let dir = keys
+                    .iter()
+                    .map(|key| {
+                        let value = part.column(key)?.get(0)?;
+                        Ok(format!("{}={}", key, Self::partition_value(&value)))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join("/");
+                Ok((dir, part.drop_many(keys.iter().map(String::as_str))))
+            })
+            .collect()
+    }
+
+    /// A partition value as written in its directory name; nulls use Hive's
+    /// default partition name.
+    fn partition_value(value: &AnyValue) -> String {
+        let text = match value {
+            AnyValue::Null => return "__HIVE_DEFAULT_PARTITION__".to_string(),
+            value => value
+                .get_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string()),
+        };
+        urlencoding::encode(&text).into_owned()
     }
 
     /// Write failed data to the configured **quarantine**.
-    pub async fn write_quarantine_data(
+    ///
+    /// Returns the number of bytes written.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn write_quarantine_data<L: AuditLogger>(
         df: &DataFrame,
         original_location: &str,
         quarantine: &Quarantine,
+        provenance: &Provenance<'_>,
+        storage: &dyn ObjectStore,
         profiles: &Profiles,
-    ) -> Result<()> {
-        let filename =
-            Self::generate_filename(original_location, true, quarantine.format.as_deref());
+        logger: &L,
+        log_to_console: bool,
+    ) -> ValidationResult<usize> {
+        let filename = Self::generate_filename(
+            original_location,
+            quarantine.filename_template.as_deref(),
+            quarantine.format.as_deref(),
+            provenance.contract,
+            false,
+        )
+        .map_err(|e| ValidationError::MovementFailed {
+            target: quarantine.location.clone().unwrap_or_default(),
+            cause: e.to_string(),
+        })?;
+        let location =
+            Self::build_destination_path(quarantine.location.as_ref().unwrap(), &filename);
         let format = quarantine.format.as_deref().unwrap_or("csv");
-        let data = Self::serialize_dataframe(df, format)?;
+        let annotated;
+        let df = if quarantine.annotate {
+            annotated =
+                Self::annotate(df, provenance).map_err(|e| ValidationError::MovementFailed {
+                    target: location.clone(),
+                    cause: e.to_string(),
+                })?;
+            &annotated
+        } else {
+            df
+        };
 
         let write_config = Source {
             r#type: quarantine.r#type.clone(),
-            location: Some(Self::build_destination_path(
-                quarantine.location.as_ref().unwrap(),
-                &filename,
-            )),
+            location: Some(location.clone()),
             profile: quarantine.profile.clone(),
+            format: None,
+            options: Default::default(),
+            csv: Default::default(),
+            on_success: Default::default(),
+            archive_location: None,
+            on_duplicate: Default::default(),
+            archive_member: None,
+            archive_mode: Default::default(),
+        };
+
+        Self::write_serialized(
+            df,
+            format,
+            &write_config,
+            provenance,
+            storage,
+            profiles,
+            logger,
+            log_to_console,
+        )
+        .await
+        .map_err(|e| ValidationError::MovementFailed {
+            target: location,
+            cause: e.to_string(),
+        })
+    }
+
+    /// Serialize `df` as `format` and write it to `config`'s location. Local
+    /// files go through a temporary name that is renamed once the write
+    /// completes, logging `movement_staged` and `movement_renamed`.
+    #[allow(clippy::too_many_arguments)]
+    async fn write_serialized<L: AuditLogger>(
+        df: &DataFrame,
+        format: &str,
+        config: &Source,
+        provenance: &Provenance<'_>,
+        storage: &dyn ObjectStore,
+        profiles: &Profiles,
+        logger: &L,
+        log_to_console: bool,
+    ) -> Result<usize> {
+        let data = Self::serialize_dataframe(df, format)?;
+        let Some(location) = config
+            .location
+            .as_deref()
+            .filter(|_| config.r#type == "local")
+        else {
+            return storage.write(config, &data, profiles).await;
+        };
+
+        let temporary = Source {
+            location: Some(Self::temporary_location(location)),
+            ..config.clone()
+        };
+        let staged = temporary.location.as_deref().unwrap_or_default();
+        let log = |event: &str, details: &str, target: &str| {
+            let message = log_action(
+                logger,
+                event,
+                Some(details),
+                Some(provenance.contract),
+                None,
+                Some(target),
+            );
+            if log_to_console {
+                println!("{}", message);
+            }
         };
 
-        Self::write_data_via_connector(&data, &write_config, profiles).await
+        let written = storage.write(&temporary, &data, profiles).await?;
+        log(
+            "movement_staged",
+            &format!("bytes={}, temporary={}", written, staged),
+            location,
+        );
+        if let Err(e) = storage.rename(&temporary, location, profiles).await {
+            // Don't leave the temporary file behind for a retry to trip over.
+            let _ = storage.delete(&temporary, profiles).await;
+            return Err(e);
+        }
+        log("movement_renamed", &format!("from={}", staged), location);
+        Ok(written)
+    }
+
+    /// Hidden name `location` is written under before its final rename. It
+    /// keeps the extension, so uploads get the final content type.
+    fn temporary_location(location: &str) -> String {
+        match location.rsplit_once('/') {
+            Some((dir, name)) => format!("{}/{}{}", dir, TEMPORARY_PREFIX, name),
+            None => format!("{}{}", TEMPORARY_PREFIX, location),
+        }
     }
 
     /// Build a full destination path by appending filename to base location.
-    fn build_destination_path(base_location: &str, filename: &str) -> String {
+    pub(crate) fn build_destination_path(base_location: &str, filename: &str) -> String {
         if base_location.ends_with('/') {
             format!("{}{}", base_location, filename)
         } else {
@@ -169,11 +478,13 @@ impl FileMovement {
     }
 
     /// Write serialized data to the configured backend.
-    async fn write_data_via_connector(
+    ///
+    /// Returns the number of bytes handed to the connector (0 for `not_moved`).
+    pub(crate) async fn write_data_via_connector(
         data: &[u8],
         config: &Source,
         profiles: &Profiles,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         match config.r#type.as_str() {
             "local" => {
                 let location = config.location.as_ref().unwrap();
@@ -182,7 +493,7 @@ impl FileMovement {
                 }
                 std::fs::write(location, data)?;
                 println!("📁 Wrote {} bytes to {}", data.len(), location);
-                Ok(())
+                Ok(data.len())
             }
             "s3" => {
                 let profile_name = config.profile.as_ref().unwrap();
@@ -194,7 +505,7 @@ impl FileMovement {
                 let connector = S3Connector::from_profile_and_url(profile, &url).await?;
                 connector.put_object_from_url(location, data).await?;
                 println!("📤 Wrote {} bytes to {}", data.len(), location);
-                Ok(())
+                Ok(data.len())
             }
             "azure" => {
                 let profile_name = config.profile.as_ref().unwrap();
@@ -206,7 +517,7 @@ impl FileMovement {
                 let connector = AzureConnector::from_profile_and_url(profile, &url).await?;
                 connector.put_object_from_url(location, data).await?;
                 println!("☁️ Wrote {} bytes to {}", data.len(), location);
-                Ok(())
+                Ok(data.len())
             }
             "gcs" => {
                 let profile_name = config.profile.as_ref().unwrap();
@@ -218,18 +529,158 @@ impl FileMovement {
                 let connector = GCSConnector::from_profile_and_url(profile, &url).await?;
                 connector.put_object_from_url(location, data).await?;
                 println!("☁️ Wrote {} bytes to {}", data.len(), location);
-                Ok(())
+                Ok(data.len())
             }
             "not_moved" => {
                 println!("📄 Marked as not_moved, skipping write");
-                Ok(())
+                Ok(0)
             }
             _ => bail!("Unsupported type: {}", config.r#type),
         }
     }
-    /// Serialize a DataFrame into the requested format (CSV or Parquet).
-    fn serialize_dataframe(df: &DataFrame, format: &str) -> Result<Vec<u8>> {
-        match format.to_lowercase().as_str() {
+
+    /// Delete the object at `config`'s location from its backend.
+    pub(crate) async fn delete_via_connector(config: &Source, profiles: &Profiles) -> Result<()> {
+        let location = config
+            .location
+            .as_ref()
+            .ok_or_else(|| anyhow!("Delete target missing location"))?;
+        let profile = || {
+            let profile_name = config
+                .profile
+                .as_ref()
+                .ok_or_else(|| anyhow!("{} target missing profile", config.r#type))?;
+            profiles
+                .get(profile_name)
+                .ok_or_else(|| anyhow!("Profile '{}' not found", profile_name))
+        };
+        match config.r#type.as_str() {
+            "local" => std::fs::remove_file(location)?,
+            "s3" => {
+                let connector =
+                    S3Connector::from_profile_and_url(profile()?, &Url::parse(location)?).await?;
+                connector.delete_object_from_url(location).await?;
+            }
+            "azure" => {
+                let connector =
+                    AzureConnector::from_profile_and_url(profile()?, &Url::parse(location)?)
+                        .await?;
+                connector.delete_object_from_url(location).await?;
+            }
+            "gcs" => {
+                let connector =
+                    GCSConnector::from_profile_and_url(profile()?, &Url::parse(location)?).await?;
+                connector.delete_object_from_url(location).await?;
+            }
+            _ => bail!("Unsupported type: {}", config.r#type),
+        }
+        println!("🗑️  Deleted {}", location);
+        Ok(())
+    }
+
+    /// Move the object at `config`'s location to `to` on the same backend:
+    /// a filesystem rename locally, a server-side copy and delete on object
+    /// stores.
+    pub(crate) async fn rename_via_connector(
+        config: &Source,
+        to: &str,
+        profiles: &Profiles,
+    ) -> Result<()> {
+        let location = config
+            .location
+            .as_ref()
+            .ok_or_else(|| anyhow!("Rename source missing location"))?;
+        let profile = || {
+            let profile_name = config
+                .profile
+                .as_ref()
+                .ok_or_else(|| anyhow!("{} source missing profile", config.r#type))?;
+            profiles
+                .get(profile_name)
+                .ok_or_else(|| anyhow!("Profile '{}' not found", profile_name))
+        };
+        match config.r#type.as_str() {
+            "local" => std::fs::rename(location, to)?,
+            "s3" => {
+                let connector =
+                    S3Connector::from_profile_and_url(profile()?, &Url::parse(location)?).await?;
+                connector.rename_object_from_url(location, to).await?;
+            }
+            "azure" => {
+                let connector =
+                    AzureConnector::from_profile_and_url(profile()?, &Url::parse(location)?)
+                        .await?;
+                connector.rename_object_from_url(location, to).await?;
+            }
+            "gcs" => {
+                let connector =
+                    GCSConnector::from_profile_and_url(profile()?, &Url::parse(location)?).await?;
+                connector.rename_object_from_url(location, to).await?;
+            }
+            _ => bail!("Unsupported type: {}", config.r#type),
+        }
+        Ok(())
+    }
+
+    /// Copy the object at `config`'s location to `to` on the same backend,
+    /// server-side for object stores.
+    pub(crate) async fn copy_via_connector(
+        config: &Source,
+        to: &str,
+        profiles: &Profiles,
+    ) -> Result<()> {
+        let location = config
+            .location
+            .as_ref()
+            .ok_or_else(|| anyhow!("Copy source missing location"))?;
+        let profile = || {
+            let profile_name = config
+                .profile
+                .as_ref()
+                .ok_or_else(|| anyhow!("{} source missing profile", config.r#type))?;
+            profiles
+                .get(profile_name)
+                .ok_or_else(|| anyhow!("Profile '{}' not found", profile_name))
+        };
+        match config.r#type.as_str() {
+            "local" => {
+                if let Some(parent) = Path::new(to).parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::copy(location, to)?;
+            }
+            "s3" => {
+                let connector =
+                    S3Connector::from_profile_and_url(profile()?, &Url::parse(location)?).await?;
+                connector.copy_object_from_url(location, to).await?;
+            }
+            "azure" => {
+                let connector =
+                    AzureConnector::from_profile_and_url(profile()?, &Url::parse(location)?)
+                        .await?;
+                connector.copy_object_from_url(location, to).await?;
+            }
+            "gcs" => {
+                let connector =
+                    GCSConnector::from_profile_and_url(profile()?, &Url::parse(location)?).await?;
+                connector.copy_object_from_url(location, to).await?;
+            }
+            _ => bail!("Unsupported type: {}", config.r#type),
+        }
+        println!("📦 Copied {} to {}", location, to);
+        Ok(())
+    }
+
+    /// Serialize a DataFrame into the requested format: `csv`, `parquet`,
+    /// or `ndjson` (`jsonl`), with an optional `.gz` or `.zst` compression
+    /// suffix.
+    pub(crate) fn serialize_dataframe(df: &DataFrame, format: &str) -> Result<Vec<u8>> {
+        let format = format.to_lowercase();
+        let (base, compression) = match format.rsplit_once('.') {
+            Some((base, compression)) => (base, Some(compression)),
+            None => (format.as_str(), None),
+        };
+        let data = match base {
             "csv" => {
                 let mut buffer = Vec::new();
                 let mut cursor = Cursor::new(&mut buffer);
@@ -237,16 +688,284 @@ impl FileMovement {
                 CsvWriter::new(&mut cursor)
                     .include_header(true)
                     .finish(&mut df_clone)?;
-                Ok(buffer)
+                buffer
             }
             "parquet" => {
                 let mut buffer = Vec::new();
                 let mut cursor = Cursor::new(&mut buffer);
                 let mut df_clone = df.clone();
                 ParquetWriter::new(&mut cursor).finish(&mut df_clone)?;
-                Ok(buffer)
+                buffer
             }
+            "ndjson" | "jsonl" => Self::serialize_ndjson(df)?,
             _ => bail!("Unsupported output format: {}", format),
+        };
+        match compression {
+            None => Ok(data),
+            Some("gz") => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&data)?;
+                Ok(encoder.finish()?)
+            }
+            Some("zst") => Ok(zstd::encode_all(&data[..], 0)?),
+            Some(other) => bail!("Unsupported output compression: {}", other),
+        }
+    }
+
+    /// One JSON object per row, keyed by column name.
+    fn serialize_ndjson(df: &DataFrame) -> Result<Vec<u8>> {
+        let mut columns: Vec<_> = df
+            .get_columns()
+            .iter()
+            .map(|column| {
+                let series = column.as_materialized_series();
+                (
+                    column.name().to_string(),
+                    series.iter().map(Self::json_value),
+                )
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        for _ in 0..df.height() {
+            let row: serde_json::Map<String, serde_json::Value> = columns
+                .iter_mut()
+                .map(|(name, values)| (name.clone(), values.next().unwrap_or_default()))
+                .collect();
+            serde_json::to_writer(&mut buffer, &row)?;
+            buffer.push(b'\n');
+        }
+        Ok(buffer)
+    }
+
+    /// JSON form of a cell; non-numeric, non-boolean values are written as
+    /// strings.
+    fn json_value(value: AnyValue) -> serde_json::Value {
+        use serde_json::Value;
+        match value {
+            AnyValue::Null => Value::Null,
+            AnyValue::Boolean(v) => v.into(),
+            AnyValue::Int8(v) => v.into(),
+            AnyValue::Int16(v) => v.into(),
+            AnyValue::Int32(v) => v.into(),
+            AnyValue::Int64(v) => v.into(),
+            AnyValue::UInt8(v) => v.into(),
+            AnyValue::UInt16(v) => v.into(),
+            AnyValue::UInt32(v) => v.into(),
+            AnyValue::UInt64(v) => v.into(),
+            AnyValue::Float32(v) => f64::from(v).into(),
+            AnyValue::Float64(v) => v.into(),
+            other => match other.get_str() {
+                Some(s) => s.into(),
+                None => other.to_string().into(),
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::ConnectorStore;
+    use crate::environment::InMemory;
+    use crate::logging::MemoryLogger;
+    use chrono::TimeZone;
+    use flate2::read::GzDecoder;
+    use polars::prelude::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    #[test]
+    fn serializes_compressed_ndjson() {
+        let df = df!(
+            "id" => [Some(1i64), None],
+            "name" => ["a", "b"],
+        )
+        .unwrap();
+
+        let mut ndjson = String::new();
+        let data = FileMovement::serialize_dataframe(&df, "NDJSON.gz").unwrap();
+        GzDecoder::new(&data[..])
+            .read_to_string(&mut ndjson)
+            .unwrap();
+        assert_eq!(
+            ndjson,
+            "{\"id\":1,\"name\":\"a\"}\n{\"id\":null,\"name\":\"b\"}\n"
+        );
+
+        let data = FileMovement::serialize_dataframe(&df, "csv.zst").unwrap();
+        let csv = zstd::decode_all(&data[..]).unwrap();
+        assert_eq!(csv, b"id,name\n1,a\n,b\n");
+
+        assert!(FileMovement::serialize_dataframe(&df, "csv.bz2").is_err());
+        assert!(FileMovement::serialize_dataframe(&df, "xml").is_err());
+    }
+
+    #[test]
+    fn annotates_provenance_columns() {
+        let df = df!("id" => [1i64, 2]).unwrap();
+        let provenance = Provenance {
+            contract: "orders",
+            run_id: "20250115T143005.000Z",
+            validated_at: Utc.with_ymd_and_hms(2025, 1, 15, 14, 30, 5).unwrap(),
+        };
+
+        let annotated = FileMovement::annotate(&df, &provenance).unwrap();
+        let data = FileMovement::serialize_dataframe(&annotated, "csv").unwrap();
+        assert_eq!(
+            String::from_utf8(data).unwrap().lines().nth(1).unwrap(),
+            "1,orders,20250115T143005.000Z,2025-01-15T14:30:05+00:00"
+        );
+        // Re-annotating replaces the columns instead of adding more.
+        let again = FileMovement::annotate(&annotated, &provenance).unwrap();
+        assert_eq!(again.width(), 4);
+    }
+
+    #[test]
+    fn temporary_name_is_hidden_next_to_the_target() {
+        assert_eq!(
+            FileMovement::temporary_location("s3://clean/orders/orders.csv.gz"),
+            "s3://clean/orders/.pipa-tmp-orders.csv.gz"
+        );
+        assert_eq!(
+            FileMovement::temporary_location("orders.csv"),
+            ".pipa-tmp-orders.csv"
+        );
+    }
+
+    fn local_destination(dir: &TempDir) -> Destination {
+        Destination {
+            r#type: "local".to_string(),
+            location: Some(dir.path().to_string_lossy().into_owned()),
+            profile: None,
+            format: None,
+            filename_template: Some("orders.{ext}".to_string()),
+            partition_by: Vec::new(),
+            annotate: false,
+        }
+    }
+
+    /// Names of the files left in `dir`.
+    fn names(dir: &TempDir) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn local_writes_are_staged_and_renamed_into_place() {
+        let dir = TempDir::new().unwrap();
+        let df = df!("id" => [1i64, 2]).unwrap();
+        let provenance = Provenance {
+            contract: "orders",
+            run_id: "run-1",
+            validated_at: Utc::now(),
+        };
+        let logger = MemoryLogger::new();
+
+        let written = FileMovement::write_success_data(
+            &df,
+            "orders.csv",
+            &local_destination(&dir),
+            &provenance,
+            &ConnectorStore,
+            &Profiles::new(),
+            &logger,
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(names(&dir), vec!["orders.csv"]);
+        let data = std::fs::read(dir.path().join("orders.csv")).unwrap();
+        assert_eq!(data, b"id\n1\n2\n");
+        assert_eq!(written, data.len());
+        let entries = logger.entries();
+        let staged = entries
+            .iter()
+            .find(|e| e["event"] == "movement_staged")
+            .unwrap();
+        assert!(
+            staged["details"]
+                .as_str()
+                .unwrap()
+                .ends_with("/.pipa-tmp-orders.csv")
+        );
+        assert!(entries.iter().any(|e| e["event"] == "movement_renamed"));
+    }
+
+    #[tokio::test]
+    async fn object_store_writes_go_straight_to_the_final_key() {
+        let memory = InMemory::new();
+        let destination = Destination {
+            r#type: "s3".to_string(),
+            location: Some("s3://clean/".to_string()),
+            profile: Some("clean".to_string()),
+            ..local_destination(&TempDir::new().unwrap())
+        };
+        let df = df!("id" => [1i64]).unwrap();
+        let provenance = Provenance {
+            contract: "orders",
+            run_id: "run-1",
+            validated_at: Utc::now(),
+        };
+        let logger = MemoryLogger::new();
+
+        FileMovement::write_success_data(
+            &df,
+            "orders.csv",
+            &destination,
+            &provenance,
+            &memory,
+            &Profiles::new(),
+            &logger,
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(memory.locations(), vec!["s3://clean/orders.csv"]);
+        assert!(logger.events().is_empty());
+        assert!(is_temporary("s3://clean/.pipa-tmp-orders.csv"));
+        assert!(!is_temporary("s3://clean/orders.csv"));
+    }
+
+    #[tokio::test]
+    async fn failed_rename_removes_the_temporary_file() {
+        let dir = TempDir::new().unwrap();
+        // A non-empty directory in the way of the final name.
+        std::fs::create_dir_all(dir.path().join("orders.csv/taken")).unwrap();
+        let df = df!("id" => [1i64]).unwrap();
+        let provenance = Provenance {
+            contract: "orders",
+            run_id: "run-1",
+            validated_at: Utc::now(),
+        };
+        let logger = MemoryLogger::new();
+
+        let result = FileMovement::write_success_data(
+            &df,
+            "orders.csv",
+            &local_destination(&dir),
+            &provenance,
+            &ConnectorStore,
+            &Profiles::new(),
+            &logger,
+            false,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ValidationError::MovementFailed { .. })
+        ));
+        assert_eq!(names(&dir), vec!["orders.csv"]);
+        assert!(dir.path().join("orders.csv").is_dir());
+        let events = logger.events();
+        assert!(events.iter().any(|e| e == "movement_staged"));
+        assert!(!events.iter().any(|e| e == "movement_renamed"));
+    }
+}
@@ -0,0 +1,412 @@
+//! Run notifications: telling Slack, Teams, a webhook, or an inbox how a
+//! contract run went.
+//!
+//! After every run (not dry runs), `run_contract_validation_with` builds a
+//! `RunNotification` from the outcome and sends it to each channel whose
+//! `on` list matches:
+//! - `success`: every rule passed;
+//! - `failure`: a rule failed, the input exceeded `max_bytes`, or the run
+//!   errored before producing an outcome;
+//! - `quarantine`: failing data was written to the quarantine (a failed
+//!   run also matches `failure`).
+//!
+//! Channels come from the contract's `[notifications]`, or from `pipa.toml`
+//! when the contract declares none. Each delivery is logged as
+//! `notification_sent` or `notification_failed`; a failed delivery never
+//! changes the run's result.
+//!
+//! | `type`    | Delivery                                                   |
+//! |-----------|------------------------------------------------------------|
+//! | `slack`   | `POST {"text": ...}` to a Slack incoming webhook            |
+//! | `teams`   | `POST {"title": ..., "text": ...}` to a Teams webhook       |
+//! | `webhook` | `POST` of the `RunNotification` as JSON, with `headers`     |
+//! | `email`   | Plain-text mail via an SMTP relay (see `notify::smtp`)      |
+
+pub mod smtp;
+
+use crate::contracts::{ChannelTarget, NotificationChannel, Notifications, NotifyOn};
+use crate::engine::contracts::ValidationOutcome;
+use crate::engine::log_action;
+use crate::engine::plan::ExecutionPlan;
+use crate::environment::Environment;
+use crate::logging::AuditLogger;
+use crate::logging::error::ValidationResult;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+
+/// How long one delivery may take before it is reported as failed.
+pub const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Failed rules listed in a notification; the rest are counted.
+const MAX_LISTED_FAILURES: usize = 20;
+
+/// Summary of one run, as sent to notification channels.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunNotification {
+    pub contract: String,
+    pub version: Option<String>,
+    pub passed: bool,
+    pub pass_count: usize,
+    pub fail_count: usize,
+    pub quarantined: bool,
+    /// Failed rules as `column: rule`, at most `MAX_LISTED_FAILURES`.
+    pub failed_rules: Vec<String>,
+    /// Why the run errored, if it did.
+    pub error: Option<String>,
+    pub timestamp: String,
+}
+
+impl RunNotification {
+    /// Summarize a run of `contract` from its result.
+    pub fn from_result(
+        contract: &str,
+        version: Option<&str>,
+        result: &ValidationResult<(ValidationOutcome, String)>,
+    ) -> Self {
+        let mut notification = Self {
+            contract: contract.to_string(),
+            version: version.map(str::to_string),
+            passed: false,
+            pass_count: 0,
+            fail_count: 0,
+            quarantined: false,
+            failed_rules: Vec::new(),
+            error: None,
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        match result {
+            Ok((outcome, _)) => {
+                notification.passed = outcome.passed;
+                notification.pass_count = outcome.pass_count;
+                notification.fail_count = outcome.fail_count;
+                notification.quarantined = outcome.quarantined;
+                notification.failed_rules = outcome
+                    .results
+                    .iter()
+                    .filter(|r| r.result == "fail" || r.result == "aborted")
+                    .take(MAX_LISTED_FAILURES)
+                    .map(|r| format!("{}: {}", r.column, r.rule))
+                    .collect();
+            }
+            Err(e) => notification.error = Some(e.to_string()),
+        }
+        notification
+    }
+
+    /// Outcomes this run counts as.
+    pub fn events(&self) -> Vec<NotifyOn> {
+        let mut events = vec![if self.passed {
+            NotifyOn::Success
+        } else {
+            NotifyOn::Failure
+        }];
+        if self.quarantined {
+            events.push(NotifyOn::Quarantine);
+        }
+        events
+    }
+
+    /// One-line summary, e.g. `❌ orders 1.2.0: 2 rules failed (data quarantined)`.
+    pub fn title(&self) -> String {
+        let name = match &self.version {
+            Some(version) => format!("{} {}", self.contract, version),
+            None => self.contract.clone(),
+        };
+        if let Some(error) = &self.error {
+            return format!("❌ {}: run failed: {}", name, error);
+        }
+        if self.passed {
+            return format!("✅ {}: {} rules passed", name, self.pass_count);
+        }
+        format!(
+            "❌ {}: {} rules failed{}",
+            name,
+            self.fail_count,
+            if self.quarantined {
+                " (data quarantined)"
+            } else {
+                ""
+            }
+        )
+    }
+
+    /// The title followed by one line per failed rule.
+    pub fn text(&self) -> String {
+        let mut text = self.title();
+        for rule in &self.failed_rules {
+            text.push_str("\n• ");
+            text.push_str(rule);
+        }
+        let unlisted = self.fail_count.saturating_sub(self.failed_rules.len());
+        if unlisted > 0 {
+            text.push_str(&format!("\n… and {} more", unlisted));
+        }
+        text
+    }
+}
+
+/// Deliver `notification` to one channel.
+pub async fn send(target: &ChannelTarget, notification: &RunNotification) -> Result<(), String> {
+    match target {
+        ChannelTarget::Slack { webhook_url } => {
+            post_json(webhook_url, &[], &json!({ "text": notification.text() })).await
+        }
+        ChannelTarget::Teams { webhook_url } => {
+            let body = json!({ "title": notification.title(), "text": notification.text() });
+            post_json(webhook_url, &[], &body).await
+        }
+        ChannelTarget::Webhook { url, headers } => {
+            let headers: Vec<(&str, &str)> = headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            let body = serde_json::to_value(notification).map_err(|e| e.to_string())?;
+            post_json(url, &headers, &body).await
+        }
+        ChannelTarget::Email {
+            smtp_host,
+            smtp_port,
+            from,
+            to,
+        } => {
+            let mail = smtp::Mail {
+                from,
+                to,
+                subject: &notification.title(),
+                body: &notification.text(),
+            };
+            smtp::send_mail(smtp_host, *smtp_port, &mail).await
+        }
+    }
+}
+
+async fn post_json(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(NOTIFY_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.post(url).json(body);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+/// Send `notification` to every channel subscribed to one of its events,
+/// logging each delivery.
+pub async fn notify<L: AuditLogger>(
+    logger: &L,
+    channels: &[NotificationChannel],
+    notification: &RunNotification,
+    log_to_console: bool,
+) {
+    let events = notification.events();
+    for channel in channels {
+        let Some(event) = events.iter().find(|event| channel.on.contains(event)) else {
+            continue;
+        };
+        let kind = channel.target.kind();
+        let (log_event, details) = match send(&channel.target, notification).await {
+            Ok(()) => (
+                "notification_sent",
+                format!("channel={}, event={}", kind, event.name()),
+            ),
+            Err(e) => (
+                "notification_failed",
+                format!("channel={}, event={}, error={}", kind, event.name(), e),
+            ),
+        };
+        let message = log_action(
+            logger,
+            log_event,
+            Some(&details),
+            Some(&notification.contract),
+            notification.version.as_deref(),
+            None,
+        );
+        if log_to_console {
+            println!("{}", message);
+        }
+    }
+}
+
+/// Notify the channels configured for `contract_name` about a finished run.
+///
+/// The contract's `[notifications]` win over the project's; when the
+/// contract can't be loaded (the run errored on it), the project's apply.
+pub(crate) async fn notify_run<L: AuditLogger>(
+    env: &Environment,
+    logger: &L,
+    contract_name: &str,
+    result: &ValidationResult<(ValidationOutcome, String)>,
+    log_to_console: bool,
+) {
+    let plan = env
+        .config
+        .contract_toml(contract_name)
+        .and_then(|toml_str| ExecutionPlan::cached(&toml_str))
+        .ok()
+        .map(|(plan, _)| plan);
+    let contract = plan.as_ref().map(|plan| &plan.contracts.contract);
+    let declared = plan
+        .as_ref()
+        .and_then(|plan| plan.contracts.notifications.clone());
+
+    let notifications = match declared {
+        Some(notifications) => notifications,
+        None => match env.config.project() {
+            Ok(project) => project.notifications.unwrap_or_default(),
+            Err(e) => {
+                let message = log_action(
+                    logger,
+                    "notification_failed",
+                    Some(&format!("error={}", e)),
+                    Some(contract_name),
+                    contract.map(|c| c.version.as_str()),
+                    None,
+                );
+                if log_to_console {
+                    println!("{}", message);
+                }
+                Notifications::default()
+            }
+        },
+    };
+    if notifications.channels.is_empty() {
+        return;
+    }
+
+    let notification = RunNotification::from_result(
+        contract.map_or(contract_name, |c| c.name.as_str()),
+        contract.map(|c| c.version.as_str()),
+        result,
+    );
+    notify(
+        logger,
+        &notifications.channels,
+        &notification,
+        log_to_console,
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::MemoryLogger;
+    use crate::logging::schema::RuleResult;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn failed_run() -> ValidationResult<(ValidationOutcome, String)> {
+        let fail = |column: &str| RuleResult {
+            column: column.to_string(),
+            rule: "not_null".to_string(),
+            result: "fail".to_string(),
+            details: None,
+            duration_ms: None,
+        };
+        Ok((
+            ValidationOutcome {
+                passed: false,
+                pass_count: 3,
+                fail_count: 2,
+                results: vec![fail("id"), fail("amount")],
+                transfer: Default::default(),
+                objects: Vec::new(),
+                quarantined: true,
+                run_id: String::new(),
+                timings: Default::default(),
+            },
+            String::new(),
+        ))
+    }
+
+    #[test]
+    fn summarizes_failed_runs() {
+        let notification = RunNotification::from_result("orders", Some("1.2.0"), &failed_run());
+        assert_eq!(
+            notification.events(),
+            vec![NotifyOn::Failure, NotifyOn::Quarantine]
+        );
+        assert_eq!(
+            notification.text(),
+            "❌ orders 1.2.0: 2 rules failed (data quarantined)\n• id: not_null\n• amount: not_null"
+        );
+    }
+
+    /// Accept one HTTP request, answer `204`, and return the request text.
+    async fn capture_one_request(listener: TcpListener) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some(head_end) = text.find("\r\n\r\n") {
+                let length = text[..head_end]
+                    .lines()
+                    .find_map(|line| {
+                        line.to_lowercase()
+                            .strip_prefix("content-length: ")?
+                            .parse()
+                            .ok()
+                    })
+                    .unwrap_or(0usize);
+                if request.len() >= head_end + 4 + length {
+                    break;
+                }
+            }
+        }
+        socket
+            .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+        String::from_utf8(request).unwrap()
+    }
+
+    #[tokio::test]
+    async fn webhook_channel_posts_the_summary_as_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks/pipa", listener.local_addr().unwrap());
+        let server = tokio::spawn(capture_one_request(listener));
+
+        let channels: Notifications = toml::from_str(&format!(
+            r#"
+[[channels]]
+type = "webhook"
+url = "{url}"
+headers = {{ X-Token = "secret" }}
+on = ["quarantine"]
+
+[[channels]]
+type = "slack"
+webhook_url = "{url}"
+on = ["success"]
+"#
+        ))
+        .unwrap();
+        let logger = MemoryLogger::new();
+        let notification = RunNotification::from_result("orders", Some("1.2.0"), &failed_run());
+        notify(&logger, &channels.channels, &notification, false).await;
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hooks/pipa HTTP/1.1"));
+        assert!(request.to_lowercase().contains("x-token: secret"));
+        assert!(request.contains(r#""quarantined":true"#));
+        assert!(request.contains(r#""failed_rules":["id: not_null","amount: not_null"]"#));
+        assert_eq!(logger.events(), vec!["notification_sent"]);
+    }
+}
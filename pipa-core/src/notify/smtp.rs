@@ -0,0 +1,203 @@
+//! Minimal SMTP client for `type = "email"` notification channels.
+//!
+//! Speaks plain SMTP (RFC 5321) to a relay: `EHLO`, `MAIL FROM`, one
+//! `RCPT TO` per recipient, `DATA`, `QUIT`. There is no TLS and no
+//! authentication, so point it at an internal relay (or a local MTA that
+//! forwards to your provider) rather than a public submission port.
+
+use super::NOTIFY_TIMEOUT;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::Utc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// A plain-text message.
+pub struct Mail<'a> {
+    pub from: &'a str,
+    pub to: &'a [String],
+    pub subject: &'a str,
+    pub body: &'a str,
+}
+
+/// Deliver `mail` through the SMTP relay at `host:port`.
+pub async fn send_mail(host: &str, port: u16, mail: &Mail<'_>) -> Result<(), String> {
+    if mail.to.is_empty() {
+        return Err("no recipients".to_string());
+    }
+    tokio::time::timeout(NOTIFY_TIMEOUT, deliver(host, port, mail))
+        .await
+        .map_err(|_| format!("SMTP {}:{} timed out", host, port))?
+}
+
+async fn deliver(host: &str, port: u16, mail: &Mail<'_>) -> Result<(), String> {
+    let stream = TcpStream::connect((host, port))
+        .await
+        .map_err(|e| format!("SMTP connect to {}:{}: {}", host, port, e))?;
+    let mut session = Session {
+        stream: BufReader::new(stream),
+    };
+
+    session.expect(220).await?;
+    let client = hostname::get()
+        .map(|h| h.to_string_lossy().to_string())
+        .unwrap_or_else(|_| "localhost".to_string());
+    session.command(&format!("EHLO {}", client), 250).await?;
+    session
+        .command(&format!("MAIL FROM:<{}>", mail.from), 250)
+        .await?;
+    for recipient in mail.to {
+        session
+            .command(&format!("RCPT TO:<{}>", recipient), 250)
+            .await?;
+    }
+    session.command("DATA", 354).await?;
+    session.write(&message(mail)).await?;
+    session.command(".", 250).await?;
+    session.command("QUIT", 221).await
+}
+
+/// Headers and dot-stuffed body, with CRLF line endings.
+fn message(mail: &Mail<'_>) -> String {
+    let mut message = format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n",
+        mail.from,
+        mail.to.join(", "),
+        encode_header(mail.subject),
+        Utc::now().to_rfc2822()
+    );
+    for line in mail.body.lines() {
+        if line.starts_with('.') {
+            message.push('.');
+        }
+        message.push_str(line);
+        message.push_str("\r\n");
+    }
+    message
+}
+
+/// `text` as an RFC 2047 encoded word when it isn't plain ASCII.
+fn encode_header(text: &str) -> String {
+    if text.is_ascii() {
+        text.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", STANDARD.encode(text))
+    }
+}
+
+struct Session {
+    stream: BufReader<TcpStream>,
+}
+
+impl Session {
+    async fn write(&mut self, text: &str) -> Result<(), String> {
+        self.stream
+            .get_mut()
+            .write_all(text.as_bytes())
+            .await
+            .map_err(|e| format!("SMTP write: {}", e))
+    }
+
+    /// Send one command line and check the reply code.
+    async fn command(&mut self, line: &str, code: u16) -> Result<(), String> {
+        self.write(&format!("{}\r\n", line)).await?;
+        self.expect(code).await
+    }
+
+    /// Read a (possibly multi-line) reply and check its code.
+    async fn expect(&mut self, code: u16) -> Result<(), String> {
+        loop {
+            let mut line = String::new();
+            let read = self
+                .stream
+                .read_line(&mut line)
+                .await
+                .map_err(|e| format!("SMTP read: {}", e))?;
+            if read == 0 {
+                return Err("SMTP connection closed".to_string());
+            }
+            let line = line.trim_end();
+            let reply: Option<u16> = line.get(..3).and_then(|c| c.parse().ok());
+            if reply != Some(code) {
+                return Err(format!("SMTP: expected {}, got '{}'", code, line));
+            }
+            // `250-...` continues a multi-line reply; `250 ...` ends it.
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// A relay that accepts everything and returns the DATA it received.
+    async fn fake_relay(listener: TcpListener) -> String {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut socket = BufReader::new(socket);
+        socket
+            .get_mut()
+            .write_all(b"220 relay ready\r\n")
+            .await
+            .unwrap();
+        let mut data = String::new();
+        let mut in_data = false;
+        loop {
+            let mut line = String::new();
+            if socket.read_line(&mut line).await.unwrap() == 0 {
+                break;
+            }
+            let reply: &[u8] = if in_data {
+                if line == ".\r\n" {
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else {
+                    data.push_str(&line);
+                    continue;
+                }
+            } else if line.starts_with("EHLO") {
+                b"250-relay\r\n250 8BITMIME\r\n"
+            } else if line.starts_with("DATA") {
+                in_data = true;
+                b"354 go ahead\r\n"
+            } else if line.starts_with("QUIT") {
+                socket.get_mut().write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 ok\r\n"
+            };
+            socket.get_mut().write_all(reply).await.unwrap();
+        }
+        data
+    }
+
+    #[tokio::test]
+    async fn delivers_dot_stuffed_message_through_relay() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let relay = tokio::spawn(fake_relay(listener));
+
+        let to = vec!["data@example.com".to_string()];
+        let mail = Mail {
+            from: "pipa@example.com",
+            to: &to,
+            subject: "❌ orders: 1 rules failed",
+            body: "❌ orders: 1 rules failed\n.hidden: not_null",
+        };
+        send_mail("127.0.0.1", port, &mail).await.unwrap();
+
+        let data = relay.await.unwrap();
+        let subject = format!(
+            "Subject: {}\r\n",
+            encode_header("❌ orders: 1 rules failed")
+        );
+        assert!(data.contains(&subject));
+        assert!(subject.starts_with("Subject: =?UTF-8?B?"));
+        assert!(data.contains("To: data@example.com\r\n"));
+        assert!(data.ends_with("\r\n\r\n❌ orders: 1 rules failed\r\n..hidden: not_null\r\n"));
+    }
+}
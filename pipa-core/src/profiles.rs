@@ -29,6 +29,9 @@
 //! ## Usage
 //! - Call `load_profiles()` to load and expand all profiles.
 //! - Pass the resulting `Profiles` into file movement or connector logic.
+//! - `add_profile_section`, `edit_profile_section`, and
+//!   `remove_profile_section` rewrite one section of the file's text,
+//!   leaving comments and the other profiles as written.
 
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -43,25 +46,67 @@ pub struct Profile {
     pub provider: String,
     pub endpoint: Option<String>,
 
+    // --- Download tuning (S3 / Azure / GCS) ---
+    pub max_download_bytes_per_sec: Option<u64>,
+    pub resume_attempts: Option<u32>,
+    pub spool_threshold_bytes: Option<u64>,
+
+    // --- Request retries (S3 / Azure / GCS) ---
+    pub retry_attempts: Option<u32>,
+    pub retry_backoff_ms: Option<u64>,
+    pub retry_max_backoff_ms: Option<u64>,
+
+    // --- Chunked uploads (S3 / Azure / GCS) ---
+    pub multipart_threshold_bytes: Option<u64>,
+    pub multipart_part_bytes: Option<u64>,
+
     // --- S3 specific fields ---
     pub region: Option<String>,
     pub access_key: Option<String>,
     pub secret_key: Option<String>,
     pub path_style: Option<bool>,
+    pub session_token: Option<String>,
+    pub role_arn: Option<String>,
+    pub external_id: Option<String>,
+    pub role_session_name: Option<String>,
 
     //pub use_ssl: Option<bool>, TODO: Implement
 
     // --- Azure specific fields ---
     pub account_name: Option<String>,
     pub connection_string: Option<String>,
+    pub sas_token: Option<String>,
+    pub tenant_id: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub managed_identity: Option<bool>,
 
     // --- GCP specific fields ---
     pub service_account_json: Option<String>,
+    pub application_default_credentials: Option<bool>,
+
+    // --- Kafka specific fields ---
+    pub consumer_group: Option<String>,
 }
 
 /// A collection of profiles, keyed by profile name (from TOML section headers).
 pub type Profiles = HashMap<String, Profile>;
 
+/// Profiles file, relative to the project root.
+pub const PROFILES_FILE: &str = "profiles.toml";
+
+/// Fields that hold credentials. Profile commands write these as `${VAR}`
+/// placeholders rather than literal values.
+pub const SECRET_FIELDS: &[&str] = &[
+    "access_key",
+    "secret_key",
+    "session_token",
+    "connection_string",
+    "sas_token",
+    "client_secret",
+    "service_account_json",
+];
+
 /// Expand an optional string field using a provided updater function.
 ///
 /// Used to replace `${VAR}` placeholders with environment variables.
@@ -82,15 +127,22 @@ where
 /// # Errors
 /// Returns an error if the file cannot be read or parsed.
 pub fn load_profiles() -> Result<Profiles, Box<dyn std::error::Error>> {
-    let content = fs::read_to_string("profiles.toml")?;
+    let content = fs::read_to_string(PROFILES_FILE)?;
     let mut profiles: Profiles = toml::from_str(&content)?;
 
     // Expand environment variables in sensitive fields
     for (_name, profile) in profiles.iter_mut() {
         expand_optional_field(&mut profile.access_key, expand_env_vars);
         expand_optional_field(&mut profile.secret_key, expand_env_vars);
+        expand_optional_field(&mut profile.session_token, expand_env_vars);
+        expand_optional_field(&mut profile.role_arn, expand_env_vars);
+        expand_optional_field(&mut profile.external_id, expand_env_vars);
         expand_optional_field(&mut profile.connection_string, expand_env_vars);
         expand_optional_field(&mut profile.account_name, expand_env_vars);
+        expand_optional_field(&mut profile.sas_token, expand_env_vars);
+        expand_optional_field(&mut profile.tenant_id, expand_env_vars);
+        expand_optional_field(&mut profile.client_id, expand_env_vars);
+        expand_optional_field(&mut profile.client_secret, expand_env_vars);
         expand_optional_field(&mut profile.service_account_json, expand_env_vars);
     }
     Ok(profiles)
@@ -110,3 +162,354 @@ fn expand_env_vars(value: &str) -> String {
         value.to_string()
     }
 }
+
+/// Parse a field value as written on the command line: a TOML value
+/// (`true`, `3`, `"text"`), or a plain string otherwise.
+pub fn parse_field_value(text: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", text))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(text.to_string()))
+}
+
+/// Append a `[name]` section with `fields` (in the given order) to the
+/// text of a profiles file.
+///
+/// # Errors
+/// Returns an error if the profile already exists, the name isn't a bare
+/// TOML key, or the result doesn't parse as profiles.
+pub fn add_profile_section(
+    content: &str,
+    name: &str,
+    fields: &[(String, toml::Value)],
+) -> Result<String, String> {
+    if !is_bare_key(name) {
+        return Err(format!(
+            "Invalid profile name '{}': use letters, digits, '_' or '-'",
+            name
+        ));
+    }
+    if section_lines(content, name).is_some() {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+
+    let mut updated = content.to_string();
+    if !updated.is_empty() {
+        if !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push('\n');
+    }
+    updated.push_str(&format!("[{}]\n", name));
+    for (key, value) in fields {
+        updated.push_str(&field_line(key, value)?);
+        updated.push('\n');
+    }
+    check_profiles(&updated)?;
+    Ok(updated)
+}
+
+/// Set and remove fields of the `[name]` section of a profiles file.
+///
+/// Fields already present are replaced in place; new ones are added after
+/// the section's last field.
+///
+/// # Errors
+/// Returns an error if the profile or a field to unset doesn't exist,
+/// `provider` is unset, or the result doesn't parse as profiles.
+pub fn edit_profile_section(
+    content: &str,
+    name: &str,
+    set: &[(String, toml::Value)],
+    unset: &[String],
+) -> Result<String, String> {
+    let (header, end) =
+        section_lines(content, name).ok_or_else(|| format!("Profile '{}' not found", name))?;
+    if unset.iter().any(|key| key == "provider") {
+        return Err("A profile's provider can't be removed".to_string());
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let section = &lines[header + 1..end];
+    let entries = section_entries(section);
+    for key in unset {
+        if !entries.iter().any(|(entry, _, _)| entry == key) {
+            return Err(format!("Profile '{}' has no field '{}'", name, key));
+        }
+    }
+
+    // Rebuild the section body, replacing or dropping entries by key.
+    let mut body: Vec<String> = Vec::new();
+    let mut last_entry = 0;
+    let mut index = 0;
+    while index < section.len() {
+        let Some((key, start, stop)) = entries.iter().find(|(_, start, _)| *start == index) else {
+            body.push(section[index].to_string());
+            index += 1;
+            continue;
+        };
+        index = *stop;
+        if unset.contains(key) {
+            continue;
+        }
+        match set.iter().find(|(k, _)| k == key) {
+            Some((_, value)) => body.push(field_line(key, value)?),
+            None => body.extend(section[*start..*stop].iter().map(|l| l.to_string())),
+        }
+        last_entry = body.len();
+    }
+    let mut added = Vec::new();
+    for (key, value) in set {
+        if !entries.iter().any(|(entry, _, _)| entry == key) {
+            added.push(field_line(key, value)?);
+        }
+    }
+    body.splice(last_entry..last_entry, added);
+
+    let mut updated: Vec<String> = lines[..=header].iter().map(|l| l.to_string()).collect();
+    updated.extend(body);
+    updated.extend(lines[end..].iter().map(|l| l.to_string()));
+    let updated = updated.join("\n") + "\n";
+    check_profiles(&updated)?;
+    Ok(updated)
+}
+
+/// Remove the `[name]` section from the text of a profiles file.
+///
+/// Comment lines directly above the section's header go with it.
+///
+/// # Errors
+/// Returns an error if the profile doesn't exist.
+pub fn remove_profile_section(content: &str, name: &str) -> Result<String, String> {
+    let (header, end) =
+        section_lines(content, name).ok_or_else(|| format!("Profile '{}' not found", name))?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    let mut start = header;
+    while start > 0 && lines[start - 1].trim_start().starts_with('#') {
+        start -= 1;
+    }
+    // Drop the blank lines that separated the section from its neighbors.
+    while start > 0 && lines[start - 1].trim().is_empty() {
+        start -= 1;
+    }
+    let mut end = end;
+    while end < lines.len() && lines[end].trim().is_empty() {
+        end += 1;
+    }
+    let mut kept: Vec<&str> = lines[..start].to_vec();
+    if start > 0 && end < lines.len() {
+        kept.push("");
+    }
+    kept.extend(&lines[end..]);
+    if kept.is_empty() {
+        return Ok(String::new());
+    }
+    Ok(kept.join("\n") + "\n")
+}
+
+/// Delimiters of TOML multi-line strings.
+const MULTILINE_DELIMITERS: [&str; 2] = ["\"\"\"", "'''"];
+
+/// Whether `name` can be written as a bare TOML key.
+fn is_bare_key(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Render `key = value`.
+fn field_line(key: &str, value: &toml::Value) -> Result<String, String> {
+    if !is_bare_key(key) {
+        return Err(format!("Invalid field name '{}'", key));
+    }
+    Ok(format!("{} = {}", key, value))
+}
+
+/// The name in a `[name]` table header line, if `line` is one.
+fn header_name(line: &str) -> Option<&str> {
+    let inner = line.trim().strip_prefix('[')?;
+    if inner.starts_with('[') {
+        return None;
+    }
+    let inner = &inner[..inner.find(']')?];
+    Some(inner.trim().trim_matches('"'))
+}
+
+/// Line range of the `[name]` section: its header line and the line after
+/// its last entry (trailing blank and comment lines belong to whatever
+/// follows).
+fn section_lines(content: &str, name: &str) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut in_string = false;
+    let mut header = None;
+    for (index, line) in lines.iter().enumerate() {
+        if !in_string && let Some(found) = header_name(line) {
+            if let Some(start) = header {
+                return Some((start, trim_trailing(&lines, start, index)));
+            }
+            if found == name {
+                header = Some(index);
+            }
+        }
+        in_string ^= MULTILINE_DELIMITERS
+            .iter()
+            .any(|delimiter| line.matches(delimiter).count() % 2 == 1);
+    }
+    header.map(|start| (start, trim_trailing(&lines, start, lines.len())))
+}
+
+/// Back `end` up over blank and comment lines, staying after `header`.
+fn trim_trailing(lines: &[&str], header: usize, mut end: usize) -> usize {
+    while end > header + 1 {
+        let line = lines[end - 1].trim();
+        if line.is_empty() || line.starts_with('#') {
+            end -= 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// `(key, first line, line after last)` of each `key = value` entry in a
+/// section body; multi-line strings span several lines.
+fn section_entries(body: &[&str]) -> Vec<(String, usize, usize)> {
+    let mut entries = Vec::new();
+    let mut index = 0;
+    while index < body.len() {
+        let line = body[index].trim();
+        let start = index;
+        index += 1;
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        for delimiter in MULTILINE_DELIMITERS {
+            if let Some(rest) = value.trim().strip_prefix(delimiter)
+                && !rest.contains(delimiter)
+            {
+                while index < body.len() && !body[index].contains(delimiter) {
+                    index += 1;
+                }
+                index = (index + 1).min(body.len());
+            }
+        }
+        entries.push((key.trim().trim_matches('"').to_string(), start, index));
+    }
+    entries
+}
+
+/// Check that edited text still parses as profiles.
+fn check_profiles(content: &str) -> Result<(), String> {
+    toml::from_str::<Profiles>(content)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid profile: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILES: &str = "# Project profiles\n\
+        \n\
+        [raw]\n\
+        provider = \"s3\"\n\
+        region = \"us-east-1\" # primary\n\
+        service_account_json = '''\n\
+        [not_a_section]\n\
+        '''\n\
+        \n\
+        # Azure\n\
+        [lake]\n\
+        provider = \"azure\"\n";
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, toml::Value)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), parse_field_value(value)))
+            .collect()
+    }
+
+    #[test]
+    fn parses_field_values() {
+        assert_eq!(parse_field_value("true"), toml::Value::Boolean(true));
+        assert_eq!(parse_field_value("3"), toml::Value::Integer(3));
+        assert_eq!(
+            parse_field_value("${AWS_SECRET}"),
+            toml::Value::String("${AWS_SECRET}".to_string())
+        );
+        assert_eq!(
+            parse_field_value("us-east-1"),
+            toml::Value::String("us-east-1".to_string())
+        );
+    }
+
+    #[test]
+    fn adds_profile_sections() {
+        let updated = add_profile_section(
+            PROFILES,
+            "gcs",
+            &fields(&[
+                ("provider", "gcs"),
+                ("application_default_credentials", "true"),
+            ]),
+        )
+        .unwrap();
+        assert!(updated.starts_with(PROFILES));
+        assert!(
+            updated.ends_with(
+                "\n\n[gcs]\nprovider = \"gcs\"\napplication_default_credentials = true\n"
+            )
+        );
+
+        let exists = add_profile_section(PROFILES, "lake", &fields(&[("provider", "azure")]));
+        assert_eq!(exists.unwrap_err(), "Profile 'lake' already exists");
+        assert!(add_profile_section(PROFILES, "new", &fields(&[("region", "x")])).is_err());
+        assert!(add_profile_section("", "bad name", &fields(&[("provider", "s3")])).is_err());
+    }
+
+    #[test]
+    fn edits_profile_sections_in_place() {
+        let updated = edit_profile_section(
+            PROFILES,
+            "raw",
+            &fields(&[("region", "eu-west-1"), ("path_style", "true")]),
+            &["service_account_json".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            "# Project profiles\n\
+            \n\
+            [raw]\n\
+            provider = \"s3\"\n\
+            region = \"eu-west-1\"\n\
+            path_style = true\n\
+            \n\
+            # Azure\n\
+            [lake]\n\
+            provider = \"azure\"\n"
+        );
+
+        let missing = edit_profile_section(PROFILES, "lake", &[], &["region".to_string()]);
+        assert_eq!(missing.unwrap_err(), "Profile 'lake' has no field 'region'");
+        assert!(edit_profile_section(PROFILES, "lake", &[], &["provider".to_string()]).is_err());
+        assert!(edit_profile_section(PROFILES, "none", &[], &[]).is_err());
+    }
+
+    #[test]
+    fn removes_profile_sections() {
+        let updated = remove_profile_section(PROFILES, "raw").unwrap();
+        assert_eq!(
+            updated,
+            "# Project profiles\n\n# Azure\n[lake]\nprovider = \"azure\"\n"
+        );
+        let updated = remove_profile_section(&updated, "lake").unwrap();
+        assert_eq!(updated, "# Project profiles\n");
+        assert!(remove_profile_section(PROFILES, "none").is_err());
+    }
+}
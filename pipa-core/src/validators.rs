@@ -9,6 +9,7 @@
 //! - `column`: column-level validators (NotNull, Unique, Pattern, etc.)
 //! - `file`: file-level validators (RowCount, Completeness, etc.)
 //! - `compound`: multi-column validators (CompoundUnique, etc.)
+//! - `custom`: registry for user-defined validators (`rule = "custom"`)
 //!
 //! The engine (`engine/validation.rs`) dispatches to these traits based
 //! on the `ContractType` enum. Each validator is responsible for
@@ -22,6 +23,7 @@ use polars::prelude::*;
 // -----------------------------------------------------------------------------
 pub mod column;
 pub mod compound;
+pub mod custom;
 pub mod file;
 
 // -----------------------------------------------------------------------------
@@ -30,7 +32,7 @@ pub mod file;
 
 /// Standardized result of a validation check.
 ///
-/// - `status`: one of `"pass"`, `"fail"`, or `"skipped"`.
+/// - `status`: one of `"pass"`, `"fail"`, `"skipped"`, or `"aborted"` (a run limit was hit).
 /// - `details`: optional human-readable explanation (e.g., failure reason).
 ///
 /// This struct is converted into a `RuleResult` for audit logging.
@@ -40,6 +42,16 @@ pub struct ValidationReport {
     pub details: Option<String>,
 }
 
+impl ValidationReport {
+    /// Skipped report for numeric rules on a column that isn't numeric.
+    pub fn not_numeric() -> Self {
+        Self {
+            status: "skipped",
+            details: Some("column could not be cast to a numeric type".to_string()),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Core traits
 // -----------------------------------------------------------------------------
@@ -48,7 +60,9 @@ pub struct ValidationReport {
 ///
 /// Implementors validate a single column of a DataFrame against a rule.
 /// Example: `NotNullValidator`, `PatternValidator`.
-pub trait Validator {
+///
+/// Column rules run in parallel, so validators must be `Send + Sync`.
+pub trait Validator: Send + Sync {
     /// Human-readable name of the validator (e.g., `"not_null"`).
     fn name(&self) -> &'static str;
 
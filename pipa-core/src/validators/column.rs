@@ -23,35 +23,45 @@
 // -----------------------------------------------------------------------------
 pub mod boolean;
 pub mod completeness;
+pub mod date_between;
 pub mod date_format;
 pub mod distinctness;
+pub mod drift;
 pub mod in_set;
 pub mod max_length;
 pub mod mean_between;
+pub mod no_future_dates;
 pub mod not_in_set;
 pub mod not_null;
 pub mod outlier_sigma;
 pub mod pattern;
 pub mod range;
+pub mod semantic;
 pub mod stdev_between;
 pub mod type_validator;
 pub mod unique;
+pub mod wasm;
 
 // -----------------------------------------------------------------------------
 // Re-export each validator struct for easy access from the engine
 // -----------------------------------------------------------------------------
 pub use boolean::BooleanValidator;
 pub use completeness::CompletenessValidator;
+pub use date_between::DateBetweenValidator;
 pub use date_format::DateFormatValidator;
 pub use distinctness::DistinctnessValidator;
+pub use drift::DriftValidator;
 pub use in_set::InSetValidator;
 pub use max_length::MaxLengthValidator;
 pub use mean_between::MeanBetweenValidator;
+pub use no_future_dates::NoFutureDatesValidator;
 pub use not_in_set::NotInSetValidator;
 pub use not_null::NotNullValidator;
 pub use outlier_sigma::OutlierSigmaValidator;
 pub use pattern::PatternValidator;
 pub use range::RangeValidator;
+pub use semantic::{SemanticKind, SemanticValidator};
 pub use stdev_between::StdevBetweenValidator;
 pub use type_validator::TypeValidator;
 pub use unique::UniqueValidator;
+pub use wasm::WasmValidator;
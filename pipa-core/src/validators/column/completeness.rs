@@ -7,39 +7,45 @@ pub struct CompletenessValidator {
     pub min_ratio: f64,
 }
 
-impl Validator for CompletenessValidator {
-    fn name(&self) -> &'static str {
-        "Completeness"
-    }
-
-    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
-        let series = df.column(column_name)?;
-
-        let total_count = series.len();
+impl CompletenessValidator {
+    /// Report for a column with `null_count` nulls in `total_count` rows.
+    pub fn report(&self, null_count: usize, total_count: usize) -> ValidationReport {
         if total_count == 0 {
-            return Ok(ValidationReport {
+            return ValidationReport {
                 status: "pass",
                 details: Some("column is empty".to_string()),
-            });
+            };
         }
 
-        let non_null_count = (total_count - series.null_count()) as f64;
+        let non_null_count = (total_count - null_count) as f64;
         let ratio = non_null_count / total_count as f64;
 
         if ratio >= self.min_ratio {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "pass",
                 details: None,
-            })
+            }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "fail",
                 details: Some(format!("ratio={:.2}, min_ratio={}", ratio, self.min_ratio)),
-            })
+            }
         }
     }
 }
 
+impl Validator for CompletenessValidator {
+    fn name(&self) -> &'static str {
+        "Completeness"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?;
+
+        Ok(self.report(series.null_count(), series.len()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
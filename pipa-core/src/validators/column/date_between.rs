@@ -0,0 +1,279 @@
+// src/validators/column/date_between.rs
+
+use crate::validators::{ValidationReport, ValidationResult, Validator};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+/// Formats tried, in order, for values and literal bounds when the rule
+/// names no format and the column declares no `date_format`.
+const DEFAULT_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d"];
+
+pub struct DateBetweenValidator {
+    pub min: Option<NaiveDateTime>,
+    pub max: Option<NaiveDateTime>,
+    /// chrono format for String columns; `None` tries ISO-8601 forms.
+    pub format: Option<String>,
+}
+
+impl DateBetweenValidator {
+    /// Report for `bad_count` values outside `[min, max]`.
+    pub fn report(&self, bad_count: usize) -> ValidationReport {
+        if bad_count > 0 {
+            let bound = |b: Option<NaiveDateTime>| b.map_or("none".to_string(), |b| b.to_string());
+            ValidationReport {
+                status: "fail",
+                details: Some(format!(
+                    "bad_count={}, min={}, max={}",
+                    bad_count,
+                    bound(self.min),
+                    bound(self.max)
+                )),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
+}
+
+impl Validator for DateBetweenValidator {
+    fn name(&self) -> &'static str {
+        "DateBetween"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?.as_materialized_series();
+        let Some(values) = column_datetimes(series, self.format.as_deref())? else {
+            return Ok(skipped());
+        };
+        let bad_count = values
+            .into_iter()
+            .flatten()
+            .filter(|v| {
+                self.min.is_some_and(|min| *v < min) || self.max.is_some_and(|max| *v > max)
+            })
+            .count();
+        Ok(self.report(bad_count))
+    }
+}
+
+/// The report for a column that holds neither dates nor strings.
+pub(crate) fn skipped() -> ValidationReport {
+    ValidationReport {
+        status: "skipped",
+        details: Some("column is not a string or date type".to_string()),
+    }
+}
+
+/// The values of `series` as date-times, or `None` if its dtype can't hold
+/// dates.
+///
+/// `Date` and `Datetime` columns are read as-is (in UTC); String values
+/// are parsed with `format`. Nulls and values that don't parse come back
+/// as `None`, leaving malformed values to `date_format`.
+pub(crate) fn column_datetimes(
+    series: &Series,
+    format: Option<&str>,
+) -> ValidationResult<Option<Vec<Option<NaiveDateTime>>>> {
+    match series.dtype() {
+        DataType::String => Ok(Some(
+            series
+                .str()?
+                .into_iter()
+                .map(|v| v.and_then(|v| parse_date_time(v, format)))
+                .collect(),
+        )),
+        DataType::Date | DataType::Datetime(_, _) => {
+            let millis = series
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?
+                .cast(&DataType::Int64)?;
+            Ok(Some(
+                millis
+                    .i64()?
+                    .into_iter()
+                    .map(|v| v.and_then(DateTime::from_timestamp_millis))
+                    .map(|v| v.map(|v| v.naive_utc()))
+                    .collect(),
+            ))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Parse `value` with `format` (or the ISO-8601 defaults); a date-only
+/// value is taken as midnight.
+pub fn parse_date_time(value: &str, format: Option<&str>) -> Option<NaiveDateTime> {
+    let formats = match &format {
+        Some(format) => std::slice::from_ref(format),
+        None => DEFAULT_FORMATS,
+    };
+    formats.iter().find_map(|format| {
+        NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, format)
+                    .ok()
+                    .map(|d| d.and_time(Default::default()))
+            })
+    })
+}
+
+/// Resolve a `date_between` bound against `now`.
+///
+/// A bound is either `now`, optionally shifted by an amount in seconds,
+/// minutes, hours, days, or weeks (`now-7d`, `now+12h`), or a literal
+/// date parsed like the column's values.
+pub fn resolve_date_bound(
+    bound: &str,
+    format: Option<&str>,
+    now: NaiveDateTime,
+) -> Result<NaiveDateTime, String> {
+    let compact: String = bound.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(offset) = compact.strip_prefix("now") else {
+        return parse_date_time(bound.trim(), format)
+            .or_else(|| parse_date_time(bound.trim(), None))
+            .ok_or_else(|| {
+                format!(
+                    "invalid date bound '{}' (expected a date, 'now', or e.g. 'now-7d')",
+                    bound
+                )
+            });
+    };
+    if offset.is_empty() {
+        return Ok(now);
+    }
+    let invalid = || {
+        format!(
+            "invalid relative date '{}' (expected e.g. now-7d, now+12h; units s, m, h, d, w)",
+            bound
+        )
+    };
+    let (sign, amount) = if let Some(amount) = offset.strip_prefix('-') {
+        (-1, amount)
+    } else if let Some(amount) = offset.strip_prefix('+') {
+        (1, amount)
+    } else {
+        return Err(invalid());
+    };
+    let split = amount.char_indices().last().map_or(0, |(i, _)| i);
+    let count: i64 = amount[..split].parse().map_err(|_| invalid())?;
+    let shift = match &amount[split..] {
+        "s" => Duration::try_seconds(count),
+        "m" => Duration::try_minutes(count),
+        "h" => Duration::try_hours(count),
+        "d" => Duration::try_days(count),
+        "w" => Duration::try_weeks(count),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    now.checked_add_signed(shift * sign).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> NaiveDateTime {
+        parse_date_time(text, None).unwrap()
+    }
+
+    fn make_str_df(values: &[Option<&str>]) -> DataFrame {
+        let s = Series::new("col".into(), values.to_vec());
+        DataFrame::new(vec![s.into()]).unwrap()
+    }
+
+    #[test]
+    fn resolves_literal_and_relative_bounds() {
+        let now = at("2025-01-15 12:00:00");
+        assert_eq!(resolve_date_bound("now", None, now).unwrap(), now);
+        assert_eq!(
+            resolve_date_bound("now-7d", None, now).unwrap(),
+            at("2025-01-08 12:00:00")
+        );
+        assert_eq!(
+            resolve_date_bound("now + 90m", None, now).unwrap(),
+            at("2025-01-15 13:30:00")
+        );
+        assert_eq!(
+            resolve_date_bound("15/01/2024", Some("%d/%m/%Y"), now).unwrap(),
+            at("2024-01-15")
+        );
+        assert_eq!(
+            resolve_date_bound("2024-01-15", Some("%d/%m/%Y"), now).unwrap(),
+            at("2024-01-15")
+        );
+        assert!(resolve_date_bound("now-7y", None, now).is_err());
+        assert!(resolve_date_bound("now7d", None, now).is_err());
+        assert!(resolve_date_bound("now-7é", None, now).is_err());
+        assert!(resolve_date_bound("last tuesday", None, now).is_err());
+    }
+
+    #[test]
+    fn fails_when_values_fall_outside_the_bounds() {
+        let df = make_str_df(&[
+            Some("2024-01-01"),
+            Some("2024-06-30 08:00:00"),
+            Some("2025-02-01"),
+            Some("garbage"),
+            None,
+        ]);
+        let validator = DateBetweenValidator {
+            min: Some(at("2024-01-01")),
+            max: Some(at("2024-12-31")),
+            format: None,
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().starts_with("bad_count=1,"));
+
+        let open_ended = DateBetweenValidator {
+            min: Some(at("2023-01-01")),
+            max: None,
+            format: None,
+        };
+        assert_eq!(open_ended.validate(&df, "col").unwrap().status, "pass");
+    }
+
+    #[test]
+    fn parses_with_the_given_format() {
+        let df = make_str_df(&[Some("31/12/2024"), Some("01/01/2025")]);
+        let validator = DateBetweenValidator {
+            min: None,
+            max: Some(at("2024-12-31")),
+            format: Some("%d/%m/%Y".to_string()),
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().contains("bad_count=1"));
+    }
+
+    #[test]
+    fn reads_date_columns_natively() {
+        let dates = Series::new("col".into(), &["2024-03-01", "2024-05-01"])
+            .cast(&DataType::Date)
+            .unwrap();
+        let df = DataFrame::new(vec![dates.into()]).unwrap();
+        let validator = DateBetweenValidator {
+            min: Some(at("2024-04-01")),
+            max: None,
+            format: None,
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().contains("bad_count=1"));
+    }
+
+    #[test]
+    fn skips_on_numeric_column() {
+        let s = Series::new("col".into(), &[1, 2, 3]);
+        let df = DataFrame::new(vec![s.into()]).unwrap();
+        let validator = DateBetweenValidator {
+            min: None,
+            max: None,
+            format: None,
+        };
+        assert_eq!(validator.validate(&df, "col").unwrap().status, "skipped");
+    }
+}
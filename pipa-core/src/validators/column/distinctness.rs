@@ -7,36 +7,46 @@ pub struct DistinctnessValidator {
     pub min_ratio: f64,
 }
 
-impl Validator for DistinctnessValidator {
-    fn name(&self) -> &'static str {
-        "Distinctness"
-    }
-
-    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
-        let series = df.column(column_name)?;
-
-        let total_count = series.len();
+impl DistinctnessValidator {
+    /// Report for a column with `unique_count` distinct values in `total_count` rows.
+    pub fn report(&self, unique_count: usize, total_count: usize) -> ValidationReport {
         if total_count == 0 {
-            return Ok(ValidationReport {
+            return ValidationReport {
                 status: "pass",
                 details: Some("column is empty".to_string()),
-            });
+            };
         }
 
-        let unique_count = series.n_unique()? as f64;
-        let ratio = unique_count / total_count as f64;
+        let ratio = unique_count as f64 / total_count as f64;
 
         if ratio >= self.min_ratio {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "pass",
                 details: None,
-            })
+            }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "fail",
                 details: Some(format!("ratio={:.2}, min_ratio={}", ratio, self.min_ratio)),
-            })
+            }
+        }
+    }
+}
+
+impl Validator for DistinctnessValidator {
+    fn name(&self) -> &'static str {
+        "Distinctness"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?;
+
+        let total_count = series.len();
+        if total_count == 0 {
+            return Ok(self.report(0, 0));
         }
+
+        Ok(self.report(series.n_unique()?, total_count))
     }
 }
 
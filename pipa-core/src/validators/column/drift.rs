@@ -0,0 +1,156 @@
+// src/validators/column/drift.rs
+
+use crate::contracts::{DriftMetric, DriftReference};
+use crate::history::ColumnStats;
+use crate::validators::{ValidationReport, ValidationResult, Validator};
+use polars::prelude::*;
+
+/// Compares a column statistic with the same statistic from a stored run.
+///
+/// `reference` is resolved by the engine from the run-history store; when
+/// there is no reference yet (first run, no baseline) the rule is skipped.
+pub struct DriftValidator {
+    pub metric: DriftMetric,
+    pub max_change: f64,
+    pub against: DriftReference,
+    pub reference: Option<f64>,
+}
+
+impl DriftValidator {
+    /// Report for the `current` value of the metric (`None` when the
+    /// column doesn't have it, e.g. the mean of a text column).
+    pub fn report(&self, current: Option<f64>) -> ValidationReport {
+        let Some(reference) = self.reference else {
+            return ValidationReport {
+                status: "skipped",
+                details: Some(format!(
+                    "no {} run to compare {} against",
+                    self.against.as_str(),
+                    self.metric.as_str()
+                )),
+            };
+        };
+
+        let Some(current) = current else {
+            return ValidationReport {
+                status: "skipped",
+                details: Some(format!(
+                    "{} is not available for this column",
+                    self.metric.as_str()
+                )),
+            };
+        };
+
+        let change = if self.metric.is_ratio() || reference == 0.0 {
+            (current - reference).abs()
+        } else {
+            (current - reference).abs() / reference.abs()
+        };
+
+        let details = format!(
+            "metric={}, current={:.4}, {}={:.4}, change={:.4}, max_change={}",
+            self.metric.as_str(),
+            current,
+            self.against.as_str(),
+            reference,
+            change,
+            self.max_change
+        );
+        ValidationReport {
+            status: if change <= self.max_change {
+                "pass"
+            } else {
+                "fail"
+            },
+            details: Some(details),
+        }
+    }
+}
+
+impl Validator for DriftValidator {
+    fn name(&self) -> &'static str {
+        "Drift"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?.as_materialized_series();
+
+        if self.reference.is_none() {
+            return Ok(self.report(None));
+        }
+
+        let current = match self.metric {
+            DriftMetric::RowCount => Some(df.height() as f64),
+            metric => ColumnStats::from_series(series)?.metric(metric),
+        };
+
+        Ok(self.report(current))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_df(values: &[f64]) -> DataFrame {
+        let s = Series::new("col".into(), values.to_vec());
+        DataFrame::new(vec![s.into()]).unwrap()
+    }
+
+    fn drift(metric: DriftMetric, max_change: f64, reference: Option<f64>) -> DriftValidator {
+        DriftValidator {
+            metric,
+            max_change,
+            against: DriftReference::Previous,
+            reference,
+        }
+    }
+
+    #[test]
+    fn passes_within_relative_change() {
+        let df = make_df(&[10.0, 11.0, 12.0]); // mean = 11.0
+        let report = drift(DriftMetric::Mean, 0.2, Some(10.0))
+            .validate(&df, "col")
+            .unwrap();
+        assert_eq!(report.status, "pass");
+    }
+
+    #[test]
+    fn fails_when_mean_shifts() {
+        let df = make_df(&[20.0, 21.0, 22.0]); // mean = 21.0
+        let report = drift(DriftMetric::Mean, 0.2, Some(10.0))
+            .validate(&df, "col")
+            .unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().contains("change=1.1000"));
+    }
+
+    #[test]
+    fn compares_ratios_by_absolute_difference() {
+        let s = Series::new("col".into(), &[Some(1.0), None, None, Some(2.0)]);
+        let df = DataFrame::new(vec![s.into()]).unwrap(); // null_ratio = 0.5
+        let report = drift(DriftMetric::NullRatio, 0.1, Some(0.45))
+            .validate(&df, "col")
+            .unwrap();
+        assert_eq!(report.status, "pass");
+    }
+
+    #[test]
+    fn compares_row_count() {
+        let df = make_df(&[1.0, 2.0]);
+        let report = drift(DriftMetric::RowCount, 0.5, Some(10.0))
+            .validate(&df, "col")
+            .unwrap();
+        assert_eq!(report.status, "fail");
+    }
+
+    #[test]
+    fn skips_without_reference() {
+        let df = make_df(&[1.0]);
+        let report = drift(DriftMetric::Mean, 0.1, None)
+            .validate(&df, "col")
+            .unwrap();
+        assert_eq!(report.status, "skipped");
+        assert!(report.details.unwrap().contains("no previous run"));
+    }
+}
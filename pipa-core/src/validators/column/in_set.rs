@@ -8,6 +8,23 @@ pub struct InSetValidator {
     pub values: HashSet<String>,
 }
 
+impl InSetValidator {
+    /// Report for `bad_count` values outside the set.
+    pub fn report(&self, bad_count: usize) -> ValidationReport {
+        if bad_count > 0 {
+            ValidationReport {
+                status: "fail",
+                details: Some(format!("bad_count={}", bad_count)),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
+}
+
 impl Validator for InSetValidator {
     fn name(&self) -> &'static str {
         "InSet"
@@ -24,33 +41,17 @@ impl Validator for InSetValidator {
         }
 
         let allowed_values: Vec<String> = self.values.iter().cloned().collect();
+        let allowed = Series::new("allowed".into(), allowed_values);
 
-        // Use the working pattern from our test with lazy DataFrame operations
-        let result = df
-            .clone()
-            .lazy()
-            .select([col(column_name)
-                .is_in(
-                    lit(Series::new("allowed".into(), allowed_values)).implode(),
-                    false,
-                )
-                .not()])
-            .collect()?;
-
-        let bad_series = result.column(column_name)?;
-        let bad_count: u32 = bad_series.bool()?.sum().unwrap_or(0);
+        // Null values are never counted; `is_in` leaves them null.
+        let found = is_in(
+            series.as_materialized_series(),
+            &allowed.implode()?.into_series(),
+            false,
+        )?;
+        let bad_count = (!&found).sum().unwrap_or(0);
 
-        if bad_count > 0 {
-            Ok(ValidationReport {
-                status: "fail",
-                details: Some(format!("bad_count={}", bad_count)),
-            })
-        } else {
-            Ok(ValidationReport {
-                status: "pass",
-                details: None,
-            })
-        }
+        Ok(self.report(bad_count as usize))
     }
 }
 
@@ -7,6 +7,26 @@ pub struct MaxLengthValidator {
     pub value: usize,
 }
 
+impl MaxLengthValidator {
+    /// Report for `bad_count` values longer than `value` characters.
+    pub fn report(&self, bad_count: usize) -> ValidationReport {
+        if bad_count > 0 {
+            ValidationReport {
+                status: "fail",
+                details: Some(format!(
+                    "bad_count={}, max_length={}",
+                    bad_count, self.value
+                )),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
+}
+
 impl Validator for MaxLengthValidator {
     fn name(&self) -> &'static str {
         "MaxLength"
@@ -16,25 +36,20 @@ impl Validator for MaxLengthValidator {
         let series = df.column(column_name)?;
 
         if let Ok(ca) = series.str() {
-            // Correct method name is str_len_chars
-            let lengths = ca.str_len_chars();
-            let mask = lengths.gt(self.value as u32);
-            let bad_count = mask.sum().unwrap_or(0);
-
-            if bad_count > 0 {
-                Ok(ValidationReport {
-                    status: "fail",
-                    details: Some(format!(
-                        "bad_count={}, max_length={}",
-                        bad_count, self.value
-                    )),
-                })
+            // A value can't have more characters than bytes, so only values
+            // over the limit in bytes need their characters counted.
+            let over_bytes = ca.str_len_bytes().gt(self.value as u32);
+            let bad_count = if over_bytes.any() {
+                ca.filter(&over_bytes)?
+                    .str_len_chars()
+                    .gt(self.value as u32)
+                    .sum()
+                    .unwrap_or(0)
             } else {
-                Ok(ValidationReport {
-                    status: "pass",
-                    details: None,
-                })
-            }
+                0
+            };
+
+            Ok(self.report(bad_count as usize))
         } else {
             Ok(ValidationReport {
                 status: "skipped",
@@ -71,6 +86,15 @@ mod tests {
         assert!(report.details.unwrap().contains("bad_count=1"));
     }
 
+    #[test]
+    fn counts_characters_not_bytes() {
+        let df = make_str_df(&[Some("héé"), Some("日本語"), Some("abcd")]);
+        let validator = MaxLengthValidator { value: 3 };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().contains("bad_count=1"));
+    }
+
     #[test]
     fn ignores_null_values() {
         let df = make_str_df(&[Some("ok"), None, Some("fine")]);
@@ -8,50 +8,50 @@ pub struct MeanBetweenValidator {
     pub max: f64,
 }
 
-impl Validator for MeanBetweenValidator {
-    fn name(&self) -> &'static str {
-        "MeanBetween"
-    }
-
-    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
-        let series = df.column(column_name)?;
-
-        let f64_series = match series.strict_cast(&DataType::Float64) {
-            Ok(s) => s,
-            Err(_) => {
-                return Ok(ValidationReport {
-                    status: "skipped",
-                    details: Some("column could not be cast to a numeric type".to_string()),
-                });
-            }
-        };
-
-        let values = f64_series.f64()?;
-
-        if let Some(mean) = values.mean() {
+impl MeanBetweenValidator {
+    /// Report for an observed `mean` (`None` when the column has no values).
+    pub fn report(&self, mean: Option<f64>) -> ValidationReport {
+        if let Some(mean) = mean {
             if mean >= self.min && mean <= self.max {
-                Ok(ValidationReport {
+                ValidationReport {
                     status: "pass",
                     details: None,
-                })
+                }
             } else {
-                Ok(ValidationReport {
+                ValidationReport {
                     status: "fail",
                     details: Some(format!(
                         "observed_mean={:.2}, min={}, max={}",
                         mean, self.min, self.max
                     )),
-                })
+                }
             }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "skipped",
                 details: Some("column contains no non-null values".to_string()),
-            })
+            }
         }
     }
 }
 
+impl Validator for MeanBetweenValidator {
+    fn name(&self) -> &'static str {
+        "MeanBetween"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?;
+
+        let f64_series = match series.strict_cast(&DataType::Float64) {
+            Ok(s) => s,
+            Err(_) => return Ok(ValidationReport::not_numeric()),
+        };
+
+        Ok(self.report(f64_series.f64()?.mean()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
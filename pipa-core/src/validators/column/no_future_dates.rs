@@ -0,0 +1,96 @@
+// src/validators/column/no_future_dates.rs
+
+use super::date_between::{column_datetimes, skipped};
+use crate::validators::{ValidationReport, ValidationResult, Validator};
+use chrono::NaiveDateTime;
+use polars::prelude::*;
+
+pub struct NoFutureDatesValidator {
+    /// The run's current time (UTC); later values fail.
+    pub now: NaiveDateTime,
+    /// chrono format for String columns; `None` tries ISO-8601 forms.
+    pub format: Option<String>,
+}
+
+impl Validator for NoFutureDatesValidator {
+    fn name(&self) -> &'static str {
+        "NoFutureDates"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?.as_materialized_series();
+        let Some(values) = column_datetimes(series, self.format.as_deref())? else {
+            return Ok(skipped());
+        };
+        let future: Vec<NaiveDateTime> = values
+            .into_iter()
+            .flatten()
+            .filter(|v| *v > self.now)
+            .collect();
+
+        match future.iter().max() {
+            Some(latest) => Ok(ValidationReport {
+                status: "fail",
+                details: Some(format!(
+                    "future_count={}, latest={}, now={}",
+                    future.len(),
+                    latest,
+                    self.now.format("%Y-%m-%d %H:%M:%S")
+                )),
+            }),
+            None => Ok(ValidationReport {
+                status: "pass",
+                details: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::column::date_between::parse_date_time;
+
+    fn make_str_df(values: &[Option<&str>]) -> DataFrame {
+        let s = Series::new("col".into(), values.to_vec());
+        DataFrame::new(vec![s.into()]).unwrap()
+    }
+
+    fn validator(format: Option<&str>) -> NoFutureDatesValidator {
+        NoFutureDatesValidator {
+            now: parse_date_time("2025-01-15 12:00:00", None).unwrap(),
+            format: format.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn fails_on_values_after_now() {
+        let df = make_str_df(&[
+            Some("2025-01-15 11:59:59"),
+            Some("2025-01-15 12:00:01"),
+            Some("2030-01-01 00:00:00"),
+            None,
+        ]);
+        let report = validator(None).validate(&df, "col").unwrap();
+        assert_eq!(report.status, "fail");
+        let details = report.details.unwrap();
+        assert!(details.contains("future_count=2"));
+        assert!(details.contains("latest=2030-01-01 00:00:00"));
+    }
+
+    #[test]
+    fn passes_when_nothing_is_in_the_future() {
+        let df = make_str_df(&[Some("14.01.2025"), Some("not a date"), None]);
+        let report = validator(Some("%d.%m.%Y")).validate(&df, "col").unwrap();
+        assert_eq!(report.status, "pass");
+        assert!(report.details.is_none());
+    }
+
+    #[test]
+    fn skips_on_numeric_column() {
+        let s = Series::new("col".into(), &[1.0, 2.0]);
+        let df = DataFrame::new(vec![s.into()]).unwrap();
+        let report = validator(None).validate(&df, "col").unwrap();
+        assert_eq!(report.status, "skipped");
+    }
+}
@@ -8,6 +8,23 @@ pub struct NotInSetValidator {
     pub values: HashSet<String>,
 }
 
+impl NotInSetValidator {
+    /// Report for `bad_count` values in the disallowed set.
+    pub fn report(&self, bad_count: usize) -> ValidationReport {
+        if bad_count > 0 {
+            ValidationReport {
+                status: "fail",
+                details: Some(format!("bad_count={}", bad_count)),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
+}
+
 impl Validator for NotInSetValidator {
     fn name(&self) -> &'static str {
         "NotInSet"
@@ -24,32 +41,17 @@ impl Validator for NotInSetValidator {
         }
 
         let disallowed_values: Vec<String> = self.values.iter().cloned().collect();
+        let disallowed = Series::new("disallowed".into(), disallowed_values);
 
-        // Use the working lazy DataFrame pattern
-        // For NotInSet, we want to find values that ARE in the disallowed set (no .not())
-        let result = df
-            .clone()
-            .lazy()
-            .select([col(column_name).is_in(
-                lit(Series::new("disallowed".into(), disallowed_values)).implode(),
-                false,
-            )])
-            .collect()?;
-
-        let bad_series = result.column(column_name)?;
-        let bad_count: u32 = bad_series.bool()?.sum().unwrap_or(0);
+        // Null values are never counted; `is_in` leaves them null.
+        let found = is_in(
+            series.as_materialized_series(),
+            &disallowed.implode()?.into_series(),
+            false,
+        )?;
+        let bad_count = found.sum().unwrap_or(0);
 
-        if bad_count > 0 {
-            Ok(ValidationReport {
-                status: "fail",
-                details: Some(format!("bad_count={}", bad_count)),
-            })
-        } else {
-            Ok(ValidationReport {
-                status: "pass",
-                details: None,
-            })
-        }
+        Ok(self.report(bad_count as usize))
     }
 }
 
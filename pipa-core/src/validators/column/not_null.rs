@@ -3,6 +3,23 @@ use polars::prelude::*;
 
 pub struct NotNullValidator;
 
+impl NotNullValidator {
+    /// Report for a column with `null_count` nulls.
+    pub fn report(&self, null_count: usize) -> ValidationReport {
+        if null_count > 0 {
+            ValidationReport {
+                status: "fail",
+                details: Some(format!("null_count={}", null_count)),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
+}
+
 impl Validator for NotNullValidator {
     fn name(&self) -> &'static str {
         "NotNull"
@@ -13,17 +30,7 @@ impl Validator for NotNullValidator {
 
         let null_count = series.null_count();
 
-        if null_count > 0 {
-            Ok(ValidationReport {
-                status: "fail",
-                details: Some(format!("null_count={}", null_count)),
-            })
-        } else {
-            Ok(ValidationReport {
-                status: "pass",
-                details: None,
-            })
-        }
+        Ok(self.report(null_count))
     }
 }
 
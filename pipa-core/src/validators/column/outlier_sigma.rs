@@ -7,6 +7,58 @@ pub struct OutlierSigmaValidator {
     pub sigma: f64,
 }
 
+impl OutlierSigmaValidator {
+    /// The column mean and the distance from it beyond which a value is an
+    /// outlier, or the final report when no value can be one.
+    pub fn bounds(
+        &self,
+        mean: Option<f64>,
+        std_dev: Option<f64>,
+    ) -> Result<(f64, f64), ValidationReport> {
+        let (Some(mean), Some(std_dev)) = (mean, std_dev) else {
+            return Err(ValidationReport {
+                status: "skipped",
+                details: Some("mean or standard deviation could not be calculated".to_string()),
+            });
+        };
+        if std_dev == 0.0 {
+            return Err(ValidationReport {
+                status: "pass",
+                details: Some("standard deviation is zero; no outliers possible".to_string()),
+            });
+        }
+        Ok((mean, self.sigma * std_dev))
+    }
+
+    /// Report for `outlier_count` values outside the bounds.
+    pub fn report(&self, outlier_count: usize) -> ValidationReport {
+        if outlier_count > 0 {
+            ValidationReport {
+                status: "fail",
+                details: Some(format!("outliers={}, sigma={}", outlier_count, self.sigma)),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
+
+    /// Number of values further than `threshold` from `mean`.
+    pub fn count_outliers(
+        values: &Float64Chunked,
+        mean: f64,
+        threshold: f64,
+    ) -> ValidationResult<usize> {
+        let deviation = (values - mean).into_series();
+
+        // Handle the Result from .gt() before calling .sum()
+        let mask = abs(&deviation)?.gt(threshold);
+        Ok(mask?.sum().unwrap_or(0) as usize)
+    }
+}
+
 impl Validator for OutlierSigmaValidator {
     fn name(&self) -> &'static str {
         "OutlierSigma"
@@ -17,51 +69,20 @@ impl Validator for OutlierSigmaValidator {
 
         let f64_series = match series.strict_cast(&DataType::Float64) {
             Ok(s) => s,
-            Err(_) => {
-                return Ok(ValidationReport {
-                    status: "skipped",
-                    details: Some("column could not be cast to a numeric type".to_string()),
-                });
-            }
+            Err(_) => return Ok(ValidationReport::not_numeric()),
         };
 
         let values = f64_series.f64()?;
 
-        if let (Some(mean), Some(std_dev)) = (values.mean(), values.std(1)) {
-            if std_dev == 0.0 {
-                return Ok(ValidationReport {
-                    status: "pass",
-                    details: Some("standard deviation is zero; no outliers possible".to_string()),
-                });
-            }
-
-            let deviation = (values - mean).into_series();
-            let threshold = self.sigma * std_dev;
-
-            let mask = abs(&deviation)?.gt(threshold);
-
-            // Handle the Result from .gt() before calling .sum()
-            let outlier_count = mask?.sum().unwrap_or(0);
-
-            if outlier_count > 0 {
-                Ok(ValidationReport {
-                    status: "fail",
-                    details: Some(format!("outliers={}, sigma={}", outlier_count, self.sigma)),
-                })
-            } else {
-                Ok(ValidationReport {
-                    status: "pass",
-                    details: None,
-                })
+        match self.bounds(values.mean(), values.std(1)) {
+            Ok((mean, threshold)) => {
+                Ok(self.report(Self::count_outliers(values, mean, threshold)?))
             }
-        } else {
-            Ok(ValidationReport {
-                status: "skipped",
-                details: Some("mean or standard deviation could not be calculated".to_string()),
-            })
+            Err(report) => Ok(report),
         }
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,10 +2,42 @@
 
 use crate::validators::{ValidationReport, ValidationResult, Validator};
 use polars::prelude::*;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 
 pub struct PatternValidator {
     pub pattern: String,
+    /// Maximum compiled regex size in bytes (`[limits] max_regex_size`).
+    pub size_limit: Option<usize>,
+    /// `pattern` already compiled (from a cached `ExecutionPlan`).
+    pub compiled: Option<Regex>,
+}
+
+impl PatternValidator {
+    /// Compile `pattern` within `size_limit`.
+    pub fn compile(&self) -> Result<Regex, regex::Error> {
+        let mut builder = RegexBuilder::new(&self.pattern);
+        if let Some(limit) = self.size_limit {
+            builder.size_limit(limit);
+        }
+        builder.build()
+    }
+}
+
+impl PatternValidator {
+    /// Report for `bad_count` values not matching the pattern.
+    pub fn report(&self, bad_count: usize) -> ValidationReport {
+        if bad_count > 0 {
+            ValidationReport {
+                status: "fail",
+                details: Some(format!("bad_count={}, pattern={}", bad_count, self.pattern)),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
 }
 
 impl Validator for PatternValidator {
@@ -16,31 +48,27 @@ impl Validator for PatternValidator {
     fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
         let series = df.column(column_name)?;
 
-        let re = Regex::new(&self.pattern)?;
-
-        if let Ok(utf8_chunked) = series.str() {
-            let bad_count = utf8_chunked
-                .into_iter()
-                .filter(|opt_val| {
-                    if let Some(val) = opt_val {
-                        !re.is_match(val)
-                    } else {
-                        false // Null values don't fail a pattern match
-                    }
-                })
-                .count();
-
-            if bad_count > 0 {
-                Ok(ValidationReport {
-                    status: "fail",
-                    details: Some(format!("bad_count={}, pattern={}", bad_count, self.pattern)),
-                })
-            } else {
-                Ok(ValidationReport {
-                    status: "pass",
-                    details: None,
-                })
+        let re = match self.compiled.clone().map_or_else(|| self.compile(), Ok) {
+            Ok(re) => re,
+            Err(regex::Error::CompiledTooBig(limit)) => {
+                return Ok(ValidationReport {
+                    status: "aborted",
+                    details: Some(format!(
+                        "limit exceeded: compiled regex larger than max_regex_size={}",
+                        limit
+                    )),
+                });
             }
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Ok(ca) = series.str() {
+            // Matched in Polars' string kernel; null values stay null and
+            // don't fail a pattern match.
+            let matched = ca.contains(re.as_str(), true)?;
+            let bad_count = (!&matched).sum().unwrap_or(0);
+
+            Ok(self.report(bad_count as usize))
         } else {
             Ok(ValidationReport {
                 status: "skipped",
@@ -69,6 +97,8 @@ mod tests {
         let df = make_str_df(&[Some("abc"), Some("abd"), Some("abe")]);
         let validator = PatternValidator {
             pattern: r"^ab.$".to_string(),
+            size_limit: None,
+            compiled: None,
         };
         let report = validator.validate(&df, "col").unwrap();
         assert_eq!(report.status, "pass");
@@ -80,6 +110,8 @@ mod tests {
         let df = make_str_df(&[Some("abc"), Some("xyz"), Some("abd")]);
         let validator = PatternValidator {
             pattern: r"^ab.$".to_string(),
+            size_limit: None,
+            compiled: None,
         };
         let report = validator.validate(&df, "col").unwrap();
         assert_eq!(report.status, "fail");
@@ -91,6 +123,8 @@ mod tests {
         let df = make_str_df(&[Some("abc"), None, Some("abd")]);
         let validator = PatternValidator {
             pattern: r"^ab.$".to_string(),
+            size_limit: None,
+            compiled: None,
         };
         let report = validator.validate(&df, "col").unwrap();
         assert_eq!(report.status, "pass");
@@ -102,6 +136,8 @@ mod tests {
         let df = DataFrame::new(vec![s.into()]).unwrap();
         let validator = PatternValidator {
             pattern: r"^ab.$".to_string(),
+            size_limit: None,
+            compiled: None,
         };
         let report = validator.validate(&df, "col").unwrap();
         assert_eq!(report.status, "pass");
@@ -112,9 +148,36 @@ mod tests {
         let df = make_int_df(&[1, 2, 3]);
         let validator = PatternValidator {
             pattern: r"^ab.$".to_string(),
+            size_limit: None,
+            compiled: None,
         };
         let report = validator.validate(&df, "col").unwrap();
         assert_eq!(report.status, "skipped");
         assert!(report.details.unwrap().contains("not a string"));
     }
+
+    #[test]
+    fn aborts_when_regex_exceeds_size_limit() {
+        let df = make_str_df(&[Some("abc")]);
+        let validator = PatternValidator {
+            pattern: r"\w{500}".to_string(),
+            size_limit: Some(1024),
+            compiled: None,
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "aborted");
+        assert!(report.details.unwrap().contains("limit exceeded"));
+    }
+
+    #[test]
+    fn uses_precompiled_regex() {
+        let df = make_str_df(&[Some("abc"), Some("xyz")]);
+        let validator = PatternValidator {
+            pattern: r"^ab.$".to_string(),
+            size_limit: None,
+            compiled: Some(Regex::new(r"^[a-z]{3}$").unwrap()),
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "pass");
+    }
 }
@@ -8,6 +8,26 @@ pub struct RangeValidator {
     pub max: i64,
 }
 
+impl RangeValidator {
+    /// Report for `bad_count` values outside `[min, max]`.
+    pub fn report(&self, bad_count: usize) -> ValidationReport {
+        if bad_count > 0 {
+            ValidationReport {
+                status: "fail",
+                details: Some(format!(
+                    "bad_count={}, min={}, max={}",
+                    bad_count, self.min, self.max
+                )),
+            }
+        } else {
+            ValidationReport {
+                status: "pass",
+                details: None,
+            }
+        }
+    }
+}
+
 impl Validator for RangeValidator {
     fn name(&self) -> &'static str {
         "Range"
@@ -21,20 +41,7 @@ impl Validator for RangeValidator {
             let mask = values.lt(self.min) | values.gt(self.max);
             let bad_count = mask.sum().unwrap_or(0);
 
-            if bad_count > 0 {
-                Ok(ValidationReport {
-                    status: "fail",
-                    details: Some(format!(
-                        "bad_count={}, min={}, max={}",
-                        bad_count, self.min, self.max
-                    )),
-                })
-            } else {
-                Ok(ValidationReport {
-                    status: "pass",
-                    details: None,
-                })
-            }
+            Ok(self.report(bad_count as usize))
         } else {
             Ok(ValidationReport {
                 status: "skipped",
@@ -0,0 +1,274 @@
+// src/validators/column/semantic.rs
+
+use crate::validators::{ValidationReport, ValidationResult, Validator};
+use polars::prelude::*;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use url::Url;
+
+/// A well-known string format checked without a user-written regex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemanticKind {
+    /// Hyphenated UUID, e.g. `123e4567-e89b-12d3-a456-426614174000`.
+    Uuid,
+    /// `local@domain` address with a dotted domain.
+    Email,
+    /// Absolute URL with a host, e.g. `https://example.com/path`.
+    Url,
+    /// Dotted-quad IPv4 address.
+    Ipv4,
+    /// IPv6 address in any RFC 4291 text form.
+    Ipv6,
+    /// E.164 phone number: `+`, then up to 15 digits not starting with 0.
+    PhoneE164,
+}
+
+impl SemanticKind {
+    /// Whether `value` is in this format.
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            SemanticKind::Uuid => is_uuid(value),
+            SemanticKind::Email => is_email(value),
+            SemanticKind::Url => Url::parse(value).is_ok_and(|url| url.host().is_some()),
+            SemanticKind::Ipv4 => value.parse::<Ipv4Addr>().is_ok(),
+            SemanticKind::Ipv6 => value.parse::<Ipv6Addr>().is_ok(),
+            SemanticKind::PhoneE164 => is_phone_e164(value),
+        }
+    }
+}
+
+pub struct SemanticValidator {
+    pub kind: SemanticKind,
+}
+
+impl SemanticValidator {
+    /// Report for `bad_count` invalid values, the first being `example`.
+    pub fn report(&self, bad_count: usize, example: Option<&str>) -> ValidationReport {
+        match example {
+            Some(example) if bad_count > 0 => ValidationReport {
+                status: "fail",
+                details: Some(format!(
+                    "bad_count={}, example=\"{}\"",
+                    bad_count,
+                    mask(example)
+                )),
+            },
+            _ => ValidationReport {
+                status: "pass",
+                details: None,
+            },
+        }
+    }
+}
+
+impl Validator for SemanticValidator {
+    fn name(&self) -> &'static str {
+        match self.kind {
+            SemanticKind::Uuid => "Uuid",
+            SemanticKind::Email => "Email",
+            SemanticKind::Url => "Url",
+            SemanticKind::Ipv4 => "Ipv4",
+            SemanticKind::Ipv6 => "Ipv6",
+            SemanticKind::PhoneE164 => "PhoneE164",
+        }
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?;
+
+        if let Ok(utf8_chunked) = series.str() {
+            let mut invalid = utf8_chunked
+                .into_iter()
+                .flatten()
+                .filter(|val| !self.kind.matches(val));
+            let example = invalid.next();
+            let bad_count = example.map_or(0, |_| 1 + invalid.count());
+            Ok(self.report(bad_count, example))
+        } else {
+            Ok(ValidationReport {
+                status: "skipped",
+                details: Some("column is not a string type".to_string()),
+            })
+        }
+    }
+}
+
+/// `value` with all but its first and last two characters replaced by `*`
+/// (all but the first for values of four characters or fewer), so failure
+/// details don't leak personal data into audit logs.
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let (head, tail) = if chars.len() > 4 { (2, 2) } else { (1, 0) };
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            if i < head || i + tail >= chars.len() {
+                *c
+            } else {
+                '*'
+            }
+        })
+        .collect()
+}
+
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+    let local_ok = !local.is_empty()
+        && local.len() <= 64
+        && local.split('.').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-".contains(c))
+        });
+    let labels: Vec<&str> = domain.split('.').collect();
+    let domain_ok = domain.len() <= 253
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+        && labels
+            .last()
+            .is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+    local_ok && domain_ok && value.len() <= 254
+}
+
+fn is_phone_e164(value: &str) -> bool {
+    value.strip_prefix('+').is_some_and(|digits| {
+        (2..=15).contains(&digits.len())
+            && !digits.starts_with('0')
+            && digits.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_str_df(values: &[Option<&str>]) -> DataFrame {
+        let s = Series::new("col".into(), values.to_vec());
+        DataFrame::new(vec![s.into()]).unwrap()
+    }
+
+    #[test]
+    fn recognizes_each_format() {
+        let cases = [
+            (
+                SemanticKind::Uuid,
+                &[
+                    "123e4567-e89b-12d3-a456-426614174000",
+                    "00000000-0000-0000-0000-000000000000",
+                ][..],
+                &[
+                    "123e4567e89b12d3a456426614174000",
+                    "123e4567-e89b-12d3-a456-42661417400g",
+                ][..],
+            ),
+            (
+                SemanticKind::Email,
+                &["jane.doe@example.com", "ops+alerts@mail.example.co.uk"][..],
+                &[
+                    "jane@localhost",
+                    "jane..doe@example.com",
+                    "@example.com",
+                    "jane@-x.com",
+                ][..],
+            ),
+            (
+                SemanticKind::Url,
+                &["https://example.com/a?b=1", "ftp://files.example.org"][..],
+                &["example.com", "mailto:jane@example.com", "https://"][..],
+            ),
+            (
+                SemanticKind::Ipv4,
+                &["192.168.0.1", "0.0.0.0"][..],
+                &["256.1.1.1", "1.2.3", "::1"][..],
+            ),
+            (
+                SemanticKind::Ipv6,
+                &["::1", "2001:db8::8a2e:370:7334"][..],
+                &["2001:db8::g", "192.168.0.1"][..],
+            ),
+            (
+                SemanticKind::PhoneE164,
+                &["+14155552671", "+447911123456"][..],
+                &[
+                    "14155552671",
+                    "+0123456",
+                    "+1 415 555 2671",
+                    "+1234567890123456",
+                ][..],
+            ),
+        ];
+        for (kind, valid, invalid) in cases {
+            for value in valid {
+                assert!(kind.matches(value), "{:?} rejected {}", kind, value);
+            }
+            for value in invalid {
+                assert!(!kind.matches(value), "{:?} accepted {}", kind, value);
+            }
+        }
+    }
+
+    #[test]
+    fn fails_with_count_and_masked_example() {
+        let df = make_str_df(&[
+            Some("jane@example.com"),
+            Some("john.smith@invalid"),
+            None,
+            Some("nobody"),
+        ]);
+        let validator = SemanticValidator {
+            kind: SemanticKind::Email,
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "fail");
+        assert_eq!(
+            report.details.unwrap(),
+            "bad_count=2, example=\"jo**************id\""
+        );
+    }
+
+    #[test]
+    fn passes_when_all_values_are_valid() {
+        let df = make_str_df(&[Some("10.0.0.1"), None]);
+        let validator = SemanticValidator {
+            kind: SemanticKind::Ipv4,
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "pass");
+        assert!(report.details.is_none());
+    }
+
+    #[test]
+    fn masks_short_values() {
+        assert_eq!(mask("abc"), "a**");
+        assert_eq!(mask("+1 415"), "+1**15");
+        assert_eq!(mask(""), "");
+    }
+
+    #[test]
+    fn skips_on_non_string_column() {
+        let s = Series::new("col".into(), &[1, 2, 3]);
+        let df = DataFrame::new(vec![s.into()]).unwrap();
+        let validator = SemanticValidator {
+            kind: SemanticKind::Uuid,
+        };
+        let report = validator.validate(&df, "col").unwrap();
+        assert_eq!(report.status, "skipped");
+    }
+}
@@ -8,51 +8,52 @@ pub struct StdevBetweenValidator {
     pub max: f64,
 }
 
-impl Validator for StdevBetweenValidator {
-    fn name(&self) -> &'static str {
-        "StdevBetween"
-    }
-
-    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
-        let series = df.column(column_name)?;
-
-        let f64_series = match series.strict_cast(&DataType::Float64) {
-            Ok(s) => s,
-            Err(_) => {
-                return Ok(ValidationReport {
-                    status: "skipped",
-                    details: Some("column could not be cast to a numeric type".to_string()),
-                });
-            }
-        };
-
-        let values = f64_series.f64()?;
-
-        // The argument `1` specifies a sample standard deviation (ddof=1).
-        if let Some(std_dev) = values.std(1) {
+impl StdevBetweenValidator {
+    /// Report for an observed sample standard deviation (`None` when it
+    /// can't be calculated, e.g. fewer than two values).
+    pub fn report(&self, std_dev: Option<f64>) -> ValidationReport {
+        if let Some(std_dev) = std_dev {
             if std_dev >= self.min && std_dev <= self.max {
-                Ok(ValidationReport {
+                ValidationReport {
                     status: "pass",
                     details: None,
-                })
+                }
             } else {
-                Ok(ValidationReport {
+                ValidationReport {
                     status: "fail",
                     details: Some(format!(
                         "observed_stdev={:.2}, min={}, max={}",
                         std_dev, self.min, self.max
                     )),
-                })
+                }
             }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "skipped",
                 details: Some("standard deviation could not be calculated".to_string()),
-            })
+            }
         }
     }
 }
 
+impl Validator for StdevBetweenValidator {
+    fn name(&self) -> &'static str {
+        "StdevBetween"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?;
+
+        let f64_series = match series.strict_cast(&DataType::Float64) {
+            Ok(s) => s,
+            Err(_) => return Ok(ValidationReport::not_numeric()),
+        };
+
+        // The argument `1` specifies a sample standard deviation (ddof=1).
+        Ok(self.report(f64_series.f64()?.std(1)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,34 +5,38 @@ use polars::prelude::*;
 
 pub struct UniqueValidator;
 
-impl Validator for UniqueValidator {
-    fn name(&self) -> &'static str {
-        "Unique"
-    }
-
-    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
-        let series = df.column(column_name)?;
-
-        let unique_count = series.n_unique()?;
-        let total_count = series.len();
-
+impl UniqueValidator {
+    /// Report for a column with `unique_count` distinct values in `total_count` rows.
+    pub fn report(&self, unique_count: usize, total_count: usize) -> ValidationReport {
         if unique_count == total_count {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "pass",
                 details: None,
-            })
+            }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "fail",
                 details: Some(format!(
                     "found {} unique values in {} total rows",
                     unique_count, total_count
                 )),
-            })
+            }
         }
     }
 }
 
+impl Validator for UniqueValidator {
+    fn name(&self) -> &'static str {
+        "Unique"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?;
+
+        Ok(self.report(series.n_unique()?, series.len()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,209 @@
+// src/validators/column/wasm.rs
+//! User-defined column validators compiled to WebAssembly.
+//!
+//! Contracts reference a module with
+//! `{ rule = "wasm", module = "validators/phone_check.wasm" }`.
+//!
+//! The module runs in a sandbox: it may not import anything, its execution is
+//! bounded by a fuel budget, and its linear memory is capped.
+//!
+//! ## Guest ABI
+//! The module must export:
+//! - `memory`
+//! - `alloc(len: i32) -> i32` — reserve `len` bytes and return their offset
+//! - `validate(ptr: i32, len: i32) -> i64` — check the encoded column and
+//!   return `(pass_count << 32) | fail_count`
+//!
+//! The column is cast to strings and encoded as a sequence of values, each a
+//! little-endian `u32` byte length followed by the UTF-8 bytes. Nulls are
+//! written as length `0xFFFFFFFF` with no bytes.
+
+use crate::logging::error::ValidationError;
+use crate::validators::{ValidationReport, ValidationResult, Validator};
+use polars::prelude::*;
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Fuel budget used when the rule doesn't set one.
+const DEFAULT_FUEL: u64 = 10_000_000_000;
+
+/// Upper bound on guest linear memory.
+const MAX_MEMORY_BYTES: usize = 1 << 30;
+
+/// Length marker for null values in the encoded column.
+const NULL_MARKER: u32 = u32::MAX;
+
+pub struct WasmValidator {
+    pub module: String,
+    pub fuel: Option<u64>,
+}
+
+impl Validator for WasmValidator {
+    fn name(&self) -> &'static str {
+        "Wasm"
+    }
+
+    fn validate(&self, df: &DataFrame, column_name: &str) -> ValidationResult<ValidationReport> {
+        let series = df.column(column_name)?.cast(&DataType::String)?;
+        let input = encode_column(series.str()?);
+
+        let wasm = std::fs::read(&self.module)?;
+        let (pass_count, fail_count) = self
+            .run(&wasm, &input)
+            .map_err(|e| ValidationError::ValidationFailed(format!("{}: {}", self.module, e)))?;
+
+        let details = Some(format!(
+            "pass={}, fail={}, module={}",
+            pass_count, fail_count, self.module
+        ));
+        if fail_count > 0 {
+            Ok(ValidationReport {
+                status: "fail",
+                details,
+            })
+        } else {
+            Ok(ValidationReport {
+                status: "pass",
+                details,
+            })
+        }
+    }
+}
+
+impl WasmValidator {
+    /// Instantiate the module in a fresh sandbox and run `validate` on `input`.
+    fn run(&self, wasm: &[u8], input: &[u8]) -> anyhow::Result<(u32, u32)> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm)?;
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .build();
+        let mut store = Store::new(&engine, limits);
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store
+            .set_fuel(self.fuel.unwrap_or(DEFAULT_FUEL))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        // No host functions are provided, so any module with imports is rejected.
+        let linker = Linker::<StoreLimits>::new(&engine);
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| anyhow::anyhow!("module does not export `memory`"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc")?;
+        let validate = instance.get_typed_func::<(i32, i32), i64>(&store, "validate")?;
+
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory
+            .write(&mut store, ptr as u32 as usize, input)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let packed = validate.call(&mut store, (ptr, len))? as u64;
+
+        Ok(((packed >> 32) as u32, packed as u32))
+    }
+}
+
+/// Encode a string column using the length-prefixed layout described above.
+fn encode_column(values: &StringChunked) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for value in values.into_iter() {
+        match value {
+            Some(v) => {
+                buf.extend_from_slice(&(v.len() as u32).to_le_bytes());
+                buf.extend_from_slice(v.as_bytes());
+            }
+            None => buf.extend_from_slice(&NULL_MARKER.to_le_bytes()),
+        }
+    }
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    // Counts non-null values as passing and nulls as failing.
+    const COUNT_NULLS: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) i32.const 0)
+          (func (export "validate") (param $ptr i32) (param $len i32) (result i64)
+            (local $pos i32) (local $n i32) (local $pass i64) (local $fail i64)
+            (local.set $pos (local.get $ptr))
+            (block $done
+              (loop $next
+                (br_if $done (i32.ge_u (local.get $pos) (i32.add (local.get $ptr) (local.get $len))))
+                (local.set $n (i32.load (local.get $pos)))
+                (local.set $pos (i32.add (local.get $pos) (i32.const 4)))
+                (if (i32.eq (local.get $n) (i32.const -1))
+                  (then (local.set $fail (i64.add (local.get $fail) (i64.const 1))))
+                  (else
+                    (local.set $pass (i64.add (local.get $pass) (i64.const 1)))
+                    (local.set $pos (i32.add (local.get $pos) (local.get $n)))))
+                (br $next)))
+            (i64.or (i64.shl (local.get $pass) (i64.const 32)) (local.get $fail))))
+    "#;
+
+    fn module_file(wat_src: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&wat::parse_str(wat_src).unwrap()).unwrap();
+        file
+    }
+
+    fn make_df(values: &[Option<&str>]) -> DataFrame {
+        let s = Series::new("col".into(), values.to_vec());
+        DataFrame::new(vec![s.into()]).unwrap()
+    }
+
+    fn validator(file: &tempfile::NamedTempFile, fuel: Option<u64>) -> WasmValidator {
+        WasmValidator {
+            module: file.path().to_string_lossy().to_string(),
+            fuel,
+        }
+    }
+
+    #[test]
+    fn passes_when_module_reports_no_failures() {
+        let file = module_file(COUNT_NULLS);
+        let df = make_df(&[Some("a"), Some("bb")]);
+        let report = validator(&file, None).validate(&df, "col").unwrap();
+        assert_eq!(report.status, "pass");
+        assert!(report.details.unwrap().starts_with("pass=2, fail=0"));
+    }
+
+    #[test]
+    fn fails_with_counts_from_module() {
+        let file = module_file(COUNT_NULLS);
+        let df = make_df(&[Some("a"), None, Some("c"), None]);
+        let report = validator(&file, None).validate(&df, "col").unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().starts_with("pass=2, fail=2"));
+    }
+
+    #[test]
+    fn rejects_modules_with_imports() {
+        let file =
+            module_file(r#"(module (import "env" "host" (func)) (memory (export "memory") 1))"#);
+        let df = make_df(&[Some("a")]);
+        assert!(validator(&file, None).validate(&df, "col").is_err());
+    }
+
+    #[test]
+    fn aborts_when_fuel_runs_out() {
+        let file = module_file(
+            r#"(module
+                 (memory (export "memory") 1)
+                 (func (export "alloc") (param i32) (result i32) i32.const 0)
+                 (func (export "validate") (param i32 i32) (result i64)
+                   (loop $spin (br $spin))
+                   i64.const 0))"#,
+        );
+        let df = make_df(&[Some("a")]);
+        assert!(validator(&file, Some(10_000)).validate(&df, "col").is_err());
+    }
+}
@@ -7,30 +7,36 @@ pub struct CompoundUniqueValidator {
     pub columns: Vec<String>,
 }
 
-impl CompoundValidator for CompoundUniqueValidator {
-    fn name(&self) -> &'static str {
-        "CompoundUnique"
-    }
-
-    fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
-        let total_rows = df.height();
-
-        let unique_df = df.unique_stable(Some(&self.columns), UniqueKeepStrategy::First, None)?;
-        let distinct_rows = unique_df.height();
-
+impl CompoundUniqueValidator {
+    /// Report for `distinct_rows` distinct combinations in `total_rows` rows.
+    pub fn report(&self, distinct_rows: usize, total_rows: usize) -> ValidationReport {
         if distinct_rows == total_rows {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "pass",
                 details: Some(format!("columns={:?}, rows={}", self.columns, total_rows)),
-            })
+            }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "fail",
                 details: Some(format!(
                     "columns={:?}, rows={}, distinct={}",
                     self.columns, total_rows, distinct_rows
                 )),
-            })
+            }
         }
     }
 }
+
+impl CompoundValidator for CompoundUniqueValidator {
+    fn name(&self) -> &'static str {
+        "CompoundUnique"
+    }
+
+    fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
+        let total_rows = df.height();
+
+        let unique_df = df.unique_stable(Some(&self.columns), UniqueKeepStrategy::First, None)?;
+
+        Ok(self.report(unique_df.height(), total_rows))
+    }
+}
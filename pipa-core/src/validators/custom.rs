@@ -0,0 +1,120 @@
+// src/validators/custom.rs
+//! Registry for user-defined validators.
+//!
+//! Library consumers register a factory under a name with `register_custom`.
+//! Contracts then reference it as:
+//!
+//! ```toml
+//! validation = [{ rule = "custom", name = "my_rule", params = { threshold = 3 } }]
+//! ```
+//!
+//! The factory receives the rule's `params` table and returns either a
+//! column-level or a file-level validator, depending on where the rule is used.
+
+use crate::logging::error::{ValidationError, ValidationResult};
+use crate::validators::{FileValidator, Validator};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Parameters passed to a custom rule (the `params` table in the contract).
+pub type CustomParams = toml::Table;
+
+/// A validator produced by a custom factory.
+pub enum CustomValidator {
+    /// Applies to a single column (declared under `[[columns]]`).
+    Column(Box<dyn Validator>),
+    /// Applies to the whole file (declared under `[file]`).
+    File(Box<dyn FileValidator>),
+}
+
+type CustomFactory = Arc<dyn Fn(&CustomParams) -> ValidationResult<CustomValidator> + Send + Sync>;
+
+static REGISTRY: Lazy<RwLock<HashMap<String, CustomFactory>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Register a custom validator factory under `name`.
+///
+/// Registering the same name twice replaces the previous factory.
+pub fn register_custom<F>(name: &str, factory: F)
+where
+    F: Fn(&CustomParams) -> ValidationResult<CustomValidator> + Send + Sync + 'static,
+{
+    REGISTRY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(name.to_string(), Arc::new(factory));
+}
+
+/// Build the custom validator registered as `name`.
+///
+/// Fails if nothing is registered under that name or the factory rejects
+/// the parameters.
+pub fn resolve_custom(name: &str, params: &CustomParams) -> ValidationResult<CustomValidator> {
+    let factory = REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(name)
+        .cloned()
+        .ok_or_else(|| {
+            ValidationError::Config(format!("no custom validator registered as '{}'", name))
+        })?;
+    factory(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::ValidationReport;
+    use polars::prelude::*;
+
+    struct MinWidth {
+        min: usize,
+    }
+
+    impl FileValidator for MinWidth {
+        fn name(&self) -> &'static str {
+            "MinWidth"
+        }
+
+        fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
+            let status = if df.width() >= self.min {
+                "pass"
+            } else {
+                "fail"
+            };
+            Ok(ValidationReport {
+                status,
+                details: None,
+            })
+        }
+    }
+
+    #[test]
+    fn resolves_registered_factory_with_params() {
+        register_custom("test_min_width", |params| {
+            let min = params
+                .get("min")
+                .and_then(|v| v.as_integer())
+                .ok_or_else(|| ValidationError::ValidationFailed("min required".into()))?;
+            Ok(CustomValidator::File(Box::new(MinWidth {
+                min: min as usize,
+            })))
+        });
+
+        let df = df!("a" => &[1i64], "b" => &[2i64]).unwrap();
+        let mut params = CustomParams::new();
+        params.insert("min".into(), toml::Value::Integer(3));
+
+        match resolve_custom("test_min_width", &params).unwrap() {
+            CustomValidator::File(v) => assert_eq!(v.validate(&df).unwrap().status, "fail"),
+            CustomValidator::Column(_) => panic!("expected file validator"),
+        }
+    }
+
+    #[test]
+    fn unregistered_name_is_an_error() {
+        let result = resolve_custom("test_does_not_exist", &CustomParams::new());
+        assert!(result.is_err());
+    }
+}
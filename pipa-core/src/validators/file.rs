@@ -1,6 +1,8 @@
 // The new src/validators/file.rs
 pub mod completeness;
+pub mod expected_columns;
 pub mod row_count;
 
 pub use completeness::FileCompletenessValidator;
+pub use expected_columns::ExpectedColumnsValidator;
 pub use row_count::RowCountValidator;
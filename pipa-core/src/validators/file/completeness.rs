@@ -7,20 +7,9 @@ pub struct FileCompletenessValidator {
     pub min_ratio: f64,
 }
 
-impl FileValidator for FileCompletenessValidator {
-    fn name(&self) -> &'static str {
-        "FileCompleteness"
-    }
-
-    fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
-        let total_rows = df.height();
-        if total_rows == 0 {
-            return Ok(ValidationReport {
-                status: "pass",
-                details: Some("file is empty".to_string()),
-            });
-        }
-
+impl FileCompletenessValidator {
+    /// Number of rows of `df` with no null values.
+    pub fn complete_rows(df: &DataFrame) -> ValidationResult<usize> {
         let masks = df
             .get_columns()
             .iter()
@@ -32,19 +21,45 @@ impl FileValidator for FileCompletenessValidator {
             .reduce(|acc, mask| acc & mask)
             .ok_or_else(|| anyhow!("Cannot compute completeness for a file with no columns"))?;
 
-        let complete_rows = complete_rows_mask.sum().unwrap_or(0) as f64;
-        let ratio = complete_rows / total_rows as f64;
+        Ok(complete_rows_mask.sum().unwrap_or(0) as usize)
+    }
+
+    /// Report for `complete_rows` fully populated rows out of `total_rows`.
+    pub fn report(&self, complete_rows: usize, total_rows: usize) -> ValidationReport {
+        if total_rows == 0 {
+            return ValidationReport {
+                status: "pass",
+                details: Some("file is empty".to_string()),
+            };
+        }
+
+        let ratio = complete_rows as f64 / total_rows as f64;
 
         if ratio >= self.min_ratio {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "pass",
                 details: None,
-            })
+            }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "fail",
                 details: Some(format!("ratio={:.2}, min_ratio={}", ratio, self.min_ratio)),
-            })
+            }
+        }
+    }
+}
+
+impl FileValidator for FileCompletenessValidator {
+    fn name(&self) -> &'static str {
+        "FileCompleteness"
+    }
+
+    fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
+        let total_rows = df.height();
+        if total_rows == 0 {
+            return Ok(self.report(0, 0));
         }
+
+        Ok(self.report(Self::complete_rows(df)?, total_rows))
     }
 }
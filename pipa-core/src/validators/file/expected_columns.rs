@@ -0,0 +1,152 @@
+// src/validators/file/expected_columns.rs
+use crate::validators::{FileValidator, ValidationReport, ValidationResult};
+use polars::prelude::*;
+
+pub struct ExpectedColumnsValidator {
+    pub columns: Vec<String>,
+    pub ordered: bool,
+    pub allow_extra_columns: bool,
+    pub allow_missing_columns: bool,
+}
+
+impl FileValidator for ExpectedColumnsValidator {
+    fn name(&self) -> &'static str {
+        "ExpectedColumns"
+    }
+
+    fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
+        let actual: Vec<String> = df
+            .get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        let missing: Vec<&String> = self
+            .columns
+            .iter()
+            .filter(|c| !actual.contains(c))
+            .collect();
+        let extra: Vec<&String> = actual
+            .iter()
+            .filter(|c| !self.columns.contains(c))
+            .collect();
+
+        let mut problems = Vec::new();
+        if !missing.is_empty() && !self.allow_missing_columns {
+            problems.push(format!("missing={:?}", missing));
+        }
+        if !extra.is_empty() && !self.allow_extra_columns {
+            problems.push(format!("extra={:?}", extra));
+        }
+
+        // Order is compared only across the columns both sides agree on,
+        // so an allowed extra/missing column never doubles as an order failure.
+        if self.ordered {
+            let expected_order: Vec<&String> =
+                self.columns.iter().filter(|c| actual.contains(c)).collect();
+            let actual_order: Vec<&String> =
+                actual.iter().filter(|c| self.columns.contains(c)).collect();
+            if expected_order != actual_order {
+                problems.push(format!(
+                    "order_mismatch expected={:?}, actual={:?}",
+                    expected_order, actual_order
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(ValidationReport {
+                status: "pass",
+                details: None,
+            })
+        } else {
+            Ok(ValidationReport {
+                status: "fail",
+                details: Some(problems.join("; ")),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_df(columns: &[&str]) -> DataFrame {
+        let cols = columns
+            .iter()
+            .map(|name| Series::new((*name).into(), &[1i64, 2, 3]).into())
+            .collect::<Vec<Column>>();
+        DataFrame::new(cols).unwrap()
+    }
+
+    fn validator(columns: &[&str]) -> ExpectedColumnsValidator {
+        ExpectedColumnsValidator {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            ordered: false,
+            allow_extra_columns: false,
+            allow_missing_columns: false,
+        }
+    }
+
+    #[test]
+    fn passes_when_columns_match_exactly() {
+        let df = make_df(&["id", "email"]);
+        let report = validator(&["id", "email"]).validate(&df).unwrap();
+        assert_eq!(report.status, "pass");
+        assert!(report.details.is_none());
+    }
+
+    #[test]
+    fn fails_on_missing_column() {
+        let df = make_df(&["id"]);
+        let report = validator(&["id", "email"]).validate(&df).unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().contains("missing=[\"email\"]"));
+    }
+
+    #[test]
+    fn fails_on_extra_column() {
+        let df = make_df(&["id", "email", "ssn"]);
+        let report = validator(&["id", "email"]).validate(&df).unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().contains("extra=[\"ssn\"]"));
+    }
+
+    #[test]
+    fn allows_extra_and_missing_when_configured() {
+        let df = make_df(&["id", "ssn"]);
+        let mut v = validator(&["id", "email"]);
+        v.allow_extra_columns = true;
+        v.allow_missing_columns = true;
+        let report = v.validate(&df).unwrap();
+        assert_eq!(report.status, "pass");
+    }
+
+    #[test]
+    fn ignores_order_by_default() {
+        let df = make_df(&["email", "id"]);
+        let report = validator(&["id", "email"]).validate(&df).unwrap();
+        assert_eq!(report.status, "pass");
+    }
+
+    #[test]
+    fn fails_on_order_mismatch_when_ordered() {
+        let df = make_df(&["email", "id"]);
+        let mut v = validator(&["id", "email"]);
+        v.ordered = true;
+        let report = v.validate(&df).unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(report.details.unwrap().contains("order_mismatch"));
+    }
+
+    #[test]
+    fn order_check_ignores_allowed_extra_columns() {
+        let df = make_df(&["id", "ssn", "email"]);
+        let mut v = validator(&["id", "email"]);
+        v.ordered = true;
+        v.allow_extra_columns = true;
+        let report = v.validate(&df).unwrap();
+        assert_eq!(report.status, "pass");
+    }
+}
@@ -7,28 +7,34 @@ pub struct RowCountValidator {
     pub max: Option<usize>,
 }
 
-impl FileValidator for RowCountValidator {
-    fn name(&self) -> &'static str {
-        "RowCount"
-    }
-
-    fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
-        let rows = df.height();
+impl RowCountValidator {
+    /// Report for a dataset of `rows` rows.
+    pub fn report(&self, rows: usize) -> ValidationReport {
         let max_check = self.max.map(|m| rows > m).unwrap_or(false);
 
         if rows < self.min || max_check {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "fail",
                 details: Some(format!(
                     "rows={}, min={}, max={:?}",
                     rows, self.min, self.max
                 )),
-            })
+            }
         } else {
-            Ok(ValidationReport {
+            ValidationReport {
                 status: "pass",
                 details: None,
-            })
+            }
         }
     }
 }
+
+impl FileValidator for RowCountValidator {
+    fn name(&self) -> &'static str {
+        "RowCount"
+    }
+
+    fn validate(&self, df: &DataFrame) -> ValidationResult<ValidationReport> {
+        Ok(self.report(df.height()))
+    }
+}
@@ -0,0 +1,72 @@
+//! Connector integration tests against Docker storage emulators.
+//!
+//! Ignored by default; run with:
+//! ```bash
+//! cargo test -p pipe-audit-core --features test-util --test connector_emulators -- --ignored
+//! ```
+#![cfg(feature = "test-util")]
+
+use pipa_core::test_util::{
+    Emulator, EmulatorHarness, Profiles, Source, docker_available, fetch_data_from_source,
+    list_source_objects,
+};
+
+const CSV: &[u8] = b"id,amount\n1,10\n2,12\n";
+
+async fn round_trip(emulator: Emulator) {
+    if !docker_available() {
+        eprintln!("skipping {:?}: docker is not available", emulator);
+        return;
+    }
+
+    let harness = EmulatorHarness::start(emulator).await.unwrap();
+    harness.seed("pipa-test", "orders.csv", CSV).await.unwrap();
+
+    let mut profiles = Profiles::new();
+    profiles.insert("emulator".to_string(), harness.profile());
+    let source = Source {
+        r#type: emulator.provider().to_string(),
+        location: Some(harness.location("pipa-test", "orders.csv")),
+        profile: Some("emulator".to_string()),
+        format: None,
+        options: Default::default(),
+        csv: Default::default(),
+        on_success: Default::default(),
+        archive_location: None,
+        on_duplicate: Default::default(),
+        archive_member: None,
+        archive_mode: Default::default(),
+    };
+
+    let fetched = fetch_data_from_source(&source, &profiles).await.unwrap();
+    let mut data = Vec::new();
+    std::io::Read::read_to_end(&mut fetched.payload.into_reader().unwrap(), &mut data).unwrap();
+    assert_eq!(data, CSV);
+
+    let location = harness.location("pipa-test", "orders.csv");
+    let prefix = location.trim_end_matches("orders.csv");
+    let listed = list_source_objects(&source, prefix, &profiles)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].location, location);
+    assert_eq!(listed[0].size, CSV.len() as u64);
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn s3_round_trip_on_localstack() {
+    round_trip(Emulator::LocalStack).await;
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn azure_round_trip_on_azurite() {
+    round_trip(Emulator::Azurite).await;
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn gcs_round_trip_on_fake_gcs() {
+    round_trip(Emulator::FakeGcs).await;
+}
@@ -1,4 +1,12 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::ArgValueCandidates;
+use pipa::contract::ExportFormat;
+use pipa::logs::LogFormat;
+use pipa::run::{parse_delay, parse_run_date, NaiveDate, OutputFormat};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::commands::completions::contract_names;
 
 /// Root CLI parser for the `pipa` data quality engine.
 ///
@@ -19,6 +27,29 @@ pub struct Cli {
     #[arg(short, long)]
     pub verbose: bool,
 
+    /// Project directory to work in (default: the current directory).
+    /// Its `.env`, contracts, profiles, logs, and run history are used.
+    #[arg(long, global = true, value_name = "DIR")]
+    pub project_dir: Option<PathBuf>,
+
+    /// Contracts directory (overrides `[paths] contracts` and `PIPA_CONTRACTS_DIR`).
+    #[arg(long, global = true, value_name = "DIR")]
+    pub contracts_dir: Option<PathBuf>,
+
+    /// Audit log directory (overrides `[paths] logs` and `PIPA_LOGS_DIR`).
+    #[arg(long, global = true, value_name = "DIR")]
+    pub logs_dir: Option<PathBuf>,
+
+    /// Environment whose contract overlays apply: `contracts/<name>.<ENV>.toml`
+    /// is merged over `contracts/<name>.toml` (overrides `PIPA_ENV`).
+    #[arg(long, global = true, value_name = "ENV")]
+    pub env: Option<String>,
+
+    /// Report format, `text` or `json` (overrides `[defaults] output` and
+    /// `PIPA_OUTPUT`); a command's `--json` always prints JSON.
+    #[arg(long, global = true, value_name = "FORMAT")]
+    pub output_format: Option<OutputFormat>,
+
     /// The top-level command to execute.
     /// If no command is provided, `main.rs` will print a help message.
     #[command(subcommand)]
@@ -37,11 +68,72 @@ pub enum Commands {
     /// or `--all` can be used to run every contract.
     Run {
         /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
         contract: Option<String>,
 
         /// Run all contracts in the project.
         #[arg(long)]
         all: bool,
+
+        /// Run only contracts tagged with any of these tags (repeatable).
+        #[arg(long = "tag", value_name = "TAG", conflicts_with = "contract")]
+        tags: Vec<String>,
+
+        /// Skip contracts tagged with any of these tags (repeatable).
+        #[arg(long = "exclude-tag", value_name = "TAG", conflicts_with = "contract")]
+        exclude_tags: Vec<String>,
+
+        /// Number of contracts to validate concurrently with `--all` or `--tag`
+        /// (default: `[defaults] jobs` in `pipa.toml`, or 1).
+        #[arg(
+            long,
+            short = 'j',
+            conflicts_with = "contract",
+            value_parser = clap::value_parser!(u16).range(1..)
+        )]
+        jobs: Option<u16>,
+
+        /// Fetch and validate without writing to destination or quarantine.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Run date for `{{ today }}`-style tokens in source locations (default: today, UTC).
+        #[arg(long, value_name = "YYYY-MM-DD", value_parser = parse_run_date)]
+        date: Option<NaiveDate>,
+
+        /// Don't show the progress display (only drawn when stderr is a terminal).
+        #[arg(long, short = 'q')]
+        quiet: bool,
+
+        /// Retry a run that fails reading its source up to N more times
+        /// (overrides the contract's `[retry] retries`).
+        #[arg(long, value_name = "N")]
+        retries: Option<u32>,
+
+        /// Wait between retries, e.g. `30s`, `500ms`, or `5m` (overrides
+        /// the contract's `[retry] delay`; default 30s).
+        #[arg(long, value_name = "DELAY", value_parser = parse_delay)]
+        retry_delay: Option<Duration>,
+
+        /// Cancel a contract's run, retries included, once it has taken
+        /// this long, e.g. `30m` (overrides `[limits] run_timeout_secs`);
+        /// `--all` moves on to the next contract.
+        #[arg(long, value_name = "DURATION", value_parser = parse_delay)]
+        timeout: Option<Duration>,
+
+        /// Fail a source fetch that takes longer than this, e.g. `2m`
+        /// (overrides `[limits] fetch_timeout_secs`).
+        #[arg(long, value_name = "DURATION", value_parser = parse_delay)]
+        fetch_timeout: Option<Duration>,
+
+        /// Print the run's results as JSON instead of console messages.
+        #[arg(long, value_name = "FORMAT", requires = "contract")]
+        output: Option<OutputFormat>,
+
+        /// Also write the run's results, per-rule results included, as JSON
+        /// to this file.
+        #[arg(long, value_name = "PATH", requires = "contract")]
+        emit_results_file: Option<PathBuf>,
     },
 
     /// Manage contracts (list, validate, show).
@@ -50,17 +142,68 @@ pub enum Commands {
         contract_command: ContractCommands,
     },
 
-    /// Manage profiles (list, test connectivity).
+    /// Manage profiles (list, test connectivity, add, edit, remove).
     Profile {
         #[command(subcommand)]
         profile_command: ProfileCommands,
     },
 
+    /// Profile a data source (no contract required).
+    ///
+    /// Computes per-column statistics: null/distinct ratios, min/max,
+    /// mean/stdev, top values, and inferred dtype.
+    ProfileData {
+        /// Data location (local path, `s3://`, `gs://`, or Azure blob URL).
+        location: String,
+
+        /// Connection profile to use for remote sources.
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Number of most frequent values to report per column.
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+
+        /// Print the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare two recorded runs of a contract.
+    ///
+    /// Reports row count deltas, added/removed columns, and per-column
+    /// statistic shifts from the run history in `.pipa/state/`.
+    DiffData {
+        /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        contract: String,
+
+        /// Run ids to compare: none diffs the last two runs, one diffs that
+        /// run against the latest.
+        #[arg(long = "run", num_args = 1, action = clap::ArgAction::Append)]
+        runs: Vec<String>,
+
+        /// Print the diff as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Inspect the supported contract rules.
+    Rules {
+        #[command(subcommand)]
+        rules_command: RulesCommands,
+    },
+
     /// Run a system health check.
     ///
     /// This typically verifies environment setup, connectors,
     /// and other prerequisites.
-    Health,
+    Health {
+        /// Also test every profile's connectivity (concurrently), with
+        /// per-profile latency and failure reasons.
+        #[arg(long)]
+        deep: bool,
+    },
 
     /// Manage logs (verify integrity).
     Logs {
@@ -70,6 +213,77 @@ pub enum Commands {
 
     /// Initialize a new project in the current directory.
     Init,
+
+    /// Work with data in contracts' quarantine locations.
+    Quarantine {
+        #[command(subcommand)]
+        quarantine_command: QuarantineCommands,
+    },
+
+    /// Run contracts on their `[schedule] cron` in a resident process.
+    Scheduler {
+        #[command(subcommand)]
+        scheduler_command: SchedulerCommands,
+    },
+
+    /// Watch contract sources and run contracts as new files appear.
+    ///
+    /// A source location that is a directory or prefix (ending in `/`)
+    /// runs the contract on each new file under it; a file location runs
+    /// it whenever the file changes. Runs until interrupted (Ctrl-C).
+    Watch {
+        /// Contracts to watch (defaults to every contract in the project).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        contracts: Vec<String>,
+
+        /// Seconds between polls of each source.
+        #[arg(
+            long,
+            default_value_t = 30,
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        interval: u64,
+
+        /// Seconds a new file's size must hold steady before it is run.
+        #[arg(long, default_value_t = 5)]
+        debounce: u64,
+
+        /// Fetch and validate without writing to destination or quarantine.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Run several projects from one invocation.
+    Workspace {
+        #[command(subcommand)]
+        workspace_command: WorkspaceCommands,
+    },
+
+    /// Print a shell completion script.
+    ///
+    /// Completes subcommands, flags, and contract names (read from
+    /// `contracts/` as you type). Load it from your shell's startup file,
+    /// e.g. `source <(pipa completions bash)` in `~/.bashrc`.
+    Completions {
+        /// Shell to complete for.
+        shell: CompletionShell,
+    },
+
+    /// Print the man page, or write a page per subcommand into a directory.
+    Man {
+        /// Directory to write `pipa.1` and the subcommand pages into.
+        #[arg(long, value_name = "DIR")]
+        dir: Option<PathBuf>,
+    },
+}
+
+/// Shells `pipa completions` can write a script for.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
 }
 
 /// Contract-related subcommands.
@@ -88,12 +302,154 @@ pub enum ContractCommands {
         file: String,
     },
 
+    /// Lint a contract for semantic problems.
+    ///
+    /// Flags unknown rules, regexes that don't compile, inverted ranges,
+    /// rules that can't evaluate their column's dtype, undeclared columns,
+    /// and a missing `[source]`, each with a code and a suggested fix.
+    /// Exits with status 1 when any finding is an error.
+    Lint {
+        /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        name: String,
+
+        /// Print the findings as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Rewrite contracts in canonical form.
+    ///
+    /// Orders sections and keys, puts each validation rule on its own
+    /// line, and normalizes spacing and quoting, keeping comments. With
+    /// `--fix`, also corrects rule names that differ from a known rule
+    /// only in case or separators (`NotNull` -> `not_null`).
+    Fmt {
+        /// Contract names (without `.toml`); formats every contract if omitted.
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        names: Vec<String>,
+
+        /// Report contracts that aren't formatted instead of rewriting
+        /// them, and exit with status 1 if there are any.
+        #[arg(long)]
+        check: bool,
+
+        /// Also correct misspelled rule names.
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Check a contract's columns against a sample of its live source.
+    ///
+    /// Reads the first rows of the source and reports declared columns
+    /// that are missing, extra columns in the data, and dtype mismatches,
+    /// without running any rules. Exits 1 when a column is missing or
+    /// mismatched (2/3 for execution/config errors, as for `pipa run`).
+    Check {
+        /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        name: String,
+
+        /// Number of rows to sample.
+        #[arg(long, default_value_t = 1000)]
+        rows: usize,
+    },
+
+    /// Compare an older version of a contract with the current one.
+    ///
+    /// Reports added/removed columns, added/removed/changed rules, and
+    /// changed settings, plus each version's content hash (the
+    /// `contract_hash` recorded in run audit entries).
+    Diff {
+        /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        name: String,
+
+        /// Path to the older contract TOML.
+        old: String,
+
+        /// Print the diff as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export a contract to another schema language.
+    ///
+    /// `json-schema` renders one row of the contract's data as a JSON
+    /// Schema: column dtypes, `not_null`, `pattern`, `in_set`/`not_in_set`,
+    /// `range`, and `max_length`. Other rules are listed on stderr.
+    Export {
+        /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        name: String,
+
+        /// Output format (`json-schema`).
+        #[arg(long, default_value = "json-schema")]
+        format: ExportFormat,
+    },
+
     /// Show contract details by name.
     ///
-    /// Prints metadata and validation rules for inspection.
+    /// Prints the contract as it will run, with `${...}` references
+    /// resolved: its source, destination, and quarantine, a table of
+    /// columns with each rule and its parameters, and the remaining
+    /// sections. Exits with status 1 if the contract is missing or invalid.
     Show {
         /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
+        name: String,
+
+        /// Print the parsed contract as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create a contract interactively.
+    ///
+    /// Asks for the name, source, and columns (optionally read from a
+    /// sample file), lints the result, and writes `contracts/<name>.toml`.
+    New {
+        /// Overwrite an existing contract file.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Generate a draft contract from a sample data file.
+    ///
+    /// Infers columns and suggests not_null/unique/range rules, writing
+    /// the result to `contracts/<name>.toml`.
+    Generate {
+        /// Sample data location (local path, `s3://`, `gs://`, or Azure blob URL).
+        location: String,
+
+        /// Contract name (defaults to the sample's file stem).
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Connection profile to use for remote sources.
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Print the draft instead of writing it to `contracts/`.
+        #[arg(long)]
+        stdout: bool,
+
+        /// Overwrite an existing contract file.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Pin a recorded run as the drift baseline.
+    ///
+    /// `drift` rules with `against = "baseline"` compare against it.
+    Baseline {
+        /// Contract name (without `.toml` extension).
+        #[arg(add = ArgValueCandidates::new(contract_names))]
         name: String,
+
+        /// Run id to pin (defaults to the most recent run).
+        #[arg(long = "run")]
+        run_id: Option<String>,
     },
 }
 
@@ -112,6 +468,87 @@ pub enum ProfileCommands {
         /// Profile name to test.
         profile: String,
     },
+
+    /// Add a profile to `profiles.toml`.
+    ///
+    /// Without `--provider`, prompts for the provider and its usual fields.
+    /// Secrets are written as `${VAR}` placeholders, never as values.
+    Add {
+        /// Profile name (the section header in `profiles.toml`).
+        profile: String,
+
+        /// Provider: `s3`, `azure`, `gcs`, `kafka`, or `local`.
+        #[arg(long)]
+        provider: Option<String>,
+
+        /// Set a field, e.g. `--set region=us-east-1` (repeatable).
+        #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+        set: Vec<(String, String)>,
+
+        /// Read a secret field from an environment variable, e.g.
+        /// `--secret-env secret_key=AWS_SECRET_ACCESS_KEY` (repeatable).
+        #[arg(long = "secret-env", value_name = "KEY=VAR", value_parser = parse_key_value)]
+        secret_env: Vec<(String, String)>,
+    },
+
+    /// Change fields of a profile in `profiles.toml`.
+    ///
+    /// Without any flags, prompts for fields to set.
+    Edit {
+        /// Profile name to edit.
+        profile: String,
+
+        /// Set a field, e.g. `--set region=us-east-1` (repeatable).
+        #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+        set: Vec<(String, String)>,
+
+        /// Read a secret field from an environment variable (repeatable).
+        #[arg(long = "secret-env", value_name = "KEY=VAR", value_parser = parse_key_value)]
+        secret_env: Vec<(String, String)>,
+
+        /// Remove a field (repeatable).
+        #[arg(long, value_name = "KEY")]
+        unset: Vec<String>,
+    },
+
+    /// Remove a profile from `profiles.toml`.
+    Remove {
+        /// Profile name to remove.
+        profile: String,
+
+        /// Don't ask for confirmation.
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// Parse a `KEY=VALUE` argument.
+fn parse_key_value(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{}'", arg)),
+    }
+}
+
+/// Rule catalog subcommands.
+///
+/// These are dispatched from `Commands::Rules` in `main.rs`.
+#[derive(Subcommand, Debug)]
+pub enum RulesCommands {
+    /// List every supported rule with its parameters.
+    List {
+        /// Print the catalog as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show parameters, semantics, and a TOML example for one rule.
+    Describe {
+        /// Rule name as written in contracts (e.g. `outlier_sigma`).
+        rule: String,
+    },
 }
 
 /// Log-related subcommands.
@@ -121,7 +558,8 @@ pub enum ProfileCommands {
 pub enum LogsCommands {
     /// Verify log integrity.
     ///
-    /// Can check a specific date or all sealed logs.
+    /// Can check a specific date or all sealed logs. Exits non-zero if a
+    /// sealed log is altered or missing, or its chain or ledger entry is broken.
     Verify {
         /// Date to verify (YYYY-MM-DD format).
         #[arg(long)]
@@ -130,6 +568,149 @@ pub enum LogsCommands {
         /// Verify all sealed logs.
         #[arg(long)]
         all: bool,
+
+        /// Print the result as JSON (for monitoring).
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Seal daily logs into the hash ledger now.
+    ///
+    /// Logs are otherwise sealed by the first entry written on a later day.
+    /// Without `--date`, every unsealed log older than today is sealed.
+    Seal {
+        /// Day to seal (YYYY-MM-DD; must be before today).
+        #[arg(long, value_name = "YYYY-MM-DD", value_parser = parse_run_date)]
+        date: Option<NaiveDate>,
+    },
+
+    /// Upload sealed daily logs to the storage `[[log_sinks]]` in `pipa.toml`.
+    ///
+    /// Sealed files are shipped automatically; this retries any whose
+    /// upload failed (e.g. while a sink was unreachable).
+    Ship,
+
+    /// Export audit entries as a CSV or Parquet table for BI tools.
+    ///
+    /// One row per rule result (or per entry without results), with the
+    /// contract, target, executor, and run summary spread into columns.
+    Export {
+        /// Output file.
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Output format (`csv` or `parquet`; default: from the output extension).
+        #[arg(long)]
+        format: Option<LogFormat>,
+
+        /// First day to export (YYYY-MM-DD; default: the oldest log).
+        #[arg(long, value_name = "YYYY-MM-DD", value_parser = parse_run_date)]
+        from: Option<NaiveDate>,
+
+        /// Last day to export (YYYY-MM-DD; default: today).
+        #[arg(long, value_name = "YYYY-MM-DD", value_parser = parse_run_date)]
+        to: Option<NaiveDate>,
+
+        /// Only export the entries of this run (the `run_id` on each entry).
+        #[arg(long)]
+        run_id: Option<String>,
+    },
+
+    /// Remove sealed daily logs older than the retention period.
+    ///
+    /// Expired logs are deleted, or moved to `archive_dir` when `[retention]`
+    /// in `pipa.toml` sets one, and each is recorded in the ledger so
+    /// `logs verify` reports it as pruned. Unsealed or altered logs are kept.
+    Prune {
+        /// Days of logs to keep (default: `keep_days` from `[retention]`).
+        #[arg(long)]
+        keep_days: Option<u32>,
+
+        /// List the logs that would be removed without touching them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Re-encrypt the hash ledger under a new key.
+    ///
+    /// The key is stored through `[ledger_key]` in `pipa.toml`: a local key
+    /// file by default, or wrapped by AWS KMS, Azure Key Vault, or GCP KMS.
+    RotateKey,
+}
+
+/// Quarantine subcommands.
+///
+/// These are dispatched from `Commands::Quarantine` in `main.rs`.
+#[derive(Subcommand, Debug)]
+pub enum QuarantineCommands {
+    /// Re-run a contract on a quarantined file, releasing it on pass.
+    ///
+    /// A file that now passes is written to the contract's destination and
+    /// removed from quarantine; one that still fails is left in place.
+    Revalidate {
+        /// Quarantined file: a name in the quarantine location or a full path/URL.
+        file: String,
+
+        /// Contract that quarantined the file.
+        #[arg(long, add = ArgValueCandidates::new(contract_names))]
+        contract: String,
+
+        /// Validate without moving or deleting anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Scheduler subcommands.
+///
+/// These are dispatched from `Commands::Scheduler` in `main.rs`.
+#[derive(Subcommand, Debug)]
+pub enum SchedulerCommands {
+    /// Start the scheduler; runs until interrupted (Ctrl-C).
+    ///
+    /// Each contract with a `[schedule]` section runs at its cron times
+    /// (UTC), and every trigger is logged to the audit trail.
+    Start {
+        /// Fetch and validate without writing to destination or quarantine.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List scheduled contracts and their next run.
+    List,
+}
+
+/// Workspace subcommands.
+///
+/// These are dispatched from `Commands::Workspace` in `main.rs`.
+#[derive(Subcommand, Debug)]
+pub enum WorkspaceCommands {
+    /// Run every project's contracts with a consolidated summary.
+    ///
+    /// Each project runs from its own directory, with its own contracts,
+    /// profiles, logs, and run history. The exit code is the most severe
+    /// across projects.
+    Run {
+        /// Project directories (comma-separated); overrides the workspace file.
+        #[arg(long, value_delimiter = ',', value_name = "DIR")]
+        projects: Vec<String>,
+
+        /// Workspace file listing the projects (defaults to `workspace.toml`).
+        #[arg(long, conflicts_with = "projects")]
+        file: Option<String>,
+
+        /// Number of contracts to validate concurrently within a project
+        /// (default: `[defaults] jobs` in `pipa.toml`, or 1).
+        #[arg(
+            long,
+            short = 'j',
+            value_parser = clap::value_parser!(u16).range(1..)
+        )]
+        jobs: Option<u16>,
+
+        /// Fetch and validate without writing to destination or quarantine.
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -142,7 +723,7 @@ mod tests {
         let args = Cli::parse_from(&["pipa", "run", "my_contract"]);
 
         match args.command {
-            Some(Commands::Run { contract, all }) => {
+            Some(Commands::Run { contract, all, .. }) => {
                 assert_eq!(contract, Some("my_contract".to_string()));
                 assert!(!all);
             }
@@ -155,7 +736,7 @@ mod tests {
         let args = Cli::parse_from(&["pipa", "run", "--all"]);
 
         match args.command {
-            Some(Commands::Run { contract, all }) => {
+            Some(Commands::Run { contract, all, .. }) => {
                 assert_eq!(contract, None);
                 assert!(all);
             }
@@ -163,6 +744,278 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_run_all_with_jobs() {
+        let args = Cli::parse_from(["pipa", "run", "--all", "--jobs", "4"]);
+
+        match args.command {
+            Some(Commands::Run { all, jobs, .. }) => {
+                assert!(all);
+                assert_eq!(jobs, Some(4));
+            }
+            _ => panic!("Expected Run command"),
+        }
+
+        assert!(Cli::try_parse_from(["pipa", "run", "--all", "--jobs", "0"]).is_err());
+        assert!(Cli::try_parse_from(["pipa", "run", "orders", "--jobs", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_run_with_tags() {
+        let args = Cli::parse_from([
+            "pipa",
+            "run",
+            "--tag",
+            "pii",
+            "--tag",
+            "critical",
+            "--exclude-tag",
+            "slow",
+        ]);
+
+        match args.command {
+            Some(Commands::Run {
+                contract,
+                all,
+                tags,
+                exclude_tags,
+                ..
+            }) => {
+                assert_eq!(contract, None);
+                assert!(!all);
+                assert_eq!(tags, vec!["pii", "critical"]);
+                assert_eq!(exclude_tags, vec!["slow"]);
+            }
+            _ => panic!("Expected Run command"),
+        }
+
+        assert!(Cli::try_parse_from(["pipa", "run", "orders", "--tag", "pii"]).is_err());
+    }
+
+    #[test]
+    fn test_run_date() {
+        let args = Cli::parse_from(["pipa", "run", "orders", "--date", "2025-01-15"]);
+
+        match args.command {
+            Some(Commands::Run { date, .. }) => {
+                assert_eq!(date, parse_run_date("2025-01-15").ok());
+            }
+            _ => panic!("Expected Run command"),
+        }
+
+        assert!(Cli::try_parse_from(["pipa", "run", "orders", "--date", "01/15/2025"]).is_err());
+    }
+
+    #[test]
+    fn test_run_dry_run() {
+        let args = Cli::parse_from(["pipa", "run", "orders", "--dry-run"]);
+
+        match args.command {
+            Some(Commands::Run {
+                contract, dry_run, ..
+            }) => {
+                assert_eq!(contract, Some("orders".to_string()));
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Run command"),
+        }
+    }
+
+    #[test]
+    fn test_run_quiet() {
+        let args = Cli::parse_from(["pipa", "run", "--all", "-q"]);
+
+        match args.command {
+            Some(Commands::Run { quiet, .. }) => assert!(quiet),
+            _ => panic!("Expected Run command"),
+        }
+    }
+
+    #[test]
+    fn test_run_retries() {
+        let args = Cli::parse_from([
+            "pipa",
+            "run",
+            "orders",
+            "--retries",
+            "3",
+            "--retry-delay",
+            "500ms",
+        ]);
+
+        match args.command {
+            Some(Commands::Run {
+                retries,
+                retry_delay,
+                ..
+            }) => {
+                assert_eq!(retries, Some(3));
+                assert_eq!(retry_delay, Some(Duration::from_millis(500)));
+            }
+            _ => panic!("Expected Run command"),
+        }
+
+        assert!(Cli::try_parse_from(["pipa", "run", "orders", "--retry-delay", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_run_timeouts() {
+        let args = Cli::parse_from([
+            "pipa",
+            "run",
+            "--all",
+            "--timeout",
+            "30m",
+            "--fetch-timeout",
+            "90",
+        ]);
+
+        match args.command {
+            Some(Commands::Run {
+                timeout,
+                fetch_timeout,
+                ..
+            }) => {
+                assert_eq!(timeout, Some(Duration::from_secs(1800)));
+                assert_eq!(fetch_timeout, Some(Duration::from_secs(90)));
+            }
+            _ => panic!("Expected Run command"),
+        }
+
+        assert!(Cli::try_parse_from(["pipa", "run", "orders", "--timeout", "10d"]).is_err());
+    }
+
+    #[test]
+    fn test_run_results_file() {
+        let args = Cli::parse_from([
+            "pipa",
+            "run",
+            "orders",
+            "--output",
+            "json",
+            "--emit-results-file",
+            "results.json",
+        ]);
+
+        match args.command {
+            Some(Commands::Run {
+                output,
+                emit_results_file,
+                ..
+            }) => {
+                assert_eq!(output, Some(OutputFormat::Json));
+                assert_eq!(emit_results_file, Some(PathBuf::from("results.json")));
+            }
+            _ => panic!("Expected Run command"),
+        }
+
+        let all = [
+            "pipa",
+            "run",
+            "--all",
+            "--emit-results-file",
+            "results.json",
+        ];
+        assert!(Cli::try_parse_from(all).is_err());
+    }
+
+    #[test]
+    fn test_scheduler_start() {
+        let args = Cli::parse_from(["pipa", "scheduler", "start", "--dry-run"]);
+
+        match args.command {
+            Some(Commands::Scheduler {
+                scheduler_command: SchedulerCommands::Start { dry_run },
+            }) => assert!(dry_run),
+            _ => panic!("Expected Scheduler Start command"),
+        }
+    }
+
+    #[test]
+    fn test_quarantine_revalidate() {
+        let args = Cli::parse_from([
+            "pipa",
+            "quarantine",
+            "revalidate",
+            "orders_20250101_120000_quarantine.csv",
+            "--contract",
+            "orders",
+        ]);
+
+        match args.command {
+            Some(Commands::Quarantine {
+                quarantine_command:
+                    QuarantineCommands::Revalidate {
+                        file,
+                        contract,
+                        dry_run,
+                    },
+            }) => {
+                assert_eq!(file, "orders_20250101_120000_quarantine.csv");
+                assert_eq!(contract, "orders");
+                assert!(!dry_run);
+            }
+            _ => panic!("Expected Quarantine Revalidate command"),
+        }
+
+        assert!(Cli::try_parse_from(["pipa", "quarantine", "revalidate", "x.csv"]).is_err());
+    }
+
+    #[test]
+    fn test_watch() {
+        let args = Cli::parse_from(["pipa", "watch", "orders", "--interval", "10"]);
+
+        match args.command {
+            Some(Commands::Watch {
+                contracts,
+                interval,
+                debounce,
+                dry_run,
+            }) => {
+                assert_eq!(contracts, vec!["orders"]);
+                assert_eq!(interval, 10);
+                assert_eq!(debounce, 5);
+                assert!(!dry_run);
+            }
+            _ => panic!("Expected Watch command"),
+        }
+
+        assert!(Cli::try_parse_from(["pipa", "watch", "--interval", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_workspace_run_projects() {
+        let args = Cli::parse_from(["pipa", "workspace", "run", "--projects", "a,b", "-j", "2"]);
+
+        match args.command {
+            Some(Commands::Workspace {
+                workspace_command:
+                    WorkspaceCommands::Run {
+                        projects,
+                        file,
+                        jobs,
+                        ..
+                    },
+            }) => {
+                assert_eq!(projects, vec!["a", "b"]);
+                assert_eq!(file, None);
+                assert_eq!(jobs, Some(2));
+            }
+            _ => panic!("Expected Workspace Run command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "pipa",
+            "workspace",
+            "run",
+            "--projects",
+            "a",
+            "--file",
+            "w.toml"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_contract_validate() {
         let args = Cli::parse_from(&["pipa", "contract", "validate", "test.toml"]);
@@ -194,17 +1047,136 @@ mod tests {
 
     #[test]
     fn test_contract_show() {
-        let args = Cli::parse_from(&["pipa", "contract", "show", "my_contract"]);
+        let args = Cli::parse_from(&["pipa", "contract", "show", "my_contract", "--json"]);
 
         match args.command {
-            Some(Commands::Contract { contract_command }) => {
-                match contract_command {
-                    ContractCommands::Show { name } => {
-                        assert_eq!(name, "my_contract");
-                    }
-                    _ => panic!("Expected Show command"),
+            Some(Commands::Contract { contract_command }) => match contract_command {
+                ContractCommands::Show { name, json } => {
+                    assert_eq!(name, "my_contract");
+                    assert!(json);
                 }
+                _ => panic!("Expected Show command"),
+            },
+            _ => panic!("Expected Contract command"),
+        }
+    }
+
+    #[test]
+    fn test_contract_lint() {
+        let args = Cli::parse_from(["pipa", "contract", "lint", "orders", "--json"]);
+
+        match args.command {
+            Some(Commands::Contract {
+                contract_command: ContractCommands::Lint { name, json },
+            }) => {
+                assert_eq!(name, "orders");
+                assert!(json);
+            }
+            _ => panic!("Expected Contract Lint command"),
+        }
+    }
+
+    #[test]
+    fn test_contract_fmt() {
+        let args = Cli::parse_from(["pipa", "contract", "fmt", "orders", "users", "--check"]);
+
+        match args.command {
+            Some(Commands::Contract {
+                contract_command: ContractCommands::Fmt { names, check, fix },
+            }) => {
+                assert_eq!(names, vec!["orders", "users"]);
+                assert!(check);
+                assert!(!fix);
             }
+            _ => panic!("Expected Contract Fmt command"),
+        }
+    }
+
+    #[test]
+    fn test_contract_check() {
+        let args = Cli::parse_from(["pipa", "contract", "check", "orders", "--rows", "50"]);
+
+        match args.command {
+            Some(Commands::Contract {
+                contract_command: ContractCommands::Check { name, rows },
+            }) => {
+                assert_eq!(name, "orders");
+                assert_eq!(rows, 50);
+            }
+            _ => panic!("Expected Contract Check command"),
+        }
+    }
+
+    #[test]
+    fn test_contract_diff() {
+        let args = Cli::parse_from(["pipa", "contract", "diff", "orders", "old/orders.toml"]);
+
+        match args.command {
+            Some(Commands::Contract {
+                contract_command: ContractCommands::Diff { name, old, json },
+            }) => {
+                assert_eq!(name, "orders");
+                assert_eq!(old, "old/orders.toml");
+                assert!(!json);
+            }
+            _ => panic!("Expected Contract Diff command"),
+        }
+    }
+
+    #[test]
+    fn test_contract_export() {
+        let args = Cli::parse_from([
+            "pipa",
+            "contract",
+            "export",
+            "--format",
+            "json-schema",
+            "orders",
+        ]);
+
+        match args.command {
+            Some(Commands::Contract {
+                contract_command: ContractCommands::Export { name, format },
+            }) => {
+                assert_eq!(name, "orders");
+                assert_eq!(format, ExportFormat::JsonSchema);
+            }
+            _ => panic!("Expected Contract Export command"),
+        }
+
+        let unknown = Cli::try_parse_from(["pipa", "contract", "export", "--format", "avro", "x"]);
+        assert!(unknown.is_err());
+    }
+
+    #[test]
+    fn test_contract_generate() {
+        let args = Cli::parse_from([
+            "pipa",
+            "contract",
+            "generate",
+            "data/people.csv",
+            "--name",
+            "people",
+            "--stdout",
+        ]);
+
+        match args.command {
+            Some(Commands::Contract { contract_command }) => match contract_command {
+                ContractCommands::Generate {
+                    location,
+                    name,
+                    profile,
+                    stdout,
+                    force,
+                } => {
+                    assert_eq!(location, "data/people.csv");
+                    assert_eq!(name, Some("people".to_string()));
+                    assert_eq!(profile, None);
+                    assert!(stdout);
+                    assert!(!force);
+                }
+                _ => panic!("Expected Generate command"),
+            },
             _ => panic!("Expected Contract command"),
         }
     }
@@ -238,16 +1210,103 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_profile_add() {
+        let args = Cli::parse_from([
+            "pipa",
+            "profile",
+            "add",
+            "raw",
+            "--provider",
+            "s3",
+            "--set",
+            "region=us-east-1",
+            "--secret-env",
+            "secret_key=RAW_SECRET",
+        ]);
+
+        match args.command {
+            Some(Commands::Profile {
+                profile_command:
+                    ProfileCommands::Add {
+                        profile,
+                        provider,
+                        set,
+                        secret_env,
+                    },
+            }) => {
+                assert_eq!(profile, "raw");
+                assert_eq!(provider.as_deref(), Some("s3"));
+                assert_eq!(set, [("region".to_string(), "us-east-1".to_string())]);
+                assert_eq!(secret_env[0].1, "RAW_SECRET");
+            }
+            _ => panic!("Expected Profile Add command"),
+        }
+        assert!(Cli::try_parse_from(["pipa", "profile", "add", "raw", "--set", "region"]).is_err());
+    }
+
+    #[test]
+    fn test_profile_data_command() {
+        let args = Cli::parse_from([
+            "pipa",
+            "profile-data",
+            "s3://bucket/people.csv",
+            "--profile",
+            "s3_raw",
+            "--top",
+            "3",
+        ]);
+
+        match args.command {
+            Some(Commands::ProfileData {
+                location,
+                profile,
+                top,
+                json,
+            }) => {
+                assert_eq!(location, "s3://bucket/people.csv");
+                assert_eq!(profile, Some("s3_raw".to_string()));
+                assert_eq!(top, 3);
+                assert!(!json);
+            }
+            _ => panic!("Expected ProfileData command"),
+        }
+    }
+
+    #[test]
+    fn test_diff_data_command() {
+        let args = Cli::parse_from(["pipa", "diff-data", "orders", "--run", "a", "--run", "b"]);
+
+        match args.command {
+            Some(Commands::DiffData {
+                contract,
+                runs,
+                json,
+            }) => {
+                assert_eq!(contract, "orders");
+                assert_eq!(runs, vec!["a".to_string(), "b".to_string()]);
+                assert!(!json);
+            }
+            _ => panic!("Expected DiffData command"),
+        }
+    }
+
     #[test]
     fn test_health_command() {
         let args = Cli::parse_from(&["pipa", "health"]);
 
         match args.command {
-            Some(Commands::Health) => {
-                // Success - health command parsed correctly
+            Some(Commands::Health { deep }) => {
+                assert!(!deep);
             }
             _ => panic!("Expected Health command"),
         }
+
+        let args = Cli::parse_from(["pipa", "health", "--deep"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Health { deep: true })
+        ));
     }
 
     #[test]
@@ -255,35 +1314,166 @@ mod tests {
         let args = Cli::parse_from(&["pipa", "logs", "verify", "--date", "2025-01-15"]);
 
         match args.command {
-            Some(Commands::Logs { logs_command }) => {
-                match logs_command {
-                    LogsCommands::Verify { date, all } => {
-                        assert_eq!(date, Some("2025-01-15".to_string()));
-                        assert!(!all);
-                    }
+            Some(Commands::Logs { logs_command }) => match logs_command {
+                LogsCommands::Verify { date, all, json } => {
+                    assert_eq!(date, Some("2025-01-15".to_string()));
+                    assert!(!all);
+                    assert!(!json);
                 }
-            }
+                _ => panic!("Expected Logs Verify command"),
+            },
             _ => panic!("Expected Logs command"),
         }
     }
 
     #[test]
     fn test_logs_verify_all() {
-        let args = Cli::parse_from(&["pipa", "logs", "verify", "--all"]);
+        let args = Cli::parse_from(&["pipa", "logs", "verify", "--all", "--json"]);
 
         match args.command {
-            Some(Commands::Logs { logs_command }) => {
-                match logs_command {
-                    LogsCommands::Verify { date, all } => {
-                        assert_eq!(date, None);
-                        assert!(all);
-                    }
+            Some(Commands::Logs { logs_command }) => match logs_command {
+                LogsCommands::Verify { date, all, json } => {
+                    assert_eq!(date, None);
+                    assert!(all);
+                    assert!(json);
                 }
-            }
+                _ => panic!("Expected Logs Verify command"),
+            },
             _ => panic!("Expected Logs command"),
         }
     }
 
+    #[test]
+    fn test_logs_seal() {
+        let args = Cli::parse_from(["pipa", "logs", "seal", "--date", "2025-01-31"]);
+
+        match args.command {
+            Some(Commands::Logs {
+                logs_command: LogsCommands::Seal { date },
+            }) => assert_eq!(date.unwrap().to_string(), "2025-01-31"),
+            _ => panic!("Expected Logs Seal command"),
+        }
+
+        let args = Cli::parse_from(["pipa", "logs", "seal"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Logs {
+                logs_command: LogsCommands::Seal { date: None },
+            })
+        ));
+    }
+
+    #[test]
+    fn test_logs_ship() {
+        let args = Cli::parse_from(["pipa", "logs", "ship"]);
+
+        match args.command {
+            Some(Commands::Logs {
+                logs_command: LogsCommands::Ship,
+            }) => {}
+            _ => panic!("Expected Logs Ship command"),
+        }
+    }
+
+    #[test]
+    fn test_logs_export() {
+        let args = Cli::parse_from([
+            "pipa",
+            "logs",
+            "export",
+            "--format",
+            "parquet",
+            "--from",
+            "2025-01-01",
+            "--to",
+            "2025-01-31",
+            "--run-id",
+            "abc",
+            "-o",
+            "audit.parquet",
+        ]);
+
+        match args.command {
+            Some(Commands::Logs {
+                logs_command:
+                    LogsCommands::Export {
+                        output,
+                        format,
+                        from,
+                        to,
+                        run_id,
+                    },
+            }) => {
+                assert_eq!(output, PathBuf::from("audit.parquet"));
+                assert_eq!(format, Some(LogFormat::Parquet));
+                assert_eq!(from.unwrap().to_string(), "2025-01-01");
+                assert_eq!(to.unwrap().to_string(), "2025-01-31");
+                assert_eq!(run_id.as_deref(), Some("abc"));
+            }
+            _ => panic!("Expected Logs Export command"),
+        }
+
+        let bad_date =
+            Cli::try_parse_from(["pipa", "logs", "export", "--from", "Jan", "-o", "a.csv"]);
+        assert!(bad_date.is_err());
+    }
+
+    #[test]
+    fn test_logs_prune() {
+        let args = Cli::parse_from(["pipa", "logs", "prune", "--keep-days", "90", "--dry-run"]);
+
+        match args.command {
+            Some(Commands::Logs {
+                logs_command: LogsCommands::Prune { keep_days, dry_run },
+            }) => {
+                assert_eq!(keep_days, Some(90));
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Logs Prune command"),
+        }
+
+        let args = Cli::parse_from(["pipa", "logs", "prune"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Logs {
+                logs_command: LogsCommands::Prune {
+                    keep_days: None,
+                    dry_run: false
+                },
+            })
+        ));
+    }
+
+    #[test]
+    fn test_logs_rotate_key() {
+        let args = Cli::parse_from(["pipa", "logs", "rotate-key"]);
+
+        match args.command {
+            Some(Commands::Logs {
+                logs_command: LogsCommands::RotateKey,
+            }) => {}
+            _ => panic!("Expected Logs RotateKey command"),
+        }
+    }
+
+    #[test]
+    fn test_project_dir_is_global() {
+        let args = Cli::parse_from([
+            "pipa",
+            "--project-dir",
+            "pipelines/orders",
+            "contract",
+            "list",
+        ]);
+        assert_eq!(args.project_dir, Some(PathBuf::from("pipelines/orders")));
+
+        let args = Cli::parse_from(["pipa", "logs", "verify", "--project-dir", "orders"]);
+        assert_eq!(args.project_dir, Some(PathBuf::from("orders")));
+
+        let args = Cli::parse_from(["pipa", "init"]);
+        assert_eq!(args.project_dir, None);
+    }
+
     #[test]
     fn test_init_command() {
         let args = Cli::parse_from(&["pipa", "init"]);
@@ -313,4 +1503,104 @@ mod tests {
         let args = Cli::parse_from(&["pipa"]);
         assert!(args.command.is_none());
     }
+
+    #[test]
+    fn test_rules_list_command() {
+        let args = Cli::parse_from(["pipa", "rules", "list", "--json"]);
+
+        match args.command {
+            Some(Commands::Rules {
+                rules_command: RulesCommands::List { json },
+            }) => assert!(json),
+            _ => panic!("Expected Rules List command"),
+        }
+    }
+
+    #[test]
+    fn test_contract_baseline_command() {
+        let args = Cli::parse_from(["pipa", "contract", "baseline", "orders", "--run", "r1"]);
+
+        match args.command {
+            Some(Commands::Contract {
+                contract_command: ContractCommands::Baseline { name, run_id },
+            }) => {
+                assert_eq!(name, "orders");
+                assert_eq!(run_id, Some("r1".to_string()));
+            }
+            _ => panic!("Expected Contract Baseline command"),
+        }
+    }
+
+    #[test]
+    fn test_rules_describe_command() {
+        let args = Cli::parse_from(["pipa", "rules", "describe", "outlier_sigma"]);
+
+        match args.command {
+            Some(Commands::Rules {
+                rules_command: RulesCommands::Describe { rule },
+            }) => assert_eq!(rule, "outlier_sigma"),
+            _ => panic!("Expected Rules Describe command"),
+        }
+    }
+
+    #[test]
+    fn test_completions_command() {
+        let args = Cli::parse_from(["pipa", "completions", "zsh"]);
+
+        match args.command {
+            Some(Commands::Completions { shell }) => assert_eq!(shell, CompletionShell::Zsh),
+            _ => panic!("Expected Completions command"),
+        }
+        assert!(Cli::try_parse_from(["pipa", "completions", "tcsh"]).is_err());
+    }
+
+    #[test]
+    fn test_man_command() {
+        let args = Cli::parse_from(["pipa", "man", "--dir", "target/man"]);
+
+        match args.command {
+            Some(Commands::Man { dir }) => assert_eq!(dir, Some(PathBuf::from("target/man"))),
+            _ => panic!("Expected Man command"),
+        }
+    }
+
+    #[test]
+    fn test_global_default_overrides() {
+        let args = Cli::parse_from([
+            "pipa",
+            "rules",
+            "list",
+            "--output-format",
+            "json",
+            "--contracts-dir",
+            "etl/contracts",
+        ]);
+
+        assert_eq!(args.output_format, Some(OutputFormat::Json));
+        assert_eq!(args.contracts_dir, Some(PathBuf::from("etl/contracts")));
+        assert_eq!(args.logs_dir, None);
+        assert_eq!(args.env, None);
+        assert_eq!(
+            Cli::parse_from(["pipa", "run", "customers", "--env", "prod"]).env,
+            Some("prod".to_string())
+        );
+        assert!(Cli::try_parse_from(["pipa", "--output-format", "yaml", "rules", "list"]).is_err());
+
+        match Cli::parse_from(["pipa", "run", "--all"]).command {
+            Some(Commands::Run { jobs, .. }) => assert_eq!(jobs, None),
+            _ => panic!("Expected Run command"),
+        }
+    }
+
+    #[test]
+    fn test_contract_new_command() {
+        let args = Cli::parse_from(["pipa", "contract", "new", "--force"]);
+
+        match args.command {
+            Some(Commands::Contract {
+                contract_command: ContractCommands::New { force },
+            }) => assert!(force),
+            _ => panic!("Expected Contract New command"),
+        }
+    }
 }
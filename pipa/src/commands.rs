@@ -11,10 +11,20 @@
 /// - `commands/*` → contains the async functions that call into
 ///                  the engine (`pipa::*`) to do real work.
 
+/// Shell completion and man page commands.
+/// Implements `commands::completions::{completions, man}`, and the
+/// contract-name candidates used while completing.
+pub mod completions;
+
 /// Contract management commands.
 /// Implements `list`, `validate`, and `show` for contracts.
 pub mod contract;
 
+/// Run diffing command.
+/// Implements `commands::diff_data::run()`, which compares the
+/// statistics of two recorded runs of a contract.
+pub mod diff_data;
+
 /// System health check command.
 /// Implements `commands::health::run()`, which verifies
 /// environment setup and connector readiness.
@@ -31,11 +41,41 @@ pub mod init;
 pub mod logs;
 
 /// Profile management commands.
-/// Implements `commands::profile::{list, test}`, which
-/// enumerate profiles and test connectivity.
+/// Implements `commands::profile::{list, test, add, edit, remove}`,
+/// which enumerate, test, and maintain entries in `profiles.toml`.
 pub mod profile;
 
+/// Data profiling command.
+/// Implements `commands::profile_data::run()`, which computes
+/// per-column statistics for a source without a contract.
+pub mod profile_data;
+
+/// Quarantine commands.
+/// Implements `commands::quarantine::revalidate()`, which re-runs a
+/// contract on a quarantined file and releases it on pass.
+pub mod quarantine;
+
+/// Rule catalog commands.
+/// Implements `commands::rules::list()`, which prints every
+/// supported contract rule and its parameters.
+pub mod rules;
+
 /// Run commands.
 /// Implements `commands::run::{run_all, run_single}`, which
 /// execute contract validation workflows.
 pub mod run;
+
+/// Scheduler commands.
+/// Implements `commands::scheduler::{start, list}`, which run
+/// contracts on their `[schedule] cron` and list schedules.
+pub mod scheduler;
+
+/// Watch command.
+/// Implements `commands::watch::run()`, which polls contract
+/// sources and runs contracts as new files appear.
+pub mod watch;
+
+/// Workspace commands.
+/// Implements `commands::workspace::run()`, which runs several
+/// projects' contracts and reports a consolidated summary.
+pub mod workspace;
@@ -0,0 +1,121 @@
+use clap::CommandFactory;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Powershell, Zsh};
+use clap_complete::CompletionCandidate;
+use pipa::run::is_overlay;
+use pipa::workspace::Project;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::cli::{Cli, CompletionShell};
+
+/// Environment variable the completion script sets when it calls back
+/// into `pipa` for candidates (`COMPLETE=bash pipa -- pipa run or`).
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Print the completion script for `shell`.
+///
+/// The script asks `pipa` itself for candidates on every <Tab>, so
+/// subcommands and flags always match the installed binary and contract
+/// names are read from `contracts/` at completion time.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// source <(pipa completions bash)
+/// ```
+pub fn completions(shell: CompletionShell) -> bool {
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+        CompletionShell::Powershell => &Powershell,
+    };
+    // Call back into this binary, so completion works without `pipa` on PATH
+    let bin = std::env::current_exe()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "pipa".to_string());
+
+    let mut script = Vec::new();
+    let written = completer
+        .write_registration(COMPLETE_VAR, "pipa", "pipa", &bin, &mut script)
+        .and_then(|()| io::stdout().write_all(&script));
+    match written {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("❌ Failed to write completions: {}", e);
+            false
+        }
+    }
+}
+
+/// Render the man page.
+///
+/// Without `dir`, prints `pipa(1)` to stdout; with it, writes `pipa.1`
+/// and a page per subcommand (`pipa-run.1`, `pipa-contract-lint.1`, ...)
+/// into `dir`.
+pub fn man(dir: Option<&Path>) -> bool {
+    let rendered = match dir {
+        Some(dir) => std::fs::create_dir_all(dir)
+            .and_then(|()| clap_mangen::generate_to(Cli::command(), dir)),
+        None => {
+            let mut page = Vec::new();
+            clap_mangen::Man::new(Cli::command())
+                .render(&mut page)
+                .and_then(|()| io::stdout().write_all(&page))
+        }
+    };
+    match rendered {
+        Ok(()) => {
+            if let Some(dir) = dir {
+                println!("✅ Man pages written to {}", dir.display());
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to write man page: {}", e);
+            false
+        }
+    }
+}
+
+/// Contract names for completion: the stems of `*.toml` files in the
+/// project's contracts directory other than environment overlays, sorted.
+pub fn contract_names() -> Vec<CompletionCandidate> {
+    toml_stems(&Project::current().contracts_dir)
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+fn toml_stems(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter(|path| !is_overlay(path))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_toml_stems_lists_contracts_sorted() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("orders.toml"), "").unwrap();
+        fs::write(temp_dir.path().join("customers.toml"), "").unwrap();
+        fs::write(temp_dir.path().join("customers.prod.toml"), "").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "").unwrap();
+
+        assert_eq!(toml_stems(temp_dir.path()), vec!["customers", "orders"]);
+        assert!(toml_stems(&temp_dir.path().join("missing")).is_empty());
+    }
+}
@@ -1,6 +1,19 @@
-use pipa::contract::{get_contract, list_contracts, validate_contract};
+use crate::commands::profile::prompt;
+use crate::commands::run::RunStatus;
 use pipa::audit_logging::JsonlLogger;
+use pipa::contract::{
+    check_contract, diff_contracts, export_contract, format_contract, generate_contract,
+    get_contract, lint_contract, lint_toml, list_contracts, set_baseline, validate_contract,
+    ContractDiff, ContractDraft, ContractParseError, DraftColumn, ExportFormat, LintSeverity,
+};
+use pipa::profiler::profile_data;
+use pipa::run::{contract_path, contracts_dir, source_type_for_location};
 use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+
+/// Source types offered by `contract new`.
+const SOURCE_TYPES: &[&str] = &["local", "s3", "gcs", "azure", "kafka"];
 
 /// List all available contracts in the project.
 ///
@@ -42,34 +55,714 @@ pub async fn list() {
 /// ```
 pub async fn validate(file: &str) {
     let logger = JsonlLogger::default();
-    let (_validation, message) = validate_contract(&logger, file);
+    let (validation, message) = validate_contract(&logger, file);
+    println!("{}", message);
+    if let Some(error) = validation.error {
+        eprintln!("   {}", error);
+    }
+    if let Some(parse_error) = &validation.parse_error {
+        print_parse_context(file, parse_error);
+    }
+    for warning in &validation.warnings {
+        println!("⚠️  {}", warning);
+    }
+}
+
+/// Print the contract line a parse error points at, with a caret under
+/// the column, e.g.:
+/// ```text
+///    12 | validation = [{ rule = "not_nul" }]
+///       |                ^
+/// ```
+fn print_parse_context(file: &str, error: &ContractParseError) {
+    let (Some(line), Some(column)) = (error.line, error.column) else {
+        return;
+    };
+    let path = error
+        .path
+        .clone()
+        .unwrap_or_else(|| contract_path(file).display().to_string());
+    let Some(text) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| content.lines().nth(line - 1).map(str::to_string))
+    else {
+        return;
+    };
+    eprintln!("   {:>4} | {}", line, text);
+    eprintln!("   {:>4} | {}^", "", " ".repeat(column.saturating_sub(1)));
+}
+
+/// Lint a contract and print each finding with its suggested fix.
+///
+/// Delegates to `pipa::contract::lint_contract(name)`. Returns `false`
+/// when any finding is an error (or the contract can't be read), so
+/// `main.rs` can exit non-zero.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract lint <name> [--json]
+/// ```
+pub fn lint(name: &str, json: bool) -> bool {
+    let logger = JsonlLogger::default();
+    let (lint, message) = match lint_contract(&logger, name) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return false;
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&lint.diagnostics) {
+            Ok(out) => println!("{}", out),
+            Err(e) => eprintln!("❌ Failed to serialize lint findings: {}", e),
+        }
+        return !lint.has_errors();
+    }
+
     println!("{}", message);
+    for diagnostic in &lint.diagnostics {
+        let icon = match diagnostic.severity {
+            LintSeverity::Error => "❌",
+            LintSeverity::Warning => "⚠️ ",
+        };
+        println!("{} {}", icon, diagnostic);
+        println!("   help: {}", diagnostic.suggestion);
+    }
+    !lint.has_errors()
+}
+
+/// Rewrite contracts in canonical form.
+///
+/// Delegates to `pipa::contract::format_contract()` for each name, or for
+/// every contract in `contracts/` when `names` is empty, and writes back
+/// the ones that changed. With `check`, nothing is written; the contracts
+/// that would change are listed and the return value is `false` if there
+/// are any. Misspelled rule names are listed either way and corrected
+/// with `fix`.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract fmt [names...] [--check] [--fix]
+/// ```
+pub fn fmt(names: &[String], check: bool, fix: bool) -> bool {
+    let logger = JsonlLogger::default();
+    let names = if names.is_empty() {
+        match list_contracts(&logger) {
+            Ok((list, _)) => {
+                let mut names = list.contracts;
+                names.sort();
+                names
+            }
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                return false;
+            }
+        }
+    } else {
+        names.to_vec()
+    };
+
+    let mut ok = true;
+    for name in &names {
+        let formatted = match format_contract(&logger, name, fix) {
+            Ok((formatted, _)) => formatted,
+            Err(e) => {
+                eprintln!("❌ {}: {}", name, e);
+                ok = false;
+                continue;
+            }
+        };
+        let path = contract_path(name);
+        for rule_fix in &formatted.fixes {
+            let icon = if fix { "🔧" } else { "⚠️ " };
+            println!(
+                "{} {}: {}: rule '{}' -> '{}'",
+                icon, name, rule_fix.location, rule_fix.from, rule_fix.to
+            );
+        }
+        if !formatted.changed {
+            continue;
+        }
+        if check {
+            println!("❌ {} is not formatted", path.display());
+            ok = false;
+        } else if let Err(e) = fs::write(&path, &formatted.toml) {
+            eprintln!("❌ Failed to write {}: {}", path.display(), e);
+            ok = false;
+        } else {
+            println!("✅ Formatted {}", path.display());
+        }
+    }
+    if ok && check {
+        println!("✅ {} contract(s) formatted", names.len());
+    }
+    ok
+}
+
+/// Check a contract's declared columns against a sample of its source.
+///
+/// Delegates to `pipa::contract::check_contract(name, rows)`, which reads
+/// the first `rows` rows and compares them with `[[columns]]`. Prints the
+/// missing columns, extra columns, and dtype mismatches, and returns the
+/// exit status: `RuleFailures` when a column is missing or mismatched.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract check <name> [--rows N]
+/// ```
+pub async fn check(name: &str, rows: usize) -> RunStatus {
+    let logger = JsonlLogger::default();
+    let (check, message) = match check_contract(&logger, name, rows).await {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("❌ Failed to check contract '{}': {}", name, e);
+            return RunStatus::from_error(&e);
+        }
+    };
+
+    println!("{}", message);
+    println!(
+        "  → {} ({} rows sampled)",
+        check.location, check.rows_sampled
+    );
+    for column in &check.missing {
+        println!("❌ missing column '{}'", column);
+    }
+    for mismatch in &check.mismatches {
+        println!(
+            "❌ column '{}' is {} in the data but declared {}",
+            mismatch.column, mismatch.actual, mismatch.declared
+        );
+    }
+    for column in &check.extra {
+        println!("⚠️  extra column '{}' is not declared", column);
+    }
+
+    if check.passed() {
+        println!("✅ Schema matches the contract");
+        RunStatus::Passed
+    } else {
+        RunStatus::RuleFailures
+    }
+}
+
+/// Compare an older version of a contract with the current one.
+///
+/// Delegates to `pipa::contract::diff_contracts(name, old)`, which diffs
+/// the TOML at `old` against `contracts/<name>.toml` and reports added and
+/// removed columns, added/removed/changed rules, and changed settings.
+/// Prints each version's content hash, which matches the `contract_hash`
+/// in the audit entries of runs made with that version.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract diff <name> <old.toml> [--json]
+/// ```
+pub fn diff(name: &str, old: &str, json: bool) {
+    let logger = JsonlLogger::default();
+    match diff_contracts(&logger, name, old) {
+        Ok((diff, message)) => {
+            if json {
+                match serde_json::to_string_pretty(&diff) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => eprintln!("❌ Failed to serialize diff: {}", e),
+                }
+            } else {
+                println!("{}", message);
+                print_contract_diff(&diff);
+            }
+        }
+        Err(e) => eprintln!("❌ Failed to diff contract: {}", e),
+    }
+}
+
+/// Print a human-readable contract diff.
+fn print_contract_diff(diff: &ContractDiff) {
+    println!(
+        "   {} ({}) → {} ({})",
+        diff.from_version,
+        short_hash(&diff.from_hash),
+        diff.to_version,
+        short_hash(&diff.to_hash)
+    );
+    for change in &diff.changes {
+        println!("   {}", change);
+    }
+    if diff.is_empty() {
+        println!("   no changes");
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(12)]
+}
+
+/// Export a contract to another schema language.
+///
+/// Delegates to `pipa::contract::export_contract(name, format)` and prints
+/// the document to stdout, so it can be redirected to a file. Rules with no
+/// equivalent in the target format are listed on stderr.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract export [--format json-schema] <name>
+/// ```
+pub fn export(name: &str, format: ExportFormat) {
+    let logger = JsonlLogger::default();
+    let export = match export_contract(&logger, name, format) {
+        Ok((export, _)) => export,
+        Err(e) => {
+            eprintln!("❌ Failed to export contract: {}", e);
+            return;
+        }
+    };
+    match serde_json::to_string_pretty(&export.document) {
+        Ok(out) => println!("{}", out),
+        Err(e) => eprintln!("❌ Failed to serialize export: {}", e),
+    }
+    for rule in &export.skipped {
+        eprintln!("⚠️  not exported: {}", rule);
+    }
 }
 
 /// Show details of a specific contract by name.
 ///
-/// Delegates to `pipa::contract::get_contract(name)`, which returns
-/// metadata about the contract. If the contract exists, this function
-/// also reads and prints the raw TOML file contents for inspection.
+/// Delegates to `pipa::contract::get_contract(name)`, which parses the
+/// contract with `${...}` references resolved, and prints what a run will
+/// use: the `[source]`, `[destination]`, and `[quarantine]` blocks, a table
+/// of columns with each rule and its parameters, and the remaining
+/// sections. With `json`, prints the parsed contract as JSON instead.
+/// Returns `false` when the contract is missing or fails to parse.
 ///
 /// Called from `main.rs` when the user runs:
 /// ```bash
-/// pipa contract show <name>
+/// pipa contract show <name> [--json]
 /// ```
-pub async fn show(name: &str) {
+pub async fn show(name: &str, json: bool) -> bool {
     let logger = JsonlLogger::default();
-    let (contract_info, message) = get_contract(&logger, name);
+    let (info, message) = get_contract(&logger, name);
+
+    if json {
+        match serde_json::to_string_pretty(&info) {
+            Ok(out) => println!("{}", out),
+            Err(e) => eprintln!("❌ Failed to serialize contract: {}", e),
+        }
+        return info.contract.is_some();
+    }
+
     println!("{}", message);
+    let Some(contract) = &info.contract else {
+        match &info.error {
+            Some(e) => eprintln!("❌ {}", e),
+            None => eprintln!("❌ Contract '{}' not found", name),
+        }
+        return false;
+    };
+    let value = match serde_json::to_value(contract) {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("❌ Failed to render contract: {}", e);
+            return false;
+        }
+    };
 
-    if contract_info.exists {
-        let path = format!("contracts/{}.toml", name);
-        match fs::read_to_string(&path) {
-            Ok(content) => {
-                println!("📄 Contract: {}", name);
-                println!("\n{}", content);
+    let meta = &contract.contract;
+    print!("📄 {} v{}", meta.name, meta.version);
+    if !meta.tags.is_empty() {
+        print!("  [{}]", meta.tags.join(", "));
+    }
+    println!();
+    for (flag, set) in [
+        ("strict_types", meta.strict_types),
+        ("strict_rules", meta.strict_rules),
+    ] {
+        if set {
+            println!("   {}", flag);
+        }
+    }
+
+    println!();
+    for section in ["source", "destination", "quarantine"] {
+        println!("{:<13}{}", section, params(&value[section]));
+    }
+
+    let mut rows = Vec::new();
+    if contract.file.is_some() {
+        rule_rows(&mut rows, "[file]", "", &value["file"]["validation"]);
+    }
+    for column in value["columns"].as_array().into_iter().flatten() {
+        let name = column["name"].as_str().unwrap_or_default();
+        let dtype = column["dtype"].as_str().unwrap_or("-");
+        rule_rows(&mut rows, name, dtype, &column["validation"]);
+    }
+    println!();
+    print_table(["COLUMN", "DTYPE", "RULE", "PARAMETERS"], &rows);
+
+    let channels = &value["notifications"]["channels"];
+    let others = [
+        ("headers", &value["headers"]),
+        ("mapping", &value["mapping"]),
+        ("transform", &value["transform"]),
+        ("compound_unique", &value["compound_unique"]),
+        ("sources", &value["sources"]),
+        ("combine", &value["combine"]),
+        ("limits", &value["limits"]),
+        ("retry", &value["retry"]),
+        ("sampling", &value["sampling"]),
+        ("streaming", &value["streaming"]),
+        ("schedule", &value["schedule"]),
+        ("notifications.channels", channels),
+    ];
+    let shown: Vec<_> = others
+        .into_iter()
+        .filter(|(_, section)| !section.is_null())
+        .collect();
+    if !shown.is_empty() {
+        println!();
+    }
+    for (key, section) in shown {
+        match section {
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    println!("[[{}]] {}", key, params(item));
+                }
             }
-            Err(_) => eprintln!("❌ Failed to read contract content"),
+            section => println!("[{}] {}", key, params(section)),
+        }
+    }
+    true
+}
+
+/// Table rows for a serialized `validation` list, with the column and
+/// dtype on the first row only.
+fn rule_rows(rows: &mut Vec<[String; 4]>, column: &str, dtype: &str, rules: &serde_json::Value) {
+    let rules = rules.as_array().map(Vec::as_slice).unwrap_or_default();
+    if rules.is_empty() {
+        rows.push([column.into(), dtype.into(), "-".into(), String::new()]);
+    }
+    for (i, rule) in rules.iter().enumerate() {
+        let mut rule = rule.clone();
+        let name = rule
+            .as_object_mut()
+            .and_then(|fields| fields.remove("rule"))
+            .and_then(|name| name.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let (column, dtype) = if i == 0 { (column, dtype) } else { ("", "") };
+        rows.push([column.into(), dtype.into(), name, params(&rule)]);
+    }
+}
+
+/// `key=value` pairs for an object's set fields (values as compact JSON),
+/// identifying keys first; `-` for an unset section.
+fn params(value: &serde_json::Value) -> String {
+    const LEADING: [&str; 6] = ["name", "type", "location", "profile", "min", "max"];
+    match prune_empty(value) {
+        serde_json::Value::Null => "-".to_string(),
+        serde_json::Value::Object(fields) => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort_by_key(|(key, _)| {
+                LEADING
+                    .iter()
+                    .position(|leading| leading == key)
+                    .unwrap_or(LEADING.len())
+            });
+            fields
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        other => other.to_string(),
+    }
+}
+
+/// `value` without null fields and empty objects.
+fn prune_empty(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| (key.clone(), prune_empty(value)))
+            .filter(|(_, value)| {
+                !value.is_null() && !value.as_object().is_some_and(|fields| fields.is_empty())
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items.iter().map(prune_empty).collect(),
+        other => other.clone(),
+    }
+}
+
+fn print_table(header: [&str; 4], rows: &[[String; 4]]) {
+    let mut widths = header.map(str::len);
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: [&str; 4]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(header);
+    for row in rows {
+        line([&row[0], &row[1], &row[2], &row[3]]);
+    }
+}
+
+/// Generate a draft contract from a sample data file.
+///
+/// Delegates to `pipa::contract::generate_contract()`, which profiles the
+/// sample and renders a TOML contract with inferred columns and suggested
+/// rules. The draft is written to `contracts/<name>.toml` (refusing to
+/// overwrite unless `force` is set), or printed when `stdout` is set.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract generate <location> [--name <name>] [--profile <p>] [--stdout] [--force]
+/// ```
+pub async fn generate(
+    location: &str,
+    name: Option<&str>,
+    profile: Option<&str>,
+    stdout: bool,
+    force: bool,
+) {
+    let logger = JsonlLogger::default();
+    let (draft, message) = match generate_contract(&logger, location, name, profile).await {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("❌ Failed to generate contract: {}", e);
+            return;
+        }
+    };
+
+    if stdout {
+        print!("{}", draft.toml);
+        return;
+    }
+
+    match write_contract(&draft.name, &draft.toml, force) {
+        Ok(path) => {
+            println!("{}", message);
+            println!("  → {} ({} columns)", path.display(), draft.columns);
+        }
+        Err(e) => eprintln!("❌ {}", e),
+    }
+}
+
+/// Create a contract interactively.
+///
+/// Asks for the contract name, the source type and location, and a
+/// connection profile, then reads the columns from a sample file (with
+/// the rules `contract generate` would suggest) or asks for each column.
+/// The rendered contract is linted before it is written to
+/// `contracts/<name>.toml` (refusing to overwrite unless `force` is set).
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract new [--force]
+/// ```
+pub async fn new(force: bool) {
+    if !io::stdin().is_terminal() {
+        eprintln!("❌ stdin is not a terminal; use `pipa contract generate <sample>` instead");
+        return;
+    }
+    let draft = match prompt_draft(force).await {
+        Ok(draft) => draft,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return;
+        }
+    };
+
+    let toml = draft.render();
+    let diagnostics = lint_toml(&toml);
+    for diagnostic in &diagnostics {
+        let icon = match diagnostic.severity {
+            LintSeverity::Error => "❌",
+            LintSeverity::Warning => "⚠️ ",
+        };
+        println!("{} {}", icon, diagnostic);
+        println!("   help: {}", diagnostic.suggestion);
+    }
+    let has_errors = diagnostics
+        .iter()
+        .any(|d| d.severity == LintSeverity::Error);
+    if has_errors && !matches!(confirm("Write the contract anyway?", false), Ok(true)) {
+        println!("Cancelled.");
+        return;
+    }
+
+    match write_contract(&draft.name, &toml, force) {
+        Ok(path) => {
+            println!("✅ Contract created");
+            println!("  → {} ({} columns)", path.display(), draft.columns.len());
+        }
+        Err(e) => eprintln!("❌ {}", e),
+    }
+}
+
+/// Ask for everything a `ContractDraft` needs.
+async fn prompt_draft(force: bool) -> Result<ContractDraft, String> {
+    let name = prompt("Contract name", None)?;
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains(char::is_whitespace) {
+        return Err(format!("'{}' is not a valid contract name", name));
+    }
+    if contract_path(&name).exists() && !force {
+        return Err(format!(
+            "{} already exists (use --force to overwrite)",
+            contract_path(&name).display()
+        ));
+    }
+
+    let location = prompt(
+        "Source location (path, s3://, gs://, or Azure blob URL)",
+        None,
+    )?;
+    if location.is_empty() {
+        return Err("A source location is required".to_string());
+    }
+    let source_type = prompt(
+        &format!("Source type ({})", SOURCE_TYPES.join(", ")),
+        Some(source_type_for_location(&location)),
+    )?;
+    if !SOURCE_TYPES.contains(&source_type.as_str()) {
+        return Err(format!("Unknown source type '{}'", source_type));
+    }
+    let profile = match source_type.as_str() {
+        "local" => None,
+        _ => Some(prompt("Connection profile (blank for none)", None)?).filter(|p| !p.is_empty()),
+    };
+
+    let mut columns = None;
+    if confirm("Read the columns from a sample file?", true)? {
+        let sample = prompt("Sample file", Some(&location))?;
+        columns = sniff_columns(&name, &sample, profile.as_deref()).await?;
+    }
+    let columns = match columns {
+        Some(columns) => columns,
+        None => prompt_columns()?,
+    };
+
+    Ok(ContractDraft {
+        name,
+        source_type,
+        location,
+        profile,
+        columns,
+    })
+}
+
+/// Columns of a sample, with its suggested rules if the operator keeps
+/// them; `None` when the sample can't be read, to ask for columns instead.
+async fn sniff_columns(
+    name: &str,
+    sample: &str,
+    profile: Option<&str>,
+) -> Result<Option<Vec<DraftColumn>>, String> {
+    let logger = JsonlLogger::default();
+    let report = match profile_data(&logger, sample, profile, 0).await {
+        Ok((report, _)) => report,
+        Err(e) => {
+            eprintln!("⚠️  Couldn't read {}: {}", sample, e);
+            return Ok(None);
+        }
+    };
+
+    let mut columns = ContractDraft::from_profile(name, &report, profile).columns;
+    println!("Found {} columns in {} rows:", columns.len(), report.rows);
+    for column in &columns {
+        println!(
+            "  {} ({}) {}",
+            column.name,
+            column.dtype.as_deref().unwrap_or("?"),
+            column.rules.join(", ")
+        );
+    }
+    if columns.iter().any(|c| !c.rules.is_empty()) && !confirm("Keep the suggested rules?", true)? {
+        columns.iter_mut().for_each(|c| c.rules.clear());
+    }
+    Ok(Some(columns))
+}
+
+/// Ask for columns one at a time until a blank name.
+fn prompt_columns() -> Result<Vec<DraftColumn>, String> {
+    let mut columns = Vec::new();
+    loop {
+        let name = prompt("Column name (blank to finish)", None)?;
+        if name.is_empty() {
+            return Ok(columns);
+        }
+        let dtype = prompt("  dtype, e.g. Int64, Float64, String (blank to skip)", None)?;
+        let mut column = DraftColumn {
+            name,
+            dtype: (!dtype.is_empty()).then_some(dtype),
+            ..Default::default()
+        };
+        if confirm("  Required (not_null)?", false)? {
+            column = column.with_rule("not_null");
+        }
+        if confirm("  Unique?", false)? {
+            column = column.with_rule("unique");
+        }
+        columns.push(column);
+    }
+}
+
+/// Ask a yes/no question; a blank answer is `default`.
+fn confirm(question: &str, default: bool) -> Result<bool, String> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let answer = prompt(&format!("{} [{}]", question, hint), None)?;
+    Ok(match answer.to_ascii_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
+}
+
+/// Write a contract to `contracts/<name>.toml`, refusing to overwrite an
+/// existing one unless `force` is set.
+fn write_contract(name: &str, toml: &str, force: bool) -> Result<PathBuf, String> {
+    let path = contract_path(name);
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        ));
+    }
+    fs::create_dir_all(contracts_dir())
+        .and_then(|_| fs::write(&path, toml))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Pin a recorded run as the drift baseline for a contract.
+///
+/// Delegates to `pipa::contract::set_baseline()`. Without `run_id` the most
+/// recent run in `.pipa/state/<name>/history.jsonl` is pinned; `drift` rules
+/// with `against = "baseline"` then compare against it.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa contract baseline <name> [--run <run_id>]
+/// ```
+pub async fn baseline(name: &str, run_id: Option<&str>) {
+    let logger = JsonlLogger::default();
+    match set_baseline(&logger, name, run_id) {
+        Ok((run, message)) => {
+            println!("{}", message);
+            println!(
+                "  → run {} ({} rows, {} columns)",
+                run.run_id,
+                run.rows,
+                run.columns.len()
+            );
         }
+        Err(e) => eprintln!("❌ Failed to set baseline: {}", e),
     }
 }
 
@@ -0,0 +1,72 @@
+use pipa::audit_logging::JsonlLogger;
+use pipa::contract::{diff_runs, RunDiff};
+
+/// Compare two recorded runs of a contract.
+///
+/// Delegates to `pipa::contract::diff_runs()`, which reads the column
+/// statistics stored in `.pipa/state/<contract>/history.jsonl` and reports
+/// row count deltas, added/removed columns, and per-column statistic shifts.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa diff-data <contract> [--run <a>] [--run <b>] [--json]
+/// ```
+pub fn run(contract: &str, runs: &[String], json: bool) {
+    let logger = JsonlLogger::default();
+    match diff_runs(&logger, contract, runs) {
+        Ok((diff, message)) => {
+            if json {
+                match serde_json::to_string_pretty(&diff) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => eprintln!("❌ Failed to serialize diff: {}", e),
+                }
+            } else {
+                println!("{}", message);
+                print_diff(&diff);
+            }
+        }
+        Err(e) => eprintln!("❌ Failed to diff runs: {}", e),
+    }
+}
+
+/// Print a human-readable run diff.
+fn print_diff(diff: &RunDiff) {
+    println!("   {} → {}", diff.from_run, diff.to_run);
+    println!(
+        "   rows: {} → {} ({:+})",
+        diff.rows_from, diff.rows_to, diff.row_delta
+    );
+    for column in &diff.added_columns {
+        println!("   + column {}", column);
+    }
+    for column in &diff.removed_columns {
+        println!("   - column {}", column);
+    }
+    for change in &diff.changes {
+        println!(
+            "   ~ {}.{}: {} → {}",
+            change.column,
+            change.metric,
+            format_value(change.from),
+            format_value(change.to)
+        );
+    }
+    if diff.is_empty() {
+        println!("   no changes");
+    }
+}
+
+fn format_value(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| format!("{:.4}", v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(Some(0.5)), "0.5000");
+        assert_eq!(format_value(None), "-");
+    }
+}
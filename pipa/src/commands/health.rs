@@ -1,5 +1,5 @@
-use pipa::health::run_health_check;
 use pipa::audit_logging::JsonlLogger;
+use pipa::health::{run_deep_health_check, run_health_check};
 
 /// Run a system health check.
 ///
@@ -7,13 +7,21 @@ use pipa::audit_logging::JsonlLogger;
 /// environment and connector checks. The `true` flag indicates that
 /// results should also be logged to the console.
 ///
+/// With `deep`, delegates to `pipa::health::run_deep_health_check()`
+/// instead, which also tests every profile's connectivity concurrently
+/// and reports per-profile latency and failure reasons.
+///
 /// Called from `main.rs` when the user runs:
 /// ```bash
-/// pipa health
+/// pipa health [--deep]
 /// ```
-pub async fn run() {
+pub async fn run(deep: bool) {
     // Run the health check; ignore the status object here, just print the message
     let logger = JsonlLogger::default();
-    let (_status, message) = run_health_check(&logger, true);
+    let (_status, message) = if deep {
+        run_deep_health_check(&logger, true).await
+    } else {
+        run_health_check(&logger, true)
+    };
     println!("{}", message);
 }
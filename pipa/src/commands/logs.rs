@@ -1,23 +1,39 @@
 use pipa::audit_logging::JsonlLogger;
-use pipa::logs::{verify_logs, FileStatus};
+use pipa::logs::{
+    export_logs, prune_logs, rotate_ledger_key, seal_logs, ship_logs, verify_logs, FileStatus,
+    FileVerification, LogFormat,
+};
+use pipa::run::NaiveDate;
+use std::path::Path;
 
 /// Verify log integrity for a given date or for all logs.
 ///
 /// Delegates to `pipa::logs::verify_logs(date)`, which performs
 /// the actual verification of log files. Prints the engine's
 /// summary message, then iterates over each file and displays
-/// its verification status with a symbol and label.
+/// its verification status with a symbol and label, plus the
+/// conflicting ledger line and a remediation hint for each failure.
+/// With `json`, prints the result as JSON instead. Returns whether
+/// every sealed log checked out.
 ///
 /// Called from `main.rs` when the user runs:
 /// ```bash
-/// pipa logs verify --date YYYY-MM-DD
-/// pipa logs verify --all
+/// pipa logs verify --date YYYY-MM-DD [--json]
+/// pipa logs verify --all [--json]
 /// ```
-pub async fn verify(date: Option<&str>, _all: bool) {
+pub async fn verify(date: Option<&str>, _all: bool, json: bool) -> bool {
     // Run verification via engine API
     let logger = JsonlLogger::default();
     let (verification, message) = verify_logs(&logger, date);
 
+    if json {
+        match serde_json::to_string_pretty(&verification) {
+            Ok(out) => println!("{}", out),
+            Err(e) => eprintln!("❌ Failed to serialize verification: {}", e),
+        }
+        return !verification.has_failures();
+    }
+
     // Print engine-provided summary message
     println!("{}", message);
 
@@ -29,9 +45,224 @@ pub async fn verify(date: Option<&str>, _all: bool) {
             FileStatus::Missing => ("❓", "missing"),
             FileStatus::Malformed => ("⚠️", "malformed"),
             FileStatus::Unsealed => ("🕒", "unsealed"),
+            FileStatus::ChainBroken { .. } => ("⛓️", "chain broken"),
+            FileStatus::Pruned => ("🗄️", "pruned"),
         };
 
-        println!("{} {} {}", symbol, file.filename, status_str);
+        match file.status {
+            FileStatus::ChainBroken { line } => {
+                println!(
+                    "{} {} {} at line {}",
+                    symbol, file.filename, status_str, line
+                )
+            }
+            FileStatus::Verified if file.timestamp.is_some() => {
+                println!("{} {} {} (timestamped)", symbol, file.filename, status_str)
+            }
+            _ => println!("{} {} {}", symbol, file.filename, status_str),
+        }
+        print_remediation(file);
+    }
+    !verification.has_failures()
+}
+
+/// Print where a failed file conflicts with the ledger and how to fix it.
+fn print_remediation(file: &FileVerification) {
+    let ledger_line = file
+        .ledger_line
+        .map(|line| format!("ledger line {}", line))
+        .unwrap_or_else(|| "the ledger".to_string());
+    match file.status {
+        FileStatus::Mismatched => {
+            println!(
+                "   {} sealed sha256 {}, file now hashes to {}",
+                ledger_line,
+                file.stored_hash.as_deref().unwrap_or("?"),
+                file.computed_hash.as_deref().unwrap_or("?")
+            );
+            println!("   hint: the file was altered after sealing; restore it from a shipped copy");
+        }
+        FileStatus::Missing => {
+            println!("   {} records it as sealed", ledger_line);
+            println!("   hint: restore the file from a shipped copy or backup");
+        }
+        FileStatus::Malformed => {
+            println!("   {} has fewer than 3 fields", ledger_line);
+            println!("   hint: restore the ledger from a backup; it was truncated or edited");
+        }
+        FileStatus::ChainBroken { .. } => {
+            println!(
+                "   hint: an entry was edited, removed, or reordered; restore it from a backup"
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Seal daily logs into the hash ledger.
+///
+/// Delegates to `pipa::logs::seal_logs()`, then lists each file sealed (or
+/// already sealed) and any sink it failed to ship to. Returns whether the
+/// logs could be sealed.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa logs seal [--date YYYY-MM-DD]
+/// ```
+pub fn seal(date: Option<NaiveDate>) -> bool {
+    let logger = JsonlLogger::default();
+    match seal_logs(&logger, date) {
+        Ok((sealing, message)) => {
+            println!("{}", message);
+            for file in &sealing.sealed {
+                println!("🔒 {} sealed", file);
+            }
+            if let Some(file) = &sealing.already_sealed {
+                println!("✅ {} was already sealed", file);
+            }
+            for file in &sealing.shipped {
+                if let Some(e) = &file.error {
+                    eprintln!(
+                        "⚠️  Failed to ship {} to {}: {}",
+                        file.filename, file.sink, e
+                    );
+                }
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to seal logs: {}", e);
+            false
+        }
+    }
+}
+
+/// Remove sealed daily logs past the retention period.
+///
+/// Delegates to `pipa::logs::prune_logs()`, which deletes or archives each
+/// expired sealed log and records it in the ledger, then lists the files
+/// pruned and any expired file kept because it is unsealed or altered.
+/// Returns whether the policy could be applied.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa logs prune [--keep-days N] [--dry-run]
+/// ```
+pub fn prune(keep_days: Option<u32>, dry_run: bool) -> bool {
+    let logger = JsonlLogger::default();
+    match prune_logs(&logger, keep_days, dry_run) {
+        Ok((pruning, message)) => {
+            println!("{}", message);
+            for file in &pruning.pruned {
+                match &file.archived_to {
+                    Some(target) => println!("🗄️  {} → {}", file.filename, target.display()),
+                    None => println!("🗑️  {} deleted", file.filename),
+                }
+            }
+            for file in &pruning.unsealed {
+                eprintln!("⚠️  Kept {}: not sealed", file);
+            }
+            for file in &pruning.mismatched {
+                eprintln!("⚠️  Kept {}: hash no longer matches the ledger", file);
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to prune logs: {}", e);
+            false
+        }
+    }
+}
+
+/// Ship sealed daily logs to the storage sinks in `pipa.toml`.
+///
+/// Delegates to `pipa::logs::ship_logs()`, which uploads each sealed file
+/// not yet recorded in `logs/shipped.txt`, and prints one line per upload.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa logs ship
+/// ```
+pub async fn ship() {
+    let logger = JsonlLogger::default();
+    match ship_logs(&logger).await {
+        Ok((shipped, message)) => {
+            println!("{}", message);
+            for file in &shipped {
+                match &file.error {
+                    None => println!("✅ {} → {}", file.filename, file.sink),
+                    Some(e) => println!("❌ {} → {}: {}", file.filename, file.sink, e),
+                }
+            }
+        }
+        Err(e) => eprintln!("❌ Failed to ship logs: {}", e),
+    }
+}
+
+/// Export audit entries as a CSV or Parquet table.
+///
+/// Delegates to `pipa::logs::export_logs()`, taking the format from
+/// `--format` or else the output file's extension, and prints the summary
+/// plus a warning for any malformed lines skipped. Returns whether the
+/// file was written.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa logs export --format parquet --from 2025-01-01 --to 2025-01-31 -o audit.parquet
+/// pipa logs export --run-id 0b5c6d1e-... -o run.csv
+/// ```
+pub fn export(
+    output: &Path,
+    format: Option<LogFormat>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    run_id: Option<&str>,
+) -> bool {
+    let Some(format) = format.or_else(|| LogFormat::from_path(output)) else {
+        eprintln!(
+            "❌ Can't tell the format of {}; pass --format csv or --format parquet",
+            output.display()
+        );
+        return false;
+    };
+    let logger = JsonlLogger::default();
+    match export_logs(&logger, format, from, to, run_id, output) {
+        Ok((export, message)) => {
+            println!("{}", message);
+            if export.malformed > 0 {
+                eprintln!("⚠️  Skipped {} malformed log lines", export.malformed);
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to export logs: {}", e);
+            false
+        }
+    }
+}
+
+/// Re-encrypt the hash ledger under a new key.
+///
+/// Delegates to `pipa::logs::rotate_ledger_key()`, which stores the new
+/// key through the `[ledger_key]` provider in `pipa.toml`. Returns whether
+/// the key was rotated.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa logs rotate-key
+/// ```
+pub fn rotate_key() -> bool {
+    let logger = JsonlLogger::default();
+    match rotate_ledger_key(&logger) {
+        Ok((rotation, message)) => {
+            println!("{}", message);
+            println!("   {} ledger entries re-encrypted", rotation.entries);
+            true
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to rotate the ledger key: {}", e);
+            false
+        }
     }
 }
 
@@ -48,6 +279,8 @@ mod tests {
             FileStatus::Missing => ("❓", "missing"),
             FileStatus::Malformed => ("⚠️", "malformed"),
             FileStatus::Unsealed => ("🕒", "unsealed"),
+            FileStatus::ChainBroken { .. } => ("⛓️", "chain broken"),
+            FileStatus::Pruned => ("🗄️", "pruned"),
         };
 
         assert_eq!(symbol, "✅");
@@ -63,6 +296,8 @@ mod tests {
             FileStatus::Missing => ("❓", "missing"),
             FileStatus::Malformed => ("⚠️", "malformed"),
             FileStatus::Unsealed => ("🕒", "unsealed"),
+            FileStatus::ChainBroken { .. } => ("⛓️", "chain broken"),
+            FileStatus::Pruned => ("🗄️", "pruned"),
         };
 
         assert_eq!(symbol, "❌");
@@ -78,6 +313,8 @@ mod tests {
             FileStatus::Missing => ("❓", "missing"),
             FileStatus::Malformed => ("⚠️", "malformed"),
             FileStatus::Unsealed => ("🕒", "unsealed"),
+            FileStatus::ChainBroken { .. } => ("⛓️", "chain broken"),
+            FileStatus::Pruned => ("🗄️", "pruned"),
         };
 
         assert_eq!(symbol, "❓");
@@ -93,12 +330,31 @@ mod tests {
             FileStatus::Missing => ("❓", "missing"),
             FileStatus::Malformed => ("⚠️", "malformed"),
             FileStatus::Unsealed => ("🕒", "unsealed"),
+            FileStatus::ChainBroken { .. } => ("⛓️", "chain broken"),
+            FileStatus::Pruned => ("🗄️", "pruned"),
         };
 
         assert_eq!(symbol, "⚠️");
         assert_eq!(status_str, "malformed");
     }
 
+    #[test]
+    fn test_file_status_chain_broken_symbol() {
+        let status = FileStatus::ChainBroken { line: 3 };
+        let (symbol, status_str) = match status {
+            FileStatus::Verified => ("✅", "verified"),
+            FileStatus::Mismatched => ("❌", "mismatched"),
+            FileStatus::Missing => ("❓", "missing"),
+            FileStatus::Malformed => ("⚠️", "malformed"),
+            FileStatus::Unsealed => ("🕒", "unsealed"),
+            FileStatus::ChainBroken { .. } => ("⛓️", "chain broken"),
+            FileStatus::Pruned => ("🗄️", "pruned"),
+        };
+
+        assert_eq!(symbol, "⛓️");
+        assert_eq!(status_str, "chain broken");
+    }
+
     #[test]
     fn test_file_status_unsealed_symbol() {
         let status = FileStatus::Unsealed;
@@ -108,6 +364,8 @@ mod tests {
             FileStatus::Missing => ("❓", "missing"),
             FileStatus::Malformed => ("⚠️", "malformed"),
             FileStatus::Unsealed => ("🕒", "unsealed"),
+            FileStatus::ChainBroken { .. } => ("⛓️", "chain broken"),
+            FileStatus::Pruned => ("🗄️", "pruned"),
         };
 
         assert_eq!(symbol, "🕒");
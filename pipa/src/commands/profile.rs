@@ -1,5 +1,18 @@
 use pipa::audit_logging::JsonlLogger;
-use pipa::profile::{list_profiles, test_profile};
+use pipa::profile::{
+    add_profile, edit_profile, list_profiles, remove_profile, test_profile, PROFILES_FILE,
+    SECRET_FIELDS,
+};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Fields prompted for by an interactive `profile add`, per provider.
+const PROVIDER_FIELDS: &[(&str, &[&str])] = &[
+    ("s3", &["region", "endpoint", "access_key", "secret_key"]),
+    ("azure", &["account_name", "connection_string"]),
+    ("gcs", &["service_account_json"]),
+    ("kafka", &["endpoint"]),
+    ("local", &[]),
+];
 
 /// List all available profiles in the project.
 ///
@@ -50,6 +63,261 @@ pub async fn test(profile_name: &str) {
     println!("{}", message);
 }
 
+/// Add a profile to `profiles.toml`.
+///
+/// With `--provider`, fields come from `--set` and `--secret-env` only.
+/// Without it, prompts for the provider and its usual fields; secret fields
+/// prompt for an environment variable name and are written as `${VAR}`.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa profile add <name> [--provider <p>] [--set key=value] [--secret-env key=VAR]
+/// ```
+pub fn add(
+    profile_name: &str,
+    provider: Option<&str>,
+    set: &[(String, String)],
+    secret_env: &[(String, String)],
+) {
+    let mut fields = match collect_fields(set, secret_env) {
+        Ok(fields) => fields,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return;
+        }
+    };
+
+    let provider = match provider {
+        Some(provider) => provider.to_string(),
+        None => match prompt_provider_fields(profile_name, &mut fields) {
+            Ok(provider) => provider,
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                return;
+            }
+        },
+    };
+    fields.retain(|(key, _)| key != "provider");
+    fields.insert(0, ("provider".to_string(), provider));
+
+    let logger = JsonlLogger::default();
+    match add_profile(&logger, profile_name, &fields) {
+        Ok(message) => println!("{}", message),
+        Err(e) => eprintln!("❌ Failed to add profile: {}", e),
+    }
+}
+
+/// Set or remove fields of a profile in `profiles.toml`.
+///
+/// Without any flags, prompts for `field` / value pairs until a blank
+/// field name.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa profile edit <name> [--set key=value] [--secret-env key=VAR] [--unset key]
+/// ```
+pub fn edit(
+    profile_name: &str,
+    set: &[(String, String)],
+    secret_env: &[(String, String)],
+    unset: &[String],
+) {
+    let mut fields = match collect_fields(set, secret_env) {
+        Ok(fields) => fields,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return;
+        }
+    };
+    if fields.is_empty() && unset.is_empty() {
+        if let Err(e) = prompt_edits(profile_name, &mut fields) {
+            eprintln!("❌ {}", e);
+            return;
+        }
+        if fields.is_empty() {
+            println!("Nothing to change.");
+            return;
+        }
+    }
+
+    let logger = JsonlLogger::default();
+    match edit_profile(&logger, profile_name, &fields, unset) {
+        Ok(message) => println!("{}", message),
+        Err(e) => eprintln!("❌ Failed to edit profile: {}", e),
+    }
+}
+
+/// Remove a profile from `profiles.toml`, after confirmation unless `yes`.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa profile remove <name> [--yes]
+/// ```
+pub fn remove(profile_name: &str, yes: bool) {
+    if !yes {
+        if !io::stdin().is_terminal() {
+            eprintln!("❌ Refusing to remove '{}' without --yes", profile_name);
+            return;
+        }
+        let question = format!(
+            "Remove profile '{}' from {}? [y/N]",
+            profile_name, PROFILES_FILE
+        );
+        match prompt(&question, None) {
+            Ok(answer)
+                if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") => {}
+            Ok(_) => {
+                println!("Cancelled.");
+                return;
+            }
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                return;
+            }
+        }
+    }
+
+    let logger = JsonlLogger::default();
+    match remove_profile(&logger, profile_name) {
+        Ok(message) => println!("{}", message),
+        Err(e) => eprintln!("❌ Failed to remove profile: {}", e),
+    }
+}
+
+/// Merge `--set` and `--secret-env` values into one field list.
+///
+/// Secret fields can't be set to literal values; they must come from
+/// `--secret-env` (or be a `${VAR}` placeholder already).
+fn collect_fields(
+    set: &[(String, String)],
+    secret_env: &[(String, String)],
+) -> Result<Vec<(String, String)>, String> {
+    let mut fields = Vec::new();
+    for (key, value) in set {
+        if SECRET_FIELDS.contains(&key.as_str()) && !is_placeholder(value) {
+            return Err(format!(
+                "'{}' is a secret; use --secret-env {}=<ENV_VAR> instead of --set",
+                key, key
+            ));
+        }
+        fields.push((key.clone(), value.clone()));
+    }
+    for (key, var) in secret_env {
+        fields.push((key.clone(), placeholder(var)));
+    }
+    Ok(fields)
+}
+
+/// Prompt for a provider and its usual fields; returns the provider.
+fn prompt_provider_fields(
+    profile_name: &str,
+    fields: &mut Vec<(String, String)>,
+) -> Result<String, String> {
+    require_terminal("--provider")?;
+    let providers: Vec<&str> = PROVIDER_FIELDS.iter().map(|(name, _)| *name).collect();
+    let provider = prompt(&format!("Provider ({})", providers.join(", ")), None)?;
+    let Some((_, names)) = PROVIDER_FIELDS.iter().find(|(name, _)| *name == provider) else {
+        return Err(format!("Unknown provider '{}'", provider));
+    };
+
+    if names.iter().any(|name| SECRET_FIELDS.contains(name)) {
+        println!("Secrets are stored as ${{VAR}} placeholders; enter the environment variable");
+        println!("that will hold each one, or '-' to leave it out.");
+    }
+    for name in names.iter() {
+        if fields.iter().any(|(key, _)| key == name) {
+            continue;
+        }
+        if let Some(value) = prompt_field(profile_name, name)? {
+            fields.push((name.to_string(), value));
+        }
+    }
+    Ok(provider)
+}
+
+/// Prompt for fields to set until a blank field name.
+fn prompt_edits(profile_name: &str, fields: &mut Vec<(String, String)>) -> Result<(), String> {
+    require_terminal("--set, --secret-env, or --unset")?;
+    loop {
+        let name = prompt("Field to set (blank to finish)", None)?;
+        if name.is_empty() {
+            return Ok(());
+        }
+        if let Some(value) = prompt_field(profile_name, &name)? {
+            fields.push((name, value));
+        }
+    }
+}
+
+/// Prompt for one field; secrets ask for an environment variable name.
+///
+/// Returns `None` when the field is left out.
+fn prompt_field(profile_name: &str, name: &str) -> Result<Option<String>, String> {
+    if SECRET_FIELDS.contains(&name) {
+        let default = default_env_var(profile_name, name);
+        let var = prompt(&format!("{} (environment variable)", name), Some(&default))?;
+        return Ok((var != "-").then(|| placeholder(&var)));
+    }
+    let value = prompt(&format!("{} (blank to skip)", name), None)?;
+    Ok((!value.is_empty()).then_some(value))
+}
+
+/// Fail unless stdin is a terminal, naming the flags to use instead.
+fn require_terminal(flags: &str) -> Result<(), String> {
+    if io::stdin().is_terminal() {
+        Ok(())
+    } else {
+        Err(format!("stdin is not a terminal; pass {} instead", flags))
+    }
+}
+
+/// Print `question` and read one trimmed line, or `default` if blank.
+pub(crate) fn prompt(question: &str, default: Option<&str>) -> Result<String, String> {
+    match default {
+        Some(default) => print!("{} [{}]: ", question, default),
+        None => print!("{}: ", question),
+    }
+    io::stdout().flush().map_err(|e| e.to_string())?;
+
+    let mut line = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(|e| e.to_string())?;
+    let answer = line.trim();
+    Ok(match default {
+        Some(default) if answer.is_empty() => default.to_string(),
+        _ => answer.to_string(),
+    })
+}
+
+/// Suggested environment variable for a secret, e.g. `RAW_SECRET_KEY`.
+fn default_env_var(profile_name: &str, field: &str) -> String {
+    format!("{}_{}", profile_name, field)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `${VAR}` placeholder for an environment variable.
+fn placeholder(var: &str) -> String {
+    if is_placeholder(var) {
+        var.to_string()
+    } else {
+        format!("${{{}}}", var)
+    }
+}
+
+fn is_placeholder(value: &str) -> bool {
+    value.starts_with("${") && value.ends_with('}')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +357,22 @@ mod tests {
             assert!(!name.is_empty());
         }
     }
+
+    #[test]
+    fn test_secret_fields_become_placeholders() {
+        let set = vec![("region".to_string(), "us-east-1".to_string())];
+        let secret_env = vec![(
+            "secret_key".to_string(),
+            "AWS_SECRET_ACCESS_KEY".to_string(),
+        )];
+        let fields = collect_fields(&set, &secret_env).unwrap();
+        assert_eq!(fields[1].1, "${AWS_SECRET_ACCESS_KEY}");
+
+        let literal = vec![("secret_key".to_string(), "hunter2".to_string())];
+        assert!(collect_fields(&literal, &[]).is_err());
+        let already = vec![("secret_key".to_string(), "${S3_SECRET}".to_string())];
+        assert!(collect_fields(&already, &[]).is_ok());
+
+        assert_eq!(default_env_var("raw-s3", "secret_key"), "RAW_S3_SECRET_KEY");
+    }
 }
@@ -0,0 +1,118 @@
+use pipa::audit_logging::JsonlLogger;
+use pipa::profiler::{profile_data, DataProfile};
+
+/// Profile a data source and print per-column statistics.
+///
+/// Delegates to `pipa::profiler::profile_data()`, which fetches the data
+/// through the regular connector/driver path and computes null/distinct
+/// ratios, min/max, mean/stdev, top values, and inferred dtype per column.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa profile-data <location> [--profile <name>] [--top <n>] [--json]
+/// ```
+pub async fn run(location: &str, profile: Option<&str>, top: usize, json: bool) {
+    let logger = JsonlLogger::default();
+    match profile_data(&logger, location, profile, top).await {
+        Ok((report, message)) => {
+            if json {
+                match serde_json::to_string_pretty(&report) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => eprintln!("❌ Failed to serialize profile: {}", e),
+                }
+            } else {
+                println!("{}", message);
+                print!("{}", format_report(&report));
+            }
+        }
+        Err(e) => eprintln!("❌ Profiling failed: {}", e),
+    }
+}
+
+/// A human-readable profile report, one block per column.
+fn format_report(report: &DataProfile) -> String {
+    let mut out = format!("   rows: {}\n", report.rows);
+    for col in &report.columns {
+        out.push_str(&format!("\n  {} ({})\n", col.name, col.inferred_dtype));
+        out.push_str(&format!("    null_ratio:     {:.4}\n", col.null_ratio));
+        out.push_str(&format!("    distinct_ratio: {:.4}\n", col.distinct_ratio));
+        out.push_str(&format!(
+            "    min / max:      {} / {}\n",
+            col.min.as_deref().unwrap_or("-"),
+            col.max.as_deref().unwrap_or("-")
+        ));
+        if let (Some(mean), Some(stdev)) = (col.mean, col.stdev) {
+            out.push_str(&format!("    mean / stdev:   {:.4} / {:.4}\n", mean, stdev));
+        }
+        if !col.top_values.is_empty() {
+            let top: Vec<String> = col
+                .top_values
+                .iter()
+                .map(|(value, count)| format!("{} ({})", value, count))
+                .collect();
+            out.push_str(&format!("    top values:     {}\n", top.join(", ")));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pipa::profiler::ColumnProfile;
+
+    #[test]
+    fn test_format_report_handles_missing_stats() {
+        let report = DataProfile {
+            location: "data/empty.csv".to_string(),
+            source_type: "local".to_string(),
+            rows: 0,
+            columns: vec![ColumnProfile {
+                name: "id".to_string(),
+                inferred_dtype: "Int64".to_string(),
+                null_ratio: 0.0,
+                distinct_ratio: 0.0,
+                min: None,
+                max: None,
+                mean: None,
+                stdev: None,
+                top_values: vec![],
+            }],
+        };
+
+        assert_eq!(
+            format_report(&report),
+            "   rows: 0\n\
+             \n  id (Int64)\n\
+             \x20   null_ratio:     0.0000\n\
+             \x20   distinct_ratio: 0.0000\n\
+             \x20   min / max:      - / -\n"
+        );
+    }
+
+    #[test]
+    fn test_format_report_lists_stats_and_top_values() {
+        let report = DataProfile {
+            location: "data/orders.csv".to_string(),
+            source_type: "local".to_string(),
+            rows: 3,
+            columns: vec![ColumnProfile {
+                name: "amount".to_string(),
+                inferred_dtype: "Float64".to_string(),
+                null_ratio: 0.0,
+                distinct_ratio: 2.0 / 3.0,
+                min: Some("1.5".to_string()),
+                max: Some("4".to_string()),
+                mean: Some(2.5),
+                stdev: Some(0.5),
+                top_values: vec![("1.5".to_string(), 2), ("4".to_string(), 1)],
+            }],
+        };
+
+        let out = format_report(&report);
+        assert!(out.contains("    distinct_ratio: 0.6667\n"));
+        assert!(out.contains("    min / max:      1.5 / 4\n"));
+        assert!(out.contains("    mean / stdev:   2.5000 / 0.5000\n"));
+        assert!(out.ends_with("    top values:     1.5 (2), 4 (1)\n"));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::commands::run::RunStatus;
+use pipa::audit_logging::JsonlLogger;
+use pipa::contract::Executor;
+use pipa::run::{contract_path, revalidate_quarantined, Environment};
+
+/// Re-validate a quarantined file and release it if it now passes.
+///
+/// This function:
+/// 1. Verifies the contract file exists in `contracts/{name}.toml`.
+/// 2. Calls `revalidate_quarantined` from the engine, which runs the
+///    contract against `file` in its `[quarantine]` location.
+/// 3. On a pass, the engine writes the data to the `[destination]` and
+///    deletes the quarantined copy; a failing file stays in quarantine.
+///
+/// With `dry_run`, the file is validated but nothing is moved or deleted.
+///
+/// Returns the `RunStatus` that `main.rs` uses as the process exit code.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa quarantine revalidate <file> --contract <name> [--dry-run]
+/// ```
+pub async fn revalidate(file: &str, contract_name: &str, dry_run: bool) -> RunStatus {
+    if !contract_path(contract_name).exists() {
+        eprintln!(
+            "❌ Contract '{}' not found. Use 'pipa contract list' to see available contracts.",
+            contract_name
+        );
+        return RunStatus::ConfigError;
+    }
+
+    let logger = JsonlLogger::default();
+    let executor = Executor {
+        user: whoami::username(),
+        host: hostname::get()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+    };
+    let env = Environment {
+        dry_run,
+        ..Environment::default()
+    };
+
+    match revalidate_quarantined(&env, &logger, contract_name, file, &executor, true).await {
+        Ok((revalidation, message)) => {
+            println!("{}", message);
+            let outcome = &revalidation.outcome;
+            if !outcome.passed {
+                eprintln!(
+                    "⚠️  Validation completed with {} failures out of {} checks",
+                    outcome.fail_count,
+                    outcome.pass_count + outcome.fail_count
+                );
+                RunStatus::RuleFailures
+            } else if revalidation.released || dry_run {
+                if dry_run {
+                    println!("🧪 Dry run: no data was moved");
+                }
+                RunStatus::Passed
+            } else {
+                eprintln!(
+                    "❌ {} passed but could not be released",
+                    revalidation.location
+                );
+                RunStatus::ExecutionError
+            }
+        }
+        Err(e) => {
+            eprintln!("❌ Revalidation failed for {}: {}", file, e);
+            RunStatus::from_error(&e)
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use pipa::rules::{catalog, describe as describe_rule, RuleInfo, RuleInput};
+
+/// List every supported contract rule.
+///
+/// Delegates to `pipa::rules::catalog()`, which describes each rule's
+/// contract name, scope (column/file), and parameters.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa rules list [--json]
+/// ```
+pub fn list(json: bool) {
+    let rules = catalog();
+    if json {
+        match serde_json::to_string_pretty(&rules) {
+            Ok(out) => println!("{}", out),
+            Err(e) => eprintln!("❌ Failed to serialize rule catalog: {}", e),
+        }
+    } else {
+        println!("📏 Supported rules:");
+        for rule in &rules {
+            print_rule(rule);
+        }
+    }
+}
+
+/// Describe a single contract rule.
+///
+/// Prints the rule's parameters, semantics (null handling, skipped cases),
+/// and a TOML example, all taken from `pipa::rules::describe()`.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa rules describe <rule>
+/// ```
+pub fn describe(name: &str) {
+    match describe_rule(name) {
+        Some(rule) => {
+            print_rule(&rule);
+            println!("\n  Notes:\n    {}", rule.notes);
+            println!("\n  Example:\n    validation = [ {} ]", rule.example);
+        }
+        None => {
+            eprintln!("❌ Unknown rule '{}'", name);
+            let names: Vec<&str> = catalog().iter().map(|r| r.name).collect();
+            eprintln!("   Supported rules: {}", names.join(", "));
+        }
+    }
+}
+
+/// Print a single rule and its parameters.
+fn print_rule(rule: &RuleInfo) {
+    println!("\n  {} [{:?}]", rule.name, rule.scope);
+    println!("    {}", rule.description);
+    if rule.input != RuleInput::Any {
+        println!("    column dtype: {:?}", rule.input);
+    }
+    for param in &rule.params {
+        println!(
+            "    - {}: {}{} — {}",
+            param.name,
+            param.ty,
+            if param.required { "" } else { " (optional)" },
+            param.description
+        );
+        if !param.values.is_empty() {
+            println!("      one of: {}", param.values.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_print_every_rule() {
+        for rule in catalog() {
+            print_rule(&rule);
+        }
+    }
+}
@@ -1,26 +1,127 @@
 use glob::glob;
 use hostname;
-use pipa::contract::Executor;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use pipa::audit_logging::JsonlLogger;
-use pipa::run::run_contract_validation;
+use pipa::contract::{Executor, ValidationOutcome};
+use pipa::run::{
+    contract_path, contracts_pattern, is_overlay, run_contract_validation_with, select_contracts,
+    CancellationToken, Environment, NaiveDate, Progress, RunProgress, TagFilter, ValidationError,
+};
+use std::fmt;
+use std::io::{self, IsTerminal};
 use std::path::Path;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::{JoinHandle, JoinSet};
 use whoami;
 
-/// Run validation for *all* contracts in the `contracts/` directory.
+/// Process exit status of `pipa run`, so CI pipelines can gate on it.
+///
+/// | Code | Meaning                                              |
+/// |------|------------------------------------------------------|
+/// | 0    | every rule passed                                    |
+/// | 1    | the run completed but at least one rule failed       |
+/// | 2    | execution error (fetching, parsing, connectors, ...) |
+/// | 3    | config error (missing/invalid contract or profiles)  |
+/// | 130  | interrupted with Ctrl-C and cancelled                |
+///
+/// With `--all`, the most severe status across contracts wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RunStatus {
+    Passed = 0,
+    RuleFailures = 1,
+    ExecutionError = 2,
+    ConfigError = 3,
+    Cancelled = 130,
+}
+
+impl RunStatus {
+    /// Process exit code for this status.
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Name of this status in `--output json` results.
+    pub fn name(self) -> &'static str {
+        match self {
+            RunStatus::Passed => "passed",
+            RunStatus::RuleFailures => "rule_failures",
+            RunStatus::ExecutionError => "execution_error",
+            RunStatus::ConfigError => "config_error",
+            RunStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Classify an engine error.
+    pub(crate) fn from_error(error: &ValidationError) -> Self {
+        if matches!(error, ValidationError::Cancelled(_)) {
+            RunStatus::Cancelled
+        } else if error.is_config() {
+            RunStatus::ConfigError
+        } else {
+            RunStatus::ExecutionError
+        }
+    }
+}
+
+/// Run validation for *all* contracts in the `contracts/` directory, or
+/// for those selected by `filter` when it has tags.
 ///
 /// This function:
 /// 1. Captures the current user and host (for audit metadata).
-/// 2. Iterates over all `*.toml` files in `contracts/`.
-/// 3. For each contract, calls `run_contract_validation` from the engine.
-/// 4. Prints the validation message and warns if failures occurred.
+/// 2. Collects all `*.toml` files in `contracts/`, or the contracts whose
+///    `tags` match `filter` (via the engine's `select_contracts`).
+/// 3. Runs `run_contract_validation_with` from the engine for each contract
+///    as a tokio task, with at most `jobs` contracts in flight.
+/// 4. Prints each validation message as it finishes, warns if failures
+///    occurred, and ends with a one-line summary.
+///
+/// Returns the most severe `RunStatus` across all contracts; finding no
+/// contracts at all is a config error.
+///
+/// Unless `quiet`, each running contract gets a progress bar on stderr
+/// when it is a terminal (see `RunBar`).
 ///
 /// Called from `main.rs` when the user runs:
 /// ```bash
-/// pipa run --all
+/// pipa run --all [--jobs <n>] [--dry-run] [--date <YYYY-MM-DD>] [--retries <n>] [--quiet]
+/// pipa run --tag <tag> [--tag <tag>...] [--exclude-tag <tag>...] [--jobs <n>] [--dry-run]
 /// ```
-pub async fn run_all() {
-    // Create logger
-    let logger = JsonlLogger::default();
+pub async fn run_all(
+    jobs: usize,
+    filter: &TagFilter,
+    dry_run: bool,
+    run_date: Option<NaiveDate>,
+    limits: RunLimits,
+    quiet: bool,
+) -> RunStatus {
+    let progress = show_progress(quiet).then(MultiProgress::new);
+    let (status, summary) =
+        run_project(jobs, filter, dry_run, run_date, limits, progress.as_ref()).await;
+    if summary.total() > 0 {
+        println!("{}", summary);
+    }
+    if dry_run {
+        println!("🧪 Dry run: no data was written");
+    }
+    status
+}
+
+/// Run the contracts of the project in the working directory, as for
+/// `run_all`, returning the status and per-contract tallies without
+/// printing the summary line. Contracts get a bar in `progress` when given.
+pub(crate) async fn run_project(
+    jobs: usize,
+    filter: &TagFilter,
+    dry_run: bool,
+    run_date: Option<NaiveDate>,
+    limits: RunLimits,
+    progress: Option<&MultiProgress>,
+) -> (RunStatus, RunSummary) {
+    // Create logger (shared by all tasks; it serializes log writes)
+    let logger = Arc::new(JsonlLogger::default());
 
     // Capture host and user for Executor metadata
     let hostname = hostname::get()
@@ -28,42 +129,299 @@ pub async fn run_all() {
         .to_string_lossy()
         .to_string();
 
-    let executor = Executor {
+    let executor = Arc::new(Executor {
         user: whoami::username(),
         host: hostname,
-    };
+    });
+
+    let env = Arc::new(run_environment(dry_run, run_date, limits));
+    let _interrupt = InterruptGuard::new(env.cancel.clone());
+    let mut status = RunStatus::Passed;
+    let mut contract_names = Vec::new();
+
+    if filter.is_active() {
+        // Select contracts by tag
+        match select_contracts(filter) {
+            Ok(names) => contract_names = names,
+            Err(e) => {
+                eprintln!("❌ Failed to select contracts by tag: {}", e);
+                return (RunStatus::from_error(&e), RunSummary::default());
+            }
+        }
+        if contract_names.is_empty() {
+            eprintln!("❌ No contracts match the tag filter");
+            return (RunStatus::ConfigError, RunSummary::default());
+        }
+    } else {
+        // Collect all contract TOML files
+        for entry in glob(&contracts_pattern()).expect("Failed to read glob pattern") {
+            match entry {
+                Ok(path) if is_overlay(&path) => {}
+                Ok(path) => {
+                    // Extract contract name from filename (strip extension)
+                    let contract_name = path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("unknown");
+                    contract_names.push(contract_name.to_string());
+                }
+                Err(_) => {
+                    eprintln!("❌ Error reading contract files. Check logs for details.");
+                    status = status.max(RunStatus::ConfigError);
+                }
+            }
+        }
 
-    // Iterate over all contract TOML files
-    for entry in glob("contracts/*.toml").expect("Failed to read glob pattern") {
-        match entry {
-            Ok(path) => {
-                // Extract contract name from filename (strip extension)
-                let contract_name = path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("unknown");
-
-                // Run validation via engine API
-                match run_contract_validation(&logger, contract_name, &executor, true).await {
-                    Ok((outcome, message)) => {
-                        println!("{}", message);
-                        if !outcome.passed {
-                            eprintln!(
-                                "⚠️  Validation completed with failures for {}",
-                                contract_name
-                            );
-                        }
-                    }
-                    Err(_) => {
-                        eprintln!(
-                            "❌ Validation failed for {}. Check logs for details.",
-                            contract_name
-                        );
-                    }
+        if contract_names.is_empty() {
+            eprintln!("❌ No contracts found in contracts/");
+            return (RunStatus::ConfigError, RunSummary::default());
+        }
+    }
+
+    // Run validations via engine API, at most `jobs` at a time
+    let permits = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+    for contract_name in contract_names {
+        let permit = permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore is never closed");
+        if env.cancel.is_cancelled() {
+            // Interrupted: don't start the contracts still waiting
+            status = status.max(RunStatus::Cancelled);
+            break;
+        }
+        let bar =
+            progress.map(|multi| RunBar::new(multi.add(ProgressBar::no_length()), &contract_name));
+        let env = Arc::clone(&env);
+        let logger = Arc::clone(&logger);
+        let executor = Arc::clone(&executor);
+        tasks.spawn(async move {
+            let _permit = permit;
+            let env = match &bar {
+                Some(bar) => Environment {
+                    progress: Progress::new(bar.clone()),
+                    ..(*env).clone()
+                },
+                None => (*env).clone(),
+            };
+            let result = run_contract_validation_with(
+                &env,
+                logger.as_ref(),
+                &contract_name,
+                &executor,
+                true,
+            )
+            .await;
+            if let Some(bar) = bar {
+                bar.finish();
+            }
+            (contract_name, result)
+        });
+    }
+
+    // Aggregate results as tasks finish
+    let mut summary = RunSummary::default();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((contract_name, Ok((outcome, message)))) => {
+                println!("{}", message);
+                if outcome.passed {
+                    summary.passed += 1;
+                } else {
+                    eprintln!(
+                        "⚠️  Validation completed with failures for {}",
+                        contract_name
+                    );
+                    print_failed_objects(&outcome);
+                    summary.failed += 1;
+                    status = status.max(RunStatus::RuleFailures);
                 }
             }
-            Err(_) => eprintln!("❌ Error reading contract files. Check logs for details."),
+            Ok((contract_name, Err(e))) => {
+                eprintln!("❌ Validation failed for {}: {}", contract_name, e);
+                summary.errors += 1;
+                status = status.max(RunStatus::from_error(&e));
+            }
+            Err(e) => {
+                eprintln!("❌ Validation task failed: {}", e);
+                summary.errors += 1;
+                status = status.max(RunStatus::ExecutionError);
+            }
+        }
+    }
+
+    (status, summary)
+}
+
+/// List the objects that failed when a glob source matched several.
+fn print_failed_objects(outcome: &ValidationOutcome) {
+    for object in outcome.objects.iter().filter(|o| !o.outcome.passed) {
+        eprintln!(
+            "   ✗ {} ({} failures)",
+            object.location, object.outcome.fail_count
+        );
+    }
+}
+
+/// `--retries` / `--retry-delay` and `--timeout` / `--fetch-timeout`,
+/// overriding each contract's `[retry]` and `[limits]` timeouts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunLimits {
+    pub retries: Option<u32>,
+    pub retry_delay: Option<Duration>,
+    pub run_timeout: Option<Duration>,
+    pub fetch_timeout: Option<Duration>,
+}
+
+/// Default engine environment, rehearsing without writes when `dry_run`,
+/// rendering source-location date tokens for `run_date` when given, and
+/// retrying and timing out as `limits` says.
+fn run_environment(dry_run: bool, run_date: Option<NaiveDate>, limits: RunLimits) -> Environment {
+    Environment {
+        dry_run,
+        run_date,
+        retries: limits.retries,
+        retry_delay: limits.retry_delay,
+        run_timeout: limits.run_timeout,
+        fetch_timeout: limits.fetch_timeout,
+        ..Environment::default()
+    }
+}
+
+/// Whether to draw progress bars: not with `--quiet`, nor when stderr is
+/// redirected to a file or pipe.
+fn show_progress(quiet: bool) -> bool {
+    !quiet && io::stderr().is_terminal()
+}
+
+/// What a `RunBar` is showing.
+const FETCHING: u8 = 0;
+const PARSING: u8 = 1;
+const CHECKING: u8 = 2;
+
+const FETCHING_TEMPLATE: &str = "{spinner} {prefix}: fetched {bytes} ({binary_bytes_per_sec})";
+
+/// Progress bar for one contract run: bytes fetched, then rows parsed,
+/// then rules completed out of those declared.
+#[derive(Clone)]
+struct RunBar {
+    bar: ProgressBar,
+    stage: Arc<AtomicU8>,
+    rows: Arc<AtomicUsize>,
+}
+
+impl RunBar {
+    fn new(bar: ProgressBar, contract_name: &str) -> Self {
+        bar.set_prefix(contract_name.to_string());
+        bar.set_style(Self::style(FETCHING_TEMPLATE));
+        bar.enable_steady_tick(std::time::Duration::from_millis(120));
+        Self {
+            bar,
+            stage: Arc::new(AtomicU8::new(FETCHING)),
+            rows: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn style(template: &str) -> ProgressStyle {
+        ProgressStyle::with_template(template)
+            .expect("progress template is valid")
+            .progress_chars("=> ")
+    }
+
+    /// Switch to `stage`, restyling the bar if it wasn't already there.
+    fn enter(&self, stage: u8, template: &str) -> bool {
+        let entered = self.stage.swap(stage, Ordering::Relaxed) != stage;
+        if entered {
+            self.bar.set_style(Self::style(template));
         }
+        entered
+    }
+
+    fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+impl RunProgress for RunBar {
+    fn downloaded(&self, bytes: u64) {
+        // A glob source fetches its next object after the last one's rules.
+        if self.enter(FETCHING, FETCHING_TEMPLATE) {
+            self.bar.set_position(0);
+            self.rows.store(0, Ordering::Relaxed);
+        }
+        self.bar.inc(bytes);
+    }
+
+    fn parsed(&self, rows: usize) {
+        self.enter(PARSING, "{spinner} {prefix}: {msg}");
+        let total = self.rows.fetch_add(rows, Ordering::Relaxed) + rows;
+        self.bar.set_message(format!("parsed {} rows", total));
+    }
+
+    fn rules_started(&self, total: usize) {
+        self.enter(CHECKING, "{spinner} {prefix}: [{bar:30}] {pos}/{len} rules");
+        self.bar.set_length(total as u64);
+        self.bar.set_position(0);
+    }
+
+    fn rule_completed(&self) {
+        self.bar.inc(1);
+    }
+}
+
+/// Cancels a run's token on Ctrl-C for as long as it is held, so in-flight
+/// contracts stop at their next safe point instead of partway through
+/// moving data; a second Ctrl-C exits at once.
+struct InterruptGuard(JoinHandle<()>);
+
+impl InterruptGuard {
+    fn new(cancel: CancellationToken) -> Self {
+        Self(tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            eprintln!("⏹️  Interrupted: stopping runs at their next safe point (Ctrl-C again to exit now)");
+            cancel.cancel();
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(RunStatus::Cancelled.code());
+            }
+        }))
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Per-contract tallies for the `run --all` summary line.
+#[derive(Debug, Default)]
+pub(crate) struct RunSummary {
+    pub(crate) passed: usize,
+    pub(crate) failed: usize,
+    pub(crate) errors: usize,
+}
+
+impl RunSummary {
+    /// Number of contracts run.
+    pub(crate) fn total(&self) -> usize {
+        self.passed + self.failed + self.errors
+    }
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "📊 {} contracts: {} passed, {} with failures, {} errors",
+            self.total(),
+            self.passed,
+            self.failed,
+            self.errors
+        )
     }
 }
 
@@ -72,14 +430,36 @@ pub async fn run_all() {
 /// This function:
 /// 1. Captures the current user and host (for audit metadata).
 /// 2. Verifies the contract file exists in `contracts/{name}.toml`.
-/// 3. Calls `run_contract_validation` from the engine.
+/// 3. Calls `run_contract_validation_with` from the engine.
 /// 4. Prints the validation message and warns if failures occurred.
 ///
+/// With `dry_run`, data is fetched and validated but nothing is written to
+/// the destination or quarantine, and audit entries carry `dry_run=true`.
+/// `run_date` (from `--date`) replaces today's date in `{{ today }}`-style
+/// tokens of the contract's source location. Source fetch failures are
+/// retried per the contract's `[retry]` block, and fetches and the run
+/// time out per its `[limits]`, unless `limits` overrides them.
+///
+/// Unless `quiet`, a progress bar on stderr (when it is a terminal) shows
+/// bytes fetched, rows parsed, and rules completed while the run is going.
+/// `report` prints the outcome as JSON instead of console messages and/or
+/// writes it to a results file (see `results_document`).
+///
+/// Returns the `RunStatus` that `main.rs` uses as the process exit code.
+///
 /// Called from `main.rs` when the user runs:
 /// ```bash
-/// pipa run <contract_name>
+/// pipa run <contract_name> [--dry-run] [--date <YYYY-MM-DD>] [--retries <n>] [--retry-delay <delay>] [--timeout <duration>] [--quiet]
+/// pipa run <contract_name> --output json [--emit-results-file <path>]
 /// ```
-pub async fn run_single(contract_name: &str) {
+pub async fn run_single(
+    contract_name: &str,
+    dry_run: bool,
+    run_date: Option<NaiveDate>,
+    limits: RunLimits,
+    report: RunReport<'_>,
+    quiet: bool,
+) -> RunStatus {
     // Create logger
     let logger = JsonlLogger::default();
 
@@ -95,41 +475,176 @@ pub async fn run_single(contract_name: &str) {
     };
 
     // Ensure the contract file exists before running
-    if !Path::new(&format!("contracts/{}.toml", contract_name)).exists() {
+    if !contract_path(contract_name).exists() {
         eprintln!(
             "❌ Contract '{}' not found. Use 'pipa contract list' to see available contracts.",
             contract_name
         );
-        return;
+        let error = ValidationError::ContractNotFound(contract_name.to_string());
+        return report.finish(contract_name, dry_run, RunStatus::ConfigError, Err(&error));
     }
 
     // Run validation via engine API
-    match run_contract_validation(&logger, contract_name, &executor, true).await {
+    let mut env = run_environment(dry_run, run_date, limits);
+    let _interrupt = InterruptGuard::new(env.cancel.clone());
+    let bar = show_progress(quiet).then(|| RunBar::new(ProgressBar::no_length(), contract_name));
+    if let Some(bar) = &bar {
+        env.progress = Progress::new(bar.clone());
+    }
+    let result =
+        run_contract_validation_with(&env, &logger, contract_name, &executor, !report.json).await;
+    if let Some(bar) = bar {
+        bar.finish();
+    }
+    let status = match &result {
         Ok((outcome, message)) => {
-            println!("{}", message);
-            if !outcome.passed {
+            if !report.json {
+                println!("{}", message);
+                println!("🆔 Run ID: {}", outcome.run_id);
+                if dry_run {
+                    println!("🧪 Dry run: no data was written");
+                }
+            }
+            if outcome.passed {
+                RunStatus::Passed
+            } else {
                 eprintln!(
                     "⚠️  Validation completed with {} failures out of {} checks",
                     outcome.fail_count,
                     outcome.pass_count + outcome.fail_count
                 );
+                print_failed_objects(outcome);
+                RunStatus::RuleFailures
+            }
+        }
+        Err(e) => {
+            eprintln!("❌ Validation failed for {}: {}", contract_name, e);
+            RunStatus::from_error(e)
+        }
+    };
+    let outcome = result.as_ref().map(|(outcome, _)| outcome);
+    report.finish(contract_name, dry_run, status, outcome)
+}
+
+/// Where `pipa run <contract>` reports its outcome as JSON: on stdout
+/// with `--output json` (in place of the console messages), and in the
+/// `--emit-results-file` file.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunReport<'a> {
+    pub json: bool,
+    pub results_file: Option<&'a Path>,
+}
+
+impl RunReport<'_> {
+    /// Print and write the results document for a finished run; a results
+    /// file that can't be written makes it an execution error.
+    fn finish(
+        self,
+        contract_name: &str,
+        dry_run: bool,
+        status: RunStatus,
+        outcome: Result<&ValidationOutcome, &ValidationError>,
+    ) -> RunStatus {
+        if !self.json && self.results_file.is_none() {
+            return status;
+        }
+        let document = results_document(contract_name, dry_run, status, outcome);
+        let text = match serde_json::to_string_pretty(&document) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("❌ Failed to serialize run results: {}", e);
+                return status.max(RunStatus::ExecutionError);
             }
+        };
+        if self.json {
+            println!("{}", text);
         }
-        Err(_) => {
-            eprintln!(
-                "❌ Validation failed for {}. Check logs for details.",
-                contract_name
-            );
+        if let Some(path) = self.results_file {
+            if let Err(e) = std::fs::write(path, format!("{}\n", text)) {
+                eprintln!("❌ Failed to write {}: {}", path.display(), e);
+                return status.max(RunStatus::ExecutionError);
+            }
         }
+        status
     }
 }
 
+/// The results document of one run. Its field names are stable:
+/// - `contract`, `dry_run`
+/// - `status`: `passed`, `rule_failures`, `execution_error`,
+///   `config_error`, or `cancelled`, and `exit_code`, the process's
+/// - `error`: why the run didn't complete, or `null`
+/// - `outcome`: `null` unless the run completed, else `passed`,
+///   `pass_count`, `fail_count`, `quarantined`, `run_id`, `results` (one
+///   `{ column, rule, result, details, duration_ms? }` per rule),
+///   `transfer` (`downloaded` / `uploaded` bytes per connector), `timings`
+///   (`fetch_ms`, `parse_ms`, `validate_ms`, `total_ms`), and `objects`
+///   (`{ location, outcome }` per object of a glob source)
+fn results_document(
+    contract_name: &str,
+    dry_run: bool,
+    status: RunStatus,
+    outcome: Result<&ValidationOutcome, &ValidationError>,
+) -> serde_json::Value {
+    let (outcome, error) = match outcome {
+        Ok(outcome) => (serde_json::to_value(outcome).ok(), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    serde_json::json!({
+        "contract": contract_name,
+        "dry_run": dry_run,
+        "status": status.name(),
+        "exit_code": status.code(),
+        "error": error,
+        "outcome": outcome,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
+    use std::path::Path;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_run_status_codes() {
+        assert_eq!(RunStatus::Passed.code(), 0);
+        assert_eq!(RunStatus::RuleFailures.code(), 1);
+        assert_eq!(RunStatus::ExecutionError.code(), 2);
+        assert_eq!(RunStatus::ConfigError.code(), 3);
+        assert_eq!(RunStatus::Cancelled.code(), 130);
+        assert_eq!(
+            RunStatus::RuleFailures.max(RunStatus::ConfigError),
+            RunStatus::ConfigError
+        );
+        assert_eq!(
+            RunStatus::from_error(&ValidationError::ContractParse("bad".into())),
+            RunStatus::ConfigError
+        );
+        assert_eq!(
+            RunStatus::from_error(&ValidationError::source_fetch("s3", "timeout")),
+            RunStatus::ExecutionError
+        );
+        assert_eq!(
+            RunStatus::from_error(&ValidationError::Cancelled("during fetch")),
+            RunStatus::Cancelled
+        );
+    }
+
+    #[test]
+    fn test_run_summary_display() {
+        let summary = RunSummary {
+            passed: 2,
+            failed: 1,
+            errors: 0,
+        };
+        assert_eq!(
+            summary.to_string(),
+            "📊 3 contracts: 2 passed, 1 with failures, 0 errors"
+        );
+    }
+
     #[test]
     fn test_contract_file_path_construction() {
         let contract_name = "test_contract";
@@ -166,10 +681,9 @@ mod tests {
         fs::write(contracts_dir.join("contract2.toml"), "test content").unwrap();
         fs::write(contracts_dir.join("readme.md"), "not a contract").unwrap();
 
-        // Change to temp directory and test glob pattern
-        std::env::set_current_dir(&temp_dir).unwrap();
-
-        let matches: Vec<_> = glob("contracts/*.toml")
+        // Glob with an absolute pattern; changing the process cwd races other tests
+        let pattern = format!("{}/*.toml", contracts_dir.display());
+        let matches: Vec<_> = glob(&pattern)
             .expect("Failed to read glob pattern")
             .filter_map(Result::ok)
             .collect();
@@ -272,9 +786,8 @@ mod tests {
         let contracts_dir = temp_dir.path().join("contracts");
         fs::create_dir_all(&contracts_dir).unwrap();
 
-        std::env::set_current_dir(&temp_dir).unwrap();
-
-        let matches: Vec<_> = glob("contracts/*.toml")
+        let pattern = format!("{}/*.toml", contracts_dir.display());
+        let matches: Vec<_> = glob(&pattern)
             .expect("Failed to read glob pattern")
             .filter_map(Result::ok)
             .collect();
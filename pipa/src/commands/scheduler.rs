@@ -0,0 +1,116 @@
+use crate::commands::run::RunStatus;
+use pipa::audit_logging::JsonlLogger;
+use pipa::contract::Executor;
+use pipa::run::{scheduled_contracts, sealing_schedule, Environment, ScheduledContract, Scheduler};
+
+/// Load the contracts that have a `[schedule]` section, printing errors.
+///
+/// No scheduled contracts is an error unless `allow_empty` (the scheduler
+/// still has logs to seal).
+fn load_scheduled(allow_empty: bool) -> Result<Vec<ScheduledContract>, RunStatus> {
+    match scheduled_contracts() {
+        Ok(scheduled) if scheduled.is_empty() && !allow_empty => {
+            eprintln!("❌ No contracts with a [schedule] section in contracts/");
+            Err(RunStatus::ConfigError)
+        }
+        Ok(scheduled) => Ok(scheduled),
+        Err(e) => {
+            eprintln!("❌ Failed to load schedules: {}", e);
+            Err(RunStatus::from_error(&e))
+        }
+    }
+}
+
+/// List scheduled contracts with their cron expression and next run.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa scheduler list
+/// ```
+pub fn list() -> RunStatus {
+    let scheduled = match load_scheduled(false) {
+        Ok(scheduled) => scheduled,
+        Err(status) => return status,
+    };
+
+    for entry in &scheduled {
+        let next = entry
+            .next_run()
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_else(|| "never".to_string());
+        println!(
+            "⏰ {:<24} {:<20} next: {}",
+            entry.contract, entry.cron, next
+        );
+    }
+    RunStatus::Passed
+}
+
+/// Run contracts on their `[schedule] cron` until interrupted.
+///
+/// This function:
+/// 1. Loads every contract with a `[schedule]` section; an invalid cron
+///    expression is a config error.
+/// 2. Starts the engine's `Scheduler`, which sleeps until the next due
+///    minute and runs the contracts due then, logging each trigger as
+///    `schedule_triggered`. With `[sealing] cron` in `pipa.toml` it also
+///    seals the previous day's log at those times; the scheduler then runs
+///    even without scheduled contracts.
+/// 3. Stops on Ctrl-C and prints a summary of the runs.
+///
+/// Returns the most severe `RunStatus` across the triggered runs.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa scheduler start [--dry-run]
+/// ```
+pub async fn start(dry_run: bool) -> RunStatus {
+    let sealing = match sealing_schedule() {
+        Ok(sealing) => sealing,
+        Err(e) => {
+            eprintln!("❌ Failed to load the sealing schedule: {}", e);
+            return RunStatus::from_error(&e);
+        }
+    };
+    let scheduled = match load_scheduled(sealing.is_some()) {
+        Ok(scheduled) => scheduled,
+        Err(status) => return status,
+    };
+
+    let logger = JsonlLogger::default();
+    let executor = Executor {
+        user: whoami::username(),
+        host: hostname::get()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+    };
+    let env = Environment {
+        dry_run,
+        ..Environment::default()
+    };
+
+    println!("Press Ctrl-C to stop the scheduler.");
+    let mut scheduler = Scheduler::new(env, scheduled);
+    if let Some(cron) = sealing {
+        println!("🔒 Sealing logs at '{}' (UTC)", cron);
+        scheduler = scheduler.with_sealing(cron);
+    }
+    let summary = scheduler
+        .run(&logger, &executor, true, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await;
+
+    println!(
+        "📊 {} scheduled runs: {} passed, {} with failures, {} errors",
+        summary.triggers, summary.passed, summary.failed, summary.errors
+    );
+    if summary.errors > 0 {
+        RunStatus::ExecutionError
+    } else if summary.failed > 0 {
+        RunStatus::RuleFailures
+    } else {
+        RunStatus::Passed
+    }
+}
@@ -0,0 +1,88 @@
+use crate::commands::run::RunStatus;
+use pipa::audit_logging::JsonlLogger;
+use pipa::contract::Executor;
+use pipa::run::{contract_path, select_contracts, Environment, TagFilter, WatchOptions, Watcher};
+use std::time::Duration;
+
+/// Watch contract sources and run contracts as new files appear.
+///
+/// This function:
+/// 1. Resolves the contracts to watch (`contracts`, or every contract in
+///    `contracts/` when empty).
+/// 2. Polls each contract's source every `interval` seconds via the
+///    engine's `Watcher`; a directory or prefix source runs the contract on
+///    each new file under it, a file source whenever the file changes.
+/// 3. Stops on Ctrl-C and prints a summary of the runs.
+///
+/// Returns the most severe `RunStatus` across the triggered runs.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa watch [<contract>...] [--interval <secs>] [--debounce <secs>] [--dry-run]
+/// ```
+pub async fn run(contracts: &[String], interval: u64, debounce: u64, dry_run: bool) -> RunStatus {
+    let contracts = if contracts.is_empty() {
+        match select_contracts(&TagFilter::default()) {
+            Ok(names) => names,
+            Err(e) => {
+                eprintln!("❌ Failed to load contracts: {}", e);
+                return RunStatus::ConfigError;
+            }
+        }
+    } else {
+        contracts.to_vec()
+    };
+    if contracts.is_empty() {
+        eprintln!("❌ No contracts found in contracts/");
+        return RunStatus::ConfigError;
+    }
+    for contract in &contracts {
+        if !contract_path(contract).exists() {
+            eprintln!(
+                "❌ Contract '{}' not found. Use 'pipa contract list' to see available contracts.",
+                contract
+            );
+            return RunStatus::ConfigError;
+        }
+    }
+
+    let logger = JsonlLogger::default();
+    let executor = Executor {
+        user: whoami::username(),
+        host: hostname::get()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+    };
+    let env = Environment {
+        dry_run,
+        ..Environment::default()
+    };
+    let options = WatchOptions {
+        contracts,
+        interval: Duration::from_secs(interval),
+        debounce: Duration::from_secs(debounce),
+    };
+
+    println!("Press Ctrl-C to stop watching.");
+    let summary = Watcher::new(env, options)
+        .run(&logger, &executor, true, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await;
+
+    println!(
+        "📊 {} runs: {} passed, {} with failures, {} errors",
+        summary.passed + summary.failed + summary.errors,
+        summary.passed,
+        summary.failed,
+        summary.errors
+    );
+    if summary.errors > 0 {
+        RunStatus::ExecutionError
+    } else if summary.failed > 0 {
+        RunStatus::RuleFailures
+    } else {
+        RunStatus::Passed
+    }
+}
@@ -0,0 +1,114 @@
+use crate::commands::run::{run_project, RunLimits, RunStatus, RunSummary};
+use pipa::run::TagFilter;
+use pipa::workspace::{load_workspace, Project, Workspace, WORKSPACE_FILE};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Run every project of a workspace and report a consolidated summary.
+///
+/// This function:
+/// 1. Takes the projects from `projects` or, when empty, from the
+///    workspace file (`workspace.toml` by default).
+/// 2. Runs each project's contracts from inside its directory (see
+///    `Project::enter`), so every project uses its own contracts,
+///    `profiles.toml`, logs, and run history.
+/// 3. Prints a line per project and a workspace-wide summary.
+///
+/// Returns the most severe `RunStatus` across all projects; a missing
+/// project directory, or one without a contracts directory, is a config
+/// error for that project.
+///
+/// Called from `main.rs` when the user runs:
+/// ```bash
+/// pipa workspace run [--projects <dir>,<dir>...] [--file <workspace.toml>] [--jobs <n>]
+/// ```
+pub async fn run(projects: &[String], file: Option<&str>, jobs: usize, dry_run: bool) -> RunStatus {
+    let workspace = if projects.is_empty() {
+        let path = Path::new(file.unwrap_or(WORKSPACE_FILE));
+        match load_workspace(path) {
+            Ok(workspace) => workspace,
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                return RunStatus::ConfigError;
+            }
+        }
+    } else {
+        Workspace::from_dirs(projects)
+    };
+
+    let original_dir = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("❌ Cannot determine working directory: {}", e);
+            return RunStatus::ExecutionError;
+        }
+    };
+
+    let mut results: Vec<(PathBuf, RunStatus, RunSummary)> = Vec::new();
+    for project in &workspace.projects {
+        println!("📦 Project {}", project.display());
+        let (status, summary) = match Project::open(project) {
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                (RunStatus::ConfigError, RunSummary::default())
+            }
+            Ok(dirs) if !dirs.contracts_dir.is_dir() => {
+                eprintln!("❌ No contracts directory in {}", project.display());
+                (RunStatus::ConfigError, RunSummary::default())
+            }
+            Ok(dirs) => match dirs.enter() {
+                Err(e) => {
+                    eprintln!("❌ Cannot enter {}: {}", project.display(), e);
+                    (RunStatus::ConfigError, RunSummary::default())
+                }
+                Ok(()) => {
+                    let outcome = run_project(
+                        jobs,
+                        &TagFilter::default(),
+                        dry_run,
+                        None,
+                        RunLimits::default(),
+                        None,
+                    )
+                    .await;
+                    if let Err(e) = env::set_current_dir(&original_dir) {
+                        eprintln!("❌ Cannot return to {}: {}", original_dir.display(), e);
+                        return RunStatus::ExecutionError;
+                    }
+                    outcome
+                }
+            },
+        };
+        results.push((project.clone(), status, summary));
+    }
+
+    println!();
+    let mut status = RunStatus::Passed;
+    let mut total = RunSummary::default();
+    for (project, project_status, summary) in &results {
+        let icon = match project_status {
+            RunStatus::Passed => "✅",
+            RunStatus::RuleFailures => "⚠️ ",
+            _ => "❌",
+        };
+        println!("{} {}: {}", icon, project.display(), summary);
+        status = status.max(*project_status);
+        total.passed += summary.passed;
+        total.failed += summary.failed;
+        total.errors += summary.errors;
+    }
+    let failed_projects = results
+        .iter()
+        .filter(|(_, s, _)| *s != RunStatus::Passed)
+        .count();
+    println!(
+        "📦 {} projects ({} not passing), {}",
+        results.len(),
+        failed_projects,
+        total
+    );
+    if dry_run {
+        println!("🧪 Dry run: no data was written");
+    }
+    status
+}
@@ -1,49 +1,291 @@
-use clap::Parser; // CLI argument parsing
+use clap::{CommandFactory, Parser}; // CLI argument parsing
+use clap_complete::CompleteEnv; // Shell completion callbacks
 
 mod cli; // Local CLI definitions (structs/enums)
 mod commands; // Local command implementations
 
-use cli::{Cli, Commands, ContractCommands, LogsCommands, ProfileCommands};
+use cli::{
+    Cli, Commands, ContractCommands, LogsCommands, ProfileCommands, QuarantineCommands,
+    RulesCommands, SchedulerCommands, WorkspaceCommands,
+};
+use pipa::run::{load_defaults, OutputFormat, TagFilter, PROJECT_FILE};
+use pipa::workspace::Project;
+use std::path::Path;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv::dotenv().ok(); // Load environment variables from .env
+    // Answer a completion script's request for candidates, then exit
+    CompleteEnv::with_factory(Cli::command)
+        .var(commands::completions::COMPLETE_VAR)
+        .complete();
     let cli = Cli::parse(); // Parse CLI args into `Cli` struct
 
+    // Directory flags win over `pipa.toml` and `.env`; they are relative to
+    // where pipa was invoked, not to `--project-dir`
+    for (var, dir) in [
+        ("PIPA_CONTRACTS_DIR", &cli.contracts_dir),
+        ("PIPA_LOGS_DIR", &cli.logs_dir),
+    ] {
+        if let Some(dir) = dir {
+            let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.clone());
+            std::env::set_var(var, dir);
+        }
+    }
+    if let Some(env) = &cli.env {
+        std::env::set_var(pipa::run::ENV_VAR, env);
+    }
+    if let Some(dir) = &cli.project_dir {
+        // Work inside the project, so its paths and `.env` are the ones used
+        if let Err(e) = Project::open(dir).and_then(|project| project.enter()) {
+            eprintln!("❌ {}", e);
+            std::process::exit(commands::run::RunStatus::ConfigError.code());
+        }
+    }
+    dotenv::dotenv().ok(); // Load environment variables from .env
+
+    // `[defaults]` from `pipa.toml` (and `PIPA_*`), under the command-line flags
+    let defaults = match load_defaults(Path::new(PROJECT_FILE)) {
+        Ok(defaults) => defaults,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(commands::run::RunStatus::ConfigError.code());
+        }
+    };
+    let json_output = cli.output_format.or(defaults.output) == Some(OutputFormat::Json);
+    let jobs_or_default = |jobs: Option<u16>| jobs.map(usize::from).or(defaults.jobs).unwrap_or(1);
+
     match cli.command {
-        Some(Commands::Run { contract, all }) => {
+        Some(Commands::Run {
+            contract,
+            all,
+            tags,
+            exclude_tags,
+            jobs,
+            dry_run,
+            date,
+            quiet,
+            retries,
+            retry_delay,
+            timeout,
+            fetch_timeout,
+            output,
+            emit_results_file,
+        }) => {
+            let config_error = commands::run::RunStatus::ConfigError.code();
+            let filter = TagFilter {
+                include: tags,
+                exclude: exclude_tags,
+            };
             if all && contract.is_some() {
                 eprintln!("❌ Cannot specify both contract name and --all");
-                std::process::exit(1);
+                std::process::exit(config_error);
             }
-            if !all && contract.is_none() {
-                eprintln!("❌ Must specify either contract name or --all");
-                std::process::exit(1);
+            if !all && contract.is_none() && !filter.is_active() {
+                eprintln!("❌ Must specify either contract name or --all (or select with --tag)");
+                std::process::exit(config_error);
             }
 
-            if all {
-                commands::run::run_all().await;
-            } else if let Some(name) = contract {
-                commands::run::run_single(&name).await;
+            let limits = commands::run::RunLimits {
+                retries,
+                retry_delay,
+                run_timeout: timeout,
+                fetch_timeout,
+            };
+            let status = match contract {
+                Some(name) => {
+                    let report = commands::run::RunReport {
+                        json: output == Some(OutputFormat::Json) || json_output,
+                        results_file: emit_results_file.as_deref(),
+                    };
+                    commands::run::run_single(&name, dry_run, date, limits, report, quiet).await
+                }
+                None => {
+                    let jobs = jobs_or_default(jobs);
+                    commands::run::run_all(jobs, &filter, dry_run, date, limits, quiet).await
+                }
+            };
+            if status != commands::run::RunStatus::Passed {
+                std::process::exit(status.code());
             }
         }
         Some(Commands::Contract { contract_command }) => match contract_command {
             ContractCommands::List => commands::contract::list().await,
             ContractCommands::Validate { file } => commands::contract::validate(&file).await,
-            ContractCommands::Show { name } => commands::contract::show(&name).await,
+            ContractCommands::Lint { name, json } => {
+                if !commands::contract::lint(&name, json || json_output) {
+                    std::process::exit(1);
+                }
+            }
+            ContractCommands::Fmt { names, check, fix } => {
+                if !commands::contract::fmt(&names, check, fix) {
+                    std::process::exit(1);
+                }
+            }
+            ContractCommands::Check { name, rows } => {
+                let status = commands::contract::check(&name, rows).await;
+                if status != commands::run::RunStatus::Passed {
+                    std::process::exit(status.code());
+                }
+            }
+            ContractCommands::Diff { name, old, json } => {
+                commands::contract::diff(&name, &old, json || json_output)
+            }
+            ContractCommands::Export { name, format } => commands::contract::export(&name, format),
+            ContractCommands::Show { name, json } => {
+                if !commands::contract::show(&name, json || json_output).await {
+                    std::process::exit(1);
+                }
+            }
+            ContractCommands::New { force } => commands::contract::new(force).await,
+            ContractCommands::Generate {
+                location,
+                name,
+                profile,
+                stdout,
+                force,
+            } => {
+                commands::contract::generate(
+                    &location,
+                    name.as_deref(),
+                    profile.as_deref(),
+                    stdout,
+                    force,
+                )
+                .await
+            }
+            ContractCommands::Baseline { name, run_id } => {
+                commands::contract::baseline(&name, run_id.as_deref()).await
+            }
         },
         Some(Commands::Profile { profile_command }) => match profile_command {
             ProfileCommands::List => commands::profile::list().await,
             ProfileCommands::Test { profile } => commands::profile::test(&profile).await,
+            ProfileCommands::Add {
+                profile,
+                provider,
+                set,
+                secret_env,
+            } => commands::profile::add(&profile, provider.as_deref(), &set, &secret_env),
+            ProfileCommands::Edit {
+                profile,
+                set,
+                secret_env,
+                unset,
+            } => commands::profile::edit(&profile, &set, &secret_env, &unset),
+            ProfileCommands::Remove { profile, yes } => commands::profile::remove(&profile, yes),
         },
-        Some(Commands::Health) => commands::health::run().await,
+        Some(Commands::ProfileData {
+            location,
+            profile,
+            top,
+            json,
+        }) => {
+            let json = json || json_output;
+            commands::profile_data::run(&location, profile.as_deref(), top, json).await
+        }
+        Some(Commands::DiffData {
+            contract,
+            runs,
+            json,
+        }) => commands::diff_data::run(&contract, &runs, json || json_output),
+        Some(Commands::Rules { rules_command }) => match rules_command {
+            RulesCommands::List { json } => commands::rules::list(json || json_output),
+            RulesCommands::Describe { rule } => commands::rules::describe(&rule),
+        },
+        Some(Commands::Health { deep }) => commands::health::run(deep).await,
 
         Some(Commands::Logs { logs_command }) => match logs_command {
-            LogsCommands::Verify { date, all } => {
-                commands::logs::verify(date.as_deref(), all).await;
+            LogsCommands::Verify { date, all, json } => {
+                if !commands::logs::verify(date.as_deref(), all, json || json_output).await {
+                    std::process::exit(1);
+                }
+            }
+            LogsCommands::Seal { date } => {
+                if !commands::logs::seal(date) {
+                    std::process::exit(1);
+                }
+            }
+            LogsCommands::Ship => commands::logs::ship().await,
+            LogsCommands::Export {
+                output,
+                format,
+                from,
+                to,
+                run_id,
+            } => {
+                if !commands::logs::export(&output, format, from, to, run_id.as_deref()) {
+                    std::process::exit(1);
+                }
+            }
+            LogsCommands::Prune { keep_days, dry_run } => {
+                if !commands::logs::prune(keep_days, dry_run) {
+                    std::process::exit(1);
+                }
+            }
+            LogsCommands::RotateKey => {
+                if !commands::logs::rotate_key() {
+                    std::process::exit(1);
+                }
             }
         },
         Some(Commands::Init) => commands::init::init_project(),
+        Some(Commands::Scheduler { scheduler_command }) => {
+            let status = match scheduler_command {
+                SchedulerCommands::Start { dry_run } => commands::scheduler::start(dry_run).await,
+                SchedulerCommands::List => commands::scheduler::list(),
+            };
+            if status != commands::run::RunStatus::Passed {
+                std::process::exit(status.code());
+            }
+        }
+        Some(Commands::Quarantine { quarantine_command }) => match quarantine_command {
+            QuarantineCommands::Revalidate {
+                file,
+                contract,
+                dry_run,
+            } => {
+                let status = commands::quarantine::revalidate(&file, &contract, dry_run).await;
+                if status != commands::run::RunStatus::Passed {
+                    std::process::exit(status.code());
+                }
+            }
+        },
+        Some(Commands::Watch {
+            contracts,
+            interval,
+            debounce,
+            dry_run,
+        }) => {
+            let status = commands::watch::run(&contracts, interval, debounce, dry_run).await;
+            if status != commands::run::RunStatus::Passed {
+                std::process::exit(status.code());
+            }
+        }
+        Some(Commands::Workspace { workspace_command }) => match workspace_command {
+            WorkspaceCommands::Run {
+                projects,
+                file,
+                jobs,
+                dry_run,
+            } => {
+                let jobs = jobs_or_default(jobs);
+                let status =
+                    commands::workspace::run(&projects, file.as_deref(), jobs, dry_run).await;
+                if status != commands::run::RunStatus::Passed {
+                    std::process::exit(status.code());
+                }
+            }
+        },
+        Some(Commands::Completions { shell }) => {
+            if !commands::completions::completions(shell) {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Man { dir }) => {
+            if !commands::completions::man(dir.as_deref()) {
+                std::process::exit(1);
+            }
+        }
         None => {
             println!("No command specified. Use --help for usage information.");
         }
@@ -38,7 +38,7 @@ fn test_run_without_args_fails() {
     let mut cmd = Command::cargo_bin("pipa").unwrap();
     cmd.arg("run")
         .assert()
-        .failure()
+        .code(3)
         .stderr(predicates::str::contains("Must specify either contract name or --all"));
 }
 
@@ -143,7 +143,7 @@ fn test_run_single_contract_not_found() {
         .arg("run")
         .arg("nonexistent")
         .assert()
-        .success()
+        .code(3)
         .stderr(predicate::str::contains("not found"));
 }
 
@@ -175,6 +175,258 @@ validation = []
         .success();
 }
 
+#[test]
+fn test_contract_validate_rejects_unrecognized_rule() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/test.toml"),
+        r#"columns = []
+
+[contract]
+name = "test"
+version = "1.0"
+tags = []
+
+[file]
+validation = [{ rule = "exists" }]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("contract")
+        .arg("validate")
+        .arg("test")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is invalid"))
+        .stderr(predicate::str::contains(
+            "rule 'exists' is not implemented under [file]",
+        ));
+}
+
+#[test]
+fn test_contract_validate_reports_parse_error_location() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/test.toml"),
+        r#"[contract]
+name = "test"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_nul" }]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("contract")
+        .arg("validate")
+        .arg("test")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is invalid"))
+        .stderr(predicate::str::contains("contracts/test.toml:8:"))
+        .stderr(predicate::str::contains("(in `columns[0].validation`)"))
+        .stderr(predicate::str::contains(
+            "8 | validation = [{ rule = \"not_nul\" }]",
+        ));
+}
+
+#[test]
+fn test_contract_lint_reports_codes_and_suggestions() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/test.toml"),
+        r#"[contract]
+name = "test"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "amount"
+dtype = "Int64"
+validation = [{ rule = "range", min = 10, max = 1 }]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "lint", "test"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("L006 column 'amount'"))
+        .stdout(predicate::str::contains("help: swap `min` and `max`"))
+        .stdout(predicate::str::contains("L009 [source]"));
+}
+
+#[test]
+fn test_contract_diff_reports_rule_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    let old = r#"[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+"#;
+    fs::write(temp_dir.path().join("orders_v1.toml"), old).unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        old.replace("1.0", "1.1")
+            .replace(r#"{ rule = "not_null" }"#, r#"{ rule = "unique" }"#),
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "diff", "orders", "orders_v1.toml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+ column 'id' rule unique"))
+        .stdout(predicate::str::contains("- column 'id' rule not_null"))
+        .stdout(predicate::str::contains(
+            "~ [contract] version: \"1.0\" → \"1.1\"",
+        ));
+}
+
+#[test]
+fn test_contract_export_json_schema() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "id"
+dtype = "Int64"
+validation = [{ rule = "not_null" }, { rule = "unique" }]
+
+[[columns]]
+name = "status"
+validation = [{ rule = "in_set", values = ["open", "closed"] }]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    let output = cmd
+        .current_dir(&temp_dir)
+        .args(["contract", "export", "--format", "json-schema", "orders"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["properties"]["id"]["type"], "integer");
+    assert_eq!(schema["required"], serde_json::json!(["id"]));
+    assert_eq!(
+        schema["properties"]["status"]["enum"],
+        serde_json::json!(["open", "closed", null])
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not exported: column 'id': unique"));
+}
+
+#[test]
+fn test_contract_check_compares_schema_with_source() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::create_dir_all(temp_dir.path().join("data")).unwrap();
+    fs::write(
+        temp_dir.path().join("data/orders.csv"),
+        "id,amount,note\n1,1.5,a\n2,2.5,b\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "id"
+dtype = "Int64"
+validation = []
+
+[[columns]]
+name = "amount"
+dtype = "Int64"
+validation = []
+
+[[columns]]
+name = "region"
+validation = []
+
+[source]
+type = "local"
+location = "data/orders.csv"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "check", "orders", "--rows", "10"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("2 rows sampled"))
+        .stdout(predicate::str::contains("missing column 'region'"))
+        .stdout(predicate::str::contains(
+            "column 'amount' is Float64 in the data but declared Int64",
+        ))
+        .stdout(predicate::str::contains("extra column 'note'"));
+}
+
+#[test]
+fn test_contract_validate_warns_on_rule_dtype_mismatch() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/test.toml"),
+        r#"[contract]
+name = "test"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "name"
+dtype = "String"
+validation = [{ rule = "mean_between", min = 0.0, max = 1.0 }]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("contract")
+        .arg("validate")
+        .arg("test")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("validated"))
+        .stdout(predicate::str::contains(
+            "rule 'mean_between' expects a numeric column but 'name' is declared String",
+        ));
+}
+
 #[test]
 fn test_contract_show_existing() {
     let temp_dir = TempDir::new().unwrap();
@@ -190,6 +442,15 @@ tags = []
 [[columns]]
 name = "id"
 validation = []
+
+[[columns]]
+name = "amount"
+dtype = "Int64"
+validation = [{ rule = "range", min = 0, max = 100 }]
+
+[source]
+type = "local"
+location = "${DATA_DIR:-data}/test.csv"
 "#;
     fs::write(contracts_dir.join("test.toml"), contract_content).unwrap();
 
@@ -199,7 +460,85 @@ validation = []
         .arg("show")
         .arg("test")
         .assert()
-        .success();
+        .success()
+        .stdout(predicate::str::contains(
+            "source       type=\"local\", location=\"data/test.csv\"",
+        ))
+        .stdout(predicate::str::contains(
+            "amount  Int64  range  min=0, max=100",
+        ));
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "show", "test", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"rule\": \"range\""));
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "show", "missing"])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn test_env_overlay_merges_over_contract() {
+    let temp_dir = TempDir::new().unwrap();
+    let contracts_dir = temp_dir.path().join("contracts");
+    fs::create_dir_all(&contracts_dir).unwrap();
+    fs::write(
+        contracts_dir.join("orders.toml"),
+        r#"[contract]
+name = "orders"
+version = "1.0"
+tags = []
+
+[[columns]]
+name = "amount"
+validation = [{ rule = "range", min = 0, max = 100 }]
+
+[source]
+type = "local"
+location = "data/orders.csv"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        contracts_dir.join("orders.prod.toml"),
+        r#"[source]
+location = "data/prod/orders.csv"
+
+[[columns]]
+name = "amount"
+validation = [{ rule = "range", max = 5000 }]
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "show", "orders", "--env", "prod"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "location=\"data/prod/orders.csv\"",
+        ))
+        .stdout(predicate::str::contains("range  min=0, max=5000"));
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "show", "orders"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("location=\"data/orders.csv\""));
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["contract", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("orders.prod").not());
 }
 
 #[test]
@@ -221,3 +560,1509 @@ fn test_short_verbose_flag_with_different_commands() {
         .assert()
         .success();
 }
+
+#[test]
+fn test_profile_data_local_csv() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("people.csv"),
+        "id,tier\n1,gold\n2,gold\n3,silver\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("profile-data")
+        .arg("people.csv")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"distinct_ratio\""))
+        .stdout(predicate::str::contains("\"gold\""));
+}
+
+#[test]
+fn test_rules_list_json() {
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.arg("rules")
+        .arg("list")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"expected_columns\""))
+        .stdout(predicate::str::contains("\"min_ratio\""));
+}
+
+#[test]
+fn test_rules_describe_prints_example() {
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.arg("rules")
+        .arg("describe")
+        .arg("outlier_sigma")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ddof=1"))
+        .stdout(predicate::str::contains(
+            "{ rule = \"outlier_sigma\", sigma = 3.0 }",
+        ));
+}
+
+#[test]
+fn test_contract_generate_writes_draft() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("people.csv"),
+        "id,tier\n1,gold\n2,silver\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("contract")
+        .arg("generate")
+        .arg("people.csv")
+        .assert()
+        .success();
+
+    let draft = fs::read_to_string(temp_dir.path().join("contracts/people.toml")).unwrap();
+    assert!(draft.contains("name = \"people\""));
+    assert!(draft.contains("{ rule = \"unique\" }"));
+}
+
+#[test]
+fn test_contract_baseline_after_run() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(
+        temp_dir.path().join("orders.csv"),
+        "id,amount\n1,10\n2,12\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "amount"
+validation = [{ rule = "drift", metric = "mean", max_change = 0.1 }]
+
+[source]
+type = "local"
+location = "orders.csv"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("contract")
+        .arg("baseline")
+        .arg("orders")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("no recorded runs"));
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("run")
+        .arg("orders")
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("contract")
+        .arg("baseline")
+        .arg("orders")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Baseline pinned"));
+
+    assert!(temp_dir
+        .path()
+        .join(".pipa/state/orders/baseline.json")
+        .exists());
+}
+
+#[test]
+fn test_run_exit_codes() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n\n").unwrap();
+    let contract = |location: &str, rule: &str| {
+        format!(
+            r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{{ rule = "{}" }}]
+
+[source]
+type = "local"
+location = "{}"
+"#,
+            rule, location
+        )
+    };
+    let run = |name: &str| {
+        Command::cargo_bin("pipa")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .arg("run")
+            .arg(name)
+            .assert()
+    };
+
+    fs::write(
+        temp_dir.path().join("contracts/passing.toml"),
+        contract("orders.csv", "unique"),
+    )
+    .unwrap();
+    run("passing").code(0);
+
+    fs::write(
+        temp_dir.path().join("contracts/failing.toml"),
+        contract("orders.csv", "not_null"),
+    )
+    .unwrap();
+    run("failing").code(1);
+
+    fs::write(
+        temp_dir.path().join("contracts/missing_data.toml"),
+        contract("missing.csv", "not_null"),
+    )
+    .unwrap();
+    run("missing_data").code(2);
+
+    fs::write(
+        temp_dir.path().join("contracts/broken.toml"),
+        "not = [valid",
+    )
+    .unwrap();
+    run("broken")
+        .code(3)
+        .stderr(predicate::str::contains("Failed to parse contract TOML"));
+
+    // --all reports the most severe status
+    Command::cargo_bin("pipa")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("run")
+        .arg("--all")
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_run_all_with_jobs() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    for i in 0..4 {
+        fs::write(
+            temp_dir.path().join(format!("data{}.csv", i)),
+            "id,amount\n1,10\n2,12\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join(format!("contracts/data{}.toml", i)),
+            format!(
+                r#"
+[contract]
+name = "data{i}"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{{ rule = "unique" }}, {{ rule = "not_null" }}]
+
+[source]
+type = "local"
+location = "data{i}.csv"
+"#
+            ),
+        )
+        .unwrap();
+    }
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("run")
+        .arg("--all")
+        .arg("--jobs")
+        .arg("3")
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains(
+            "4 contracts: 4 passed, 0 with failures, 0 errors",
+        ));
+
+    // Concurrent runs must not interleave audit log lines
+    for entry in fs::read_dir(temp_dir.path().join("logs")).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "jsonl") {
+            for line in fs::read_to_string(&path).unwrap().lines() {
+                serde_json::from_str::<serde_json::Value>(line).unwrap();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_run_by_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n2\n").unwrap();
+    for (name, tags) in [
+        ("orders", r#"["pii", "critical"]"#),
+        ("customers", r#"["pii", "slow"]"#),
+        ("metrics", "[]"),
+    ] {
+        fs::write(
+            temp_dir.path().join(format!("contracts/{}.toml", name)),
+            format!(
+                r#"
+[contract]
+name = "{name}"
+version = "1.0.0"
+tags = {tags}
+
+[[columns]]
+name = "id"
+validation = [{{ rule = "unique" }}]
+
+[source]
+type = "local"
+location = "orders.csv"
+"#
+            ),
+        )
+        .unwrap();
+    }
+    let run = |args: &[&str]| {
+        Command::cargo_bin("pipa")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .arg("run")
+            .args(args)
+            .assert()
+    };
+
+    run(&["--tag", "pii"])
+        .code(0)
+        .stdout(predicate::str::contains("2 contracts: 2 passed"));
+    run(&["--tag", "pii", "--exclude-tag", "slow"])
+        .code(0)
+        .stdout(predicate::str::contains("1 contracts: 1 passed"));
+    run(&["--exclude-tag", "pii"])
+        .code(0)
+        .stdout(predicate::str::contains("1 contracts: 1 passed"));
+    run(&["--tag", "nightly"])
+        .code(3)
+        .stderr(predicate::str::contains(
+            "No contracts match the tag filter",
+        ));
+}
+
+#[test]
+fn test_run_dry_run_writes_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n2\n").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "unique" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+
+[destination]
+type = "local"
+location = "clean/"
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("pipa")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["run", "orders", "--dry-run"])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("Dry run: no data was written"));
+
+    assert!(!temp_dir.path().join("clean").exists());
+    assert!(!temp_dir.path().join(".pipa/state").exists());
+    let logs: String = fs::read_dir(temp_dir.path().join("logs"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect();
+    assert!(logs.contains("dry_run=true, Dry run: would write to destination clean/"));
+}
+
+#[test]
+fn test_run_emits_results_file() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n1\n").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }, { rule = "unique" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("pipa")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["run", "orders", "--output", "json"])
+        .args(["--emit-results-file", "results.json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .clone();
+
+    let printed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let written = fs::read_to_string(temp_dir.path().join("results.json")).unwrap();
+    let results: serde_json::Value = serde_json::from_str(&written).unwrap();
+    assert_eq!(printed, results);
+    assert_eq!(results["contract"], "orders");
+    assert_eq!(results["status"], "rule_failures");
+    assert_eq!(results["exit_code"], 1);
+    assert_eq!(results["error"], serde_json::Value::Null);
+
+    let outcome = &results["outcome"];
+    assert_eq!(outcome["passed"], false);
+    assert_eq!(outcome["pass_count"], 1);
+    assert_eq!(outcome["fail_count"], 1);
+    assert_eq!(outcome["results"][1]["column"], "id");
+    assert_eq!(outcome["results"][1]["rule"], "Unique");
+    assert_eq!(outcome["results"][1]["result"], "fail");
+    assert!(outcome["timings"]["total_ms"].is_number());
+}
+
+#[test]
+fn test_workspace_run_consolidates_projects() {
+    let temp_dir = TempDir::new().unwrap();
+    for (project, csv) in [("orders", "id\n1\n2\n"), ("billing", "id\n1\n1\n")] {
+        let root = temp_dir.path().join("pipelines").join(project);
+        fs::create_dir_all(root.join("contracts")).unwrap();
+        fs::write(root.join("profiles.toml"), "").unwrap();
+        fs::write(root.join("data.csv"), csv).unwrap();
+        fs::write(
+            root.join(format!("contracts/{}.toml", project)),
+            format!(
+                r#"
+[contract]
+name = "{project}"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{{ rule = "unique" }}]
+
+[source]
+type = "local"
+location = "data.csv"
+"#
+            ),
+        )
+        .unwrap();
+    }
+    fs::write(
+        temp_dir.path().join("workspace.toml"),
+        r#"projects = ["pipelines/orders", "pipelines/billing"]"#,
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        Command::cargo_bin("pipa")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["workspace", "run"])
+            .args(args)
+            .assert()
+    };
+
+    run(&[]).code(1).stdout(predicate::str::contains(
+        "2 projects (1 not passing), 📊 2 contracts: 1 passed, 1 with failures",
+    ));
+    assert!(temp_dir.path().join("pipelines/orders/logs").is_dir());
+    assert!(temp_dir.path().join("pipelines/billing/logs").is_dir());
+
+    run(&["--projects", "pipelines/orders"])
+        .code(0)
+        .stdout(predicate::str::contains("1 projects (0 not passing)"));
+    run(&["--projects", "pipelines/orders,pipelines/missing"])
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Project directory pipelines/missing does not exist",
+        ));
+}
+
+#[test]
+fn test_project_dir_runs_against_another_project() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("orders");
+    fs::create_dir(&project).unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+
+    pipa()
+        .args(["--project-dir", "orders", "init"])
+        .assert()
+        .success();
+    assert!(project.join("contracts/example.toml").is_file());
+    assert!(project.join("profiles.toml").is_file());
+    assert!(!temp_dir.path().join("contracts").exists());
+
+    pipa()
+        .args(["contract", "list", "--project-dir", "orders"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("example"));
+    assert!(project.join("logs").is_dir());
+    assert!(!temp_dir.path().join("logs").exists());
+
+    pipa()
+        .args(["--project-dir", "billing", "contract", "list"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Project directory billing does not exist",
+        ));
+}
+
+#[test]
+fn test_scheduler_list_and_invalid_cron() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    let contract = |cron: &str| {
+        format!(
+            r#"
+columns = []
+
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[source]
+type = "local"
+location = "orders.csv"
+
+[schedule]
+cron = "{cron}"
+"#
+        )
+    };
+    let pipa = || {
+        let mut command = Command::cargo_bin("pipa").unwrap();
+        command.current_dir(&temp_dir);
+        command
+    };
+
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        contract("0 6 * * *"),
+    )
+    .unwrap();
+    pipa()
+        .args(["scheduler", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("orders").and(predicate::str::contains("T06:00:00")));
+
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        contract("0 25 * * *"),
+    )
+    .unwrap();
+    pipa()
+        .args(["contract", "validate", "orders"])
+        .assert()
+        .stderr(predicate::str::contains("Invalid cron expression"));
+    pipa()
+        .args(["scheduler", "start"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("hour"));
+}
+
+#[test]
+fn test_diff_data_between_runs() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+columns = []
+
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[source]
+type = "local"
+location = "orders.csv"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("diff-data")
+        .arg("orders")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("at least two recorded runs"));
+
+    for data in [
+        "id,amount\n1,10\n2,12\n",
+        "id,amount,region\n1,10,eu\n2,20,us\n3,30,eu\n",
+    ] {
+        fs::write(temp_dir.path().join("orders.csv"), data).unwrap();
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir)
+            .arg("run")
+            .arg("orders")
+            .assert()
+            .success();
+    }
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("diff-data")
+        .arg("orders")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"row_delta\": 1"))
+        .stdout(predicate::str::contains("\"region\""));
+}
+
+#[test]
+fn test_profile_add_edit_remove() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "# Team profiles\n").unwrap();
+    let pipa = || {
+        let mut command = Command::cargo_bin("pipa").unwrap();
+        command.current_dir(&temp_dir);
+        command
+    };
+    let profiles = || fs::read_to_string(temp_dir.path().join("profiles.toml")).unwrap();
+
+    pipa()
+        .args([
+            "profile",
+            "add",
+            "raw",
+            "--provider",
+            "s3",
+            "--set",
+            "region=us-east-1",
+        ])
+        .args(["--secret-env", "secret_key=RAW_SECRET"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Profile 'raw' added"));
+    assert_eq!(
+        profiles(),
+        concat!(
+            "# Team profiles\n\n[raw]\nprovider = \"s3\"\n",
+            "region = \"us-east-1\"\nsecret_key = \"${RAW_SECRET}\"\n",
+        )
+    );
+
+    pipa()
+        .args([
+            "profile",
+            "add",
+            "leak",
+            "--provider",
+            "s3",
+            "--set",
+            "secret_key=hunter2",
+        ])
+        .assert()
+        .stderr(predicate::str::contains("--secret-env"));
+
+    pipa()
+        .args([
+            "profile",
+            "edit",
+            "raw",
+            "--set",
+            "path_style=true",
+            "--unset",
+            "region",
+        ])
+        .assert()
+        .success();
+    assert!(profiles().contains("path_style = true\n"));
+    assert!(!profiles().contains("region"));
+
+    pipa()
+        .args(["profile", "remove", "raw"])
+        .assert()
+        .stderr(predicate::str::contains("--yes"));
+    pipa()
+        .args(["profile", "remove", "raw", "--yes"])
+        .assert()
+        .success();
+    assert_eq!(profiles(), "# Team profiles\n");
+}
+
+#[test]
+fn test_health_deep_reports_each_profile() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("profiles.toml"),
+        "[disk]\nprovider = \"local\"\n\n[legacy]\nprovider = \"sftp\"\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("pipa")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["health", "--deep"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("profile 'disk' (local) reachable")
+                .and(predicate::str::contains("profile 'legacy' (sftp) failed"))
+                .and(predicate::str::contains("not supported")),
+        );
+}
+
+#[test]
+fn test_logs_ship_uploads_sealed_files_to_local_sink() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    fs::write(
+        temp_dir.path().join("pipa.toml"),
+        "[[log_sinks]]\ntype = \"local\"\nlocation = \"archive/\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("logs/audit-2020-01-01.jsonl"),
+        "{\"event\":\"old\"}\n",
+    )
+    .unwrap();
+
+    pipa().args(["logs", "ship"]).assert().success();
+
+    let archive = temp_dir.path().join("archive");
+    assert!(archive.join("audit-2020-01-01.jsonl").exists());
+    assert!(archive.join("audit-2020-01-01.jsonl.sha256").exists());
+    let shipped = fs::read_to_string(temp_dir.path().join("logs/shipped.txt")).unwrap();
+    assert!(shipped.contains("archive/ audit-2020-01-01.jsonl\n"));
+}
+
+#[test]
+fn test_logs_export_csv_explodes_rule_results() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    fs::write(
+        temp_dir.path().join("logs/audit-2025-01-02.jsonl"),
+        concat!(
+            r#"{"timestamp":"2025-01-02T08:00:00Z","level":"AUDIT","event":"validation_summary","#,
+            r#""contract":{"name":"orders","version":"1"},"executor":{"user":"etl","host":"w1"},"#,
+            r#""results":[{"column":"id","rule":"not_null","result":"pass"},"#,
+            r#"{"column":"id","rule":"unique","result":"fail","details":"2 duplicates"}]}"#,
+            "\n"
+        ),
+    )
+    .unwrap();
+
+    pipa()
+        .args([
+            "logs",
+            "export",
+            "--from",
+            "2025-01-01",
+            "--to",
+            "2025-01-31",
+            "-o",
+            "audit.csv",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Exported 2 audit rows to audit.csv",
+        ));
+
+    let csv = fs::read_to_string(temp_dir.path().join("audit.csv")).unwrap();
+    let mut lines = csv.lines();
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("date,timestamp,level,event,contract_name"));
+    assert_eq!(lines.clone().count(), 2);
+    assert!(lines.any(|l| l.contains("orders") && l.contains("unique,fail,2 duplicates")));
+
+    pipa()
+        .args(["logs", "export", "-o", "audit.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--format csv"));
+}
+
+#[test]
+fn test_logs_verify_detects_edits_to_todays_log() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    pipa().args(["contract", "list"]).assert().success();
+    pipa().args(["contract", "list"]).assert().success();
+    pipa()
+        .args(["logs", "verify", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unsealed").and(predicate::str::contains("chain").not()));
+
+    let today = fs::read_dir(temp_dir.path().join("logs"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_string_lossy().ends_with(".jsonl"))
+        .unwrap();
+    let content = fs::read_to_string(&today).unwrap();
+    let tampered = content.replacen("\"AUDIT\"", "\"INFO\"", 1);
+    assert_ne!(content, tampered);
+    fs::write(&today, tampered).unwrap();
+
+    pipa()
+        .args(["logs", "verify", "--all"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("chain broken at line 2"));
+}
+
+#[test]
+fn test_logs_verify_fails_on_altered_sealed_log() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    let old_log = temp_dir.path().join("logs/audit-2020-01-01.jsonl");
+    fs::write(&old_log, "{\"event\":\"old\"}\n").unwrap();
+    pipa()
+        .args(["logs", "seal", "--date", "2020-01-01"])
+        .assert()
+        .success();
+    pipa()
+        .args(["logs", "verify", "--date", "2020-01-01"])
+        .assert()
+        .success();
+
+    fs::write(&old_log, "{\"event\":\"edited\"}\n").unwrap();
+    pipa()
+        .args(["logs", "verify", "--date", "2020-01-01"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "audit-2020-01-01.jsonl mismatched",
+        ))
+        .stdout(predicate::str::contains("ledger line 1 sealed sha256"));
+
+    let output = pipa()
+        .args(["logs", "verify", "--all", "--json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["mismatched"], 1);
+    let file = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["filename"] == "audit-2020-01-01.jsonl")
+        .unwrap();
+    assert_eq!(file["status"], "mismatched");
+    assert_eq!(file["ledger_line"], 1);
+}
+
+/// Accept one HTTP request on `listener` and answer 200 with its body.
+fn echo_once(listener: std::net::TcpListener) -> std::thread::JoinHandle<String> {
+    use std::io::{BufRead, BufReader, Read, Write};
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", length);
+        let stream = reader.get_mut();
+        stream.write_all(response.as_bytes()).unwrap();
+        stream.write_all(&body).unwrap();
+        String::from_utf8(body).unwrap()
+    })
+}
+
+#[test]
+fn test_sealed_logs_are_notarized() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    fs::write(
+        temp_dir.path().join("pipa.toml"),
+        format!(
+            "[notarization]\ntype = \"http\"\nurl = \"http://{}/hashes\"\n",
+            listener.local_addr().unwrap()
+        ),
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("logs/audit-2020-01-01.jsonl"),
+        "{\"event\":\"old\"}\n",
+    )
+    .unwrap();
+    let notary = echo_once(listener);
+
+    pipa().args(["contract", "list"]).assert().success();
+    let request = notary.join().unwrap();
+    assert!(request.contains("\"file\":\"audit-2020-01-01.jsonl\""));
+
+    pipa()
+        .args(["logs", "verify", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "audit-2020-01-01.jsonl verified (timestamped)",
+        ));
+}
+
+#[test]
+fn test_logs_rotate_key_keeps_sealed_logs_verifiable() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    fs::write(
+        temp_dir.path().join("logs/audit-2020-01-01.jsonl"),
+        "{\"event\":\"old\"}\n",
+    )
+    .unwrap();
+    pipa().args(["contract", "list"]).assert().success();
+
+    let key_path = temp_dir.path().join("config/ledger.key");
+    let old_key = fs::read(&key_path).unwrap();
+    pipa()
+        .args(["logs", "rotate-key"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rotated the ledger key (local)"));
+    assert_ne!(fs::read(&key_path).unwrap(), old_key);
+
+    pipa()
+        .args(["logs", "verify", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("audit-2020-01-01.jsonl verified"));
+}
+
+#[test]
+fn test_logs_seal_seals_a_past_day_once() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    fs::write(
+        temp_dir.path().join("logs/audit-2020-01-01.jsonl"),
+        "{\"event\":\"old\"}\n",
+    )
+    .unwrap();
+
+    pipa()
+        .args(["logs", "seal", "--date", "2020-01-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Sealed 1 daily logs"))
+        .stdout(predicate::str::contains("audit-2020-01-01.jsonl sealed"));
+    pipa()
+        .args(["logs", "seal", "--date", "2020-01-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "audit-2020-01-01.jsonl was already sealed",
+        ));
+    pipa()
+        .args(["logs", "seal", "--date", "2020-01-02"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No audit log for 2020-01-02"));
+    pipa()
+        .args(["logs", "seal", "--date", "2999-01-01"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("still open"));
+
+    let logs = fs::read_dir(temp_dir.path().join("logs"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| !path.ends_with("audit-2020-01-01.jsonl"))
+        .filter(|path| path.to_string_lossy().ends_with(".jsonl"))
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect::<String>();
+    assert!(logs.contains("\"event\":\"logs_sealed\""));
+}
+
+#[test]
+fn test_logs_prune_archives_sealed_logs_past_retention() {
+    let temp_dir = TempDir::new().unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+    pipa().arg("init").assert().success();
+    fs::write(
+        temp_dir.path().join("pipa.toml"),
+        "[retention]\nkeep_days = 30\narchive_dir = \"archive\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("logs/audit-2020-01-01.jsonl"),
+        "{\"event\":\"old\"}\n",
+    )
+    .unwrap();
+    pipa()
+        .args(["logs", "seal", "--date", "2020-01-01"])
+        .assert()
+        .success();
+
+    pipa()
+        .args(["logs", "prune", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would prune 1 daily logs older than 30 days",
+        ));
+    assert!(temp_dir.path().join("logs/audit-2020-01-01.jsonl").exists());
+
+    pipa()
+        .args(["logs", "prune"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Pruned 1 daily logs older than 30 days",
+        ));
+    assert!(!temp_dir.path().join("logs/audit-2020-01-01.jsonl").exists());
+    assert!(temp_dir
+        .path()
+        .join("archive/audit-2020-01-01.jsonl")
+        .exists());
+
+    pipa()
+        .args(["logs", "verify", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("audit-2020-01-01.jsonl pruned"));
+}
+
+#[test]
+fn test_logs_prune_requires_a_retention_period() {
+    let temp_dir = TempDir::new().unwrap();
+    Command::cargo_bin("pipa")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("init")
+        .assert()
+        .success();
+
+    Command::cargo_bin("pipa")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["logs", "prune"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No retention period"));
+}
+
+#[test]
+fn test_logs_dir_env_override() {
+    let temp_dir = TempDir::new().unwrap();
+    Command::cargo_bin("pipa")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .env("PIPA_LOGS_DIR", "audit")
+        .arg("init")
+        .assert()
+        .success();
+
+    assert!(temp_dir.path().join("audit").is_dir());
+    assert!(!temp_dir.path().join("logs").exists());
+}
+
+#[test]
+fn test_quarantine_revalidate_releases_fixed_data() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(
+        temp_dir.path().join("orders.csv"),
+        "id,amount\n1,10\n2,-5\n",
+    )
+    .unwrap();
+    let contract = |min: i64| {
+        format!(
+            r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "amount"
+validation = [{{ rule = "range", min = {}, max = 100 }}]
+
+[source]
+type = "local"
+location = "orders.csv"
+
+[destination]
+type = "local"
+location = "clean"
+
+[quarantine]
+type = "local"
+location = "quarantine"
+"#,
+            min
+        )
+    };
+    let contract_file = temp_dir.path().join("contracts/orders.toml");
+    fs::write(&contract_file, contract(0)).unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["run", "orders"])
+        .assert()
+        .code(1);
+    let quarantined: Vec<String> = fs::read_dir(temp_dir.path().join("quarantine"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(quarantined.len(), 1);
+
+    // Still failing: the file stays in quarantine
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args([
+            "quarantine",
+            "revalidate",
+            &quarantined[0],
+            "--contract",
+            "orders",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("still fails validation"));
+    assert!(temp_dir
+        .path()
+        .join("quarantine")
+        .join(&quarantined[0])
+        .exists());
+
+    fs::write(&contract_file, contract(-10)).unwrap();
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args([
+            "quarantine",
+            "revalidate",
+            &quarantined[0],
+            "--contract",
+            "orders",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Released"));
+
+    assert!(!temp_dir
+        .path()
+        .join("quarantine")
+        .join(&quarantined[0])
+        .exists());
+    let released: Vec<String> = fs::read_dir(temp_dir.path().join("clean"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(released.len(), 1);
+    assert!(released[0].starts_with("orders_"));
+    assert!(!released[0].contains("quarantine"));
+}
+
+#[test]
+fn test_run_archives_local_source_on_success() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+on_success = "archive"
+archive_location = "processed"
+
+[destination]
+type = "local"
+location = "clean"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["run", "orders"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived source orders.csv"));
+
+    assert!(!temp_dir.path().join("orders.csv").exists());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("processed/orders.csv")).unwrap(),
+        "id\n1\n"
+    );
+}
+
+#[test]
+fn test_run_names_moved_files_from_filename_template() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+
+[destination]
+type = "local"
+location = "clean"
+filename_template = "{contract}/{stem}-{status}.{ext}"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["run", "orders"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("clean/orders/orders-passed.csv")).unwrap(),
+        "id\n1\n"
+    );
+}
+
+#[test]
+fn test_run_writes_hive_partitions() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(
+        temp_dir.path().join("orders.csv"),
+        "id,country,load_date\n1,US,2025-01-15\n2,DE,2025-01-15\n3,US,2025-01-15\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+
+[destination]
+type = "local"
+location = "lake"
+partition_by = ["country", "load_date"]
+filename_template = "part-0.{ext}"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["run", "orders"])
+        .assert()
+        .success();
+
+    let lake = temp_dir.path().join("lake");
+    assert_eq!(
+        fs::read_to_string(lake.join("country=US/load_date=2025-01-15/part-0.csv")).unwrap(),
+        "id\n1\n3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(lake.join("country=DE/load_date=2025-01-15/part-0.csv")).unwrap(),
+        "id\n2\n"
+    );
+}
+
+#[test]
+fn test_run_writes_compressed_ndjson() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id,name\n1,a\n2,b\n").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+
+[destination]
+type = "local"
+location = "clean"
+format = "ndjson.gz"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["run", "orders"])
+        .assert()
+        .success();
+
+    let written: Vec<_> = fs::read_dir(temp_dir.path().join("clean"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(written.len(), 1);
+    let name = written[0].file_name().unwrap().to_string_lossy();
+    assert!(name.starts_with("orders_") && name.ends_with(".ndjson.gz"));
+    // gzip magic bytes
+    assert_eq!(fs::read(&written[0]).unwrap()[..2], [0x1f, 0x8b]);
+}
+
+#[test]
+fn test_run_annotates_moved_data() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+
+[destination]
+type = "local"
+location = "clean"
+filename_template = "orders.csv"
+annotate = true
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("pipa").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["run", "orders"])
+        .assert()
+        .success();
+
+    let written = fs::read_to_string(temp_dir.path().join("clean/orders.csv")).unwrap();
+    let mut lines = written.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "id,_pipa_contract,_pipa_run_id,_pipa_validated_at"
+    );
+    assert!(lines.next().unwrap().starts_with("1,orders,"));
+}
+
+#[test]
+fn test_logs_export_filters_by_run_id() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("contracts")).unwrap();
+    fs::write(temp_dir.path().join("profiles.toml"), "").unwrap();
+    fs::write(temp_dir.path().join("orders.csv"), "id\n1\n").unwrap();
+    fs::write(
+        temp_dir.path().join("contracts/orders.toml"),
+        r#"
+[contract]
+name = "orders"
+version = "1.0.0"
+tags = []
+
+[[columns]]
+name = "id"
+validation = [{ rule = "not_null" }]
+
+[source]
+type = "local"
+location = "orders.csv"
+"#,
+    )
+    .unwrap();
+    let pipa = || {
+        let mut cmd = Command::cargo_bin("pipa").unwrap();
+        cmd.current_dir(&temp_dir);
+        cmd
+    };
+
+    pipa().args(["run", "orders"]).assert().success();
+    let output = pipa().args(["run", "orders"]).assert().success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    let run_id = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("🆔 Run ID: "))
+        .unwrap()
+        .to_string();
+
+    pipa()
+        .args(["logs", "export", "--run-id", &run_id, "-o", "run.csv"])
+        .assert()
+        .success();
+
+    let csv = fs::read_to_string(temp_dir.path().join("run.csv")).unwrap();
+    let rows: Vec<&str> = csv.lines().skip(1).collect();
+    assert!(rows
+        .iter()
+        .any(|row| row.contains("contract_validation_started")));
+    assert!(rows
+        .iter()
+        .any(|row| row.contains("contract_validation_completed")));
+    assert!(rows.iter().all(|row| row.ends_with(&run_id)));
+    assert_eq!(
+        rows.iter()
+            .filter(|row| row.contains("contract_validation_started"))
+            .count(),
+        1
+    );
+}